@@ -0,0 +1,91 @@
+use crate::html_tree::wrap_listener;
+use crate::props::{Prop, SortedPropList, BOOLEAN_SET, LISTENER_SET};
+use crate::stringify::Stringify;
+use proc_macro2::TokenStream;
+use quote::{quote, quote_spanned, ToTokens};
+use syn::parse::{Parse, ParseStream};
+use syn::spanned::Spanned;
+use syn::Token;
+
+/// Attributes of an attribute group, separated by whitespace or commas.
+pub struct Attrs {
+    attributes: SortedPropList,
+    listeners: SortedPropList,
+}
+
+impl Parse for Attrs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut props = Vec::new();
+        while !input.is_empty() {
+            props.push(input.parse::<Prop>()?);
+            if input.peek(Token![,]) {
+                input.parse::<Token![,]>()?;
+            }
+        }
+
+        let mut attributes = SortedPropList::new(props);
+        let listeners =
+            attributes.drain_filter(|prop| LISTENER_SET.contains(prop.label.to_string().as_str()));
+        attributes.check_no_duplicates()?;
+        attributes.check_all(Prop::check_no_modifier)?;
+        attributes.check_all(|prop| match prop.label.to_string().as_str() {
+            "ref" | "key" => Err(syn::Error::new_spanned(
+                &prop.label,
+                format!(
+                    "`{}` can't be part of an attribute group, set it on the element instead",
+                    prop.label
+                ),
+            )),
+            _ => Ok(()),
+        })?;
+
+        Ok(Self {
+            attributes,
+            listeners,
+        })
+    }
+}
+
+impl ToTokens for Attrs {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let attributes = self.attributes.iter().map(|Prop { label, value, .. }| {
+            let key = label.to_lit_str();
+            let name = label.to_string();
+            if name == "class" {
+                quote_spanned! {value.span()=>
+                    __yew_attrs.add_class(
+                        ::std::convert::Into::<::yew::html::Classes>::into(#value)
+                    );
+                }
+            } else if BOOLEAN_SET.contains(name.as_str()) {
+                quote_spanned! {value.span()=>
+                    __yew_attrs.set_attribute(#key, if #value {
+                        ::std::option::Option::Some(
+                            ::std::borrow::Cow::<'static, str>::Borrowed(#key)
+                        )
+                    } else {
+                        ::std::option::Option::None
+                    });
+                }
+            } else {
+                let value = value.optimize_literals();
+                quote_spanned! {value.span()=>
+                    __yew_attrs.set_attribute(#key, #value);
+                }
+            }
+        });
+        let listeners = self.listeners.iter().map(|prop| {
+            let listener = wrap_listener(prop);
+            quote! { __yew_attrs.__macro_push_listener(#listener); }
+        });
+
+        tokens.extend(quote! {
+            {
+                let mut __yew_attrs = ::yew::virtual_dom::AttrGroup::new();
+                #(#attributes)*
+                #(#listeners)*
+                __yew_attrs
+            }
+        });
+    }
+}
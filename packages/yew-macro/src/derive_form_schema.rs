@@ -0,0 +1,254 @@
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+use syn::ext::IdentExt;
+use syn::parse::{Parse, ParseStream, Result};
+use syn::spanned::Spanned;
+use syn::{Data, DeriveInput, Error, Fields, Ident, Lit, LitStr, Meta, NestedMeta, Path, Type};
+
+struct FormField {
+    ident: Ident,
+    ty: Type,
+    label: String,
+    help: Option<String>,
+    kind: Option<Ident>,
+    required: bool,
+    validate: Option<Path>,
+}
+
+pub struct DeriveFormSchemaInput {
+    ident: Ident,
+    fields: Vec<FormField>,
+}
+
+impl Parse for DeriveFormSchemaInput {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let input: DeriveInput = input.parse()?;
+        // The fields are described in a constant, which can't depend on generic parameters
+        if !input.generics.params.is_empty() {
+            return Err(Error::new_spanned(
+                &input.generics,
+                "`FormSchema` can't be derived for generic structs",
+            ));
+        }
+        let named = match &input.data {
+            Data::Struct(data) => match &data.fields {
+                Fields::Named(fields) => &fields.named,
+                _ => {
+                    return Err(Error::new_spanned(
+                        &input.ident,
+                        "`FormSchema` can only be derived for structs with named fields",
+                    ))
+                }
+            },
+            _ => {
+                return Err(Error::new_spanned(
+                    &input.ident,
+                    "`FormSchema` can only be derived for structs",
+                ))
+            }
+        };
+
+        let mut fields = Vec::new();
+        for field in named {
+            let ident = field.ident.clone().expect("named field");
+            let mut form_field = FormField {
+                label: default_label(&ident),
+                ident,
+                ty: field.ty.clone(),
+                help: None,
+                kind: None,
+                required: false,
+                validate: None,
+            };
+            let mut skip = false;
+            for attr in field.attrs.iter().filter(|a| a.path.is_ident("form")) {
+                let list = match attr.parse_meta()? {
+                    Meta::List(list) => list,
+                    meta => {
+                        return Err(Error::new_spanned(
+                            meta,
+                            "expected a list, e.g. `#[form(label = \"Name\")]`",
+                        ))
+                    }
+                };
+                for nested in list.nested {
+                    match nested {
+                        NestedMeta::Meta(Meta::Path(path)) if path.is_ident("required") => {
+                            form_field.required = true
+                        }
+                        NestedMeta::Meta(Meta::Path(path)) if path.is_ident("skip") => skip = true,
+                        NestedMeta::Meta(Meta::NameValue(nv)) => {
+                            let value = match &nv.lit {
+                                Lit::Str(value) => value,
+                                lit => return Err(Error::new_spanned(lit, "expected a string")),
+                            };
+                            if nv.path.is_ident("label") {
+                                form_field.label = value.value();
+                            } else if nv.path.is_ident("help") {
+                                form_field.help = Some(value.value());
+                            } else if nv.path.is_ident("kind") {
+                                form_field.kind = Some(parse_kind(value)?);
+                            } else if nv.path.is_ident("validate") {
+                                form_field.validate = Some(value.parse()?);
+                            } else {
+                                return Err(unknown_option(&nv.path));
+                            }
+                        }
+                        nested => return Err(unknown_option(&nested)),
+                    }
+                }
+            }
+            if !skip {
+                fields.push(form_field);
+            }
+        }
+
+        Ok(Self {
+            ident: input.ident,
+            fields,
+        })
+    }
+}
+
+fn unknown_option(tokens: &impl Spanned) -> Error {
+    Error::new(
+        tokens.span(),
+        "unknown option, expected one of `label`, `help`, `kind`, `required`, `validate` \
+         and `skip`",
+    )
+}
+
+fn parse_kind(kind: &LitStr) -> Result<Ident> {
+    let variant = match kind.value().as_str() {
+        "text" => "Text",
+        "textarea" => "TextArea",
+        "number" => "Number",
+        "checkbox" => "Checkbox",
+        "email" => "Email",
+        "password" => "Password",
+        "date" => "Date",
+        _ => {
+            return Err(Error::new_spanned(
+                kind,
+                "unknown kind, expected one of `text`, `textarea`, `number`, `checkbox`, \
+                 `email`, `password` and `date`",
+            ))
+        }
+    };
+    Ok(Ident::new(variant, kind.span()))
+}
+
+/// Turns `first_name` into `First name`
+fn default_label(ident: &Ident) -> String {
+    let name = ident.unraw().to_string().replace('_', " ");
+    let mut chars = name.trim().chars();
+    chars
+        .next()
+        .map(|first| first.to_uppercase().chain(chars).collect())
+        .unwrap_or_default()
+}
+
+impl ToTokens for DeriveFormSchemaInput {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let Self { ident, fields } = self;
+
+        let schemas = fields.iter().map(|field| {
+            let FormField {
+                ident,
+                ty,
+                label,
+                help,
+                kind,
+                required,
+                ..
+            } = field;
+            let name = ident.unraw().to_string();
+            let kind = match kind {
+                Some(kind) => quote! { ::yew::form::FieldKind::#kind },
+                None => quote! { <#ty as ::yew::form::FormValue>::KIND },
+            };
+            let help = match help {
+                Some(help) => quote! { ::std::option::Option::Some(#help) },
+                None => quote! { ::std::option::Option::None },
+            };
+            quote! {
+                ::yew::form::FieldSchema {
+                    name: #name,
+                    label: #label,
+                    kind: #kind,
+                    required: #required,
+                    help: #help,
+                }
+            }
+        });
+        let names: Vec<_> = fields
+            .iter()
+            .map(|field| field.ident.unraw().to_string())
+            .collect();
+        let idents: Vec<_> = fields.iter().map(|field| &field.ident).collect();
+        let validations = fields.iter().map(|field| {
+            let ident = &field.ident;
+            let required = if field.required {
+                quote! {
+                    if ::yew::form::FormValue::is_empty(&self.#ident) {
+                        return ::std::result::Result::Err(::std::string::String::from("required"));
+                    }
+                }
+            } else {
+                quote! {}
+            };
+            let validate = match &field.validate {
+                Some(path) => quote! { #path(&self.#ident) },
+                None => quote! { ::std::result::Result::Ok(()) },
+            };
+            quote! {{
+                #required
+                #validate
+            }}
+        });
+
+        tokens.extend(quote! {
+            impl ::yew::form::FormSchema for #ident {
+                fn fields() -> &'static [::yew::form::FieldSchema] {
+                    const FIELDS: &[::yew::form::FieldSchema] = &[#(#schemas),*];
+                    FIELDS
+                }
+
+                fn value(&self, name: &str) -> ::std::option::Option<::yew::form::FieldValue> {
+                    match name {
+                        #(#names => ::std::option::Option::Some(
+                            ::yew::form::FormValue::to_field(&self.#idents)
+                        ),)*
+                        _ => ::std::option::Option::None,
+                    }
+                }
+
+                fn set_value(
+                    &mut self,
+                    name: &str,
+                    value: ::yew::form::FieldValue,
+                ) -> ::std::result::Result<(), ::std::string::String> {
+                    match name {
+                        #(#names => {
+                            self.#idents = ::yew::form::FormValue::from_field(value)?;
+                            ::std::result::Result::Ok(())
+                        })*
+                        _ => ::std::result::Result::Err(
+                            ::std::format!("unknown field `{}`", name)
+                        ),
+                    }
+                }
+
+                fn validate_field(
+                    &self,
+                    name: &str,
+                ) -> ::std::result::Result<(), ::std::string::String> {
+                    match name {
+                        #(#names => #validations)*
+                        _ => ::std::result::Result::Ok(()),
+                    }
+                }
+            }
+        });
+    }
+}
@@ -0,0 +1,58 @@
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+use syn::parse::{Parse, ParseStream, Result};
+use syn::punctuated::Punctuated;
+use syn::{DeriveInput, Generics, Ident, Token, Type};
+
+pub struct DeriveServiceInput {
+    ident: Ident,
+    generics: Generics,
+    interfaces: Vec<Type>,
+}
+
+impl Parse for DeriveServiceInput {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let input: DeriveInput = input.parse()?;
+
+        let mut interfaces = Vec::new();
+        for attr in input.attrs.iter().filter(|a| a.path.is_ident("service")) {
+            let types =
+                attr.parse_args_with(Punctuated::<Type, Token![,]>::parse_separated_nonempty)?;
+            interfaces.extend(types);
+        }
+        if interfaces.is_empty() {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                "expected at least one interface in a `#[service(...)]` attribute, \
+                 e.g. `#[service(dyn Logger)]`",
+            ));
+        }
+
+        Ok(Self {
+            ident: input.ident,
+            generics: input.generics,
+            interfaces,
+        })
+    }
+}
+
+impl ToTokens for DeriveServiceInput {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let Self {
+            ident,
+            generics,
+            interfaces,
+        } = self;
+        let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+        for interface in interfaces {
+            tokens.extend(quote! {
+                impl#impl_generics ::yew::context::IntoService<#interface> for #ident#ty_generics #where_clause {
+                    fn into_service(self) -> ::yew::context::Service<#interface> {
+                        ::yew::context::Service::new(::std::rc::Rc::new(self))
+                    }
+                }
+            });
+        }
+    }
+}
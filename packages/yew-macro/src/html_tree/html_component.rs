@@ -1,4 +1,4 @@
-use super::{HtmlChildrenTree, TagTokens};
+use super::{HtmlChildrenTree, TagTokens, Whitespace};
 use crate::{props::ComponentProps, PeekValue};
 use boolinator::Boolinator;
 use proc_macro2::Span;
@@ -87,6 +87,12 @@ impl Parse for HtmlComponent {
     }
 }
 
+impl HtmlComponent {
+    pub fn apply_whitespace(&mut self, mode: Whitespace) {
+        self.children.apply_whitespace(mode);
+    }
+}
+
 impl ToTokens for HtmlComponent {
     fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
         let Self {
@@ -1,14 +1,14 @@
-use super::{HtmlChildrenTree, HtmlDashedName, TagTokens};
+use super::{HtmlChildrenTree, HtmlDashedName, TagTokens, Whitespace};
 use crate::props::{ClassesForm, ElementProps, Prop};
 use crate::stringify::{Stringify, Value};
 use crate::{non_capitalized_ascii, Peek, PeekValue};
 use boolinator::Boolinator;
-use proc_macro2::{Delimiter, TokenStream};
+use proc_macro2::{Delimiter, Span, TokenStream};
 use quote::{quote, quote_spanned, ToTokens};
 use syn::buffer::Cursor;
 use syn::parse::{Parse, ParseStream};
 use syn::spanned::Spanned;
-use syn::{Block, Expr, Ident, Lit, LitStr, Token};
+use syn::{Block, Expr, ExprLit, ExprMethodCall, ExprPath, Ident, Lit, LitStr, Token};
 
 pub struct HtmlElement {
     name: TagName,
@@ -88,6 +88,20 @@ impl Parse for HtmlElement {
     }
 }
 
+impl HtmlElement {
+    pub fn apply_whitespace(&mut self, mode: Whitespace) {
+        // Whitespace is significant in these
+        if let TagName::Lit(name) = &self.name {
+            if let "pre" | "textarea" | "script" | "style" =
+                name.to_ascii_lowercase_string().as_str()
+            {
+                return;
+            }
+        }
+        self.children.apply_whitespace(mode);
+    }
+}
+
 impl ToTokens for HtmlElement {
     #[allow(clippy::cognitive_complexity)]
     fn to_tokens(&self, tokens: &mut TokenStream) {
@@ -106,6 +120,12 @@ impl ToTokens for HtmlElement {
             node_ref,
             key,
             listeners,
+            before_patch,
+            after_patch,
+            scroll_anchor,
+            commands,
+            inert,
+            spreads,
         } = &props;
 
         // attributes with special treatment
@@ -123,7 +143,13 @@ impl ToTokens for HtmlElement {
         let key = key
             .as_ref()
             .map(|attr| {
-                let value = attr.value.optimize_literals();
+                // integer keys are kept as is, as they are not stored as strings
+                let value = match &attr.value {
+                    Expr::Lit(ExprLit {
+                        lit: Lit::Int(_), ..
+                    }) => attr.value.to_token_stream(),
+                    value => value.optimize_literals(),
+                };
                 quote_spanned! {value.span()=>
                     ::std::option::Option::Some(
                         ::std::convert::Into::<::yew::virtual_dom::Key>::into(#value)
@@ -143,6 +169,47 @@ impl ToTokens for HtmlElement {
             })
             .unwrap_or(quote! { false });
 
+        let patch_hooks = {
+            let before = before_patch.as_ref().map(|attr| {
+                let value = &attr.value;
+                quote_spanned! {value.span()=> __yew_vtag.set_before_patch(#value); }
+            });
+            let after = after_patch.as_ref().map(|attr| {
+                let value = &attr.value;
+                quote_spanned! {value.span()=> __yew_vtag.set_after_patch(#value); }
+            });
+            let anchor = scroll_anchor.as_ref().map(|attr| {
+                let value = &attr.value;
+                quote_spanned! {value.span()=>
+                    __yew_vtag.set_scroll_anchor(
+                        ::yew::html::IntoPropValue::<::yew::html::NodeRef>::into_prop_value(#value)
+                    );
+                }
+            });
+            let commands = commands.as_ref().map(|attr| {
+                let value = &attr.value;
+                quote_spanned! {value.span()=> __yew_vtag.set_commands(#value); }
+            });
+            let inert = inert.as_ref().map(|attr| {
+                let value = &attr.value;
+                quote_spanned! {value.span()=> __yew_vtag.set_inert(#value); }
+            });
+            let spreads = spreads.iter().map(|group| {
+                quote_spanned! {group.span()=> __yew_vtag.spread(&#group); }
+            });
+            if before.is_some()
+                || after.is_some()
+                || anchor.is_some()
+                || commands.is_some()
+                || inert.is_some()
+                || !props.spreads.is_empty()
+            {
+                Some(quote! { #(#spreads)* #before #after #anchor #commands #inert })
+            } else {
+                None
+            }
+        };
+
         // other attributes
 
         let attributes = {
@@ -271,12 +338,7 @@ impl ToTokens for HtmlElement {
         let listeners = if listeners.is_empty() {
             quote! { ::std::vec![] }
         } else {
-            let listeners_it = listeners.iter().map(|Prop { label, value, .. }| {
-                let name = &label.name;
-                quote! {
-                    ::yew::html::#name::Wrapper::__macro_new(#value)
-                }
-            });
+            let listeners_it = listeners.iter().map(wrap_listener);
 
             quote! { ::std::vec![#(#listeners_it),*].into_iter().flatten().collect() }
         };
@@ -294,51 +356,55 @@ impl ToTokens for HtmlElement {
             TagName::Lit(name) => {
                 let name_span = name.span();
                 let name = name.to_ascii_lowercase_string();
-                match &*name {
+                let vtag = match &*name {
                     "input" => {
                         quote_spanned! {name_span=>
-                            #[allow(clippy::redundant_clone, unused_braces)]
-                            ::std::convert::Into::<::yew::virtual_dom::VNode>::into(
-                                ::yew::virtual_dom::VTag::__new_input(
-                                    #value,
-                                    #checked,
-                                    #node_ref,
-                                    #key,
-                                    #attributes,
-                                    #listeners,
-                                ),
+                            ::yew::virtual_dom::VTag::__new_input(
+                                #value,
+                                #checked,
+                                #node_ref,
+                                #key,
+                                #attributes,
+                                #listeners,
                             )
                         }
                     }
                     "textarea" => {
                         quote_spanned! {name_span=>
-                            #[allow(clippy::redundant_clone, unused_braces)]
-                            ::std::convert::Into::<::yew::virtual_dom::VNode>::into(
-                                ::yew::virtual_dom::VTag::__new_textarea(
-                                    #value,
-                                    #node_ref,
-                                    #key,
-                                    #attributes,
-                                    #listeners,
-                                ),
+                            ::yew::virtual_dom::VTag::__new_textarea(
+                                #value,
+                                #node_ref,
+                                #key,
+                                #attributes,
+                                #listeners,
                             )
                         }
                     }
                     _ => {
                         quote_spanned! {name_span=>
-                            #[allow(clippy::redundant_clone, unused_braces)]
-                            ::std::convert::Into::<::yew::virtual_dom::VNode>::into(
-                                ::yew::virtual_dom::VTag::__new_other(
-                                    ::std::borrow::Cow::<'static, str>::Borrowed(#name),
-                                    #node_ref,
-                                    #key,
-                                    #attributes,
-                                    #listeners,
-                                    #child_list,
-                                ),
+                            ::yew::virtual_dom::VTag::__new_other(
+                                ::std::borrow::Cow::<'static, str>::Borrowed(#name),
+                                #node_ref,
+                                #key,
+                                #attributes,
+                                #listeners,
+                                #child_list,
                             )
                         }
                     }
+                };
+                match &patch_hooks {
+                    // Keep the common case a single constructor call
+                    None => quote_spanned! {name_span=>
+                        #[allow(clippy::redundant_clone, unused_braces)]
+                        ::std::convert::Into::<::yew::virtual_dom::VNode>::into(#vtag)
+                    },
+                    Some(patch_hooks) => quote_spanned! {name_span=> {
+                        #[allow(clippy::redundant_clone, unused_braces)]
+                        let mut __yew_vtag = #vtag;
+                        #patch_hooks
+                        ::std::convert::Into::<::yew::virtual_dom::VNode>::into(__yew_vtag)
+                    }},
                 }
             }
             TagName::Expr(name) => {
@@ -428,6 +494,8 @@ impl ToTokens for HtmlElement {
                         }
                     }
 
+                    #patch_hooks
+
                     ::std::convert::Into::<::yew::virtual_dom::VNode>::into(#vtag)
                 }}
             }
@@ -447,6 +515,89 @@ fn wrap_attr_prop(prop: &Prop) -> TokenStream {
     }
 }
 
+/// Expands a listener into an `Option<Rc<dyn Listener>>`
+pub fn wrap_listener(prop: &Prop) -> TokenStream {
+    let Prop {
+        label,
+        modifier,
+        value,
+    } = prop;
+    let name = &label.name;
+    let constructor = match modifier {
+        Some(modifier) => Ident::new(&format!("__macro_new_{}", modifier), Span::call_site()),
+        None => Ident::new("__macro_new", Span::call_site()),
+    };
+    let value = wrap_listener_value(value);
+    quote! {
+        ::yew::html::#name::Wrapper::#constructor(#value)
+    }
+}
+
+/// Expands the value of a listener. Unit variants like `Msg::Increment` are sent with
+/// `ctx.link().callback`, so they need the `ctx` of the `view` they are rendered in. The messages
+/// returned by the closures passed to `callback` methods are checked against the `Message` of the
+/// component at the closure body.
+fn wrap_listener_value(value: &Expr) -> TokenStream {
+    match value {
+        Expr::Path(path) if is_variant_path(path) => {
+            let callback = syn::parse2(quote_spanned! {value.span()=>
+                ctx.link().callback(|_| #value)
+            })
+            .expect("a callback of a variant is an expression");
+            wrap_listener_value(&callback)
+        }
+        Expr::MethodCall(call) if call.args.len() == 1 => {
+            let closure = match call.args.first() {
+                Some(Expr::Closure(closure))
+                    if call.method == "callback" || call.method == "callback_once" =>
+                {
+                    closure
+                }
+                _ => return value.to_token_stream(),
+            };
+            let ExprMethodCall {
+                receiver,
+                method,
+                turbofish,
+                ..
+            } = call;
+            let body = &closure.body;
+            let mut closure = closure.clone();
+            // The check is copied into the closure
+            closure.capture = Some(Token![move](closure.or1_token.span()));
+            closure.body = Box::new(Expr::Verbatim(quote_spanned! {body.span()=>
+                { __yew_check.check(#body) }
+            }));
+            quote! {{
+                #[allow(unused_imports)]
+                use ::yew::html::{AnyMessageCheck as _, ScopeMessageCheck as _};
+                let __yew_receiver = &#receiver;
+                let __yew_check =
+                    (&&::yew::html::MessageCheckOf::new(__yew_receiver)).message_check();
+                __yew_receiver.#method#turbofish(#closure)
+            }}
+        }
+        _ => value.to_token_stream(),
+    }
+}
+
+/// Whether `path` looks like a unit variant of a type, e.g. `Msg::Increment`, rather than a
+/// constant or an item of a module. Other paths can be passed in parentheses.
+fn is_variant_path(path: &ExprPath) -> bool {
+    let segments = &path.path.segments;
+    if path.qself.is_some() || segments.len() < 2 {
+        return false;
+    }
+    let is_camel_case = |ident: &Ident| {
+        let name = ident.to_string();
+        name.starts_with(|c: char| c.is_ascii_uppercase())
+            && (name.len() == 1 || name.chars().any(|c| c.is_ascii_lowercase()))
+    };
+    let variant = &segments[segments.len() - 1];
+    let ty = &segments[segments.len() - 2];
+    variant.arguments.is_empty() && is_camel_case(&variant.ident) && is_camel_case(&ty.ident)
+}
+
 struct DynamicName {
     at: Token![@],
     expr: Option<Block>,
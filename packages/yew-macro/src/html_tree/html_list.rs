@@ -1,4 +1,4 @@
-use super::{html_dashed_name::HtmlDashedName, HtmlChildrenTree, TagTokens};
+use super::{html_dashed_name::HtmlDashedName, HtmlChildrenTree, TagTokens, Whitespace};
 use crate::{props::Prop, Peek, PeekValue};
 use boolinator::Boolinator;
 use quote::{quote, quote_spanned, ToTokens};
@@ -46,6 +46,9 @@ impl Parse for HtmlList {
         }
 
         let close = input.parse::<HtmlListClose>()?;
+        if let Some(mode) = open.props.whitespace {
+            children.apply_whitespace(mode);
+        }
 
         Ok(Self {
             open,
@@ -55,6 +58,14 @@ impl Parse for HtmlList {
     }
 }
 
+impl HtmlList {
+    pub fn apply_whitespace(&mut self, mode: Whitespace) {
+        if self.open.props.whitespace.is_none() {
+            self.children.apply_whitespace(mode);
+        }
+    }
+}
+
 impl ToTokens for HtmlList {
     fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
         let Self {
@@ -119,28 +130,40 @@ impl Parse for HtmlListOpen {
 
 struct HtmlListProps {
     key: Option<Expr>,
+    whitespace: Option<Whitespace>,
 }
 impl Parse for HtmlListProps {
     fn parse(input: ParseStream) -> syn::Result<Self> {
-        let key = if input.is_empty() {
-            None
-        } else {
+        let mut props = Self {
+            key: None,
+            whitespace: None,
+        };
+        while !input.is_empty() {
             let prop: Prop = input.parse()?;
-            if !input.is_empty() {
-                return Err(input.error("only a single `key` prop is allowed on a fragment"));
-            }
-
-            if prop.label.to_ascii_lowercase_string() != "key" {
-                return Err(syn::Error::new_spanned(
-                    prop.label,
-                    "fragments only accept the `key` prop",
-                ));
+            let label = prop.label.to_ascii_lowercase_string();
+            let duplicate = match label.as_str() {
+                "key" => props.key.replace(prop.value).is_some(),
+                "whitespace" => props
+                    .whitespace
+                    .replace(Whitespace::from_expr(&prop.value)?)
+                    .is_some(),
+                _ => {
+                    return Err(syn::Error::new_spanned(
+                        prop.label,
+                        "fragments only accept the `key` and `whitespace` props",
+                    ))
+                }
+            };
+            if duplicate {
+                let message = match label.as_str() {
+                    "key" => "only a single `key` prop is allowed on a fragment".to_owned(),
+                    _ => format!("`{}` can only be specified once", label),
+                };
+                return Err(syn::Error::new_spanned(prop.label, message));
             }
+        }
 
-            Some(prop.value)
-        };
-
-        Ok(Self { key })
+        Ok(props)
     }
 }
 
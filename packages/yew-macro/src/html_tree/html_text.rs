@@ -0,0 +1,103 @@
+use crate::stringify::Stringify;
+use crate::PeekValue;
+use proc_macro2::TokenStream;
+use quote::{quote_spanned, ToTokens};
+use syn::buffer::Cursor;
+use syn::parse::{Parse, ParseStream};
+use syn::{Expr, ExprLit, Lit, LitStr};
+
+/// A string literal written directly among children, e.g. `<p>"Hello, " {name}</p>`.
+///
+/// Unlike literals in blocks, like `{" "}`, these follow the [`Whitespace`] mode of the enclosing
+/// fragment.
+pub struct HtmlText(LitStr);
+
+impl PeekValue<()> for HtmlText {
+    fn peek(cursor: Cursor) -> Option<()> {
+        let (lit, _) = cursor.literal()?;
+        match Lit::new(lit) {
+            Lit::Str(_) => Some(()),
+            _ => None,
+        }
+    }
+}
+
+impl Parse for HtmlText {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        input.parse().map(Self)
+    }
+}
+
+impl HtmlText {
+    /// Applies `mode` to the text. Returns `false` if nothing remains of it.
+    pub fn apply_whitespace(&mut self, mode: Whitespace) -> bool {
+        let value = self.0.value();
+        let text = match mode {
+            Whitespace::Preserve => return true,
+            Whitespace::Collapse => collapse(&value, false),
+            Whitespace::Trim => collapse(&value, true),
+        };
+        self.0 = LitStr::new(&text, self.0.span());
+        !text.is_empty()
+    }
+}
+
+impl ToTokens for HtmlText {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let text = self.0.stringify();
+        tokens.extend(quote_spanned! {self.0.span()=> ::yew::virtual_dom::VText::new(#text) });
+    }
+}
+
+/// How whitespace in the text literals of a fragment is rendered, set with
+/// `<whitespace="trim">...</>`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Whitespace {
+    /// Keeps text as written, the default
+    Preserve,
+    /// Collapses runs of whitespace into a single space, as browsers render them
+    Collapse,
+    /// Also removes whitespace at the start and end of texts and drops texts which are only
+    /// whitespace. Spaces between texts are then written as `{" "}`.
+    Trim,
+}
+
+impl Whitespace {
+    pub fn from_expr(expr: &Expr) -> syn::Result<Self> {
+        if let Expr::Lit(ExprLit {
+            lit: Lit::Str(lit), ..
+        }) = expr
+        {
+            match lit.value().as_str() {
+                "preserve" => return Ok(Self::Preserve),
+                "collapse" => return Ok(Self::Collapse),
+                "trim" => return Ok(Self::Trim),
+                _ => {}
+            }
+        }
+        Err(syn::Error::new_spanned(
+            expr,
+            r#"expected one of `"preserve"`, `"collapse"` or `"trim"`"#,
+        ))
+    }
+}
+
+fn collapse(text: &str, trim: bool) -> String {
+    let mut collapsed = String::with_capacity(text.len());
+    let mut space = false;
+    for c in text.chars() {
+        if c.is_whitespace() {
+            space = true;
+            continue;
+        }
+        if space && (!trim || !collapsed.is_empty()) {
+            collapsed.push(' ');
+        }
+        space = false;
+        collapsed.push(c);
+    }
+    if space && !trim {
+        collapsed.push(' ');
+    }
+    collapsed
+}
@@ -13,15 +13,18 @@ mod html_element;
 mod html_iterable;
 mod html_list;
 mod html_node;
+mod html_text;
 mod tag;
 
 use html_block::HtmlBlock;
 use html_component::HtmlComponent;
 pub use html_dashed_name::HtmlDashedName;
+pub use html_element::wrap_listener;
 use html_element::HtmlElement;
 use html_iterable::HtmlIterable;
 use html_list::HtmlList;
 use html_node::HtmlNode;
+use html_text::{HtmlText, Whitespace};
 use tag::TagTokens;
 
 pub enum HtmlType {
@@ -29,6 +32,7 @@ pub enum HtmlType {
     Component,
     List,
     Element,
+    Text,
     Empty,
 }
 
@@ -37,6 +41,7 @@ pub enum HtmlTree {
     Component(Box<HtmlComponent>),
     List(Box<HtmlList>),
     Element(Box<HtmlElement>),
+    Text(Box<HtmlText>),
     Empty,
 }
 
@@ -50,6 +55,7 @@ impl Parse for HtmlTree {
             HtmlType::Element => HtmlTree::Element(Box::new(input.parse()?)),
             HtmlType::Block => HtmlTree::Block(Box::new(input.parse()?)),
             HtmlType::List => HtmlTree::List(Box::new(input.parse()?)),
+            HtmlType::Text => HtmlTree::Text(Box::new(input.parse()?)),
         };
         Ok(html_tree)
     }
@@ -71,6 +77,8 @@ impl HtmlTree {
             .is_some()
         {
             Some(HtmlType::Block)
+        } else if HtmlText::peek(input.cursor()).is_some() {
+            Some(HtmlType::Text)
         } else if input.peek(Token![<]) {
             let _lt: Token![<] = input.parse().ok()?;
 
@@ -115,6 +123,7 @@ impl ToTokens for HtmlTree {
             HtmlTree::Element(tag) => tag.to_tokens(tokens),
             HtmlTree::List(list) => list.to_tokens(tokens),
             HtmlTree::Block(block) => block.to_tokens(tokens),
+            HtmlTree::Text(text) => text.to_tokens(tokens),
         }
     }
 }
@@ -208,6 +217,29 @@ impl HtmlChildrenTree {
         self.0.is_empty()
     }
 
+    /// Applies `mode` to the text literals among the children and their descendants, except in
+    /// fragments with their own mode
+    pub fn apply_whitespace(&mut self, mode: Whitespace) {
+        let children = std::mem::take(&mut self.0);
+        self.0 = children
+            .into_iter()
+            .filter_map(|mut child| {
+                match &mut child {
+                    HtmlTree::Text(text) => {
+                        if !text.apply_whitespace(mode) {
+                            return None;
+                        }
+                    }
+                    HtmlTree::Element(element) => element.apply_whitespace(mode),
+                    HtmlTree::Component(component) => component.apply_whitespace(mode),
+                    HtmlTree::List(list) => list.apply_whitespace(mode),
+                    HtmlTree::Block(_) | HtmlTree::Empty => {}
+                }
+                Some(child)
+            })
+            .collect();
+    }
+
     // Check if each child represents a single node.
     // This is the case when no expressions are used.
     fn only_single_node_children(&self) -> bool {
@@ -46,14 +46,19 @@
 //!
 //! Please refer to [https://github.com/yewstack/yew](https://github.com/yewstack/yew) for how to set this up.
 
+mod attrs;
 mod classes;
+mod derive_form_schema;
 mod derive_props;
+mod derive_service;
 mod function_component;
 mod html_tree;
 mod props;
 mod stringify;
 
+use derive_form_schema::DeriveFormSchemaInput;
 use derive_props::DerivePropsInput;
+use derive_service::DeriveServiceInput;
 use function_component::{function_component_impl, FunctionComponent, FunctionComponentName};
 use html_tree::{HtmlRoot, HtmlRootVNode};
 use proc_macro::TokenStream;
@@ -96,6 +101,18 @@ pub fn derive_props(input: TokenStream) -> TokenStream {
     TokenStream::from(input.into_token_stream())
 }
 
+#[proc_macro_derive(Service, attributes(service))]
+pub fn derive_service(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveServiceInput);
+    TokenStream::from(input.into_token_stream())
+}
+
+#[proc_macro_derive(FormSchema, attributes(form))]
+pub fn derive_form_schema(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveFormSchemaInput);
+    TokenStream::from(input.into_token_stream())
+}
+
 #[proc_macro]
 pub fn html_nested(input: TokenStream) -> TokenStream {
     let root = parse_macro_input!(input as HtmlRoot);
@@ -114,6 +131,12 @@ pub fn props(input: TokenStream) -> TokenStream {
     TokenStream::from(props.into_token_stream())
 }
 
+#[proc_macro]
+pub fn attrs(input: TokenStream) -> TokenStream {
+    let attrs = parse_macro_input!(input as attrs::Attrs);
+    TokenStream::from(attrs.into_token_stream())
+}
+
 #[proc_macro]
 pub fn classes(input: TokenStream) -> TokenStream {
     let classes = parse_macro_input!(input as classes::Classes);
@@ -197,6 +197,7 @@ impl TryFrom<Props> for ComponentProps {
     fn try_from(props: Props) -> Result<Self, Self::Error> {
         props.check_no_duplicates()?;
         props.check_all(|prop| {
+            prop.check_no_modifier()?;
             if !prop.label.extended.is_empty() {
                 Err(syn::Error::new_spanned(
                     &prop.label,
@@ -1,8 +1,10 @@
-use super::{Prop, Props, SpecialProps};
+use super::{Prop, Props, SortedPropList, SpecialProps};
 use lazy_static::lazy_static;
 use std::collections::HashSet;
+use std::convert::TryFrom;
 use syn::parse::{Parse, ParseStream};
-use syn::{Expr, ExprTuple};
+use syn::token::Brace;
+use syn::{Expr, ExprBlock, ExprPath, ExprTuple, Token};
 
 pub enum ClassesForm {
     Tuple(ExprTuple),
@@ -26,17 +28,43 @@ pub struct ElementProps {
     pub checked: Option<Prop>,
     pub node_ref: Option<Prop>,
     pub key: Option<Prop>,
+    pub before_patch: Option<Prop>,
+    pub after_patch: Option<Prop>,
+    pub scroll_anchor: Option<Prop>,
+    pub commands: Option<Prop>,
+    pub inert: Option<Prop>,
+    /// Attribute groups spread onto the element with `..group` or `..{group}`
+    pub spreads: Vec<Expr>,
 }
 
 impl Parse for ElementProps {
     fn parse(input: ParseStream) -> syn::Result<Self> {
-        let mut props = input.parse::<Props>()?;
+        let mut spreads = Vec::new();
+        let mut prop_list = Vec::new();
+        while !input.is_empty() {
+            if input.peek(Token![..]) {
+                spreads.push(parse_spread(input)?);
+            } else {
+                prop_list.push(input.parse()?);
+            }
+        }
+        let mut props = Props::try_from(SortedPropList::new(prop_list))?;
 
         let listeners =
             props.drain_filter(|prop| LISTENER_SET.contains(prop.label.to_string().as_str()));
 
         // Multiple listener attributes are allowed, but no others
         props.check_no_duplicates()?;
+        props.check_all(Prop::check_no_modifier)?;
+        listeners.check_all(|prop| match &prop.modifier {
+            Some(modifier) if modifier == "committed" && prop.label.to_string() != "oninput" => {
+                Err(syn::Error::new_spanned(
+                    modifier,
+                    "the `committed` modifier is only supported on `oninput`",
+                ))
+            }
+            _ => Ok(()),
+        })?;
 
         let booleans =
             props.drain_filter(|prop| BOOLEAN_SET.contains(prop.label.to_string().as_str()));
@@ -46,6 +74,11 @@ impl Parse for ElementProps {
             .map(|prop| ClassesForm::from_expr(prop.value));
         let value = props.pop("value");
         let checked = props.pop("checked");
+        let before_patch = props.pop("on_before_patch");
+        let after_patch = props.pop("on_after_patch");
+        let scroll_anchor = props.pop("scroll_anchor");
+        let commands = props.pop("commands");
+        let inert = props.pop("inert");
 
         let SpecialProps { node_ref, key } = props.special;
 
@@ -58,12 +91,32 @@ impl Parse for ElementProps {
             value,
             node_ref,
             key,
+            before_patch,
+            after_patch,
+            scroll_anchor,
+            commands,
+            inert,
+            spreads,
+        })
+    }
+}
+
+fn parse_spread(input: ParseStream) -> syn::Result<Expr> {
+    input.parse::<Token![..]>()?;
+    if input.peek(Brace) {
+        input.parse::<ExprBlock>().map(Expr::Block)
+    } else {
+        input.parse::<ExprPath>().map(Expr::Path).map_err(|err| {
+            syn::Error::new(
+                err.span(),
+                "expected an attribute group. Consider adding braces around your expression: `..{group}`",
+            )
         })
     }
 }
 
 lazy_static! {
-    static ref BOOLEAN_SET: HashSet<&'static str> = {
+    pub(crate) static ref BOOLEAN_SET: HashSet<&'static str> = {
         vec![
             "async",
             "autofocus",
@@ -89,7 +142,7 @@ lazy_static! {
 }
 
 lazy_static! {
-    static ref LISTENER_SET: HashSet<&'static str> = {
+    pub(crate) static ref LISTENER_SET: HashSet<&'static str> = {
         vec![
             // Living Standard
             // From: https://html.spec.whatwg.org/multipage/webappapis.html#globaleventhandlers
@@ -173,6 +226,9 @@ lazy_static! {
             "onanimationend",
             "onanimationiteration",
             "onanimationstart",
+            "oncompositionend",
+            "oncompositionstart",
+            "oncompositionupdate",
             "ongotpointercapture",
             "onloadend",
             "onlostpointercapture",
@@ -8,11 +8,13 @@ use syn::{
     braced,
     parse::{Parse, ParseStream},
     token::Brace,
-    Block, Expr, ExprBlock, ExprPath, Stmt, Token,
+    Block, Expr, ExprBlock, ExprPath, Ident, Stmt, Token,
 };
 
 pub struct Prop {
     pub label: HtmlDashedName,
+    /// Modifier of the form `label:modifier={value}`. Only supported on listeners.
+    pub modifier: Option<Ident>,
     /// Punctuation between `label` and `value`.
     pub value: Expr,
 }
@@ -28,6 +30,20 @@ impl Parse for Prop {
 
 /// Helpers for parsing props
 impl Prop {
+    /// Fail if the prop has a modifier, which are only allowed on element listeners
+    pub fn check_no_modifier(&self) -> syn::Result<()> {
+        match &self.modifier {
+            Some(modifier) => Err(syn::Error::new_spanned(
+                modifier,
+                format!(
+                    "`{}` is not an event listener and can't be `:{}`",
+                    self.label, modifier
+                ),
+            )),
+            None => Ok(()),
+        }
+    }
+
     /// Parse a prop using the shorthand syntax `{value}`, short for `value={value}`
     /// This only allows for labels with no hyphens, as it would otherwise create
     /// an ambiguity in the syntax
@@ -56,12 +72,29 @@ impl Prop {
             ));
         }?;
 
-        Ok(Self { label, value: expr })
+        Ok(Self {
+            label,
+            modifier: None,
+            value: expr,
+        })
     }
 
     /// Parse a prop of the form `label={value}`
     fn parse_prop_assignment(input: ParseStream) -> syn::Result<Self> {
         let label = input.parse::<HtmlDashedName>()?;
+        let modifier = if input.peek(Token![:]) {
+            input.parse::<Token![:]>()?;
+            let modifier = input.parse::<Ident>()?;
+            if modifier != "once" && modifier != "committed" {
+                return Err(syn::Error::new_spanned(
+                    modifier,
+                    "unknown modifier. Supported modifiers are `once` and `committed` on event listeners",
+                ));
+            }
+            Some(modifier)
+        } else {
+            None
+        };
         let equals = input.parse::<Token![=]>().map_err(|_| {
             syn::Error::new_spanned(
                 &label,
@@ -75,7 +108,11 @@ impl Prop {
             ));
         }
         let value = strip_braces(input.parse::<Expr>()?)?;
-        Ok(Self { label, value })
+        Ok(Self {
+            label,
+            modifier,
+            value,
+        })
     }
 }
 
@@ -286,6 +323,7 @@ impl TryFrom<SortedPropList> for Props {
 
     fn try_from(mut prop_list: SortedPropList) -> Result<Self, Self::Error> {
         let special = SpecialProps::pop_from(&mut prop_list)?;
+        special.check_all(Prop::check_no_modifier)?;
         Ok(Self { special, prop_list })
     }
 }
@@ -0,0 +1,43 @@
+use yew::form::{FieldKind, FormSchema, SchemaForm};
+use yew::prelude::*;
+
+mod validators {
+    pub fn not_admin(name: &String) -> Result<(), String> {
+        if name == "admin" {
+            Err("reserved".to_string())
+        } else {
+            Ok(())
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, FormSchema)]
+struct Account {
+    #[form(required, validate = "validators::not_admin")]
+    name: String,
+    #[form(label = "E-mail", kind = "email", help = "Never shared")]
+    email: Option<String>,
+    r#type: u8,
+    newsletter: bool,
+    #[form(skip)]
+    id: u64,
+}
+
+fn compile_pass() {
+    let account = Account {
+        name: String::new(),
+        email: None,
+        r#type: 0,
+        newsletter: false,
+        id: 0,
+    };
+    assert_eq!(Account::fields()[1].kind, FieldKind::Email);
+    assert_eq!(Account::fields()[2].label, "Type");
+    let _ = account.validate();
+
+    html! {
+        <SchemaForm<Account> value={account} onchange={Callback::from(|_| ())} />
+    };
+}
+
+fn main() {}
@@ -0,0 +1,6 @@
+#[allow(dead_code)]
+#[rustversion::attr(stable(1.51), test)]
+fn derive_form_schema() {
+    let t = trybuild::TestCases::new();
+    t.pass("tests/derive_form_schema/pass.rs");
+}
@@ -0,0 +1,51 @@
+use yew::context::{IntoService, Service, ServiceProvider};
+use yew::prelude::*;
+
+trait Logger {
+    fn log(&self, message: &str);
+}
+
+trait Clock {
+    fn now(&self) -> u64;
+}
+
+#[derive(Service)]
+#[service(dyn Logger, dyn Clock)]
+struct ConsoleLogger;
+
+impl Logger for ConsoleLogger {
+    fn log(&self, _message: &str) {}
+}
+
+impl Clock for ConsoleLogger {
+    fn now(&self) -> u64 {
+        0
+    }
+}
+
+#[derive(Service)]
+#[service(dyn Logger)]
+struct Prefixed<T: 'static> {
+    prefix: T,
+}
+
+impl<T: 'static> Logger for Prefixed<T> {
+    fn log(&self, _message: &str) {}
+}
+
+fn compile_pass() {
+    let logger: Service<dyn Logger> = ConsoleLogger.into_service();
+    logger.log("hello");
+    let clock: Service<dyn Clock> = ConsoleLogger.into_service();
+    let _ = clock.now();
+    let _: Service<dyn Logger> = Prefixed { prefix: 1 }.into_service();
+
+    type LoggerProvider = ServiceProvider<dyn Logger>;
+    html! {
+        <LoggerProvider context={logger}>
+            <div />
+        </LoggerProvider>
+    };
+}
+
+fn main() {}
@@ -43,6 +43,7 @@ fn compile_pass() {
             <img class={classes!("avatar", "hidden")} src="http://pic.com" />
             <img class="avatar hidden" />
             <button onclick={&onclick} {onclick} />
+            <div onanimationend:once={Callback::from(|_| ())} ontransitionend:once={|_| ()} />
             <a href="http://google.com" />
             <custom-tag-a>
                 <custom-tag-b />
@@ -65,6 +66,21 @@ fn compile_pass() {
             <track kind={Some(Cow::Borrowed("subtitles"))} src={cow_none.clone()} />
             <track kind={Some(Cow::Borrowed("5"))} mixed="works" />
             <input value={Some(Cow::Borrowed("value"))} onblur={Some(Callback::from(|_| ()))} />
+            <input oninput:committed={|_| ()} oncompositionstart={|_| ()} oncompositionend={|_| ()} />
+            <div
+                onpointerdown={|_: PointerEvent| ()}
+                onpointermove={|_: PointerEvent| ()}
+                onpointerup={|_: PointerEvent| ()}
+                onpointercancel={|_: PointerEvent| ()}
+                ongotpointercapture={|_: PointerEvent| ()}
+                onlostpointercapture={|_: PointerEvent| ()}
+            />
+            <li on_before_patch={|_: yew::web_sys::Element| ()} on_after_patch={Callback::from(|_| ())} />
+            <@{dyn_tag()} on_after_patch={|_| ()} />
+            <input commands={vec![yew::virtual_dom::ElementCommand::Focus]} />
+            <div commands={[yew::virtual_dom::ElementCommand::ScrollTo(0)]} />
+            <div inert=true><button /></div>
+            <@{dyn_tag()} inert={!true} />
         </div>
     };
 
@@ -74,9 +90,51 @@ fn compile_pass() {
     ];
     html! { <div>{children}</div> };
 
+    // attribute groups
+    let group = yew::attrs! { class="btn", role="button" tabindex=0, onclick={&onclick} };
+    html! { <div ..group id="spread" /> };
+    html! { <input ..{&group} ..{yew::virtual_dom::AttrGroup::new()} /> };
+
     // handle misleading angle brackets
     html! { <div data-val={<String as Default>::default()}></div> };
     html! { <div><a data-val={<String as Default>::default()} /></div> };
 }
 
+enum Msg {
+    Increment,
+    Set(u32),
+}
+
+impl From<u32> for Msg {
+    fn from(value: u32) -> Self {
+        Self::Set(value)
+    }
+}
+
+struct Counter {
+    link: yew::html::Scope<Self>,
+}
+
+impl Component for Counter {
+    type Message = Msg;
+    type Properties = ();
+
+    fn create(ctx: &Context<Self>) -> Self {
+        Self {
+            link: ctx.link().clone(),
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        html! {
+            <>
+                <button onclick={Msg::Increment} />
+                <button onclick={ctx.link().callback(|_| Msg::Set(1))} />
+                <button onclick={ctx.link().callback_once(move |_: MouseEvent| 2_u32)} />
+                <button onclick={self.link.callback(|_| Msg::Increment)} />
+            </>
+        }
+    }
+}
+
 fn main() {}
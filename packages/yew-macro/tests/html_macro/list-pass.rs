@@ -19,4 +19,12 @@ fn main() {
         ::yew::html! { <span>{ "World" }</span> },
     ];
     ::yew::html! { <>{ children }</> };
+
+    ::yew::html! { <>"Hello, " <b>"World"</b></> };
+    ::yew::html! {
+        <whitespace="trim" key="text">
+            "Hello,"{ " " }<b>"World"</b>
+            <whitespace="preserve"><pre>"  indented"</pre></>
+        </>
+    };
 }
@@ -9,6 +9,9 @@ use syn::parse_macro_input;
 ///  `yew_router::Routable` trait and `const`s for the routes passed which are used with `Route`
 /// component.
 ///
+/// Variants can declare metadata with the `meta` attribute, which is listed together with the
+/// routes by `Routable::routes`, e.g. `#[meta(title = "Home", changefreq = "daily")]`.
+///
 /// # Example
 ///
 /// ```
@@ -23,7 +26,7 @@ use syn::parse_macro_input;
 ///     NotFound,
 /// }
 /// ```
-#[proc_macro_derive(Routable, attributes(at, not_found))]
+#[proc_macro_derive(Routable, attributes(at, not_found, meta))]
 pub fn routable_derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let input = parse_macro_input!(input as Routable);
     routable_derive_impl(input).into()
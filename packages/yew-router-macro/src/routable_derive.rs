@@ -3,14 +3,19 @@ use quote::quote;
 use syn::parse::{Parse, ParseStream};
 use syn::punctuated::Punctuated;
 use syn::spanned::Spanned;
-use syn::{Data, DeriveInput, Fields, Ident, LitStr, Variant};
+use syn::{Data, DeriveInput, Fields, Ident, Lit, LitStr, MetaNameValue, Token, Variant};
 
 const AT_ATTR_IDENT: &str = "at";
 const NOT_FOUND_ATTR_IDENT: &str = "not_found";
+const META_ATTR_IDENT: &str = "meta";
+
+/// Metadata of a route, as `(key, value)` pairs
+type RouteMeta = Vec<(String, LitStr)>;
 
 pub struct Routable {
     ident: Ident,
     ats: Vec<LitStr>,
+    metas: Vec<RouteMeta>,
     variants: Punctuated<Variant, syn::token::Comma>,
     not_found_route: Option<Ident>,
 }
@@ -36,11 +41,17 @@ impl Parse for Routable {
         };
 
         let (not_found_route, ats) = parse_variants_attributes(&data.variants)?;
+        let metas = data
+            .variants
+            .iter()
+            .map(parse_meta_attributes)
+            .collect::<syn::Result<_>>()?;
 
         Ok(Self {
             ident,
             variants: data.variants,
             ats,
+            metas,
             not_found_route,
         })
     }
@@ -108,6 +119,47 @@ fn parse_variants_attributes(
     Ok((not_founds.into_iter().next(), ats))
 }
 
+/// Parses the `#[meta(key = "value", ...)]` attributes of a variant
+fn parse_meta_attributes(variant: &Variant) -> syn::Result<RouteMeta> {
+    let mut meta = RouteMeta::new();
+    for attr in variant
+        .attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident(META_ATTR_IDENT))
+    {
+        let pairs =
+            attr.parse_args_with(Punctuated::<MetaNameValue, Token![,]>::parse_terminated)?;
+        for pair in pairs {
+            let key = match pair.path.get_ident() {
+                Some(key) => key.to_string(),
+                None => {
+                    return Err(syn::Error::new_spanned(
+                        pair.path,
+                        "expected an identifier as metadata key",
+                    ))
+                }
+            };
+            let value = match pair.lit {
+                Lit::Str(value) => value,
+                lit => {
+                    return Err(syn::Error::new_spanned(
+                        lit,
+                        "expected a string literal as metadata value",
+                    ))
+                }
+            };
+            if meta.iter().any(|(k, _)| *k == key) {
+                return Err(syn::Error::new_spanned(
+                    pair.path,
+                    format!("duplicate metadata key `{}`", key),
+                ));
+            }
+            meta.push((key, value));
+        }
+    }
+    Ok(meta)
+}
+
 impl Routable {
     fn build_from_path(&self) -> TokenStream {
         let from_path_matches = self.variants.iter().enumerate().map(|(i, variant)| {
@@ -189,6 +241,20 @@ pub fn routable_derive_impl(input: Routable) -> TokenStream {
     let from_path = input.build_from_path();
     let to_path = input.build_to_path();
 
+    let routes = input
+        .variants
+        .iter()
+        .zip(ats)
+        .zip(&input.metas)
+        .map(|((variant, at), meta)| {
+            let not_found = not_found_route.as_ref() == Some(&variant.ident);
+            let keys = meta.iter().map(|(key, _)| key);
+            let values = meta.iter().map(|(_, value)| value);
+            quote! {
+                ::yew_router::RouteInfo::__new(#at, #not_found, &[#((#keys, #values)),*])
+            }
+        });
+
     let not_found_route = match not_found_route {
         Some(route) => quote! { ::std::option::Option::Some(Self::#route) },
         None => quote! { ::std::option::Option::None },
@@ -211,8 +277,9 @@ pub fn routable_derive_impl(input: Routable) -> TokenStream {
             #from_path
             #to_path
 
-            fn routes() -> ::std::vec::Vec<&'static str> {
-                ::std::vec![#(#ats),*]
+            fn routes() -> &'static [::yew_router::RouteInfo] {
+                const ROUTES: &[::yew_router::RouteInfo] = &[#(#routes),*];
+                ROUTES
             }
 
             fn not_found_route() -> ::std::option::Option<Self> {
@@ -0,0 +1,15 @@
+#[derive(yew_router::Routable)]
+enum Routes {
+    #[at("/")]
+    #[meta(priority = 1)]
+    One,
+}
+
+#[derive(yew_router::Routable)]
+enum RoutesTwo {
+    #[at("/")]
+    #[meta(title = "One", title = "Two")]
+    One,
+}
+
+fn main() {}
@@ -0,0 +1,30 @@
+#![no_implicit_prelude]
+
+#[derive(Debug, PartialEq, Clone, ::yew_router::Routable)]
+enum Routes {
+    #[at("/")]
+    #[meta(title = "Home", changefreq = "daily")]
+    Home,
+    #[at("/post/:id")]
+    #[meta(title = "Post")]
+    Post { id: u32 },
+    #[at("/404")]
+    #[not_found]
+    NotFound,
+}
+
+fn main() {
+    use ::yew_router::Routable;
+
+    let routes = Routes::routes();
+    ::std::assert_eq!(routes.len(), 3);
+    ::std::assert_eq!(routes[0].path(), "/");
+    ::std::assert_eq!(
+        routes[0].meta("changefreq"),
+        ::std::option::Option::Some("daily")
+    );
+    ::std::assert!(routes[1].is_dynamic());
+    ::std::assert_eq!(routes[1].meta_entries(), &[("title", "Post")]);
+    ::std::assert!(routes[2].is_not_found());
+    ::std::assert!(routes[2].meta("title").is_none());
+}
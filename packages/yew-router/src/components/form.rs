@@ -0,0 +1,50 @@
+use crate::utils::base_url;
+use crate::Routable;
+use std::marker::PhantomData;
+use yew::prelude::*;
+use yew::virtual_dom::AttrValue;
+
+/// Props for [`Form`]
+#[derive(Properties, Clone, PartialEq)]
+pub struct FormProps<R: Routable + Clone + PartialEq> {
+    /// Route the form is submitted to
+    pub action: R,
+    /// HTTP method of the submission when it is not handled by the app
+    #[prop_or(AttrValue::Borrowed("post"))]
+    pub method: AttrValue,
+    /// CSS classes to add to the form element (optional).
+    #[prop_or_default]
+    pub classes: Classes,
+    pub children: Children,
+}
+
+/// A `<form>` submitted to a route, see [`form`](crate::form).
+///
+/// The form is a plain form element, which the browser submits to the route on its own until
+/// the app is loaded.
+pub struct Form<R: Routable + Clone + PartialEq + 'static> {
+    _data: PhantomData<R>,
+}
+
+impl<R: Routable + Clone + PartialEq + 'static> Component for Form<R> {
+    type Message = ();
+    type Properties = FormProps<R>;
+
+    fn create(_ctx: &Context<Self>) -> Self {
+        Self { _data: PhantomData }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let props = ctx.props();
+        let action = format!(
+            "{}{}",
+            base_url().unwrap_or_default(),
+            props.action.to_path()
+        );
+        html! {
+            <form class={props.classes.clone()} {action} method={props.method.clone()}>
+                { props.children.clone() }
+            </form>
+        }
+    }
+}
@@ -0,0 +1,229 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt::Write;
+use web_sys::Element;
+use yew::prelude::*;
+
+/// Attribute marking the `<meta>` elements managed by [`Head`]
+const MANAGED_ATTRIBUTE: &str = "data-yew-head";
+
+/// A `<meta>` tag declared by [`Head`]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Meta {
+    attribute: &'static str,
+    key: String,
+    content: String,
+}
+
+impl Meta {
+    /// A `<meta name="..." content="...">` tag, e.g. `description`
+    pub fn name(name: impl Into<String>, content: impl Into<String>) -> Self {
+        Self {
+            attribute: "name",
+            key: name.into(),
+            content: content.into(),
+        }
+    }
+
+    /// A `<meta property="..." content="...">` tag, e.g. the OpenGraph tag `og:title`
+    pub fn property(property: impl Into<String>, content: impl Into<String>) -> Self {
+        Self {
+            attribute: "property",
+            key: property.into(),
+            content: content.into(),
+        }
+    }
+
+    fn id(&self) -> (&'static str, &str) {
+        (self.attribute, &self.key)
+    }
+}
+
+/// Props for [`Head`]
+#[derive(Properties, Clone, PartialEq)]
+pub struct HeadProps {
+    /// Title of the document
+    #[prop_or_default]
+    pub title: Option<String>,
+    /// `<meta>` tags of the document
+    #[prop_or_default]
+    pub meta: Vec<Meta>,
+}
+
+/// Declares the title and `<meta>` tags of the document while it is mounted.
+///
+/// Route components render a `Head` to describe their page. When several `Head`s are mounted, the
+/// one mounted last wins for every tag it declares. The tags of a `Head` are removed when it is
+/// unmounted, e.g. when the [`Router`](crate::Router) switches to another route, and the tags of
+/// the remaining `Head`s apply again.
+///
+/// Pages pre-rendered on the server can include the tags with [`head_html`].
+///
+/// # Example
+/// ```rust
+/// # use yew::prelude::*;
+/// use yew_router::components::{Head, Meta};
+///
+/// # #[function_component(Post)]
+/// # fn post() -> Html {
+/// html! {
+///     <>
+///         <Head
+///             title={"Hello world".to_string()}
+///             meta={vec![
+///                 Meta::name("description", "The first post"),
+///                 Meta::property("og:title", "Hello world"),
+///             ]}
+///         />
+///         <h1>{ "Hello world" }</h1>
+///     </>
+/// }
+/// # }
+/// ```
+pub struct Head {
+    id: usize,
+}
+
+/// Tags declared by the mounted [`Head`]s, in mounting order
+#[derive(Default)]
+struct Registry {
+    next_id: usize,
+    entries: Vec<(usize, HeadProps)>,
+    /// Title of the document before the first `Head` was mounted
+    initial_title: Option<String>,
+}
+
+thread_local! {
+    static REGISTRY: RefCell<Registry> = Default::default();
+}
+
+impl Registry {
+    fn title(&self) -> Option<&str> {
+        self.entries
+            .iter()
+            .rev()
+            .find_map(|(_, props)| props.title.as_deref())
+    }
+
+    /// Returns the winning `<meta>` tags, in the order they were first declared
+    fn meta(&self) -> Vec<&Meta> {
+        let mut meta: Vec<&Meta> = Vec::new();
+        let mut index = HashMap::new();
+        for (_, props) in &self.entries {
+            for m in &props.meta {
+                match index.get(&m.id()) {
+                    Some(&i) => meta[i] = m,
+                    None => {
+                        index.insert(m.id(), meta.len());
+                        meta.push(m);
+                    }
+                }
+            }
+        }
+        meta
+    }
+
+    /// Updates the document with the declared tags
+    fn apply(&self) {
+        let document = yew::utils::document();
+        document.set_title(
+            self.title()
+                .or_else(|| self.initial_title.as_deref())
+                .unwrap_or_default(),
+        );
+
+        let head = match document.head() {
+            Some(head) => head,
+            None => return,
+        };
+        let managed = head
+            .query_selector_all(&format!("meta[{}]", MANAGED_ATTRIBUTE))
+            .unwrap();
+        for i in 0..managed.length() {
+            if let Some(node) = managed.item(i) {
+                let _ = head.remove_child(&node);
+            }
+        }
+        for m in self.meta() {
+            let el: Element = document.create_element("meta").unwrap();
+            el.set_attribute(MANAGED_ATTRIBUTE, "").unwrap();
+            el.set_attribute(m.attribute, &m.key).unwrap();
+            el.set_attribute("content", &m.content).unwrap();
+            head.append_child(&el).unwrap();
+        }
+    }
+}
+
+impl Component for Head {
+    type Message = ();
+    type Properties = HeadProps;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let id = REGISTRY.with(|registry| {
+            let mut registry = registry.borrow_mut();
+            if registry.entries.is_empty() {
+                registry.initial_title = Some(yew::utils::document().title());
+            }
+            let id = registry.next_id;
+            registry.next_id += 1;
+            registry.entries.push((id, ctx.props().clone()));
+            registry.apply();
+            id
+        });
+        Self { id }
+    }
+
+    fn changed(&mut self, ctx: &Context<Self>) -> bool {
+        REGISTRY.with(|registry| {
+            let mut registry = registry.borrow_mut();
+            if let Some(entry) = registry.entries.iter_mut().find(|(id, _)| *id == self.id) {
+                entry.1 = ctx.props().clone();
+            }
+            registry.apply();
+        });
+        false
+    }
+
+    fn view(&self, _ctx: &Context<Self>) -> Html {
+        html! {}
+    }
+
+    fn destroy(&mut self, _ctx: &Context<Self>) {
+        REGISTRY.with(|registry| {
+            let mut registry = registry.borrow_mut();
+            registry.entries.retain(|(id, _)| *id != self.id);
+            registry.apply();
+        });
+    }
+}
+
+/// Returns the `<title>` and `<meta>` tags declared by the mounted [`Head`]s as HTML, to be
+/// inserted into the `<head>` of pre-rendered pages.
+pub fn head_html() -> String {
+    REGISTRY.with(|registry| {
+        let registry = registry.borrow();
+        let mut html = String::new();
+        if let Some(title) = registry.title() {
+            write!(html, "<title>{}</title>", escape(title)).unwrap();
+        }
+        for m in registry.meta() {
+            write!(
+                html,
+                r#"<meta {} {}="{}" content="{}">"#,
+                MANAGED_ATTRIBUTE,
+                m.attribute,
+                escape(&m.key),
+                escape(&m.content)
+            )
+            .unwrap();
+        }
+        html
+    })
+}
+
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
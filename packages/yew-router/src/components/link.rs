@@ -1,4 +1,4 @@
-use crate::{service, Routable};
+use crate::{preload, service, Routable};
 use std::marker::PhantomData;
 use yew::prelude::*;
 
@@ -14,12 +14,16 @@ pub struct LinkProps<R: Routable + Clone + PartialEq> {
 }
 
 /// A wrapper around `<a>` tag to be used with [`Router`](crate::Router)
+///
+/// Hovering or focusing the link starts loading the data of its route, see
+/// [`preload`](crate::preload).
 pub struct Link<R: Routable + Clone + PartialEq + 'static> {
     _data: PhantomData<R>,
 }
 
 pub enum Msg {
     OnClick,
+    Preload,
 }
 
 impl<R: Routable + Clone + PartialEq + 'static> Component for Link<R> {
@@ -36,6 +40,10 @@ impl<R: Routable + Clone + PartialEq + 'static> Component for Link<R> {
                 service::push_route(ctx.props().route.clone());
                 false
             }
+            Msg::Preload => {
+                preload::run(&ctx.props().route.to_path());
+                false
+            }
         }
     }
 
@@ -47,6 +55,8 @@ impl<R: Routable + Clone + PartialEq + 'static> Component for Link<R> {
                     e.prevent_default();
                     Msg::OnClick
                 })}
+                onmouseenter={ctx.link().callback(|_| Msg::Preload)}
+                onfocus={ctx.link().callback(|_| Msg::Preload)}
             >
                 { ctx.props().children.clone() }
             </a>
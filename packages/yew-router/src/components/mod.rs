@@ -1,4 +1,8 @@
 //! Components to interface with [Router][crate::Router].
 
+mod form;
+mod head;
 mod link;
+pub use form::*;
+pub use head::*;
 pub use link::*;
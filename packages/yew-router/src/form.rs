@@ -0,0 +1,204 @@
+//! Handling of native form submissions inside the app.
+//!
+//! Forms are rendered as plain `<form>` elements with an `action` URL, e.g. with
+//! [`Form`](crate::components::Form), so they still submit to the server while the app is not
+//! loaded or if it fails to load. Once handlers are registered with [`register_form_handler`] or
+//! [`use_form_handler`], submissions to a path matching a route of the handler are intercepted:
+//! the fields are deserialized from the form data into a typed struct and passed to the handler
+//! instead of being sent.
+//!
+//! Submissions are left to the browser if no handler matches, the fields don't deserialize, the
+//! form targets another window or origin, or a listener of the form prevented the default
+//! action already. File inputs are submitted by their file name.
+
+use crate::utils::{base_url, route_matcher};
+use crate::Routable;
+use gloo::events::EventListener;
+use serde::de::DeserializeOwned;
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+use wasm_bindgen::JsCast;
+use web_sys::{FormData, HtmlElement, HtmlFormElement, SubmitEvent, Url, UrlSearchParams};
+use yew::functional::{use_effect_with_deps, use_ref};
+
+/// Handles a submission to a path with the url-encoded data. Returns `false` if it didn't.
+type Handler = Rc<dyn Fn(&str, &str) -> bool>;
+
+thread_local! {
+    static HANDLERS: RefCell<Vec<(usize, Handler)>> = RefCell::new(Vec::new());
+    static NEXT_ID: Cell<usize> = Cell::new(0);
+    /// Listens to submissions while there are handlers
+    static LISTENER: RefCell<Option<EventListener>> = RefCell::new(None);
+}
+
+/// Registers `handler`, which is called with the route and the data of forms submitted to a path
+/// of `R`, until the returned handle is dropped.
+///
+/// # Example
+/// ```rust
+/// use serde::Deserialize;
+/// use yew_router::form::register_form_handler;
+/// use yew_router::prelude::*;
+///
+/// #[derive(Debug, Clone, PartialEq, Routable)]
+/// enum Route {
+///     #[at("/post/:id/comments")]
+///     Comments { id: u32 },
+/// }
+///
+/// #[derive(Deserialize)]
+/// struct Comment {
+///     author: String,
+///     text: String,
+/// }
+///
+/// let _handle = register_form_handler(|route: Route, comment: Comment| {
+///     // post the comment
+/// });
+/// ```
+pub fn register_form_handler<R, T, F>(handler: F) -> FormHandlerHandle
+where
+    R: Routable + 'static,
+    T: DeserializeOwned + 'static,
+    F: Fn(R, T) + 'static,
+{
+    let matcher = route_matcher::<R>();
+    let handler: Handler = Rc::new(move |path, data| {
+        let route = match matcher(path) {
+            Some(route) => route,
+            None => return false,
+        };
+        match serde_urlencoded::from_str(data) {
+            Ok(data) => {
+                handler(route, data);
+                true
+            }
+            Err(err) => {
+                weblog::console_warn!("form data doesn't match its handler:", err.to_string());
+                false
+            }
+        }
+    });
+
+    let id = NEXT_ID.with(|next_id| next_id.replace(next_id.get() + 1));
+    HANDLERS.with(|handlers| handlers.borrow_mut().push((id, handler)));
+    LISTENER.with(|listener| {
+        listener.borrow_mut().get_or_insert_with(|| {
+            EventListener::new(&yew::utils::document(), "submit", |e| {
+                intercept(e.unchecked_ref())
+            })
+        });
+    });
+    FormHandlerHandle { id }
+}
+
+/// Handle of a handler registered with [`register_form_handler`]. The handler is removed when
+/// the handle is dropped.
+#[derive(Debug)]
+pub struct FormHandlerHandle {
+    id: usize,
+}
+
+impl Drop for FormHandlerHandle {
+    fn drop(&mut self) {
+        let empty = HANDLERS.with(|handlers| {
+            let mut handlers = handlers.borrow_mut();
+            handlers.retain(|(id, _)| *id != self.id);
+            handlers.is_empty()
+        });
+        if empty {
+            LISTENER.with(|listener| listener.borrow_mut().take());
+        }
+    }
+}
+
+/// Registers `handler` with [`register_form_handler`] while the component is mounted. The
+/// latest `handler` is called, so it may capture the current state.
+pub fn use_form_handler<R, T, F>(handler: F)
+where
+    R: Routable + 'static,
+    T: DeserializeOwned + 'static,
+    F: Fn(R, T) + 'static,
+{
+    let current = use_ref(|| None::<Rc<dyn Fn(R, T)>>);
+    *current.borrow_mut() = Some(Rc::new(handler));
+    use_effect_with_deps(
+        move |_| {
+            let handle = register_form_handler(move |route, data| {
+                let handler = current.borrow().clone();
+                if let Some(handler) = handler {
+                    handler(route, data);
+                }
+            });
+            move || drop(handle)
+        },
+        (),
+    );
+}
+
+/// Returns the path of the action of `form` without the base URL, if it is in the app
+fn action_path(form: &HtmlFormElement) -> Option<String> {
+    let target = form.target();
+    if !target.is_empty() && target != "_self" {
+        return None;
+    }
+    let url = Url::new(&form.action()).ok()?;
+    let location = yew::utils::window().location();
+    if url.origin() != location.origin().ok()? {
+        return None;
+    }
+    let path = url.pathname();
+    match base_url() {
+        Some(base) => path.strip_prefix(&base).map(str::to_owned),
+        None => Some(path),
+    }
+}
+
+/// Returns the fields of `form` url-encoded, including the button that submitted it
+fn encode(form: &HtmlFormElement, submitter: Option<HtmlElement>) -> Option<String> {
+    let data = FormData::new_with_form(form).ok()?;
+    if let Some(submitter) = submitter {
+        let name = submitter.get_attribute("name").unwrap_or_default();
+        if !name.is_empty() {
+            let value = js_sys::Reflect::get(&submitter, &"value".into())
+                .ok()
+                .and_then(|value| value.as_string())
+                .unwrap_or_default();
+            data.append_with_str(&name, &value).ok()?;
+        }
+    }
+    let params = UrlSearchParams::new_with_str_sequence_sequence(&data).ok()?;
+    Some(params.to_string().into())
+}
+
+fn intercept(e: &SubmitEvent) {
+    if e.default_prevented() {
+        return;
+    }
+    let form = match e
+        .target()
+        .and_then(|t| t.dyn_into::<HtmlFormElement>().ok())
+    {
+        Some(form) => form,
+        None => return,
+    };
+    if form.method() == "dialog" {
+        return;
+    }
+    let (path, data) = match (action_path(&form), encode(&form, e.submitter())) {
+        (Some(path), Some(data)) => (path, data),
+        _ => return,
+    };
+
+    let handlers: Vec<Handler> = HANDLERS.with(|handlers| {
+        handlers
+            .borrow()
+            .iter()
+            .map(|(_, handler)| Rc::clone(handler))
+            .collect()
+    });
+    // The first handler that takes the submission handles it
+    if handlers.iter().any(|handler| handler(&path, &data)) {
+        e.prevent_default();
+    }
+}
@@ -0,0 +1,161 @@
+//! Guards that can hold or cancel navigations.
+//!
+//! Navigations made with [`push_route`](crate::push_route) and [`Link`](crate::prelude::Link)
+//! wait for every registered guard and only proceed if all of them allow it. Navigations with
+//! the back and forward buttons of the browser are not guarded.
+
+use std::cell::{Cell, RefCell};
+use std::future::Future;
+use std::pin::Pin;
+use std::rc::Rc;
+use wasm_bindgen_futures::spawn_local;
+use yew::dialog::{DialogHandle, DialogOptions, DialogResult, Dialogs};
+use yew::functional::{use_context, use_effect_with_deps};
+use yew::{html, Callback, Html};
+
+type Guard = Rc<dyn Fn(&str) -> Pin<Box<dyn Future<Output = bool>>>>;
+
+thread_local! {
+    static GUARDS: RefCell<Vec<(usize, Guard)>> = RefCell::new(Vec::new());
+    static NEXT_ID: Cell<usize> = Cell::new(0);
+    /// Number of the latest navigation, which supersedes the ones still waiting for guards
+    static NAVIGATION: Cell<u64> = Cell::new(0);
+}
+
+/// Registers `guard`, which is called with the path of every navigation until the returned
+/// handle is dropped. The navigation proceeds if the future it returns resolves to `true`.
+pub fn register_guard<F, Fut>(guard: F) -> GuardHandle
+where
+    F: Fn(&str) -> Fut + 'static,
+    Fut: Future<Output = bool> + 'static,
+{
+    let id = NEXT_ID.with(|next_id| next_id.replace(next_id.get() + 1));
+    let guard: Guard = Rc::new(move |path| Box::pin(guard(path)));
+    GUARDS.with(|guards| guards.borrow_mut().push((id, guard)));
+    GuardHandle { id }
+}
+
+/// Handle of a guard registered with [`register_guard`]. The guard is removed when the handle is
+/// dropped.
+#[derive(Debug)]
+pub struct GuardHandle {
+    id: usize,
+}
+
+impl Drop for GuardHandle {
+    fn drop(&mut self) {
+        GUARDS.with(|guards| guards.borrow_mut().retain(|(id, _)| *id != self.id));
+    }
+}
+
+/// Calls `navigate` if the guards allow navigating to `path`, which excludes the base URL.
+///
+/// A navigation still waiting for its guards when another one starts is dropped, even if they
+/// allow it later, so only the latest navigation can proceed.
+pub(crate) fn check(path: String, navigate: impl FnOnce() + 'static) {
+    let navigation = NAVIGATION.with(|latest| {
+        latest.set(latest.get() + 1);
+        latest.get()
+    });
+    let is_latest = move || NAVIGATION.with(|latest| latest.get() == navigation);
+    let guards: Vec<Guard> = GUARDS.with(|guards| {
+        guards
+            .borrow()
+            .iter()
+            .map(|(_, guard)| Rc::clone(guard))
+            .collect()
+    });
+    if guards.is_empty() {
+        navigate();
+        return;
+    }
+
+    spawn_local(async move {
+        for guard in guards {
+            if !guard(&path).await || !is_latest() {
+                return;
+            }
+        }
+        navigate();
+    });
+}
+
+/// Registers a guard that asks the user to confirm every navigation in a dialog of the
+/// closest [`DialogProvider`](yew::dialog::DialogProvider).
+///
+/// `render` renders the content of the dialog, which closes it with `true` to leave the page.
+/// Dismissing the dialog cancels the navigation.
+pub fn confirm_navigation(
+    dialogs: Dialogs,
+    render: impl Fn(&DialogHandle<bool>) -> Html + 'static,
+) -> GuardHandle {
+    let render = Rc::new(render);
+    register_guard(move |_path| {
+        let render = Rc::clone(&render);
+        let result = dialogs.open(DialogOptions::default(), move |dialog| render(dialog));
+        async move { result.await == DialogResult::Closed(true) }
+    })
+}
+
+/// Renders a prompt with `message` and buttons to leave or stay on the page
+pub fn render_confirm_prompt(message: &str, dialog: &DialogHandle<bool>) -> Html {
+    let leave = {
+        let dialog = dialog.clone();
+        Callback::from(move |_| dialog.close(true))
+    };
+    let stay = {
+        let dialog = dialog.clone();
+        Callback::from(move |_| dialog.close(false))
+    };
+    html! {
+        <>
+            <p>{ message }</p>
+            <button onclick={stay}>{ "Stay" }</button>
+            <button onclick={leave}>{ "Leave" }</button>
+        </>
+    }
+}
+
+/// Asks the user to confirm leaving the page with `message` while `enabled` is `true`, e.g.
+/// while a form has unsaved changes. The prompt is rendered with [`render_confirm_prompt`] by
+/// the closest [`DialogProvider`](yew::dialog::DialogProvider).
+///
+/// # Example
+/// ```rust
+/// # use yew::prelude::*;
+/// use yew::functional::use_state;
+/// use yew_router::guard::use_confirm_navigation;
+///
+/// #[function_component(Editor)]
+/// fn editor() -> Html {
+///     let dirty = use_state(|| false);
+///     use_confirm_navigation(*dirty, "Discard your changes?");
+///     let oninput = {
+///         let dirty = dirty.clone();
+///         Callback::from(move |_| dirty.set(true))
+///     };
+///     html! { <textarea {oninput} /> }
+/// }
+/// ```
+pub fn use_confirm_navigation(enabled: bool, message: &str) {
+    let dialogs = use_context::<Dialogs>();
+    use_effect_with_deps(
+        move |(enabled, message)| {
+            let guard = match (enabled, dialogs) {
+                (true, Some(dialogs)) => {
+                    let message = message.clone();
+                    Some(confirm_navigation(dialogs, move |dialog| {
+                        render_confirm_prompt(&message, dialog)
+                    }))
+                }
+                (true, None) => {
+                    weblog::console_warn!("use_confirm_navigation needs a DialogProvider");
+                    None
+                }
+                (false, _) => None,
+            };
+            move || drop(guard)
+        },
+        (enabled, message.to_owned()),
+    );
+}
@@ -59,6 +59,9 @@ extern crate self as yew_router;
 #[path = "macro_helpers.rs"]
 pub mod __macro;
 pub mod components;
+pub mod form;
+pub mod guard;
+pub mod preload;
 mod routable;
 pub mod router;
 mod service;
@@ -66,7 +69,7 @@ pub(crate) mod utils;
 
 pub use service::*;
 
-pub use routable::Routable;
+pub use routable::{Routable, RouteInfo};
 pub use router::{RenderFn, Router};
 
 pub mod prelude {
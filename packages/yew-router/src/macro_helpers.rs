@@ -8,18 +8,21 @@ pub type Router = route_recognizer::Router<String>;
 pub fn build_router<R: Routable>() -> Router {
     let base = base_url();
     let mut router = Router::new();
-    R::routes().iter().for_each(|path| {
-        match &base {
-            Some(base) => {
-                let route = format!("{}{}", base, path);
-                let route = strip_slash_suffix(&route);
-                router.add(route, path.to_string());
-            }
-            _ => {
-                router.add(path, path.to_string());
-            }
-        };
-    });
+    R::routes()
+        .iter()
+        .map(|route| route.path())
+        .for_each(|path| {
+            match &base {
+                Some(base) => {
+                    let route = format!("{}{}", base, path);
+                    let route = strip_slash_suffix(&route);
+                    router.add(route, path.to_string());
+                }
+                _ => {
+                    router.add(path, path.to_string());
+                }
+            };
+        });
 
     router
 }
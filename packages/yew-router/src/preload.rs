@@ -0,0 +1,125 @@
+//! Data of routes loaded when a navigation starts.
+//!
+//! A [`Preload`] whose input is a route is registered with [`register_preload`] or
+//! [`use_route_preload`]. Navigations made with [`push_route`](crate::push_route) and
+//! [`Link`](crate::prelude::Link) start loading the data of their route once the
+//! [guards](crate::guard) allowed them, before the page is rendered. Links also start it when
+//! they are hovered or focused.
+//!
+//! The page renders a [`Preloaded`](yew::preload::Preloaded) with its route, which takes the
+//! data that is loaded or still loading instead of starting another load.
+
+use crate::utils::route_matcher;
+use crate::Routable;
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+use yew::functional::use_effect_with_deps;
+use yew::preload::{preload, Preload};
+
+type Preloader = Rc<dyn Fn(&str)>;
+
+thread_local! {
+    static PRELOADERS: RefCell<Vec<(usize, Preloader)>> = RefCell::new(Vec::new());
+    static NEXT_ID: Cell<usize> = Cell::new(0);
+}
+
+/// Registers `P` to load the data of routes of its input type until the returned handle is
+/// dropped.
+///
+/// # Example
+/// ```rust
+/// # use std::{future::Future, pin::Pin};
+/// use yew::preload::Preload;
+/// use yew_router::prelude::*;
+/// use yew_router::preload::register_preload;
+///
+/// # async fn fetch_post(id: u32) -> String { id.to_string() }
+/// #[derive(Debug, Clone, PartialEq, Routable)]
+/// enum Route {
+///     #[at("/")]
+///     Home,
+///     #[at("/post/:id")]
+///     Post { id: u32 },
+/// }
+///
+/// struct RouteData;
+///
+/// impl Preload for RouteData {
+///     type Input = Route;
+///     type Data = Option<String>;
+///
+///     fn preload(route: &Route) -> Pin<Box<dyn Future<Output = Option<String>>>> {
+///         let route = route.clone();
+///         Box::pin(async move {
+///             match route {
+///                 Route::Post { id } => Some(fetch_post(id).await),
+///                 Route::Home => None,
+///             }
+///         })
+///     }
+/// }
+///
+/// let _handle = register_preload::<RouteData>();
+/// ```
+pub fn register_preload<P>() -> PreloadHandle
+where
+    P: Preload,
+    P::Input: Routable,
+{
+    let matcher = route_matcher::<P::Input>();
+    let preloader: Preloader = Rc::new(move |path| {
+        if let Some(route) = matcher(path) {
+            preload::<P>(route);
+        }
+    });
+
+    let id = NEXT_ID.with(|next_id| next_id.replace(next_id.get() + 1));
+    PRELOADERS.with(|preloaders| preloaders.borrow_mut().push((id, preloader)));
+    PreloadHandle { id }
+}
+
+/// Handle of a preloader registered with [`register_preload`]. The preloader is removed when the
+/// handle is dropped.
+#[derive(Debug)]
+pub struct PreloadHandle {
+    id: usize,
+}
+
+impl Drop for PreloadHandle {
+    fn drop(&mut self) {
+        PRELOADERS.with(|preloaders| preloaders.borrow_mut().retain(|(id, _)| *id != self.id));
+    }
+}
+
+/// Registers `P` with [`register_preload`] while the component is mounted
+pub fn use_route_preload<P>()
+where
+    P: Preload,
+    P::Input: Routable,
+{
+    use_effect_with_deps(
+        |_| {
+            let handle = register_preload::<P>();
+            move || drop(handle)
+        },
+        (),
+    );
+}
+
+/// Starts loading the data of the routes matching `url`, which excludes the base URL
+pub(crate) fn run(url: &str) {
+    let path = url
+        .split(|c| c == '?' || c == '#')
+        .next()
+        .unwrap_or_default();
+    let preloaders: Vec<Preloader> = PRELOADERS.with(|preloaders| {
+        preloaders
+            .borrow()
+            .iter()
+            .map(|(_, preloader)| Rc::clone(preloader))
+            .collect()
+    });
+    for preloader in preloaders {
+        preloader(path);
+    }
+}
@@ -20,8 +20,10 @@ pub trait Routable: Sized + Clone {
     /// Converts the route to a string that can passed to the history API.
     fn to_path(&self) -> String;
 
-    /// Lists all the available routes
-    fn routes() -> Vec<&'static str>;
+    /// Lists all the available routes, in the order of the variants.
+    ///
+    /// This can be used to enumerate the pages of an app, e.g. to generate a sitemap.
+    fn routes() -> &'static [RouteInfo];
 
     /// The route to redirect to on 404
     fn not_found_route() -> Option<Self>;
@@ -37,3 +39,72 @@ pub trait Routable: Sized + Clone {
     /// Called when [`Router`](crate::Router) is destroyed.
     fn cleanup() {}
 }
+
+/// A route of a [`Routable`] type, as listed by [`Routable::routes`].
+///
+/// Metadata is declared with the `meta` attribute of the derive macro:
+///
+/// ```
+/// # use yew_router::Routable;
+/// #[derive(Debug, Clone, Copy, PartialEq, Routable)]
+/// enum Route {
+///     #[at("/")]
+///     #[meta(title = "Home", changefreq = "daily")]
+///     Home,
+///     #[at("/post/:id")]
+///     Post { id: u32 },
+/// }
+///
+/// let home = &Route::routes()[0];
+/// assert_eq!(home.path(), "/");
+/// assert_eq!(home.meta("changefreq"), Some("daily"));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RouteInfo {
+    path: &'static str,
+    not_found: bool,
+    meta: &'static [(&'static str, &'static str)],
+}
+
+impl RouteInfo {
+    #[doc(hidden)]
+    pub const fn __new(
+        path: &'static str,
+        not_found: bool,
+        meta: &'static [(&'static str, &'static str)],
+    ) -> Self {
+        Self {
+            path,
+            not_found,
+            meta,
+        }
+    }
+
+    /// Returns the pattern of the route, e.g. `/post/:id`
+    pub fn path(&self) -> &'static str {
+        self.path
+    }
+
+    /// Returns whether the route has parameters, so it doesn't stand for a single page
+    pub fn is_dynamic(&self) -> bool {
+        self.path.split('/').any(|segment| segment.starts_with(':'))
+    }
+
+    /// Returns whether this is the route marked with `not_found`
+    pub fn is_not_found(&self) -> bool {
+        self.not_found
+    }
+
+    /// Returns the metadata value of `key`
+    pub fn meta(&self, key: &str) -> Option<&'static str> {
+        self.meta
+            .iter()
+            .find(|(k, _)| *k == key)
+            .map(|(_, value)| *value)
+    }
+
+    /// Returns all metadata of the route, in declaration order
+    pub fn meta_entries(&self) -> &'static [(&'static str, &'static str)] {
+        self.meta
+    }
+}
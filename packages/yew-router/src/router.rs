@@ -2,7 +2,8 @@
 
 use crate::Routable;
 use gloo::events::EventListener;
-use std::marker::PhantomData;
+use gloo::timers::callback::Timeout;
+use std::collections::HashMap;
 use std::rc::Rc;
 use yew::prelude::*;
 
@@ -37,25 +38,58 @@ impl<T> PartialEq for RenderFn<T> {
 pub struct RouterProps<R> {
     /// Callback which returns [`Html`] to be rendered for the current route.
     pub render: RenderFn<R>,
+    /// Duration of the transition between routes. If it isn't 0, the routes are rendered in
+    /// elements of the class `yew-route` and the previous route is kept for this long after a
+    /// navigation.
+    #[prop_or(0)]
+    pub transition_ms: u32,
 }
 
 impl<R> Clone for RouterProps<R> {
     fn clone(&self) -> Self {
         Self {
             render: self.render.clone(),
+            transition_ms: self.transition_ms,
         }
     }
 }
 
 impl<R> PartialEq for RouterProps<R> {
     fn eq(&self, other: &Self) -> bool {
-        self.render.eq(&other.render)
+        self.render.eq(&other.render) && self.transition_ms == other.transition_ms
     }
 }
 
 #[doc(hidden)]
 pub enum Msg {
     ReRender,
+    Entered(usize),
+    Remove(usize),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Phase {
+    Entering,
+    Shown,
+    Leaving,
+}
+
+impl Phase {
+    fn class(self) -> &'static str {
+        match self {
+            Self::Entering => "yew-route-entering",
+            Self::Shown => "yew-route-shown",
+            Self::Leaving => "yew-route-leaving",
+        }
+    }
+}
+
+/// A route rendered by the router, which may be leaving
+struct Page<R> {
+    key: usize,
+    pathname: String,
+    route: Option<R>,
+    phase: Phase,
 }
 
 /// The router component.
@@ -64,11 +98,107 @@ pub enum Msg {
 /// If such a route is provided, it redirects to the specified route.
 /// Otherwise `html! {}` is rendered and a message is logged to console
 /// stating that no route can be matched.
+///
+/// Paths are matched under the [base URL](yew::AppConfig::base_url) of the app, if it was mounted
+/// with one, or else the `href` of the `<base>` element of the document.
 /// See the [crate level document][crate] for more information.
+///
+/// # Transitions
+///
+/// With a [`transition_ms`](RouterProps::transition_ms), the previous route stays mounted next to
+/// the new one while they transition. Their elements have classes following their lifecycle for
+/// CSS transitions: `yew-route-entering` right after a navigation, `yew-route-shown` a moment
+/// later and `yew-route-leaving` until the previous route is removed. Navigations to the current
+/// path don't transition.
+///
+/// ```rust
+/// # use yew::prelude::*;
+/// # use yew_router::prelude::*;
+/// # #[derive(Debug, Clone, Copy, PartialEq, Routable)]
+/// # enum Route {
+/// #     #[at("/")]
+/// #     Home,
+/// # }
+/// # fn switch(_route: &Route) -> Html { html! {} }
+/// # fn view() -> Html {
+/// // .yew-route { transition: opacity 300ms; }
+/// // .yew-route-entering, .yew-route-leaving { opacity: 0; }
+/// // .yew-route-leaving { position: absolute; }
+/// html! {
+///     <Router<Route> render={Router::render(switch)} transition_ms=300 />
+/// }
+/// # }
+/// ```
 pub struct Router<R: Routable + 'static> {
     #[allow(dead_code)] // only exists to drop listener on component drop
     route_listener: EventListener,
-    _data: PhantomData<R>,
+    /// The current route and the leaving ones, in the order they were navigated to
+    pages: Vec<Page<R>>,
+    next_key: usize,
+    timers: HashMap<usize, Timeout>,
+}
+
+impl<R: Routable + 'static> Router<R> {
+    fn page(&mut self, pathname: String, phase: Phase) -> Page<R> {
+        let key = self.next_key;
+        self.next_key += 1;
+        Page {
+            key,
+            route: R::recognize(&pathname),
+            pathname,
+            phase,
+        }
+    }
+
+    /// Calls `update` with `msg` after `ms`
+    fn schedule(&mut self, ctx: &Context<Self>, key: usize, ms: u32, msg: Msg) {
+        let link = ctx.link().clone();
+        let timeout = Timeout::new(ms, move || link.send_message(msg));
+        self.timers.insert(key, timeout);
+    }
+
+    fn navigate(&mut self, ctx: &Context<Self>) {
+        let pathname = yew::utils::window().location().pathname().unwrap();
+        let transition_ms = ctx.props().transition_ms;
+        if transition_ms == 0 {
+            self.timers.clear();
+            self.pages.clear();
+            let page = self.page(pathname, Phase::Shown);
+            self.pages.push(page);
+            return;
+        }
+        if self
+            .pages
+            .last()
+            .map_or(false, |page| page.pathname == pathname)
+        {
+            return;
+        }
+
+        let leaving: Vec<_> = self
+            .pages
+            .iter_mut()
+            .filter(|page| page.phase != Phase::Leaving)
+            .map(|page| {
+                page.phase = Phase::Leaving;
+                page.key
+            })
+            .collect();
+        for key in leaving {
+            self.schedule(ctx, key, transition_ms, Msg::Remove(key));
+        }
+        let page = self.page(pathname, Phase::Entering);
+        // The entering class has to be rendered before it is replaced, so it transitions
+        self.schedule(ctx, page.key, 20, Msg::Entered(page.key));
+        self.pages.push(page);
+    }
+
+    fn render_page(&self, ctx: &Context<Self>, page: &Page<R>) -> Html {
+        match &page.route {
+            Some(route) => (ctx.props().render.0)(route),
+            None => html! {},
+        }
+    }
 }
 
 impl<R> Component for Router<R>
@@ -79,34 +209,71 @@ where
     type Properties = RouterProps<R>;
 
     fn create(ctx: &Context<Self>) -> Self {
+        // The base URL the app was mounted with takes precedence over the `<base>` element
+        if let Some(base) = ctx.link().app_config().base_url() {
+            crate::utils::set_base_url(base);
+        }
         let link = ctx.link().clone();
         let route_listener = EventListener::new(&yew::utils::window(), "popstate", move |_| {
             link.send_message(Msg::ReRender)
         });
 
-        Self {
+        let mut router = Self {
             route_listener,
-            _data: PhantomData,
-        }
+            pages: Vec::new(),
+            next_key: 0,
+            timers: HashMap::new(),
+        };
+        let pathname = yew::utils::window().location().pathname().unwrap();
+        let page = router.page(pathname, Phase::Shown);
+        router.pages.push(page);
+        router
     }
 
-    fn update(&mut self, _ctx: &Context<Self>, msg: Self::Message) -> bool {
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
         match msg {
-            Msg::ReRender => true,
+            Msg::ReRender => {
+                self.navigate(ctx);
+                true
+            }
+            Msg::Entered(key) => {
+                self.timers.remove(&key);
+                match self.pages.iter_mut().find(|page| page.key == key) {
+                    Some(page) if page.phase == Phase::Entering => {
+                        page.phase = Phase::Shown;
+                        true
+                    }
+                    _ => false,
+                }
+            }
+            Msg::Remove(key) => {
+                self.timers.remove(&key);
+                self.pages.retain(|page| page.key != key);
+                true
+            }
         }
     }
 
     fn view(&self, ctx: &Context<Self>) -> Html {
-        let pathname = yew::utils::window().location().pathname().unwrap();
-        let route = R::recognize(&pathname);
+        let current = match self.pages.last() {
+            Some(page) => page,
+            None => return html! {},
+        };
+        if current.route.is_none() {
+            weblog::console_warn!("no route matched");
+        }
+        if ctx.props().transition_ms == 0 {
+            return self.render_page(ctx, current);
+        }
 
-        match route {
-            Some(route) => (ctx.props().render.0)(&route),
-            None => {
-                weblog::console_warn!("no route matched");
-                html! {}
+        let pages = self.pages.iter().map(|page| {
+            html! {
+                <div key={page.key.to_string()} class={classes!("yew-route", page.phase.class())}>
+                    { self.render_page(ctx, page) }
+                </div>
             }
-        }
+        });
+        html! { <>{ for pages }</> }
     }
 
     fn destroy(&mut self, _ctx: &Context<Self>) {
@@ -1,5 +1,6 @@
 use crate::utils::base_url;
 use crate::Routable;
+use crate::{guard, preload};
 use gloo::events::EventListener;
 use serde::{Deserialize, Serialize};
 use wasm_bindgen::JsValue;
@@ -33,6 +34,13 @@ where
 }
 
 fn push_impl(url: String) {
+    guard::check(url.clone(), move || {
+        preload::run(&url);
+        navigate(url)
+    });
+}
+
+fn navigate(url: String) {
     let history = yew::utils::window().history().expect("no history");
     let base = base_url();
     let path = match base {
@@ -1,3 +1,4 @@
+use crate::Routable;
 use std::cell::RefCell;
 use wasm_bindgen::JsCast;
 
@@ -21,6 +22,13 @@ pub fn base_url() -> Option<String> {
     BASE_URL.with(|it| it.borrow().as_ref().map(|it| it.to_string()))
 }
 
+/// Uses `base` instead of the `<base>` element of the document, e.g. the base URL of the app
+pub(crate) fn set_base_url(base: &str) {
+    BASE_URL_LOADED.call_once(|| ());
+    let base = strip_slash_suffix(base);
+    BASE_URL.with(|val| *val.borrow_mut() = Some(base.to_owned()).filter(|b| !b.is_empty()));
+}
+
 pub fn fetch_base_url() -> Option<String> {
     match yew::utils::document().query_selector("base[href]") {
         Ok(Some(base)) => {
@@ -41,6 +49,21 @@ pub fn fetch_base_url() -> Option<String> {
     }
 }
 
+/// Returns a function matching paths without the base URL with the routes of `R`.
+///
+/// Unlike [`Routable::recognize`], it doesn't fall back to the not found route and leaves the
+/// current route alone.
+pub(crate) fn route_matcher<R: Routable>() -> impl Fn(&str) -> Option<R> {
+    let mut router = route_recognizer::Router::new();
+    for route in R::routes() {
+        router.add(route.path(), route.path().to_owned());
+    }
+    move |path| {
+        let matched = router.recognize(strip_slash_suffix(path)).ok()?;
+        R::from_path(matched.handler(), &matched.params().into_iter().collect())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use serde::Serialize;
@@ -0,0 +1,59 @@
+use serde::Deserialize;
+use std::cell::RefCell;
+use std::rc::Rc;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+use yew_router::form::register_form_handler;
+use yew_router::prelude::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[derive(Debug, Clone, Copy, PartialEq, Routable)]
+enum Routes {
+    #[at("/post/:id/comments")]
+    Comments { id: u32 },
+}
+
+#[derive(Debug, PartialEq, Deserialize)]
+struct Comment {
+    text: String,
+    action: String,
+}
+
+#[test]
+fn submission_is_handled() {
+    let document = yew::utils::document();
+    let root = document.create_element("div").unwrap();
+    root.set_inner_html(
+        r#"<form action="/post/3/comments" method="post">
+            <input name="text" value="Nice post">
+            <button id="publish" name="action" value="publish">Publish</button>
+        </form>"#,
+    );
+    document.body().unwrap().append_child(&root).unwrap();
+
+    let submitted = Rc::new(RefCell::new(None));
+    let _handle = {
+        let submitted = Rc::clone(&submitted);
+        register_form_handler(move |route: Routes, comment: Comment| {
+            *submitted.borrow_mut() = Some((route, comment));
+        })
+    };
+    document
+        .get_element_by_id("publish")
+        .unwrap()
+        .unchecked_into::<web_sys::HtmlElement>()
+        .click();
+
+    assert_eq!(
+        submitted.borrow_mut().take(),
+        Some((
+            Routes::Comments { id: 3 },
+            Comment {
+                text: "Nice post".to_owned(),
+                action: "publish".to_owned(),
+            }
+        ))
+    );
+    root.remove();
+}
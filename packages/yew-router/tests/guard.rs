@@ -0,0 +1,78 @@
+use wasm_bindgen_futures::JsFuture;
+use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+use yew_router::guard::register_guard;
+use yew_router::prelude::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[derive(Debug, Clone, Copy, PartialEq, Routable)]
+enum Routes {
+    #[at("/")]
+    Home,
+    #[at("/slow")]
+    Slow,
+    #[at("/fast")]
+    Fast,
+}
+
+async fn sleep(ms: i32) {
+    let promise = js_sys::Promise::new(&mut |resolve, _| {
+        yew::utils::window()
+            .set_timeout_with_callback_and_timeout_and_arguments_0(&resolve, ms)
+            .unwrap();
+    });
+    JsFuture::from(promise).await.unwrap();
+}
+
+fn path() -> String {
+    yew::utils::window().location().pathname().unwrap()
+}
+
+async fn go_home() {
+    yew_router::push_route(Routes::Home);
+    sleep(10).await;
+    assert_eq!(path(), "/");
+}
+
+#[test]
+async fn allowed_navigations_proceed() {
+    go_home().await;
+    let _guard = register_guard(|_| async { true });
+    yew_router::push_route(Routes::Fast);
+    sleep(10).await;
+    assert_eq!(path(), "/fast");
+}
+
+#[test]
+async fn denied_navigations_are_cancelled() {
+    go_home().await;
+    let guard = register_guard(|path| {
+        let allow = path != "/fast";
+        async move { allow }
+    });
+    yew_router::push_route(Routes::Fast);
+    sleep(10).await;
+    assert_eq!(path(), "/");
+
+    // The guard is removed with its handle
+    drop(guard);
+    yew_router::push_route(Routes::Fast);
+    sleep(10).await;
+    assert_eq!(path(), "/fast");
+}
+
+#[test]
+async fn only_the_latest_navigation_proceeds() {
+    go_home().await;
+    let _guard = register_guard(|path| {
+        let delay = if path == "/slow" { 50 } else { 10 };
+        async move {
+            sleep(delay).await;
+            true
+        }
+    });
+    yew_router::push_route(Routes::Slow);
+    yew_router::push_route(Routes::Fast);
+    sleep(100).await;
+    assert_eq!(path(), "/fast");
+}
@@ -0,0 +1,77 @@
+use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+use yew::prelude::*;
+use yew_router::components::{head_html, Head, Meta};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+struct Page {
+    show_post: bool,
+}
+
+impl Component for Page {
+    type Message = ();
+    type Properties = ();
+
+    fn create(_ctx: &Context<Self>) -> Self {
+        Self { show_post: true }
+    }
+
+    fn update(&mut self, _ctx: &Context<Self>, _msg: Self::Message) -> bool {
+        self.show_post = false;
+        true
+    }
+
+    fn view(&self, _ctx: &Context<Self>) -> Html {
+        let post = if self.show_post {
+            html! {
+                <Head
+                    title={"Post".to_string()}
+                    meta={vec![Meta::name("description", "A post")]}
+                />
+            }
+        } else {
+            html! {}
+        };
+        html! {
+            <>
+                <Head
+                    title={"Blog".to_string()}
+                    meta={vec![
+                        Meta::name("description", "All posts"),
+                        Meta::property("og:type", "website"),
+                    ]}
+                />
+                { post }
+            </>
+        }
+    }
+}
+
+fn description() -> Option<String> {
+    yew::utils::document()
+        .query_selector(r#"meta[name="description"]"#)
+        .unwrap()
+        .and_then(|meta| meta.get_attribute("content"))
+}
+
+#[test]
+fn last_mounted_head_wins_until_unmounted() {
+    let element = yew::utils::document().create_element("div").unwrap();
+    let app = yew::start_app_in_element::<Page>(element);
+
+    assert_eq!(yew::utils::document().title(), "Post");
+    assert_eq!(description().as_deref(), Some("A post"));
+    assert_eq!(
+        head_html(),
+        "<title>Post</title>\
+        <meta data-yew-head name=\"description\" content=\"A post\">\
+        <meta data-yew-head property=\"og:type\" content=\"website\">"
+    );
+
+    app.send_message(());
+    assert_eq!(yew::utils::document().title(), "Blog");
+    assert_eq!(description().as_deref(), Some("All posts"));
+
+    app.destroy();
+    assert_eq!(head_html(), "");
+}
@@ -0,0 +1,82 @@
+use wasm_bindgen_futures::JsFuture;
+use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+use yew::prelude::*;
+use yew_router::prelude::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[derive(Debug, Clone, Copy, PartialEq, Routable)]
+enum Routes {
+    #[at("/")]
+    Home,
+    #[at("/other")]
+    Other,
+}
+
+struct App;
+
+impl Component for App {
+    type Message = ();
+    type Properties = ();
+
+    fn create(_ctx: &Context<Self>) -> Self {
+        Self
+    }
+
+    fn view(&self, _ctx: &Context<Self>) -> Html {
+        let switch = Router::render(|routes| match routes {
+            Routes::Home => html! { <p>{ "Home" }</p> },
+            Routes::Other => html! { <p>{ "Other" }</p> },
+        });
+        html! { <Router<Routes> render={switch} transition_ms=100 /> }
+    }
+}
+
+async fn sleep(ms: i32) {
+    let promise = js_sys::Promise::new(&mut |resolve, _| {
+        yew::utils::window()
+            .set_timeout_with_callback_and_timeout_and_arguments_0(&resolve, ms)
+            .unwrap();
+    });
+    JsFuture::from(promise).await.unwrap();
+}
+
+fn pages(host: &web_sys::Element) -> Vec<(String, String)> {
+    let elements = host.query_selector_all(".yew-route").unwrap();
+    (0..elements.length())
+        .filter_map(|index| elements.get(index))
+        .map(|node| {
+            let element: web_sys::Element = wasm_bindgen::JsCast::unchecked_into(node);
+            (element.class_name(), element.text_content().unwrap())
+        })
+        .collect()
+}
+
+#[test]
+async fn previous_route_is_kept_while_transitioning() {
+    let host = yew::utils::document().create_element("div").unwrap();
+    yew::start_app_in_element::<App>(host.clone());
+    yew_router::push_route(Routes::Home);
+    sleep(150).await;
+
+    yew_router::push_route(Routes::Other);
+    assert_eq!(
+        pages(&host),
+        vec![
+            ("yew-route yew-route-leaving".to_owned(), "Home".to_owned()),
+            (
+                "yew-route yew-route-entering".to_owned(),
+                "Other".to_owned()
+            ),
+        ]
+    );
+
+    sleep(50).await;
+    assert_eq!(pages(&host)[1].0, "yew-route yew-route-shown");
+
+    sleep(100).await;
+    assert_eq!(
+        pages(&host),
+        vec![("yew-route yew-route-shown".to_owned(), "Other".to_owned())]
+    );
+}
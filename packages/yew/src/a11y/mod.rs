@@ -0,0 +1,3 @@
+//! Accessibility helpers.
+
+pub mod widgets;
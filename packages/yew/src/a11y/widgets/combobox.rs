@@ -0,0 +1,167 @@
+use super::{item_id, navigate, ItemState, Orientation, RenderItem};
+use crate::html::{Classes, TargetCast};
+use crate::virtual_dom::AttrValue;
+use crate::{html, Callback, Component, Context, Html, Properties};
+use web_sys::{HtmlInputElement, InputEvent, KeyboardEvent};
+
+/// Returns the id of the popup listbox of the [`Combobox`] with `id`
+pub fn combobox_listbox_id(id: &str) -> String {
+    format!("{}-listbox", id)
+}
+
+/// Props for [`Combobox`]
+#[derive(Clone, PartialEq, Properties)]
+pub struct ComboboxProps {
+    /// Id of the text input. The listbox gets an id derived with [`combobox_listbox_id`] and the
+    /// options ids derived from that with [`item_id`].
+    pub id: AttrValue,
+    /// Current text of the input
+    pub value: AttrValue,
+    /// Called with the text of the input when it is edited
+    pub oninput: Callback<String>,
+    /// Number of suggested options for the current text
+    pub len: usize,
+    /// Renders the content of an option
+    pub render: RenderItem,
+    /// Called with the index of an option chosen with Enter or a click
+    pub onselect: Callback<usize>,
+    /// Accessible name of the input
+    #[prop_or_default]
+    pub label: Option<AttrValue>,
+    /// Classes of the input element
+    #[prop_or_default]
+    pub class: Classes,
+    /// Classes of the listbox element
+    #[prop_or_default]
+    pub listbox_class: Classes,
+}
+
+#[doc(hidden)]
+pub enum ComboboxMsg {
+    Input(String),
+    Activate(usize),
+    Choose(usize),
+    Collapse,
+}
+
+/// A [combobox](https://www.w3.org/TR/wai-aria-practices-1.1/#combobox) with a text input and a
+/// popup listbox of suggestions.
+///
+/// The input keeps the focus and points to the active option with `aria-activedescendant`. The
+/// listbox expands when the text is edited or Down is pressed and collapses when an option is
+/// chosen or Escape is pressed.
+#[derive(Debug)]
+pub struct Combobox {
+    expanded: bool,
+    active: Option<usize>,
+}
+
+impl Component for Combobox {
+    type Message = ComboboxMsg;
+    type Properties = ComboboxProps;
+
+    fn create(_ctx: &Context<Self>) -> Self {
+        Self {
+            expanded: false,
+            active: None,
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            ComboboxMsg::Input(value) => {
+                self.expanded = true;
+                self.active = None;
+                ctx.props().oninput.emit(value);
+            }
+            ComboboxMsg::Activate(index) => {
+                self.expanded = true;
+                self.active = Some(index);
+            }
+            ComboboxMsg::Choose(index) => {
+                self.expanded = false;
+                self.active = None;
+                ctx.props().onselect.emit(index);
+            }
+            ComboboxMsg::Collapse => {
+                self.expanded = false;
+                self.active = None;
+            }
+        }
+        true
+    }
+
+    fn changed(&mut self, ctx: &Context<Self>) -> bool {
+        if self.active.map_or(false, |i| i >= ctx.props().len) {
+            self.active = None;
+        }
+        true
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let props = ctx.props();
+        let link = ctx.link();
+        let listbox_id = combobox_listbox_id(&props.id);
+        let expanded = self.expanded && props.len > 0;
+        let (active, len) = (self.active, props.len);
+        let onkeydown = link.batch_callback(move |e: KeyboardEvent| {
+            let msg = match e.key().as_str() {
+                "Enter" if expanded => ComboboxMsg::Choose(active?),
+                "Escape" if expanded => ComboboxMsg::Collapse,
+                key @ "ArrowDown" | key @ "ArrowUp" => {
+                    ComboboxMsg::Activate(navigate(key, active, len, Orientation::Vertical)?)
+                }
+                _ => return None,
+            };
+            e.prevent_default();
+            Some(msg)
+        });
+        let oninput = link.callback(|e: InputEvent| {
+            ComboboxMsg::Input(e.target_unchecked_into::<HtmlInputElement>().value())
+        });
+
+        html! {
+            <>
+                <input
+                    type="text"
+                    role="combobox"
+                    id={props.id.clone()}
+                    class={props.class.clone()}
+                    value={props.value.clone()}
+                    autocomplete="off"
+                    aria-label={props.label.clone()}
+                    aria-autocomplete="list"
+                    aria-expanded={expanded.to_string()}
+                    aria-controls={listbox_id.clone()}
+                    aria-activedescendant={self.active.filter(|_| expanded).map(|i| item_id(&listbox_id, i))}
+                    onblur={link.callback(|_| ComboboxMsg::Collapse)}
+                    {oninput}
+                    {onkeydown}
+                />
+                <ul
+                    role="listbox"
+                    id={listbox_id.clone()}
+                    class={props.listbox_class.clone()}
+                    hidden={!expanded}
+                >
+                    { for (0..if expanded { len } else { 0 }).map(|index| html! {
+                        <li
+                            role="option"
+                            id={item_id(&listbox_id, index)}
+                            aria-selected={(self.active == Some(index)).to_string()}
+                            // Choose on mousedown, as the blur of the input collapses the listbox
+                            // before a click would be delivered
+                            onmousedown={link.callback(move |_| ComboboxMsg::Choose(index))}
+                        >
+                            { props.render.render(ItemState {
+                                index,
+                                active: self.active == Some(index),
+                                selected: false,
+                            }) }
+                        </li>
+                    }) }
+                </ul>
+            </>
+        }
+    }
+}
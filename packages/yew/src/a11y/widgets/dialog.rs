@@ -0,0 +1,143 @@
+use crate::html::{Classes, NodeRef};
+use crate::utils::document;
+use crate::virtual_dom::AttrValue;
+use crate::{html, Callback, Children, Component, Context, Html, Properties};
+use wasm_bindgen::JsCast;
+use web_sys::{Element, HtmlElement, KeyboardEvent};
+
+/// Selector of the elements that can take part in the tab sequence
+const FOCUSABLE: &str = "a[href], area[href], button:not([disabled]), input:not([disabled]), \
+                         select:not([disabled]), textarea:not([disabled]), \
+                         [tabindex]:not([tabindex=\"-1\"])";
+
+/// Props for [`Dialog`]
+#[derive(Clone, PartialEq, Properties)]
+pub struct DialogProps {
+    /// Called when Escape is pressed, so the dialog can be closed
+    #[prop_or_default]
+    pub onclose: Callback<()>,
+    /// Id of the element labelling the dialog, usually its title
+    #[prop_or_default]
+    pub labelledby: Option<AttrValue>,
+    /// Id of the element describing the dialog
+    #[prop_or_default]
+    pub describedby: Option<AttrValue>,
+    /// Classes of the dialog element
+    #[prop_or_default]
+    pub class: Classes,
+    /// Content of the dialog
+    #[prop_or_default]
+    pub children: Children,
+}
+
+/// A modal [dialog](https://www.w3.org/TR/wai-aria-practices-1.1/#dialog_modal).
+///
+/// The dialog is open as long as it is rendered. It moves the focus to its first focusable
+/// element when mounted, keeps Tab and Shift+Tab within itself and gives the focus back to the
+/// previously focused element when destroyed.
+pub struct Dialog {
+    node_ref: NodeRef,
+    /// Element that had the focus before the dialog opened
+    return_focus: Option<Element>,
+}
+
+fn focusable(dialog: &Element) -> Vec<HtmlElement> {
+    let list = match dialog.query_selector_all(FOCUSABLE) {
+        Ok(list) => list,
+        Err(_) => return Vec::new(),
+    };
+    (0..list.length())
+        .filter_map(|i| list.get(i)?.dyn_into::<HtmlElement>().ok())
+        .collect()
+}
+
+/// Wraps the focus around at the ends of the dialog for a Tab key press
+fn trap_focus(dialog: &Element, e: &KeyboardEvent) {
+    let elements = focusable(dialog);
+    let (first, last) = match (elements.first(), elements.last()) {
+        (Some(first), Some(last)) => (first, last),
+        _ => {
+            // Nothing to move to, keep the focus on the dialog
+            e.prevent_default();
+            return;
+        }
+    };
+    let active = document().active_element();
+    let at = |el: &HtmlElement| active.as_ref() == Some(el.unchecked_ref::<Element>());
+    if e.shift_key() {
+        if at(first) || active.as_ref() == Some(dialog) {
+            e.prevent_default();
+            let _ = last.focus();
+        }
+    } else if at(last) {
+        e.prevent_default();
+        let _ = first.focus();
+    }
+}
+
+impl Component for Dialog {
+    type Message = ();
+    type Properties = DialogProps;
+
+    fn create(_ctx: &Context<Self>) -> Self {
+        Self {
+            node_ref: NodeRef::default(),
+            return_focus: document().active_element(),
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let props = ctx.props();
+        let onkeydown = {
+            let node_ref = self.node_ref.clone();
+            let onclose = props.onclose.clone();
+            Callback::from(move |e: KeyboardEvent| match e.key().as_str() {
+                "Escape" => {
+                    e.prevent_default();
+                    onclose.emit(());
+                }
+                "Tab" => {
+                    if let Some(dialog) = node_ref.cast::<Element>() {
+                        trap_focus(&dialog, &e);
+                    }
+                }
+                _ => (),
+            })
+        };
+
+        html! {
+            <div
+                role="dialog"
+                aria-modal="true"
+                aria-labelledby={props.labelledby.clone()}
+                aria-describedby={props.describedby.clone()}
+                tabindex="-1"
+                ref={self.node_ref.clone()}
+                class={props.class.clone()}
+                {onkeydown}
+            >
+                { for props.children.iter() }
+            </div>
+        }
+    }
+
+    fn rendered(&mut self, _ctx: &Context<Self>, first_render: bool) {
+        if !first_render {
+            return;
+        }
+        if let Some(dialog) = self.node_ref.cast::<HtmlElement>() {
+            let target = focusable(&dialog).into_iter().next().unwrap_or(dialog);
+            let _ = target.focus();
+        }
+    }
+
+    fn destroy(&mut self, _ctx: &Context<Self>) {
+        if let Some(el) = self
+            .return_focus
+            .take()
+            .and_then(|el| el.dyn_into::<HtmlElement>().ok())
+        {
+            let _ = el.focus();
+        }
+    }
+}
@@ -0,0 +1,122 @@
+use super::{item_id, navigate, ItemState, Orientation, RenderItem};
+use crate::html::Classes;
+use crate::virtual_dom::AttrValue;
+use crate::{html, Callback, Component, Context, Html, Properties};
+use web_sys::KeyboardEvent;
+
+/// Props for [`Listbox`]
+#[derive(Clone, PartialEq, Properties)]
+pub struct ListboxProps {
+    /// Id of the listbox. The options get ids derived with [`item_id`].
+    pub id: AttrValue,
+    /// Number of options
+    pub len: usize,
+    /// Renders the content of an option
+    pub render: RenderItem,
+    /// Index of the selected option
+    #[prop_or_default]
+    pub selected: Option<usize>,
+    /// Called with the index of an option chosen with Enter, Space or a click
+    #[prop_or_default]
+    pub onselect: Callback<usize>,
+    /// Accessible name of the listbox
+    #[prop_or_default]
+    pub label: Option<AttrValue>,
+    /// Classes of the listbox element
+    #[prop_or_default]
+    pub class: Classes,
+}
+
+#[doc(hidden)]
+pub enum ListboxMsg {
+    Focus,
+    Activate(usize),
+    Choose(usize),
+}
+
+/// A single-select [listbox](https://www.w3.org/TR/wai-aria-practices-1.1/#Listbox).
+///
+/// The listbox keeps focus and points to the active option with `aria-activedescendant`. The
+/// arrow keys, Home and End move the active option and Enter or Space select it.
+#[derive(Debug)]
+pub struct Listbox {
+    active: Option<usize>,
+}
+
+impl Component for Listbox {
+    type Message = ListboxMsg;
+    type Properties = ListboxProps;
+
+    fn create(_ctx: &Context<Self>) -> Self {
+        Self { active: None }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            ListboxMsg::Focus => {
+                if self.active.is_some() || ctx.props().len == 0 {
+                    return false;
+                }
+                self.active = Some(ctx.props().selected.unwrap_or(0));
+            }
+            ListboxMsg::Activate(index) => self.active = Some(index),
+            ListboxMsg::Choose(index) => {
+                self.active = Some(index);
+                ctx.props().onselect.emit(index);
+            }
+        }
+        true
+    }
+
+    fn changed(&mut self, ctx: &Context<Self>) -> bool {
+        if self.active.map_or(false, |i| i >= ctx.props().len) {
+            self.active = None;
+        }
+        true
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let props = ctx.props();
+        let link = ctx.link();
+        let (active, len) = (self.active, props.len);
+        let onkeydown = link.batch_callback(move |e: KeyboardEvent| {
+            let msg = match e.key().as_str() {
+                "Enter" | " " => ListboxMsg::Choose(active?),
+                key => ListboxMsg::Activate(navigate(key, active, len, Orientation::Vertical)?),
+            };
+            e.prevent_default();
+            Some(msg)
+        });
+
+        html! {
+            <ul
+                role="listbox"
+                id={props.id.clone()}
+                tabindex="0"
+                class={props.class.clone()}
+                aria-label={props.label.clone()}
+                aria-activedescendant={self.active.map(|i| item_id(&props.id, i))}
+                onfocus={link.callback(|_| ListboxMsg::Focus)}
+                {onkeydown}
+            >
+                { for (0..len).map(|index| {
+                    let selected = props.selected == Some(index);
+                    html! {
+                        <li
+                            role="option"
+                            id={item_id(&props.id, index)}
+                            aria-selected={selected.to_string()}
+                            onclick={link.callback(move |_| ListboxMsg::Choose(index))}
+                        >
+                            { props.render.render(ItemState {
+                                index,
+                                active: self.active == Some(index),
+                                selected,
+                            }) }
+                        </li>
+                    }
+                }) }
+            </ul>
+        }
+    }
+}
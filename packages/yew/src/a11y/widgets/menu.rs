@@ -0,0 +1,152 @@
+use super::{item_id, navigate, ItemState, Orientation, RenderItem};
+use crate::html::{Classes, NodeRef};
+use crate::virtual_dom::AttrValue;
+use crate::{html, Callback, Component, Context, Html, Properties};
+use web_sys::{HtmlElement, KeyboardEvent};
+
+/// Props for [`Menu`]
+#[derive(Clone, PartialEq, Properties)]
+pub struct MenuProps {
+    /// Id of the menu. The items get ids derived with [`item_id`].
+    pub id: AttrValue,
+    /// Number of items
+    pub len: usize,
+    /// Renders the content of an item
+    pub render: RenderItem,
+    /// Called with the index of an item activated with Enter, Space or a click
+    #[prop_or_default]
+    pub onactivate: Callback<usize>,
+    /// Called when Escape or Tab is pressed, so the menu can be closed
+    #[prop_or_default]
+    pub onclose: Callback<()>,
+    /// Move the focus into the menu when it is mounted
+    #[prop_or(true)]
+    pub autofocus: bool,
+    /// Accessible name of the menu
+    #[prop_or_default]
+    pub label: Option<AttrValue>,
+    /// Arrow keys moving between the items
+    #[prop_or_default]
+    pub orientation: Orientation,
+    /// Classes of the menu element
+    #[prop_or_default]
+    pub class: Classes,
+}
+
+#[doc(hidden)]
+pub enum MenuMsg {
+    Focus(usize),
+    Activate(usize),
+    Close,
+}
+
+/// A [menu](https://www.w3.org/TR/wai-aria-practices-1.1/#menu) of actions.
+///
+/// The items use a roving tabindex: only the focused item is in the tab sequence and the arrow
+/// keys, Home and End move the focus between the items.
+pub struct Menu {
+    focused: usize,
+    refs: Vec<NodeRef>,
+    /// Focus the item at `focused` after the next render
+    move_focus: bool,
+}
+
+impl Component for Menu {
+    type Message = MenuMsg;
+    type Properties = MenuProps;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        Self {
+            focused: 0,
+            refs: vec![NodeRef::default(); ctx.props().len],
+            move_focus: ctx.props().autofocus,
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            MenuMsg::Focus(index) => {
+                self.focused = index;
+                self.move_focus = true;
+            }
+            MenuMsg::Activate(index) => {
+                self.focused = index;
+                ctx.props().onactivate.emit(index);
+            }
+            MenuMsg::Close => {
+                ctx.props().onclose.emit(());
+                return false;
+            }
+        }
+        true
+    }
+
+    fn changed(&mut self, ctx: &Context<Self>) -> bool {
+        let len = ctx.props().len;
+        self.refs.resize_with(len, NodeRef::default);
+        if self.focused >= len {
+            self.focused = len.saturating_sub(1);
+        }
+        true
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let props = ctx.props();
+        let link = ctx.link();
+        let (focused, len, orientation) = (self.focused, props.len, props.orientation);
+        let onkeydown = link.batch_callback(move |e: KeyboardEvent| {
+            let msg = match e.key().as_str() {
+                "Enter" | " " => MenuMsg::Activate(focused),
+                // Tab moves the focus out of the menu as usual
+                "Tab" => return Some(MenuMsg::Close),
+                "Escape" => MenuMsg::Close,
+                key => MenuMsg::Focus(navigate(key, Some(focused), len, orientation)?),
+            };
+            e.prevent_default();
+            Some(msg)
+        });
+        let aria_orientation = match orientation {
+            Orientation::Horizontal => "horizontal",
+            Orientation::Vertical => "vertical",
+        };
+
+        html! {
+            <ul
+                role="menu"
+                id={props.id.clone()}
+                class={props.class.clone()}
+                aria-label={props.label.clone()}
+                aria-orientation={aria_orientation}
+                {onkeydown}
+            >
+                { for (0..len).map(|index| html! {
+                    <li
+                        role="menuitem"
+                        id={item_id(&props.id, index)}
+                        ref={self.refs.get(index).cloned().unwrap_or_default()}
+                        tabindex={if index == focused { "0" } else { "-1" }}
+                        onclick={link.callback(move |_| MenuMsg::Activate(index))}
+                    >
+                        { props.render.render(ItemState {
+                            index,
+                            active: index == focused,
+                            selected: false,
+                        }) }
+                    </li>
+                }) }
+            </ul>
+        }
+    }
+
+    fn rendered(&mut self, _ctx: &Context<Self>, _first_render: bool) {
+        if std::mem::take(&mut self.move_focus) {
+            if let Some(el) = self
+                .refs
+                .get(self.focused)
+                .and_then(|r| r.cast::<HtmlElement>())
+            {
+                let _ = el.focus();
+            }
+        }
+    }
+}
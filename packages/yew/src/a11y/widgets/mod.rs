@@ -0,0 +1,167 @@
+//! Headless components implementing the keyboard interaction and ARIA semantics of common
+//! widget patterns from the
+//! [WAI-ARIA Authoring Practices](https://www.w3.org/TR/wai-aria-practices-1.1/).
+//!
+//! The components render the structural elements with the right roles and states and handle
+//! focus and keyboard input. They come without any styling: pass classes to the components and
+//! render the content of items with a [`RenderItem`].
+
+mod combobox;
+mod dialog;
+mod listbox;
+mod menu;
+mod tabs;
+
+pub use combobox::*;
+pub use dialog::*;
+pub use listbox::*;
+pub use menu::*;
+pub use tabs::*;
+
+use crate::Html;
+use std::rc::Rc;
+
+/// State of an item of a widget, passed to [`RenderItem`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ItemState {
+    /// Position of the item
+    pub index: usize,
+    /// The item has keyboard focus or is the active descendant of the widget
+    pub active: bool,
+    /// The item is selected
+    pub selected: bool,
+}
+
+/// Renders the content of the item of a widget
+pub struct RenderItem(Rc<dyn Fn(ItemState) -> Html>);
+
+impl RenderItem {
+    /// Creates a new [`RenderItem`]
+    pub fn new(value: impl Fn(ItemState) -> Html + 'static) -> Self {
+        Self(Rc::new(value))
+    }
+
+    fn render(&self, state: ItemState) -> Html {
+        (self.0)(state)
+    }
+}
+
+impl Clone for RenderItem {
+    fn clone(&self) -> Self {
+        Self(Rc::clone(&self.0))
+    }
+}
+
+impl PartialEq for RenderItem {
+    fn eq(&self, other: &Self) -> bool {
+        // https://github.com/rust-lang/rust-clippy/issues/6524
+        #[allow(clippy::vtable_address_comparisons)]
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+/// Returns the id of the item at `index` of the widget with `id`.
+///
+/// Items of the widgets in this module get these ids, which can be used to refer to them from
+/// other elements.
+pub fn item_id(id: &str, index: usize) -> String {
+    format!("{}-item-{}", id, index)
+}
+
+/// Axis along which the arrow keys move between items
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Orientation {
+    /// Left and right arrow keys
+    Horizontal,
+    /// Up and down arrow keys
+    Vertical,
+}
+
+impl Default for Orientation {
+    fn default() -> Self {
+        Self::Vertical
+    }
+}
+
+/// Returns the item to move to for a navigation key, or `None`, if `key` is not a navigation key.
+///
+/// Arrow keys wrap around at either end.
+pub(crate) fn navigate(
+    key: &str,
+    current: Option<usize>,
+    len: usize,
+    orientation: Orientation,
+) -> Option<usize> {
+    if len == 0 {
+        return None;
+    }
+    let (prev, next) = match orientation {
+        Orientation::Horizontal => ("ArrowLeft", "ArrowRight"),
+        Orientation::Vertical => ("ArrowUp", "ArrowDown"),
+    };
+    match key {
+        "Home" => Some(0),
+        "End" => Some(len - 1),
+        k if k == next => Some(current.map_or(0, |i| (i + 1) % len)),
+        k if k == prev => Some(current.map_or(len - 1, |i| (i + len - 1) % len)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::html::{NodeRef, Scope};
+    use crate::utils::document;
+    use crate::{html, Callback};
+
+    #[cfg(feature = "wasm_test")]
+    use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+
+    #[cfg(feature = "wasm_test")]
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    #[test]
+    fn navigate_wraps_around() {
+        use Orientation::*;
+
+        assert_eq!(navigate("ArrowDown", None, 3, Vertical), Some(0));
+        assert_eq!(navigate("ArrowDown", Some(2), 3, Vertical), Some(0));
+        assert_eq!(navigate("ArrowUp", Some(0), 3, Vertical), Some(2));
+        assert_eq!(navigate("ArrowUp", None, 3, Vertical), Some(2));
+        assert_eq!(navigate("ArrowRight", Some(1), 3, Horizontal), Some(2));
+        assert_eq!(navigate("ArrowDown", Some(1), 3, Horizontal), None);
+        assert_eq!(navigate("Home", Some(2), 3, Vertical), Some(0));
+        assert_eq!(navigate("End", None, 3, Horizontal), Some(2));
+        assert_eq!(navigate("ArrowDown", None, 0, Vertical), None);
+        assert_eq!(navigate("a", Some(1), 3, Vertical), None);
+    }
+
+    #[test]
+    fn listbox_marks_selected_option() {
+        let scope = Scope::<Listbox>::new(None);
+        let el = document().create_element("div").unwrap();
+        let props = ListboxProps {
+            id: "fruits".into(),
+            len: 2,
+            render: RenderItem::new(|item| html! { { item.index } }),
+            selected: Some(1),
+            onselect: Callback::default(),
+            label: None,
+            class: Default::default(),
+        };
+        scope.mount_in_place(
+            el.clone(),
+            NodeRef::default(),
+            NodeRef::default(),
+            Rc::new(props),
+        );
+
+        let selected = el
+            .query_selector("[role=option][aria-selected=true]")
+            .unwrap()
+            .unwrap();
+        assert_eq!(selected.id(), item_id("fruits", 1));
+        assert_eq!(el.query_selector_all("[role=option]").unwrap().length(), 2);
+    }
+}
@@ -0,0 +1,158 @@
+use super::{item_id, navigate, ItemState, Orientation, RenderItem};
+use crate::html::{Classes, NodeRef};
+use crate::virtual_dom::AttrValue;
+use crate::{html, Callback, Component, Context, Html, Properties};
+use web_sys::{HtmlElement, KeyboardEvent};
+
+/// Returns the id of the panel of the tab at `index` of the [`Tabs`] with `id`
+pub fn tab_panel_id(id: &str, index: usize) -> String {
+    format!("{}-panel-{}", id, index)
+}
+
+/// Props for [`Tabs`]
+#[derive(Clone, PartialEq, Properties)]
+pub struct TabsProps {
+    /// Id of the tab list. The tabs get ids derived with [`item_id`] and the panel with
+    /// [`tab_panel_id`].
+    pub id: AttrValue,
+    /// Number of tabs
+    pub len: usize,
+    /// Index of the selected tab
+    pub selected: usize,
+    /// Called with the index of a tab selected with the keyboard or a click
+    pub onselect: Callback<usize>,
+    /// Renders the content of a tab
+    pub render_tab: RenderItem,
+    /// Renders the panel of the selected tab. Its `index` is the one of the tab.
+    pub render_panel: RenderItem,
+    /// Accessible name of the tab list
+    #[prop_or_default]
+    pub label: Option<AttrValue>,
+    /// Arrow keys moving between the tabs
+    #[prop_or(Orientation::Horizontal)]
+    pub orientation: Orientation,
+    /// Classes of the tab list element
+    #[prop_or_default]
+    pub class: Classes,
+    /// Classes of the panel element
+    #[prop_or_default]
+    pub panel_class: Classes,
+}
+
+#[doc(hidden)]
+pub enum TabsMsg {
+    Select(usize),
+}
+
+/// [Tabs](https://www.w3.org/TR/wai-aria-practices-1.1/#tabpanel) with a single panel showing
+/// the selected tab.
+///
+/// The tabs use a roving tabindex. Moving between the tabs with the arrow keys, Home and End
+/// selects them right away.
+pub struct Tabs {
+    refs: Vec<NodeRef>,
+    /// Focus the selected tab after the next render
+    move_focus: bool,
+}
+
+impl Component for Tabs {
+    type Message = TabsMsg;
+    type Properties = TabsProps;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        Self {
+            refs: vec![NodeRef::default(); ctx.props().len],
+            move_focus: false,
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            TabsMsg::Select(index) => {
+                self.move_focus = true;
+                ctx.props().onselect.emit(index);
+            }
+        }
+        false
+    }
+
+    fn changed(&mut self, ctx: &Context<Self>) -> bool {
+        self.refs.resize_with(ctx.props().len, NodeRef::default);
+        true
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let props = ctx.props();
+        let link = ctx.link();
+        let (selected, len, orientation) = (props.selected, props.len, props.orientation);
+        let onkeydown = link.batch_callback(move |e: KeyboardEvent| {
+            let index = navigate(&e.key(), Some(selected), len, orientation)?;
+            e.prevent_default();
+            Some(TabsMsg::Select(index))
+        });
+        let aria_orientation = match orientation {
+            Orientation::Horizontal => "horizontal",
+            Orientation::Vertical => "vertical",
+        };
+
+        html! {
+            <>
+                <div
+                    role="tablist"
+                    id={props.id.clone()}
+                    class={props.class.clone()}
+                    aria-label={props.label.clone()}
+                    aria-orientation={aria_orientation}
+                    {onkeydown}
+                >
+                    { for (0..len).map(|index| {
+                        let is_selected = index == selected;
+                        html! {
+                            <button
+                                type="button"
+                                role="tab"
+                                id={item_id(&props.id, index)}
+                                ref={self.refs.get(index).cloned().unwrap_or_default()}
+                                aria-selected={is_selected.to_string()}
+                                aria-controls={tab_panel_id(&props.id, index)}
+                                tabindex={if is_selected { "0" } else { "-1" }}
+                                onclick={link.callback(move |_| TabsMsg::Select(index))}
+                            >
+                                { props.render_tab.render(ItemState {
+                                    index,
+                                    active: is_selected,
+                                    selected: is_selected,
+                                }) }
+                            </button>
+                        }
+                    }) }
+                </div>
+                <div
+                    role="tabpanel"
+                    id={tab_panel_id(&props.id, selected)}
+                    class={props.panel_class.clone()}
+                    aria-labelledby={item_id(&props.id, selected)}
+                    tabindex="0"
+                >
+                    { props.render_panel.render(ItemState {
+                        index: selected,
+                        active: true,
+                        selected: true,
+                    }) }
+                </div>
+            </>
+        }
+    }
+
+    fn rendered(&mut self, ctx: &Context<Self>, _first_render: bool) {
+        if std::mem::take(&mut self.move_focus) {
+            if let Some(el) = self
+                .refs
+                .get(ctx.props().selected)
+                .and_then(|r| r.cast::<HtmlElement>())
+            {
+                let _ = el.focus();
+            }
+        }
+    }
+}
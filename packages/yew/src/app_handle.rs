@@ -3,8 +3,10 @@
 
 use std::ops::Deref;
 
-use crate::html::{Component, NodeRef, Scope, Scoped};
-use crate::utils::document;
+use crate::html::{Component, ListenerConfig, NodeRef, Registry, RootListeners, Scope, Scoped};
+use crate::utils::Environment;
+use crate::Error;
+use std::marker::PhantomData;
 use std::rc::Rc;
 use web_sys::Element;
 
@@ -23,29 +25,45 @@ where
     /// similarly to the `program` function in Elm. You should provide an initial model, `update`
     /// function which will update the state of the model and a `view` function which
     /// will render the model to a virtual DOM tree.
-    pub(crate) fn mount_with_props(element: Element, props: Rc<COMP::Properties>) -> Self {
-        clear_element(&element);
+    pub(crate) fn mount_with_props(
+        element: Element,
+        props: Rc<COMP::Properties>,
+        listeners: ListenerConfig,
+        mut config: AppConfig,
+        environment: Environment,
+    ) -> Result<Self, Error> {
+        clear_element(&element)?;
+        config.root = Some(element.clone());
         let app = Self {
-            scope: Scope::new(None),
+            scope: Scope::new_root(AppRoot {
+                listeners: RootListeners::new(listeners, Some(element.clone())),
+                registry: Registry::default(),
+                config: Rc::new(config),
+                environment,
+            }),
         };
         app.scope
             .mount_in_place(element, NodeRef::default(), NodeRef::default(), props);
 
-        app
+        Ok(app)
     }
 
     /// Alternative to `mount_with_props` which replaces the body element with a component which
     /// has a body element at the root of the HTML generated by its `view` method. Use this method
     /// when you need to manipulate the body element. For example, adding/removing app-wide
     /// CSS classes of the body element.
-    pub(crate) fn mount_as_body_with_props(props: Rc<COMP::Properties>) -> Self {
-        let html_element = document().document_element().unwrap();
-        let body_element = document().body().expect("no body node found");
-        html_element
-            .remove_child(&body_element)
-            .expect("can't remove body child");
+    pub(crate) fn mount_as_body_with_props(
+        props: Rc<COMP::Properties>,
+        listeners: ListenerConfig,
+        config: AppConfig,
+        environment: Environment,
+    ) -> Result<Self, Error> {
+        let document = environment.try_document().ok_or(Error::NoDocument)?;
+        let html_element = document.document_element().ok_or(Error::NoDocument)?;
+        let body_element = document.body().ok_or(Error::NoBody)?;
+        html_element.remove_child(&body_element)?;
 
-        Self::mount_with_props(html_element, props)
+        Self::mount_with_props(html_element, props, listeners, config, environment)
     }
 
     /// Schedule the app for destruction
@@ -66,8 +84,394 @@ where
 }
 
 /// Removes anything from the given element.
-fn clear_element(element: &Element) {
+fn clear_element(element: &Element) -> Result<(), Error> {
     while let Some(child) = element.last_child() {
-        element.remove_child(&child).expect("can't remove a child");
+        element.remove_child(&child)?;
+    }
+    Ok(())
+}
+
+/// Settings and state shared by all the components of an app
+#[derive(Debug, Default)]
+pub(crate) struct AppRoot {
+    pub(crate) listeners: RootListeners,
+    /// Scopes registered with [`Scope::register_as`]
+    pub(crate) registry: Registry,
+    pub(crate) config: Rc<AppConfig>,
+    /// The window and document the app renders into
+    pub(crate) environment: Environment,
+}
+
+/// Settings of an app, which all of its components can read with
+/// [`Scope::app_config`](crate::html::Scope::app_config) or
+/// [`use_app_config`](crate::functional::use_app_config).
+///
+/// They are set when the app is mounted with an [`AppBuilder`], so libraries can adapt to the app
+/// they are used in, e.g. to resolve paths against its base URL, without global state.
+/// Components that are not part of a mounted app, e.g. in tests, get the default settings.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AppConfig {
+    root: Option<Element>,
+    base_url: Option<String>,
+    asset_prefix: Option<String>,
+    debug: bool,
+    stable_output: bool,
+}
+
+impl Default for AppConfig {
+    fn default() -> Self {
+        Self {
+            root: None,
+            base_url: None,
+            asset_prefix: None,
+            debug: cfg!(debug_assertions),
+            stable_output: false,
+        }
+    }
+}
+
+impl AppConfig {
+    /// Returns the element the app is mounted in
+    pub fn root(&self) -> Option<&Element> {
+        self.root.as_ref()
+    }
+
+    /// Returns the path the app is served under, e.g. `/app`, without a trailing slash
+    pub fn base_url(&self) -> Option<&str> {
+        self.base_url.as_deref()
+    }
+
+    /// Returns the prefix of the URLs of static assets, e.g. a CDN
+    pub fn asset_prefix(&self) -> Option<&str> {
+        self.asset_prefix.as_deref()
+    }
+
+    /// Returns the URL of the static asset at `path`
+    pub fn asset(&self, path: &str) -> String {
+        match &self.asset_prefix {
+            Some(prefix) => format!(
+                "{}/{}",
+                prefix.trim_end_matches('/'),
+                path.trim_start_matches('/')
+            ),
+            None => path.to_owned(),
+        }
+    }
+
+    /// Returns whether the app runs in debug mode, which defaults to whether it was built with
+    /// debug assertions
+    pub fn debug(&self) -> bool {
+        self.debug
+    }
+
+    /// Returns whether the app renders deterministic DOM output for visual regression and
+    /// DOM-diff tests.
+    ///
+    /// The attributes of new elements are then set in the order of their names, and no comments
+    /// marking components are inserted with the `component_markers` feature. Attributes added
+    /// to an element later are appended, as reordering the others would set them again, which
+    /// e.g. reloads images. Generated markup, like the ids of
+    /// [a11y widgets](crate::a11y::widgets), only depends on props and is stable regardless of
+    /// this setting. The empty text nodes marking the position of portals and empty lists are
+    /// kept, as the virtual DOM inserts nodes relative to them, and they produce no markup.
+    pub fn stable_output(&self) -> bool {
+        self.stable_output
+    }
+}
+
+/// Builder for mounting an app with settings other than the defaults used by the `start_app*`
+/// functions.
+///
+/// # Example
+/// ```rust
+/// # use yew::prelude::*;
+/// use yew::html::{ListenerConfig, ListenerMode};
+/// use yew::AppBuilder;
+/// # struct App;
+/// # impl Component for App {
+/// #     type Message = ();
+/// #     type Properties = ();
+/// #     fn create(_ctx: &Context<Self>) -> Self { Self }
+/// #     fn view(&self, _ctx: &Context<Self>) -> Html { html! {} }
+/// # }
+///
+/// # fn start() {
+/// let element = yew::utils::document().get_element_by_id("widget").unwrap();
+/// let app = AppBuilder::<App>::new()
+///     .listeners(ListenerConfig::default().mode(ListenerMode::Delegated))
+///     .mount(element, ());
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct AppBuilder<COMP: Component> {
+    listeners: ListenerConfig,
+    config: AppConfig,
+    environment: Environment,
+    _marker: PhantomData<COMP>,
+}
+
+impl<COMP: Component> Default for AppBuilder<COMP> {
+    fn default() -> Self {
+        Self {
+            listeners: ListenerConfig::default(),
+            config: AppConfig::default(),
+            environment: Environment::default(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<COMP: Component> AppBuilder<COMP> {
+    /// Creates a builder with the default settings
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets how the listeners of the app are attached and which events Yew bubbles
+    pub fn listeners(mut self, config: ListenerConfig) -> Self {
+        self.listeners = config;
+        self
+    }
+
+    /// Renders the app into the document of `environment` instead of the current one. The element
+    /// the app is mounted in must be part of that document.
+    pub fn environment(mut self, environment: Environment) -> Self {
+        self.environment = environment;
+        self
+    }
+
+    /// Sets the path the app is served under, see [`AppConfig::base_url`]
+    pub fn base_url(mut self, base_url: impl Into<String>) -> Self {
+        let base_url = base_url.into();
+        self.config.base_url = Some(base_url.trim_end_matches('/').to_owned());
+        self
+    }
+
+    /// Sets the prefix of the URLs of static assets, see [`AppConfig::asset`]
+    pub fn asset_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.config.asset_prefix = Some(prefix.into());
+        self
+    }
+
+    /// Turns the debug mode of the app on or off, see [`AppConfig::debug`]
+    pub fn debug(mut self, debug: bool) -> Self {
+        self.config.debug = debug;
+        self
+    }
+
+    /// Turns the deterministic DOM output for visual regression tests on or off, see
+    /// [`AppConfig::stable_output`]
+    pub fn stable_output(mut self, stable_output: bool) -> Self {
+        self.config.stable_output = stable_output;
+        self
+    }
+
+    /// Mounts the app in `element`, removing its current children.
+    ///
+    /// # Panics
+    /// Panics if the app could not be mounted, see [`try_mount`](Self::try_mount).
+    pub fn mount(self, element: Element, props: COMP::Properties) -> AppHandle<COMP> {
+        self.try_mount(element, props)
+            .unwrap_or_else(|err| panic!("failed to mount app: {}", err))
+    }
+
+    /// Mounts the app in the body of the document.
+    ///
+    /// # Panics
+    /// Panics if the app could not be mounted, see [`try_mount_to_body`](Self::try_mount_to_body).
+    pub fn mount_to_body(self, props: COMP::Properties) -> AppHandle<COMP> {
+        self.try_mount_to_body(props)
+            .unwrap_or_else(|err| panic!("failed to mount app: {}", err))
+    }
+
+    /// Replaces the body of the document with the app, which must render a `<body>` element at
+    /// its root. See [`start_app_as_body`](crate::start_app_as_body).
+    ///
+    /// # Panics
+    /// Panics if the app could not be mounted, see [`try_mount_as_body`](Self::try_mount_as_body).
+    pub fn mount_as_body(self, props: COMP::Properties) -> AppHandle<COMP> {
+        self.try_mount_as_body(props)
+            .unwrap_or_else(|err| panic!("failed to mount app: {}", err))
+    }
+
+    /// Mounts the app in `element`, removing its current children. Fails if the children can't
+    /// be removed.
+    pub fn try_mount(
+        self,
+        element: Element,
+        props: COMP::Properties,
+    ) -> Result<AppHandle<COMP>, Error> {
+        crate::set_default_panic_hook();
+        AppHandle::mount_with_props(
+            element,
+            Rc::new(props),
+            self.listeners,
+            self.config,
+            self.environment,
+        )
+    }
+
+    /// Mounts the app in the body of the document. Fails if there is no document or body, e.g.
+    /// when the script runs in the `<head>` before the body was parsed.
+    pub fn try_mount_to_body(self, props: COMP::Properties) -> Result<AppHandle<COMP>, Error> {
+        let document = self.environment.try_document().ok_or(Error::NoDocument)?;
+        let body = document.body().ok_or(Error::NoBody)?;
+        self.try_mount(body.into(), props)
+    }
+
+    /// Replaces the body of the document with the app. Fails if there is no document or body.
+    pub fn try_mount_as_body(self, props: COMP::Properties) -> Result<AppHandle<COMP>, Error> {
+        crate::set_default_panic_hook();
+        AppHandle::mount_as_body_with_props(
+            Rc::new(props),
+            self.listeners,
+            self.config,
+            self.environment,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate self as yew;
+
+    use super::*;
+    use crate::{html, Context, Html};
+
+    #[cfg(feature = "wasm_test")]
+    use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+
+    #[cfg(feature = "wasm_test")]
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    struct App;
+
+    impl Component for App {
+        type Message = ();
+        type Properties = ();
+
+        fn create(_ctx: &Context<Self>) -> Self {
+            Self
+        }
+
+        fn view(&self, ctx: &Context<Self>) -> Html {
+            html! { <img src={ctx.link().app_config().asset("/logo.svg")} /> }
+        }
+    }
+
+    #[test]
+    fn asset_urls_join_prefix() {
+        let builder = AppBuilder::<App>::new().asset_prefix("https://cdn.example.com/");
+        assert_eq!(
+            builder.config.asset("/logo.svg"),
+            "https://cdn.example.com/logo.svg"
+        );
+        assert_eq!(AppConfig::default().asset("logo.svg"), "logo.svg");
+    }
+
+    #[cfg(feature = "wasm_test")]
+    #[test]
+    fn config_is_shared_with_components() {
+        let element = crate::utils::document().create_element("div").unwrap();
+        let app = AppBuilder::<App>::new()
+            .base_url("/app/")
+            .asset_prefix("/static")
+            .mount(element.clone(), ());
+
+        assert_eq!(app.app_config().base_url(), Some("/app"));
+        assert_eq!(app.app_config().root(), Some(&element));
+        assert_eq!(element.inner_html(), r#"<img src="/static/logo.svg">"#);
+        app.destroy();
+    }
+
+    struct Toggled {
+        updated: bool,
+    }
+
+    impl Component for Toggled {
+        type Message = ();
+        type Properties = ();
+
+        fn create(_ctx: &Context<Self>) -> Self {
+            Self { updated: false }
+        }
+
+        fn update(&mut self, _ctx: &Context<Self>, _msg: ()) -> bool {
+            self.updated = true;
+            true
+        }
+
+        fn view(&self, _ctx: &Context<Self>) -> Html {
+            let state = self.updated.then(|| "updated");
+            html! { <p id="p" data-state={state} class="text" /> }
+        }
+    }
+
+    #[cfg(feature = "wasm_test")]
+    #[test]
+    fn stable_output_sorts_attributes() {
+        let element = crate::utils::document().create_element("div").unwrap();
+        let app = AppBuilder::<Toggled>::new()
+            .stable_output(true)
+            .mount(element.clone(), ());
+        assert_eq!(element.inner_html(), r#"<p class="text" id="p"></p>"#);
+
+        // Only the new attribute is set
+        let ops = crate::virtual_dom::ops::record_ops(|| app.send_message(()));
+        assert_eq!(ops.len(), 1);
+        assert_eq!(
+            element.inner_html(),
+            r#"<p class="text" id="p" data-state="updated"></p>"#
+        );
+        app.destroy();
+    }
+
+    #[cfg(feature = "wasm_test")]
+    #[test]
+    fn sorted_attributes_are_recorded() {
+        use crate::virtual_dom::ops::{self, OpApplier};
+
+        let document = crate::utils::document();
+        let element = document.create_element("div").unwrap();
+        let mirror = document.create_element("div").unwrap();
+        let mut applier = OpApplier::new();
+        applier.bind(ops::node_id(&element), mirror.clone().into());
+
+        let mut app = None;
+        let mount = ops::record_ops(|| {
+            app = Some(
+                AppBuilder::<Toggled>::new()
+                    .stable_output(true)
+                    .mount(element.clone(), ()),
+            )
+        });
+        applier.apply_all(mount).unwrap();
+        let app = app.unwrap();
+        applier
+            .apply_all(ops::record_ops(|| app.send_message(())))
+            .unwrap();
+        assert_eq!(mirror.inner_html(), element.inner_html());
+        app.destroy();
+    }
+
+    #[cfg(feature = "wasm_test")]
+    #[test]
+    fn renders_into_the_environment_document() {
+        let document = crate::utils::document()
+            .implementation()
+            .unwrap()
+            .create_html_document()
+            .unwrap();
+        let element = document.create_element("div").unwrap();
+        document.body().unwrap().append_child(&element).unwrap();
+
+        let app = AppBuilder::<App>::new()
+            .environment(Environment::of_document(document.clone()))
+            .mount(element.clone(), ());
+
+        let img = element.first_child().unwrap();
+        assert_eq!(img.owner_document(), Some(document.clone()));
+        assert_eq!(app.environment().document(), document);
+        app.destroy();
     }
 }
@@ -5,7 +5,9 @@
 //! - [Timer](https://github.com/yewstack/yew/tree/master/examples/timer)
 
 use crate::html::ImplicitClone;
-use std::cell::RefCell;
+use std::any::Any;
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
 use std::fmt;
 use std::rc::Rc;
 
@@ -26,6 +28,56 @@ pub enum Callback<IN> {
 
 type CallbackOnce<IN> = RefCell<Option<Box<dyn FnOnce(IN)>>>;
 
+thread_local! {
+    /// Ids of the callbacks created with [`Callback::with_id`], by the address of their function
+    static IDS: RefCell<HashMap<usize, Box<dyn CallbackId>>> = Default::default();
+}
+
+fn address<IN>(func: &Rc<dyn Fn(IN)>) -> usize {
+    Rc::as_ptr(func) as *const () as usize
+}
+
+/// Removes the id of a callback when its function is dropped
+struct IdGuard(Rc<Cell<usize>>);
+
+impl Drop for IdGuard {
+    fn drop(&mut self) {
+        // Not accessible anymore while the thread is shutting down
+        let _ = IDS.try_with(|ids| ids.borrow_mut().remove(&self.0.get()));
+    }
+}
+
+/// Returns whether the functions of two callbacks were created with equal ids
+fn eq_ids<IN>(a: &Rc<dyn Fn(IN)>, b: &Rc<dyn Fn(IN)>) -> bool {
+    IDS.with(|ids| {
+        let ids = ids.borrow();
+        match (ids.get(&address(a)), ids.get(&address(b))) {
+            (Some(a), Some(b)) => a.eq_id(&**b),
+            _ => false,
+        }
+    })
+}
+
+/// Identity of a [`Callback`] created with [`Callback::with_id`]. Implemented for every type
+/// with `PartialEq`.
+pub trait CallbackId {
+    #[doc(hidden)]
+    fn as_any(&self) -> &dyn Any;
+
+    /// Returns whether `other` is an id of the same type equal to this one
+    fn eq_id(&self, other: &dyn CallbackId) -> bool;
+}
+
+impl<T: PartialEq + 'static> CallbackId for T {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn eq_id(&self, other: &dyn CallbackId) -> bool {
+        other.as_any().downcast_ref::<T>() == Some(self)
+    }
+}
+
 impl<IN, F: Fn(IN) + 'static> From<F> for Callback<IN> {
     fn from(func: F) -> Self {
         Callback::Callback(Rc::new(func))
@@ -45,7 +97,9 @@ impl<IN> Clone for Callback<IN> {
 impl<IN> PartialEq for Callback<IN> {
     fn eq(&self, other: &Callback<IN>) -> bool {
         match (&self, &other) {
-            (Callback::Callback(cb), Callback::Callback(other_cb)) => Rc::ptr_eq(cb, other_cb),
+            (Callback::Callback(cb), Callback::Callback(other_cb)) => {
+                Rc::ptr_eq(cb, other_cb) || eq_ids(cb, other_cb)
+            }
             (Callback::CallbackOnce(cb), Callback::CallbackOnce(other_cb)) => {
                 Rc::ptr_eq(cb, other_cb)
             }
@@ -88,6 +142,37 @@ impl<IN> Callback<IN> {
         Callback::CallbackOnce(Rc::new(RefCell::new(Some(Box::new(func)))))
     }
 
+    /// Creates a callback which is equal to the callbacks created with an equal `id`, instead of
+    /// only to its clones. Properties holding a callback rebuilt on every render then stay equal,
+    /// so the child isn't rendered again.
+    ///
+    /// The id must tell apart everything `func` depends on: callbacks with equal ids are assumed
+    /// to do the same, whatever their functions.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use yew::Callback;
+    /// # fn select_item(_id: u32) {}
+    /// let onselect = |id: u32| Callback::with_id(("select", id), move |_: ()| select_item(id));
+    /// assert_eq!(onselect(1), onselect(1));
+    /// assert_ne!(onselect(1), onselect(2));
+    /// ```
+    pub fn with_id<ID, F>(id: ID, func: F) -> Self
+    where
+        ID: PartialEq + 'static,
+        F: Fn(IN) + 'static,
+    {
+        let guarded_address = Rc::new(Cell::new(0));
+        let guard = IdGuard(Rc::clone(&guarded_address));
+        let func: Rc<dyn Fn(IN)> = Rc::new(move |input| {
+            let _guard = &guard;
+            func(input)
+        });
+        guarded_address.set(address(&func));
+        IDS.with(|ids| ids.borrow_mut().insert(address(&func), Box::new(id)));
+        Callback::Callback(func)
+    }
+
     /// Creates a "no-op" callback which can be used when it is not suitable to use an
     /// `Option<Callback>`.
     pub fn noop() -> Self {
@@ -118,3 +203,25 @@ impl<IN: 'static> Callback<IN> {
 }
 
 impl<T> ImplicitClone for Callback<T> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn identified_callbacks_compare_ids() {
+        let a = Callback::with_id(1, |_: ()| ());
+        assert_eq!(a, Callback::with_id(1, |_: ()| ()));
+        assert_ne!(a, Callback::with_id(2, |_: ()| ()));
+        // Ids of different types are never equal
+        assert_ne!(a, Callback::with_id(1_u8, |_: ()| ()));
+        assert_ne!(a, Callback::from(|_: ()| ()));
+        assert_eq!(a, a.clone());
+
+        // Ids are dropped with their callbacks
+        let count = || IDS.with(|ids| ids.borrow().len());
+        let before = count();
+        drop(Callback::with_id(3, |_: ()| ()));
+        assert_eq!(count(), before);
+    }
+}
@@ -0,0 +1,484 @@
+//! This module contains [`CodeBlock`], a component rendering syntax highlighted code.
+//!
+//! Code is split into lines, which a [`Highlighter`] splits into [`Token`]s rendered as
+//! `<span>`s with the class of their kind. Lines that didn't change since the last render are not
+//! tokenized again and render the same nodes, so the differ leaves them and the selection in
+//! them untouched.
+//!
+//! [`SimpleHighlighter`] knows keywords, comments, strings and numbers of a few languages. Other
+//! highlighters can be plugged in by implementing [`Highlighter`].
+
+use crate::html::{Component, Context, Html};
+use crate::virtual_dom::{AttrValue, VTag, VText};
+use crate::{classes, html, Properties};
+use std::collections::HashMap;
+use std::fmt;
+use std::rc::Rc;
+
+/// A piece of a line of code
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Token {
+    /// The text of the token
+    pub text: String,
+    /// Class of the `<span>` of the token, e.g. `keyword`. Tokens without class are rendered as
+    /// text.
+    pub class: Option<&'static str>,
+}
+
+impl Token {
+    /// Creates a token with `class`
+    pub fn new(text: impl Into<String>, class: Option<&'static str>) -> Self {
+        Self {
+            text: text.into(),
+            class,
+        }
+    }
+}
+
+/// Splits lines of code into [`Token`]s.
+///
+/// Lines are tokenized one by one. Constructs spanning lines, like block comments, are tracked
+/// in a state that is passed from the end of a line to the next one. The state at the start of
+/// the code is `0`.
+pub trait Highlighter {
+    /// Tokenizes `line`, which starts in `state`, and updates `state` to the one at its end
+    fn highlight_line(&self, line: &str, state: &mut u32) -> Vec<Token>;
+}
+
+/// A [`Highlighter`] that leaves code as it is
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PlainText;
+
+impl Highlighter for PlainText {
+    fn highlight_line(&self, line: &str, _state: &mut u32) -> Vec<Token> {
+        vec![Token::new(line, None)]
+    }
+}
+
+/// A [`Highlighter`] for languages with C-like tokens. Tokens get the classes `keyword`,
+/// `comment`, `string` and `number`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SimpleHighlighter {
+    /// Keywords of the language
+    pub keywords: &'static [&'static str],
+    /// Start of comments to the end of the line
+    pub line_comment: Option<&'static str>,
+    /// Start and end of block comments
+    pub block_comment: Option<(&'static str, &'static str)>,
+    /// Quotes of strings
+    pub quotes: &'static [char],
+}
+
+/// State of a [`SimpleHighlighter`] inside a block comment
+const IN_BLOCK_COMMENT: u32 = 1;
+
+impl SimpleHighlighter {
+    /// A highlighter for Rust
+    pub fn rust() -> Self {
+        Self {
+            keywords: &[
+                "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else",
+                "enum", "extern", "false", "fn", "for", "if", "impl", "in", "let", "loop", "match",
+                "mod", "move", "mut", "pub", "ref", "return", "self", "Self", "static", "struct",
+                "super", "trait", "true", "type", "unsafe", "use", "where", "while",
+            ],
+            line_comment: Some("//"),
+            block_comment: Some(("/*", "*/")),
+            quotes: &['"'],
+        }
+    }
+
+    /// A highlighter for JavaScript and TypeScript
+    pub fn javascript() -> Self {
+        Self {
+            keywords: &[
+                "async",
+                "await",
+                "break",
+                "case",
+                "catch",
+                "class",
+                "const",
+                "continue",
+                "default",
+                "delete",
+                "else",
+                "export",
+                "extends",
+                "false",
+                "finally",
+                "for",
+                "function",
+                "if",
+                "import",
+                "in",
+                "instanceof",
+                "let",
+                "new",
+                "null",
+                "of",
+                "return",
+                "switch",
+                "this",
+                "throw",
+                "true",
+                "try",
+                "typeof",
+                "undefined",
+                "var",
+                "while",
+                "yield",
+            ],
+            line_comment: Some("//"),
+            block_comment: Some(("/*", "*/")),
+            quotes: &['"', '\'', '`'],
+        }
+    }
+
+    /// Returns the highlighter of `language`, if there is one
+    pub fn for_language(language: &str) -> Option<Self> {
+        match language {
+            "rust" | "rs" => Some(Self::rust()),
+            "javascript" | "js" | "typescript" | "ts" => Some(Self::javascript()),
+            _ => None,
+        }
+    }
+}
+
+impl Highlighter for SimpleHighlighter {
+    fn highlight_line(&self, line: &str, state: &mut u32) -> Vec<Token> {
+        let mut tokens: Vec<Token> = Vec::new();
+        let mut push = |text: &str, class| match tokens.last_mut() {
+            Some(last) if last.class == class && class.is_none() => last.text.push_str(text),
+            _ => tokens.push(Token::new(text, class)),
+        };
+
+        let mut rest = line;
+        while !rest.is_empty() {
+            if *state == IN_BLOCK_COMMENT {
+                let end = self.block_comment.map_or("", |(_, end)| end);
+                let len = match rest.find(end) {
+                    Some(index) if !end.is_empty() => {
+                        *state = 0;
+                        index + end.len()
+                    }
+                    _ => rest.len(),
+                };
+                push(&rest[..len], Some("comment"));
+                rest = &rest[len..];
+                continue;
+            }
+            if self
+                .line_comment
+                .map_or(false, |start| rest.starts_with(start))
+            {
+                push(rest, Some("comment"));
+                break;
+            }
+            if let Some((start, _)) = self
+                .block_comment
+                .filter(|(start, _)| rest.starts_with(start))
+            {
+                *state = IN_BLOCK_COMMENT;
+                push(start, Some("comment"));
+                rest = &rest[start.len()..];
+                continue;
+            }
+
+            let first = rest.chars().next().expect("not empty");
+            let len = if self.quotes.contains(&first) {
+                let mut escaped = false;
+                let end = rest[first.len_utf8()..].find(|c| {
+                    let end = c == first && !escaped;
+                    escaped = c == '\\' && !escaped;
+                    end
+                });
+                // Unterminated strings end with the line
+                let len = end.map_or(rest.len(), |end| end + 2 * first.len_utf8());
+                push(&rest[..len], Some("string"));
+                len
+            } else if first.is_alphanumeric() || first == '_' {
+                let len = rest
+                    .find(|c: char| {
+                        !(c.is_alphanumeric() || c == '_' || (first.is_ascii_digit() && c == '.'))
+                    })
+                    .unwrap_or_else(|| rest.len());
+                let word = &rest[..len];
+                let class = if first.is_ascii_digit() {
+                    Some("number")
+                } else if self.keywords.contains(&word) {
+                    Some("keyword")
+                } else {
+                    None
+                };
+                push(word, class);
+                len
+            } else {
+                push(&rest[..first.len_utf8()], None);
+                first.len_utf8()
+            };
+            rest = &rest[len..];
+        }
+        tokens
+    }
+}
+
+/// A shared [`Highlighter`]. Highlighters are equal if they are the same instance.
+#[derive(Clone)]
+pub struct HighlighterRef(Rc<dyn Highlighter>);
+
+impl HighlighterRef {
+    /// Wraps `highlighter`
+    pub fn new(highlighter: impl Highlighter + 'static) -> Self {
+        Self(Rc::new(highlighter))
+    }
+}
+
+impl PartialEq for HighlighterRef {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl fmt::Debug for HighlighterRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("HighlighterRef<_>")
+    }
+}
+
+/// Rendered lines by their text and the state at their start
+#[derive(Default)]
+struct LineCache {
+    lines: HashMap<(String, u32), (Html, u32)>,
+}
+
+impl LineCache {
+    fn render(&mut self, code: &str, highlighter: &dyn Highlighter) -> Vec<Html> {
+        let mut previous = std::mem::take(&mut self.lines);
+        let mut state = 0;
+        code.lines()
+            .map(|line| {
+                let key = (line.to_owned(), state);
+                let (html, end) = match previous
+                    .remove(&key)
+                    .or_else(|| self.lines.get(&key).cloned())
+                {
+                    Some(cached) => cached,
+                    None => {
+                        let mut end = state;
+                        let tokens = highlighter.highlight_line(line, &mut end);
+                        (render_line(tokens), end)
+                    }
+                };
+                state = end;
+                self.lines.insert(key, (html.clone(), end));
+                html
+            })
+            .collect()
+    }
+}
+
+fn render_line(tokens: Vec<Token>) -> Html {
+    let mut line = VTag::new("span");
+    line.add_attribute("class", "yew-code-line");
+    for token in tokens {
+        let text = VText::new(token.text).into();
+        match token.class {
+            Some(class) => {
+                let mut span = VTag::new("span");
+                span.add_attribute("class", class);
+                span.add_child(text);
+                line.add_child(span.into());
+            }
+            None => line.add_child(text),
+        }
+    }
+    line.add_child(VText::new("\n").into());
+    line.into()
+}
+
+/// Props for [`CodeBlock`]
+#[derive(Debug, Clone, PartialEq, Properties)]
+pub struct CodeBlockProps {
+    /// The code
+    pub code: AttrValue,
+    /// Language of the code, e.g. `rust`. It is added to the classes as `language-rust`.
+    #[prop_or_default]
+    pub language: Option<AttrValue>,
+    /// Tokenizes the code. Defaults to the [`SimpleHighlighter`] of the language, if any.
+    #[prop_or_default]
+    pub highlighter: Option<HighlighterRef>,
+    /// Classes of the `<pre>`
+    #[prop_or_default]
+    pub class: Option<String>,
+}
+
+/// Renders code in a `<pre>`, highlighted with a [`Highlighter`].
+///
+/// # Example
+/// ```rust
+/// # use yew::prelude::*;
+/// use yew::code_block::CodeBlock;
+///
+/// #[function_component(Example)]
+/// fn example() -> Html {
+///     html! { <CodeBlock language="rust" code="fn main() {\n    println!(\"hi\");\n}" /> }
+/// }
+/// ```
+pub struct CodeBlock {
+    /// The highlighter and language the cache was filled with
+    highlighted_with: (Option<HighlighterRef>, Option<AttrValue>),
+    highlighter: HighlighterRef,
+    cache: LineCache,
+    lines: Vec<Html>,
+}
+
+impl CodeBlock {
+    fn highlighter(props: &CodeBlockProps) -> HighlighterRef {
+        props.highlighter.clone().unwrap_or_else(|| {
+            match props
+                .language
+                .as_deref()
+                .and_then(SimpleHighlighter::for_language)
+            {
+                Some(highlighter) => HighlighterRef::new(highlighter),
+                None => HighlighterRef::new(PlainText),
+            }
+        })
+    }
+}
+
+impl Component for CodeBlock {
+    type Message = ();
+    type Properties = CodeBlockProps;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let props = ctx.props();
+        let highlighter = Self::highlighter(props);
+        let mut cache = LineCache::default();
+        let lines = cache.render(&props.code, &*highlighter.0);
+        Self {
+            highlighted_with: (props.highlighter.clone(), props.language.clone()),
+            highlighter,
+            cache,
+            lines,
+        }
+    }
+
+    fn changed(&mut self, ctx: &Context<Self>) -> bool {
+        let props = ctx.props();
+        let highlighted_with = (props.highlighter.clone(), props.language.clone());
+        if highlighted_with != self.highlighted_with {
+            self.highlighted_with = highlighted_with;
+            self.highlighter = Self::highlighter(props);
+            self.cache = LineCache::default();
+        }
+        self.lines = self.cache.render(&props.code, &*self.highlighter.0);
+        true
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let props = ctx.props();
+        let language = props
+            .language
+            .as_ref()
+            .map(|language| format!("language-{}", language));
+        html! {
+            <pre class={classes!("yew-code", language, props.class.clone())}>
+                <code>{ for self.lines.iter().cloned() }</code>
+            </pre>
+        }
+    }
+}
+
+impl fmt::Debug for CodeBlock {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CodeBlock")
+            .field("highlighter", &self.highlighter)
+            .field("lines", &self.lines.len())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    #[cfg(feature = "wasm_test")]
+    use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+
+    #[cfg(feature = "wasm_test")]
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    fn tokens(line: &str, state: &mut u32) -> Vec<(&'static str, String)> {
+        SimpleHighlighter::rust()
+            .highlight_line(line, state)
+            .into_iter()
+            .map(|token| (token.class.unwrap_or(""), token.text))
+            .collect()
+    }
+
+    #[test]
+    fn highlights_tokens() {
+        let mut state = 0;
+        assert_eq!(
+            tokens(r#"let x = "a\"b" + 1.5; // done"#, &mut state),
+            vec![
+                ("keyword", "let".to_string()),
+                ("", " x = ".to_string()),
+                ("string", r#""a\"b""#.to_string()),
+                ("", " + ".to_string()),
+                ("number", "1.5".to_string()),
+                ("", "; ".to_string()),
+                ("comment", "// done".to_string()),
+            ]
+        );
+
+        assert_eq!(
+            tokens("fn /* a", &mut state),
+            vec![
+                ("keyword", "fn".to_string()),
+                ("", " ".to_string()),
+                ("comment", "/*".to_string()),
+                ("comment", " a".to_string()),
+            ]
+        );
+        assert_eq!(state, IN_BLOCK_COMMENT);
+        assert_eq!(
+            tokens("b */ if", &mut state),
+            vec![
+                ("comment", "b */".to_string()),
+                ("", " ".to_string()),
+                ("keyword", "if".to_string()),
+            ]
+        );
+        assert_eq!(state, 0);
+    }
+
+    struct Counting(Cell<usize>);
+
+    impl Highlighter for Counting {
+        fn highlight_line(&self, line: &str, state: &mut u32) -> Vec<Token> {
+            self.0.set(self.0.get() + 1);
+            SimpleHighlighter::rust().highlight_line(line, state)
+        }
+    }
+
+    #[test]
+    fn tokenizes_changed_lines_only() {
+        let highlighter = Counting(Cell::new(0));
+        let mut cache = LineCache::default();
+        let first = cache.render("let a = 1;\nlet b = 2;", &highlighter);
+        assert_eq!(highlighter.0.get(), 2);
+
+        let second = cache.render("let a = 1;\nlet c = 0;\nlet b = 2;", &highlighter);
+        assert_eq!(highlighter.0.get(), 3);
+        assert_eq!(first[0], second[0]);
+        assert_eq!(first[1], second[2]);
+
+        // Lines after the start of a block comment are in another state
+        cache.render("/*\nlet a = 1;\nlet c = 0;\nlet b = 2;", &highlighter);
+        assert_eq!(highlighter.0.get(), 7);
+    }
+}
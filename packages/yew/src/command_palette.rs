@@ -0,0 +1,624 @@
+//! A command palette: a searchable list of the actions of an app, opened with a shortcut.
+//!
+//! Components register [`Command`]s with the closest [`CommandPalette`], with [`use_command`]
+//! or [`Scope::register_command`]. A command stays registered as long as its [`CommandHandle`] is
+//! alive, so commands come and go with the components that provide them.
+//!
+//! The palette opens with <kbd>Ctrl</kbd>+<kbd>K</kbd> by default. It is a modal
+//! [`Dialog`] rendered into the body through a [portal](crate::create_portal), containing a
+//! [`Combobox`] which matches the typed text fuzzily against the labels and keywords of the
+//! commands. Commands with a [`Shortcut`] also run when it is pressed anywhere in the page.
+
+use crate::a11y::widgets::{Combobox, Dialog, RenderItem};
+use crate::context::ContextProvider;
+use crate::functional::{use_context, use_hook};
+use crate::html::Scope;
+use crate::utils::{document, window};
+use crate::virtual_dom::AttrValue;
+use crate::{
+    classes, create_portal, html, Callback, Children, Component, Context, Html, Properties,
+};
+use gloo::events::EventListener;
+use std::cell::RefCell;
+use std::fmt;
+use std::rc::{Rc, Weak};
+use std::str::FromStr;
+use wasm_bindgen::JsCast;
+use web_sys::{Element, KeyboardEvent};
+
+/// Error of parsing a [`Shortcut`]
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum ShortcutError {
+    /// The shortcut has only modifiers
+    #[error("shortcut `{0}` has no key")]
+    NoKey(String),
+    /// A modifier is not one of Ctrl, Alt, Shift or Meta
+    #[error("unknown modifier `{0}`")]
+    UnknownModifier(String),
+}
+
+/// A key combination, like <kbd>Ctrl</kbd>+<kbd>Shift</kbd>+<kbd>P</kbd>.
+///
+/// Shortcuts are parsed from text like `"Ctrl+Shift+P"`. The key is the last part and compared
+/// case-insensitively with [`KeyboardEvent::key`], e.g. `"Enter"` or `"/"`. `Cmd` and `Super`
+/// are aliases of `Meta`, `Control` of `Ctrl` and `Option` of `Alt`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Shortcut {
+    ctrl: bool,
+    alt: bool,
+    shift: bool,
+    meta: bool,
+    key: String,
+}
+
+impl Shortcut {
+    /// Returns whether `event` is a press of this shortcut
+    pub fn matches(&self, event: &KeyboardEvent) -> bool {
+        event.ctrl_key() == self.ctrl
+            && event.alt_key() == self.alt
+            && event.shift_key() == self.shift
+            && event.meta_key() == self.meta
+            && event.key().to_lowercase() == self.key
+    }
+}
+
+impl FromStr for Shortcut {
+    type Err = ShortcutError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // The key may be `+` itself, as in `Ctrl++`
+        let (modifiers, key) = match s.strip_suffix("++") {
+            Some(modifiers) => (modifiers, "+"),
+            None => match s.rfind('+') {
+                Some(i) => (&s[..i], &s[i + 1..]),
+                None => ("", s),
+            },
+        };
+        if key.is_empty() {
+            return Err(ShortcutError::NoKey(s.to_owned()));
+        }
+
+        let mut shortcut = Self {
+            ctrl: false,
+            alt: false,
+            shift: false,
+            meta: false,
+            key: key.to_lowercase(),
+        };
+        for modifier in modifiers.split('+').filter(|m| !m.is_empty()) {
+            let flag = match modifier.to_lowercase().as_str() {
+                "ctrl" | "control" => &mut shortcut.ctrl,
+                "alt" | "option" => &mut shortcut.alt,
+                "shift" => &mut shortcut.shift,
+                "meta" | "cmd" | "super" => &mut shortcut.meta,
+                _ => return Err(ShortcutError::UnknownModifier(modifier.to_owned())),
+            };
+            *flag = true;
+        }
+        Ok(shortcut)
+    }
+}
+
+impl fmt::Display for Shortcut {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let modifiers = [
+            (self.ctrl, "Ctrl+"),
+            (self.alt, "Alt+"),
+            (self.shift, "Shift+"),
+            (self.meta, "Meta+"),
+        ];
+        for (_, name) in modifiers.iter().filter(|(on, _)| *on) {
+            f.write_str(name)?;
+        }
+        let mut chars = self.key.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => write!(f, "{}", c.to_uppercase()),
+            _ => {
+                // Named keys like `enter` are shown as `Enter`
+                let mut chars = self.key.chars();
+                let first = chars.next().map(|c| c.to_uppercase().to_string());
+                write!(f, "{}{}", first.unwrap_or_default(), chars.as_str())
+            }
+        }
+    }
+}
+
+/// An action listed in a [`CommandPalette`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Command {
+    /// Text shown in the palette
+    pub label: AttrValue,
+    /// Other words the command is found with
+    pub keywords: Vec<AttrValue>,
+    /// Runs the command without opening the palette
+    pub shortcut: Option<Shortcut>,
+    /// Called when the command runs
+    pub action: Callback<()>,
+}
+
+impl Command {
+    /// Creates a command
+    pub fn new(label: impl Into<AttrValue>, action: Callback<()>) -> Self {
+        Self {
+            label: label.into(),
+            keywords: Vec::new(),
+            shortcut: None,
+            action,
+        }
+    }
+
+    /// Adds keywords the command is found with
+    pub fn keywords<K: Into<AttrValue>>(mut self, keywords: impl IntoIterator<Item = K>) -> Self {
+        self.keywords.extend(keywords.into_iter().map(Into::into));
+        self
+    }
+
+    /// Sets the shortcut of the command
+    pub fn shortcut(mut self, shortcut: Shortcut) -> Self {
+        self.shortcut = Some(shortcut);
+        self
+    }
+
+    /// Returns how well `query` matches the command, see [`fuzzy_score`]
+    fn score(&self, query: &str) -> Option<u32> {
+        std::iter::once(&self.label)
+            .chain(&self.keywords)
+            .filter_map(|text| fuzzy_score(query, text))
+            .max()
+    }
+}
+
+/// Returns how well `query` matches `text`, or `None` if it doesn't.
+///
+/// The characters of `query` have to appear in `text` in the same order, ignoring case. Matches
+/// score higher the more of them are consecutive or at the start of words.
+pub fn fuzzy_score(query: &str, text: &str) -> Option<u32> {
+    let mut score = 0;
+    let mut text = text.chars().flat_map(char::to_lowercase).enumerate();
+    let mut previous: Option<(usize, char)> = None;
+    for wanted in query.chars().flat_map(char::to_lowercase) {
+        if wanted.is_whitespace() {
+            continue;
+        }
+        let mut before = previous.map(|(_, c)| c);
+        let (index, _) = loop {
+            let (index, c) = text.next()?;
+            if c == wanted {
+                break (index, c);
+            }
+            before = Some(c);
+        };
+        score += 1;
+        if before.map_or(true, |c| !c.is_alphanumeric()) {
+            score += 5;
+        }
+        if previous.map_or(false, |(i, _)| i + 1 == index) {
+            score += 3;
+        }
+        previous = Some((index, wanted));
+    }
+    Some(score)
+}
+
+/// Returns the indices of the commands matching `query`, best matches first
+fn search(commands: &[Command], query: &str) -> Vec<usize> {
+    let mut matches: Vec<_> = commands
+        .iter()
+        .enumerate()
+        .filter_map(|(i, command)| Some((command.score(query)?, i)))
+        .collect();
+    // Stable, so equal matches stay in the order they were registered
+    matches.sort_by(|a, b| b.0.cmp(&a.0));
+    matches.into_iter().map(|(_, i)| i).collect()
+}
+
+#[derive(Debug)]
+struct Registry {
+    next_id: usize,
+    commands: Vec<(usize, Command)>,
+    /// Shortcut of the palette itself
+    toggle: Shortcut,
+}
+
+/// The commands of a [`CommandPalette`], provided to its descendants as a context
+#[derive(Debug, Clone)]
+pub struct Commands {
+    registry: Rc<RefCell<Registry>>,
+    link: Callback<CommandPaletteMsg>,
+}
+
+impl PartialEq for Commands {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.registry, &other.registry)
+    }
+}
+
+impl Commands {
+    /// Registers `command` until the returned handle is dropped
+    pub fn register(&self, command: Command) -> CommandHandle {
+        let mut registry = self.registry.borrow_mut();
+        let id = registry.next_id;
+        registry.next_id += 1;
+        registry.commands.push((id, command));
+        self.link.emit(CommandPaletteMsg::Changed);
+        CommandHandle {
+            id,
+            registry: Rc::downgrade(&self.registry),
+            link: self.link.clone(),
+        }
+    }
+
+    /// Opens the palette
+    pub fn open(&self) {
+        self.link.emit(CommandPaletteMsg::Open);
+    }
+
+    /// Closes the palette
+    pub fn close(&self) {
+        self.link.emit(CommandPaletteMsg::Close);
+    }
+
+    /// Returns the registered commands
+    pub fn list(&self) -> Vec<Command> {
+        let registry = self.registry.borrow();
+        registry.commands.iter().map(|(_, c)| c.clone()).collect()
+    }
+}
+
+/// Keeps a [`Command`] registered. Dropping it removes the command from the palette.
+#[derive(Debug)]
+#[must_use = "the command is unregistered when the handle is dropped"]
+pub struct CommandHandle {
+    id: usize,
+    registry: Weak<RefCell<Registry>>,
+    link: Callback<CommandPaletteMsg>,
+}
+
+impl CommandHandle {
+    /// Replaces the registered command, e.g. with one capturing newer state
+    pub fn set(&self, command: Command) {
+        let registry = match self.registry.upgrade() {
+            Some(registry) => registry,
+            None => return,
+        };
+        let mut registry = registry.borrow_mut();
+        if let Some((_, current)) = registry.commands.iter_mut().find(|(id, _)| *id == self.id) {
+            // A new action alone doesn't change what the palette shows
+            let shown = current.label != command.label
+                || current.keywords != command.keywords
+                || current.shortcut != command.shortcut;
+            *current = command;
+            if shown {
+                self.link.emit(CommandPaletteMsg::Changed);
+            }
+        }
+    }
+}
+
+impl Drop for CommandHandle {
+    fn drop(&mut self) {
+        if let Some(registry) = self.registry.upgrade() {
+            let id = self.id;
+            registry.borrow_mut().commands.retain(|(i, _)| *i != id);
+            self.link.emit(CommandPaletteMsg::Changed);
+        }
+    }
+}
+
+/// Registers `command` with the closest [`CommandPalette`] while the component is mounted.
+///
+/// The command is replaced on every render, so its action may capture the current state.
+///
+/// # Panics
+/// Panics if the component is not a descendant of a [`CommandPalette`].
+///
+/// # Example
+/// ```rust
+/// # use yew::prelude::*;
+/// use yew::command_palette::{use_command, Command};
+///
+/// #[function_component(Counter)]
+/// fn counter() -> Html {
+///     let count = use_state(|| 0);
+///     let reset = {
+///         let count = count.clone();
+///         Callback::from(move |_| count.set(0))
+///     };
+///     use_command(Command::new("Reset counter", reset).keywords(["zero", "clear"]));
+///     html! { <p>{ *count }</p> }
+/// }
+/// ```
+pub fn use_command(command: Command) {
+    let commands =
+        use_context::<Commands>().expect("use_command must be used inside a CommandPalette");
+    use_hook(
+        || None,
+        move |handle: &mut Option<CommandHandle>, _| match handle {
+            Some(handle) => handle.set(command),
+            None => *handle = Some(commands.register(command)),
+        },
+        |handle| drop(handle.take()),
+    );
+}
+
+impl<COMP: Component> Scope<COMP> {
+    /// Registers `command` with the closest [`CommandPalette`] until the returned handle is
+    /// dropped. Returns `None` if the component is not a descendant of one.
+    ///
+    /// Keep the handle in the component to register the command while it is mounted.
+    pub fn register_command(&self, command: Command) -> Option<CommandHandle> {
+        let (commands, _handle) = self.context::<Commands>(Callback::noop())?;
+        Some(commands.register(command))
+    }
+}
+
+fn default_shortcut() -> Shortcut {
+    "Ctrl+K".parse().unwrap()
+}
+
+/// Props for [`CommandPalette`]
+#[derive(Debug, Clone, PartialEq, Properties)]
+pub struct CommandPaletteProps {
+    /// Opens and closes the palette
+    #[prop_or_else(default_shortcut)]
+    pub shortcut: Shortcut,
+    /// Accessible name of the search input
+    #[prop_or(AttrValue::Borrowed("Search commands"))]
+    pub label: AttrValue,
+    /// Maximum number of matches shown
+    #[prop_or(10)]
+    pub max: usize,
+    /// Classes of the dialog
+    #[prop_or_default]
+    pub class: crate::Classes,
+    /// The components that may register commands
+    #[prop_or_default]
+    pub children: Children,
+}
+
+#[doc(hidden)]
+#[derive(Debug)]
+pub enum CommandPaletteMsg {
+    Open,
+    Close,
+    Toggle,
+    Changed,
+    Query(String),
+    /// Runs the match at this position
+    Run(usize),
+}
+
+/// Provides [`Commands`] to its children and shows them in a palette, see the
+/// [module docs](self).
+///
+/// The dialog has the class `yew-command-palette`; the label and the shortcut of a match are in
+/// elements with the classes `yew-command-label` and `yew-command-shortcut`.
+///
+/// # Example
+/// ```rust
+/// # use yew::prelude::*;
+/// use yew::command_palette::{CommandPalette, Shortcut};
+/// # #[function_component(Editor)]
+/// # fn editor() -> Html { html! {} }
+///
+/// # fn view() -> Html {
+/// let shortcut: Shortcut = "Ctrl+Shift+P".parse().unwrap();
+/// html! {
+///     <CommandPalette {shortcut}>
+///         <Editor />
+///     </CommandPalette>
+/// }
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct CommandPalette {
+    commands: Commands,
+    host: Element,
+    open: bool,
+    query: String,
+    _listener: EventListener,
+}
+
+impl CommandPalette {
+    /// Returns the commands matching the query, best matches first
+    fn matches(&self, ctx: &Context<Self>) -> Vec<Command> {
+        let registry = self.commands.registry.borrow();
+        let commands: Vec<_> = registry.commands.iter().map(|(_, c)| c.clone()).collect();
+        let mut matches = search(&commands, &self.query);
+        matches.truncate(ctx.props().max);
+        matches.into_iter().map(|i| commands[i].clone()).collect()
+    }
+
+    fn set_open(&mut self, open: bool) -> bool {
+        if self.open == open {
+            return false;
+        }
+        self.open = open;
+        self.query.clear();
+        true
+    }
+}
+
+impl Component for CommandPalette {
+    type Message = CommandPaletteMsg;
+    type Properties = CommandPaletteProps;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let registry = Rc::new(RefCell::new(Registry {
+            next_id: 0,
+            commands: Vec::new(),
+            toggle: ctx.props().shortcut.clone(),
+        }));
+        let link = ctx.link().callback(|msg| msg);
+
+        let listener = {
+            let registry = Rc::clone(&registry);
+            let link = link.clone();
+            EventListener::new(&window(), "keydown", move |e| {
+                let e = e.unchecked_ref::<KeyboardEvent>();
+                if e.default_prevented() {
+                    return;
+                }
+                let registry = registry.borrow();
+                if registry.toggle.matches(e) {
+                    e.prevent_default();
+                    drop(registry);
+                    link.emit(CommandPaletteMsg::Toggle);
+                    return;
+                }
+                let action = registry.commands.iter().find_map(|(_, command)| {
+                    let shortcut = command.shortcut.as_ref()?;
+                    shortcut.matches(e).then(|| command.action.clone())
+                });
+                drop(registry);
+                if let Some(action) = action {
+                    e.prevent_default();
+                    action.emit(());
+                }
+            })
+        };
+
+        Self {
+            commands: Commands { registry, link },
+            host: document().body().expect("no body node found").into(),
+            open: false,
+            query: String::new(),
+            _listener: listener,
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            CommandPaletteMsg::Open => self.set_open(true),
+            CommandPaletteMsg::Close => self.set_open(false),
+            CommandPaletteMsg::Toggle => self.set_open(!self.open),
+            CommandPaletteMsg::Changed => self.open,
+            CommandPaletteMsg::Query(query) => {
+                self.query = query;
+                true
+            }
+            CommandPaletteMsg::Run(position) => {
+                let command = match self.matches(ctx).into_iter().nth(position) {
+                    Some(command) => command,
+                    None => return false,
+                };
+                self.set_open(false);
+                command.action.emit(());
+                true
+            }
+        }
+    }
+
+    fn changed(&mut self, ctx: &Context<Self>) -> bool {
+        self.commands.registry.borrow_mut().toggle = ctx.props().shortcut.clone();
+        true
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let props = ctx.props();
+        let palette = if self.open {
+            let link = ctx.link();
+            let matches = Rc::new(self.matches(ctx));
+            let len = matches.len();
+            let render = RenderItem::new(move |item| {
+                let command = &matches[item.index];
+                let shortcut = command.shortcut.as_ref().map(|shortcut| {
+                    html! { <kbd class="yew-command-shortcut">{ shortcut.to_string() }</kbd> }
+                });
+                html! {
+                    <>
+                        <span class="yew-command-label">{ command.label.clone() }</span>
+                        { for shortcut }
+                    </>
+                }
+            });
+            // Enter runs the best match unless the combobox chose an option
+            let onkeydown = link.batch_callback(|e: KeyboardEvent| {
+                (e.key() == "Enter" && !e.default_prevented()).then(|| CommandPaletteMsg::Run(0))
+            });
+            let palette = html! {
+                <Dialog
+                    onclose={link.callback(|_| CommandPaletteMsg::Close)}
+                    class={classes!("yew-command-palette", props.class.clone())}
+                >
+                    <div {onkeydown}>
+                        <Combobox
+                            id="yew-command-palette"
+                            value={self.query.clone()}
+                            oninput={link.callback(CommandPaletteMsg::Query)}
+                            {len}
+                            {render}
+                            onselect={link.callback(CommandPaletteMsg::Run)}
+                            label={props.label.clone()}
+                        />
+                    </div>
+                </Dialog>
+            };
+            create_portal(palette, self.host.clone())
+        } else {
+            Html::default()
+        };
+
+        html! {
+            <ContextProvider<Commands> context={self.commands.clone()}>
+                { for props.children.iter() }
+                { palette }
+            </ContextProvider<Commands>>
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "wasm_test")]
+    use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+
+    #[cfg(feature = "wasm_test")]
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    #[test]
+    fn parses_shortcuts() {
+        let shortcut: Shortcut = "ctrl+shift+p".parse().unwrap();
+        assert_eq!(shortcut.to_string(), "Ctrl+Shift+P");
+        assert_eq!(
+            "Cmd+Enter".parse::<Shortcut>().unwrap().to_string(),
+            "Meta+Enter"
+        );
+        assert_eq!("Ctrl++".parse::<Shortcut>().unwrap().to_string(), "Ctrl++");
+        assert_eq!("/".parse::<Shortcut>().unwrap().to_string(), "/");
+        assert_eq!(
+            "Ctrl+".parse::<Shortcut>(),
+            Err(ShortcutError::NoKey("Ctrl+".to_owned()))
+        );
+        assert_eq!(
+            "Hyper+K".parse::<Shortcut>(),
+            Err(ShortcutError::UnknownModifier("Hyper".to_owned()))
+        );
+    }
+
+    #[test]
+    fn fuzzy_matches_in_order() {
+        assert_eq!(fuzzy_score("", "Open file"), Some(0));
+        assert!(fuzzy_score("of", "Open file").is_some());
+        assert!(fuzzy_score("fo", "Open file").is_none());
+        assert!(fuzzy_score("OPEN", "open file").is_some());
+        // Word starts and consecutive characters score higher
+        assert!(fuzzy_score("of", "Open file") > fuzzy_score("of", "Proof"));
+        assert!(fuzzy_score("file", "Open file") > fuzzy_score("file", "Find illegal"));
+    }
+
+    #[test]
+    fn search_ranks_matches() {
+        let commands = vec![
+            Command::new("Close tab", Callback::noop()),
+            Command::new("Open file", Callback::noop()),
+            Command::new("Settings", Callback::noop()).keywords(["preferences", "options"]),
+        ];
+        assert_eq!(search(&commands, ""), vec![0, 1, 2]);
+        assert_eq!(search(&commands, "of"), vec![1]);
+        assert_eq!(search(&commands, "pref"), vec![2]);
+        assert_eq!(search(&commands, "o"), vec![1, 2, 0]);
+    }
+}
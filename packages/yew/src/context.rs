@@ -1,9 +1,15 @@
-//! This module defines the `ContextProvider` component.
+//! This module defines the `ContextProvider` component and the [`Service`] handles that can be
+//! provided through it.
 
 use crate::html::Scope;
 use crate::{html, Callback, Children, Component, Context, Html, Properties};
 use slab::Slab;
 use std::cell::RefCell;
+use std::fmt;
+use std::ops::Deref;
+use std::rc::Rc;
+
+pub use yew_macro::Service;
 
 /// Props for [`ContextProvider`]
 #[derive(Debug, Clone, PartialEq, Properties)]
@@ -62,6 +68,11 @@ impl<T: Clone + PartialEq> ContextProvider<T> {
         )
     }
 
+    /// Returns the current context value without subscribing to changes.
+    pub(crate) fn current(&self) -> T {
+        self.context.clone()
+    }
+
     /// Notify all subscribed consumers and remove dropped consumers from the list.
     fn notify_consumers(&mut self) {
         let consumers: Vec<Callback<T>> = self
@@ -110,3 +121,127 @@ impl<T: Clone + PartialEq + 'static> Component for ContextProvider<T> {
         html! { <>{ self.children.clone() }</> }
     }
 }
+
+/// A shared handle to a service, looked up by its interface `I` rather than its concrete type.
+///
+/// `I` is usually a trait object, such as `dyn Logger`. Providing a `Service<dyn Logger>` with a
+/// [`ServiceProvider`] lets components deep in the tree use whichever implementation was
+/// provided, so that a fake can be swapped in for the real one in tests.
+///
+/// Two handles are equal if they point to the same service.
+///
+/// # Example
+/// ```rust
+/// # use yew::prelude::*;
+/// use yew::context::{IntoService, Service, ServiceProvider};
+///
+/// trait Logger {
+///     fn log(&self, message: &str);
+/// }
+///
+/// #[derive(Service)]
+/// #[service(dyn Logger)]
+/// struct ConsoleLogger;
+///
+/// impl Logger for ConsoleLogger {
+///     fn log(&self, message: &str) {
+///         // ...
+/// #       let _ = message;
+///     }
+/// }
+///
+/// #[function_component(SaveButton)]
+/// fn save_button() -> Html {
+///     let logger = use_service::<dyn Logger>().expect("no logger provided");
+///     let onclick = Callback::from(move |_| logger.log("saved"));
+///     html! { <button {onclick}>{ "Save" }</button> }
+/// }
+///
+/// #[function_component(App)]
+/// fn app() -> Html {
+///     type LoggerProvider = ServiceProvider<dyn Logger>;
+///     html! {
+///         <LoggerProvider context={ConsoleLogger.into_service()}>
+///             <SaveButton />
+///         </LoggerProvider>
+///     }
+/// }
+/// ```
+pub struct Service<I: ?Sized>(Rc<I>);
+
+impl<I: ?Sized> Service<I> {
+    /// Creates a new handle to the service. The concrete type of the service is coerced to the
+    /// interface, e.g. `Service::<dyn Logger>::new(Rc::new(ConsoleLogger))`.
+    pub fn new(service: Rc<I>) -> Self {
+        Self(service)
+    }
+
+    /// Returns the shared service
+    pub fn as_rc(&self) -> &Rc<I> {
+        &self.0
+    }
+}
+
+impl<I: ?Sized> Clone for Service<I> {
+    fn clone(&self) -> Self {
+        Self(Rc::clone(&self.0))
+    }
+}
+
+impl<I: ?Sized> PartialEq for Service<I> {
+    fn eq(&self, other: &Self) -> bool {
+        // https://github.com/rust-lang/rust-clippy/issues/6524
+        #[allow(clippy::vtable_address_comparisons)]
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl<I: ?Sized> Deref for Service<I> {
+    type Target = I;
+
+    fn deref(&self) -> &I {
+        &self.0
+    }
+}
+
+impl<I: ?Sized> From<Rc<I>> for Service<I> {
+    fn from(service: Rc<I>) -> Self {
+        Self(service)
+    }
+}
+
+impl<I: ?Sized> fmt::Debug for Service<I> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Service<_>")
+    }
+}
+
+/// Conversion of a concrete service into a [`Service`] handle for the interface `I`.
+///
+/// This is usually implemented with `#[derive(Service)]`, listing the interfaces in a
+/// `#[service(...)]` attribute:
+/// ```rust
+/// # trait Logger {}
+/// # trait Clock {}
+/// #[derive(yew::context::Service)]
+/// #[service(dyn Logger, dyn Clock)]
+/// struct System;
+/// # impl Logger for System {}
+/// # impl Clock for System {}
+/// ```
+pub trait IntoService<I: ?Sized> {
+    /// Wraps `self` in a [`Service`] handle
+    fn into_service(self) -> Service<I>;
+}
+
+impl<I: ?Sized> IntoService<I> for Rc<I> {
+    fn into_service(self) -> Service<I> {
+        Service(self)
+    }
+}
+
+/// A [`ContextProvider`] of the service implementing the interface `I`.
+///
+/// Use it through a type alias in `html!`, e.g.
+/// `type LoggerProvider = ServiceProvider<dyn Logger>;`.
+pub type ServiceProvider<I> = ContextProvider<Service<I>>;
@@ -0,0 +1,652 @@
+//! Context menus opened with a right click or the context menu key.
+//!
+//! A [`ContextMenu`] wraps a subtree and replaces the browser's context menu inside it with a
+//! menu of [`MenuEntry`]s. The menu follows the
+//! [menu pattern](https://www.w3.org/TR/wai-aria-practices-1.1/#menu) of the WAI-ARIA Authoring
+//! Practices: the arrow keys move between the items and in and out of submenus, Enter and Space
+//! activate an item, and typing a character moves to the next item starting with it.
+
+use crate::a11y::widgets::{navigate, Orientation};
+use crate::html::{Component, Context, Html, NodeRef};
+use crate::popover::{compute_position, Align, Placement, PositionOptions, Rect, Side};
+use crate::utils::{document, window};
+use crate::virtual_dom::AttrValue;
+use crate::{classes, create_portal, html, Callback, Children, Properties};
+use gloo::events::{EventListener, EventListenerOptions};
+use std::marker::PhantomData;
+use wasm_bindgen::JsCast;
+use web_sys::{Element, HtmlElement, KeyboardEvent, MouseEvent, Node};
+
+/// An entry of a [`ContextMenu`]
+#[derive(Debug, Clone, PartialEq)]
+pub enum MenuEntry<A> {
+    /// An item which emits its action when activated
+    Item {
+        /// Text of the item
+        label: AttrValue,
+        /// Passed to [`ContextMenuProps::onselect`]
+        action: A,
+        /// The item can be focused but not activated
+        disabled: bool,
+    },
+    /// An item which opens a submenu
+    Submenu {
+        /// Text of the item
+        label: AttrValue,
+        /// Entries of the submenu
+        entries: Vec<MenuEntry<A>>,
+    },
+    /// A line between groups of items
+    Separator,
+}
+
+impl<A> MenuEntry<A> {
+    /// Creates an item with an action
+    pub fn item(label: impl Into<AttrValue>, action: A) -> Self {
+        Self::Item {
+            label: label.into(),
+            action,
+            disabled: false,
+        }
+    }
+
+    /// Creates an item opening a submenu
+    pub fn submenu(label: impl Into<AttrValue>, entries: Vec<MenuEntry<A>>) -> Self {
+        Self::Submenu {
+            label: label.into(),
+            entries,
+        }
+    }
+
+    /// Disables an item. Other entries are returned unchanged.
+    pub fn disabled(mut self) -> Self {
+        if let Self::Item { disabled, .. } = &mut self {
+            *disabled = true;
+        }
+        self
+    }
+
+    fn label(&self) -> Option<&str> {
+        match self {
+            Self::Item { label, .. } | Self::Submenu { label, .. } => Some(label),
+            Self::Separator => None,
+        }
+    }
+}
+
+/// Returns the indices of the entries that can be focused
+fn focusable<A>(entries: &[MenuEntry<A>]) -> Vec<usize> {
+    (0..entries.len())
+        .filter(|&i| entries[i].label().is_some())
+        .collect()
+}
+
+/// Returns the entry to focus after pressing `key` on the entry at `current`
+fn next_focus<A>(entries: &[MenuEntry<A>], current: usize, key: &str) -> Option<usize> {
+    let focusable = focusable(entries);
+    let position = focusable.iter().position(|&i| i == current);
+    if let Some(next) = navigate(key, position, focusable.len(), Orientation::Vertical) {
+        return Some(focusable[next]);
+    }
+
+    // Type-ahead: the next item whose label starts with the typed character
+    let mut chars = key.chars();
+    let typed = match (chars.next(), chars.next()) {
+        (Some(c), None) if !c.is_whitespace() => c,
+        _ => return None,
+    };
+    let starts_with = |i: &usize| {
+        entries[*i]
+            .label()
+            .and_then(|label| label.chars().next())
+            .map_or(false, |c| c.to_lowercase().eq(typed.to_lowercase()))
+    };
+    let len = entries.len();
+    (current + 1..len)
+        .chain(0..=current.min(len))
+        .find(starts_with)
+}
+
+/// Returns the entries shown at `level` when the submenus at the focused items of `levels` are
+/// open
+fn entries_at<'a, A>(
+    items: &'a [MenuEntry<A>],
+    levels: &[Level],
+    level: usize,
+) -> Option<&'a [MenuEntry<A>]> {
+    let mut entries = items;
+    for parent in levels.get(..level)? {
+        match entries.get(parent.focused)? {
+            MenuEntry::Submenu {
+                entries: submenu, ..
+            } => entries = submenu,
+            _ => return None,
+        }
+    }
+    Some(entries)
+}
+
+/// Props for [`ContextMenu`]
+#[derive(Debug, Clone, PartialEq, Properties)]
+pub struct ContextMenuProps<A: Clone + PartialEq + 'static> {
+    /// Entries of the menu
+    pub items: Vec<MenuEntry<A>>,
+    /// Called with the action of an activated item
+    pub onselect: Callback<A>,
+    /// Accessible name of the menu
+    #[prop_or_default]
+    pub label: Option<AttrValue>,
+    /// Classes of the menu and its submenus
+    #[prop_or_default]
+    pub class: Option<String>,
+    /// The subtree in which the menu is opened
+    #[prop_or_default]
+    pub children: Children,
+}
+
+#[doc(hidden)]
+pub enum ContextMenuMsg {
+    Open {
+        x: f64,
+        y: f64,
+    },
+    Hover {
+        level: usize,
+        index: usize,
+    },
+    Key {
+        level: usize,
+        key: String,
+    },
+    Activate {
+        level: usize,
+        index: usize,
+    },
+    /// A pointer or scroll event outside of the menu, or the window lost focus
+    Dismiss(Option<Node>),
+}
+
+/// An open menu or submenu
+#[derive(Debug)]
+struct Level {
+    focused: usize,
+    menu: NodeRef,
+    items: Vec<NodeRef>,
+}
+
+impl Level {
+    fn new<A>(entries: &[MenuEntry<A>]) -> Self {
+        Self {
+            focused: focusable(entries).first().copied().unwrap_or(0),
+            menu: NodeRef::default(),
+            items: vec![NodeRef::default(); entries.len()],
+        }
+    }
+}
+
+/// Replaces the browser's context menu in its children with a menu of [`MenuEntry`]s.
+///
+/// The menu is rendered into the body at the pointer, or below the focused element if it was
+/// opened with the keyboard. Submenus open next to their item, on whichever side fits. The menus
+/// have the class `yew-context-menu`.
+///
+/// # Example
+/// ```rust
+/// # use yew::prelude::*;
+/// use yew::context_menu::{ContextMenu, MenuEntry};
+///
+/// #[derive(Clone, PartialEq)]
+/// enum Action {
+///     Copy,
+///     Paste,
+///     Share(&'static str),
+/// }
+///
+/// #[function_component(File)]
+/// fn file() -> Html {
+///     let items = vec![
+///         MenuEntry::item("Copy", Action::Copy),
+///         MenuEntry::item("Paste", Action::Paste).disabled(),
+///         MenuEntry::Separator,
+///         MenuEntry::submenu(
+///             "Share",
+///             vec![
+///                 MenuEntry::item("Email", Action::Share("email")),
+///                 MenuEntry::item("Link", Action::Share("link")),
+///             ],
+///         ),
+///     ];
+///     let onselect = Callback::from(|action: Action| {
+///         // ...
+///     });
+///     html! {
+///         <ContextMenu<Action> {items} {onselect} label="File">
+///             <div class="file">{ "report.pdf" }</div>
+///         </ContextMenu<Action>>
+///     }
+/// }
+/// ```
+#[derive(Debug)]
+pub struct ContextMenu<A: Clone + PartialEq + 'static> {
+    host: Element,
+    /// Where the menu was opened, if it is open
+    point: Option<(f64, f64)>,
+    levels: Vec<Level>,
+    /// Focus the focused item of this level after the next render
+    move_focus: Option<usize>,
+    /// The element that had focus before the menu was opened
+    restore_focus: Option<HtmlElement>,
+    listeners: Vec<EventListener>,
+    _action: PhantomData<A>,
+}
+
+impl<A: Clone + PartialEq + 'static> ContextMenu<A> {
+    fn open(&mut self, ctx: &Context<Self>, x: f64, y: f64) -> bool {
+        if ctx.props().items.is_empty() {
+            return false;
+        }
+        if self.point.is_none() {
+            self.restore_focus = document()
+                .active_element()
+                .and_then(|el| el.dyn_into().ok());
+        }
+        self.point = Some((x, y));
+        self.levels = vec![Level::new(&ctx.props().items)];
+        self.move_focus = Some(0);
+
+        let window = window();
+        let dismiss = |event, options| {
+            let link = ctx.link().clone();
+            EventListener::new_with_options(&window, event, options, move |e| {
+                let target = e.target().and_then(|target| target.dyn_into().ok());
+                link.send_message(ContextMenuMsg::Dismiss(target))
+            })
+        };
+        let capture = EventListenerOptions::run_in_capture_phase();
+        self.listeners = vec![
+            dismiss("pointerdown", capture),
+            dismiss("scroll", capture),
+            // `blur` of the window itself, not of the elements in it
+            dismiss("blur", EventListenerOptions::default()),
+            dismiss("resize", EventListenerOptions::default()),
+        ];
+        true
+    }
+
+    fn close(&mut self) -> bool {
+        if self.point.take().is_none() {
+            return false;
+        }
+        self.levels.clear();
+        self.listeners.clear();
+        if let Some(el) = self.restore_focus.take() {
+            let _ = el.focus();
+        }
+        true
+    }
+
+    /// Opens the submenu of the focused item at `level`, closing any deeper submenus
+    fn open_submenu(&mut self, ctx: &Context<Self>, level: usize) -> bool {
+        self.levels.truncate(level + 1);
+        let entries = match entries_at(&ctx.props().items, &self.levels, level + 1) {
+            Some(entries) if !focusable(entries).is_empty() => entries,
+            _ => return false,
+        };
+        self.levels.push(Level::new(entries));
+        true
+    }
+
+    fn focus(&mut self, level: usize, index: usize) {
+        self.levels.truncate(level + 1);
+        if let Some(state) = self.levels.get_mut(level) {
+            state.focused = index;
+            self.move_focus = Some(level);
+        }
+    }
+
+    fn activate(&mut self, ctx: &Context<Self>, level: usize, index: usize) -> bool {
+        let props = ctx.props();
+        match entries_at(&props.items, &self.levels, level).and_then(|e| e.get(index)) {
+            Some(MenuEntry::Item {
+                action,
+                disabled: false,
+                ..
+            }) => {
+                let action = action.clone();
+                self.close();
+                props.onselect.emit(action);
+            }
+            Some(MenuEntry::Submenu { .. }) => {
+                self.focus(level, index);
+                if self.open_submenu(ctx, level) {
+                    self.move_focus = Some(level + 1);
+                }
+            }
+            _ => return false,
+        }
+        true
+    }
+
+    fn key(&mut self, ctx: &Context<Self>, level: usize, key: &str) -> bool {
+        let focused = match self.levels.get(level) {
+            Some(state) => state.focused,
+            None => return false,
+        };
+        match key {
+            "ArrowRight" => {
+                if !self.open_submenu(ctx, level) {
+                    return false;
+                }
+                self.move_focus = Some(level + 1);
+            }
+            "ArrowLeft" | "Escape" if level > 0 => {
+                self.levels.truncate(level);
+                self.move_focus = Some(level - 1);
+            }
+            "Escape" | "Tab" => return self.close(),
+            "Enter" | " " => return self.activate(ctx, level, focused),
+            key => {
+                let entries = entries_at(&ctx.props().items, &self.levels, level);
+                match entries.and_then(|entries| next_focus(entries, focused, key)) {
+                    Some(index) => self.focus(level, index),
+                    None => return false,
+                }
+            }
+        }
+        true
+    }
+
+    fn view_level(&self, ctx: &Context<Self>, level: usize) -> Html {
+        let props = ctx.props();
+        let link = ctx.link();
+        let state = &self.levels[level];
+        let entries = match entries_at(&props.items, &self.levels, level) {
+            Some(entries) => entries,
+            None => return Html::default(),
+        };
+        let label = match level {
+            0 => props.label.clone(),
+            _ => entries_at(&props.items, &self.levels, level - 1)
+                .and_then(|parent| parent.get(self.levels[level - 1].focused))
+                .and_then(|parent| parent.label())
+                .map(|label| AttrValue::from(label.to_owned())),
+        };
+        let onkeydown = link.batch_callback(move |e: KeyboardEvent| {
+            let key = e.key();
+            let handled = matches!(
+                key.as_str(),
+                "ArrowUp" | "ArrowDown" | "ArrowLeft" | "ArrowRight" | "Home" | "End"
+            ) || matches!(key.as_str(), "Enter" | " " | "Escape")
+                || key.chars().count() == 1;
+            if handled {
+                e.prevent_default();
+            } else if key != "Tab" {
+                return None;
+            }
+            Some(ContextMenuMsg::Key { level, key })
+        });
+
+        let entry = |(index, entry): (usize, &MenuEntry<A>)| {
+            let (label, disabled, expanded) = match entry {
+                MenuEntry::Separator => return html! { <li role="separator" /> },
+                MenuEntry::Item {
+                    label, disabled, ..
+                } => (label.clone(), *disabled, None),
+                MenuEntry::Submenu { label, .. } => {
+                    let expanded = self.levels.len() > level + 1 && state.focused == index;
+                    (label.clone(), false, Some(expanded.to_string()))
+                }
+            };
+            html! {
+                <li
+                    role="menuitem"
+                    ref={state.items.get(index).cloned().unwrap_or_default()}
+                    tabindex={if index == state.focused { "0" } else { "-1" }}
+                    aria-disabled={if disabled { Some("true") } else { None }}
+                    aria-haspopup={expanded.as_ref().map(|_| "menu")}
+                    aria-expanded={expanded}
+                    onclick={link.callback(move |_| ContextMenuMsg::Activate { level, index })}
+                    onmouseenter={link.callback(move |_| ContextMenuMsg::Hover { level, index })}
+                >
+                    { label }
+                </li>
+            }
+        };
+
+        html! {
+            <ul
+                role="menu"
+                ref={state.menu.clone()}
+                class={classes!("yew-context-menu", props.class.clone())}
+                aria-label={label}
+                tabindex="-1"
+                {onkeydown}
+            >
+                { for entries.iter().enumerate().map(entry) }
+            </ul>
+        }
+    }
+}
+
+impl<A: Clone + PartialEq + 'static> Component for ContextMenu<A> {
+    type Message = ContextMenuMsg;
+    type Properties = ContextMenuProps<A>;
+
+    fn create(_ctx: &Context<Self>) -> Self {
+        Self {
+            host: document().body().expect("no body").into(),
+            point: None,
+            levels: Vec::new(),
+            move_focus: None,
+            restore_focus: None,
+            listeners: Vec::new(),
+            _action: PhantomData,
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            ContextMenuMsg::Open { x, y } => self.open(ctx, x, y),
+            ContextMenuMsg::Hover { level, index } => {
+                self.focus(level, index);
+                self.open_submenu(ctx, level);
+                true
+            }
+            ContextMenuMsg::Key { level, key } => self.key(ctx, level, &key),
+            ContextMenuMsg::Activate { level, index } => self.activate(ctx, level, index),
+            ContextMenuMsg::Dismiss(target) => {
+                let inside = target.map_or(false, |target| {
+                    self.levels.iter().any(|level| {
+                        level
+                            .menu
+                            .get()
+                            .map_or(false, |menu| menu.contains(Some(&target)))
+                    })
+                });
+                !inside && self.close()
+            }
+        }
+    }
+
+    fn changed(&mut self, ctx: &Context<Self>) -> bool {
+        if ctx.props().items.is_empty() {
+            self.close();
+            return true;
+        }
+        // Keep the submenus that still exist
+        let mut entries: &[MenuEntry<A>] = &ctx.props().items;
+        let mut open = 0;
+        for level in &mut self.levels {
+            open += 1;
+            level.items.resize_with(entries.len(), NodeRef::default);
+            if entries.get(level.focused).and_then(|e| e.label()).is_none() {
+                level.focused = focusable(entries).first().copied().unwrap_or(0);
+            }
+            match entries.get(level.focused) {
+                Some(MenuEntry::Submenu {
+                    entries: submenu, ..
+                }) => entries = submenu,
+                _ => break,
+            }
+        }
+        self.levels.truncate(open);
+        true
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let oncontextmenu = ctx.link().callback(|e: MouseEvent| {
+            e.prevent_default();
+            // The innermost menu wins
+            e.stop_propagation();
+            let (x, y) = (f64::from(e.client_x()), f64::from(e.client_y()));
+            // Opened with the keyboard: place it below the focused element
+            if (x, y) == (0.0, 0.0) {
+                if let Some(target) = e.target().and_then(|t| t.dyn_into::<Element>().ok()) {
+                    let rect = Rect::of(&target);
+                    return ContextMenuMsg::Open {
+                        x: rect.x,
+                        y: rect.y + rect.height,
+                    };
+                }
+            }
+            ContextMenuMsg::Open { x, y }
+        });
+        let menus = if self.point.is_some() {
+            let menus = html! {
+                <>{ for (0..self.levels.len()).map(|level| self.view_level(ctx, level)) }</>
+            };
+            create_portal(menus, self.host.clone())
+        } else {
+            Html::default()
+        };
+
+        html! {
+            <>
+                <div style="display: contents" {oncontextmenu}>
+                    { for ctx.props().children.iter() }
+                </div>
+                { menus }
+            </>
+        }
+    }
+
+    fn rendered(&mut self, _ctx: &Context<Self>, _first_render: bool) {
+        if let Some((x, y)) = self.point {
+            let viewport = Rect::viewport();
+            let mut anchor = Rect {
+                x,
+                y,
+                width: 0.0,
+                height: 0.0,
+            };
+            let mut placement = Placement::new(Side::Bottom, Align::Start);
+            for level in &self.levels {
+                let menu = match level.menu.cast::<Element>() {
+                    Some(menu) => menu,
+                    None => break,
+                };
+                if !menu.has_attribute("style") {
+                    // Measured before it is placed
+                    let _ = menu.set_attribute("style", "position: fixed; visibility: hidden");
+                }
+                let size = Rect::of(&menu);
+                let options = PositionOptions {
+                    placement,
+                    offset: 0.0,
+                    ..Default::default()
+                };
+                let position =
+                    compute_position(anchor, (size.width, size.height), viewport, options);
+                let style = format!(
+                    "position: fixed; left: {}px; top: {}px",
+                    position.x.round(),
+                    position.y.round()
+                );
+                let _ = menu.set_attribute("style", &style);
+
+                // The next level is the submenu of the focused item
+                anchor = match level
+                    .items
+                    .get(level.focused)
+                    .and_then(|r| r.cast::<Element>())
+                {
+                    Some(item) => Rect::of(&item),
+                    None => break,
+                };
+                placement = Placement::new(Side::Right, Align::Start);
+            }
+        }
+
+        if let Some(level) = self.move_focus.take() {
+            let item = self
+                .levels
+                .get(level)
+                .and_then(|level| level.items.get(level.focused))
+                .and_then(|item| item.cast::<HtmlElement>());
+            if let Some(item) = item {
+                let _ = item.focus();
+            }
+        }
+    }
+
+    fn destroy(&mut self, _ctx: &Context<Self>) {
+        self.close();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "wasm_test")]
+    use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+
+    #[cfg(feature = "wasm_test")]
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    fn entries() -> Vec<MenuEntry<u32>> {
+        vec![
+            MenuEntry::item("Copy", 0),
+            MenuEntry::item("Cut", 1).disabled(),
+            MenuEntry::Separator,
+            MenuEntry::submenu(
+                "Share",
+                vec![MenuEntry::item("Email", 2), MenuEntry::item("Link", 3)],
+            ),
+        ]
+    }
+
+    #[test]
+    fn focus_skips_separators() {
+        let entries = entries();
+        assert_eq!(focusable(&entries), vec![0, 1, 3]);
+        assert_eq!(next_focus(&entries, 1, "ArrowDown"), Some(3));
+        assert_eq!(next_focus(&entries, 3, "ArrowDown"), Some(0));
+        assert_eq!(next_focus(&entries, 3, "ArrowUp"), Some(1));
+        assert_eq!(next_focus(&entries, 0, "End"), Some(3));
+        assert_eq!(next_focus(&entries, 0, "PageDown"), None);
+    }
+
+    #[test]
+    fn type_ahead_finds_next_item() {
+        let entries = entries();
+        assert_eq!(next_focus(&entries, 0, "c"), Some(1));
+        assert_eq!(next_focus(&entries, 1, "C"), Some(0));
+        assert_eq!(next_focus(&entries, 0, "s"), Some(3));
+        assert_eq!(next_focus(&entries, 0, "x"), None);
+        assert_eq!(next_focus(&entries, 0, " "), None);
+    }
+
+    #[test]
+    fn entries_follow_open_submenus() {
+        let entries = entries();
+        let mut levels = vec![Level::new(&entries)];
+        assert_eq!(entries_at(&entries, &levels, 0).map(<[_]>::len), Some(4));
+        assert!(entries_at(&entries, &levels, 1).is_none());
+
+        levels[0].focused = 3;
+        let submenu = entries_at(&entries, &levels, 1).unwrap();
+        assert_eq!(submenu[1], MenuEntry::item("Link", 3));
+        assert!(entries_at(&entries, &levels, 2).is_none());
+    }
+}
@@ -0,0 +1,239 @@
+//! This module contains the [`Defer`] component, which mounts heavy children later.
+
+use crate::html::NodeRef;
+use crate::infinite_scroll::ReachEndObserver;
+use crate::utils::window;
+use crate::{html, Callback, Children, Classes, Component, Context, Html, Properties};
+use wasm_bindgen::closure::Closure;
+use wasm_bindgen::{JsCast, JsValue};
+use web_sys::Element;
+
+/// When [`Defer`] mounts its children
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeferUntil {
+    /// Once the browser is idle, after the page was rendered
+    Idle,
+    /// Once the placeholder becomes visible
+    Visible,
+}
+
+impl Default for DeferUntil {
+    fn default() -> Self {
+        Self::Idle
+    }
+}
+
+/// Calls a callback once the browser is idle. Browsers without `requestIdleCallback` call it
+/// after a timeout.
+///
+/// The callback is cancelled when it is dropped.
+struct IdleCallback {
+    id: IdleId,
+    _closure: Closure<dyn FnMut()>,
+}
+
+enum IdleId {
+    Idle(u32),
+    Timeout(i32),
+}
+
+impl IdleCallback {
+    fn new(callback: Callback<()>) -> Self {
+        let closure = Closure::once(move || callback.emit(()));
+        let function = closure.as_ref().unchecked_ref();
+        let window = window();
+        let supported = js_sys::Reflect::has(&window, &JsValue::from_str("requestIdleCallback"))
+            .unwrap_or(false);
+        let id = if supported {
+            IdleId::Idle(window.request_idle_callback(function).unwrap())
+        } else {
+            IdleId::Timeout(window.set_timeout_with_callback(function).unwrap())
+        };
+        Self {
+            id,
+            _closure: closure,
+        }
+    }
+}
+
+impl Drop for IdleCallback {
+    fn drop(&mut self) {
+        match self.id {
+            IdleId::Idle(id) => window().cancel_idle_callback(id),
+            IdleId::Timeout(id) => window().clear_timeout_with_handle(id),
+        }
+    }
+}
+
+/// Properties of [`Defer`]
+#[derive(Debug, Clone, PartialEq, Properties)]
+pub struct DeferProps {
+    /// When the children are mounted
+    #[prop_or_default]
+    pub until: DeferUntil,
+    /// CSS width of the placeholder, e.g. `"100%"`
+    #[prop_or_default]
+    pub width: Option<String>,
+    /// CSS height of the placeholder, e.g. `"300px"`. It should match the height of the children
+    /// to keep the layout from shifting when they are mounted.
+    #[prop_or_default]
+    pub height: Option<String>,
+    /// Classes of the placeholder
+    #[prop_or_default]
+    pub class: Classes,
+    /// Shown inside the placeholder until the children are mounted
+    #[prop_or_default]
+    pub placeholder: Html,
+    /// The deferred children
+    #[prop_or_default]
+    pub children: Children,
+}
+
+/// Delays mounting its children until the browser is idle or a placeholder taking their place
+/// becomes visible, see [`DeferUntil`].
+///
+/// Until then a placeholder of the given size is rendered, so heavy components, e.g. charts or
+/// editors, don't delay the first render of a page. Once mounted, the children stay mounted.
+///
+/// # Example
+/// ```rust
+/// # use yew::prelude::*;
+/// use yew::defer::{Defer, DeferUntil};
+///
+/// # #[function_component(Chart)]
+/// # fn chart() -> Html { html! {} }
+/// # fn view() -> Html {
+/// html! {
+///     <Defer until={DeferUntil::Visible} height="400px">
+///         <Chart />
+///     </Defer>
+/// }
+/// # }
+/// ```
+pub struct Defer {
+    mounted: bool,
+    placeholder: NodeRef,
+    observer: Option<ReachEndObserver>,
+    idle: Option<IdleCallback>,
+}
+
+impl Component for Defer {
+    type Message = ();
+    type Properties = DeferProps;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let idle = match ctx.props().until {
+            DeferUntil::Idle => Some(IdleCallback::new(ctx.link().callback(|_| ()))),
+            DeferUntil::Visible => None,
+        };
+        Self {
+            mounted: false,
+            placeholder: NodeRef::default(),
+            observer: None,
+            idle,
+        }
+    }
+
+    fn update(&mut self, _ctx: &Context<Self>, _msg: Self::Message) -> bool {
+        if self.mounted {
+            return false;
+        }
+        self.mounted = true;
+        self.observer = None;
+        self.idle = None;
+        true
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let props = ctx.props();
+        if self.mounted {
+            return html! { <>{ for props.children.iter() }</> };
+        }
+
+        let mut style = String::new();
+        if let Some(width) = &props.width {
+            style.push_str(&format!("width: {};", width));
+        }
+        if let Some(height) = &props.height {
+            style.push_str(&format!("height: {};", height));
+        }
+        html! {
+            <div ref={self.placeholder.clone()} class={props.class.clone()} {style}>
+                { props.placeholder.clone() }
+            </div>
+        }
+    }
+
+    fn rendered(&mut self, ctx: &Context<Self>, first_render: bool) {
+        if !first_render || ctx.props().until != DeferUntil::Visible {
+            return;
+        }
+        if let Some(el) = self.placeholder.cast::<Element>() {
+            let onvisible = ctx.link().callback(|_| ());
+            self.observer = Some(ReachEndObserver::new(el, onvisible));
+        }
+    }
+}
+
+#[cfg(all(test, feature = "wasm_test"))]
+mod tests {
+    extern crate self as yew;
+
+    use super::*;
+    use crate::utils::document;
+    use wasm_bindgen_futures::JsFuture;
+    use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    async fn sleep(ms: i32) {
+        let promise = js_sys::Promise::new(&mut |resolve, _| {
+            window()
+                .set_timeout_with_callback_and_timeout_and_arguments_0(&resolve, ms)
+                .unwrap();
+        });
+        JsFuture::from(promise).await.unwrap();
+    }
+
+    struct App;
+
+    impl Component for App {
+        type Message = ();
+        type Properties = ();
+
+        fn create(_ctx: &Context<Self>) -> Self {
+            Self
+        }
+
+        fn view(&self, _ctx: &Context<Self>) -> Html {
+            html! {
+                <Defer height="20px" placeholder={html! { "placeholder" }}>
+                    { "content" }
+                </Defer>
+            }
+        }
+    }
+
+    #[test]
+    async fn mounts_children_when_idle() {
+        let root = document().create_element("div").unwrap();
+        document().body().unwrap().append_child(&root).unwrap();
+        crate::start_app_in_element::<App>(root.clone());
+        assert_eq!(root.text_content().as_deref(), Some("placeholder"));
+        assert_eq!(
+            root.first_element_child()
+                .unwrap()
+                .get_attribute("style")
+                .as_deref(),
+            Some("height: 20px;")
+        );
+
+        for _ in 0..50 {
+            if root.text_content().as_deref() == Some("content") {
+                break;
+            }
+            sleep(20).await;
+        }
+        assert_eq!(root.text_content().as_deref(), Some("content"));
+    }
+}
@@ -0,0 +1,567 @@
+//! Dialogs opened from code, whose results are futures.
+//!
+//! [`DialogProvider`] renders the open dialogs into the body of the document through a
+//! [portal](crate::create_portal) and provides [`Dialogs`] to its descendants, which function
+//! components get with [`use_dialogs`] and struct components with [`Scope::dialogs`].
+//! [`Dialogs::open`] returns a future of the [`DialogResult`], so struct components can turn it
+//! into a message with [`send_future`](Scope::send_future).
+//!
+//! Dialogs are stacked in the order they were opened and each is rendered in a backdrop
+//! element of the class `yew-dialog-backdrop`. They use the [`Dialog`] widget, which keeps the
+//! focus inside the dialog and returns it when the dialog closes.
+
+use crate::a11y::widgets::Dialog;
+use crate::context::ContextProvider;
+use crate::functional::use_context;
+use crate::history_entry::HistoryEntry;
+use crate::html::Scope;
+use crate::utils::document;
+use crate::virtual_dom::AttrValue;
+use crate::{
+    classes, create_portal, html, Callback, Children, Classes, Component, Context, Html, Properties,
+};
+use std::cell::{Cell, RefCell};
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::task::{Poll, Waker};
+use web_sys::{Element, MouseEvent};
+
+/// Outcome of a dialog
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DialogResult<T> {
+    /// The dialog was closed with a value, see [`DialogHandle::close`]
+    Closed(T),
+    /// The dialog was closed with Escape, a click on the backdrop or by going back, or its
+    /// provider was destroyed
+    Dismissed,
+}
+
+impl<T> DialogResult<T> {
+    /// Returns the value the dialog was closed with
+    pub fn ok(self) -> Option<T> {
+        match self {
+            Self::Closed(value) => Some(value),
+            Self::Dismissed => None,
+        }
+    }
+}
+
+/// How a dialog can be dismissed and how it looks
+#[derive(Debug, Clone, PartialEq)]
+pub struct DialogOptions {
+    /// Escape dismisses the dialog
+    pub close_on_escape: bool,
+    /// A click on the backdrop dismisses the dialog
+    pub close_on_backdrop: bool,
+    /// Going back in the history of the browser dismisses the dialog, see
+    /// [`HistoryEntry`](crate::history_entry::HistoryEntry)
+    pub close_on_back: bool,
+    /// Id of the element labelling the dialog, usually its title
+    pub labelledby: Option<AttrValue>,
+    /// Classes of the dialog element
+    pub class: Classes,
+}
+
+impl Default for DialogOptions {
+    fn default() -> Self {
+        Self {
+            close_on_escape: true,
+            close_on_backdrop: true,
+            close_on_back: false,
+            labelledby: None,
+            class: Classes::new(),
+        }
+    }
+}
+
+struct Slot<T> {
+    result: Option<DialogResult<T>>,
+    waker: Option<Waker>,
+}
+
+/// Future of the result of a dialog, see [`Dialogs::open`]
+pub struct DialogFuture<T> {
+    slot: Rc<RefCell<Slot<T>>>,
+}
+
+impl<T> Future for DialogFuture<T> {
+    type Output = DialogResult<T>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut std::task::Context<'_>) -> Poll<Self::Output> {
+        let mut slot = self.slot.borrow_mut();
+        match slot.result.take() {
+            Some(result) => Poll::Ready(result),
+            None => {
+                slot.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+impl<T> fmt::Debug for DialogFuture<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("DialogFuture<_>")
+    }
+}
+
+/// Resolves the future of `slot`, unless it was resolved already. Returns whether it was.
+fn resolve<T>(slot: &RefCell<Slot<T>>, done: &Cell<bool>, result: DialogResult<T>) -> bool {
+    if done.replace(true) {
+        return false;
+    }
+    let waker = {
+        let mut slot = slot.borrow_mut();
+        slot.result = Some(result);
+        slot.waker.take()
+    };
+    if let Some(waker) = waker {
+        waker.wake();
+    }
+    true
+}
+
+/// Closes the dialog it was passed to, see [`Dialogs::open`]
+pub struct DialogHandle<T> {
+    id: usize,
+    link: Callback<DialogMsg>,
+    slot: Rc<RefCell<Slot<T>>>,
+    done: Rc<Cell<bool>>,
+}
+
+impl<T> DialogHandle<T> {
+    /// Closes the dialog with `value`
+    pub fn close(&self, value: T) {
+        if resolve(&self.slot, &self.done, DialogResult::Closed(value)) {
+            self.link.emit(DialogMsg::Remove(self.id));
+        }
+    }
+
+    /// Closes the dialog as if it was dismissed
+    pub fn dismiss(&self) {
+        if resolve(&self.slot, &self.done, DialogResult::Dismissed) {
+            self.link.emit(DialogMsg::Remove(self.id));
+        }
+    }
+}
+
+impl<T> Clone for DialogHandle<T> {
+    fn clone(&self) -> Self {
+        Self {
+            id: self.id,
+            link: self.link.clone(),
+            slot: Rc::clone(&self.slot),
+            done: Rc::clone(&self.done),
+        }
+    }
+}
+
+impl<T> fmt::Debug for DialogHandle<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DialogHandle")
+            .field("id", &self.id)
+            .finish()
+    }
+}
+
+/// Opens dialogs in the closest [`DialogProvider`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Dialogs {
+    link: Callback<DialogMsg>,
+    next_id: Rc<Cell<usize>>,
+}
+
+impl Dialogs {
+    /// Opens a dialog on top of the open ones and returns the future of its result.
+    ///
+    /// `render` renders the content of the dialog, which closes it with the [`DialogHandle`] it
+    /// is passed. It is called whenever the provider renders, so the content should get its
+    /// state from components rendered by it.
+    pub fn open<T: 'static>(
+        &self,
+        options: DialogOptions,
+        render: impl Fn(&DialogHandle<T>) -> Html + 'static,
+    ) -> DialogFuture<T> {
+        let id = self.next_id.get();
+        self.next_id.set(id + 1);
+        let slot = Rc::new(RefCell::new(Slot {
+            result: None,
+            waker: None,
+        }));
+        let handle = DialogHandle {
+            id,
+            link: self.link.clone(),
+            slot: Rc::clone(&slot),
+            done: Rc::default(),
+        };
+        let dismiss = {
+            let handle = handle.clone();
+            Rc::new(move || handle.dismiss()) as Rc<dyn Fn()>
+        };
+        self.link.emit(DialogMsg::Open(Entry {
+            id,
+            options,
+            render: Rc::new(move || render(&handle)),
+            dismiss,
+            history: None,
+        }));
+        DialogFuture { slot }
+    }
+}
+
+/// Returns the [`Dialogs`] of the closest [`DialogProvider`].
+///
+/// # Panics
+/// Panics if the component is not a descendant of a [`DialogProvider`].
+///
+/// # Example
+/// ```rust
+/// # use yew::prelude::*;
+/// use wasm_bindgen_futures::spawn_local;
+/// use yew::dialog::{use_dialogs, DialogOptions, DialogResult};
+///
+/// #[function_component(DeleteButton)]
+/// fn delete_button() -> Html {
+///     let dialogs = use_dialogs();
+///     let onclick = Callback::from(move |_| {
+///         let confirmed = dialogs.open(DialogOptions::default(), |dialog| {
+///             let confirm = {
+///                 let dialog = dialog.clone();
+///                 Callback::from(move |_| dialog.close(()))
+///             };
+///             let cancel = {
+///                 let dialog = dialog.clone();
+///                 Callback::from(move |_| dialog.dismiss())
+///             };
+///             html! {
+///                 <>
+///                     <p>{ "Delete the file?" }</p>
+///                     <button onclick={confirm}>{ "Delete" }</button>
+///                     <button onclick={cancel}>{ "Cancel" }</button>
+///                 </>
+///             }
+///         });
+///         spawn_local(async move {
+///             if let DialogResult::Closed(()) = confirmed.await {
+///                 // Delete the file
+///             }
+///         });
+///     });
+///     html! { <button {onclick}>{ "Delete" }</button> }
+/// }
+/// ```
+pub fn use_dialogs() -> Dialogs {
+    use_context::<Dialogs>().expect("use_dialogs must be used inside a DialogProvider")
+}
+
+impl<COMP: Component> Scope<COMP> {
+    /// Returns the [`Dialogs`] of the closest [`DialogProvider`], if the component is a
+    /// descendant of one
+    pub fn dialogs(&self) -> Option<Dialogs> {
+        let (dialogs, _handle) = self.context::<Dialogs>(Callback::noop())?;
+        Some(dialogs)
+    }
+}
+
+#[doc(hidden)]
+pub struct Entry {
+    id: usize,
+    options: DialogOptions,
+    render: Rc<dyn Fn() -> Html>,
+    dismiss: Rc<dyn Fn()>,
+    history: Option<HistoryEntry>,
+}
+
+#[doc(hidden)]
+pub enum DialogMsg {
+    Open(Entry),
+    Remove(usize),
+}
+
+impl fmt::Debug for DialogMsg {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Open(entry) => write!(f, "Open({})", entry.id),
+            Self::Remove(id) => write!(f, "Remove({})", id),
+        }
+    }
+}
+
+/// Properties of [`DialogProvider`]
+#[derive(Debug, Clone, PartialEq, Properties)]
+pub struct DialogProviderProps {
+    /// The components that may open dialogs
+    #[prop_or_default]
+    pub children: Children,
+}
+
+/// Renders the dialogs opened with [`Dialogs::open`], see the [module docs](self).
+///
+/// # Example
+/// ```rust
+/// # use yew::prelude::*;
+/// use yew::dialog::{DialogOptions, DialogProvider};
+///
+/// enum Msg {
+///     Rename,
+///     Renamed(Option<String>),
+/// }
+///
+/// struct File;
+///
+/// impl Component for File {
+///     type Message = Msg;
+///     type Properties = ();
+///
+///     fn create(_ctx: &Context<Self>) -> Self {
+///         Self
+///     }
+///
+///     fn update(&mut self, ctx: &Context<Self>, msg: Msg) -> bool {
+///         match msg {
+///             Msg::Rename => {
+///                 if let Some(dialogs) = ctx.link().dialogs() {
+///                     let renamed = dialogs.open(DialogOptions::default(), |dialog| {
+///                         let dialog = dialog.clone();
+///                         let name = "new.txt".to_owned();
+///                         let onclick = Callback::from(move |_| dialog.close(name.clone()));
+///                         html! { <button {onclick}>{ "Rename to new.txt" }</button> }
+///                     });
+///                     ctx.link()
+///                         .send_future(async { Msg::Renamed(renamed.await.ok()) });
+///                 }
+///                 false
+///             }
+///             Msg::Renamed(_) => true,
+///         }
+///     }
+///
+///     fn view(&self, ctx: &Context<Self>) -> Html {
+///         html! { <button onclick={ctx.link().callback(|_| Msg::Rename)}>{ "Rename" }</button> }
+///     }
+/// }
+///
+/// # fn view() -> Html {
+/// html! {
+///     <DialogProvider>
+///         <File />
+///     </DialogProvider>
+/// }
+/// # }
+/// ```
+pub struct DialogProvider {
+    dialogs: Dialogs,
+    host: Element,
+    entries: Vec<Entry>,
+}
+
+impl Component for DialogProvider {
+    type Message = DialogMsg;
+    type Properties = DialogProviderProps;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        Self {
+            dialogs: Dialogs {
+                link: ctx.link().callback(|msg| msg),
+                next_id: Rc::default(),
+            },
+            host: document().body().expect("no body node found").into(),
+            entries: Vec::new(),
+        }
+    }
+
+    fn update(&mut self, _ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            DialogMsg::Open(mut entry) => {
+                if entry.options.close_on_back {
+                    let dismiss = Rc::clone(&entry.dismiss);
+                    entry.history = Some(HistoryEntry::push(Callback::from(move |_| dismiss())));
+                }
+                self.entries.push(entry);
+            }
+            // Dropping an entry removes its synthetic history entry, if it has one
+            DialogMsg::Remove(id) => self.entries.retain(|entry| entry.id != id),
+        }
+        true
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let top = self.entries.len().saturating_sub(1);
+        let dialogs = self.entries.iter().enumerate().map(|(index, entry)| {
+            let options = &entry.options;
+            let onclose = if options.close_on_escape {
+                let dismiss = Rc::clone(&entry.dismiss);
+                Callback::from(move |_| dismiss())
+            } else {
+                Callback::noop()
+            };
+            let onclick = if options.close_on_backdrop {
+                let dismiss = Rc::clone(&entry.dismiss);
+                Callback::from(move |e: MouseEvent| {
+                    // Clicks inside the dialog bubble to the backdrop
+                    if e.target() == e.current_target() {
+                        dismiss();
+                    }
+                })
+            } else {
+                Callback::noop()
+            };
+            let class = classes!(
+                "yew-dialog-backdrop",
+                (index == top).then(|| "yew-dialog-top")
+            );
+            html! {
+                <div
+                    key={entry.id.to_string()}
+                    {class}
+                    {onclick}
+                >
+                    <Dialog
+                        {onclose}
+                        labelledby={options.labelledby.clone()}
+                        class={options.class.clone()}
+                    >
+                        { (entry.render)() }
+                    </Dialog>
+                </div>
+            }
+        });
+
+        html! {
+            <ContextProvider<Dialogs> context={self.dialogs.clone()}>
+                { for ctx.props().children.iter() }
+                { create_portal(html! { <>{ for dialogs }</> }, self.host.clone()) }
+            </ContextProvider<Dialogs>>
+        }
+    }
+
+    fn destroy(&mut self, _ctx: &Context<Self>) {
+        for entry in self.entries.drain(..) {
+            (entry.dismiss)();
+        }
+    }
+}
+
+#[cfg(all(test, feature = "wasm_test"))]
+mod tests {
+    extern crate self as yew;
+
+    use super::*;
+    use wasm_bindgen::JsCast;
+    use wasm_bindgen_futures::spawn_local;
+    use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+    use web_sys::HtmlElement;
+
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    async fn tick() {
+        wasm_bindgen_futures::JsFuture::from(js_sys::Promise::resolve(
+            &wasm_bindgen::JsValue::NULL,
+        ))
+        .await
+        .unwrap();
+    }
+
+    struct Opener;
+
+    impl Component for Opener {
+        type Message = ();
+        type Properties = ();
+
+        fn create(ctx: &Context<Self>) -> Self {
+            let dialogs = ctx.link().dialogs().unwrap();
+            let first = dialogs.open(DialogOptions::default(), |dialog| {
+                let dialog = dialog.clone();
+                let onclick = Callback::from(move |_| dialog.close(42));
+                html! { <button id="test-dialog-close" {onclick}>{ "Close" }</button> }
+            });
+            let second = dialogs.open::<()>(
+                DialogOptions {
+                    close_on_backdrop: false,
+                    ..DialogOptions::default()
+                },
+                |_| html! { "second" },
+            );
+            spawn_local(async move {
+                assert_eq!(first.await, DialogResult::Closed(42));
+                drop(second);
+            });
+            Self
+        }
+
+        fn view(&self, _ctx: &Context<Self>) -> Html {
+            html! {}
+        }
+    }
+
+    struct App;
+
+    impl Component for App {
+        type Message = ();
+        type Properties = ();
+
+        fn create(_ctx: &Context<Self>) -> Self {
+            Self
+        }
+
+        fn view(&self, _ctx: &Context<Self>) -> Html {
+            html! {
+                <DialogProvider>
+                    <Opener />
+                </DialogProvider>
+            }
+        }
+    }
+
+    #[test]
+    async fn dialogs_stack_and_close() {
+        let root = document().create_element("div").unwrap();
+        document().body().unwrap().append_child(&root).unwrap();
+        let app = crate::start_app_in_element::<App>(root);
+
+        let backdrops = document()
+            .query_selector_all(".yew-dialog-backdrop")
+            .unwrap();
+        assert_eq!(backdrops.length(), 2);
+        let top = document()
+            .query_selector(".yew-dialog-top")
+            .unwrap()
+            .unwrap();
+        assert_eq!(top.text_content().as_deref(), Some("second"));
+
+        // The backdrop of the second dialog doesn't close it
+        top.unchecked_ref::<HtmlElement>().click();
+        assert_eq!(
+            document()
+                .query_selector_all(".yew-dialog-backdrop")
+                .unwrap()
+                .length(),
+            2
+        );
+
+        document()
+            .get_element_by_id("test-dialog-close")
+            .unwrap()
+            .unchecked_into::<HtmlElement>()
+            .click();
+        tick().await;
+        assert_eq!(
+            document()
+                .query_selector_all(".yew-dialog-backdrop")
+                .unwrap()
+                .length(),
+            1
+        );
+
+        app.destroy();
+        assert_eq!(
+            document()
+                .query_selector_all(".yew-dialog-backdrop")
+                .unwrap()
+                .length(),
+            0
+        );
+    }
+}
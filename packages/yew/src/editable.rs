@@ -0,0 +1,285 @@
+//! This module contains [`Editable`], a `contenteditable` region.
+//!
+//! The content of the region belongs to the browser while the user edits it: the region is
+//! rendered as a [`VRef`](crate::virtual_dom::VNode::VRef), so the differ never touches the nodes
+//! in it. Edits are passed to the parent as [`EditEvent`]s. Values passed in by the parent that
+//! differ from the content replace it, keeping the caret at the same text offset.
+
+use crate::html::{Component, Context, Html};
+use crate::text_selection::{selection_in, set_selection, TextRange};
+use crate::utils::document;
+use crate::virtual_dom::{AttrValue, VNode};
+use crate::{Callback, Properties};
+use gloo::events::EventListener;
+use wasm_bindgen::JsCast;
+use web_sys::{Element, HtmlElement, InputEvent};
+
+/// How the content of an [`Editable`] is read and written
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EditMode {
+    /// The content is plain text. Line breaks are kept as `\n`.
+    PlainText,
+    /// The content is HTML, e.g. with bold text. The value is set as `innerHTML`, so it must
+    /// come from a trusted source or be sanitized.
+    Html,
+}
+
+impl Default for EditMode {
+    fn default() -> Self {
+        Self::PlainText
+    }
+}
+
+/// Kind of an edit, from the
+/// [`inputType`](https://developer.mozilla.org/en-US/docs/Web/API/InputEvent/inputType) of its
+/// event
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EditKind {
+    /// Text was typed
+    InsertText,
+    /// A new paragraph was started with <kbd>Enter</kbd>
+    InsertParagraph,
+    /// A line break was inserted with <kbd>Shift</kbd>+<kbd>Enter</kbd>
+    InsertLineBreak,
+    /// Content was pasted or dropped
+    InsertFromPaste,
+    /// Text was composed with an input method
+    InsertComposition,
+    /// Content was deleted or cut
+    Delete,
+    /// Text was formatted, e.g. `formatBold`
+    Format(String),
+    /// An edit was undone
+    Undo,
+    /// An edit was redone
+    Redo,
+    /// Any other `inputType`
+    Other(String),
+}
+
+impl From<&str> for EditKind {
+    fn from(input_type: &str) -> Self {
+        match input_type {
+            "insertText" | "insertReplacementText" => Self::InsertText,
+            "insertParagraph" => Self::InsertParagraph,
+            "insertLineBreak" => Self::InsertLineBreak,
+            "insertFromPaste" | "insertFromDrop" | "insertFromYank" => Self::InsertFromPaste,
+            "insertCompositionText" => Self::InsertComposition,
+            "historyUndo" => Self::Undo,
+            "historyRedo" => Self::Redo,
+            kind if kind.starts_with("delete") => Self::Delete,
+            kind if kind.starts_with("format") => Self::Format(kind.to_owned()),
+            kind => Self::Other(kind.to_owned()),
+        }
+    }
+}
+
+/// An edit of an [`Editable`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EditEvent {
+    /// Kind of the edit
+    pub kind: EditKind,
+    /// Inserted text, if any
+    pub data: Option<String>,
+    /// The content after the edit, as text or HTML depending on the [`EditMode`]
+    pub value: String,
+}
+
+/// Props for [`Editable`]
+#[derive(Debug, Clone, PartialEq, Properties)]
+pub struct EditableProps {
+    /// The content. It replaces the edited content if they differ.
+    pub value: AttrValue,
+    /// Called after every edit
+    pub onchange: Callback<EditEvent>,
+    /// How the content is read and written
+    #[prop_or_default]
+    pub mode: EditMode,
+    /// Classes of the region
+    #[prop_or_default]
+    pub class: Option<String>,
+    /// Accessible label of the region
+    #[prop_or_default]
+    pub label: Option<String>,
+    /// The content can't be edited
+    #[prop_or_default]
+    pub readonly: bool,
+}
+
+#[doc(hidden)]
+pub enum EditableMsg {
+    Input(EditKind, Option<String>),
+}
+
+/// A `contenteditable` region, see the [module documentation](self).
+///
+/// # Example
+/// ```rust
+/// # use yew::prelude::*;
+/// use yew::editable::{EditMode, Editable};
+///
+/// #[function_component(Notes)]
+/// fn notes() -> Html {
+///     let notes = use_state(|| "<b>Hello</b> world".to_string());
+///     let onchange = {
+///         let notes = notes.clone();
+///         Callback::from(move |e: yew::editable::EditEvent| notes.set(e.value))
+///     };
+///     html! { <Editable value={(*notes).clone()} mode={EditMode::Html} {onchange} /> }
+/// }
+/// ```
+#[derive(Debug)]
+pub struct Editable {
+    element: HtmlElement,
+    _listener: EventListener,
+}
+
+impl Editable {
+    fn read(&self, mode: EditMode) -> String {
+        match mode {
+            EditMode::PlainText => self.element.inner_text(),
+            EditMode::Html => self.element.inner_html(),
+        }
+    }
+
+    fn write(&self, value: &str, mode: EditMode) {
+        match mode {
+            EditMode::PlainText => self.element.set_inner_text(value),
+            EditMode::Html => self.element.set_inner_html(value),
+        }
+    }
+
+    fn apply_props(&self, props: &EditableProps) {
+        let el = &self.element;
+        let editable = match (props.readonly, props.mode) {
+            (true, _) => "false",
+            (false, EditMode::PlainText) => "plaintext-only",
+            (false, EditMode::Html) => "true",
+        };
+        el.set_content_editable(editable);
+        // Browsers without `plaintext-only` ignore it
+        if el.content_editable() != editable {
+            el.set_content_editable("true");
+        }
+        set_attribute(el, "class", props.class.as_deref());
+        set_attribute(el, "aria-label", props.label.as_deref());
+        set_attribute(el, "aria-readonly", Some("true").filter(|_| props.readonly));
+        // Keeps the spaces and line breaks of plain text
+        let style = Some("white-space: pre-wrap").filter(|_| props.mode == EditMode::PlainText);
+        set_attribute(el, "style", style);
+
+        if self.read(props.mode) != props.value.as_ref() {
+            let caret = selection_in(el).map(|range| range.start);
+            self.write(&props.value, props.mode);
+            if let Some(offset) = caret {
+                set_selection(el, TextRange::caret(offset));
+            }
+        }
+    }
+}
+
+fn set_attribute(el: &Element, name: &str, value: Option<&str>) {
+    let _ = match value {
+        Some(value) => el.set_attribute(name, value),
+        None => el.remove_attribute(name),
+    };
+}
+
+impl Component for Editable {
+    type Message = EditableMsg;
+    type Properties = EditableProps;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let element: HtmlElement = document()
+            .create_element("div")
+            .expect("failed to create element")
+            .unchecked_into();
+        element.set_attribute("role", "textbox").unwrap();
+        element.set_attribute("aria-multiline", "true").unwrap();
+
+        let link = ctx.link().clone();
+        let listener = EventListener::new(&element, "input", move |event| {
+            let event = event.unchecked_ref::<InputEvent>();
+            let kind = EditKind::from(event.input_type().as_str());
+            link.send_message(EditableMsg::Input(kind, event.data()));
+        });
+
+        let editable = Self {
+            element,
+            _listener: listener,
+        };
+        editable.apply_props(ctx.props());
+        editable
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        let EditableMsg::Input(kind, data) = msg;
+        let props = ctx.props();
+        props.onchange.emit(EditEvent {
+            kind,
+            data,
+            value: self.read(props.mode),
+        });
+        false
+    }
+
+    fn changed(&mut self, ctx: &Context<Self>) -> bool {
+        self.apply_props(ctx.props());
+        false
+    }
+
+    fn view(&self, _ctx: &Context<Self>) -> Html {
+        VNode::VRef(self.element.clone().into())
+    }
+}
+
+#[cfg(all(test, feature = "wasm_test"))]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+    use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+    use web_sys::InputEventInit;
+
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    #[test]
+    fn passes_edits_and_keeps_caret() {
+        let host = document().create_element("div").unwrap();
+        document().body().unwrap().append_child(&host).unwrap();
+        let events = Rc::new(RefCell::new(Vec::new()));
+        let onchange = {
+            let events = Rc::clone(&events);
+            Callback::from(move |e: EditEvent| events.borrow_mut().push(e))
+        };
+        let props = EditableProps {
+            value: "Hello world".into(),
+            onchange,
+            mode: EditMode::PlainText,
+            class: None,
+            label: None,
+            readonly: false,
+        };
+        crate::start_app_with_props_in_element::<Editable>(host.clone(), props);
+        let region: HtmlElement = host.first_element_child().unwrap().unchecked_into();
+        assert_eq!(region.inner_text(), "Hello world");
+
+        region.set_inner_text("Hello, world");
+        let mut init = InputEventInit::new();
+        init.input_type("insertText").data(Some(","));
+        let event = InputEvent::new_with_event_init_dict("input", &init).unwrap();
+        region.dispatch_event(&event).unwrap();
+        assert_eq!(
+            *events.borrow(),
+            vec![EditEvent {
+                kind: EditKind::InsertText,
+                data: Some(",".to_string()),
+                value: "Hello, world".to_string(),
+            }]
+        );
+
+        set_selection(&region, TextRange::caret(6));
+        assert_eq!(selection_in(&region), Some(TextRange::caret(6)));
+        host.remove();
+    }
+}
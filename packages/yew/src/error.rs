@@ -0,0 +1,24 @@
+//! This module contains [`Error`], the error of mounting an app.
+
+use thiserror::Error;
+use wasm_bindgen::JsValue;
+
+/// Error of mounting an app, see [`AppBuilder`](crate::AppBuilder)
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum Error {
+    /// There is no window or document, e.g. in a worker
+    #[error("no document available")]
+    NoDocument,
+    /// The document has no `<body>` element
+    #[error("no body node found")]
+    NoBody,
+    /// A DOM operation needed to mount the app failed
+    #[error("DOM operation failed: {0}")]
+    Dom(String),
+}
+
+impl From<JsValue> for Error {
+    fn from(err: JsValue) -> Self {
+        Self::Dom(err.as_string().unwrap_or_else(|| format!("{:?}", err)))
+    }
+}
@@ -0,0 +1,106 @@
+//! Reporting of the anomalies the framework recovers from.
+//!
+//! By default they are logged as warnings. Apps forward them to their telemetry instead with
+//! [`set_event_sink`], e.g. to find out about extensions changing the DOM of the app in
+//! production.
+
+use std::cell::RefCell;
+use std::fmt;
+use std::rc::Rc;
+
+/// An anomaly the framework recovered from
+#[derive(Debug, Clone, PartialEq)]
+pub enum FrameworkEvent {
+    /// An event didn't have the type its listener expects, e.g. a plain `Event` dispatched as
+    /// `click` by a script. The listener is called with it anyway. Only checked while a sink is
+    /// set.
+    ListenerCastFailed {
+        /// Name of the event, e.g. `"click"`
+        event: &'static str,
+        /// Type the listener expects, e.g. `"MouseEvent"`
+        expected: &'static str,
+    },
+    /// A node to remove was not found in its parent, i.e. the DOM was changed by something other
+    /// than the app
+    NodeNotFound {
+        /// Kind of the node, e.g. `"VTag"`
+        node: &'static str,
+    },
+}
+
+impl fmt::Display for FrameworkEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ListenerCastFailed { event, expected } => {
+                write!(f, "`{}` event is not a {}", event, expected)
+            }
+            Self::NodeNotFound { node } => write!(f, "Node not found to remove {}", node),
+        }
+    }
+}
+
+thread_local! {
+    static SINK: RefCell<Option<Rc<dyn Fn(&FrameworkEvent)>>> = RefCell::new(None);
+}
+
+/// Sets the function the framework reports its [`FrameworkEvent`]s to, instead of logging them.
+///
+/// # Example
+/// ```rust
+/// use yew::event_sink::FrameworkEvent;
+///
+/// # fn send_to_telemetry(_: &str) {}
+/// yew::set_event_sink(|event: &FrameworkEvent| send_to_telemetry(&event.to_string()));
+/// ```
+pub fn set_event_sink(sink: impl Fn(&FrameworkEvent) + 'static) {
+    SINK.with(|s| *s.borrow_mut() = Some(Rc::new(sink)));
+}
+
+/// Removes the sink set with [`set_event_sink`], so events are logged again
+pub fn reset_event_sink() {
+    SINK.with(|s| s.borrow_mut().take());
+}
+
+/// Returns whether a sink is set, for events which are only checked for a sink
+pub(crate) fn has_sink() -> bool {
+    SINK.with(|s| s.borrow().is_some())
+}
+
+/// Reports `event` to the sink, or logs it if there is none
+pub(crate) fn report(event: FrameworkEvent) {
+    // The sink may set another one
+    match SINK.with(|s| s.borrow().clone()) {
+        Some(sink) => sink(&event),
+        None => log::warn!("{}", event),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "wasm_test")]
+    use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+
+    #[cfg(feature = "wasm_test")]
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    #[test]
+    fn events_go_to_the_sink() {
+        let events = Rc::new(RefCell::new(Vec::new()));
+        {
+            let events = Rc::clone(&events);
+            set_event_sink(move |event| events.borrow_mut().push(event.clone()));
+        }
+        assert!(has_sink());
+        report(FrameworkEvent::NodeNotFound { node: "VText" });
+        reset_event_sink();
+        assert!(!has_sink());
+        report(FrameworkEvent::NodeNotFound { node: "VTag" });
+
+        assert_eq!(
+            *events.borrow(),
+            vec![FrameworkEvent::NodeNotFound { node: "VText" }]
+        );
+    }
+}
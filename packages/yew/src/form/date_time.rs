@@ -0,0 +1,469 @@
+use crate::html::{Component, Context, Html, TargetCast};
+use crate::{html, Callback, Properties};
+use web_sys::{Event, HtmlInputElement};
+
+/// Type of a date or time `<input>`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DateTimeKind {
+    /// `<input type="date">`
+    Date,
+    /// `<input type="time">`
+    Time,
+    /// `<input type="datetime-local">`
+    DateTimeLocal,
+}
+
+impl DateTimeKind {
+    /// Returns the `type` attribute of the input
+    pub fn input_type(self) -> &'static str {
+        match self {
+            Self::Date => "date",
+            Self::Time => "time",
+            Self::DateTimeLocal => "datetime-local",
+        }
+    }
+}
+
+/// Time zone in which instants are shown in `datetime-local` inputs, which have no time zone.
+/// Dates and times without time zone are shown as they are.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeZone {
+    /// The time zone of the browser
+    Local,
+    /// UTC
+    Utc,
+}
+
+impl Default for TimeZone {
+    fn default() -> Self {
+        Self::Local
+    }
+}
+
+/// A date or time type that can be edited in a date or time `<input>`.
+///
+/// It is implemented for the types of `chrono` and `time` with the features of the same names.
+/// Types without time zone can also be passed to the `value` of inputs directly.
+pub trait DateTimeValue: Sized {
+    /// Type of input the type is edited with
+    const KIND: DateTimeKind;
+
+    /// Formats the value as expected by the `value` of the input
+    fn to_input(&self, zone: TimeZone) -> String;
+
+    /// Parses the `value` of the input. Returns `None` if the input is empty or incomplete.
+    fn from_input(value: &str, zone: TimeZone) -> Option<Self>;
+}
+
+/// Parses the value of the date or time input targeted by `event`, e.g. in `onchange`.
+///
+/// # Example
+/// ```rust
+/// # use yew::prelude::*;
+/// # #[cfg(feature = "chrono")]
+/// # fn example() {
+/// use chrono::NaiveDate;
+/// use yew::form::{input_value, TimeZone};
+///
+/// let onchange = Callback::from(|e: Event| {
+///     let date: Option<NaiveDate> = input_value(&e, TimeZone::Local);
+/// });
+/// # }
+/// ```
+pub fn input_value<T: DateTimeValue>(event: &Event, zone: TimeZone) -> Option<T> {
+    let input: HtmlInputElement = event.target_unchecked_into();
+    T::from_input(&input.value(), zone)
+}
+
+#[cfg(any(feature = "chrono", feature = "time"))]
+/// Parses `YYYY-MM-DD`
+fn parse_date(value: &str) -> Option<(i32, u32, u32)> {
+    let mut parts = value.splitn(3, '-');
+    // Years beyond 9999 have more digits
+    let year = parts.next()?.parse().ok()?;
+    let month = parts.next()?.parse().ok()?;
+    let day = parts.next()?.parse().ok()?;
+    Some((year, month, day))
+}
+
+#[cfg(any(feature = "chrono", feature = "time"))]
+/// Parses `HH:MM`, `HH:MM:SS` and `HH:MM:SS.mmm`, dropping the fractional seconds
+fn parse_time(value: &str) -> Option<(u32, u32, u32)> {
+    let mut parts = value.splitn(3, ':');
+    let hour = parts.next()?.parse().ok()?;
+    let minute = parts.next()?.parse().ok()?;
+    let second = match parts.next() {
+        Some(second) => second.split('.').next()?.parse().ok()?,
+        None => 0,
+    };
+    Some((hour, minute, second))
+}
+
+#[cfg(any(feature = "chrono", feature = "time"))]
+/// Parses `YYYY-MM-DDTHH:MM[:SS]`
+fn parse_date_time(value: &str) -> Option<((i32, u32, u32), (u32, u32, u32))> {
+    let (date, time) = value.split_at(value.find('T')?);
+    Some((parse_date(date)?, parse_time(&time[1..])?))
+}
+
+#[cfg(any(feature = "chrono", feature = "time"))]
+fn format_date((year, month, day): (i32, u32, u32)) -> String {
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+#[cfg(any(feature = "chrono", feature = "time"))]
+/// Seconds are omitted if they are zero, as inputs do without a `step`
+fn format_time((hour, minute, second): (u32, u32, u32)) -> String {
+    if second == 0 {
+        format!("{:02}:{:02}", hour, minute)
+    } else {
+        format!("{:02}:{:02}:{:02}", hour, minute, second)
+    }
+}
+
+#[cfg(any(feature = "chrono", feature = "time"))]
+fn format_date_time(date: (i32, u32, u32), time: (u32, u32, u32)) -> String {
+    format!("{}T{}", format_date(date), format_time(time))
+}
+
+/// Returns the wall clock time of the instant at `millis` since the Unix epoch in `zone`
+#[cfg(any(feature = "chrono", feature = "time"))]
+fn wall_clock(millis: f64, zone: TimeZone) -> String {
+    let date = js_sys::Date::new(&millis.into());
+    let (date, time) = match zone {
+        TimeZone::Local => (
+            (
+                date.get_full_year() as i32,
+                date.get_month() + 1,
+                date.get_date(),
+            ),
+            (date.get_hours(), date.get_minutes(), date.get_seconds()),
+        ),
+        TimeZone::Utc => (
+            (
+                date.get_utc_full_year() as i32,
+                date.get_utc_month() + 1,
+                date.get_utc_date(),
+            ),
+            (
+                date.get_utc_hours(),
+                date.get_utc_minutes(),
+                date.get_utc_seconds(),
+            ),
+        ),
+    };
+    format_date_time(date, time)
+}
+
+/// Returns the milliseconds since the Unix epoch of the wall clock time `value` in `zone`
+#[cfg(any(feature = "chrono", feature = "time"))]
+fn instant(value: &str, zone: TimeZone) -> Option<f64> {
+    let ((year, month, day), (hour, minute, second)) = parse_date_time(value)?;
+    let (year, month) = (year as u32, month.checked_sub(1)? as i32);
+    let millis = match zone {
+        TimeZone::Local => {
+            let date = js_sys::Date::new_with_year_month_day_hr_min_sec(
+                year,
+                month,
+                day as i32,
+                hour as i32,
+                minute as i32,
+                second as i32,
+            );
+            date.get_time()
+        }
+        TimeZone::Utc => {
+            js_sys::Date::utc(year as f64, month as f64)
+                + (((f64::from(day) - 1.0) * 24.0 + f64::from(hour)) * 60.0 + f64::from(minute))
+                    * 60_000.0
+                + f64::from(second) * 1000.0
+        }
+    };
+    Some(millis).filter(|millis| !millis.is_nan())
+}
+
+#[cfg(feature = "chrono")]
+mod chrono_impls {
+    use super::*;
+    use chrono::{DateTime, Datelike, NaiveDate, NaiveDateTime, NaiveTime, TimeZone as _};
+    use chrono::{Timelike, Utc};
+
+    fn date_parts(date: &NaiveDate) -> (i32, u32, u32) {
+        (date.year(), date.month(), date.day())
+    }
+
+    fn time_parts(time: &NaiveTime) -> (u32, u32, u32) {
+        (time.hour(), time.minute(), time.second())
+    }
+
+    impl DateTimeValue for NaiveDate {
+        const KIND: DateTimeKind = DateTimeKind::Date;
+
+        fn to_input(&self, _zone: TimeZone) -> String {
+            format_date(date_parts(self))
+        }
+
+        fn from_input(value: &str, _zone: TimeZone) -> Option<Self> {
+            let (year, month, day) = parse_date(value)?;
+            NaiveDate::from_ymd_opt(year, month, day)
+        }
+    }
+
+    impl DateTimeValue for NaiveTime {
+        const KIND: DateTimeKind = DateTimeKind::Time;
+
+        fn to_input(&self, _zone: TimeZone) -> String {
+            format_time(time_parts(self))
+        }
+
+        fn from_input(value: &str, _zone: TimeZone) -> Option<Self> {
+            let (hour, minute, second) = parse_time(value)?;
+            NaiveTime::from_hms_opt(hour, minute, second)
+        }
+    }
+
+    impl DateTimeValue for NaiveDateTime {
+        const KIND: DateTimeKind = DateTimeKind::DateTimeLocal;
+
+        fn to_input(&self, _zone: TimeZone) -> String {
+            format_date_time(date_parts(&self.date()), time_parts(&self.time()))
+        }
+
+        fn from_input(value: &str, _zone: TimeZone) -> Option<Self> {
+            let ((year, month, day), (hour, minute, second)) = parse_date_time(value)?;
+            NaiveDate::from_ymd_opt(year, month, day)?.and_hms_opt(hour, minute, second)
+        }
+    }
+
+    impl DateTimeValue for DateTime<Utc> {
+        const KIND: DateTimeKind = DateTimeKind::DateTimeLocal;
+
+        fn to_input(&self, zone: TimeZone) -> String {
+            wall_clock(self.timestamp_millis() as f64, zone)
+        }
+
+        fn from_input(value: &str, zone: TimeZone) -> Option<Self> {
+            Utc.timestamp_millis_opt(instant(value, zone)? as i64)
+                .single()
+        }
+    }
+}
+
+#[cfg(feature = "time")]
+mod time_impls {
+    use super::*;
+    use std::convert::TryFrom;
+    use time::{Date, Month, OffsetDateTime, PrimitiveDateTime, Time};
+
+    fn date_parts(date: &Date) -> (i32, u32, u32) {
+        (date.year(), date.month() as u32, u32::from(date.day()))
+    }
+
+    fn time_parts(time: &Time) -> (u32, u32, u32) {
+        let (hour, minute, second) = time.as_hms();
+        (hour.into(), minute.into(), second.into())
+    }
+
+    fn date(year: i32, month: u32, day: u32) -> Option<Date> {
+        let month = Month::try_from(u8::try_from(month).ok()?).ok()?;
+        Date::from_calendar_date(year, month, u8::try_from(day).ok()?).ok()
+    }
+
+    fn time(hour: u32, minute: u32, second: u32) -> Option<Time> {
+        let part = |value: u32| u8::try_from(value).ok();
+        Time::from_hms(part(hour)?, part(minute)?, part(second)?).ok()
+    }
+
+    impl DateTimeValue for Date {
+        const KIND: DateTimeKind = DateTimeKind::Date;
+
+        fn to_input(&self, _zone: TimeZone) -> String {
+            format_date(date_parts(self))
+        }
+
+        fn from_input(value: &str, _zone: TimeZone) -> Option<Self> {
+            let (year, month, day) = parse_date(value)?;
+            date(year, month, day)
+        }
+    }
+
+    impl DateTimeValue for Time {
+        const KIND: DateTimeKind = DateTimeKind::Time;
+
+        fn to_input(&self, _zone: TimeZone) -> String {
+            format_time(time_parts(self))
+        }
+
+        fn from_input(value: &str, _zone: TimeZone) -> Option<Self> {
+            let (hour, minute, second) = parse_time(value)?;
+            time(hour, minute, second)
+        }
+    }
+
+    impl DateTimeValue for PrimitiveDateTime {
+        const KIND: DateTimeKind = DateTimeKind::DateTimeLocal;
+
+        fn to_input(&self, _zone: TimeZone) -> String {
+            format_date_time(date_parts(&self.date()), time_parts(&self.time()))
+        }
+
+        fn from_input(value: &str, _zone: TimeZone) -> Option<Self> {
+            let ((year, month, day), (hour, minute, second)) = parse_date_time(value)?;
+            Some(PrimitiveDateTime::new(
+                date(year, month, day)?,
+                time(hour, minute, second)?,
+            ))
+        }
+    }
+
+    impl DateTimeValue for OffsetDateTime {
+        const KIND: DateTimeKind = DateTimeKind::DateTimeLocal;
+
+        fn to_input(&self, zone: TimeZone) -> String {
+            let millis = self.unix_timestamp_nanos() / 1_000_000;
+            wall_clock(millis as f64, zone)
+        }
+
+        fn from_input(value: &str, zone: TimeZone) -> Option<Self> {
+            let nanos = instant(value, zone)? as i128 * 1_000_000;
+            OffsetDateTime::from_unix_timestamp_nanos(nanos).ok()
+        }
+    }
+}
+
+/// Props for [`DateTimeInput`]
+#[derive(Debug, Clone, PartialEq, Properties)]
+pub struct DateTimeInputProps<T: DateTimeValue + Clone + PartialEq + 'static> {
+    /// The value, `None` if the input is empty
+    pub value: Option<T>,
+    /// Called with the new value whenever the input changes. Incomplete input is passed as
+    /// `None`.
+    pub onchange: Callback<Option<T>>,
+    /// Time zone instants are shown in
+    #[prop_or_default]
+    pub zone: TimeZone,
+    /// Earliest value that can be picked
+    #[prop_or_default]
+    pub min: Option<T>,
+    /// Latest value that can be picked
+    #[prop_or_default]
+    pub max: Option<T>,
+    /// `name` of the input
+    #[prop_or_default]
+    pub name: Option<String>,
+    /// Classes of the input
+    #[prop_or_default]
+    pub class: Option<String>,
+}
+
+/// A date or time `<input>` bound to a [`DateTimeValue`], whose type decides the type of the
+/// input.
+///
+/// # Example
+/// ```rust
+/// # use yew::prelude::*;
+/// # #[cfg(feature = "chrono")]
+/// # mod example {
+/// # use yew::prelude::*;
+/// use chrono::{DateTime, Utc};
+/// use yew::form::{DateTimeInput, TimeZone};
+///
+/// #[function_component(Deadline)]
+/// fn deadline() -> Html {
+///     let deadline = use_state(|| None::<DateTime<Utc>>);
+///     let onchange = {
+///         let deadline = deadline.clone();
+///         Callback::from(move |value| deadline.set(value))
+///     };
+///     html! {
+///         <DateTimeInput<DateTime<Utc>> value={*deadline} zone={TimeZone::Local} {onchange} />
+///     }
+/// }
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct DateTimeInput<T: DateTimeValue + Clone + PartialEq + 'static> {
+    _value: std::marker::PhantomData<T>,
+}
+
+impl<T: DateTimeValue + Clone + PartialEq + 'static> Component for DateTimeInput<T> {
+    type Message = Option<T>;
+    type Properties = DateTimeInputProps<T>;
+
+    fn create(_ctx: &Context<Self>) -> Self {
+        Self {
+            _value: std::marker::PhantomData,
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, value: Self::Message) -> bool {
+        if value != ctx.props().value {
+            ctx.props().onchange.emit(value);
+        }
+        false
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let props = ctx.props();
+        let zone = props.zone;
+        let format = |value: &Option<T>| value.as_ref().map(|value| value.to_input(zone));
+        let oninput = ctx
+            .link()
+            .callback(move |e: web_sys::InputEvent| input_value(&e, zone));
+
+        html! {
+            <input
+                type={T::KIND.input_type()}
+                name={props.name.clone()}
+                class={props.class.clone()}
+                value={format(&props.value).unwrap_or_default()}
+                min={format(&props.min)}
+                max={format(&props.max)}
+                {oninput}
+            />
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "wasm_test")]
+    use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+
+    #[cfg(feature = "wasm_test")]
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    #[cfg(any(feature = "chrono", feature = "time"))]
+    #[test]
+    fn parses_input_values() {
+        assert_eq!(parse_date("2021-07-31"), Some((2021, 7, 31)));
+        assert_eq!(parse_date("2021-07"), None);
+        assert_eq!(parse_time("09:05"), Some((9, 5, 0)));
+        assert_eq!(parse_time("09:05:30.250"), Some((9, 5, 30)));
+        assert_eq!(
+            parse_date_time("2021-07-31T09:05"),
+            Some(((2021, 7, 31), (9, 5, 0)))
+        );
+        assert_eq!(
+            format_date_time((2021, 7, 1), (9, 5, 0)),
+            "2021-07-01T09:05"
+        );
+        assert_eq!(format_time((23, 0, 7)), "23:00:07");
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn converts_chrono_types() {
+        use chrono::{NaiveDate, NaiveDateTime};
+
+        let date = NaiveDate::from_ymd(2021, 2, 28);
+        assert_eq!(date.to_input(TimeZone::Local), "2021-02-28");
+        assert_eq!(NaiveDate::from_input("2021-02-29", TimeZone::Local), None);
+
+        let value = NaiveDateTime::from_input("2021-02-28T13:45:10", TimeZone::Local).unwrap();
+        assert_eq!(value, date.and_hms(13, 45, 10));
+        assert_eq!(value.to_input(TimeZone::Local), "2021-02-28T13:45:10");
+    }
+}
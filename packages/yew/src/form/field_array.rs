@@ -0,0 +1,453 @@
+use super::FieldState;
+use crate::functional::use_hook;
+use crate::virtual_dom::Key;
+use std::fmt;
+use std::ops::Deref;
+use std::rc::Rc;
+
+/// Key of a row of a [`FieldArray`]. It stays the same when rows are inserted, removed or
+/// moved, unlike the index of the row, so inputs keep their DOM state when rendered with it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct RowKey(u64);
+
+impl From<RowKey> for Key {
+    fn from(key: RowKey) -> Self {
+        key.0.into()
+    }
+}
+
+/// A row of a [`FieldArray`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Row<T> {
+    key: RowKey,
+    value: T,
+    state: FieldState,
+}
+
+impl<T> Row<T> {
+    /// Returns the stable key of the row
+    pub fn key(&self) -> RowKey {
+        self.key
+    }
+
+    /// Returns the value of the row
+    pub fn value(&self) -> &T {
+        &self.value
+    }
+
+    /// Returns the validation state of the row
+    pub fn state(&self) -> &FieldState {
+        &self.state
+    }
+}
+
+type Validator<T> = Rc<dyn Fn(&T) -> Result<(), String>>;
+
+/// A list of form fields whose rows can be added, removed and reordered.
+///
+/// Every row keeps its [`RowKey`] and [`FieldState`] while rows around it change. Rows are
+/// validated with the validator set with [`with_validator`](Self::with_validator) once they
+/// were [touched](Self::touch), and on every change afterwards.
+///
+/// # Example
+/// ```rust
+/// # use yew::prelude::*;
+/// use yew::form::{use_field_array, FieldArray};
+///
+/// #[function_component(Emails)]
+/// fn emails() -> Html {
+///     let emails = use_field_array(|| {
+///         FieldArray::new(vec![String::new()]).with_validator(|email: &String| {
+///             if email.contains('@') {
+///                 Ok(())
+///             } else {
+///                 Err("not an email address".to_string())
+///             }
+///         })
+///     });
+///     let add = {
+///         let emails = emails.clone();
+///         Callback::from(move |_| emails.push(String::new()))
+///     };
+///
+///     html! {
+///         <>
+///             { for emails.rows().iter().map(|row| {
+///                 let key = row.key();
+///                 let oninput = {
+///                     let emails = emails.clone();
+///                     Callback::from(move |e: InputEvent| {
+///                         let input: web_sys::HtmlInputElement = e.target_unchecked_into();
+///                         emails.set(key, input.value());
+///                     })
+///                 };
+///                 let onblur = {
+///                     let emails = emails.clone();
+///                     Callback::from(move |_| emails.touch(key))
+///                 };
+///                 let remove = {
+///                     let emails = emails.clone();
+///                     Callback::from(move |_| emails.remove(key))
+///                 };
+///                 html! {
+///                     <div key={key}>
+///                         <input value={row.value().clone()} {oninput} {onblur} />
+///                         <button onclick={remove}>{ "Remove" }</button>
+///                         { for row.state().visible_error() }
+///                     </div>
+///                 }
+///             }) }
+///             <button onclick={add}>{ "Add" }</button>
+///         </>
+///     }
+/// }
+/// ```
+pub struct FieldArray<T> {
+    rows: Vec<Row<T>>,
+    next_key: u64,
+    validator: Option<Validator<T>>,
+}
+
+impl<T> FieldArray<T> {
+    /// Creates an array with a row for every value
+    pub fn new(values: impl IntoIterator<Item = T>) -> Self {
+        let mut array = Self {
+            rows: Vec::new(),
+            next_key: 0,
+            validator: None,
+        };
+        for value in values {
+            array.push(value);
+        }
+        array
+    }
+
+    /// Sets the validator of the rows
+    pub fn with_validator(
+        mut self,
+        validator: impl Fn(&T) -> Result<(), String> + 'static,
+    ) -> Self {
+        self.validator = Some(Rc::new(validator));
+        self
+    }
+
+    /// Returns the rows in their current order
+    pub fn rows(&self) -> &[Row<T>] {
+        &self.rows
+    }
+
+    /// Returns the row with `key`
+    pub fn get(&self, key: RowKey) -> Option<&Row<T>> {
+        self.rows.iter().find(|row| row.key == key)
+    }
+
+    /// Returns the current index of the row with `key`
+    pub fn index_of(&self, key: RowKey) -> Option<usize> {
+        self.rows.iter().position(|row| row.key == key)
+    }
+
+    /// Returns the values of the rows in their current order
+    pub fn values(&self) -> impl Iterator<Item = &T> {
+        self.rows.iter().map(|row| &row.value)
+    }
+
+    /// Returns the number of rows
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Returns whether there are no rows
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    /// Returns whether no row has an error. Rows that were not validated yet count as valid.
+    pub fn is_valid(&self) -> bool {
+        self.rows.iter().all(|row| row.state.error.is_none())
+    }
+
+    /// Appends a row and returns its key
+    pub fn push(&mut self, value: T) -> RowKey {
+        self.insert(self.rows.len(), value)
+    }
+
+    /// Inserts a row at `index` and returns its key
+    ///
+    /// # Panics
+    /// Panics if `index` is greater than the number of rows.
+    pub fn insert(&mut self, index: usize, value: T) -> RowKey {
+        let key = RowKey(self.next_key);
+        self.next_key += 1;
+        self.rows.insert(
+            index,
+            Row {
+                key,
+                value,
+                state: FieldState::default(),
+            },
+        );
+        key
+    }
+
+    /// Removes the row with `key` and returns its value
+    pub fn remove(&mut self, key: RowKey) -> Option<T> {
+        let index = self.index_of(key)?;
+        Some(self.rows.remove(index).value)
+    }
+
+    /// Moves the row with `key` to `index`, which is clamped to the last row. Returns whether
+    /// the order changed.
+    pub fn move_to(&mut self, key: RowKey, index: usize) -> bool {
+        let from = match self.index_of(key) {
+            Some(from) => from,
+            None => return false,
+        };
+        let to = index.min(self.rows.len() - 1);
+        let row = self.rows.remove(from);
+        self.rows.insert(to, row);
+        from != to
+    }
+
+    /// Replaces the value of the row with `key`, which is validated again if it was touched.
+    /// Returns whether there was such a row.
+    pub fn set(&mut self, key: RowKey, value: T) -> bool {
+        let validator = self.validator.clone();
+        match self.rows.iter_mut().find(|row| row.key == key) {
+            Some(row) => {
+                row.value = value;
+                if row.state.touched {
+                    validate(&validator, row);
+                }
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Marks the row with `key` as touched and validates it. Returns whether its state changed.
+    pub fn touch(&mut self, key: RowKey) -> bool {
+        let validator = self.validator.clone();
+        match self.rows.iter_mut().find(|row| row.key == key) {
+            Some(row) => {
+                let before = row.state.clone();
+                row.state.touched = true;
+                validate(&validator, row);
+                row.state != before
+            }
+            None => false,
+        }
+    }
+
+    /// Marks all rows as touched and validates them, e.g. before the form is submitted.
+    /// Returns whether all rows are valid.
+    pub fn validate(&mut self) -> bool {
+        for row in &mut self.rows {
+            row.state.touched = true;
+            validate(&self.validator, row);
+        }
+        self.is_valid()
+    }
+}
+
+fn validate<T>(validator: &Option<Validator<T>>, row: &mut Row<T>) {
+    if let Some(validator) = validator {
+        row.state.error = validator(&row.value).err();
+    }
+}
+
+impl<T: Clone> Clone for FieldArray<T> {
+    fn clone(&self) -> Self {
+        Self {
+            rows: self.rows.clone(),
+            next_key: self.next_key,
+            validator: self.validator.clone(),
+        }
+    }
+}
+
+impl<T: PartialEq> PartialEq for FieldArray<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.rows == other.rows
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for FieldArray<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FieldArray")
+            .field("rows", &self.rows)
+            .finish()
+    }
+}
+
+struct UseFieldArray<T> {
+    array: Rc<FieldArray<T>>,
+}
+
+/// This hook keeps a [`FieldArray`] created by `init`, see its example.
+pub fn use_field_array<T, F>(init: F) -> UseFieldArrayHandle<T>
+where
+    T: Clone + 'static,
+    F: FnOnce() -> FieldArray<T> + 'static,
+{
+    use_hook(
+        move || UseFieldArray {
+            array: Rc::new(init()),
+        },
+        move |hook, updater| {
+            let dispatch: Rc<dyn Fn(Box<dyn FnOnce(&mut FieldArray<T>) -> bool>)> =
+                Rc::new(move |f| {
+                    updater
+                        .callback(move |st: &mut UseFieldArray<T>| f(Rc::make_mut(&mut st.array)))
+                });
+            UseFieldArrayHandle {
+                array: Rc::clone(&hook.array),
+                dispatch,
+            }
+        },
+        |_| {},
+    )
+}
+
+/// Handle of the [`use_field_array`] hook.
+///
+/// It dereferences to the [`FieldArray`] of the current render. Its methods update the array
+/// and re-render the component if it changed.
+pub struct UseFieldArrayHandle<T> {
+    array: Rc<FieldArray<T>>,
+    #[allow(clippy::type_complexity)]
+    dispatch: Rc<dyn Fn(Box<dyn FnOnce(&mut FieldArray<T>) -> bool>)>,
+}
+
+impl<T: Clone + 'static> UseFieldArrayHandle<T> {
+    /// See [`FieldArray::push`]
+    pub fn push(&self, value: T) {
+        (self.dispatch)(Box::new(move |array| {
+            array.push(value);
+            true
+        }))
+    }
+
+    /// See [`FieldArray::insert`]
+    pub fn insert(&self, index: usize, value: T) {
+        (self.dispatch)(Box::new(move |array| {
+            array.insert(index.min(array.len()), value);
+            true
+        }))
+    }
+
+    /// See [`FieldArray::remove`]
+    pub fn remove(&self, key: RowKey) {
+        (self.dispatch)(Box::new(move |array| array.remove(key).is_some()))
+    }
+
+    /// See [`FieldArray::move_to`]
+    pub fn move_to(&self, key: RowKey, index: usize) {
+        (self.dispatch)(Box::new(move |array| array.move_to(key, index)))
+    }
+
+    /// See [`FieldArray::set`]
+    pub fn set(&self, key: RowKey, value: T) {
+        (self.dispatch)(Box::new(move |array| array.set(key, value)))
+    }
+
+    /// See [`FieldArray::touch`]
+    pub fn touch(&self, key: RowKey) {
+        (self.dispatch)(Box::new(move |array| array.touch(key)))
+    }
+
+    /// See [`FieldArray::validate`]
+    pub fn validate(&self) {
+        (self.dispatch)(Box::new(|array| {
+            array.validate();
+            true
+        }))
+    }
+}
+
+impl<T> Deref for UseFieldArrayHandle<T> {
+    type Target = FieldArray<T>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.array
+    }
+}
+
+impl<T> Clone for UseFieldArrayHandle<T> {
+    fn clone(&self) -> Self {
+        Self {
+            array: Rc::clone(&self.array),
+            dispatch: Rc::clone(&self.dispatch),
+        }
+    }
+}
+
+impl<T> PartialEq for UseFieldArrayHandle<T> {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.array, &other.array)
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for UseFieldArrayHandle<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("UseFieldArrayHandle")
+            .field("array", &self.array)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "wasm_test")]
+    use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+
+    #[cfg(feature = "wasm_test")]
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    fn not_empty(value: &&str) -> Result<(), String> {
+        if value.is_empty() {
+            Err("required".to_string())
+        } else {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn rows_keep_keys_and_state() {
+        let mut array = FieldArray::new(vec!["a", ""]).with_validator(not_empty);
+        let a = array.rows()[0].key();
+        let empty = array.rows()[1].key();
+        assert!(array.touch(empty));
+        assert_eq!(
+            array.get(empty).unwrap().state().visible_error(),
+            Some("required")
+        );
+
+        // Inserting before the invalid row keeps its key and error
+        let b = array.insert(0, "b");
+        assert_eq!(array.index_of(empty), Some(2));
+        assert_eq!(
+            array.get(empty).unwrap().state().visible_error(),
+            Some("required")
+        );
+        assert!(array.get(b).unwrap().state().error.is_none());
+
+        assert!(array.move_to(empty, 0));
+        assert_eq!(
+            array.values().copied().collect::<Vec<_>>(),
+            vec!["", "b", "a"]
+        );
+        assert!(!array.is_valid());
+
+        assert!(array.set(empty, "c"));
+        assert!(array.is_valid());
+        assert_eq!(array.remove(a), Some("a"));
+        assert_eq!(array.remove(a), None);
+
+        // New rows never reuse keys
+        let d = array.push("");
+        assert!(d != a && d != b && d != empty);
+        assert!(!array.validate());
+    }
+}
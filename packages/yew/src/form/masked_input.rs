@@ -0,0 +1,338 @@
+use crate::html::{Component, Context, Html, NodeRef};
+use crate::{html, Callback, Properties};
+use std::borrow::Cow;
+use web_sys::HtmlInputElement;
+
+/// Format of a [`MaskedInput`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Mask {
+    /// A fixed pattern in which `9` is a digit, `a` a letter and `*` a digit or letter. Other
+    /// characters are inserted as typed, e.g. `(999) 999-9999`. They should not be digits or
+    /// letters, which would be mistaken for input.
+    Pattern(Cow<'static, str>),
+    /// An amount with a currency symbol, thousands separators and up to `decimals` decimals,
+    /// e.g. `$1,234.50`
+    Currency {
+        /// Symbol in front of the amount
+        symbol: Cow<'static, str>,
+        /// Maximum number of decimals
+        decimals: u8,
+    },
+}
+
+/// Result of [`Mask::apply`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Masked {
+    /// The formatted text
+    pub text: String,
+    /// The characters typed by the user, without the formatting
+    pub raw: String,
+    /// The caret in `text` in UTF-16 code units, like the selection of inputs
+    pub caret: u32,
+}
+
+impl Mask {
+    /// Creates a [`Mask::Pattern`]
+    pub fn pattern(pattern: impl Into<Cow<'static, str>>) -> Self {
+        Self::Pattern(pattern.into())
+    }
+
+    /// A US phone number like `(555) 123-4567`
+    pub fn phone() -> Self {
+        Self::pattern("(999) 999-9999")
+    }
+
+    /// A date like `2021-07-31`
+    pub fn date() -> Self {
+        Self::pattern("9999-99-99")
+    }
+
+    /// An amount with two decimals after `symbol`
+    pub fn currency(symbol: impl Into<Cow<'static, str>>) -> Self {
+        Self::Currency {
+            symbol: symbol.into(),
+            decimals: 2,
+        }
+    }
+
+    /// Formats `text`, which has the caret at `caret` in UTF-16 code units, and returns where
+    /// the caret goes in the result so it stays after the same typed character.
+    pub fn apply(&self, text: &str, caret: u32) -> Masked {
+        // Characters of the result, with the index in `text` of those that were typed
+        let mut out: Vec<(char, Option<usize>)> = Vec::new();
+        let typed: Vec<(usize, char)> = text.chars().enumerate().collect();
+
+        match self {
+            Self::Pattern(pattern) => {
+                let mut typed = typed.into_iter();
+                let mut literals = Vec::new();
+                'slots: for slot in pattern.chars() {
+                    let accepts: fn(&char) -> bool = match slot {
+                        '9' => char::is_ascii_digit,
+                        'a' => char::is_alphabetic,
+                        '*' => char::is_alphanumeric,
+                        _ => {
+                            literals.push(slot);
+                            continue;
+                        }
+                    };
+                    for (index, c) in &mut typed {
+                        if accepts(&c) {
+                            // Literals are only inserted in front of typed characters
+                            out.extend(literals.drain(..).map(|literal| (literal, None)));
+                            out.push((c, Some(index)));
+                            continue 'slots;
+                        }
+                    }
+                    break;
+                }
+            }
+            Self::Currency { symbol, decimals } => {
+                let mut integer = Vec::new();
+                let mut fraction: Option<Vec<_>> = None;
+                let mut dot = None;
+                for (index, c) in typed {
+                    match (c, &mut fraction) {
+                        ('.', None) if *decimals > 0 => {
+                            dot = Some(index);
+                            fraction = Some(Vec::new());
+                        }
+                        ('0'..='9', None) => integer.push((c, Some(index))),
+                        ('0'..='9', Some(fraction)) if fraction.len() < *decimals as usize => {
+                            fraction.push((c, Some(index)))
+                        }
+                        _ => {}
+                    }
+                }
+                // Leading zeros are dropped, except the last one of the integer part
+                let zeros = integer
+                    .iter()
+                    .take_while(|(c, _)| *c == '0')
+                    .count()
+                    .min(integer.len().saturating_sub(1));
+                integer.drain(..zeros);
+
+                if !integer.is_empty() || fraction.is_some() {
+                    out.extend(symbol.chars().map(|c| (c, None)));
+                }
+                let len = integer.len();
+                for (i, digit) in integer.into_iter().enumerate() {
+                    if i > 0 && (len - i) % 3 == 0 {
+                        out.push((',', None));
+                    }
+                    out.push(digit);
+                }
+                if let Some(fraction) = fraction {
+                    if len == 0 {
+                        out.push(('0', None));
+                    }
+                    out.push(('.', dot));
+                    out.extend(fraction);
+                }
+            }
+        }
+
+        // The caret goes after the last typed character that was in front of it, and past the
+        // literals inserted before the next one
+        let before = char_index(text, caret);
+        let mut end = out
+            .iter()
+            .rposition(|(_, index)| matches!(index, Some(index) if *index < before))
+            .map_or(0, |position| position + 1);
+        if let Some(offset) = out[end..].iter().position(|(_, index)| index.is_some()) {
+            end += offset;
+        }
+        Masked {
+            caret: out[..end].iter().map(|(c, _)| c.len_utf16() as u32).sum(),
+            raw: out
+                .iter()
+                .filter(|(_, index)| index.is_some())
+                .map(|(c, _)| c)
+                .collect(),
+            text: out.into_iter().map(|(c, _)| c).collect(),
+        }
+    }
+
+    /// Formats a raw value
+    pub fn format(&self, raw: &str) -> String {
+        self.apply(raw, 0).text
+    }
+}
+
+/// Converts an offset in UTF-16 code units to a character index
+fn char_index(text: &str, utf16: u32) -> usize {
+    let mut units = 0;
+    text.chars()
+        .take_while(|c| {
+            units += c.len_utf16() as u32;
+            units <= utf16
+        })
+        .count()
+}
+
+/// Props for [`MaskedInput`]
+#[derive(Debug, Clone, PartialEq, Properties)]
+pub struct MaskedInputProps {
+    /// The raw value, without the formatting of the mask
+    pub value: String,
+    /// Format of the input
+    pub mask: Mask,
+    /// Called with the raw value whenever it changes
+    pub onchange: Callback<String>,
+    /// `name` of the input
+    #[prop_or_default]
+    pub name: Option<String>,
+    /// `placeholder` of the input
+    #[prop_or_default]
+    pub placeholder: Option<String>,
+    /// Classes of the input
+    #[prop_or_default]
+    pub class: Option<String>,
+}
+
+#[doc(hidden)]
+pub enum MaskedInputMsg {
+    Input,
+}
+
+/// An `<input>` formatted with a [`Mask`] on every keystroke.
+///
+/// The input is formatted as soon as the user types, so the caret is put back after the
+/// character that was typed rather than jumping to the end. The rendered value then matches
+/// the DOM, which the differ leaves untouched. If the parent renders another value instead, the
+/// caret is restored after the render.
+///
+/// # Example
+/// ```rust
+/// # use yew::prelude::*;
+/// use yew::form::{Mask, MaskedInput};
+///
+/// #[function_component(Phone)]
+/// fn phone() -> Html {
+///     let phone = use_state(String::new);
+///     let onchange = {
+///         let phone = phone.clone();
+///         Callback::from(move |value| phone.set(value))
+///     };
+///     html! { <MaskedInput mask={Mask::phone()} value={(*phone).clone()} {onchange} /> }
+/// }
+/// ```
+#[derive(Debug)]
+pub struct MaskedInput {
+    input: NodeRef,
+    /// Text and caret to restore after the next render
+    pending: Option<Masked>,
+}
+
+impl Component for MaskedInput {
+    type Message = MaskedInputMsg;
+    type Properties = MaskedInputProps;
+
+    fn create(_ctx: &Context<Self>) -> Self {
+        Self {
+            input: NodeRef::default(),
+            pending: None,
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        let MaskedInputMsg::Input = msg;
+        let input = match self.input.cast::<HtmlInputElement>() {
+            Some(input) => input,
+            None => return false,
+        };
+        let caret = input.selection_start().ok().flatten().unwrap_or(0);
+        let masked = ctx.props().mask.apply(&input.value(), caret);
+        input.set_value(&masked.text);
+        let _ = input.set_selection_range(masked.caret, masked.caret);
+        if masked.raw != ctx.props().value {
+            ctx.props().onchange.emit(masked.raw.clone());
+        }
+        self.pending = Some(masked);
+        false
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let props = ctx.props();
+        let inputmode = match &props.mask {
+            Mask::Pattern(pattern) if pattern.contains(|c| c == 'a' || c == '*') => "text",
+            Mask::Pattern(_) => "numeric",
+            Mask::Currency { .. } => "decimal",
+        };
+        let oninput = ctx
+            .link()
+            .callback(|_: web_sys::InputEvent| MaskedInputMsg::Input);
+
+        html! {
+            <input
+                ref={self.input.clone()}
+                type="text"
+                {inputmode}
+                name={props.name.clone()}
+                placeholder={props.placeholder.clone()}
+                class={props.class.clone()}
+                value={props.mask.format(&props.value)}
+                {oninput}
+            />
+        }
+    }
+
+    fn rendered(&mut self, _ctx: &Context<Self>, _first_render: bool) {
+        let (masked, input) = match (self.pending.take(), self.input.cast::<HtmlInputElement>()) {
+            (Some(masked), Some(input)) => (masked, input),
+            _ => return,
+        };
+        if input.value() == masked.text {
+            let _ = input.set_selection_range(masked.caret, masked.caret);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "wasm_test")]
+    use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+
+    #[cfg(feature = "wasm_test")]
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    #[test]
+    fn formats_patterns() {
+        let phone = Mask::phone();
+        assert_eq!(phone.format("5551234567"), "(555) 123-4567");
+        assert_eq!(phone.format("555x12"), "(555) 12");
+
+        // Typing "9" after "(555) 12" keeps the caret after it
+        let masked = phone.apply("(555) 192", 8);
+        assert_eq!(masked.text, "(555) 192");
+        assert_eq!(masked.raw, "555192");
+        assert_eq!(masked.caret, 8);
+
+        // Typing the fourth digit moves the caret past the inserted literals
+        let masked = phone.apply("(5551", 5);
+        assert_eq!(masked.text, "(555) 1");
+        assert_eq!(masked.caret, 7);
+
+        // Deleting in the middle keeps the caret where it was
+        let masked = phone.apply("(555) 23-4567", 6);
+        assert_eq!(masked.text, "(555) 234-567");
+        assert_eq!(masked.caret, 6);
+    }
+
+    #[test]
+    fn formats_currencies() {
+        let currency = Mask::currency("$");
+        assert_eq!(currency.format(""), "");
+        assert_eq!(currency.apply("1234.5", 0).raw, "1234.5");
+        assert_eq!(currency.format("001234.567"), "$1,234.56");
+        assert_eq!(currency.format(".5"), "$0.5");
+
+        // Typing a digit in front of the separator
+        let masked = currency.apply("$1,2345", 7);
+        assert_eq!(masked.text, "$12,345");
+        assert_eq!(masked.raw, "12345");
+        assert_eq!(masked.caret, 7);
+    }
+}
@@ -0,0 +1,34 @@
+//! Building blocks of forms.
+//!
+//! The state of a single field is kept in a [`FieldState`]. Lists of fields whose rows can be
+//! added, removed and reordered are kept in a [`FieldArray`], which gives every row a stable
+//! [`RowKey`] to render it with. Forms can be generated from a struct deriving
+//! [`FormSchema`](trait@FormSchema) with [`SchemaForm`]. [`MaskedInput`] formats inputs
+//! like phone numbers while they are typed and [`DateTimeInput`] binds date and time inputs to
+//! the types of `chrono` and `time`.
+
+mod date_time;
+mod field_array;
+mod masked_input;
+mod schema;
+
+pub use date_time::*;
+pub use field_array::*;
+pub use masked_input::*;
+pub use schema::*;
+
+/// Validation state of a field
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FieldState {
+    /// The user interacted with the field, so its error should be shown
+    pub touched: bool,
+    /// Error of the last validation
+    pub error: Option<String>,
+}
+
+impl FieldState {
+    /// Returns the error if the field was touched
+    pub fn visible_error(&self) -> Option<&str> {
+        self.error.as_deref().filter(|_| self.touched)
+    }
+}
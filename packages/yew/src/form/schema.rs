@@ -0,0 +1,561 @@
+use super::FieldState;
+use crate::html::{Component, Context, Html, TargetCast};
+use crate::{html, Callback, Properties};
+use std::collections::HashMap;
+use std::fmt;
+use std::rc::Rc;
+use web_sys::{HtmlInputElement, HtmlTextAreaElement};
+
+/// Derives [`FormSchema`](trait@FormSchema) for a struct with named fields.
+///
+/// Every field must implement [`FormValue`]. Fields are configured with `#[form(...)]`
+/// attributes:
+/// - `label = "..."`: the label, defaults to the field name in sentence case
+/// - `help = "..."`: a hint shown below the input
+/// - `kind = "..."`: one of `text`, `textarea`, `number`, `checkbox`, `email`, `password` and
+///   `date`, defaults to [`FormValue::KIND`]
+/// - `required`: the field must not be empty
+/// - `validate = "path"`: a `fn(&T) -> Result<(), String>` validating the field
+/// - `skip`: the field is not part of the form
+pub use yew_macro::FormSchema;
+
+/// Kind of input rendered for a field
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FieldKind {
+    /// A single line of text
+    Text,
+    /// Multiple lines of text
+    TextArea,
+    /// A number
+    Number,
+    /// A checkbox
+    Checkbox,
+    /// An email address
+    Email,
+    /// A password
+    Password,
+    /// A date
+    Date,
+}
+
+impl FieldKind {
+    /// Returns the `type` attribute of the `<input>` of the kind
+    pub fn input_type(self) -> &'static str {
+        match self {
+            Self::Text | Self::TextArea => "text",
+            Self::Number => "number",
+            Self::Checkbox => "checkbox",
+            Self::Email => "email",
+            Self::Password => "password",
+            Self::Date => "date",
+        }
+    }
+}
+
+/// Description of a field of a [`FormSchema`](trait@FormSchema)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldSchema {
+    /// Name of the field in the struct
+    pub name: &'static str,
+    /// Label of the field
+    pub label: &'static str,
+    /// Kind of input rendered for the field
+    pub kind: FieldKind,
+    /// The field must not be empty
+    pub required: bool,
+    /// A hint shown below the input
+    pub help: Option<&'static str>,
+}
+
+/// Value of a field as edited in an input
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FieldValue {
+    /// Text of a text input
+    Text(String),
+    /// State of a checkbox
+    Bool(bool),
+}
+
+/// A type that can be edited as a field of a [`FormSchema`](trait@FormSchema)
+pub trait FormValue: Sized {
+    /// Kind of input the type is edited with by default
+    const KIND: FieldKind;
+
+    /// Returns the value shown in the input
+    fn to_field(&self) -> FieldValue;
+
+    /// Parses the value of the input
+    fn from_field(value: FieldValue) -> Result<Self, String>;
+
+    /// Returns whether the value is empty, which fails required fields
+    fn is_empty(&self) -> bool {
+        false
+    }
+}
+
+impl FormValue for String {
+    const KIND: FieldKind = FieldKind::Text;
+
+    fn to_field(&self) -> FieldValue {
+        FieldValue::Text(self.clone())
+    }
+
+    fn from_field(value: FieldValue) -> Result<Self, String> {
+        match value {
+            FieldValue::Text(text) => Ok(text),
+            FieldValue::Bool(value) => Ok(value.to_string()),
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.trim().is_empty()
+    }
+}
+
+impl FormValue for bool {
+    const KIND: FieldKind = FieldKind::Checkbox;
+
+    fn to_field(&self) -> FieldValue {
+        FieldValue::Bool(*self)
+    }
+
+    fn from_field(value: FieldValue) -> Result<Self, String> {
+        match value {
+            FieldValue::Bool(value) => Ok(value),
+            FieldValue::Text(text) => text.parse().map_err(|_| "not a boolean".to_string()),
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        !*self
+    }
+}
+
+macro_rules! impl_form_value_for_numbers {
+    ($($ty:ty)*) => {
+        $(
+            impl FormValue for $ty {
+                const KIND: FieldKind = FieldKind::Number;
+
+                fn to_field(&self) -> FieldValue {
+                    FieldValue::Text(self.to_string())
+                }
+
+                fn from_field(value: FieldValue) -> Result<Self, String> {
+                    match value {
+                        FieldValue::Text(text) => {
+                            text.trim().parse().map_err(|_| "not a valid number".to_string())
+                        }
+                        FieldValue::Bool(_) => Err("not a valid number".to_string()),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_form_value_for_numbers!(u8 u16 u32 u64 usize i8 i16 i32 i64 isize f32 f64);
+
+/// Empty inputs are `None`
+impl<T: FormValue> FormValue for Option<T> {
+    const KIND: FieldKind = T::KIND;
+
+    fn to_field(&self) -> FieldValue {
+        match self {
+            Some(value) => value.to_field(),
+            None => FieldValue::Text(String::new()),
+        }
+    }
+
+    fn from_field(value: FieldValue) -> Result<Self, String> {
+        match value {
+            FieldValue::Text(text) if text.trim().is_empty() => Ok(None),
+            value => T::from_field(value).map(Some),
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.as_ref().map_or(true, T::is_empty)
+    }
+}
+
+/// A struct whose fields can be edited in a generated form, see [`SchemaForm`]. It is
+/// implemented with `#[derive(FormSchema)]`.
+pub trait FormSchema {
+    /// Returns the fields in the order they are declared
+    fn fields() -> &'static [FieldSchema];
+
+    /// Returns the value of the field `name`
+    fn value(&self, name: &str) -> Option<FieldValue>;
+
+    /// Sets the field `name` to the parsed `value`
+    fn set_value(&mut self, name: &str, value: FieldValue) -> Result<(), String>;
+
+    /// Validates the field `name`
+    fn validate_field(&self, name: &str) -> Result<(), String>;
+
+    /// Validates all fields and returns the errors by field name
+    fn validate(&self) -> HashMap<&'static str, String> {
+        Self::fields()
+            .iter()
+            .filter_map(|field| Some((field.name, self.validate_field(field.name).err()?)))
+            .collect()
+    }
+}
+
+/// The current state of a field passed to a [`FieldRenderer`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldInput {
+    /// Value shown in the input
+    pub value: FieldValue,
+    /// Validation state of the field
+    pub state: FieldState,
+    /// Must be called with the new value whenever the input changes
+    pub onchange: Callback<FieldValue>,
+    /// Must be called when the input loses focus, to show its errors
+    pub onblur: Callback<()>,
+}
+
+/// Renders the fields of a [`SchemaForm`].
+///
+/// Renderers that only change some fields can call [`render_default`] for the others.
+pub trait FieldRenderer {
+    /// Renders `field` with its label, input, help and error
+    fn render(&self, field: &FieldSchema, input: &FieldInput) -> Html;
+}
+
+impl<F: Fn(&FieldSchema, &FieldInput) -> Html> FieldRenderer for F {
+    fn render(&self, field: &FieldSchema, input: &FieldInput) -> Html {
+        self(field, input)
+    }
+}
+
+/// Renders a field as a `<label>` containing its input, followed by its help and error
+pub fn render_default(field: &FieldSchema, input: &FieldInput) -> Html {
+    let text = match &input.value {
+        FieldValue::Text(text) => text.clone(),
+        FieldValue::Bool(_) => String::new(),
+    };
+    let onblur = input.onblur.reform(|_| ());
+    let control = match field.kind {
+        FieldKind::TextArea => {
+            let oninput = input.onchange.reform(|e: web_sys::InputEvent| {
+                let textarea: HtmlTextAreaElement = e.target_unchecked_into();
+                FieldValue::Text(textarea.value())
+            });
+            html! {
+                <textarea name={field.name} required={field.required} value={text}
+                    {oninput} {onblur} />
+            }
+        }
+        FieldKind::Checkbox => {
+            let checked = input.value == FieldValue::Bool(true);
+            let onchange = input.onchange.reform(|e: web_sys::Event| {
+                let checkbox: HtmlInputElement = e.target_unchecked_into();
+                FieldValue::Bool(checkbox.checked())
+            });
+            html! {
+                <input type="checkbox" name={field.name} {checked} {onchange} {onblur} />
+            }
+        }
+        kind => {
+            let oninput = input.onchange.reform(|e: web_sys::InputEvent| {
+                let input: HtmlInputElement = e.target_unchecked_into();
+                FieldValue::Text(input.value())
+            });
+            html! {
+                <input type={kind.input_type()} name={field.name} required={field.required}
+                    value={text} {oninput} {onblur} />
+            }
+        }
+    };
+    let error = input.state.visible_error().map(|error| {
+        html! { <span class="yew-form-error" role="alert">{ error }</span> }
+    });
+    let help = field
+        .help
+        .map(|help| html! { <span class="yew-form-help">{ help }</span> });
+
+    html! {
+        <div class="yew-form-field">
+            <label>
+                { field.label }
+                { control }
+            </label>
+            { for help }
+            { for error }
+        </div>
+    }
+}
+
+/// A shared [`FieldRenderer`]. Renderers are equal if they are the same instance.
+#[derive(Clone)]
+pub struct Renderer(Rc<dyn FieldRenderer>);
+
+impl Renderer {
+    /// Wraps `renderer`
+    pub fn new(renderer: impl FieldRenderer + 'static) -> Self {
+        Self(Rc::new(renderer))
+    }
+}
+
+impl Default for Renderer {
+    fn default() -> Self {
+        Self::new(render_default)
+    }
+}
+
+impl PartialEq for Renderer {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl fmt::Debug for Renderer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Renderer<_>")
+    }
+}
+
+/// Props for [`SchemaForm`]
+#[derive(Debug, Clone, PartialEq, Properties)]
+pub struct SchemaFormProps<T: FormSchema + Clone + PartialEq + 'static> {
+    /// The edited value
+    pub value: T,
+    /// Called with the new value whenever a field changes
+    pub onchange: Callback<T>,
+    /// Called with the value when the form is submitted and all fields are valid
+    #[prop_or_default]
+    pub onsubmit: Callback<T>,
+    /// Renders the fields
+    #[prop_or_default]
+    pub renderer: Renderer,
+    /// Label of the submit button
+    #[prop_or("Submit")]
+    pub submit_label: &'static str,
+    /// Classes of the `<form>`
+    #[prop_or_default]
+    pub class: Option<String>,
+}
+
+#[doc(hidden)]
+pub enum SchemaFormMsg {
+    Change(&'static str, FieldValue),
+    Blur(&'static str),
+    Submit,
+}
+
+/// A form generated from the [`FormSchema`](trait@FormSchema) of its value.
+///
+/// The form is controlled: changes are passed to `onchange` and shown once the parent passes
+/// the new value back. Fields are validated once they lost focus and on every change
+/// afterwards. Submitting validates all fields.
+///
+/// # Example
+/// ```rust
+/// # use yew::prelude::*;
+/// use yew::form::{FormSchema, SchemaForm};
+///
+/// fn valid_name(name: &String) -> Result<(), String> {
+///     if name.len() <= 20 {
+///         Ok(())
+///     } else {
+///         Err("at most 20 characters".to_string())
+///     }
+/// }
+///
+/// #[derive(Clone, Default, PartialEq, FormSchema)]
+/// struct User {
+///     #[form(required, validate = "valid_name")]
+///     name: String,
+///     #[form(label = "E-mail", kind = "email")]
+///     email: String,
+///     age: Option<u32>,
+///     #[form(help = "Admins can edit all users")]
+///     admin: bool,
+///     #[form(skip)]
+///     id: u64,
+/// }
+///
+/// #[function_component(EditUser)]
+/// fn edit_user() -> Html {
+///     let user = use_state(User::default);
+///     let onchange = {
+///         let user = user.clone();
+///         Callback::from(move |value| user.set(value))
+///     };
+///     html! { <SchemaForm<User> value={(*user).clone()} {onchange} /> }
+/// }
+/// ```
+#[derive(Debug)]
+pub struct SchemaForm<T: FormSchema + Clone + PartialEq + 'static> {
+    states: HashMap<&'static str, FieldState>,
+    _value: std::marker::PhantomData<T>,
+}
+
+impl<T: FormSchema + Clone + PartialEq + 'static> SchemaForm<T> {
+    fn state(&mut self, name: &'static str) -> &mut FieldState {
+        self.states.entry(name).or_default()
+    }
+}
+
+impl<T: FormSchema + Clone + PartialEq + 'static> Component for SchemaForm<T> {
+    type Message = SchemaFormMsg;
+    type Properties = SchemaFormProps<T>;
+
+    fn create(_ctx: &Context<Self>) -> Self {
+        Self {
+            states: HashMap::new(),
+            _value: std::marker::PhantomData,
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        let props = ctx.props();
+        match msg {
+            SchemaFormMsg::Change(name, value) => {
+                let mut new_value = props.value.clone();
+                match new_value.set_value(name, value) {
+                    // Inputs that can't be parsed keep the last valid value
+                    Err(error) => {
+                        let state = self.state(name);
+                        state.touched = true;
+                        state.error = Some(error);
+                    }
+                    Ok(()) => {
+                        let error = new_value.validate_field(name).err();
+                        self.state(name).error = error;
+                        props.onchange.emit(new_value);
+                    }
+                }
+                true
+            }
+            SchemaFormMsg::Blur(name) => {
+                let error = match self.states.get(name).and_then(|state| state.error.clone()) {
+                    // Keep parse errors
+                    Some(error) => Some(error),
+                    None => props.value.validate_field(name).err(),
+                };
+                let state = self.state(name);
+                let changed = !state.touched || state.error != error;
+                state.touched = true;
+                state.error = error;
+                changed
+            }
+            SchemaFormMsg::Submit => {
+                let mut errors = props.value.validate();
+                for field in T::fields() {
+                    let state = self.state(field.name);
+                    state.touched = true;
+                    state.error = errors.remove(field.name);
+                }
+                if self.states.values().all(|state| state.error.is_none()) {
+                    props.onsubmit.emit(props.value.clone());
+                }
+                true
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let props = ctx.props();
+        let fields = T::fields().iter().map(|field| {
+            let name = field.name;
+            let input = FieldInput {
+                value: props.value.value(name).expect("field of the schema"),
+                state: self.states.get(name).cloned().unwrap_or_default(),
+                onchange: ctx
+                    .link()
+                    .callback(move |value| SchemaFormMsg::Change(name, value)),
+                onblur: ctx.link().callback(move |_| SchemaFormMsg::Blur(name)),
+            };
+            props.renderer.0.render(field, &input)
+        });
+        let onsubmit = ctx.link().callback(|e: web_sys::Event| {
+            e.prevent_default();
+            SchemaFormMsg::Submit
+        });
+
+        html! {
+            <form class={props.class.clone()} novalidate=true {onsubmit}>
+                { for fields }
+                <button type="submit">{ props.submit_label }</button>
+            </form>
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate self as yew;
+
+    use super::*;
+
+    #[cfg(feature = "wasm_test")]
+    use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+
+    #[cfg(feature = "wasm_test")]
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    fn positive(value: &Option<i32>) -> Result<(), String> {
+        match value {
+            Some(value) if *value <= 0 => Err("must be positive".to_string()),
+            _ => Ok(()),
+        }
+    }
+
+    #[derive(Clone, Default, PartialEq, FormSchema)]
+    struct Item {
+        #[form(required)]
+        item_name: String,
+        #[form(label = "Amount", validate = "positive")]
+        count: Option<i32>,
+        #[form(kind = "textarea", help = "Shown to customers")]
+        description: String,
+        #[form(skip)]
+        #[allow(dead_code)]
+        id: u64,
+    }
+
+    #[test]
+    fn derives_schema() {
+        let fields = Item::fields();
+        assert_eq!(fields.len(), 3);
+        assert_eq!(
+            fields[0],
+            FieldSchema {
+                name: "item_name",
+                label: "Item name",
+                kind: FieldKind::Text,
+                required: true,
+                help: None,
+            }
+        );
+        assert_eq!(fields[1].label, "Amount");
+        assert_eq!(fields[1].kind, FieldKind::Number);
+        assert_eq!(fields[2].kind, FieldKind::TextArea);
+        assert_eq!(fields[2].help, Some("Shown to customers"));
+
+        let mut item = Item::default();
+        assert_eq!(
+            item.validate_field("item_name"),
+            Err("required".to_string())
+        );
+        item.set_value("count", FieldValue::Text("-1".to_string()))
+            .unwrap();
+        assert_eq!(item.count, Some(-1));
+        assert_eq!(
+            item.validate().get("count").map(String::as_str),
+            Some("must be positive")
+        );
+        assert!(item
+            .set_value("count", FieldValue::Text("x".to_string()))
+            .is_err());
+        item.set_value("count", FieldValue::Text(String::new()))
+            .unwrap();
+        assert_eq!(item.value("count"), Some(FieldValue::Text(String::new())));
+        assert!(item
+            .set_value("id", FieldValue::Text("1".to_string()))
+            .is_err());
+    }
+}
@@ -0,0 +1,232 @@
+//! Fullscreen and pointer lock.
+//!
+//! [`request_fullscreen`] and [`request_pointer_lock`] act on the elements of [`NodeRef`]s.
+//! [`FullscreenProvider`] provides the current [`FullscreenState`] to its descendants, which
+//! function components get with [`use_fullscreen`], so games and media players re-render when the
+//! user leaves fullscreen or releases the pointer, e.g. with the Escape key.
+
+use crate::context::ContextProvider;
+use crate::functional::use_context;
+use crate::html::NodeRef;
+use crate::utils::document;
+use crate::{html, Children, Component, Context, Html, Properties};
+use gloo::events::EventListener;
+use wasm_bindgen::JsValue;
+use web_sys::Element;
+
+/// Error of a fullscreen request
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum FullscreenError {
+    /// The [`NodeRef`] doesn't reference a mounted element
+    #[error("the element is not mounted")]
+    NotMounted,
+    /// The browser denied the request, e.g. because it wasn't made in response to user input
+    #[error("fullscreen request denied: {0:?}")]
+    Js(JsValue),
+}
+
+/// Shows the element referenced by `node` in fullscreen.
+///
+/// Browsers only allow this in response to user input, like in a click listener.
+pub fn request_fullscreen(node: &NodeRef) -> Result<(), FullscreenError> {
+    node.cast::<Element>()
+        .ok_or(FullscreenError::NotMounted)?
+        .request_fullscreen()
+        .map_err(FullscreenError::Js)
+}
+
+/// Leaves fullscreen, if an element is shown in fullscreen
+pub fn exit_fullscreen() {
+    let document = document();
+    if document.fullscreen_element().is_some() {
+        document.exit_fullscreen();
+    }
+}
+
+/// Locks the pointer to the element referenced by `node`, which then receives the mouse
+/// movements while the pointer is hidden.
+///
+/// Browsers only allow this in response to user input. Whether the pointer was locked is known
+/// once the [`FullscreenState`] changes.
+pub fn request_pointer_lock(node: &NodeRef) -> Result<(), FullscreenError> {
+    node.cast::<Element>()
+        .ok_or(FullscreenError::NotMounted)?
+        .request_pointer_lock();
+    Ok(())
+}
+
+/// Releases the pointer, if it is locked
+pub fn exit_pointer_lock() {
+    document().exit_pointer_lock();
+}
+
+/// The elements shown in fullscreen and locking the pointer, provided by [`FullscreenProvider`]
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FullscreenState {
+    fullscreen: Option<Element>,
+    pointer_lock: Option<Element>,
+}
+
+impl FullscreenState {
+    /// Returns the state of the document
+    pub fn current() -> Self {
+        let document = document();
+        Self {
+            fullscreen: document.fullscreen_element(),
+            pointer_lock: document.pointer_lock_element(),
+        }
+    }
+
+    /// Returns the element shown in fullscreen
+    pub fn fullscreen_element(&self) -> Option<&Element> {
+        self.fullscreen.as_ref()
+    }
+
+    /// Returns whether an element is shown in fullscreen
+    pub fn is_fullscreen(&self) -> bool {
+        self.fullscreen.is_some()
+    }
+
+    /// Returns whether the element referenced by `node` is shown in fullscreen
+    pub fn is_fullscreen_node(&self, node: &NodeRef) -> bool {
+        Self::is(&self.fullscreen, node)
+    }
+
+    /// Returns the element the pointer is locked to
+    pub fn pointer_lock_element(&self) -> Option<&Element> {
+        self.pointer_lock.as_ref()
+    }
+
+    /// Returns whether the pointer is locked
+    pub fn is_pointer_locked(&self) -> bool {
+        self.pointer_lock.is_some()
+    }
+
+    /// Returns whether the pointer is locked to the element referenced by `node`
+    pub fn is_pointer_locked_to(&self, node: &NodeRef) -> bool {
+        Self::is(&self.pointer_lock, node)
+    }
+
+    fn is(element: &Option<Element>, node: &NodeRef) -> bool {
+        match (element, node.cast::<Element>()) {
+            (Some(element), Some(node)) => *element == node,
+            _ => false,
+        }
+    }
+}
+
+/// Returns the [`FullscreenState`] of the closest [`FullscreenProvider`]. The component is
+/// rendered again when it changes.
+///
+/// # Panics
+/// Panics if the component is not a descendant of a [`FullscreenProvider`].
+///
+/// # Example
+/// ```rust
+/// # use yew::prelude::*;
+/// use yew::fullscreen::{exit_fullscreen, request_fullscreen, use_fullscreen};
+///
+/// #[function_component(Player)]
+/// fn player() -> Html {
+///     let video = (*use_state(NodeRef::default)).clone();
+///     let state = use_fullscreen();
+///     let onclick = if state.is_fullscreen_node(&video) {
+///         Callback::from(|_| exit_fullscreen())
+///     } else {
+///         let video = video.clone();
+///         Callback::from(move |_| {
+///             let _ = request_fullscreen(&video);
+///         })
+///     };
+///     html! {
+///         <>
+///             <video ref={video} src="intro.webm" />
+///             <button {onclick}>{ "Toggle fullscreen" }</button>
+///         </>
+///     }
+/// }
+/// ```
+pub fn use_fullscreen() -> FullscreenState {
+    use_context::<FullscreenState>()
+        .expect("use_fullscreen must be used inside a FullscreenProvider")
+}
+
+/// Properties of [`FullscreenProvider`]
+#[derive(Debug, Clone, PartialEq, Properties)]
+pub struct FullscreenProviderProps {
+    /// The components using the state
+    #[prop_or_default]
+    pub children: Children,
+}
+
+#[doc(hidden)]
+#[derive(Debug)]
+pub struct FullscreenChanged;
+
+/// Provides the [`FullscreenState`] of the document, updated on `fullscreenchange` and
+/// `pointerlockchange` events.
+///
+/// Struct components get the state with
+/// [`Scope::context::<FullscreenState>`](crate::html::Scope::context).
+pub struct FullscreenProvider {
+    state: FullscreenState,
+    _listeners: [EventListener; 2],
+}
+
+impl Component for FullscreenProvider {
+    type Message = FullscreenChanged;
+    type Properties = FullscreenProviderProps;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let document = document();
+        let listen = |event| {
+            let link = ctx.link().clone();
+            EventListener::new(&document, event, move |_| {
+                link.send_message(FullscreenChanged)
+            })
+        };
+        Self {
+            state: FullscreenState::current(),
+            _listeners: [listen("fullscreenchange"), listen("pointerlockchange")],
+        }
+    }
+
+    fn update(&mut self, _ctx: &Context<Self>, _msg: Self::Message) -> bool {
+        let state = FullscreenState::current();
+        if state == self.state {
+            return false;
+        }
+        self.state = state;
+        true
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        html! {
+            <ContextProvider<FullscreenState> context={self.state.clone()}>
+                { for ctx.props().children.iter() }
+            </ContextProvider<FullscreenState>>
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "wasm_test")]
+    use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+
+    #[cfg(feature = "wasm_test")]
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    #[test]
+    fn requests_need_mounted_elements() {
+        let node = NodeRef::default();
+        assert_eq!(request_fullscreen(&node), Err(FullscreenError::NotMounted));
+        assert_eq!(
+            request_pointer_lock(&node),
+            Err(FullscreenError::NotMounted)
+        );
+        assert!(!FullscreenState::default().is_fullscreen_node(&node));
+    }
+}
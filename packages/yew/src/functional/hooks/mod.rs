@@ -1,9 +1,11 @@
+mod use_app_config;
 mod use_context;
 mod use_effect;
 mod use_reducer;
 mod use_ref;
 mod use_state;
 
+pub use use_app_config::*;
 pub use use_context::*;
 pub use use_effect::*;
 pub use use_reducer::*;
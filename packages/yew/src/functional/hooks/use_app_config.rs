@@ -0,0 +1,20 @@
+use crate::app_handle::AppConfig;
+use crate::functional::get_current_scope;
+use std::rc::Rc;
+
+/// Returns the settings of the app the component is part of.
+///
+/// # Example
+/// ```rust
+/// # use yew::prelude::*;
+/// #[function_component(Logo)]
+/// fn logo() -> Html {
+///     let config = use_app_config();
+///     html! { <img src={config.asset("logo.svg")} alt="Logo" /> }
+/// }
+/// ```
+pub fn use_app_config() -> Rc<AppConfig> {
+    let scope = get_current_scope()
+        .expect("No current Scope. `use_app_config` can only be called inside function components");
+    Rc::clone(&scope.root().config)
+}
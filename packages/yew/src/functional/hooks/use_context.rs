@@ -1,4 +1,4 @@
-use crate::context::ContextHandle;
+use crate::context::{ContextHandle, Service};
 use crate::functional::{get_current_scope, use_hook};
 
 /// Hook for consuming context values in function components.
@@ -63,3 +63,26 @@ pub fn use_context<T: Clone + PartialEq + 'static>() -> Option<T> {
         },
     )
 }
+
+/// Hook for looking up a service by its interface in function components.
+///
+/// This is [`use_context`] for a [`Service<I>`] provided by a
+/// [`ServiceProvider<I>`](crate::context::ServiceProvider). The component re-renders when the
+/// provided service is swapped for another one.
+///
+/// # Example
+/// ```rust
+/// # use yew::prelude::*;
+/// trait Clock {
+///     fn now(&self) -> f64;
+/// }
+///
+/// #[function_component(Timestamp)]
+/// pub fn timestamp() -> Html {
+///     let clock = use_service::<dyn Clock>().expect("no clock provided");
+///     html! { <time>{ clock.now() }</time> }
+/// }
+/// ```
+pub fn use_service<I: ?Sized + 'static>() -> Option<Service<I>> {
+    use_context::<Service<I>>()
+}
@@ -1,10 +1,23 @@
-use crate::functional::use_hook;
+use crate::functional::{use_hook, HookUpdater};
 use std::{borrow::Borrow, rc::Rc};
 
 struct UseEffect<Destructor> {
     destructor: Option<Box<Destructor>>,
 }
 
+/// Runs `cb` after the render, in the layout phase if `layout` is set
+fn schedule<T: 'static>(
+    updater: &HookUpdater,
+    layout: bool,
+    cb: impl FnOnce(&mut T) -> bool + 'static,
+) {
+    if layout {
+        updater.layout(cb);
+    } else {
+        updater.post_render(cb);
+    }
+}
+
 /// This hook is used for hooking into the component's lifecycle.
 ///
 /// # Example
@@ -36,6 +49,51 @@ struct UseEffect<Destructor> {
 /// }
 /// ```
 pub fn use_effect<Destructor>(callback: impl FnOnce() -> Destructor + 'static)
+where
+    Destructor: FnOnce() + 'static,
+{
+    effect(callback, false)
+}
+
+/// This hook is similar to [`use_effect`], but the effect runs synchronously once the component
+/// has rendered, before the effects of [`use_effect`] and before the browser updates the page.
+///
+/// Use it for code measuring or adjusting the layout of the rendered elements, which must not
+/// be seen by the user in between, e.g. to position a tooltip. As with [`use_effect`], no effect
+/// runs unless the component is mounted in a document, e.g. when its markup is only serialized.
+///
+/// # Example
+/// ```rust
+/// # use yew::prelude::*;
+/// # use web_sys::Element;
+/// #[function_component(Tooltip)]
+/// fn tooltip() -> Html {
+///     let node = (*use_ref(NodeRef::default).borrow()).clone();
+///     {
+///         let node = node.clone();
+///         use_layout_effect(move || {
+///             if let Some(el) = node.cast::<Element>() {
+///                 // Flip the tooltip above its anchor when it overflows the viewport
+///                 let rect = el.get_bounding_client_rect();
+///                 let height = yew::utils::window().inner_height().unwrap().as_f64().unwrap();
+///                 if rect.bottom() > height {
+///                     let _ = el.class_list().add_1("above");
+///                 }
+///             }
+///             || ()
+///         });
+///     }
+///     html! { <div ref={node} class="tooltip">{ "Hint" }</div> }
+/// }
+/// ```
+pub fn use_layout_effect<Destructor>(callback: impl FnOnce() -> Destructor + 'static)
+where
+    Destructor: FnOnce() + 'static,
+{
+    effect(callback, true)
+}
+
+fn effect<Destructor>(callback: impl FnOnce() -> Destructor + 'static, layout: bool)
 where
     Destructor: FnOnce() + 'static,
 {
@@ -47,14 +105,18 @@ where
         },
         |_, updater| {
             // Run on every render
-            updater.post_render(move |state: &mut UseEffect<Destructor>| {
-                if let Some(de) = state.destructor.take() {
-                    de();
-                }
-                let new_destructor = callback();
-                state.destructor.replace(Box::new(new_destructor));
-                false
-            });
+            schedule(
+                &updater,
+                layout,
+                move |state: &mut UseEffect<Destructor>| {
+                    if let Some(de) = state.destructor.take() {
+                        de();
+                    }
+                    let new_destructor = callback();
+                    state.destructor.replace(Box::new(new_destructor));
+                    false
+                },
+            );
         },
         |hook| {
             if let Some(destructor) = hook.destructor.take() {
@@ -79,6 +141,31 @@ where
     Callback: FnOnce(&Dependents) -> Destructor + 'static,
     Destructor: FnOnce() + 'static,
     Dependents: PartialEq + 'static,
+{
+    effect_with_deps(callback, deps, false)
+}
+
+/// This hook is similar to [`use_layout_effect`] but it accepts dependencies, like
+/// [`use_effect_with_deps`].
+pub fn use_layout_effect_with_deps<Callback, Destructor, Dependents>(
+    callback: Callback,
+    deps: Dependents,
+) where
+    Callback: FnOnce(&Dependents) -> Destructor + 'static,
+    Destructor: FnOnce() + 'static,
+    Dependents: PartialEq + 'static,
+{
+    effect_with_deps(callback, deps, true)
+}
+
+fn effect_with_deps<Callback, Destructor, Dependents>(
+    callback: Callback,
+    deps: Dependents,
+    layout: bool,
+) where
+    Callback: FnOnce(&Dependents) -> Destructor + 'static,
+    Destructor: FnOnce() + 'static,
+    Dependents: PartialEq + 'static,
 {
     let deps = Rc::new(deps);
     let deps_c = deps.clone();
@@ -92,21 +179,25 @@ where
             }
         },
         move |_, updater| {
-            updater.post_render(move |state: &mut UseEffectDeps<Destructor, Dependents>| {
-                if state.deps != deps {
-                    if let Some(de) = state.destructor.take() {
-                        de();
+            schedule(
+                &updater,
+                layout,
+                move |state: &mut UseEffectDeps<Destructor, Dependents>| {
+                    if state.deps != deps {
+                        if let Some(de) = state.destructor.take() {
+                            de();
+                        }
+                        let new_destructor = callback(deps.borrow());
+                        state.deps = deps;
+                        state.destructor.replace(Box::new(new_destructor));
+                    } else if state.destructor.is_none() {
+                        state
+                            .destructor
+                            .replace(Box::new(callback(state.deps.borrow())));
                     }
-                    let new_destructor = callback(deps.borrow());
-                    state.deps = deps;
-                    state.destructor.replace(Box::new(new_destructor));
-                } else if state.destructor.is_none() {
-                    state
-                        .destructor
-                        .replace(Box::new(callback(state.deps.borrow())));
-                }
-                false
-            });
+                    false
+                },
+            );
         },
         |hook| {
             if let Some(destructor) = hook.destructor.take() {
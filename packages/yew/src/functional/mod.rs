@@ -55,7 +55,19 @@ pub use yew_macro::function_component;
 scoped_thread_local!(static mut CURRENT_HOOK: HookState);
 
 type Msg = Box<dyn FnOnce() -> bool>;
-type ProcessMessage = Rc<dyn Fn(Msg, bool)>;
+type ProcessMessage = Rc<dyn Fn(Msg, Phase)>;
+
+/// When a message of a hook is processed
+#[derive(Clone, Copy)]
+enum Phase {
+    /// Sent to the component right away
+    Update,
+    /// Sent to the component once it has rendered
+    PostRender,
+    /// Run while the component is notified that it rendered, before the messages of
+    /// [`Phase::PostRender`]
+    Layout,
+}
 
 struct HookState {
     counter: usize,
@@ -81,6 +93,7 @@ pub struct FunctionComponent<T: FunctionProvider + 'static> {
     _never: std::marker::PhantomData<T>,
     hook_state: RefCell<HookState>,
     message_queue: MsgQueue,
+    layout_queue: MsgQueue,
 }
 
 impl<T: FunctionProvider> fmt::Debug for FunctionComponent<T> {
@@ -110,21 +123,21 @@ where
     fn create(ctx: &Context<Self>) -> Self {
         let scope = AnyScope::from(ctx.link().clone());
         let message_queue = MsgQueue::default();
+        let layout_queue = MsgQueue::default();
 
         Self {
             _never: std::marker::PhantomData::default(),
             message_queue: message_queue.clone(),
+            layout_queue: layout_queue.clone(),
             hook_state: RefCell::new(HookState {
                 counter: 0,
                 scope,
                 process_message: {
                     let scope = ctx.link().clone();
-                    Rc::new(move |msg, post_render| {
-                        if post_render {
-                            message_queue.push(msg);
-                        } else {
-                            scope.send_message(msg);
-                        }
+                    Rc::new(move |msg, phase| match phase {
+                        Phase::Update => scope.send_message(msg),
+                        Phase::PostRender => message_queue.push(msg),
+                        Phase::Layout => layout_queue.push(msg),
                     })
                 },
                 hooks: vec![],
@@ -142,6 +155,11 @@ where
     }
 
     fn rendered(&mut self, ctx: &Context<Self>, _first_render: bool) {
+        let layout = self.layout_queue.drain();
+        let should_render = layout.into_iter().fold(false, |acc, msg| msg() || acc);
+        if should_render {
+            ctx.link().send_message(Box::new(|| true) as Msg);
+        }
         for msg in self.message_queue.drain() {
             ctx.link().send_message(msg);
         }
@@ -179,9 +197,11 @@ impl MsgQueue {
 /// The `HookUpdater` provides a convenient interface for hooking into the lifecycle of
 /// the underlying Yew Component that backs the function component.
 ///
-/// Two interfaces are provided - callback and post_render.
+/// Three interfaces are provided - callback, post_render and layout.
 /// - `callback` allows the creation of regular yew callbacks on the host component.
 /// - `post_render` allows the creation of events that happen after a render is complete.
+/// - `layout` allows the creation of events that happen synchronously once the render is
+///   complete, before the events of `post_render`.
 ///
 /// See [`use_effect`](hooks::use_effect()) and [`use_context`](hooks::use_context())
 /// for more details on how to use the hook updater to provide function components
@@ -198,35 +218,35 @@ impl HookUpdater {
     where
         F: FnOnce(&mut T) -> bool + 'static,
     {
-        let internal_hook_state = self.hook.clone();
-        let process_message = self.process_message.clone();
-
         // Update the component
         // We're calling "link.send_message", so we're not calling it post-render
-        let post_render = false;
-        process_message(
-            Box::new(move || {
-                let mut r = internal_hook_state.borrow_mut();
-                let hook: &mut T = r
-                    .downcast_mut()
-                    .expect("internal error: hook downcasted to wrong type");
-                cb(hook)
-            }),
-            post_render,
-        );
+        self.queue(cb, Phase::Update);
     }
 
     /// Callback called after the render
     pub fn post_render<T: 'static, F>(&self, cb: F)
+    where
+        F: FnOnce(&mut T) -> bool + 'static,
+    {
+        // We're calling "message_queue.push", so it runs after the render
+        self.queue(cb, Phase::PostRender);
+    }
+
+    /// Callback called synchronously once the render is complete, before the ones of
+    /// [`post_render`](Self::post_render) and before the browser updates the page
+    pub fn layout<T: 'static, F>(&self, cb: F)
+    where
+        F: FnOnce(&mut T) -> bool + 'static,
+    {
+        self.queue(cb, Phase::Layout);
+    }
+
+    fn queue<T: 'static, F>(&self, cb: F, phase: Phase)
     where
         F: FnOnce(&mut T) -> bool + 'static,
     {
         let internal_hook_state = self.hook.clone();
         let process_message = self.process_message.clone();
-
-        // Update the component
-        // We're calling "message_queue.push", so not calling it post-render
-        let post_render = true;
         process_message(
             Box::new(move || {
                 let mut hook = internal_hook_state.borrow_mut();
@@ -235,7 +255,7 @@ impl HookUpdater {
                     .expect("internal error: hook downcasted to wrong type");
                 cb(hook)
             }),
-            post_render,
+            phase,
         );
     }
 }
@@ -0,0 +1,339 @@
+//! This module defines the `GestureArea` component.
+
+use crate::html::{Classes, NodeRef};
+use crate::{html, Callback, Children, Component, Context, Html, Properties};
+use gloo::timers::callback::Timeout;
+use web_sys::PointerEvent;
+
+/// A position in client coordinates
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Point {
+    /// Horizontal coordinate
+    pub x: f64,
+    /// Vertical coordinate
+    pub y: f64,
+}
+
+impl Point {
+    fn of(event: &PointerEvent) -> Self {
+        Self {
+            x: event.client_x() as f64,
+            y: event.client_y() as f64,
+        }
+    }
+
+    fn distance(self, other: Point) -> f64 {
+        (self.x - other.x).hypot(self.y - other.y)
+    }
+}
+
+/// Direction of a [Swipe]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SwipeDirection {
+    /// Towards the left edge
+    Left,
+    /// Towards the right edge
+    Right,
+    /// Towards the top edge
+    Up,
+    /// Towards the bottom edge
+    Down,
+}
+
+impl SwipeDirection {
+    /// Direction along the dominant axis of a movement
+    fn of(dx: f64, dy: f64) -> Self {
+        if dx.abs() >= dy.abs() {
+            if dx < 0. {
+                Self::Left
+            } else {
+                Self::Right
+            }
+        } else if dy < 0. {
+            Self::Up
+        } else {
+            Self::Down
+        }
+    }
+}
+
+/// A fast single pointer movement
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Swipe {
+    /// Dominant direction of the movement
+    pub direction: SwipeDirection,
+    /// Distance between the start and end positions in pixels
+    pub distance: f64,
+    /// Average velocity in pixels per millisecond
+    pub velocity: f64,
+}
+
+/// Props for [`GestureArea`]
+#[derive(Debug, Clone, PartialEq, Properties)]
+pub struct GestureAreaProps {
+    /// Called when a pointer is released quickly without moving
+    #[prop_or_default]
+    pub ontap: Option<Callback<Point>>,
+    /// Called when a pointer is held down without moving for `long_press_ms`
+    #[prop_or_default]
+    pub onlongpress: Option<Callback<Point>>,
+    /// Called when a pointer is released after a fast movement
+    #[prop_or_default]
+    pub onswipe: Option<Callback<Swipe>>,
+    /// Called while two pointers are down with the ratio of their current to their initial
+    /// distance
+    #[prop_or_default]
+    pub onpinch: Option<Callback<f64>>,
+
+    /// Milliseconds a pointer has to be held down for a long press
+    #[prop_or(500)]
+    pub long_press_ms: u32,
+    /// Maximum movement in pixels of a pointer still considered a tap or long press
+    #[prop_or(10.)]
+    pub tap_slop: f64,
+    /// Minimum movement in pixels of a swipe
+    #[prop_or(30.)]
+    pub swipe_distance: f64,
+    /// Minimum velocity of a swipe in pixels per millisecond
+    #[prop_or(0.3)]
+    pub swipe_velocity: f64,
+
+    /// Classes of the wrapping `<div>`
+    #[prop_or_default]
+    pub class: Classes,
+    /// Children
+    #[prop_or_default]
+    pub children: Children,
+}
+
+/// Messages of [`GestureArea`]
+#[doc(hidden)]
+pub enum GestureAreaMsg {
+    Down(PointerEvent),
+    Move(PointerEvent),
+    Up(PointerEvent),
+    Cancel(PointerEvent),
+    LongPress,
+}
+
+/// State of a pointer that is down
+#[derive(Debug, Clone, Copy)]
+struct Track {
+    id: i32,
+    start: Point,
+    start_time: f64,
+    last: Point,
+}
+
+/// Recognizes gestures within its children.
+///
+/// The component wraps its children in a `<div>` with `touch-action: none` and captures every
+/// pointer that goes down inside it, so a gesture is tracked to its end even if the pointer leaves
+/// the area.
+///
+/// A single pointer produces a tap, a long press or a swipe. Putting a second pointer down starts
+/// a pinch and cancels the single pointer gestures until all pointers are up again.
+pub struct GestureArea {
+    node_ref: NodeRef,
+    pointers: Vec<Track>,
+    /// Distance of the first two pointers when the pinch started
+    pinch_start: Option<f64>,
+    /// The current pointers do not produce single pointer gestures anymore
+    consumed: bool,
+    long_press: Option<Timeout>,
+}
+
+impl GestureArea {
+    fn track_mut(&mut self, id: i32) -> Option<&mut Track> {
+        self.pointers.iter_mut().find(|t| t.id == id)
+    }
+
+    fn remove(&mut self, id: i32) -> Option<Track> {
+        let i = self.pointers.iter().position(|t| t.id == id)?;
+        self.node_ref.release_pointer_capture(id);
+        let track = self.pointers.remove(i);
+        if self.pointers.is_empty() {
+            self.pinch_start = None;
+            self.consumed = false;
+        }
+        Some(track)
+    }
+
+    fn pinch_distance(&self) -> Option<f64> {
+        match self.pointers.as_slice() {
+            [a, b, ..] => Some(a.last.distance(b.last)),
+            _ => None,
+        }
+    }
+
+    fn down(&mut self, ctx: &Context<Self>, event: PointerEvent) {
+        // Capturing fails, if the pointer is already gone. The gesture is then canceled by the
+        // browser anyway.
+        self.node_ref.set_pointer_capture(event.pointer_id());
+
+        let point = Point::of(&event);
+        self.pointers.push(Track {
+            id: event.pointer_id(),
+            start: point,
+            start_time: event.time_stamp(),
+            last: point,
+        });
+
+        match self.pointers.len() {
+            1 => {
+                if ctx.props().onlongpress.is_some() {
+                    let link = ctx.link().clone();
+                    self.long_press = Some(Timeout::new(ctx.props().long_press_ms, move || {
+                        link.send_message(GestureAreaMsg::LongPress)
+                    }));
+                }
+            }
+            _ => {
+                self.long_press = None;
+                self.consumed = true;
+                if self.pinch_start.is_none() {
+                    self.pinch_start = self.pinch_distance();
+                }
+            }
+        }
+    }
+
+    fn moved(&mut self, ctx: &Context<Self>, event: PointerEvent) {
+        let slop = ctx.props().tap_slop;
+        let track = match self.track_mut(event.pointer_id()) {
+            Some(track) => track,
+            None => return,
+        };
+        track.last = Point::of(&event);
+        if track.start.distance(track.last) > slop {
+            self.long_press = None;
+        }
+
+        if let (Some(start), Some(current)) = (self.pinch_start, self.pinch_distance()) {
+            if let Some(onpinch) = &ctx.props().onpinch {
+                if start > 0. {
+                    onpinch.emit(current / start);
+                }
+            }
+        }
+    }
+
+    fn up(&mut self, ctx: &Context<Self>, event: PointerEvent) {
+        self.long_press = None;
+        let consumed = self.consumed;
+        let mut track = match self.remove(event.pointer_id()) {
+            Some(track) => track,
+            None => return,
+        };
+        if consumed {
+            return;
+        }
+        track.last = Point::of(&event);
+
+        let props = ctx.props();
+        let distance = track.start.distance(track.last);
+        let duration = event.time_stamp() - track.start_time;
+        if distance <= props.tap_slop {
+            if duration < props.long_press_ms as f64 {
+                if let Some(ontap) = &props.ontap {
+                    ontap.emit(track.last);
+                }
+            }
+        } else if distance >= props.swipe_distance {
+            let velocity = distance / duration.max(1.);
+            if velocity >= props.swipe_velocity {
+                if let Some(onswipe) = &props.onswipe {
+                    onswipe.emit(Swipe {
+                        direction: SwipeDirection::of(
+                            track.last.x - track.start.x,
+                            track.last.y - track.start.y,
+                        ),
+                        distance,
+                        velocity,
+                    });
+                }
+            }
+        }
+    }
+}
+
+impl Component for GestureArea {
+    type Message = GestureAreaMsg;
+    type Properties = GestureAreaProps;
+
+    fn create(_ctx: &Context<Self>) -> Self {
+        Self {
+            node_ref: NodeRef::default(),
+            pointers: Vec::new(),
+            pinch_start: None,
+            consumed: false,
+            long_press: None,
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            GestureAreaMsg::Down(event) => self.down(ctx, event),
+            GestureAreaMsg::Move(event) => self.moved(ctx, event),
+            GestureAreaMsg::Up(event) => self.up(ctx, event),
+            GestureAreaMsg::Cancel(event) => {
+                self.long_press = None;
+                self.remove(event.pointer_id());
+            }
+            GestureAreaMsg::LongPress => {
+                self.long_press = None;
+                if let [track] = self.pointers.as_slice() {
+                    let point = track.last;
+                    self.consumed = true;
+                    if let Some(onlongpress) = &ctx.props().onlongpress {
+                        onlongpress.emit(point);
+                    }
+                }
+            }
+        }
+        false
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let link = ctx.link();
+        html! {
+            <div
+                ref={self.node_ref.clone()}
+                class={ctx.props().class.clone()}
+                style="touch-action: none"
+                onpointerdown={link.callback(GestureAreaMsg::Down)}
+                onpointermove={link.callback(GestureAreaMsg::Move)}
+                onpointerup={link.callback(GestureAreaMsg::Up)}
+                onpointercancel={link.callback(GestureAreaMsg::Cancel)}
+            >
+                { for ctx.props().children.iter() }
+            </div>
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "wasm_test")]
+    use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+
+    #[cfg(feature = "wasm_test")]
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    #[test]
+    fn swipe_direction_follows_dominant_axis() {
+        assert_eq!(SwipeDirection::of(-40., 10.), SwipeDirection::Left);
+        assert_eq!(SwipeDirection::of(40., -10.), SwipeDirection::Right);
+        assert_eq!(SwipeDirection::of(5., -40.), SwipeDirection::Up);
+        assert_eq!(SwipeDirection::of(-5., 40.), SwipeDirection::Down);
+    }
+
+    #[test]
+    fn point_distance() {
+        let a = Point { x: 0., y: 0. };
+        let b = Point { x: 3., y: 4. };
+        assert!((a.distance(b) - 5.).abs() < f64::EPSILON);
+    }
+}
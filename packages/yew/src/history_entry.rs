@@ -0,0 +1,334 @@
+//! Transient UI state coupled to history entries.
+//!
+//! On mobile, the back button or gesture is how users close a modal, a drawer or a menu. A
+//! [`HistoryEntry`] pushes a synthetic entry onto the history of the browser while such state is
+//! open, so going back closes it instead of leaving the page. When the state is closed by other
+//! means, dropping the entry removes the synthetic entry again, so it doesn't take a click on the
+//! back button to leave. Function components use [`use_history_entry`], and dialogs opened with
+//! [`DialogOptions::close_on_back`](crate::dialog::DialogOptions::close_on_back) use it
+//! automatically.
+//!
+//! Entries opened on top of each other are closed one at a time, the last opened first. The
+//! synthetic entries keep the URL of the page, unless they are pushed with a hash, see
+//! [`HistoryEntry::push_with_hash`].
+
+use crate::functional::use_effect_with_deps;
+use crate::utils::window;
+use crate::Callback;
+use gloo::events::EventListener;
+use std::cell::RefCell;
+use std::fmt;
+use wasm_bindgen::JsValue;
+use web_sys::History;
+
+/// Property of the history state marking the synthetic entries
+const MARKER: &str = "yewHistoryEntry";
+
+struct Open {
+    id: f64,
+    on_back: Callback<()>,
+    url: Option<String>,
+    /// The synthetic entry was pushed. It isn't while the entry of a previously dropped
+    /// [`HistoryEntry`] is being removed.
+    pushed: bool,
+}
+
+struct Registry {
+    /// Ids are seeded with the time the page loaded, so the markers left in the history by a
+    /// previous load of the page are never mistaken for entries of this one
+    next_id: f64,
+    /// Open entries, in the order they were opened
+    open: Vec<Open>,
+    /// Number of `history.back()` calls of dropped entries whose `popstate` is yet to come
+    pending_backs: usize,
+    listener: Option<EventListener>,
+}
+
+thread_local! {
+    static REGISTRY: RefCell<Registry> = RefCell::new(Registry {
+        next_id: js_sys::Date::now().floor(),
+        open: Vec::new(),
+        pending_backs: 0,
+        listener: None,
+    });
+}
+
+fn history() -> History {
+    window().history().expect("no history found")
+}
+
+/// Returns the id of the synthetic entry the history is at, if it is at one
+fn current_marker(history: &History) -> Option<f64> {
+    let state = history.state().ok()?;
+    if !state.is_object() {
+        return None;
+    }
+    js_sys::Reflect::get(&state, &JsValue::from_str(MARKER))
+        .ok()?
+        .as_f64()
+}
+
+fn push(history: &History, open: &mut Open) {
+    let state = js_sys::Object::new();
+    let _ = js_sys::Reflect::set(&state, &JsValue::from_str(MARKER), &JsValue::from(open.id));
+    let _ = history.push_state_with_url(&state, "", open.url.as_deref());
+    open.pushed = true;
+}
+
+fn on_popstate() {
+    let history = history();
+    let current = current_marker(&history);
+    let closed = REGISTRY.with(|registry| {
+        let mut registry = registry.borrow_mut();
+        let registry = &mut *registry;
+        if registry.pending_backs > 0 {
+            // The entry of a dropped `HistoryEntry` was removed, so the ones opened since can be
+            // pushed without being removed with it
+            registry.pending_backs -= 1;
+            if registry.pending_backs == 0 {
+                for open in registry.open.iter_mut().filter(|open| !open.pushed) {
+                    push(&history, open);
+                }
+            }
+            return Vec::new();
+        }
+
+        // Going back closes the entries above the one the history is at now
+        let current_id = current.unwrap_or(f64::NEG_INFINITY);
+        let split = registry
+            .open
+            .iter()
+            .position(|open| open.pushed && open.id > current_id)
+            .unwrap_or(registry.open.len());
+        let closed: Vec<_> = registry.open.drain(split..).collect();
+
+        // Going forward to the synthetic entry of a closed state skips it
+        if let Some(id) = current {
+            if !registry.open.iter().any(|open| open.id == id) {
+                let _ = history.back();
+            }
+        }
+        closed
+    });
+    // The closed states may drop other entries
+    for open in closed.into_iter().rev() {
+        open.on_back.emit(());
+    }
+}
+
+/// Keeps a synthetic history entry for transient UI state, see the [module docs](self).
+///
+/// Going back calls `on_back`, which should close the state and drop the entry. Dropping the
+/// entry while the history is at it goes back to remove it.
+///
+/// # Example
+/// ```rust,no_run
+/// # use yew::prelude::*;
+/// use yew::history_entry::HistoryEntry;
+///
+/// enum Msg {
+///     OpenMenu,
+///     CloseMenu,
+/// }
+///
+/// struct Nav {
+///     menu: Option<HistoryEntry>,
+/// }
+///
+/// impl Component for Nav {
+///     type Message = Msg;
+///     type Properties = ();
+///
+///     fn create(_ctx: &Context<Self>) -> Self {
+///         Self { menu: None }
+///     }
+///
+///     fn update(&mut self, ctx: &Context<Self>, msg: Msg) -> bool {
+///         match msg {
+///             Msg::OpenMenu => {
+///                 let on_back = ctx.link().callback(|_| Msg::CloseMenu);
+///                 self.menu = Some(HistoryEntry::push_with_hash("menu", on_back));
+///             }
+///             // Dropping the entry removes it from the history, unless going back closed the menu
+///             Msg::CloseMenu => self.menu = None,
+///         }
+///         true
+///     }
+///
+///     fn view(&self, ctx: &Context<Self>) -> Html {
+///         let menu = self.menu.is_some().then(|| html! {
+///             <nav>
+///                 <button onclick={ctx.link().callback(|_| Msg::CloseMenu)}>{ "Close" }</button>
+///             </nav>
+///         });
+///         html! {
+///             <>
+///                 <button onclick={ctx.link().callback(|_| Msg::OpenMenu)}>{ "Menu" }</button>
+///                 { for menu }
+///             </>
+///         }
+///     }
+/// }
+/// ```
+pub struct HistoryEntry {
+    id: f64,
+}
+
+impl HistoryEntry {
+    /// Pushes a synthetic entry with the URL of the page. `on_back` is called when the user goes
+    /// back from it.
+    pub fn push(on_back: Callback<()>) -> Self {
+        Self::open(on_back, None)
+    }
+
+    /// Pushes a synthetic entry whose URL is the one of the page with the fragment `hash`, e.g.
+    /// `"menu"`. `on_back` is called when the user goes back from it.
+    pub fn push_with_hash(hash: &str, on_back: Callback<()>) -> Self {
+        let location = window().location();
+        let url = format!(
+            "{}{}#{}",
+            location.pathname().unwrap_or_default(),
+            location.search().unwrap_or_default(),
+            hash.trim_start_matches('#'),
+        );
+        Self::open(on_back, Some(url))
+    }
+
+    fn open(on_back: Callback<()>, url: Option<String>) -> Self {
+        REGISTRY.with(|registry| {
+            let mut registry = registry.borrow_mut();
+            if registry.listener.is_none() {
+                registry.listener =
+                    Some(EventListener::new(&window(), "popstate", |_| on_popstate()));
+            }
+            let id = registry.next_id;
+            registry.next_id += 1.0;
+            let mut open = Open {
+                id,
+                on_back,
+                url,
+                pushed: false,
+            };
+            if registry.pending_backs == 0 {
+                push(&history(), &mut open);
+            }
+            registry.open.push(open);
+            Self { id }
+        })
+    }
+
+    /// Returns whether the entry is still open, i.e. the user didn't go back from it
+    pub fn is_open(&self) -> bool {
+        REGISTRY.with(|registry| registry.borrow().open.iter().any(|open| open.id == self.id))
+    }
+}
+
+impl Drop for HistoryEntry {
+    fn drop(&mut self) {
+        REGISTRY.with(|registry| {
+            let mut registry = registry.borrow_mut();
+            let index = match registry.open.iter().position(|open| open.id == self.id) {
+                Some(index) => index,
+                // The user went back from the entry
+                None => return,
+            };
+            let open = registry.open.remove(index);
+            // An entry which is not current anymore, e.g. because another one was pushed on top
+            // of it, is skipped when the user goes forward to it
+            let history = history();
+            if open.pushed && current_marker(&history) == Some(self.id) {
+                registry.pending_backs += 1;
+                let _ = history.back();
+            }
+        })
+    }
+}
+
+impl fmt::Debug for HistoryEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HistoryEntry")
+            .field("id", &self.id)
+            .finish()
+    }
+}
+
+/// Keeps a synthetic history entry while `open` is true, e.g. while a drawer is open. `on_back`
+/// is called when the user goes back from it, and should set `open` to false.
+///
+/// # Example
+/// ```rust
+/// # use yew::prelude::*;
+/// use yew::history_entry::use_history_entry;
+///
+/// #[function_component(Drawer)]
+/// fn drawer() -> Html {
+///     let open = use_state(|| false);
+///     let close = {
+///         let open = open.clone();
+///         Callback::from(move |_| open.set(false))
+///     };
+///     use_history_entry(*open, close.clone());
+///
+///     if *open {
+///         html! { <aside><button onclick={close}>{ "Close" }</button></aside> }
+///     } else {
+///         let open = open.clone();
+///         html! { <button onclick={Callback::from(move |_| open.set(true))}>{ "Open" }</button> }
+///     }
+/// }
+/// ```
+pub fn use_history_entry(open: bool, on_back: Callback<()>) {
+    use_effect_with_deps(
+        move |open| {
+            let entry = open.then(|| HistoryEntry::push(on_back));
+            move || drop(entry)
+        },
+        open,
+    );
+}
+
+#[cfg(all(test, feature = "wasm_test"))]
+mod tests {
+    use super::*;
+    use std::rc::Rc;
+    use wasm_bindgen_futures::JsFuture;
+    use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    async fn sleep(ms: i32) {
+        let promise = js_sys::Promise::new(&mut |resolve, _| {
+            window()
+                .set_timeout_with_callback_and_timeout_and_arguments_0(&resolve, ms)
+                .unwrap();
+        });
+        JsFuture::from(promise).await.unwrap();
+    }
+
+    #[test]
+    async fn back_closes_the_last_entry_and_drop_cleans_up() {
+        let history = history();
+        let length = history.length().unwrap();
+
+        let backs = Rc::new(RefCell::new(Vec::new()));
+        let on_back = |name: &'static str| {
+            let backs = Rc::clone(&backs);
+            Callback::from(move |_| backs.borrow_mut().push(name))
+        };
+        let drawer = HistoryEntry::push(on_back("drawer"));
+        let modal = HistoryEntry::push(on_back("modal"));
+        assert_eq!(history.length().unwrap(), length + 2);
+
+        history.back().unwrap();
+        sleep(50).await;
+        assert_eq!(*backs.borrow(), vec!["modal"]);
+        assert!(!modal.is_open());
+        assert!(drawer.is_open());
+        drop(modal);
+
+        drop(drawer);
+        sleep(50).await;
+        assert_eq!(*backs.borrow(), vec!["modal"]);
+        assert_eq!(current_marker(&history), None);
+    }
+}
@@ -65,6 +65,65 @@ impl Classes {
     }
 }
 
+/// A prefix applied to the classes emitted by a component.
+///
+/// Returning a scope from [`Component::style_scope`](super::Component::style_scope) makes every
+/// class in the component's `view` prefixed with it, so component libraries can ship CSS without
+/// worrying about collisions with the classes of the application.
+///
+/// # Example
+///
+/// ```
+/// # use yew::html::StyleScope;
+/// let scope = StyleScope::new("card");
+/// assert_eq!(scope.prefix("title  large"), "card-title card-large");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct StyleScope(Cow<'static, str>);
+
+impl StyleScope {
+    /// Creates a style scope with the given name.
+    pub fn new(name: impl Into<Cow<'static, str>>) -> Self {
+        Self(name.into())
+    }
+
+    /// Creates a style scope that is unique to the type `T`.
+    ///
+    /// The name is a hash of the type's name, so it stays the same across renders and page loads
+    /// of the same build.
+    pub fn of<T: ?Sized + 'static>() -> Self {
+        // 32 bit FNV-1a
+        let hash = std::any::type_name::<T>()
+            .bytes()
+            .fold(0x811c_9dc5_u32, |hash, b| {
+                (hash ^ u32::from(b)).wrapping_mul(0x0100_0193)
+            });
+        Self(Cow::Owned(format!("s{:08x}", hash)))
+    }
+
+    /// Returns the name of the scope.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Prefixes every class of the whitespace separated list `classes` with the scope.
+    ///
+    /// This is the transformation applied to the `class` attribute of the elements of a scoped
+    /// component, and can be used to reference the scoped classes from stylesheets.
+    pub fn prefix(&self, classes: &str) -> String {
+        let mut prefixed = String::with_capacity(classes.len());
+        for class in classes.split_whitespace() {
+            if !prefixed.is_empty() {
+                prefixed.push(' ');
+            }
+            prefixed.push_str(&self.0);
+            prefixed.push('-');
+            prefixed.push_str(class);
+        }
+        prefixed
+    }
+}
+
 impl IntoPropValue<AttrValue> for Classes {
     #[inline]
     fn into_prop_value(mut self) -> AttrValue {
@@ -271,6 +330,19 @@ mod tests {
         assert!(subject.contains("bar"));
     }
 
+    #[test]
+    fn style_scope_prefixes_every_class() {
+        let scope = StyleScope::new("lib");
+        assert_eq!(scope.prefix("foo bar"), "lib-foo lib-bar");
+        assert_eq!(scope.prefix(" "), "");
+    }
+
+    #[test]
+    fn style_scope_is_unique_per_type() {
+        assert_eq!(StyleScope::of::<TestClass>(), StyleScope::of::<TestClass>());
+        assert_ne!(StyleScope::of::<TestClass>(), StyleScope::of::<Classes>());
+    }
+
     #[test]
     fn can_be_collected() {
         let classes = vec!["foo", "bar"];
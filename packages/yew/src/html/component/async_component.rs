@@ -0,0 +1,303 @@
+//! Components that load data before they are created
+
+use super::{Component, Context, Properties};
+use crate::html::Html;
+use std::future::Future;
+use std::pin::Pin;
+use wasm_bindgen_futures::spawn_local;
+
+/// A component that awaits data before it is created.
+///
+/// Instead of starting with placeholder state and loading data with a message, an
+/// `AsyncComponent` gets the data it needs in [`create`](Self::create). Until the future returned
+/// by [`prepare`](Self::prepare) completes, the [`fallback`](Self::fallback) is rendered.
+///
+/// The component is used through [`Async`], which implements [`Component`]. Messages sent to it
+/// before it is created are queued and passed to [`update`](Self::update) after
+/// [`create`](Self::create).
+///
+/// # Example
+/// ```rust
+/// # use yew::prelude::*;
+/// use std::future::Future;
+/// use std::pin::Pin;
+/// use yew::html::{Async, AsyncComponent};
+///
+/// # async fn fetch_user(id: u32) -> String { id.to_string() }
+/// #[derive(Clone, PartialEq, Properties)]
+/// pub struct ProfileProps {
+///     pub id: u32,
+/// }
+///
+/// pub struct ProfileInner {
+///     name: String,
+/// }
+///
+/// impl AsyncComponent for ProfileInner {
+///     type Message = ();
+///     type Properties = ProfileProps;
+///     type Data = String;
+///
+///     fn prepare(props: &ProfileProps) -> Pin<Box<dyn Future<Output = String>>> {
+///         Box::pin(fetch_user(props.id))
+///     }
+///
+///     fn create(_ctx: &Context<Async<Self>>, name: String) -> Self {
+///         Self { name }
+///     }
+///
+///     fn view(&self, _ctx: &Context<Async<Self>>) -> Html {
+///         html! { <h1>{ &self.name }</h1> }
+///     }
+///
+///     fn fallback(_ctx: &Context<Async<Self>>) -> Html {
+///         html! { <p>{ "Loading…" }</p> }
+///     }
+/// }
+///
+/// pub type Profile = Async<ProfileInner>;
+///
+/// # fn view() -> Html {
+/// html! { <Profile id=42 /> }
+/// # }
+/// ```
+pub trait AsyncComponent: Sized + 'static {
+    /// Messages are used to make the component dynamic and interactive
+    type Message: 'static;
+
+    /// The component's properties
+    type Properties: Properties;
+
+    /// Data the component is created with
+    type Data: 'static;
+
+    /// Returns the future that loads the data of the component. It is called when the component
+    /// is mounted and again if its props change before the data is ready, in which case the
+    /// result of the earlier future is discarded.
+    fn prepare(props: &Self::Properties) -> Pin<Box<dyn Future<Output = Self::Data>>>;
+
+    /// Creates the component with the prepared data
+    fn create(ctx: &Context<Async<Self>>, data: Self::Data) -> Self;
+
+    /// See [`Component::update`]
+    #[allow(unused_variables)]
+    fn update(&mut self, ctx: &Context<Async<Self>>, msg: Self::Message) -> bool {
+        true
+    }
+
+    /// See [`Component::changed`]
+    #[allow(unused_variables)]
+    fn changed(&mut self, ctx: &Context<Async<Self>>) -> bool {
+        true
+    }
+
+    /// See [`Component::view`]
+    fn view(&self, ctx: &Context<Async<Self>>) -> Html;
+
+    /// Renders the component while the data is being prepared
+    #[allow(unused_variables)]
+    fn fallback(ctx: &Context<Async<Self>>) -> Html {
+        Html::default()
+    }
+
+    /// See [`Component::rendered`]. `first_render` is `true` for the first render after
+    /// [`create`](Self::create).
+    #[allow(unused_variables)]
+    fn rendered(&mut self, ctx: &Context<Async<Self>>, first_render: bool) {}
+
+    /// See [`Component::destroy`]
+    #[allow(unused_variables)]
+    fn destroy(&mut self, ctx: &Context<Async<Self>>) {}
+}
+
+/// The [`Component`] rendering an [`AsyncComponent`]
+pub struct Async<COMP: AsyncComponent> {
+    state: AsyncState<COMP>,
+}
+
+enum AsyncState<COMP: AsyncComponent> {
+    Preparing {
+        /// Incremented whenever the data is prepared again, to discard stale results
+        generation: u32,
+        queued: Vec<COMP::Message>,
+    },
+    Ready {
+        component: COMP,
+        has_rendered: bool,
+    },
+}
+
+impl<COMP: AsyncComponent> Async<COMP> {
+    /// Returns the component, if it has been created
+    pub fn get(&self) -> Option<&COMP> {
+        match &self.state {
+            AsyncState::Ready { component, .. } => Some(component),
+            AsyncState::Preparing { .. } => None,
+        }
+    }
+
+    fn prepare(ctx: &Context<Self>, generation: u32) {
+        let future = COMP::prepare(ctx.props());
+        let scope = ctx.link().clone();
+        spawn_local(async move {
+            let data = future.await;
+            scope.run_with_component(move |this: &mut Self, ctx| this.ready(ctx, generation, data));
+        });
+    }
+
+    fn ready(&mut self, ctx: &Context<Self>, generation: u32, data: COMP::Data) -> bool {
+        let queued = match &mut self.state {
+            AsyncState::Preparing {
+                generation: current,
+                queued,
+            } if *current == generation => std::mem::take(queued),
+            _ => return false,
+        };
+
+        let mut component = COMP::create(ctx, data);
+        for msg in queued {
+            component.update(ctx, msg);
+        }
+        self.state = AsyncState::Ready {
+            component,
+            has_rendered: false,
+        };
+        true
+    }
+}
+
+impl<COMP: AsyncComponent> Component for Async<COMP> {
+    type Message = COMP::Message;
+    type Properties = COMP::Properties;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        Self::prepare(ctx, 0);
+        Self {
+            state: AsyncState::Preparing {
+                generation: 0,
+                queued: Vec::new(),
+            },
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match &mut self.state {
+            AsyncState::Preparing { queued, .. } => {
+                queued.push(msg);
+                false
+            }
+            AsyncState::Ready { component, .. } => component.update(ctx, msg),
+        }
+    }
+
+    fn changed(&mut self, ctx: &Context<Self>) -> bool {
+        match &mut self.state {
+            AsyncState::Preparing { generation, .. } => {
+                *generation += 1;
+                Self::prepare(ctx, *generation);
+                // The fallback may depend on the props
+                true
+            }
+            AsyncState::Ready { component, .. } => component.changed(ctx),
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        match &self.state {
+            AsyncState::Preparing { .. } => COMP::fallback(ctx),
+            AsyncState::Ready { component, .. } => component.view(ctx),
+        }
+    }
+
+    fn rendered(&mut self, ctx: &Context<Self>, _first_render: bool) {
+        if let AsyncState::Ready {
+            component,
+            has_rendered,
+        } = &mut self.state
+        {
+            component.rendered(ctx, !std::mem::replace(has_rendered, true));
+        }
+    }
+
+    fn destroy(&mut self, ctx: &Context<Self>) {
+        if let AsyncState::Ready { component, .. } = &mut self.state {
+            component.destroy(ctx);
+        }
+    }
+}
+
+#[cfg(all(test, feature = "wasm_test"))]
+mod tests {
+    extern crate self as yew;
+
+    use super::*;
+    use crate::html::{NodeRef, Scope};
+    use crate::utils::document;
+    use crate::{html, Properties};
+    use std::rc::Rc;
+    use wasm_bindgen::JsValue;
+    use wasm_bindgen_futures::JsFuture;
+    use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    #[derive(Clone, PartialEq, Properties)]
+    struct Props {
+        value: u32,
+    }
+
+    struct Doubled {
+        value: u32,
+        messages: u32,
+    }
+
+    impl AsyncComponent for Doubled {
+        type Message = ();
+        type Properties = Props;
+        type Data = u32;
+
+        fn prepare(props: &Props) -> Pin<Box<dyn Future<Output = u32>>> {
+            let value = props.value;
+            Box::pin(async move { value * 2 })
+        }
+
+        fn create(_ctx: &Context<Async<Self>>, value: u32) -> Self {
+            Self { value, messages: 0 }
+        }
+
+        fn update(&mut self, _ctx: &Context<Async<Self>>, _msg: ()) -> bool {
+            self.messages += 1;
+            true
+        }
+
+        fn view(&self, _ctx: &Context<Async<Self>>) -> Html {
+            html! { <p>{ self.value }{ "/" }{ self.messages }</p> }
+        }
+
+        fn fallback(_ctx: &Context<Async<Self>>) -> Html {
+            html! { <p>{ "loading" }</p> }
+        }
+    }
+
+    /// Lets the spawned futures run
+    async fn tick() {
+        JsFuture::from(js_sys::Promise::resolve(&JsValue::NULL))
+            .await
+            .unwrap();
+    }
+
+    #[test]
+    async fn renders_fallback_until_ready() {
+        let scope = Scope::<Async<Doubled>>::new(None);
+        let el = document().create_element("div").unwrap();
+        let props = Rc::new(Props { value: 21 });
+        scope.mount_in_place(el.clone(), NodeRef::default(), NodeRef::default(), props);
+
+        assert_eq!(el.text_content().as_deref(), Some("loading"));
+        scope.send_message(());
+
+        tick().await;
+        assert_eq!(el.text_content().as_deref(), Some("42/1"));
+        assert_eq!(scope.get_component().unwrap().get().unwrap().value, 42);
+    }
+}
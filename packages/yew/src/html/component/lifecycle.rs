@@ -1,6 +1,6 @@
 //! Component lifecycle module
 
-use super::{Component, Scope};
+use super::{AnyScope, Component, Scope};
 use crate::scheduler::{self, Runnable, Shared};
 use crate::virtual_dom::{VDiff, VNode};
 use crate::{Context, NodeRef};
@@ -13,6 +13,8 @@ pub(crate) struct ComponentState<COMP: Component> {
 
     context: Context<COMP>,
     parent: Element,
+    /// Links to the next sibling passed by the parent. It stays the same object across updates,
+    /// so nodes positioned by it, like those of components rendering nothing, follow the parent.
     next_sibling: NodeRef,
     node_ref: NodeRef,
     has_rendered: bool,
@@ -32,12 +34,14 @@ impl<COMP: Component> ComponentState<COMP> {
         let context = Context { scope, props };
 
         let component = Box::new(COMP::create(&context));
+        let own_next_sibling = NodeRef::default();
+        own_next_sibling.link(next_sibling);
         Self {
             component,
             root_node,
             context,
             parent,
-            next_sibling,
+            next_sibling: own_next_sibling,
             node_ref,
             has_rendered: false,
             pending_root: None,
@@ -45,6 +49,13 @@ impl<COMP: Component> ComponentState<COMP> {
         }
     }
 
+    /// Moves the rendered nodes to `next_parent`, which the component renders into from then on
+    pub(crate) fn shift(&mut self, next_parent: Element, next_sibling: NodeRef) {
+        self.root_node.shift(&next_parent, next_sibling.clone());
+        self.parent = next_parent;
+        self.next_sibling.link(next_sibling);
+    }
+
     fn drain_pending_updates(&mut self, state: &Shared<Option<ComponentState<COMP>>>) {
         if !self.pending_updates.is_empty() {
             scheduler::push_component_updates(self.pending_updates.drain(..).map(|update| {
@@ -96,6 +107,8 @@ pub(crate) enum UpdateEvent<COMP: Component> {
     MessageBatch(Vec<COMP::Message>),
     /// Wraps properties, node ref, and next sibling for a component.
     Properties(Rc<COMP::Properties>, NodeRef, NodeRef),
+    /// Runs a function on the component, which returns whether to re-render, like `update`.
+    Run(Box<dyn FnOnce(&mut COMP, &Context<COMP>) -> bool>),
 }
 
 pub(crate) struct ComponentRunnable<COMP: Component> {
@@ -140,19 +153,29 @@ impl<COMP: Component> Runnable for ComponentRunnable<COMP> {
                             // When components are updated, a new node ref could have been passed in
                             state.node_ref = node_ref;
                             // When components are updated, their siblings were likely also updated
-                            state.next_sibling = next_sibling;
+                            state.next_sibling.link(next_sibling);
                             // Only trigger changed if props were changed
                             if state.context.props != props {
                                 state.context.props = Rc::clone(&props);
-                                state.component.changed(&state.context)
+                                let should_render = state.component.changed(&state.context);
+                                if !should_render {
+                                    state.component.props_updated(&state.context);
+                                }
+                                should_render
                             } else {
                                 false
                             }
                         }
+                        UpdateEvent::Run(f) => f(&mut state.component, &state.context),
                     };
 
                     if should_render {
-                        state.pending_root = Some(state.component.view(&state.context));
+                        state.context.scope.rotate_keyed_callbacks();
+                        let mut root = state.component.view(&state.context);
+                        if let Some(style_scope) = COMP::style_scope() {
+                            root.apply_style_scope(&style_scope);
+                        }
+                        state.pending_root = Some(root);
                         state.context.scope.process(ComponentLifecycleEvent::Render);
                     };
                 }
@@ -180,10 +203,16 @@ impl<COMP: Component> Runnable for ComponentRunnable<COMP> {
                     state.component.rendered(&state.context, first_render);
                     state.has_rendered = true;
                     state.drain_pending_updates(&self.state);
+                    if first_render {
+                        state.context.scope.flush_after_first_render();
+                    }
                 }
             }
             ComponentLifecycleEvent::Destroy => {
                 if let Some(mut state) = current_state.take() {
+                    state.context.scope.abort_futures();
+                    state.context.scope.clear_keyed_callbacks();
+                    AnyScope::from(state.context.scope.clone()).unregister_child();
                     state.component.destroy(&state.context);
                     state.root_node.detach(&state.parent);
                     state.node_ref.set(None);
@@ -245,9 +274,8 @@ mod tests {
     #[derive(Clone, Properties, Default, PartialEq)]
     struct Props {
         lifecycle: Rc<RefCell<Vec<String>>>,
-        #[allow(dead_code)]
-        #[cfg(feature = "wasm_test")]
         create_message: Option<bool>,
+        after_first_render_message: Option<bool>,
         update_message: RefCell<Option<bool>>,
         view_message: RefCell<Option<bool>>,
         rendered_message: RefCell<Option<bool>>,
@@ -263,7 +291,9 @@ mod tests {
 
         fn create(ctx: &Context<Self>) -> Self {
             ctx.props().lifecycle.borrow_mut().push("create".into());
-            #[cfg(feature = "wasm_test")]
+            if let Some(msg) = ctx.props().after_first_render_message {
+                ctx.link().send_after_first_render(msg);
+            }
             if let Some(msg) = ctx.props().create_message {
                 ctx.link().send_message(msg);
             }
@@ -299,6 +329,10 @@ mod tests {
             false
         }
 
+        fn props_updated(&mut self, _ctx: &Context<Self>) {
+            self.lifecycle.borrow_mut().push("props updated".into());
+        }
+
         fn view(&self, ctx: &Context<Self>) -> Html {
             if let Some(msg) = ctx.props().view_message.borrow_mut().take() {
                 ctx.link().send_message(msg);
@@ -346,7 +380,6 @@ mod tests {
         test_lifecycle(
             Props {
                 lifecycle: lifecycle.clone(),
-                #[cfg(feature = "wasm_test")]
                 create_message: Some(false),
                 ..Props::default()
             },
@@ -426,7 +459,6 @@ mod tests {
         test_lifecycle(
             Props {
                 lifecycle,
-                #[cfg(feature = "wasm_test")]
                 create_message: Some(true),
                 update_message: RefCell::new(Some(true)),
                 ..Props::default()
@@ -445,4 +477,68 @@ mod tests {
             ],
         );
     }
+
+    #[test]
+    fn messages_before_first_render() {
+        let lifecycle: Rc<RefCell<Vec<String>>> = Rc::default();
+
+        // Sent after the messages sent with `send_message`, even though it was queued first
+        test_lifecycle(
+            Props {
+                lifecycle: lifecycle.clone(),
+                after_first_render_message: Some(true),
+                create_message: Some(false),
+                ..Props::default()
+            },
+            &[
+                "create".to_string(),
+                "view".to_string(),
+                "child rendered".to_string(),
+                "rendered(true)".to_string(),
+                "update(false)".to_string(),
+                "update(true)".to_string(),
+                "view".to_string(),
+                "rendered(false)".to_string(),
+            ],
+        );
+
+        // Sent right away once the component has rendered
+        let scope = Scope::<Comp>::new(None);
+        let el = crate::utils::document().create_element("div").unwrap();
+        let props = Props {
+            lifecycle: lifecycle.clone(),
+            ..Props::default()
+        };
+        scope.mount_in_place(el, NodeRef::default(), NodeRef::default(), Rc::new(props));
+        lifecycle.borrow_mut().clear();
+        scope.send_after_first_render(false);
+        assert_eq!(&lifecycle.borrow()[..], &["update(false)".to_string()]);
+    }
+
+    #[test]
+    fn props_updated_without_render() {
+        let lifecycle: Rc<RefCell<Vec<String>>> = Rc::default();
+        let scope = Scope::<Comp>::new(None);
+        let el = crate::utils::document().create_element("div").unwrap();
+        let props = || Props {
+            lifecycle: lifecycle.clone(),
+            ..Props::default()
+        };
+        scope.mount_in_place(el, NodeRef::default(), NodeRef::default(), Rc::new(props()));
+        lifecycle.borrow_mut().clear();
+
+        // Equal properties are not applied
+        scope.reuse(Rc::new(props()), NodeRef::default(), NodeRef::default());
+        assert!(lifecycle.borrow().is_empty());
+
+        let changed = Props {
+            create_message: Some(true),
+            ..props()
+        };
+        scope.reuse(Rc::new(changed), NodeRef::default(), NodeRef::default());
+        assert_eq!(
+            &lifecycle.borrow()[..],
+            &["change".to_string(), "props updated".to_string()]
+        );
+    }
 }
@@ -1,15 +1,24 @@
 //! Components wrapped with context including properties, state, and link
 
+mod async_component;
 mod children;
 mod lifecycle;
+mod optimistic;
 mod properties;
+mod registry;
 mod scope;
 
-use super::Html;
+use super::{Html, StyleScope};
+pub use async_component::{Async, AsyncComponent};
 pub use children::*;
+pub use optimistic::Optimistic;
 pub use properties::*;
+pub(crate) use registry::Registry;
+pub use registry::{Registration, ScopeTag};
 pub(crate) use scope::Scoped;
-pub use scope::{AnyScope, Scope, SendAsMessage};
+pub use scope::{
+    AnyScope, FutureHandle, MessageDispatcher, MockScope, Scope, SendAsMessage, WeakScope,
+};
 use std::rc::Rc;
 
 /// The [`Component`]'s context. This contains component's [`Scope`] and and props and
@@ -73,6 +82,14 @@ pub trait Component: Sized + 'static {
         true
     }
 
+    /// Called when new properties were applied without rendering the component, i.e. when
+    /// [`changed`](Component::changed) returned `false`.
+    ///
+    /// Components wrapping a JS widget forward the new properties to it here, without a render
+    /// and a diff of their view.
+    #[allow(unused_variables)]
+    fn props_updated(&mut self, ctx: &Context<Self>) {}
+
     /// Components define their visual layout using a JSX-style syntax through the use of the
     /// `html!` procedural macro. The full guide to using the macro can be found in [Yew's
     /// documentation](https://yew.rs/concepts/html).
@@ -83,6 +100,18 @@ pub trait Component: Sized + 'static {
     #[allow(unused_variables)]
     fn rendered(&mut self, ctx: &Context<Self>, first_render: bool) {}
 
+    /// Returns the style scope of the component.
+    ///
+    /// When a scope is returned, the classes of every element rendered by [`Component::view`] are
+    /// prefixed with it, see [`StyleScope::prefix`]. Elements rendered by child components are
+    /// left untouched. Note that `children` passed in by a parent are rendered by this component
+    /// and are therefore prefixed with its scope.
+    ///
+    /// Use [`StyleScope::of`] for a scope that is unique to the component type.
+    fn style_scope() -> Option<StyleScope> {
+        None
+    }
+
     /// Called right before a Component is unmounted.
     #[allow(unused_variables)]
     fn destroy(&mut self, ctx: &Context<Self>) {}
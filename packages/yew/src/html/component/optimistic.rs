@@ -0,0 +1,203 @@
+//! Optimistic updates for mutations that are confirmed asynchronously
+
+use super::{Component, Scope};
+use crate::callback::Callback;
+use std::future::Future;
+use wasm_bindgen_futures::spawn_local;
+
+/// A change that is shown before the mutation it stands for is confirmed.
+///
+/// [`run`](Self::run) sends the `apply` message right away and then awaits the mutation, e.g. a
+/// request to a server. If the mutation fails, the `rollback` message is sent to undo the change
+/// and the error is passed to the callback set with [`onerror`](Self::onerror). If it succeeds,
+/// the message built with [`onsuccess`](Self::onsuccess) is sent, if any.
+///
+/// # Example
+/// ```rust
+/// # use yew::prelude::*;
+/// use yew::html::Optimistic;
+///
+/// # async fn save_like(id: u32) -> Result<(), String> { Ok(()) }
+/// enum Msg {
+///     Like,
+///     AddLike,
+///     RemoveLike,
+///     Failed(String),
+/// }
+///
+/// # struct Post { id: u32, likes: u32, error: Option<String> }
+/// # impl Component for Post {
+/// #     type Message = Msg;
+/// #     type Properties = ();
+/// #     fn create(_ctx: &Context<Self>) -> Self { unimplemented!() }
+/// #     fn view(&self, _ctx: &Context<Self>) -> Html { unimplemented!() }
+/// fn update(&mut self, ctx: &Context<Self>, msg: Msg) -> bool {
+///     match msg {
+///         Msg::Like => {
+///             Optimistic::new(Msg::AddLike, Msg::RemoveLike)
+///                 .onerror(ctx.link().callback(Msg::Failed))
+///                 .run(ctx.link(), save_like(self.id));
+///             false
+///         }
+///         Msg::AddLike => {
+///             self.likes += 1;
+///             true
+///         }
+///         Msg::RemoveLike => {
+///             self.likes -= 1;
+///             true
+///         }
+///         Msg::Failed(err) => {
+///             self.error = Some(err);
+///             true
+///         }
+///     }
+/// }
+/// # }
+/// ```
+pub struct Optimistic<COMP: Component, T, E> {
+    apply: COMP::Message,
+    rollback: COMP::Message,
+    onsuccess: Option<Box<dyn FnOnce(T) -> COMP::Message>>,
+    onerror: Option<Callback<E>>,
+}
+
+impl<COMP: Component, T: 'static, E: 'static> Optimistic<COMP, T, E> {
+    /// Creates an update that sends `apply` right away and `rollback` if the mutation fails
+    pub fn new(apply: impl Into<COMP::Message>, rollback: impl Into<COMP::Message>) -> Self {
+        Self {
+            apply: apply.into(),
+            rollback: rollback.into(),
+            onsuccess: None,
+            onerror: None,
+        }
+    }
+
+    /// Sets the message sent with the result of a successful mutation, e.g. to replace a
+    /// temporary id with the one assigned by a server
+    pub fn onsuccess<M: Into<COMP::Message>>(mut self, f: impl FnOnce(T) -> M + 'static) -> Self {
+        self.onsuccess = Some(Box::new(move |value| f(value).into()));
+        self
+    }
+
+    /// Sets the callback receiving the error of a failed mutation. It is called after the
+    /// `rollback` message was sent.
+    pub fn onerror(mut self, callback: Callback<E>) -> Self {
+        self.onerror = Some(callback);
+        self
+    }
+
+    /// Sends the `apply` message and runs `mutation`
+    pub fn run<F>(self, scope: &Scope<COMP>, mutation: F)
+    where
+        F: Future<Output = Result<T, E>> + 'static,
+    {
+        let Self {
+            apply,
+            rollback,
+            onsuccess,
+            onerror,
+        } = self;
+
+        scope.send_message(apply);
+        let scope = scope.clone();
+        spawn_local(async move {
+            match mutation.await {
+                Ok(value) => {
+                    if let Some(onsuccess) = onsuccess {
+                        scope.send_message(onsuccess(value));
+                    }
+                }
+                Err(err) => {
+                    scope.send_message(rollback);
+                    if let Some(onerror) = onerror {
+                        onerror.emit(err);
+                    }
+                }
+            }
+        });
+    }
+}
+
+impl<COMP: Component, T, E> std::fmt::Debug for Optimistic<COMP, T, E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Optimistic<_>")
+    }
+}
+
+#[cfg(all(test, feature = "wasm_test"))]
+mod tests {
+    extern crate self as yew;
+
+    use super::*;
+    use crate::html::{Context, Html, NodeRef};
+    use crate::utils::document;
+    use crate::{html, Callback};
+    use std::cell::RefCell;
+    use std::rc::Rc;
+    use wasm_bindgen::JsValue;
+    use wasm_bindgen_futures::JsFuture;
+    use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    struct Counter {
+        count: i32,
+    }
+
+    impl Component for Counter {
+        type Message = i32;
+        type Properties = ();
+
+        fn create(_ctx: &Context<Self>) -> Self {
+            Self { count: 0 }
+        }
+
+        fn update(&mut self, _ctx: &Context<Self>, msg: i32) -> bool {
+            self.count += msg;
+            true
+        }
+
+        fn view(&self, _ctx: &Context<Self>) -> Html {
+            html! { { self.count } }
+        }
+    }
+
+    async fn tick() {
+        JsFuture::from(js_sys::Promise::resolve(&JsValue::NULL))
+            .await
+            .unwrap();
+    }
+
+    #[test]
+    async fn rolls_back_failed_mutations() {
+        let scope = Scope::<Counter>::new(None);
+        let el = document().create_element("div").unwrap();
+        scope.mount_in_place(
+            el.clone(),
+            NodeRef::default(),
+            NodeRef::default(),
+            Rc::new(()),
+        );
+
+        let errors = Rc::new(RefCell::new(Vec::new()));
+        let onerror = {
+            let errors = Rc::clone(&errors);
+            Callback::from(move |err: &'static str| errors.borrow_mut().push(err))
+        };
+
+        Optimistic::new(1, -1)
+            .onerror(onerror.clone())
+            .run(&scope, async { Ok::<(), _>(()) });
+        assert_eq!(el.text_content().as_deref(), Some("1"));
+
+        Optimistic::new(1, -1)
+            .onerror(onerror)
+            .run(&scope, async { Err::<(), _>("offline") });
+        assert_eq!(el.text_content().as_deref(), Some("2"));
+
+        tick().await;
+        assert_eq!(el.text_content().as_deref(), Some("1"));
+        assert_eq!(*errors.borrow(), vec!["offline"]);
+    }
+}
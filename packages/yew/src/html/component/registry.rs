@@ -0,0 +1,115 @@
+//! Lookup of components registered under a tag
+
+use super::{AnyScope, Component};
+use crate::app_handle::AppRoot;
+use std::any::TypeId;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt;
+use std::rc::Weak;
+
+/// A tag under which a component of type [`Component`](Self::Component) registers its scope, see
+/// [`Scope::register_as`](super::Scope::register_as).
+///
+/// Tags are usually empty types, so a component can be registered under a tag of its own
+/// without exposing its type to the components looking it up.
+///
+/// # Example
+/// ```rust
+/// # use yew::prelude::*;
+/// use yew::html::{Registration, ScopeTag};
+///
+/// pub enum ToastMsg {
+///     Show(String),
+/// }
+///
+/// pub struct Toasts {
+///     _registration: Registration,
+/// }
+///
+/// /// Tag of the toast container of the app
+/// pub struct ToastsTag;
+///
+/// impl ScopeTag for ToastsTag {
+///     type Component = Toasts;
+/// }
+///
+/// impl Component for Toasts {
+///     type Message = ToastMsg;
+///     type Properties = ();
+///
+///     fn create(ctx: &Context<Self>) -> Self {
+///         Self {
+///             _registration: ctx.link().register_as::<ToastsTag>(),
+///         }
+///     }
+///
+///     fn view(&self, _ctx: &Context<Self>) -> Html {
+///         html! {}
+///     }
+/// }
+///
+/// // In any other component of the app
+/// # fn notify(ctx: &Context<Toasts>) {
+/// ctx.link()
+///     .send_to_registered::<ToastsTag>(ToastMsg::Show("Saved".to_owned()));
+/// # }
+/// ```
+pub trait ScopeTag: 'static {
+    /// Type of the registered component
+    type Component: Component;
+}
+
+/// Scopes registered under tags, shared by the components of an app
+#[derive(Debug, Default)]
+pub(crate) struct Registry {
+    scopes: RefCell<HashMap<TypeId, AnyScope>>,
+}
+
+impl Registry {
+    pub(crate) fn insert(&self, tag: TypeId, scope: AnyScope) {
+        let replaced = self.scopes.borrow_mut().insert(tag, scope);
+        // Dropping a scope may drop a component and its registrations
+        drop(replaced);
+    }
+
+    pub(crate) fn get(&self, tag: TypeId) -> Option<AnyScope> {
+        self.scopes.borrow().get(&tag).cloned()
+    }
+
+    /// Removes the scope registered under `tag`, unless another scope replaced it
+    fn remove(&self, tag: TypeId, scope: &AnyScope) {
+        let removed = {
+            let mut scopes = self.scopes.borrow_mut();
+            if scopes.get(&tag) == Some(scope) {
+                scopes.remove(&tag)
+            } else {
+                None
+            }
+        };
+        drop(removed);
+    }
+}
+
+/// Registration of a scope under a [`ScopeTag`]. The scope is unregistered when it is dropped,
+/// so it is usually kept in the state of the registered component.
+#[must_use = "the scope is unregistered when the registration is dropped"]
+pub struct Registration {
+    pub(crate) root: Weak<AppRoot>,
+    pub(crate) tag: TypeId,
+    pub(crate) scope: AnyScope,
+}
+
+impl Drop for Registration {
+    fn drop(&mut self) {
+        if let Some(root) = self.root.upgrade() {
+            root.registry.remove(self.tag, &self.scope);
+        }
+    }
+}
+
+impl fmt::Debug for Registration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Registration")
+    }
+}
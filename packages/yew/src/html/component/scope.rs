@@ -4,19 +4,28 @@ use super::{
     lifecycle::{
         ComponentLifecycleEvent, ComponentRunnable, ComponentState, CreateEvent, UpdateEvent,
     },
-    Component,
+    Component, Context, Registration, ScopeTag,
 };
+use crate::app_handle::{AppConfig, AppRoot};
 use crate::callback::Callback;
-use crate::context::{ContextHandle, ContextProvider};
+use crate::context::{ContextHandle, ContextProvider, Service, ServiceProvider};
 use crate::html::NodeRef;
 use crate::scheduler::{self, Shared};
-use crate::utils::document;
+use crate::utils::Environment;
+use crate::virtual_dom::mutation::{self, Insertion, Mutation};
 use crate::virtual_dom::{insert_node, VNode};
+use futures::stream::{Stream, StreamExt};
+use gloo::timers::callback::Timeout;
 use std::any::{Any, TypeId};
-use std::cell::{Ref, RefCell};
+use std::cell::{Cell, Ref, RefCell};
+use std::collections::HashMap;
 use std::future::Future;
+use std::hash::Hash;
 use std::ops::Deref;
-use std::rc::Rc;
+use std::pin::Pin;
+use std::rc::{Rc, Weak};
+use std::task::{Poll, Waker};
+use std::time::Duration;
 use std::{fmt, iter};
 use wasm_bindgen_futures::spawn_local;
 use web_sys::{Element, Node};
@@ -26,7 +35,14 @@ use web_sys::{Element, Node};
 pub struct AnyScope {
     type_id: TypeId,
     parent: Option<Rc<AnyScope>>,
+    root: Rc<AppRoot>,
     state: Rc<dyn Any>,
+    // Erased `Rc<ScopeShared<COMP>>`
+    shared: Rc<dyn Any>,
+    // Erased `Scope::snapshot_html` of the linked component type
+    snapshot_html: fn(&AnyScope) -> Option<String>,
+    // Erased access to the child scopes kept in `shared`
+    children: fn(&AnyScope) -> Option<&RefCell<Vec<AnyScope>>>,
 }
 
 impl<COMP: Component> From<Scope<COMP>> for AnyScope {
@@ -34,18 +50,45 @@ impl<COMP: Component> From<Scope<COMP>> for AnyScope {
         AnyScope {
             type_id: TypeId::of::<COMP>(),
             parent: scope.parent,
+            root: scope.root,
             state: scope.state,
+            shared: scope.shared,
+            snapshot_html: snapshot_html::<COMP>,
+            children: children::<COMP>,
         }
     }
 }
 
+/// Scopes are equal if they are linked to the same component
+impl PartialEq for AnyScope {
+    fn eq(&self, other: &Self) -> bool {
+        // Only the data pointers are compared, the vtables of `dyn Any` may differ
+        Rc::as_ptr(&self.state) as *const () == Rc::as_ptr(&other.state) as *const ()
+    }
+}
+
+impl Eq for AnyScope {}
+
+fn snapshot_html<COMP: Component>(scope: &AnyScope) -> Option<String> {
+    scope.clone().downcast::<COMP>().snapshot_html()
+}
+
+fn children<COMP: Component>(scope: &AnyScope) -> Option<&RefCell<Vec<AnyScope>>> {
+    let shared = scope.shared.downcast_ref::<ScopeShared<COMP>>()?;
+    Some(&shared.children)
+}
+
 impl AnyScope {
     #[cfg(test)]
     pub(crate) fn test() -> Self {
         Self {
             type_id: TypeId::of::<()>(),
             parent: None,
+            root: Default::default(),
             state: Rc::new(()),
+            shared: Rc::new(()),
+            snapshot_html: |_| None,
+            children: |_| None,
         }
     }
 
@@ -59,24 +102,130 @@ impl AnyScope {
         &self.type_id
     }
 
-    /// Attempts to downcast into a typed scope
+    /// Returns the settings of the app the component is part of
+    pub(crate) fn root(&self) -> &AppRoot {
+        &self.root
+    }
+
+    /// Returns the settings of the app the component is part of
+    pub fn app_config(&self) -> &AppConfig {
+        &self.root.config
+    }
+
+    /// Returns the window and document the app the component is part of renders into
+    pub fn environment(&self) -> &Environment {
+        &self.root.environment
+    }
+
+    /// Downcasts into a typed scope.
+    ///
+    /// # Panics
+    /// Panics if the linked component is not a `COMP`, see [`try_downcast`](Self::try_downcast).
     pub fn downcast<COMP: Component>(self) -> Scope<COMP> {
-        Scope {
-            parent: self.parent,
-            state: self
-                .state
+        self.try_downcast().expect("unexpected component type")
+    }
+
+    /// Returns the typed scope if the linked component is a `COMP`
+    pub fn try_downcast<COMP: Component>(&self) -> Option<Scope<COMP>> {
+        Some(Scope {
+            parent: self.parent.clone(),
+            root: Rc::clone(&self.root),
+            state: Rc::clone(&self.state)
                 .downcast::<RefCell<Option<ComponentState<COMP>>>>()
-                .expect("unexpected component type"),
+                .ok()?,
+            shared: Rc::clone(&self.shared)
+                .downcast::<ScopeShared<COMP>>()
+                .ok()?,
+        })
+    }
+
+    /// Serializes the rendered subtree of the linked component into an HTML string.
+    ///
+    /// See [`Scope::snapshot_html`] for details.
+    pub fn outer_html(&self) -> Option<String> {
+        (self.snapshot_html)(self)
+    }
+
+    /// Returns the scopes of the mounted child components, in the order they were mounted.
+    ///
+    /// Only the components rendered by the linked component are children, not the ones they
+    /// render in turn, see [`find_child`](Self::find_child) to search those.
+    pub fn iter_children(&self) -> impl Iterator<Item = AnyScope> {
+        let children = (self.children)(self).map(|children| children.borrow().clone());
+        children.unwrap_or_default().into_iter()
+    }
+
+    /// Returns the scope of the first mounted descendant of type `C`, searching depth-first in
+    /// the order the components were mounted, e.g. for tests or focus managers
+    pub fn find_child<C: Component>(&self) -> Option<Scope<C>> {
+        self.iter_children().find_map(|child| {
+            child
+                .try_downcast::<C>()
+                .or_else(|| child.find_child::<C>())
+        })
+    }
+
+    /// Adds the linked component to the children of its parent. Called when it is mounted.
+    fn register_child(&self) {
+        if let Some(children) = self.parent.as_ref().and_then(|p| (p.children)(p)) {
+            children.borrow_mut().push(self.clone());
+        }
+    }
+
+    /// Removes the linked component from the children of its parent. Called when it is
+    /// destroyed, which also releases the parent.
+    pub(crate) fn unregister_child(&self) {
+        if let Some(children) = self.parent.as_ref().and_then(|p| (p.children)(p)) {
+            children.borrow_mut().retain(|child| child != self);
+        }
+    }
+
+    /// Returns the scope of the closest ancestor of type `C`, not counting the linked component
+    /// itself.
+    ///
+    /// Tying a component to the type of its ancestor couples them tightly, so prefer passing
+    /// callbacks down or a [context](Self::context) for reusable components.
+    pub fn find_ancestor<C: Component>(&self) -> Option<Scope<C>> {
+        iter::successors(self.get_parent(), |scope| scope.get_parent())
+            .find_map(AnyScope::try_downcast::<C>)
+    }
+
+    /// Sends `msg` to the closest ancestor of type `C`, see [`find_ancestor`](Self::find_ancestor).
+    /// Returns whether there was such an ancestor.
+    pub fn send_to_ancestor<C: Component>(&self, msg: impl Into<C::Message>) -> bool {
+        match self.find_ancestor::<C>() {
+            Some(scope) => {
+                scope.send_message(msg);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Returns the scope of the component of the app registered under the tag `T`, see
+    /// [`Scope::register_as`]
+    pub fn find_registered<T: ScopeTag>(&self) -> Option<Scope<T::Component>> {
+        self.root.registry.get(TypeId::of::<T>())?.try_downcast()
+    }
+
+    /// Sends `msg` to the component registered under the tag `T`. Returns whether there was
+    /// one.
+    pub fn send_to_registered<T: ScopeTag>(
+        &self,
+        msg: impl Into<<T::Component as Component>::Message>,
+    ) -> bool {
+        match self.find_registered::<T>() {
+            Some(scope) => {
+                scope.send_message(msg);
+                true
+            }
+            None => false,
         }
     }
 
     fn find_parent_scope<C: Component>(&self) -> Option<Scope<C>> {
-        let expected_type_id = TypeId::of::<C>();
         iter::successors(Some(self), |scope| scope.get_parent())
-            .filter(|scope| scope.get_type_id() == &expected_type_id)
-            .cloned()
-            .map(AnyScope::downcast::<C>)
-            .next()
+            .find_map(AnyScope::try_downcast::<C>)
     }
 
     /// Accesses a value provided by a parent `ContextProvider` component of the
@@ -90,12 +239,35 @@ impl AnyScope {
         let component = scope.get_component()?;
         Some(component.subscribe_consumer(callback, scope_clone))
     }
+
+    /// Returns the current value provided by a parent `ContextProvider` component of the same
+    /// type, without subscribing to its changes, e.g. to read a theme once in
+    /// [`Component::create`].
+    pub fn context_value<T: Clone + PartialEq + 'static>(&self) -> Option<T> {
+        let scope = self.find_parent_scope::<ContextProvider<T>>()?;
+        let component = scope.get_component()?;
+        Some(component.current())
+    }
+
+    /// Looks up the service implementing the interface `I` provided by the closest parent
+    /// [`ServiceProvider`].
+    ///
+    /// Unlike [`context`](Self::context), this does not subscribe to changes. Services are
+    /// expected to stay the same for the lifetime of a component.
+    pub fn service<I: ?Sized + 'static>(&self) -> Option<Service<I>> {
+        let scope = self.find_parent_scope::<ServiceProvider<I>>()?;
+        let component = scope.get_component()?;
+        Some(component.current())
+    }
 }
 
 pub(crate) trait Scoped {
     fn to_any(&self) -> AnyScope;
     fn root_vnode(&self) -> Option<Ref<'_, VNode>>;
     fn destroy(&mut self);
+    /// Moves the rendered nodes to `parent` before `next_sibling`, where the component renders
+    /// from then on, without destroying it
+    fn shift_node(&self, parent: Element, next_sibling: NodeRef);
 }
 
 impl<COMP: Component> Scoped for Scope<COMP> {
@@ -118,12 +290,170 @@ impl<COMP: Component> Scoped for Scope<COMP> {
     fn destroy(&mut self) {
         self.process(ComponentLifecycleEvent::Destroy);
     }
+
+    fn shift_node(&self, parent: Element, next_sibling: NodeRef) {
+        if let Some(state) = self.state.borrow_mut().as_mut() {
+            state.shift(parent, next_sibling);
+        }
+    }
+}
+
+/// State of a [`Scope`] that is kept outside of the component state, so it can be accessed while
+/// the component is borrowed, e.g. in [`Component::create`]
+struct ScopeShared<COMP: Component> {
+    /// Set for scopes created by [`MockScope`], which record messages instead of scheduling them
+    mock_messages: Option<RefCell<Vec<COMP::Message>>>,
+    /// Messages to send once the component has rendered for the first time. `None` afterwards.
+    after_first_render: RefCell<Option<Vec<COMP::Message>>>,
+    /// Futures sent to the component, which are aborted when it is destroyed. `None` afterwards.
+    futures: RefCell<Option<Vec<Rc<AbortState>>>>,
+    keyed_callbacks: RefCell<KeyedCallbacks>,
+    /// Scopes of the mounted child components
+    children: RefCell<Vec<AnyScope>>,
+}
+
+impl<COMP: Component> ScopeShared<COMP> {
+    fn new(mock: bool) -> Self {
+        Self {
+            mock_messages: if mock { Some(Default::default()) } else { None },
+            after_first_render: RefCell::new(Some(Vec::new())),
+            futures: RefCell::new(Some(Vec::new())),
+            keyed_callbacks: RefCell::default(),
+            children: RefCell::default(),
+        }
+    }
+}
+
+/// Callbacks created with [`Scope::callback_keyed`]. Maps of each key and input type are stored
+/// under the `TypeId` of `(K, IN)`.
+#[derive(Default)]
+struct KeyedCallbacks {
+    /// Callbacks used since the current view started
+    current: HashMap<TypeId, Box<dyn Any>>,
+    /// Callbacks used during the previous view, dropped when the next one starts
+    previous: HashMap<TypeId, Box<dyn Any>>,
+}
+
+#[derive(Default)]
+struct AbortState {
+    aborted: Cell<bool>,
+    finished: Cell<bool>,
+    /// Wakes the task of the future, so it is dropped as soon as it is aborted
+    waker: RefCell<Option<Waker>>,
+}
+
+/// Aborts a future sent to a component with [`Scope::send_future`] or spawned with
+/// [`Scope::spawn`].
+///
+/// The futures of a component are aborted when it is destroyed. Dropping the handle doesn't
+/// abort the future.
+#[derive(Clone)]
+pub struct FutureHandle {
+    state: Rc<AbortState>,
+}
+
+impl FutureHandle {
+    /// Drops the future without sending its message, e.g. to cancel a request the user no
+    /// longer waits for. Does nothing if the future has finished.
+    pub fn abort(&self) {
+        if self.state.finished.get() || self.state.aborted.replace(true) {
+            return;
+        }
+        let waker = self.state.waker.borrow_mut().take();
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+
+    /// Returns whether the future was aborted before it finished
+    pub fn is_aborted(&self) -> bool {
+        self.state.aborted.get()
+    }
+
+    /// Returns whether the future has finished or was aborted
+    pub fn is_finished(&self) -> bool {
+        self.state.finished.get() || self.state.aborted.get()
+    }
+}
+
+impl fmt::Debug for FutureHandle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FutureHandle")
+            .field("aborted", &self.state.aborted.get())
+            .field("finished", &self.state.finished.get())
+            .finish()
+    }
+}
+
+/// Future resolving to `None` as soon as it is aborted, which drops the wrapped future
+struct Abortable<F> {
+    future: Pin<Box<F>>,
+    state: Rc<AbortState>,
+}
+
+impl<F: Future> Future for Abortable<F> {
+    type Output = Option<F::Output>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut std::task::Context<'_>) -> Poll<Self::Output> {
+        if self.state.aborted.get() {
+            return Poll::Ready(None);
+        }
+        match self.future.as_mut().poll(cx) {
+            Poll::Ready(output) => Poll::Ready(Some(output)),
+            Poll::Pending => {
+                *self.state.waker.borrow_mut() = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+/// Future resolving once `setTimeout` fires. Dropping it clears the timeout.
+struct Delay {
+    ms: u32,
+    timeout: Option<Timeout>,
+    fired: Rc<Cell<bool>>,
+    waker: Rc<RefCell<Option<Waker>>>,
+}
+
+impl Delay {
+    fn new(duration: Duration) -> Self {
+        Self {
+            ms: duration.as_millis().min(u32::MAX as u128) as u32,
+            timeout: None,
+            fired: Rc::default(),
+            waker: Rc::default(),
+        }
+    }
+}
+
+impl Future for Delay {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut std::task::Context<'_>) -> Poll<()> {
+        if self.fired.get() {
+            return Poll::Ready(());
+        }
+        *self.waker.borrow_mut() = Some(cx.waker().clone());
+        if self.timeout.is_none() {
+            let (fired, waker) = (Rc::clone(&self.fired), Rc::clone(&self.waker));
+            self.timeout = Some(Timeout::new(self.ms, move || {
+                fired.set(true);
+                if let Some(waker) = waker.borrow_mut().take() {
+                    waker.wake();
+                }
+            }));
+        }
+        Poll::Pending
+    }
 }
 
 /// A context which allows sending messages to a component.
 pub struct Scope<COMP: Component> {
     parent: Option<Rc<AnyScope>>,
+    root: Rc<AppRoot>,
     state: Shared<Option<ComponentState<COMP>>>,
+    shared: Rc<ScopeShared<COMP>>,
 }
 
 impl<COMP: Component> fmt::Debug for Scope<COMP> {
@@ -136,17 +466,219 @@ impl<COMP: Component> Clone for Scope<COMP> {
     fn clone(&self) -> Self {
         Scope {
             parent: self.parent.clone(),
+            root: self.root.clone(),
+            state: self.state.clone(),
+            shared: self.shared.clone(),
+        }
+    }
+}
+
+/// Scopes are equal if they are linked to the same component
+impl<COMP: Component> PartialEq for Scope<COMP> {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.state, &other.state)
+    }
+}
+
+impl<COMP: Component> Eq for Scope<COMP> {}
+
+/// A non-owning reference to the [`Scope`] of a component, created with [`Scope::downgrade`].
+///
+/// Unlike a scope, it doesn't keep the component alive, so long-lived callbacks like global
+/// listeners hold one to send messages without leaking the component once it is destroyed.
+pub struct WeakScope<COMP: Component> {
+    parent: Option<Weak<AnyScope>>,
+    root: Weak<AppRoot>,
+    state: Weak<RefCell<Option<ComponentState<COMP>>>>,
+    shared: Weak<ScopeShared<COMP>>,
+}
+
+impl<COMP: Component> WeakScope<COMP> {
+    /// Returns the scope, unless the component was destroyed
+    pub fn upgrade(&self) -> Option<Scope<COMP>> {
+        let parent = match &self.parent {
+            Some(parent) => Some(parent.upgrade()?),
+            None => None,
+        };
+        let scope = Scope {
+            parent,
+            root: self.root.upgrade()?,
+            state: self.state.upgrade()?,
+            shared: self.shared.upgrade()?,
+        };
+        if scope.is_destroyed() {
+            None
+        } else {
+            Some(scope)
+        }
+    }
+
+    /// Sends `msg` to the component, unless it was destroyed
+    pub fn send_message<T>(&self, msg: T)
+    where
+        T: Into<COMP::Message>,
+    {
+        if let Some(scope) = self.upgrade() {
+            scope.send_message(msg);
+        }
+    }
+}
+
+impl<COMP: Component> Clone for WeakScope<COMP> {
+    fn clone(&self) -> Self {
+        WeakScope {
+            parent: self.parent.clone(),
+            root: self.root.clone(),
             state: self.state.clone(),
+            shared: self.shared.clone(),
+        }
+    }
+}
+
+impl<COMP: Component> fmt::Debug for WeakScope<COMP> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("WeakScope<_>")
+    }
+}
+
+/// Sends messages of type `M` to a component whose type is erased, see [`Scope::dispatcher`].
+///
+/// Libraries hold a `Rc<dyn MessageDispatcher<M>>` to push messages into whichever component
+/// registered with them.
+pub trait MessageDispatcher<M> {
+    /// Sends `msg` to the component
+    fn dispatch(&self, msg: M);
+
+    /// Sends `messages` to the component as a batch
+    fn dispatch_batch(&self, messages: Vec<M>);
+}
+
+impl<COMP, M> MessageDispatcher<M> for Scope<COMP>
+where
+    COMP: Component,
+    M: Into<COMP::Message>,
+{
+    fn dispatch(&self, msg: M) {
+        self.send_message(msg);
+    }
+
+    fn dispatch_batch(&self, messages: Vec<M>) {
+        self.send_message_batch(messages.into_iter().map(Into::into).collect());
+    }
+}
+
+/// Messages sent once the component was destroyed are dropped
+impl<COMP, M> MessageDispatcher<M> for WeakScope<COMP>
+where
+    COMP: Component,
+    M: Into<COMP::Message>,
+{
+    fn dispatch(&self, msg: M) {
+        self.send_message(msg);
+    }
+
+    fn dispatch_batch(&self, messages: Vec<M>) {
+        if let Some(scope) = self.upgrade() {
+            scope.dispatch_batch(messages);
         }
     }
 }
 
 impl<COMP: Component> Scope<COMP> {
+    /// Returns a non-owning reference to the scope, see [`WeakScope`]
+    pub fn downgrade(&self) -> WeakScope<COMP> {
+        WeakScope {
+            parent: self.parent.as_ref().map(Rc::downgrade),
+            root: Rc::downgrade(&self.root),
+            state: Rc::downgrade(&self.state),
+            shared: Rc::downgrade(&self.shared),
+        }
+    }
+
+    /// Returns a [`MessageDispatcher`] sending messages of type `M` to the component, for code
+    /// which doesn't know its type.
+    ///
+    /// The dispatcher keeps the component alive like the scope, see
+    /// [`weak_dispatcher`](Self::weak_dispatcher) for one which doesn't.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use yew::prelude::*;
+    /// use std::rc::Rc;
+    /// use yew::html::MessageDispatcher;
+    ///
+    /// /// Notifies whichever component registered last
+    /// #[derive(Default)]
+    /// struct Notifier {
+    ///     target: Option<Rc<dyn MessageDispatcher<String>>>,
+    /// }
+    ///
+    /// impl Notifier {
+    ///     fn notify(&self, text: &str) {
+    ///         if let Some(target) = &self.target {
+    ///             target.dispatch(text.to_owned());
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// enum Msg {
+    ///     Notified(String),
+    /// }
+    ///
+    /// impl From<String> for Msg {
+    ///     fn from(text: String) -> Self {
+    ///         Msg::Notified(text)
+    ///     }
+    /// }
+    ///
+    /// # struct Banner;
+    /// # impl Component for Banner {
+    /// #     type Message = Msg;
+    /// #     type Properties = ();
+    /// #     fn create(_ctx: &Context<Self>) -> Self { Banner }
+    /// #     fn view(&self, _ctx: &Context<Self>) -> Html { html! {} }
+    /// # }
+    /// fn register(notifier: &mut Notifier, ctx: &Context<Banner>) {
+    ///     notifier.target = Some(ctx.link().dispatcher());
+    /// }
+    /// ```
+    pub fn dispatcher<M>(&self) -> Rc<dyn MessageDispatcher<M>>
+    where
+        M: Into<COMP::Message> + 'static,
+    {
+        Rc::new(self.clone())
+    }
+
+    /// Returns a [`MessageDispatcher`] like [`dispatcher`](Self::dispatcher), which doesn't keep
+    /// the component alive and drops the messages sent once it is destroyed
+    pub fn weak_dispatcher<M>(&self) -> Rc<dyn MessageDispatcher<M>>
+    where
+        M: Into<COMP::Message> + 'static,
+    {
+        Rc::new(self.downgrade())
+    }
+
+    /// Returns whether the component was destroyed
+    fn is_destroyed(&self) -> bool {
+        // The futures are taken when the component is destroyed
+        self.shared.futures.borrow().is_none()
+    }
+
     /// Returns the parent scope
     pub fn get_parent(&self) -> Option<&AnyScope> {
         self.parent.as_deref()
     }
 
+    /// Returns the settings of the app the component is part of
+    pub fn app_config(&self) -> &AppConfig {
+        &self.root.config
+    }
+
+    /// Returns the window and document the app the component is part of renders into
+    pub fn environment(&self) -> &Environment {
+        &self.root.environment
+    }
+
     /// Returns the linked component if available
     pub fn get_component(&self) -> Option<impl Deref<Target = COMP> + '_> {
         self.state.try_borrow().ok().and_then(|state_ref| {
@@ -157,10 +689,45 @@ impl<COMP: Component> Scope<COMP> {
         })
     }
 
+    /// Serializes the currently rendered subtree of the component into an HTML string.
+    ///
+    /// Child components are resolved to the markup they rendered, so the result can be used to
+    /// implement features like "download as HTML" or print previews without walking the DOM
+    /// manually.
+    ///
+    /// Returns `None` if the component is not mounted or is currently being updated.
+    pub fn snapshot_html(&self) -> Option<String> {
+        let state_ref = self.state.try_borrow().ok()?;
+        let mut html = String::new();
+        state_ref.as_ref()?.root_node.write_html(&mut html);
+        Some(html)
+    }
+
     pub(crate) fn new(parent: Option<AnyScope>) -> Self {
-        let parent = parent.map(Rc::new);
-        let state = Rc::new(RefCell::new(None));
-        Scope { parent, state }
+        Self::with_shared(parent, ScopeShared::new(false))
+    }
+
+    /// Creates the scope of the root component of an app
+    pub(crate) fn new_root(root: AppRoot) -> Self {
+        Scope {
+            parent: None,
+            root: Rc::new(root),
+            state: Rc::new(RefCell::new(None)),
+            shared: Rc::new(ScopeShared::new(false)),
+        }
+    }
+
+    fn with_shared(parent: Option<AnyScope>, shared: ScopeShared<COMP>) -> Self {
+        Scope {
+            // Components share the settings of the app they are part of
+            root: parent
+                .as_ref()
+                .map(|p| Rc::clone(&p.root))
+                .unwrap_or_default(),
+            parent: parent.map(Rc::new),
+            state: Rc::new(RefCell::new(None)),
+            shared: Rc::new(shared),
+        }
     }
 
     /// Mounts a component with `props` to the specified `element` in the DOM.
@@ -172,12 +739,22 @@ impl<COMP: Component> Scope<COMP> {
         props: Rc<COMP::Properties>,
     ) {
         let placeholder = {
-            let placeholder: Node = document().create_text_node("").into();
-            insert_node(&placeholder, &parent, next_sibling.get().as_ref());
+            let placeholder: Node = self.root.environment.document().create_text_node("").into();
+            mutation::record(Mutation::CreateText {
+                node: &placeholder,
+                text: "",
+            });
+            insert_node(
+                &placeholder,
+                &parent,
+                next_sibling.get().as_ref(),
+                Insertion::Created,
+            );
             node_ref.set(Some(placeholder.clone()));
             VNode::VRef(placeholder)
         };
 
+        self.to_any().register_child();
         self.schedule(UpdateEvent::First.into());
         self.process(ComponentLifecycleEvent::Create(CreateEvent {
             parent,
@@ -223,10 +800,18 @@ impl<COMP: Component> Scope<COMP> {
     ///
     /// Please be aware that currently this method synchronously
     /// schedules a call to the [Component](Component) interface.
+    ///
+    /// Messages sent before the component has rendered for the first time, e.g. from
+    /// [`Component::create`], are queued and passed to [`Component::update`] in the order they
+    /// were sent, right after the first call to [`Component::rendered`].
     pub fn send_message<T>(&self, msg: T)
     where
         T: Into<COMP::Message>,
     {
+        if let Some(messages) = &self.shared.mock_messages {
+            messages.borrow_mut().push(msg.into());
+            return;
+        }
         self.process(UpdateEvent::Message(msg.into()).into());
     }
 
@@ -244,10 +829,117 @@ impl<COMP: Component> Scope<COMP> {
         if messages.is_empty() {
             return;
         }
+        if let Some(mock_messages) = &self.shared.mock_messages {
+            mock_messages.borrow_mut().extend(messages);
+            return;
+        }
 
         self.process(UpdateEvent::MessageBatch(messages).into());
     }
 
+    /// Sends a batch of messages like [`send_message_batch`](Self::send_message_batch), which is
+    /// processed `chunk_size` messages at a time. The browser handles input and paints between
+    /// chunks, so huge batches don't block the page. With a
+    /// [`YieldPolicy`](scheduler::YieldPolicy), the chunks run in slices ended by the policy
+    /// instead of yielding after every chunk.
+    ///
+    /// The component renders once, after the last chunk, if any of its updates asked for it.
+    /// Messages sent by other means meanwhile are processed in between chunks. The remaining
+    /// messages are dropped if the component is destroyed before the last chunk.
+    pub fn send_message_batch_chunked(&self, messages: Vec<COMP::Message>, chunk_size: usize) {
+        if messages.is_empty() {
+            return;
+        }
+        if self.shared.mock_messages.is_some() {
+            return self.send_message_batch(messages);
+        }
+
+        let scope = self.clone();
+        let chunk_size = chunk_size.max(1);
+        let should_render = Rc::new(Cell::new(false));
+        self.spawn_abortable(async move {
+            let mut messages = messages.into_iter().peekable();
+            let mut start_time = js_sys::Date::now();
+            loop {
+                let chunk: Vec<_> = messages.by_ref().take(chunk_size).collect();
+                let last = messages.peek().is_none();
+                let should_render = Rc::clone(&should_render);
+                scope.run_with_component(move |component, ctx| {
+                    let updated = chunk
+                        .into_iter()
+                        .fold(false, |acc, msg| component.update(ctx, msg) || acc);
+                    should_render.set(should_render.get() || updated);
+                    last && should_render.get()
+                });
+                if last {
+                    break;
+                }
+                let pending = (messages.len() + chunk_size - 1) / chunk_size;
+                if scheduler::render_yields(start_time, pending).unwrap_or(true) {
+                    // Yields to the browser
+                    Delay::new(Duration::from_millis(0)).await;
+                    start_time = js_sys::Date::now();
+                }
+            }
+        });
+    }
+
+    /// Sends a message to the component once it has rendered for the first time, or right away
+    /// if it already has.
+    ///
+    /// The messages are passed to [`Component::update`] after the first call to
+    /// [`Component::rendered`] and after any messages sent with
+    /// [`send_message`](Self::send_message) before the first render. This makes it explicit that
+    /// work like loading initial data starts only once the component is in the DOM:
+    /// ```rust
+    /// # use yew::prelude::*;
+    /// # struct Feed;
+    /// # enum Msg { Load }
+    /// # impl Component for Feed {
+    /// #     type Message = Msg;
+    /// #     type Properties = ();
+    /// fn create(ctx: &Context<Self>) -> Self {
+    ///     ctx.link().send_after_first_render(Msg::Load);
+    ///     Feed
+    /// }
+    /// #     fn view(&self, _ctx: &Context<Self>) -> Html { html! {} }
+    /// # }
+    /// ```
+    pub fn send_after_first_render<T>(&self, msg: T)
+    where
+        T: Into<COMP::Message>,
+    {
+        let msg = msg.into();
+        let msg = match self.shared.after_first_render.borrow_mut().as_mut() {
+            Some(queue) if self.shared.mock_messages.is_none() => {
+                queue.push(msg);
+                return;
+            }
+            _ => msg,
+        };
+        self.send_message(msg);
+    }
+
+    /// Runs `f` on the component like a message, i.e. after any pending updates and followed by a
+    /// render if `f` returns `true`. Nothing is run for mock scopes.
+    pub(crate) fn run_with_component(
+        &self,
+        f: impl FnOnce(&mut COMP, &Context<COMP>) -> bool + 'static,
+    ) {
+        if self.shared.mock_messages.is_none() {
+            self.process(UpdateEvent::Run(Box::new(f)).into());
+        }
+    }
+
+    /// Sends the messages queued with [`send_after_first_render`](Self::send_after_first_render).
+    /// Called after the first render of the component.
+    pub(crate) fn flush_after_first_render(&self) {
+        let queue = self.shared.after_first_render.borrow_mut().take();
+        if let Some(messages) = queue {
+            self.send_message_batch(messages);
+        }
+    }
+
     /// Creates a `Callback` which will send a message to the linked
     /// component's update method when invoked.
     ///
@@ -267,6 +959,104 @@ impl<COMP: Component> Scope<COMP> {
         closure.into()
     }
 
+    /// Creates a `Callback` like [`callback`](Self::callback), which is equal to the callbacks
+    /// created by this scope with an equal `id`, see [`Callback::with_id`]. Ids of different
+    /// components are never equal.
+    pub fn callback_with_id<ID, F, IN, M>(&self, id: ID, function: F) -> Callback<IN>
+    where
+        ID: PartialEq + 'static,
+        M: Into<COMP::Message>,
+        F: Fn(IN) -> M + 'static,
+    {
+        // The scope is identified by its shared state, which lives as long as the component
+        let scope_id = Rc::as_ptr(&self.shared) as *const () as usize;
+        let scope = self.clone();
+        Callback::with_id((scope_id, id), move |input| {
+            let output = function(input);
+            scope.send_message(output);
+        })
+    }
+
+    /// Creates a `Callback` like [`callback`](Self::callback), which is the same for the same
+    /// `key` across renders, e.g. the `onclick` of every item of a list. Children receiving it keep
+    /// equal properties, so they aren't rendered again.
+    ///
+    /// The callback is created by the first call with `key`, so `function` must only depend on
+    /// `key` and its input. Callbacks not requested during a view are dropped when the next one
+    /// starts.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use yew::prelude::*;
+    /// # #[derive(Clone, PartialEq, Properties)]
+    /// # struct RowProps { name: String, onselect: Callback<MouseEvent> }
+    /// # #[function_component(Row)]
+    /// # fn row(_props: &RowProps) -> Html { html! {} }
+    /// enum Msg {
+    ///     Select(u32),
+    /// }
+    ///
+    /// struct List {
+    ///     items: Vec<(u32, String)>,
+    /// }
+    ///
+    /// impl Component for List {
+    ///     type Message = Msg;
+    ///     type Properties = ();
+    /// #   fn create(_ctx: &Context<Self>) -> Self { Self { items: Vec::new() } }
+    /// #   fn update(&mut self, _ctx: &Context<Self>, _msg: Msg) -> bool { false }
+    ///
+    ///     fn view(&self, ctx: &Context<Self>) -> Html {
+    ///         let rows = self.items.iter().map(|(id, name)| {
+    ///             let id = *id;
+    ///             let onselect = ctx.link().callback_keyed(id, move |_| Msg::Select(id));
+    ///             html! { <Row key={id} name={name.clone()} {onselect} /> }
+    ///         });
+    ///         html! { <ul>{ for rows }</ul> }
+    ///     }
+    /// }
+    /// ```
+    pub fn callback_keyed<K, F, IN, M>(&self, key: K, function: F) -> Callback<IN>
+    where
+        K: Hash + Eq + 'static,
+        IN: 'static,
+        M: Into<COMP::Message>,
+        F: Fn(IN) -> M + 'static,
+    {
+        type Map<K, IN> = HashMap<K, Callback<IN>>;
+
+        let mut cache = self.shared.keyed_callbacks.borrow_mut();
+        let cache = &mut *cache;
+        let type_id = TypeId::of::<(K, IN)>();
+        let previous = cache
+            .previous
+            .get_mut(&type_id)
+            .and_then(|map| map.downcast_mut::<Map<K, IN>>())
+            .and_then(|map| map.remove(&key));
+        let current = cache
+            .current
+            .entry(type_id)
+            .or_insert_with(|| Box::new(Map::<K, IN>::new()))
+            .downcast_mut::<Map<K, IN>>()
+            .expect("keyed callbacks stored under another type");
+        current
+            .entry(key)
+            .or_insert_with(|| previous.unwrap_or_else(|| self.callback(function)))
+            .clone()
+    }
+
+    /// Drops the keyed callbacks which were not requested during the previous view. Called
+    /// before every view.
+    pub(crate) fn rotate_keyed_callbacks(&self) {
+        let mut cache = self.shared.keyed_callbacks.borrow_mut();
+        cache.previous = std::mem::take(&mut cache.current);
+    }
+
+    /// Drops the keyed callbacks, which hold the scope, when the component is destroyed
+    pub(crate) fn clear_keyed_callbacks(&self) {
+        *self.shared.keyed_callbacks.borrow_mut() = KeyedCallbacks::default();
+    }
+
     /// Creates a `Callback` from an `FnOnce` which will send a message
     /// to the linked component's update method when invoked.
     ///
@@ -391,9 +1181,12 @@ impl<COMP: Component> Scope<COMP> {
     /// This method processes a Future that returns a message and sends it back to the component's
     /// loop.
     ///
+    /// The future is dropped when the component is destroyed, or earlier with the returned
+    /// [`FutureHandle`].
+    ///
     /// # Panics
     /// If the future panics, then the promise will not resolve, and will leak.
-    pub fn send_future<F, M>(&self, future: F)
+    pub fn send_future<F, M>(&self, future: F) -> FutureHandle
     where
         M: Into<COMP::Message>,
         F: Future<Output = M> + 'static,
@@ -403,13 +1196,14 @@ impl<COMP: Component> Scope<COMP> {
             let message: COMP::Message = future.await.into();
             link.send_message(message);
         };
-        spawn_local(js_future);
+        self.spawn_abortable(js_future)
     }
 
-    /// Registers a Future that resolves to multiple messages.
+    /// Registers a Future that resolves to multiple messages, see
+    /// [`send_future`](Self::send_future).
     /// # Panics
     /// If the future panics, then the promise will not resolve, and will leak.
-    pub fn send_future_batch<F>(&self, future: F)
+    pub fn send_future_batch<F>(&self, future: F) -> FutureHandle
     where
         F: Future<Output = Vec<COMP::Message>> + 'static,
     {
@@ -418,7 +1212,238 @@ impl<COMP: Component> Scope<COMP> {
             let messages: Vec<COMP::Message> = future.await;
             link.send_message_batch(messages);
         };
-        spawn_local(js_future);
+        self.spawn_abortable(js_future)
+    }
+
+    /// Sends `msg` to the component once `delay` has passed, unless the component is destroyed
+    /// before. The returned [`FutureHandle`] cancels it earlier.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use yew::prelude::*;
+    /// use std::time::Duration;
+    ///
+    /// enum Msg {
+    ///     Show(String),
+    ///     Hide,
+    /// }
+    ///
+    /// struct Toast {
+    ///     text: Option<String>,
+    /// }
+    ///
+    /// impl Component for Toast {
+    ///     type Message = Msg;
+    ///     type Properties = ();
+    /// #   fn create(_ctx: &Context<Self>) -> Self { Self { text: None } }
+    /// #   fn view(&self, _ctx: &Context<Self>) -> Html { html! {} }
+    ///
+    ///     fn update(&mut self, ctx: &Context<Self>, msg: Msg) -> bool {
+    ///         match msg {
+    ///             Msg::Show(text) => {
+    ///                 self.text = Some(text);
+    ///                 ctx.link().send_message_after(Msg::Hide, Duration::from_secs(3));
+    ///             }
+    ///             Msg::Hide => self.text = None,
+    ///         }
+    ///         true
+    ///     }
+    /// }
+    /// ```
+    pub fn send_message_after<T>(&self, msg: T, delay: Duration) -> FutureHandle
+    where
+        T: Into<COMP::Message>,
+    {
+        self.send_future(async move {
+            Delay::new(delay).await;
+            msg
+        })
+    }
+
+    /// Sends `messages` to the component as a batch once `delay` has passed, see
+    /// [`send_message_after`](Self::send_message_after).
+    pub fn send_message_batch_after(
+        &self,
+        messages: Vec<COMP::Message>,
+        delay: Duration,
+    ) -> FutureHandle {
+        self.send_future_batch(async move {
+            Delay::new(delay).await;
+            messages
+        })
+    }
+
+    /// Creates a `Callback` like [`callback`](Self::callback), which sends the message of its
+    /// last call once it hasn't been called for `delay`, e.g. to search once the user stops
+    /// typing.
+    ///
+    /// The pending call is kept by the callback, so create it once, e.g. in
+    /// [`Component::create`], instead of in every view.
+    pub fn callback_debounced<F, IN, M>(&self, delay: Duration, function: F) -> Callback<IN>
+    where
+        M: Into<COMP::Message>,
+        F: Fn(IN) -> M + 'static,
+    {
+        let scope = self.clone();
+        let pending = RefCell::new(None::<FutureHandle>);
+        let closure = move |input| {
+            let message = function(input);
+            if let Some(handle) = pending.borrow_mut().take() {
+                handle.abort();
+            }
+            *pending.borrow_mut() = Some(scope.send_message_after(message, delay));
+        };
+        closure.into()
+    }
+
+    /// Creates a `Callback` like [`callback`](Self::callback), which sends a message at most
+    /// once per `delay`, e.g. for `scroll` events. The first call sends its message right away;
+    /// the message of the last call made during the following `delay` is sent once it has
+    /// passed.
+    ///
+    /// Like [`callback_debounced`](Self::callback_debounced), create it once instead of in every
+    /// view.
+    pub fn callback_throttled<F, IN, M>(&self, delay: Duration, function: F) -> Callback<IN>
+    where
+        M: Into<COMP::Message>,
+        F: Fn(IN) -> M + 'static,
+    {
+        struct Throttle<MSG> {
+            window: Option<FutureHandle>,
+            pending: Option<MSG>,
+        }
+
+        let scope = self.clone();
+        let throttle = Rc::new(RefCell::new(Throttle {
+            window: None,
+            pending: None,
+        }));
+        let closure = move |input| {
+            let message: COMP::Message = function(input).into();
+            {
+                let mut state = throttle.borrow_mut();
+                if state
+                    .window
+                    .as_ref()
+                    .map_or(false, |window| !window.is_finished())
+                {
+                    state.pending = Some(message);
+                    return;
+                }
+            }
+            let (link, state) = (scope.clone(), Rc::clone(&throttle));
+            let window = scope.spawn_abortable(async move {
+                loop {
+                    Delay::new(delay).await;
+                    let pending = state.borrow_mut().pending.take();
+                    match pending {
+                        Some(message) => link.send_message(message),
+                        None => break,
+                    }
+                }
+            });
+            throttle.borrow_mut().window = Some(window);
+            // The state isn't borrowed while the message is handled, which may call the callback
+            scope.send_message(message);
+        };
+        closure.into()
+    }
+
+    /// Sends every item of `stream` to the component as a message, until the stream ends or the
+    /// component is destroyed, e.g. the messages of a WebSocket. The returned [`FutureHandle`]
+    /// stops forwarding earlier.
+    pub fn send_stream<S, M>(&self, stream: S) -> FutureHandle
+    where
+        M: Into<COMP::Message>,
+        S: Stream<Item = M> + 'static,
+    {
+        let link = self.clone();
+        let mut stream = Box::pin(stream);
+        self.spawn_abortable(async move {
+            while let Some(message) = stream.next().await {
+                link.send_message(message);
+            }
+        })
+    }
+
+    /// Runs `future` until it completes or the component is destroyed, whichever comes first,
+    /// e.g. to keep a connection open while the component is shown. The returned
+    /// [`FutureHandle`] aborts it earlier.
+    ///
+    /// Futures spawned once the component is destroyed, e.g. in [`Component::destroy`], are
+    /// aborted right away.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use yew::prelude::*;
+    /// # async fn sleep(_ms: u32) {}
+    /// struct Clock {
+    ///     ticks: u32,
+    /// }
+    ///
+    /// impl Component for Clock {
+    ///     type Message = ();
+    ///     type Properties = ();
+    ///
+    ///     fn create(ctx: &Context<Self>) -> Self {
+    ///         let link = ctx.link().clone();
+    ///         // Stops ticking when the clock is removed, without any flag to check
+    ///         ctx.link().spawn(async move {
+    ///             loop {
+    ///                 sleep(1000).await;
+    ///                 link.send_message(());
+    ///             }
+    ///         });
+    ///         Self { ticks: 0 }
+    ///     }
+    ///
+    ///     fn update(&mut self, _ctx: &Context<Self>, _msg: ()) -> bool {
+    ///         self.ticks += 1;
+    ///         true
+    ///     }
+    ///
+    ///     fn view(&self, _ctx: &Context<Self>) -> Html {
+    ///         html! { <p>{ self.ticks }</p> }
+    ///     }
+    /// }
+    /// ```
+    pub fn spawn<F>(&self, future: F) -> FutureHandle
+    where
+        F: Future<Output = ()> + 'static,
+    {
+        self.spawn_abortable(future)
+    }
+
+    fn spawn_abortable(&self, future: impl Future<Output = ()> + 'static) -> FutureHandle {
+        let state = Rc::new(AbortState::default());
+        match &mut *self.shared.futures.borrow_mut() {
+            Some(futures) => {
+                futures.retain(|state| !state.finished.get() && !state.aborted.get());
+                futures.push(Rc::clone(&state));
+            }
+            None => {
+                state.aborted.set(true);
+                return FutureHandle { state };
+            }
+        }
+        let abortable = Abortable {
+            future: Box::pin(future),
+            state: Rc::clone(&state),
+        };
+        let finished = Rc::clone(&state);
+        spawn_local(async move {
+            abortable.await;
+            finished.finished.set(true);
+        });
+        FutureHandle { state }
+    }
+
+    /// Aborts the futures of the component, which is being destroyed
+    pub(crate) fn abort_futures(&self) {
+        let futures = self.shared.futures.borrow_mut().take();
+        for state in futures.into_iter().flatten() {
+            FutureHandle { state }.abort();
+        }
     }
 
     /// Accesses a value provided by a parent `ContextProvider` component of the
@@ -429,6 +1454,201 @@ impl<COMP: Component> Scope<COMP> {
     ) -> Option<(T, ContextHandle<T>)> {
         self.to_any().context(callback)
     }
+
+    /// Returns the current value provided by a parent `ContextProvider` component of the same
+    /// type, without subscribing to its changes. See [`AnyScope::context_value`].
+    pub fn context_value<T: Clone + PartialEq + 'static>(&self) -> Option<T> {
+        self.to_any().context_value()
+    }
+
+    /// Looks up the service implementing the interface `I` provided by the closest parent
+    /// [`ServiceProvider`]. See [`AnyScope::service`].
+    pub fn service<I: ?Sized + 'static>(&self) -> Option<Service<I>> {
+        self.to_any().service()
+    }
+
+    /// Returns the scopes of the mounted child components. See [`AnyScope::iter_children`].
+    pub fn iter_children(&self) -> impl Iterator<Item = AnyScope> {
+        self.to_any().iter_children()
+    }
+
+    /// Returns the scope of the first mounted descendant of type `C`. See
+    /// [`AnyScope::find_child`].
+    pub fn find_child<C: Component>(&self) -> Option<Scope<C>> {
+        self.to_any().find_child()
+    }
+
+    /// Returns the scope of the closest ancestor of type `C`. See [`AnyScope::find_ancestor`].
+    pub fn find_ancestor<C: Component>(&self) -> Option<Scope<C>> {
+        self.to_any().find_ancestor()
+    }
+
+    /// Sends `msg` to the closest ancestor of type `C`. Returns whether there was such an
+    /// ancestor.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use yew::prelude::*;
+    /// # struct Editor;
+    /// # impl Component for Editor {
+    /// #     type Message = EditorMsg;
+    /// #     type Properties = ();
+    /// #     fn create(_ctx: &Context<Self>) -> Self { Self }
+    /// #     fn view(&self, _ctx: &Context<Self>) -> Html { html! {} }
+    /// # }
+    /// enum EditorMsg {
+    ///     Save,
+    /// }
+    ///
+    /// struct SaveButton;
+    ///
+    /// impl Component for SaveButton {
+    ///     type Message = ();
+    ///     type Properties = ();
+    ///
+    ///     fn create(_ctx: &Context<Self>) -> Self {
+    ///         Self
+    ///     }
+    ///
+    ///     fn update(&mut self, ctx: &Context<Self>, _msg: ()) -> bool {
+    ///         ctx.link().send_to_ancestor::<Editor>(EditorMsg::Save);
+    ///         false
+    ///     }
+    ///
+    ///     fn view(&self, ctx: &Context<Self>) -> Html {
+    ///         html! { <button onclick={ctx.link().callback(|_| ())}>{ "Save" }</button> }
+    ///     }
+    /// }
+    /// ```
+    pub fn send_to_ancestor<C: Component>(&self, msg: impl Into<C::Message>) -> bool {
+        self.to_any().send_to_ancestor::<C>(msg)
+    }
+
+    /// Registers the component under the tag `T` until the returned [`Registration`] is dropped,
+    /// so any component of the app can look it up with [`find_registered`](Self::find_registered)
+    /// instead of getting callbacks passed down through every level.
+    ///
+    /// There is one component per tag and app. A later registration replaces an earlier one.
+    pub fn register_as<T: ScopeTag<Component = COMP>>(&self) -> Registration {
+        let tag = TypeId::of::<T>();
+        let scope = self.to_any();
+        self.root.registry.insert(tag, scope.clone());
+        Registration {
+            root: Rc::downgrade(&self.root),
+            tag,
+            scope,
+        }
+    }
+
+    /// Returns the scope of the component registered under the tag `T`. See
+    /// [`AnyScope::find_registered`].
+    pub fn find_registered<T: ScopeTag>(&self) -> Option<Scope<T::Component>> {
+        self.to_any().find_registered::<T>()
+    }
+
+    /// Sends `msg` to the component registered under the tag `T`. Returns whether there was
+    /// one.
+    pub fn send_to_registered<T: ScopeTag>(
+        &self,
+        msg: impl Into<<T::Component as Component>::Message>,
+    ) -> bool {
+        self.to_any().send_to_registered::<T>(msg)
+    }
+}
+
+/// A [`Scope`] for unit testing logic that sends messages to a component.
+///
+/// It dereferences to a `Scope<COMP>` that is not linked to a component: messages sent through
+/// it, its callbacks and its futures are recorded instead of being scheduled, so they can be
+/// checked with [`take_messages`](Self::take_messages). As there is no component,
+/// [`Scope::get_component`] returns `None`.
+///
+/// # Example
+/// ```rust
+/// # use yew::prelude::*;
+/// use yew::html::{MockScope, Scope};
+///
+/// # struct Counter;
+/// #[derive(Debug, PartialEq)]
+/// enum Msg {
+///     Add(i32),
+/// }
+/// # impl Component for Counter {
+/// #     type Message = Msg;
+/// #     type Properties = ();
+/// #     fn create(_ctx: &Context<Self>) -> Self { Counter }
+/// #     fn view(&self, _ctx: &Context<Self>) -> Html { html! {} }
+/// # }
+///
+/// fn on_key(link: &Scope<Counter>, key: &str) {
+///     match key {
+///         "+" => link.send_message(Msg::Add(1)),
+///         "-" => link.send_message(Msg::Add(-1)),
+///         _ => (),
+///     }
+/// }
+///
+/// let scope = MockScope::<Counter>::new();
+/// on_key(&scope, "+");
+/// on_key(&scope, "x");
+/// scope.callback(|_: ()| Msg::Add(5)).emit(());
+/// assert_eq!(scope.take_messages(), vec![Msg::Add(1), Msg::Add(5)]);
+/// ```
+pub struct MockScope<COMP: Component> {
+    scope: Scope<COMP>,
+}
+
+impl<COMP: Component> MockScope<COMP> {
+    /// Creates a mock scope without a parent
+    pub fn new() -> Self {
+        Self::with_parent(None)
+    }
+
+    /// Creates a mock scope whose [`Scope::get_parent`] returns `parent`, e.g. to test lookups of
+    /// contexts
+    pub fn with_parent(parent: Option<AnyScope>) -> Self {
+        Self {
+            scope: Scope::with_shared(parent, ScopeShared::new(true)),
+        }
+    }
+
+    fn messages(&self) -> &RefCell<Vec<COMP::Message>> {
+        self.scope
+            .shared
+            .mock_messages
+            .as_ref()
+            .expect("mock scope without message log")
+    }
+
+    /// Returns the messages sent so far and clears the record
+    pub fn take_messages(&self) -> Vec<COMP::Message> {
+        std::mem::take(&mut *self.messages().borrow_mut())
+    }
+
+    /// Returns the number of messages sent since they were last taken
+    pub fn message_count(&self) -> usize {
+        self.messages().borrow().len()
+    }
+}
+
+impl<COMP: Component> Default for MockScope<COMP> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<COMP: Component> Deref for MockScope<COMP> {
+    type Target = Scope<COMP>;
+
+    fn deref(&self) -> &Scope<COMP> {
+        &self.scope
+    }
+}
+
+impl<COMP: Component> fmt::Debug for MockScope<COMP> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("MockScope<_>")
+    }
 }
 
 /// Defines a message type that can be sent to a component.
@@ -458,3 +1678,419 @@ where
         scope.send_message_batch(self);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    extern crate self as yew;
+
+    use super::*;
+    use crate::utils::document;
+    use crate::{html, Context, Html};
+
+    #[cfg(feature = "wasm_test")]
+    use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+
+    #[cfg(feature = "wasm_test")]
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    struct Child;
+
+    impl Component for Child {
+        type Message = ();
+        type Properties = ();
+
+        fn create(_ctx: &Context<Self>) -> Self {
+            Child
+        }
+
+        fn view(&self, _ctx: &Context<Self>) -> Html {
+            html! { <p class="child">{ "child" }</p> }
+        }
+    }
+
+    struct Comp;
+
+    impl Component for Comp {
+        type Message = ();
+        type Properties = ();
+
+        fn create(_ctx: &Context<Self>) -> Self {
+            Comp
+        }
+
+        fn view(&self, _ctx: &Context<Self>) -> Html {
+            html! {
+                <>
+                    { "1 < 2" }
+                    <Child />
+                </>
+            }
+        }
+    }
+
+    #[test]
+    fn snapshot_html_resolves_components() {
+        let scope = Scope::<Comp>::new(None);
+        let el = document().create_element("div").unwrap();
+        scope.mount_in_place(el, NodeRef::default(), NodeRef::default(), Rc::new(()));
+
+        let expected = r#"1 &lt; 2<p class="child">child</p>"#;
+        assert_eq!(scope.snapshot_html().as_deref(), Some(expected));
+        assert_eq!(scope.to_any().outer_html().as_deref(), Some(expected));
+
+        scope.clone().destroy();
+        assert_eq!(scope.snapshot_html(), None);
+    }
+
+    #[test]
+    fn mounted_children_are_listed() {
+        let scope = Scope::<Comp>::new(None);
+        let el = document().create_element("div").unwrap();
+        scope.mount_in_place(el, NodeRef::default(), NodeRef::default(), Rc::new(()));
+
+        let children: Vec<_> = scope.iter_children().collect();
+        assert_eq!(children.len(), 1);
+        let child = scope.find_child::<Child>().unwrap();
+        assert_eq!(children[0], child.to_any());
+        assert!(child.iter_children().next().is_none());
+        assert!(scope.find_child::<Comp>().is_none());
+
+        child.to_any().unregister_child();
+        assert!(scope.iter_children().next().is_none());
+    }
+
+    #[test]
+    fn try_downcast_and_compare_scopes() {
+        let parent = Scope::<Comp>::new(None);
+        let child = Scope::<Child>::new(Some(parent.to_any()));
+        let any = child.to_any();
+
+        assert!(any.try_downcast::<Comp>().is_none());
+        assert!(any.try_downcast::<Child>().unwrap() == child);
+        assert!(any.get_parent().unwrap().try_downcast::<Comp>().unwrap() == parent);
+        assert_eq!(any, child.clone().to_any());
+        assert_ne!(any, parent.to_any());
+        assert!(Scope::<Child>::new(None) != child);
+    }
+
+    #[test]
+    fn send_to_ancestor_skips_other_types() {
+        let counter = MockScope::<Counter>::new();
+        let comp = Scope::<Comp>::new(Some(counter.to_any()));
+        let child = Scope::<Child>::new(Some(comp.to_any()));
+
+        assert!(child.send_to_ancestor::<Counter>(2));
+        assert_eq!(counter.take_messages(), vec![2]);
+        assert!(child.find_ancestor::<Comp>().unwrap() == comp);
+        // The component itself is not its own ancestor
+        assert!(child.find_ancestor::<Child>().is_none());
+        assert!(!comp.send_to_ancestor::<Comp>(()));
+    }
+
+    struct CounterTag;
+
+    impl ScopeTag for CounterTag {
+        type Component = Counter;
+    }
+
+    #[test]
+    fn registered_scopes_are_found_until_dropped() {
+        let counter = MockScope::<Counter>::new();
+        let comp = Scope::<Comp>::new(Some(counter.to_any()));
+        assert!(!comp.send_to_registered::<CounterTag>(1));
+
+        let registration = counter.register_as::<CounterTag>();
+        assert!(comp.send_to_registered::<CounterTag>(2));
+        assert_eq!(counter.take_messages(), vec![2]);
+        // Other apps have registries of their own
+        assert!(Scope::<Comp>::new(None)
+            .find_registered::<CounterTag>()
+            .is_none());
+
+        drop(registration);
+        assert!(comp.find_registered::<CounterTag>().is_none());
+    }
+
+    struct Counter;
+
+    impl Component for Counter {
+        type Message = i32;
+        type Properties = ();
+
+        fn create(_ctx: &Context<Self>) -> Self {
+            Counter
+        }
+
+        fn view(&self, _ctx: &Context<Self>) -> Html {
+            html! {}
+        }
+    }
+
+    #[cfg(feature = "wasm_test")]
+    #[test]
+    async fn chunked_batches_render_once() {
+        thread_local! {
+            static TALLY_VIEWS: Cell<usize> = Cell::new(0);
+        }
+
+        struct Tally {
+            sum: i32,
+        }
+
+        impl Component for Tally {
+            type Message = i32;
+            type Properties = ();
+
+            fn create(_ctx: &Context<Self>) -> Self {
+                Tally { sum: 0 }
+            }
+
+            fn update(&mut self, _ctx: &Context<Self>, msg: i32) -> bool {
+                self.sum += msg;
+                true
+            }
+
+            fn view(&self, _ctx: &Context<Self>) -> Html {
+                TALLY_VIEWS.with(|views| views.set(views.get() + 1));
+                html! { { self.sum } }
+            }
+        }
+
+        let scope = Scope::<Tally>::new(None);
+        let el = document().create_element("div").unwrap();
+        scope.mount_in_place(
+            el.clone(),
+            NodeRef::default(),
+            NodeRef::default(),
+            Rc::new(()),
+        );
+        assert_eq!(TALLY_VIEWS.with(Cell::get), 1);
+
+        scope.send_message_batch_chunked((1..=10).collect(), 3);
+        assert_eq!(scope.get_component().unwrap().sum, 0);
+        sleep(50).await;
+        assert_eq!(scope.get_component().unwrap().sum, 55);
+        assert_eq!(el.text_content().unwrap(), "55");
+        assert_eq!(TALLY_VIEWS.with(Cell::get), 2);
+    }
+
+    #[test]
+    fn mock_scope_records_messages() {
+        let mock = MockScope::<Counter>::new();
+        mock.send_message(1);
+        mock.callback(|n: i32| n * 2).emit(2);
+        mock.batch_callback(|n: i32| vec![n, n]).emit(3);
+        mock.send_message_batch(Vec::new());
+        assert_eq!(mock.message_count(), 4);
+        assert_eq!(mock.take_messages(), vec![1, 4, 3, 3]);
+        assert!(mock.get_component().is_none());
+
+        // Type-erased copies keep recording
+        mock.to_any().downcast::<Counter>().send_message(5);
+        assert_eq!(mock.take_messages(), vec![5]);
+    }
+
+    #[test]
+    fn weak_scopes_do_not_outlive_the_component() {
+        let mock = MockScope::<Counter>::new();
+        let weak = mock.downgrade();
+        assert!(weak.upgrade().map_or(false, |scope| scope == *mock));
+        weak.send_message(1);
+        assert_eq!(mock.take_messages(), vec![1]);
+
+        mock.abort_futures();
+        assert!(weak.upgrade().is_none());
+        weak.send_message(2);
+        assert!(mock.take_messages().is_empty());
+
+        let mock = MockScope::<Counter>::new();
+        let weak = mock.downgrade();
+        drop(mock);
+        assert!(weak.upgrade().is_none());
+    }
+
+    #[test]
+    fn dispatchers_erase_the_component_type() {
+        let mock = MockScope::<Counter>::new();
+        let dispatchers: Vec<Rc<dyn MessageDispatcher<i8>>> =
+            vec![mock.dispatcher(), mock.weak_dispatcher()];
+        for dispatcher in &dispatchers {
+            dispatcher.dispatch(1);
+            dispatcher.dispatch_batch(vec![2, 3]);
+        }
+        assert_eq!(mock.take_messages(), vec![1, 2, 3, 1, 2, 3]);
+
+        // Weak dispatchers drop the messages of destroyed components
+        mock.abort_futures();
+        dispatchers[1].dispatch(4);
+        assert!(mock.take_messages().is_empty());
+    }
+
+    #[test]
+    fn identified_callbacks_are_equal_within_a_scope() {
+        let mock = MockScope::<Counter>::new();
+        let other = MockScope::<Counter>::new();
+        let a = mock.callback_with_id(1, |n: i32| n);
+        assert_eq!(a, mock.callback_with_id(1, |n: i32| n + 1));
+        assert_ne!(a, mock.callback_with_id(2, |n: i32| n));
+        assert_ne!(a, other.callback_with_id(1, |n: i32| n));
+        a.emit(3);
+        assert_eq!(mock.take_messages(), vec![3]);
+    }
+
+    #[test]
+    fn keyed_callbacks_are_reused_across_views() {
+        let mock = MockScope::<Counter>::new();
+        let first = mock.callback_keyed(1, |n: i32| n);
+        assert_eq!(mock.callback_keyed(1, |n: i32| n), first);
+        assert_ne!(mock.callback_keyed(2, |n: i32| n), first);
+        // Keys of different types are kept apart
+        assert_ne!(mock.callback_keyed(1_u8, |n: i32| n), first);
+
+        mock.rotate_keyed_callbacks();
+        assert_eq!(mock.callback_keyed(1, |n: i32| n), first);
+        first.emit(3);
+        assert_eq!(mock.take_messages(), vec![3]);
+
+        // Callbacks not requested during a view are dropped
+        mock.rotate_keyed_callbacks();
+        mock.rotate_keyed_callbacks();
+        assert_ne!(mock.callback_keyed(1, |n: i32| n), first);
+    }
+
+    #[cfg(feature = "wasm_test")]
+    async fn sleep(ms: i32) {
+        let promise = js_sys::Promise::new(&mut |resolve, _| {
+            crate::utils::window()
+                .set_timeout_with_callback_and_timeout_and_arguments_0(&resolve, ms)
+                .unwrap();
+        });
+        wasm_bindgen_futures::JsFuture::from(promise).await.unwrap();
+    }
+
+    #[cfg(feature = "wasm_test")]
+    #[test]
+    async fn aborted_futures_send_no_messages() {
+        let mock = MockScope::<Counter>::new();
+        let sent = mock.send_future(async {
+            sleep(10).await;
+            1
+        });
+        let aborted = mock.send_future(async {
+            sleep(10).await;
+            2
+        });
+        aborted.abort();
+        sleep(30).await;
+        assert_eq!(mock.take_messages(), vec![1]);
+        assert!(sent.is_finished() && !sent.is_aborted());
+        assert!(aborted.is_aborted());
+
+        // Destroying the component aborts the pending futures
+        let pending = mock.send_future(async {
+            sleep(10).await;
+            3
+        });
+        mock.abort_futures();
+        sleep(30).await;
+        assert!(mock.take_messages().is_empty());
+        assert!(pending.is_aborted());
+    }
+
+    #[cfg(feature = "wasm_test")]
+    #[test]
+    async fn streams_are_forwarded_until_aborted() {
+        use futures::stream;
+
+        let mock = MockScope::<Counter>::new();
+        let ended = mock.send_stream(stream::iter(vec![1, 2, 3]));
+        sleep(10).await;
+        assert_eq!(mock.take_messages(), vec![1, 2, 3]);
+        assert!(ended.is_finished() && !ended.is_aborted());
+
+        let endless = mock.send_stream(stream::iter(vec![4]).chain(stream::pending()));
+        sleep(10).await;
+        assert_eq!(mock.take_messages(), vec![4]);
+        assert!(!endless.is_finished());
+        mock.abort_futures();
+        assert!(endless.is_aborted());
+    }
+
+    #[cfg(feature = "wasm_test")]
+    #[test]
+    async fn delayed_messages_are_sent_unless_cancelled() {
+        let mock = MockScope::<Counter>::new();
+        mock.send_message_after(1, Duration::from_millis(10));
+        mock.send_message_batch_after(vec![2, 3], Duration::from_millis(20));
+        mock.send_message_after(4, Duration::from_millis(10))
+            .abort();
+        assert_eq!(mock.message_count(), 0);
+        sleep(40).await;
+        assert_eq!(mock.take_messages(), vec![1, 2, 3]);
+
+        // Destroying the component cancels the pending messages
+        let pending = mock.send_message_after(5, Duration::from_millis(10));
+        mock.abort_futures();
+        sleep(20).await;
+        assert!(mock.take_messages().is_empty());
+        assert!(pending.is_aborted());
+    }
+
+    #[cfg(feature = "wasm_test")]
+    #[test]
+    async fn debounced_callbacks_send_the_last_message() {
+        let mock = MockScope::<Counter>::new();
+        let callback = mock.callback_debounced(Duration::from_millis(20), |n: i32| n);
+        callback.emit(1);
+        callback.emit(2);
+        sleep(5).await;
+        callback.emit(3);
+        assert_eq!(mock.message_count(), 0);
+        sleep(40).await;
+        assert_eq!(mock.take_messages(), vec![3]);
+    }
+
+    #[cfg(feature = "wasm_test")]
+    #[test]
+    async fn throttled_callbacks_send_once_per_delay() {
+        let mock = MockScope::<Counter>::new();
+        let callback = mock.callback_throttled(Duration::from_millis(20), |n: i32| n);
+        callback.emit(1);
+        callback.emit(2);
+        callback.emit(3);
+        assert_eq!(mock.take_messages(), vec![1]);
+        sleep(30).await;
+        assert_eq!(mock.take_messages(), vec![3]);
+
+        // The window closes once a delay passes without calls
+        sleep(30).await;
+        callback.emit(4);
+        assert_eq!(mock.take_messages(), vec![4]);
+    }
+
+    #[cfg(feature = "wasm_test")]
+    #[test]
+    async fn spawned_tasks_end_with_the_component() {
+        let mock = MockScope::<Counter>::new();
+        let ran = Rc::new(Cell::new(0));
+        let task = |ran: &Rc<Cell<i32>>| {
+            let ran = Rc::clone(ran);
+            async move {
+                sleep(10).await;
+                ran.set(ran.get() + 1);
+            }
+        };
+        let finished = mock.spawn(task(&ran));
+        sleep(30).await;
+        assert!(finished.is_finished() && !finished.is_aborted());
+        assert_eq!(ran.get(), 1);
+
+        let pending = mock.spawn(task(&ran));
+        mock.abort_futures();
+        // Tasks spawned by a destroyed component never run
+        let late = mock.spawn(task(&ran));
+        sleep(30).await;
+        assert_eq!(ran.get(), 1);
+        assert!(pending.is_aborted() && late.is_aborted());
+    }
+}
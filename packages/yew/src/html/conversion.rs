@@ -1,4 +1,6 @@
 use super::{Component, NodeRef, Scope};
+#[cfg(any(feature = "chrono", feature = "time"))]
+use crate::form::{DateTimeValue, TimeZone};
 use std::{borrow::Cow, rc::Rc};
 
 /// Marker trait for types that the [`html!`] macro may clone implicitly.
@@ -86,6 +88,22 @@ impl_into_prop!(|value: &'static str| -> String { value.to_owned() });
 impl_into_prop!(|value: &'static str| -> Cow<'static, str> { Cow::Borrowed(value) });
 impl_into_prop!(|value: String| -> Cow<'static, str> { Cow::Owned(value) });
 
+// values of date and time inputs
+macro_rules! impl_into_prop_for_date_time {
+    ($($ty:ty)*) => {
+        $(
+            impl_into_prop!(|value: $ty| -> Cow<'static, str> {
+                Cow::Owned(DateTimeValue::to_input(&value, TimeZone::Local))
+            });
+        )*
+    };
+}
+
+#[cfg(feature = "chrono")]
+impl_into_prop_for_date_time!(chrono::NaiveDate chrono::NaiveTime chrono::NaiveDateTime);
+#[cfg(feature = "time")]
+impl_into_prop_for_date_time!(time::Date time::Time time::PrimitiveDateTime);
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -0,0 +1,136 @@
+//! IME-safe `input` listener, set with `oninput:committed` in the `html!` macro.
+
+use super::{oninput, IntoEventCallback};
+use crate::callback::Callback;
+use crate::virtual_dom::{inert::is_inert_target, Listener};
+use gloo::events::{EventListener, EventListenerOptions};
+use std::cell::RefCell;
+use std::rc::Rc;
+use wasm_bindgen::JsCast;
+use web_sys::{Element, EventTarget, InputEvent};
+
+/// Listener for `input` events that holds back the events fired while an
+/// [IME](https://developer.mozilla.org/en-US/docs/Glossary/Input_method_editor) composition is in
+/// progress.
+///
+/// The last held back event is emitted when the composition ends, so a controlled input only ever
+/// sees committed text and not the intermediate states of the composition.
+#[derive(Clone, Debug)]
+pub struct CommittedInput {
+    callback: Callback<InputEvent>,
+}
+
+impl CommittedInput {
+    /// Create a listener for an [InputEvent] callback
+    pub fn new(callback: Callback<InputEvent>) -> Self {
+        Self { callback }
+    }
+}
+
+impl Listener for CommittedInput {
+    fn kind(&self) -> &'static str {
+        "oninput"
+    }
+
+    fn attach(&self, element: &Element) -> EventListener {
+        let target = EventTarget::from(element.clone());
+        let callback = self.callback.clone();
+
+        // Last `input` event fired during the current composition
+        let pending: Rc<RefCell<Option<InputEvent>>> = Default::default();
+        // `compositionend` listener armed by the first event of a composition. Dropped together
+        // with the `input` listener.
+        let composition_end: RefCell<Option<EventListener>> = Default::default();
+
+        let listener = move |event: &web_sys::Event| {
+            if is_inert_target(event) {
+                return;
+            }
+            let event: InputEvent = event.clone().unchecked_into();
+            if !event.is_composing() {
+                pending.borrow_mut().take();
+                callback.emit(event);
+                return;
+            }
+
+            if pending.borrow_mut().replace(event).is_none() {
+                let pending = Rc::clone(&pending);
+                let callback = callback.clone();
+                *composition_end.borrow_mut() =
+                    Some(EventListener::once(&target, "compositionend", move |_| {
+                        let event = pending.borrow_mut().take();
+                        if let Some(event) = event {
+                            callback.emit(event);
+                        }
+                    }));
+            }
+        };
+        EventListener::new_with_options(
+            &EventTarget::from(element.clone()),
+            "input",
+            EventListenerOptions::enable_prevent_default(),
+            listener,
+        )
+    }
+}
+
+impl oninput::Wrapper {
+    #[doc(hidden)]
+    #[inline]
+    pub fn __macro_new_committed(
+        callback: impl IntoEventCallback<InputEvent>,
+    ) -> Option<Rc<dyn Listener>> {
+        let callback = callback.into_event_callback()?;
+        Some(Rc::new(CommittedInput::new(callback)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::document;
+    use std::cell::Cell;
+    use web_sys::{Event, InputEventInit};
+
+    #[cfg(feature = "wasm_test")]
+    use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+
+    #[cfg(feature = "wasm_test")]
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    fn input_event(is_composing: bool) -> InputEvent {
+        InputEvent::new_with_event_init_dict(
+            "input",
+            InputEventInit::new().is_composing(is_composing),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn holds_back_input_during_composition() {
+        let el = document().create_element("input").unwrap();
+        let emitted = Rc::new(Cell::new(0));
+        let listener = CommittedInput::new({
+            let emitted = Rc::clone(&emitted);
+            Callback::from(move |_| emitted.set(emitted.get() + 1))
+        });
+        let _handle = listener.attach(&el);
+
+        el.dispatch_event(&input_event(false)).unwrap();
+        assert_eq!(emitted.get(), 1);
+
+        el.dispatch_event(&input_event(true)).unwrap();
+        el.dispatch_event(&input_event(true)).unwrap();
+        assert_eq!(emitted.get(), 1);
+
+        let end = Event::new("compositionend").unwrap();
+        el.dispatch_event(&end).unwrap();
+        assert_eq!(emitted.get(), 2);
+
+        // A second composition arms the `compositionend` listener again
+        el.dispatch_event(&input_event(true)).unwrap();
+        el.dispatch_event(&Event::new("compositionend").unwrap())
+            .unwrap();
+        assert_eq!(emitted.get(), 3);
+    }
+}
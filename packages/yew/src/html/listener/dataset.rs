@@ -0,0 +1,137 @@
+//! Typed access to the `data-*` attributes of the elements of an event.
+
+use std::str::FromStr;
+use wasm_bindgen::JsCast;
+use web_sys::{Element, Event, EventTarget};
+
+fn attribute_name(name: &str) -> String {
+    format!("data-{}", name)
+}
+
+fn parse<T: FromStr>(element: &Element, attribute: &str) -> Option<T> {
+    element.get_attribute(attribute)?.parse().ok()
+}
+
+/// A trait to read the `data-*` attributes of the elements of an event, parsed into a type.
+///
+/// This is the usual way to tell apart the items of a list with a single listener on the list,
+/// instead of a callback for every item.
+///
+/// # Example
+///
+/// ```
+/// use yew::prelude::*;
+/// # enum Msg {
+/// #   Remove(usize),
+/// # }
+/// # struct Comp {
+/// #   items: Vec<String>,
+/// # }
+/// # impl Component for Comp {
+/// # type Message = Msg;
+/// # type Properties = ();
+/// # fn create(ctx: &Context<Self>) -> Self {
+/// #   Self { items: Vec::new() }
+/// # }
+///
+/// fn view(&self, ctx: &Context<Self>) -> Html {
+///     let onclick = ctx
+///         .link()
+///         .batch_callback(|e: MouseEvent| e.dataset::<usize>("index").map(Msg::Remove));
+///     html! {
+///         <ul {onclick}>
+///             { for self.items.iter().enumerate().map(|(index, item)| html! {
+///                 <li data-index={index.to_string()}><b>{ item }</b></li>
+///             }) }
+///         </ul>
+///     }
+/// }
+/// # }
+/// ```
+pub trait EventDataset
+where
+    Self: AsRef<Event>,
+{
+    /// Parses the attribute `data-{name}` of the event's target, or of its closest ancestor
+    /// having it up to the event's current target, e.g. the `li` of a click on its text.
+    ///
+    /// Returns [`None`] if no such element has the attribute, or if its value doesn't parse
+    /// into `T`.
+    fn dataset<T: FromStr>(&self, name: &str) -> Option<T> {
+        let event = self.as_ref();
+        let attribute = attribute_name(name);
+        let current = event.current_target();
+        let mut element = event.target()?.dyn_into::<Element>().ok();
+        while let Some(el) = element {
+            if el.has_attribute(&attribute) {
+                return parse(&el, &attribute);
+            }
+            if current.as_ref() == Some(el.unchecked_ref::<EventTarget>()) {
+                return None;
+            }
+            element = el.parent_element();
+        }
+        None
+    }
+
+    /// Parses the attribute `data-{name}` of the event's current target, i.e. the element the
+    /// listener is set on.
+    ///
+    /// Returns [`None`] if the element doesn't have the attribute, or if its value doesn't parse
+    /// into `T`.
+    fn current_dataset<T: FromStr>(&self, name: &str) -> Option<T> {
+        let element = self.as_ref().current_target()?.dyn_into::<Element>().ok()?;
+        parse(&element, &attribute_name(name))
+    }
+}
+
+impl<E: AsRef<Event>> EventDataset for E {}
+
+#[cfg(all(test, feature = "wasm_test"))]
+mod tests {
+    use super::*;
+    use crate::utils::document;
+    use gloo::events::EventListener;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+    use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+    use web_sys::HtmlElement;
+
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    #[test]
+    fn reads_closest_attribute() {
+        let list = document().create_element("ul").unwrap();
+        list.set_inner_html(
+            r#"<li data-index="2" data-name="b"><b>item</b></li><li data-index="x"></li>"#,
+        );
+        list.set_attribute("data-list", "7").unwrap();
+
+        let read = Rc::new(RefCell::new(Vec::new()));
+        let _listener = {
+            let read = Rc::clone(&read);
+            EventListener::new(&list, "click", move |e| {
+                read.borrow_mut().push((
+                    e.dataset::<u32>("index"),
+                    e.dataset::<String>("name"),
+                    e.current_dataset::<u32>("list"),
+                    e.dataset::<u32>("list"),
+                ));
+            })
+        };
+        let click = |selector: &str| {
+            let el = list.query_selector(selector).unwrap().unwrap();
+            el.unchecked_into::<HtmlElement>().click();
+        };
+        click("b");
+        click("li + li");
+
+        assert_eq!(
+            *read.borrow(),
+            vec![
+                (Some(2), Some("b".to_owned()), Some(7), Some(7)),
+                (None, None, Some(7), Some(7)),
+            ]
+        );
+    }
+}
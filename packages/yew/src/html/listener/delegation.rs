@@ -0,0 +1,555 @@
+//! Per-app configuration of how listeners are attached, and the registry of listeners delegated
+//! to the element an app is mounted in.
+
+use crate::virtual_dom::Listener;
+use gloo::events::{EventListener, EventListenerOptions};
+use slab::Slab;
+use std::cell::{Cell, RefCell};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::rc::{Rc, Weak};
+use wasm_bindgen::{JsCast, JsValue};
+use web_sys::{Element, Event};
+
+/// Function handling the events a delegated listener receives
+pub(crate) type Handler = Rc<dyn Fn(&Event)>;
+
+/// How the listeners of an app are attached to the DOM
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ListenerMode {
+    /// Every listener is attached to its own element. This is the default.
+    Direct,
+    /// Listeners of events that bubble are registered with a single DOM listener per event type
+    /// on the element the app is mounted in, which calls them in bubbling order.
+    ///
+    /// Listeners of events that don't bubble (e.g. `focus`, `toggle` or `ended`), passive
+    /// listeners, listeners created with `new_once` and listeners of elements in other documents,
+    /// e.g. rendered with an [`IFramePortal`](crate::iframe_portal::IFramePortal), are still
+    /// attached to their elements.
+    Delegated,
+}
+
+impl Default for ListenerMode {
+    fn default() -> Self {
+        Self::Direct
+    }
+}
+
+/// Configuration of the listeners of an app, set with
+/// [`AppBuilder::listeners`](crate::AppBuilder::listeners).
+///
+/// # Example
+/// ```rust
+/// # use yew::prelude::*;
+/// use yew::html::{ListenerConfig, ListenerMode};
+/// # struct App;
+/// # impl Component for App {
+/// #     type Message = ();
+/// #     type Properties = ();
+/// #     fn create(_ctx: &Context<Self>) -> Self { Self }
+/// #     fn view(&self, _ctx: &Context<Self>) -> Html { html! {} }
+/// # }
+///
+/// # fn start() {
+/// // Another framework on the page handles clicks bubbling out of our elements
+/// let config = ListenerConfig::default()
+///     .mode(ListenerMode::Delegated)
+///     .disable_bubbling("click");
+/// yew::AppBuilder::<App>::new().listeners(config).mount_to_body(());
+/// # }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ListenerConfig {
+    mode: ListenerMode,
+    non_bubbling: HashSet<&'static str>,
+}
+
+impl ListenerConfig {
+    /// Sets how listeners are attached
+    pub fn mode(mut self, mode: ListenerMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Stops Yew from bubbling events of the type `event`, e.g. `"click"`, to listeners of the
+    /// same app: only the innermost listener of the app is called.
+    ///
+    /// The event itself keeps propagating in the DOM, so listeners attached outside of Yew still
+    /// receive it. With [`ListenerMode::Direct`], listeners attached with `new_once` and
+    /// passive listeners are not affected.
+    pub fn disable_bubbling(mut self, event: &'static str) -> Self {
+        self.non_bubbling.insert(event);
+        self
+    }
+
+    /// Returns how listeners are attached
+    pub fn get_mode(&self) -> ListenerMode {
+        self.mode
+    }
+
+    /// Returns whether Yew bubbles events of the type `event` to listeners of the same app
+    pub fn bubbles(&self, event: &str) -> bool {
+        !self.non_bubbling.contains(event)
+    }
+}
+
+/// Events known to bubble, which can be listened to on the app root. Listeners of other events,
+/// e.g. `focus`, `toggle` or the events of media elements, are attached to their elements.
+const BUBBLING_EVENTS: &[&str] = &[
+    "animationcancel",
+    "animationend",
+    "animationiteration",
+    "animationstart",
+    "auxclick",
+    "change",
+    "click",
+    "compositionend",
+    "compositionstart",
+    "compositionupdate",
+    "contextmenu",
+    "copy",
+    "cut",
+    "dblclick",
+    "drag",
+    "dragend",
+    "dragenter",
+    "dragleave",
+    "dragover",
+    "dragstart",
+    "drop",
+    "focusin",
+    "focusout",
+    "gotpointercapture",
+    "input",
+    "keydown",
+    "keypress",
+    "keyup",
+    "lostpointercapture",
+    "mousedown",
+    "mousemove",
+    "mouseout",
+    "mouseover",
+    "mouseup",
+    "paste",
+    "pointercancel",
+    "pointerdown",
+    "pointermove",
+    "pointerout",
+    "pointerover",
+    "pointerup",
+    "reset",
+    "select",
+    "selectstart",
+    "submit",
+    "touchcancel",
+    "touchend",
+    "touchmove",
+    "touchstart",
+    "transitioncancel",
+    "transitionend",
+    "transitionrun",
+    "transitionstart",
+    "wheel",
+];
+
+thread_local! {
+    static NEXT_ROOT_ID: Cell<u32> = Cell::new(0);
+    // Shared by all apps, as the elements of nested apps are visited when dispatching the events
+    // of the outer app
+    static NEXT_ELEMENT_ID: Cell<u32> = Cell::new(0);
+}
+
+/// Name of the property identifying the elements with delegated listeners
+const ELEMENT_ID_KEY: &str = "__yew_listener_id";
+
+/// Listeners of an app, shared by all the scopes of the app
+pub(crate) struct RootListeners {
+    config: ListenerConfig,
+    /// Element the listeners are delegated to, if the app is mounted in one
+    host: Option<Element>,
+    /// Property set on events already handled by a listener of the app, for events that are not
+    /// bubbled by Yew
+    handled_key: JsValue,
+    kinds: RefCell<HashMap<&'static str, Delegated>>,
+}
+
+/// DOM listener on the host element dispatching events of one type
+struct Delegated {
+    _listener: EventListener,
+    handlers: Rc<RefCell<ElementHandlers>>,
+}
+
+type ElementHandlers = HashMap<u32, Slab<Handler>>;
+
+impl RootListeners {
+    pub(crate) fn new(config: ListenerConfig, host: Option<Element>) -> Self {
+        let id = NEXT_ROOT_ID.with(|id| id.replace(id.get() + 1));
+        Self {
+            config,
+            host,
+            handled_key: JsValue::from_str(&format!("__yew_handled_{}", id)),
+            kinds: Default::default(),
+        }
+    }
+
+    /// Attaches `listener` to `element` according to the configuration of the app
+    pub(crate) fn attach(&self, listener: &dyn Listener, element: &Element) -> ListenerHandle {
+        let name = listener.event_name();
+        if listener.is_once() {
+            if let Some(handler) = listener.handler(element) {
+                return Self::attach_once(listener.kind(), name, element, handler);
+            }
+        }
+        let handler = match listener.handler(element) {
+            Some(handler) => handler,
+            None => return ListenerHandle::Direct(listener.attach(element)),
+        };
+
+        match &self.host {
+            // Events in other documents, e.g. of iframes, don't bubble up to the host
+            Some(host)
+                if self.config.mode == ListenerMode::Delegated
+                    && BUBBLING_EVENTS.contains(&name)
+                    && element.owner_document() == host.owner_document() =>
+            {
+                self.delegate(host, name, element, handler)
+            }
+            _ if !self.config.bubbles(name) => {
+                let handled_key = self.handled_key.clone();
+                ListenerHandle::Direct(EventListener::new_with_options(
+                    element,
+                    name,
+                    EventListenerOptions::enable_prevent_default(),
+                    move |event| {
+                        let handled = js_sys::Reflect::get(event, &handled_key)
+                            .map_or(false, |v| v.is_truthy());
+                        if !handled {
+                            let _ = js_sys::Reflect::set(event, &handled_key, &JsValue::TRUE);
+                            handler(event);
+                        }
+                    },
+                ))
+            }
+            _ => ListenerHandle::Direct(listener.attach(element)),
+        }
+    }
+
+    /// Attaches a listener which the browser removes after the first event, remembering whether
+    /// it has fired
+    fn attach_once(
+        kind: &'static str,
+        name: &'static str,
+        element: &Element,
+        handler: Handler,
+    ) -> ListenerHandle {
+        let options = if name == "touchstart" || name == "touchmove" {
+            EventListenerOptions::default()
+        } else {
+            EventListenerOptions::enable_prevent_default()
+        };
+        let fired = Rc::new(Cell::new(false));
+        let listener = {
+            let fired = Rc::clone(&fired);
+            EventListener::once_with_options(element, name, options, move |event| {
+                fired.set(true);
+                handler(event);
+            })
+        };
+        ListenerHandle::Once {
+            kind,
+            fired,
+            _listener: listener,
+        }
+    }
+
+    fn delegate(
+        &self,
+        host: &Element,
+        name: &'static str,
+        element: &Element,
+        handler: Handler,
+    ) -> ListenerHandle {
+        let element_id = Self::element_id(element);
+        let mut kinds = self.kinds.borrow_mut();
+        let delegated = kinds.entry(name).or_insert_with(|| {
+            let handlers: Rc<RefCell<ElementHandlers>> = Default::default();
+            let listener = {
+                let handlers = Rc::clone(&handlers);
+                let target = host.clone();
+                let host = host.clone();
+                let bubbles = self.config.bubbles(name);
+                EventListener::new_with_options(
+                    &target,
+                    name,
+                    EventListenerOptions::enable_prevent_default(),
+                    move |event| dispatch(&host, &handlers, bubbles, event),
+                )
+            };
+            Delegated {
+                _listener: listener,
+                handlers,
+            }
+        });
+
+        let key = delegated
+            .handlers
+            .borrow_mut()
+            .entry(element_id)
+            .or_default()
+            .insert(handler);
+        ListenerHandle::Delegated {
+            handlers: Rc::downgrade(&delegated.handlers),
+            element_id,
+            key,
+        }
+    }
+
+    /// Returns the id of the element, assigning one if it has none yet
+    fn element_id(element: &Element) -> u32 {
+        let key = JsValue::from_str(ELEMENT_ID_KEY);
+        if let Some(id) = js_sys::Reflect::get(element, &key)
+            .ok()
+            .and_then(|id| id.as_f64())
+        {
+            return id as u32;
+        }
+
+        let id = NEXT_ELEMENT_ID.with(|id| id.replace(id.get() + 1));
+        let _ = js_sys::Reflect::set(element, &key, &JsValue::from(id));
+        id
+    }
+}
+
+impl Default for RootListeners {
+    fn default() -> Self {
+        Self::new(ListenerConfig::default(), None)
+    }
+}
+
+impl fmt::Debug for RootListeners {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RootListeners")
+            .field("config", &self.config)
+            .field("host", &self.host)
+            .finish()
+    }
+}
+
+/// Calls the handlers of the elements from the target of `event` up to `host`
+fn dispatch(host: &Element, handlers: &RefCell<ElementHandlers>, bubbles: bool, event: &Event) {
+    let key = JsValue::from_str(ELEMENT_ID_KEY);
+    let mut current = event.target().and_then(|t| t.dyn_into::<Element>().ok());
+    while let Some(element) = current {
+        if &element == host {
+            break;
+        }
+
+        let id = js_sys::Reflect::get(&element, &key)
+            .ok()
+            .and_then(|id| id.as_f64());
+        if let Some(id) = id {
+            // Handlers may add or remove listeners
+            let found: Vec<Handler> = handlers
+                .borrow()
+                .get(&(id as u32))
+                .map(|slab| slab.iter().map(|(_, h)| Rc::clone(h)).collect())
+                .unwrap_or_default();
+            for handler in &found {
+                handler(event);
+            }
+            if !found.is_empty() && (!bubbles || event.cancel_bubble()) {
+                break;
+            }
+        }
+
+        current = element.parent_element();
+    }
+}
+
+/// Keeps an attached listener alive. The listener is removed when the handle is dropped.
+pub(crate) enum ListenerHandle {
+    Direct(EventListener),
+    Once {
+        kind: &'static str,
+        fired: Rc<Cell<bool>>,
+        _listener: EventListener,
+    },
+    Delegated {
+        handlers: Weak<RefCell<ElementHandlers>>,
+        element_id: u32,
+        key: usize,
+    },
+}
+
+impl ListenerHandle {
+    /// Returns the kind of the listener, if it is a `once` listener which has fired
+    pub(crate) fn fired_once(&self) -> Option<&'static str> {
+        match self {
+            Self::Once { kind, fired, .. } if fired.get() => Some(kind),
+            _ => None,
+        }
+    }
+}
+
+impl Drop for ListenerHandle {
+    fn drop(&mut self) {
+        if let Self::Delegated {
+            handlers,
+            element_id,
+            key,
+        } = self
+        {
+            if let Some(handlers) = handlers.upgrade() {
+                let mut handlers = handlers.borrow_mut();
+                if let Some(slab) = handlers.get_mut(element_id) {
+                    slab.remove(*key);
+                    if slab.is_empty() {
+                        handlers.remove(element_id);
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl fmt::Debug for ListenerHandle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Direct(listener) => f.debug_tuple("Direct").field(listener).finish(),
+            Self::Once { kind, fired, .. } => f
+                .debug_struct("Once")
+                .field("kind", kind)
+                .field("fired", &fired.get())
+                .finish(),
+            Self::Delegated { element_id, .. } => f
+                .debug_struct("Delegated")
+                .field("element_id", element_id)
+                .finish(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate self as yew;
+
+    use super::*;
+    use crate::utils::document;
+    use crate::{html, AppBuilder, Component, Context, Html, Properties};
+
+    #[cfg(feature = "wasm_test")]
+    use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+
+    #[cfg(feature = "wasm_test")]
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    #[derive(Clone, Properties)]
+    struct Props {
+        clicks: Rc<RefCell<Vec<&'static str>>>,
+    }
+
+    impl PartialEq for Props {
+        fn eq(&self, other: &Self) -> bool {
+            Rc::ptr_eq(&self.clicks, &other.clicks)
+        }
+    }
+
+    struct Nested;
+
+    impl Component for Nested {
+        type Message = ();
+        type Properties = Props;
+
+        fn create(_ctx: &Context<Self>) -> Self {
+            Self
+        }
+
+        fn view(&self, ctx: &Context<Self>) -> Html {
+            let onclick = |name| {
+                let clicks = Rc::clone(&ctx.props().clicks);
+                move |_| clicks.borrow_mut().push(name)
+            };
+            html! {
+                <div onclick={onclick("outer")}>
+                    <span onclick={onclick("inner")}><b>{ "click" }</b></span>
+                </div>
+            }
+        }
+    }
+
+    /// Mounts `Nested`, clicks the innermost element and returns the listeners called
+    fn clicks(config: ListenerConfig) -> Vec<&'static str> {
+        let host = document().create_element("div").unwrap();
+        let props = Props {
+            clicks: Default::default(),
+        };
+        let app = AppBuilder::<Nested>::new()
+            .listeners(config)
+            .mount(host.clone(), props.clone());
+
+        let target = host.query_selector("b").unwrap().unwrap();
+        target.unchecked_into::<web_sys::HtmlElement>().click();
+
+        app.destroy();
+        props.clicks.take()
+    }
+
+    #[test]
+    fn listeners_bubble_in_both_modes() {
+        let direct = ListenerConfig::default();
+        assert_eq!(clicks(direct), vec!["inner", "outer"]);
+
+        let delegated = ListenerConfig::default().mode(ListenerMode::Delegated);
+        assert_eq!(clicks(delegated), vec!["inner", "outer"]);
+    }
+
+    #[test]
+    fn non_bubbling_listeners_fire_when_delegated() {
+        struct Details;
+
+        impl Component for Details {
+            type Message = ();
+            type Properties = Props;
+
+            fn create(_ctx: &Context<Self>) -> Self {
+                Self
+            }
+
+            fn view(&self, ctx: &Context<Self>) -> Html {
+                let ontoggle = {
+                    let clicks = Rc::clone(&ctx.props().clicks);
+                    move |_| clicks.borrow_mut().push("toggle")
+                };
+                html! { <details {ontoggle}><summary>{ "summary" }</summary></details> }
+            }
+        }
+
+        let host = document().create_element("div").unwrap();
+        let props = Props {
+            clicks: Default::default(),
+        };
+        let app = AppBuilder::<Details>::new()
+            .listeners(ListenerConfig::default().mode(ListenerMode::Delegated))
+            .mount(host.clone(), props.clone());
+
+        // Like the event of the browser, this one doesn't bubble
+        let details = host.query_selector("details").unwrap().unwrap();
+        details
+            .dispatch_event(&Event::new("toggle").unwrap())
+            .unwrap();
+
+        app.destroy();
+        assert_eq!(props.clicks.take(), vec!["toggle"]);
+    }
+
+    #[test]
+    fn disabled_bubbling_calls_innermost_listener() {
+        let direct = ListenerConfig::default().disable_bubbling("click");
+        assert_eq!(clicks(direct), vec!["inner"]);
+
+        let delegated = ListenerConfig::default()
+            .mode(ListenerMode::Delegated)
+            .disable_bubbling("click");
+        assert_eq!(clicks(delegated), vec!["inner"]);
+    }
+}
@@ -76,6 +76,9 @@ impl_action! {
     onanimationend(name: "animationend", event: AnimationEvent) -> web_sys::AnimationEvent => |_, event| { event }
     onanimationiteration(name: "animationiteration", event: AnimationEvent) -> web_sys::AnimationEvent => |_, event| { event }
     onanimationstart(name: "animationstart", event: AnimationEvent) -> web_sys::AnimationEvent => |_, event| { event }
+    oncompositionend(name: "compositionend", event: CompositionEvent) -> web_sys::CompositionEvent => |_, event| { event }
+    oncompositionstart(name: "compositionstart", event: CompositionEvent) -> web_sys::CompositionEvent => |_, event| { event }
+    oncompositionupdate(name: "compositionupdate", event: CompositionEvent) -> web_sys::CompositionEvent => |_, event| { event }
     ongotpointercapture(name: "gotpointercapture", event: PointerEvent) -> web_sys::PointerEvent => |_, event| { event }
     onloadend(name: "loadend", event: ProgressEvent) -> web_sys::ProgressEvent => |_, event| { event }
     onlostpointercapture(name: "lostpointercapture", event: PointerEvent) -> web_sys::PointerEvent => |_, event| { event }
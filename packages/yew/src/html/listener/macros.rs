@@ -4,11 +4,12 @@ macro_rules! impl_action {
         #[doc(hidden)]
         pub mod $action {
             use crate::callback::Callback;
+            use crate::event_sink::{self, FrameworkEvent};
             #[allow(unused_imports)]
             use crate::html::{listener::*, IntoPropValue};
-            use crate::virtual_dom::Listener;
+            use crate::virtual_dom::{inert::is_inert_target, Listener};
             use gloo::events::{EventListener, EventListenerOptions};
-            use wasm_bindgen::JsValue;
+            use wasm_bindgen::{JsCast, JsValue};
             use web_sys::{$type as WebSysType, Element, EventTarget};
             use std::rc::Rc;
 
@@ -16,12 +17,20 @@ macro_rules! impl_action {
             #[derive(Clone, Debug)]
             pub struct Wrapper {
                 callback: Callback<Event>,
+                once: bool,
             }
 
             impl Wrapper {
                 /// Create a wrapper for an event-typed callback
                 pub fn new(callback: Callback<Event>) -> Self {
-                    Wrapper { callback }
+                    Wrapper { callback, once: false }
+                }
+
+                /// Create a wrapper for an event-typed callback, which is only invoked for the
+                /// first event after being attached. The DOM listener and the callback are
+                /// dropped after that.
+                pub fn new_once(callback: Callback<Event>) -> Self {
+                    Wrapper { callback, once: true }
                 }
 
                 #[doc(hidden)]
@@ -30,6 +39,37 @@ macro_rules! impl_action {
                     let callback = callback.into_event_callback()?;
                     Some(Rc::new(Self::new(callback)))
                 }
+
+                #[doc(hidden)]
+                #[inline]
+                pub fn __macro_new_once(callback: impl IntoEventCallback<Event>) -> Option<Rc<dyn Listener>> {
+                    let callback = callback.into_event_callback()?;
+                    Some(Rc::new(Self::new_once(callback)))
+                }
+
+                /// Returns the function handling the events of `element`
+                fn listener(&self, element: &Element) -> Rc<dyn Fn(&web_sys::Event)> {
+                    let this = element.clone();
+                    let callback = self.callback.clone();
+                    Rc::new(move |event: &web_sys::Event| {
+                        if is_inert_target(event) {
+                            return;
+                        }
+                        check_type(event);
+                        let event: WebSysType = JsValue::from(event).into();
+                        callback.emit($convert(&this, event));
+                    })
+                }
+            }
+
+            /// Reports events which are not a `WebSysType`, while a sink is set
+            fn check_type(event: &web_sys::Event) {
+                if event_sink::has_sink() && !event.is_instance_of::<WebSysType>() {
+                    event_sink::report(FrameworkEvent::ListenerCastFailed {
+                        event: $name,
+                        expected: stringify!($type),
+                    });
+                }
             }
 
             /// And event type which keeps the returned type.
@@ -40,24 +80,41 @@ macro_rules! impl_action {
                     stringify!($action)
                 }
 
+                fn event_name(&self) -> &'static str {
+                    $name
+                }
+
                 fn attach(&self, element: &Element) -> EventListener {
-                    let this = element.clone();
-                    let callback = self.callback.clone();
-                    let listener = move |
-                        event: &web_sys::Event
-                    | {
-                        let event: WebSysType = JsValue::from(event).into();
-                        callback.emit($convert(&this, event));
-                    };
+                    let handler = self.listener(element);
+                    let listener = move |event: &web_sys::Event| handler(event);
+                    let target = EventTarget::from(element.clone());
                     // We should only set passive event listeners for `touchstart` and `touchmove`.
                     // See here: https://developer.mozilla.org/en-US/docs/Web/API/EventTarget/addEventListener#Improving_scrolling_performance_with_passive_listeners
-                    if $name == "touchstart" || $name == "touchmove" {
-                        EventListener::new(&EventTarget::from(element.clone()), $name, listener)
+                    let options = if $name == "touchstart" || $name == "touchmove" {
+                        EventListenerOptions::default()
                     } else {
-                        let options = EventListenerOptions::enable_prevent_default();
-                        EventListener::new_with_options(&EventTarget::from(element.clone()), $name, options, listener)
+                        EventListenerOptions::enable_prevent_default()
+                    };
+                    if self.once {
+                        // The browser removes the listener after the first event and the closure
+                        // is freed once it has been called
+                        EventListener::once_with_options(&target, $name, options, listener)
+                    } else {
+                        EventListener::new_with_options(&target, $name, options, listener)
                     }
                 }
+
+                fn handler(&self, element: &Element) -> Option<Rc<dyn Fn(&web_sys::Event)>> {
+                    // Passive listeners need their own DOM listener
+                    if !self.once && ($name == "touchstart" || $name == "touchmove") {
+                        return None;
+                    }
+                    Some(self.listener(element))
+                }
+
+                fn is_once(&self) -> bool {
+                    self.once
+                }
             }
         }
     )*};
@@ -0,0 +1,174 @@
+//! Support for messages in the listeners of `html!`.
+//!
+//! Listeners like `onclick={ctx.link().callback(|_| Msg::Increment)}` have the messages returned
+//! by their closures checked against the `Message` type of the component, so a mismatch is
+//! reported at the closure body. Listeners like `onclick={Msg::Increment}` are expanded to
+//! `ctx.link().callback(|_| Msg::Increment)`, so they are checked the same way.
+
+use crate::html::{Component, Scope};
+use std::marker::PhantomData;
+
+/// Checks the messages returned by a `callback` closure in a listener
+#[doc(hidden)]
+pub struct MessageCheck<COMP>(PhantomData<fn() -> COMP>);
+
+impl<COMP> Clone for MessageCheck<COMP> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<COMP> Copy for MessageCheck<COMP> {}
+
+impl<COMP: Component> MessageCheck<COMP> {
+    #[inline]
+    pub fn check<M: Into<COMP::Message>>(self, message: M) -> M {
+        message
+    }
+}
+
+/// Used instead of [`MessageCheck`] for `callback` methods of other types than [`Scope`]
+#[doc(hidden)]
+#[derive(Clone, Copy)]
+pub struct NoMessageCheck;
+
+impl NoMessageCheck {
+    #[inline]
+    pub fn check<M>(self, message: M) -> M {
+        message
+    }
+}
+
+/// Selects the message check for the receiver of a `callback` method of type `T`, with
+/// `(&&MessageCheckOf::new(&receiver)).message_check()`
+#[doc(hidden)]
+pub struct MessageCheckOf<T>(PhantomData<fn() -> T>);
+
+impl<T> MessageCheckOf<T> {
+    #[inline]
+    pub fn new(_receiver: &T) -> Self {
+        Self(PhantomData)
+    }
+}
+
+#[doc(hidden)]
+pub trait ScopeMessageCheck {
+    type Check;
+    fn message_check(&self) -> Self::Check;
+}
+
+impl<COMP: Component> ScopeMessageCheck for &MessageCheckOf<Scope<COMP>> {
+    type Check = MessageCheck<COMP>;
+
+    fn message_check(&self) -> Self::Check {
+        MessageCheck(PhantomData)
+    }
+}
+
+impl<COMP: Component> ScopeMessageCheck for &MessageCheckOf<&Scope<COMP>> {
+    type Check = MessageCheck<COMP>;
+
+    fn message_check(&self) -> Self::Check {
+        MessageCheck(PhantomData)
+    }
+}
+
+#[doc(hidden)]
+pub trait AnyMessageCheck {
+    fn message_check(&self) -> NoMessageCheck;
+}
+
+impl<T> AnyMessageCheck for MessageCheckOf<T> {
+    fn message_check(&self) -> NoMessageCheck {
+        NoMessageCheck
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate self as yew;
+
+    use super::*;
+    use crate::utils::document;
+    use crate::{html, AppBuilder, Callback, Context, Html};
+    use wasm_bindgen::JsCast;
+    use web_sys::{HtmlElement, MouseEvent};
+
+    #[cfg(feature = "wasm_test")]
+    use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+
+    #[cfg(feature = "wasm_test")]
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    enum Msg {
+        Increment,
+        Add(u32),
+    }
+
+    struct Counter {
+        count: u32,
+    }
+
+    impl Component for Counter {
+        type Message = Msg;
+        type Properties = ();
+
+        fn create(_ctx: &Context<Self>) -> Self {
+            Self { count: 0 }
+        }
+
+        fn update(&mut self, _ctx: &Context<Self>, msg: Self::Message) -> bool {
+            match msg {
+                Msg::Increment => self.count += 1,
+                Msg::Add(count) => self.count += count,
+            }
+            true
+        }
+
+        fn view(&self, ctx: &Context<Self>) -> Html {
+            html! {
+                <>
+                    <button id="increment" onclick={Msg::Increment} />
+                    <button id="add" onclick={ctx.link().callback(|_| Msg::Add(2))} />
+                    <p>{ self.count }</p>
+                </>
+            }
+        }
+    }
+
+    #[test]
+    fn listeners_send_messages() {
+        let host = document().create_element("div").unwrap();
+        let app = AppBuilder::<Counter>::new().mount(host.clone(), ());
+
+        let click = |id: &str| {
+            let selector = format!("#{}", id);
+            let button = host.query_selector(&selector).unwrap().unwrap();
+            button.unchecked_into::<HtmlElement>().click();
+        };
+        click("increment");
+        click("add");
+        let count = host.query_selector("p").unwrap().unwrap();
+        assert_eq!(count.text_content().as_deref(), Some("3"));
+        app.destroy();
+    }
+
+    #[test]
+    fn other_callback_methods_are_not_checked() {
+        struct Link;
+
+        impl Link {
+            fn callback<F>(&self, f: F) -> Callback<MouseEvent>
+            where
+                F: Fn(MouseEvent) -> u8 + 'static,
+            {
+                Callback::from(move |event| {
+                    f(event);
+                })
+            }
+        }
+
+        let link = Link;
+        let _ = html! { <button onclick={link.callback(|_| 1)} /> };
+    }
+}
@@ -1,12 +1,24 @@
 #[macro_use]
 mod macros;
+mod committed_input;
+mod dataset;
+mod delegation;
 mod events;
+mod message;
 
 use wasm_bindgen::JsCast;
 use web_sys::{Event, EventTarget};
 
 use crate::Callback;
+pub use committed_input::CommittedInput;
+pub use dataset::EventDataset;
+pub use delegation::{ListenerConfig, ListenerMode};
+pub(crate) use delegation::{ListenerHandle, RootListeners};
 pub use events::*;
+#[doc(hidden)]
+pub use message::{
+    AnyMessageCheck, MessageCheck, MessageCheckOf, NoMessageCheck, ScopeMessageCheck,
+};
 
 /// A trait to obtain a generic event target.
 ///
@@ -13,8 +13,8 @@ pub use listener::*;
 use crate::virtual_dom::VNode;
 use std::cell::RefCell;
 use std::rc::Rc;
-use wasm_bindgen::JsValue;
-use web_sys::Node;
+use wasm_bindgen::{JsCast, JsValue};
+use web_sys::{Element, Node};
 
 /// A type which expected as a result of `view` function implementation.
 pub type Html = VNode;
@@ -81,6 +81,8 @@ impl std::fmt::Debug for NodeRef {
 struct NodeRefInner {
     node: Option<Node>,
     link: Option<NodeRef>,
+    /// Pointers captured with [NodeRef::set_pointer_capture]
+    pointer_captures: Vec<i32>,
 }
 
 impl NodeRef {
@@ -103,19 +105,74 @@ impl NodeRef {
         node_ref
     }
 
+    /// Captures the pointer with `pointer_id` on the referenced element, so it receives all
+    /// events of the pointer until it is released.
+    ///
+    /// Unlike calling [Element::set_pointer_capture] directly, the capture survives re-renders
+    /// that replace the referenced element: it is moved to the new element for as long as the
+    /// pointer is active. Returns `false`, if there is no referenced element or the pointer is not
+    /// active.
+    pub fn set_pointer_capture(&self, pointer_id: i32) -> bool {
+        let el = match self.cast::<Element>() {
+            Some(el) => el,
+            None => return false,
+        };
+        if el.set_pointer_capture(pointer_id).is_err() {
+            return false;
+        }
+        let mut this = self.0.borrow_mut();
+        if !this.pointer_captures.contains(&pointer_id) {
+            this.pointer_captures.push(pointer_id);
+        }
+        true
+    }
+
+    /// Releases a pointer captured with [NodeRef::set_pointer_capture]
+    pub fn release_pointer_capture(&self, pointer_id: i32) {
+        self.0
+            .borrow_mut()
+            .pointer_captures
+            .retain(|id| *id != pointer_id);
+        if let Some(el) = self.cast::<Element>() {
+            // Fails, if the pointer is not active anymore, which releases it as well
+            let _ = el.release_pointer_capture(pointer_id);
+        }
+    }
+
+    /// Returns true, if the referenced element has captured the pointer with `pointer_id`
+    pub fn has_pointer_capture(&self, pointer_id: i32) -> bool {
+        self.cast::<Element>()
+            .map_or(false, |el| el.has_pointer_capture(pointer_id))
+    }
+
     /// Place a Node in a reference for later use
     pub(crate) fn set(&self, node: Option<Node>) {
         let mut this = self.0.borrow_mut();
+        if !this.pointer_captures.is_empty() && node.is_some() && this.node != node {
+            // Move the captures to the new element. Pointers that are not active anymore can't be
+            // captured and are forgotten.
+            if let Some(el) = node.as_ref().and_then(|n| n.dyn_ref::<Element>()) {
+                this.pointer_captures
+                    .retain(|id| el.set_pointer_capture(*id).is_ok());
+            }
+        }
         this.node = node;
         this.link = None;
     }
 
     /// Link a downstream `NodeRef`
     pub(crate) fn link(&self, node_ref: Self) {
-        // Avoid circular references
+        // Avoid circular references, also through the links of `node_ref`
         if self == &node_ref {
             return;
         }
+        let mut next = Some(Rc::clone(&node_ref.0));
+        while let Some(inner) = next {
+            if Rc::ptr_eq(&inner, &self.0) {
+                return;
+            }
+            next = inner.borrow().link.as_ref().map(|link| Rc::clone(&link.0));
+        }
 
         let mut this = self.0.borrow_mut();
         this.node = None;
@@ -162,4 +219,16 @@ mod tests {
         node_ref_2.link(node_ref);
         assert_eq!(node, node_ref_2.get().unwrap());
     }
+
+    #[test]
+    fn pointer_capture_requires_element_and_active_pointer() {
+        let node_ref = NodeRef::default();
+        assert!(!node_ref.set_pointer_capture(1));
+
+        node_ref.set(Some(document().create_element("div").unwrap().into()));
+        // No pointer is active in tests
+        assert!(!node_ref.set_pointer_capture(1));
+        assert!(!node_ref.has_pointer_capture(1));
+        assert!(node_ref.0.borrow().pointer_captures.is_empty());
+    }
 }
@@ -0,0 +1,326 @@
+//! Rendering part of the tree into the document of an iframe.
+//!
+//! [`IFramePortal`] renders an empty same-origin iframe and renders its children into the body
+//! of the iframe's document through a [portal](crate::create_portal). The children stay part of
+//! the tree of the component rendering the portal, but they are not styled by the stylesheets of
+//! the page, which makes the iframe useful for isolating styles or for printing part of a page.
+
+use crate::html::{Component, Context, Html, NodeRef};
+use crate::utils::{document, Environment};
+use crate::virtual_dom::AttrValue;
+use crate::{classes, create_portal, html, Callback, Children, Properties};
+use gloo::events::EventListener;
+use web_sys::{Document, Event, HtmlIFrameElement, Node};
+
+/// Content of the iframe before the children are rendered into it
+const SRCDOC: &str =
+    r#"<!DOCTYPE html><html><head><meta charset="utf-8"></head><body></body></html>"#;
+
+/// Selects the stylesheets copied from the page with [`IFramePortalProps::copy_styles`]
+const PAGE_STYLES: &str = r#"style, link[rel="stylesheet"]"#;
+
+/// Props for [`IFramePortal`]
+#[derive(Debug, Clone, PartialEq, Properties)]
+pub struct IFramePortalProps {
+    /// CSS rules added to the head of the iframe in `<style>` elements
+    #[prop_or_default]
+    pub styles: Vec<AttrValue>,
+    /// URLs of stylesheets added to the head of the iframe
+    #[prop_or_default]
+    pub stylesheets: Vec<AttrValue>,
+    /// Copies the `<style>` and `<link rel="stylesheet">` elements of the page into the iframe
+    /// when it is loaded
+    #[prop_or_default]
+    pub copy_styles: bool,
+    /// Types of events in the iframe passed to `onevent`, e.g. `"keydown"`. Events in the
+    /// iframe don't reach the listeners of the page, e.g. of global keyboard shortcuts.
+    #[prop_or_default]
+    pub forward: Vec<&'static str>,
+    /// Receives the events of the types in `forward`
+    #[prop_or_default]
+    pub onevent: Callback<Event>,
+    /// Called with the environment of the iframe once the children are rendered into it, e.g.
+    /// to print the iframe with `environment.window().print()`
+    #[prop_or_default]
+    pub onload: Callback<Environment>,
+    /// Title of the iframe, announced by screen readers
+    #[prop_or_default]
+    pub title: Option<String>,
+    /// Classes of the iframe
+    #[prop_or_default]
+    pub class: Option<String>,
+    /// Content rendered into the body of the iframe
+    #[prop_or_default]
+    pub children: Children,
+}
+
+#[doc(hidden)]
+pub enum IFramePortalMsg {
+    Loaded,
+    Forward(Event),
+}
+
+/// Renders its children into the document of an iframe.
+///
+/// The children are rendered once the iframe is loaded, and again into the new document if the
+/// iframe is reloaded, e.g. because it was moved in the page. Their listeners work as in the
+/// page, as do contexts and messages of their components.
+///
+/// # Example
+/// ```rust
+/// # use yew::prelude::*;
+/// use yew::iframe_portal::IFramePortal;
+/// use yew::utils::Environment;
+/// use yew::virtual_dom::AttrValue;
+///
+/// #[function_component(Receipt)]
+/// fn receipt() -> Html {
+///     let print = Callback::from(|environment: Environment| {
+///         let _ = environment.window().print();
+///     });
+///     let styles: Vec<AttrValue> = vec!["body { font: 12pt serif; }".into()];
+///     html! {
+///         <IFramePortal title="Receipt" {styles} onload={print}>
+///             <h1>{ "Receipt" }</h1>
+///         </IFramePortal>
+///     }
+/// }
+/// ```
+#[derive(Debug)]
+pub struct IFramePortal {
+    iframe: NodeRef,
+    document: Option<Document>,
+    listeners: Vec<EventListener>,
+    /// `onload` is called after the next render
+    loaded: bool,
+}
+
+impl IFramePortal {
+    /// Copies the stylesheets of the page into the head of `frame`
+    pub(crate) fn copy_styles(frame: &Document, head: &Node) {
+        let styles = match document().query_selector_all(PAGE_STYLES) {
+            Ok(styles) => styles,
+            Err(_) => return,
+        };
+        for i in 0..styles.length() {
+            if let Some(style) = styles.item(i) {
+                if let Ok(copy) = frame.import_node_with_deep(&style, true) {
+                    let _ = head.append_child(&copy);
+                }
+            }
+        }
+    }
+
+    fn listen(&mut self, ctx: &Context<Self>) {
+        self.listeners.clear();
+        let document = match &self.document {
+            Some(document) => document,
+            None => return,
+        };
+        self.listeners = ctx
+            .props()
+            .forward
+            .iter()
+            .map(|event| {
+                let link = ctx.link().clone();
+                EventListener::new(document, *event, move |event| {
+                    link.send_message(IFramePortalMsg::Forward(event.clone()))
+                })
+            })
+            .collect();
+    }
+}
+
+impl Component for IFramePortal {
+    type Message = IFramePortalMsg;
+    type Properties = IFramePortalProps;
+
+    fn create(_ctx: &Context<Self>) -> Self {
+        Self {
+            iframe: NodeRef::default(),
+            document: None,
+            listeners: Vec::new(),
+            loaded: false,
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            IFramePortalMsg::Loaded => {
+                let document = self
+                    .iframe
+                    .cast::<HtmlIFrameElement>()
+                    .and_then(|iframe| iframe.content_document());
+                if let (Some(document), true) = (&document, ctx.props().copy_styles) {
+                    if let Some(head) = document.head() {
+                        Self::copy_styles(document, &head);
+                    }
+                }
+                self.loaded = document.is_some();
+                self.document = document;
+                self.listen(ctx);
+                true
+            }
+            IFramePortalMsg::Forward(event) => {
+                ctx.props().onevent.emit(event);
+                false
+            }
+        }
+    }
+
+    fn changed(&mut self, ctx: &Context<Self>) -> bool {
+        self.listen(ctx);
+        true
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let props = ctx.props();
+        let content = self
+            .document
+            .as_ref()
+            .and_then(|document| Some((document.head()?, document.body()?)))
+            .map(|(head, body)| {
+                let styles = html! {
+                    <>
+                        { for props.styles.iter().map(|css| html! {
+                            <style>{ css.to_string() }</style>
+                        }) }
+                        { for props.stylesheets.iter().map(|href| html! {
+                            <link rel="stylesheet" href={href.clone()} />
+                        }) }
+                    </>
+                };
+                let children = html! { <>{ for props.children.iter() }</> };
+                html! {
+                    <>
+                        { create_portal(styles, head.into()) }
+                        { create_portal(children, body.into()) }
+                    </>
+                }
+            });
+        html! {
+            <>
+                <iframe
+                    ref={self.iframe.clone()}
+                    class={classes!("yew-iframe-portal", props.class.clone())}
+                    title={props.title.clone()}
+                    srcdoc={SRCDOC}
+                    onload={ctx.link().callback(|_| IFramePortalMsg::Loaded)}
+                />
+                { for content }
+            </>
+        }
+    }
+
+    fn rendered(&mut self, ctx: &Context<Self>, _first_render: bool) {
+        if let (true, Some(document)) = (self.loaded, &self.document) {
+            self.loaded = false;
+            ctx.props()
+                .onload
+                .emit(Environment::of_document(document.clone()));
+        }
+    }
+}
+
+#[cfg(all(test, feature = "wasm_test"))]
+mod tests {
+    extern crate self as yew;
+
+    use super::*;
+    use crate::context::ContextProvider;
+    use crate::AppBuilder;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+    use wasm_bindgen::{JsCast, JsValue};
+    use wasm_bindgen_futures::JsFuture;
+    use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    #[derive(Clone, PartialEq, Properties)]
+    struct Props {
+        onload: Callback<Environment>,
+        clicks: Callback<()>,
+    }
+
+    struct Framed;
+
+    impl Component for Framed {
+        type Message = ();
+        type Properties = Props;
+
+        fn create(_ctx: &Context<Self>) -> Self {
+            Self
+        }
+
+        fn view(&self, ctx: &Context<Self>) -> Html {
+            let props = ctx.props();
+            let styles: Vec<AttrValue> = vec!["b { color: red; }".into()];
+            html! {
+                <ContextProvider<&'static str> context="from the page">
+                    <IFramePortal {styles} onload={props.onload.clone()}>
+                        <Label onclick={props.clicks.clone()} />
+                    </IFramePortal>
+                </ContextProvider<&'static str>>
+            }
+        }
+    }
+
+    #[derive(Clone, PartialEq, Properties)]
+    struct LabelProps {
+        onclick: Callback<()>,
+    }
+
+    struct Label;
+
+    impl Component for Label {
+        type Message = ();
+        type Properties = LabelProps;
+
+        fn create(_ctx: &Context<Self>) -> Self {
+            Self
+        }
+
+        fn view(&self, ctx: &Context<Self>) -> Html {
+            let (text, _) = ctx
+                .link()
+                .context::<&'static str>(Callback::noop())
+                .unwrap();
+            let onclick = ctx.props().onclick.reform(|_| ());
+            html! { <b {onclick}>{ text }</b> }
+        }
+    }
+
+    #[test]
+    async fn children_are_rendered_into_the_iframe() {
+        let host = document().create_element("div").unwrap();
+        document().body().unwrap().append_child(&host).unwrap();
+
+        let environment = Rc::new(RefCell::new(None));
+        let clicks = Rc::new(RefCell::new(0));
+        let loaded = js_sys::Promise::new(&mut |resolve, _| {
+            let environment = Rc::clone(&environment);
+            let onload = Callback::from(move |env| {
+                *environment.borrow_mut() = Some(env);
+                resolve.call0(&JsValue::NULL).unwrap();
+            });
+            let clicks = Rc::clone(&clicks);
+            let props = Props {
+                onload,
+                clicks: Callback::from(move |_| *clicks.borrow_mut() += 1),
+            };
+            AppBuilder::<Framed>::new().mount(host.clone(), props);
+        });
+        JsFuture::from(loaded).await.unwrap();
+
+        let frame = environment.borrow().clone().unwrap().document();
+        assert_ne!(frame, document());
+        let label = frame.query_selector("body > b").unwrap().unwrap();
+        assert_eq!(label.text_content().as_deref(), Some("from the page"));
+        assert!(frame.query_selector("head > style").unwrap().is_some());
+
+        label.unchecked_into::<web_sys::HtmlElement>().click();
+        assert_eq!(*clicks.borrow(), 1);
+        host.remove();
+    }
+}
@@ -0,0 +1,322 @@
+//! This module contains the [`Image`] component, which shows an image once it is decoded.
+
+use crate::html::NodeRef;
+use crate::infinite_scroll::ReachEndObserver;
+use crate::{html, Callback, Classes, Component, Context, Html, Properties};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{Element, HtmlImageElement};
+
+/// Properties of [`Image`]
+#[derive(Debug, Clone, PartialEq, Properties)]
+pub struct ImageProps {
+    /// URL of the image
+    pub src: String,
+    /// Candidates of the image for different resolutions, see `<img srcset>`
+    #[prop_or_default]
+    pub srcset: Option<String>,
+    /// Sizes the image is shown at, see `<img sizes>`
+    #[prop_or_default]
+    pub sizes: Option<String>,
+    /// Alternative text
+    #[prop_or_default]
+    pub alt: String,
+    /// Width of the image and the placeholder
+    #[prop_or_default]
+    pub width: Option<u32>,
+    /// Height of the image and the placeholder
+    #[prop_or_default]
+    pub height: Option<u32>,
+    /// Classes of the image and the placeholder
+    #[prop_or_default]
+    pub class: Classes,
+    /// URL of a small version of the image, which is shown blurred until the image is decoded
+    #[prop_or_default]
+    pub thumbnail: Option<String>,
+    /// Shown until the image is decoded, if there is no `thumbnail`, and if the image fails to
+    /// load
+    #[prop_or_default]
+    pub placeholder: Html,
+    /// Loads the image only once the placeholder becomes visible
+    #[prop_or_default]
+    pub lazy: bool,
+    /// Called when the image was decoded
+    #[prop_or_default]
+    pub onload: Callback<()>,
+    /// Called when the image failed to load or decode
+    #[prop_or_default]
+    pub onerror: Callback<()>,
+}
+
+impl ImageProps {
+    fn source(&self) -> (String, Option<String>, Option<String>) {
+        (self.src.clone(), self.srcset.clone(), self.sizes.clone())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum State {
+    /// Waiting for the placeholder to become visible
+    Idle,
+    Loading,
+    Loaded,
+    Failed,
+}
+
+#[doc(hidden)]
+#[derive(Debug)]
+pub enum ImageMsg {
+    Visible,
+    Decoded(u32),
+    Failed(u32),
+}
+
+/// Shows an image once it was loaded and decoded, and a placeholder until then.
+///
+/// The image is loaded into an element outside of the document and decoded with
+/// `HtmlImageElement.decode()`, so it never shows up partially loaded and doesn't delay the
+/// next frames while it is decoded. With `lazy`, loading starts when the placeholder becomes
+/// visible.
+///
+/// Changing the source shows the placeholder again until the new image is decoded.
+///
+/// # Example
+/// ```rust
+/// # use yew::prelude::*;
+/// use yew::image::Image;
+///
+/// # fn view() -> Html {
+/// html! {
+///     <Image
+///         src="/photos/beach.jpg"
+///         srcset="/photos/beach-800.jpg 800w, /photos/beach-1600.jpg 1600w"
+///         sizes="(max-width: 800px) 100vw, 800px"
+///         thumbnail="/photos/beach-thumb.jpg"
+///         alt="A beach at sunset"
+///         width=800
+///         height=600
+///         lazy=true
+///         onerror={Callback::from(|_| log::warn!("failed to load the photo"))}
+///     />
+/// }
+/// # }
+/// ```
+pub struct Image {
+    state: State,
+    source: (String, Option<String>, Option<String>),
+    /// Incremented when the source changes, so results for earlier sources are discarded
+    generation: u32,
+    placeholder: NodeRef,
+    observer: Option<ReachEndObserver>,
+}
+
+impl Image {
+    fn load(&mut self, ctx: &Context<Self>) {
+        self.state = State::Loading;
+        self.observer = None;
+
+        let img = match HtmlImageElement::new() {
+            Ok(img) => img,
+            Err(_) => {
+                ctx.link().send_message(ImageMsg::Failed(self.generation));
+                return;
+            }
+        };
+        let (src, srcset, sizes) = &self.source;
+        // `sizes` and `srcset` are set first, so only the candidate in use is fetched
+        if let Some(sizes) = sizes {
+            img.set_sizes(sizes);
+        }
+        if let Some(srcset) = srcset {
+            img.set_srcset(srcset);
+        }
+        img.set_src(src);
+
+        let generation = self.generation;
+        ctx.link().send_future(async move {
+            match JsFuture::from(img.decode()).await {
+                Ok(_) => ImageMsg::Decoded(generation),
+                Err(_) => ImageMsg::Failed(generation),
+            }
+        });
+    }
+
+    fn size_style(props: &ImageProps) -> String {
+        let mut style = String::new();
+        if let Some(width) = props.width {
+            style.push_str(&format!("width: {}px;", width));
+        }
+        if let Some(height) = props.height {
+            style.push_str(&format!("height: {}px;", height));
+        }
+        style
+    }
+}
+
+impl Component for Image {
+    type Message = ImageMsg;
+    type Properties = ImageProps;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let mut image = Self {
+            state: State::Idle,
+            source: ctx.props().source(),
+            generation: 0,
+            placeholder: NodeRef::default(),
+            observer: None,
+        };
+        if !ctx.props().lazy {
+            image.load(ctx);
+        }
+        image
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            ImageMsg::Visible => {
+                if self.state == State::Idle {
+                    self.load(ctx);
+                }
+                false
+            }
+            ImageMsg::Decoded(generation) if generation == self.generation => {
+                self.state = State::Loaded;
+                ctx.props().onload.emit(());
+                true
+            }
+            ImageMsg::Failed(generation) if generation == self.generation => {
+                self.state = State::Failed;
+                ctx.props().onerror.emit(());
+                true
+            }
+            ImageMsg::Decoded(_) | ImageMsg::Failed(_) => false,
+        }
+    }
+
+    fn changed(&mut self, ctx: &Context<Self>) -> bool {
+        let source = ctx.props().source();
+        if source != self.source {
+            self.source = source;
+            self.generation += 1;
+            self.state = State::Idle;
+            if !ctx.props().lazy {
+                self.load(ctx);
+            }
+        }
+        true
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let props = ctx.props();
+        let width = props.width.map(|width| width.to_string());
+        let height = props.height.map(|height| height.to_string());
+
+        match (self.state, &props.thumbnail) {
+            (State::Loaded, _) => html! {
+                <img
+                    src={props.src.clone()}
+                    srcset={props.srcset.clone()}
+                    sizes={props.sizes.clone()}
+                    alt={props.alt.clone()}
+                    {width}
+                    {height}
+                    class={props.class.clone()}
+                />
+            },
+            (State::Idle, Some(thumbnail)) | (State::Loading, Some(thumbnail)) => html! {
+                <img
+                    ref={self.placeholder.clone()}
+                    src={thumbnail.clone()}
+                    alt={props.alt.clone()}
+                    {width}
+                    {height}
+                    class={props.class.clone()}
+                    style="filter: blur(8px)"
+                    aria-busy="true"
+                />
+            },
+            _ => html! {
+                <div
+                    ref={self.placeholder.clone()}
+                    class={props.class.clone()}
+                    style={Self::size_style(props)}
+                    role="img"
+                    aria-label={props.alt.clone()}
+                    aria-busy={(self.state != State::Failed).to_string()}
+                >
+                    { props.placeholder.clone() }
+                </div>
+            },
+        }
+    }
+
+    fn rendered(&mut self, ctx: &Context<Self>, _first_render: bool) {
+        if self.state != State::Idle {
+            return;
+        }
+        // The placeholder element changes between the thumbnail and the fallback
+        if let Some(el) = self.placeholder.cast::<Element>() {
+            let onvisible = ctx.link().callback(|_| ImageMsg::Visible);
+            self.observer = Some(ReachEndObserver::new(el, onvisible));
+        }
+    }
+}
+
+#[cfg(all(test, feature = "wasm_test"))]
+mod tests {
+    extern crate self as yew;
+
+    use super::*;
+    use crate::utils::document;
+    use std::cell::Cell;
+    use std::rc::Rc;
+    use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    async fn sleep(ms: i32) {
+        let promise = js_sys::Promise::new(&mut |resolve, _| {
+            crate::utils::window()
+                .set_timeout_with_callback_and_timeout_and_arguments_0(&resolve, ms)
+                .unwrap();
+        });
+        JsFuture::from(promise).await.unwrap();
+    }
+
+    #[test]
+    async fn shows_placeholder_when_loading_fails() {
+        let failed = Rc::new(Cell::new(false));
+        let onerror = {
+            let failed = Rc::clone(&failed);
+            Callback::from(move |_| failed.set(true))
+        };
+        let root = document().create_element("div").unwrap();
+        document().body().unwrap().append_child(&root).unwrap();
+        crate::start_app_with_props_in_element::<Image>(
+            root.clone(),
+            ImageProps {
+                src: "/missing-image.png".to_owned(),
+                srcset: None,
+                sizes: None,
+                alt: "missing".to_owned(),
+                width: Some(10),
+                height: Some(10),
+                class: Classes::new(),
+                thumbnail: None,
+                placeholder: html! { "loading" },
+                lazy: false,
+                onload: Callback::noop(),
+                onerror,
+            },
+        );
+        assert_eq!(root.text_content().as_deref(), Some("loading"));
+
+        for _ in 0..50 {
+            if failed.get() {
+                break;
+            }
+            sleep(20).await;
+        }
+        assert!(failed.get());
+        assert!(root.query_selector("img").unwrap().is_none());
+    }
+}
@@ -0,0 +1,579 @@
+//! A typed wrapper of IndexedDB.
+//!
+//! Values are converted with `serde` and requests are futures, so they can be passed to
+//! [`send_future`](crate::html::Scope::send_future) directly. Every request runs in a
+//! transaction of its own.
+//!
+//! # Example
+//! ```rust
+//! # use yew::prelude::*;
+//! use serde::{Deserialize, Serialize};
+//! use yew::indexed_db::{Database, IdbError, Store};
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Note {
+//!     id: u32,
+//!     tag: String,
+//!     text: String,
+//! }
+//!
+//! async fn notes_tagged(tag: &str) -> Result<Vec<Note>, IdbError> {
+//!     let db = Database::open("app", 1, |upgrade| {
+//!         if upgrade.old_version() < 1 {
+//!             let notes = upgrade.create_store("notes", Some("id"), false)?;
+//!             notes.create_index("by_tag", "tag", false)?;
+//!         }
+//!         Ok(())
+//!     })
+//!     .await?;
+//!     let notes: Store<Note> = db.store("notes");
+//!     notes.index("by_tag").get_all(tag).await
+//! }
+//! ```
+
+use crate::utils::window;
+use js_sys::{Promise, JSON};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::cell::RefCell;
+use std::fmt;
+use std::future::Future;
+use std::marker::PhantomData;
+use std::rc::Rc;
+use wasm_bindgen::closure::Closure;
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{
+    IdbCursorWithValue, IdbDatabase, IdbIndexParameters, IdbObjectStore, IdbObjectStoreParameters,
+    IdbRequest, IdbTransaction, IdbTransactionMode, IdbVersionChangeEvent,
+};
+
+/// Error of an IndexedDB operation
+#[derive(Debug, thiserror::Error)]
+pub enum IdbError {
+    /// IndexedDB is not available, e.g. in private windows of some browsers
+    #[error("IndexedDB is not available")]
+    Unavailable,
+    /// IndexedDB failed the request
+    #[error("IndexedDB error: {0:?}")]
+    Js(JsValue),
+    /// A value could not be converted
+    #[error("failed to convert value: {0}")]
+    Serde(#[from] serde_json::Error),
+}
+
+impl From<JsValue> for IdbError {
+    fn from(err: JsValue) -> Self {
+        Self::Js(err)
+    }
+}
+
+fn to_js<T: Serialize>(value: &T) -> Result<JsValue, IdbError> {
+    Ok(JSON::parse(&serde_json::to_string(value)?)?)
+}
+
+fn from_js<T: DeserializeOwned>(value: &JsValue) -> Result<T, IdbError> {
+    let json = String::from(JSON::stringify(value)?);
+    Ok(serde_json::from_str(&json)?)
+}
+
+/// Resolves to the result of `request`
+async fn await_request(request: IdbRequest) -> Result<JsValue, IdbError> {
+    let mut handlers = None;
+    let promise = Promise::new(&mut |resolve, reject| {
+        let onsuccess = {
+            let request = request.clone();
+            Closure::once(move || {
+                let result = request.result().unwrap_or(JsValue::UNDEFINED);
+                let _ = resolve.call1(&JsValue::UNDEFINED, &result);
+            })
+        };
+        let onerror = {
+            let request = request.clone();
+            Closure::once(move || {
+                let err = request
+                    .error()
+                    .ok()
+                    .flatten()
+                    .map_or(JsValue::UNDEFINED, JsValue::from);
+                let _ = reject.call1(&JsValue::UNDEFINED, &err);
+            })
+        };
+        request.set_onsuccess(Some(onsuccess.as_ref().unchecked_ref()));
+        request.set_onerror(Some(onerror.as_ref().unchecked_ref()));
+        handlers = Some((onsuccess, onerror));
+    });
+    let result = JsFuture::from(promise).await;
+    request.set_onsuccess(None);
+    request.set_onerror(None);
+    drop(handlers);
+    Ok(result?)
+}
+
+/// An open database
+#[derive(Debug, Clone)]
+pub struct Database {
+    db: IdbDatabase,
+}
+
+impl Database {
+    /// Opens the database `name` at `version`.
+    ///
+    /// If the database doesn't exist or has an older version, `upgrade` is called to create the
+    /// stores and indexes. Failing the upgrade aborts opening the database.
+    pub fn open(
+        name: &str,
+        version: u32,
+        upgrade: impl FnOnce(&Upgrade) -> Result<(), IdbError> + 'static,
+    ) -> impl Future<Output = Result<Self, IdbError>> + 'static {
+        let request = window()
+            .indexed_db()
+            .ok()
+            .flatten()
+            .ok_or(IdbError::Unavailable)
+            .and_then(|factory| Ok(factory.open_with_u32(name, version)?));
+        async move {
+            let request = request?;
+            let onupgradeneeded = {
+                let request = request.clone();
+                Closure::once(move |event: IdbVersionChangeEvent| {
+                    let upgrade = Upgrade {
+                        db: request.result().unwrap().unchecked_into(),
+                        transaction: request.transaction(),
+                        old_version: event.old_version() as u32,
+                    };
+                    if let Err(err) = upgrade(&upgrade) {
+                        log::error!("failed to upgrade IndexedDB database: {}", err);
+                        if let Some(transaction) = &upgrade.transaction {
+                            let _ = transaction.abort();
+                        }
+                    }
+                })
+            };
+            request.set_onupgradeneeded(Some(onupgradeneeded.as_ref().unchecked_ref()));
+            let result = await_request(request.clone().into()).await;
+            request.set_onupgradeneeded(None);
+            Ok(Self {
+                db: result?.unchecked_into(),
+            })
+        }
+    }
+
+    /// Returns the store `name`, whose values are of type `T`
+    pub fn store<T>(&self, name: &str) -> Store<T> {
+        Store {
+            db: self.db.clone(),
+            name: Rc::from(name),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Closes the database
+    pub fn close(&self) {
+        self.db.close();
+    }
+}
+
+/// Changes of the schema of a database, see [`Database::open`]
+#[derive(Debug)]
+pub struct Upgrade {
+    db: IdbDatabase,
+    transaction: Option<IdbTransaction>,
+    old_version: u32,
+}
+
+impl Upgrade {
+    /// Returns the version the database is upgraded from, `0` if it is created
+    pub fn old_version(&self) -> u32 {
+        self.old_version
+    }
+
+    /// Creates a store. Its values are keyed by the property at `key_path`, or by keys passed
+    /// to [`Store::put_with_key`] if it is `None`. With `auto_increment`, missing keys are
+    /// generated.
+    pub fn create_store(
+        &self,
+        name: &str,
+        key_path: Option<&str>,
+        auto_increment: bool,
+    ) -> Result<UpgradeStore, IdbError> {
+        let mut params = IdbObjectStoreParameters::new();
+        params.auto_increment(auto_increment);
+        if let Some(key_path) = key_path {
+            params.key_path(Some(&JsValue::from_str(key_path)));
+        }
+        Ok(UpgradeStore {
+            store: self
+                .db
+                .create_object_store_with_optional_parameters(name, &params)?,
+        })
+    }
+
+    /// Returns an existing store, e.g. to add indexes to it
+    pub fn store(&self, name: &str) -> Result<UpgradeStore, IdbError> {
+        let transaction = self.transaction.as_ref().ok_or(IdbError::Unavailable)?;
+        Ok(UpgradeStore {
+            store: transaction.object_store(name)?,
+        })
+    }
+
+    /// Deletes a store with all its values
+    pub fn delete_store(&self, name: &str) -> Result<(), IdbError> {
+        Ok(self.db.delete_object_store(name)?)
+    }
+}
+
+/// A store being created or changed in an [`Upgrade`]
+#[derive(Debug)]
+pub struct UpgradeStore {
+    store: IdbObjectStore,
+}
+
+impl UpgradeStore {
+    /// Creates an index of the values by the property at `key_path`
+    pub fn create_index(&self, name: &str, key_path: &str, unique: bool) -> Result<(), IdbError> {
+        let mut params = IdbIndexParameters::new();
+        params.unique(unique);
+        self.store
+            .create_index_with_str_and_optional_parameters(name, key_path, &params)?;
+        Ok(())
+    }
+
+    /// Deletes an index
+    pub fn delete_index(&self, name: &str) -> Result<(), IdbError> {
+        Ok(self.store.delete_index(name)?)
+    }
+}
+
+/// A store of values of type `T`.
+///
+/// Keys are anything that converts into a [`JsValue`], e.g. numbers and strings. Methods taking a
+/// `query` also accept an [`IdbKeyRange`](web_sys::IdbKeyRange).
+pub struct Store<T> {
+    db: IdbDatabase,
+    name: Rc<str>,
+    _marker: PhantomData<T>,
+}
+
+impl<T: Serialize + DeserializeOwned + 'static> Store<T> {
+    fn object_store(&self, mode: IdbTransactionMode) -> Result<IdbObjectStore, IdbError> {
+        Ok(self
+            .db
+            .transaction_with_str_and_mode(&self.name, mode)?
+            .object_store(&self.name)?)
+    }
+
+    fn request(
+        &self,
+        mode: IdbTransactionMode,
+        f: impl FnOnce(&IdbObjectStore) -> Result<IdbRequest, JsValue>,
+    ) -> impl Future<Output = Result<JsValue, IdbError>> + 'static {
+        let request = self.object_store(mode).and_then(|store| Ok(f(&store)?));
+        async move { await_request(request?).await }
+    }
+
+    /// Returns the value with `key`
+    pub fn get(
+        &self,
+        key: impl Into<JsValue>,
+    ) -> impl Future<Output = Result<Option<T>, IdbError>> + 'static {
+        let key = key.into();
+        let request = self.request(IdbTransactionMode::Readonly, |store| store.get(&key));
+        async move {
+            let value = request.await?;
+            if value.is_undefined() {
+                Ok(None)
+            } else {
+                from_js(&value).map(Some)
+            }
+        }
+    }
+
+    /// Returns all values, ordered by their keys
+    pub fn all(&self) -> impl Future<Output = Result<Vec<T>, IdbError>> + 'static {
+        let request = self.request(IdbTransactionMode::Readonly, |store| store.get_all());
+        async move { from_js(&request.await?) }
+    }
+
+    /// Returns the values matching `query`, ordered by their keys
+    pub fn get_all(
+        &self,
+        query: impl Into<JsValue>,
+    ) -> impl Future<Output = Result<Vec<T>, IdbError>> + 'static {
+        let query = query.into();
+        let request = self.request(IdbTransactionMode::Readonly, |store| {
+            store.get_all_with_key(&query)
+        });
+        async move { from_js(&request.await?) }
+    }
+
+    /// Inserts or replaces `value` in a store with a key path and resolves to its key
+    pub fn put(&self, value: &T) -> impl Future<Output = Result<JsValue, IdbError>> + 'static {
+        let request = to_js(value).map(|value| {
+            self.request(IdbTransactionMode::Readwrite, move |store| {
+                store.put(&value)
+            })
+        });
+        async move { request?.await }
+    }
+
+    /// Inserts or replaces `value` with `key` in a store without key path
+    pub fn put_with_key(
+        &self,
+        value: &T,
+        key: impl Into<JsValue>,
+    ) -> impl Future<Output = Result<(), IdbError>> + 'static {
+        let key = key.into();
+        let request = to_js(value).map(|value| {
+            self.request(IdbTransactionMode::Readwrite, move |store| {
+                store.put_with_key(&value, &key)
+            })
+        });
+        async move { request?.await.map(drop) }
+    }
+
+    /// Deletes the values matching `query`
+    pub fn delete(
+        &self,
+        query: impl Into<JsValue>,
+    ) -> impl Future<Output = Result<(), IdbError>> + 'static {
+        let query = query.into();
+        let request = self.request(IdbTransactionMode::Readwrite, |store| store.delete(&query));
+        async move { request.await.map(drop) }
+    }
+
+    /// Deletes all values
+    pub fn clear(&self) -> impl Future<Output = Result<(), IdbError>> + 'static {
+        let request = self.request(IdbTransactionMode::Readwrite, |store| store.clear());
+        async move { request.await.map(drop) }
+    }
+
+    /// Returns the number of values
+    pub fn count(&self) -> impl Future<Output = Result<u32, IdbError>> + 'static {
+        let request = self.request(IdbTransactionMode::Readonly, |store| store.count());
+        async move { Ok(request.await?.as_f64().unwrap_or_default() as u32) }
+    }
+
+    /// Calls `f` with every value, ordered by their keys, until it returns `false`.
+    ///
+    /// Unlike [`all`](Self::all), the values are not loaded at once.
+    pub fn for_each(
+        &self,
+        f: impl FnMut(T) -> bool + 'static,
+    ) -> impl Future<Output = Result<(), IdbError>> + 'static {
+        let request = self
+            .object_store(IdbTransactionMode::Readonly)
+            .and_then(|store| Ok(store.open_cursor()?));
+        async move {
+            let request = request?;
+            let error: Rc<RefCell<Option<IdbError>>> = Default::default();
+            let mut f = Some(f);
+            let mut handlers = None;
+            let promise = Promise::new(&mut |resolve, reject| {
+                // Called for every value, as the cursor advances
+                let onsuccess = {
+                    let request = request.clone();
+                    let error = Rc::clone(&error);
+                    let mut f = f.take().unwrap();
+                    Closure::wrap(Box::new(move || {
+                        let cursor = request
+                            .result()
+                            .ok()
+                            .and_then(|cursor| cursor.dyn_into::<IdbCursorWithValue>().ok());
+                        let next = cursor.map(|cursor| -> Result<bool, IdbError> {
+                            let value = from_js(&cursor.value()?)?;
+                            if f(value) {
+                                cursor.continue_()?;
+                                Ok(true)
+                            } else {
+                                Ok(false)
+                            }
+                        });
+                        match next {
+                            Some(Ok(true)) => {}
+                            Some(Err(err)) => {
+                                *error.borrow_mut() = Some(err);
+                                let _ = resolve.call0(&JsValue::UNDEFINED);
+                            }
+                            _ => {
+                                let _ = resolve.call0(&JsValue::UNDEFINED);
+                            }
+                        }
+                    }) as Box<dyn FnMut()>)
+                };
+                let onerror = {
+                    let request = request.clone();
+                    Closure::once(move || {
+                        let err = request
+                            .error()
+                            .ok()
+                            .flatten()
+                            .map_or(JsValue::UNDEFINED, JsValue::from);
+                        let _ = reject.call1(&JsValue::UNDEFINED, &err);
+                    })
+                };
+                request.set_onsuccess(Some(onsuccess.as_ref().unchecked_ref()));
+                request.set_onerror(Some(onerror.as_ref().unchecked_ref()));
+                handlers = Some((onsuccess, onerror));
+            });
+            let result = JsFuture::from(promise).await;
+            request.set_onsuccess(None);
+            request.set_onerror(None);
+            drop(handlers);
+            result?;
+            match error.borrow_mut().take() {
+                Some(err) => Err(err),
+                None => Ok(()),
+            }
+        }
+    }
+
+    /// Returns the index `name` of the store
+    pub fn index(&self, name: &str) -> Index<T> {
+        Index {
+            store: self.clone(),
+            name: Rc::from(name),
+        }
+    }
+}
+
+impl<T> Clone for Store<T> {
+    fn clone(&self) -> Self {
+        Self {
+            db: self.db.clone(),
+            name: Rc::clone(&self.name),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T> fmt::Debug for Store<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Store").field("name", &self.name).finish()
+    }
+}
+
+/// An index of a [`Store`]
+pub struct Index<T> {
+    store: Store<T>,
+    name: Rc<str>,
+}
+
+impl<T: Serialize + DeserializeOwned + 'static> Index<T> {
+    fn request(
+        &self,
+        f: impl FnOnce(&web_sys::IdbIndex) -> Result<IdbRequest, JsValue>,
+    ) -> impl Future<Output = Result<JsValue, IdbError>> + 'static {
+        let name = Rc::clone(&self.name);
+        self.store
+            .request(IdbTransactionMode::Readonly, move |store| {
+                f(&store.index(&name)?)
+            })
+    }
+
+    /// Returns the first value whose indexed property matches `query`
+    pub fn get(
+        &self,
+        query: impl Into<JsValue>,
+    ) -> impl Future<Output = Result<Option<T>, IdbError>> + 'static {
+        let query = query.into();
+        let request = self.request(|index| index.get(&query));
+        async move {
+            let value = request.await?;
+            if value.is_undefined() {
+                Ok(None)
+            } else {
+                from_js(&value).map(Some)
+            }
+        }
+    }
+
+    /// Returns the values whose indexed property matches `query`
+    pub fn get_all(
+        &self,
+        query: impl Into<JsValue>,
+    ) -> impl Future<Output = Result<Vec<T>, IdbError>> + 'static {
+        let query = query.into();
+        let request = self.request(|index| index.get_all_with_key(&query));
+        async move { from_js(&request.await?) }
+    }
+
+    /// Returns the number of values whose indexed property matches `query`
+    pub fn count(
+        &self,
+        query: impl Into<JsValue>,
+    ) -> impl Future<Output = Result<u32, IdbError>> + 'static {
+        let query = query.into();
+        let request = self.request(|index| index.count_with_key(&query));
+        async move { Ok(request.await?.as_f64().unwrap_or_default() as u32) }
+    }
+}
+
+impl<T> fmt::Debug for Index<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Index")
+            .field("store", &self.store.name)
+            .field("name", &self.name)
+            .finish()
+    }
+}
+
+#[cfg(all(test, feature = "wasm_test"))]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+    use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    struct Note {
+        id: u32,
+        tag: String,
+    }
+
+    #[test]
+    async fn stores_and_queries_values() {
+        let name = format!("yew-test-{}", js_sys::Math::random());
+        let db = Database::open(&name, 1, |upgrade| {
+            assert_eq!(upgrade.old_version(), 0);
+            let notes = upgrade.create_store("notes", Some("id"), false)?;
+            notes.create_index("by_tag", "tag", false)
+        })
+        .await
+        .unwrap();
+        let notes: Store<Note> = db.store("notes");
+
+        for (id, tag) in [(1, "a"), (2, "b"), (3, "a")].iter() {
+            let note = Note {
+                id: *id,
+                tag: tag.to_string(),
+            };
+            notes.put(&note).await.unwrap();
+        }
+
+        assert_eq!(notes.get(2).await.unwrap().unwrap().tag, "b");
+        assert_eq!(notes.get(4).await.unwrap(), None);
+        assert_eq!(notes.count().await.unwrap(), 3);
+
+        let tagged = notes.index("by_tag").get_all("a").await.unwrap();
+        assert_eq!(tagged.iter().map(|n| n.id).collect::<Vec<_>>(), vec![1, 3]);
+
+        let seen = Rc::new(RefCell::new(Vec::new()));
+        {
+            let seen = Rc::clone(&seen);
+            notes
+                .for_each(move |note| {
+                    seen.borrow_mut().push(note.id);
+                    note.id < 2
+                })
+                .await
+                .unwrap();
+        }
+        assert_eq!(*seen.borrow(), vec![1, 2]);
+
+        notes.delete(1).await.unwrap();
+        assert_eq!(notes.all().await.unwrap().len(), 2);
+        db.close();
+    }
+}
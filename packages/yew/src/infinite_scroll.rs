@@ -0,0 +1,284 @@
+//! Loading more items when the end of a list scrolls into view.
+//!
+//! Both [`use_infinite_scroll`] and [`InfiniteScroll`] watch a sentinel element at the end of a
+//! list with an `IntersectionObserver` and call a callback once when it becomes visible. They
+//! are re-armed after the next render in which they are enabled, which is usually the render
+//! showing the loaded items. If the sentinel is still visible then, e.g. because the items don't
+//! fill the container yet, the callback is called again.
+
+use crate::functional::{use_effect, use_ref};
+use crate::html::NodeRef;
+use crate::{html, Callback, Children, Component, Context, Html, Properties};
+use js_sys::Array;
+use std::cell::RefCell;
+use std::rc::Rc;
+use wasm_bindgen::closure::Closure;
+use wasm_bindgen::JsCast;
+use web_sys::{Element, IntersectionObserver, IntersectionObserverEntry};
+
+/// State shared with the callback of the observer
+struct Shared {
+    onreachend: Callback<()>,
+    enabled: bool,
+    /// The sentinel was reported visible since the observer was last armed
+    fired: bool,
+}
+
+impl Shared {
+    /// Handles a report of the observer and returns whether the callback should be called
+    fn report(&mut self, intersecting: bool) -> bool {
+        if !intersecting || self.fired {
+            return false;
+        }
+        // Visibility is reported on changes only, so a report while disabled is kept until the
+        // observer is re-armed
+        self.fired = true;
+        self.enabled
+    }
+}
+
+/// Calls a callback when a sentinel element becomes visible.
+///
+/// The observer is disconnected when it is dropped.
+pub struct ReachEndObserver {
+    observer: IntersectionObserver,
+    sentinel: Element,
+    shared: Rc<RefCell<Shared>>,
+    _closure: Closure<dyn FnMut(Array)>,
+}
+
+impl ReachEndObserver {
+    /// Starts observing `sentinel`
+    pub fn new(sentinel: Element, onreachend: Callback<()>) -> Self {
+        let shared = Rc::new(RefCell::new(Shared {
+            onreachend,
+            enabled: true,
+            fired: false,
+        }));
+        let closure = {
+            let shared = Rc::clone(&shared);
+            Closure::wrap(Box::new(move |entries: Array| {
+                let intersecting = entries.iter().any(|entry| {
+                    entry
+                        .unchecked_into::<IntersectionObserverEntry>()
+                        .is_intersecting()
+                });
+                let emit = shared.borrow_mut().report(intersecting);
+                if emit {
+                    // The callback may drop or update the observer
+                    let onreachend = shared.borrow().onreachend.clone();
+                    onreachend.emit(());
+                }
+            }) as Box<dyn FnMut(Array)>)
+        };
+        let observer = IntersectionObserver::new(closure.as_ref().unchecked_ref()).unwrap();
+        observer.observe(&sentinel);
+        Self {
+            observer,
+            sentinel,
+            shared,
+            _closure: closure,
+        }
+    }
+
+    /// Sets the callback
+    pub fn set_onreachend(&self, onreachend: Callback<()>) {
+        self.shared.borrow_mut().onreachend = onreachend;
+    }
+
+    /// Enables or disables the callback, e.g. while items are loading or when there are no more
+    /// items to load. Enabling re-arms the observer, if it fired.
+    pub fn set_enabled(&self, enabled: bool) {
+        let mut shared = self.shared.borrow_mut();
+        shared.enabled = enabled;
+        if enabled && shared.fired {
+            shared.fired = false;
+            // Observing again reports the current visibility
+            self.observer.unobserve(&self.sentinel);
+            self.observer.observe(&self.sentinel);
+        }
+    }
+}
+
+impl Drop for ReachEndObserver {
+    fn drop(&mut self) {
+        self.observer.disconnect();
+    }
+}
+
+impl std::fmt::Debug for ReachEndObserver {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("ReachEndObserver")
+    }
+}
+
+/// Calls `onreachend` when the element referenced by the returned [`NodeRef`] becomes visible.
+///
+/// Pass `enabled: false` while items are loading or when all items are loaded. Otherwise the
+/// observer is re-armed after every render, see the [module docs](self).
+///
+/// # Example
+/// ```rust
+/// # use yew::prelude::*;
+/// use yew::infinite_scroll::use_infinite_scroll;
+///
+/// #[function_component(Feed)]
+/// fn feed() -> Html {
+///     let items = use_state(|| 20);
+///     let onreachend = {
+///         let items = items.clone();
+///         Callback::from(move |_| items.set(*items + 20))
+///     };
+///     let sentinel = use_infinite_scroll(onreachend, *items < 200);
+///
+///     html! {
+///         <ul>
+///             { for (0..*items).map(|i| html! { <li>{ i }</li> }) }
+///             <li ref={sentinel} />
+///         </ul>
+///     }
+/// }
+/// ```
+pub fn use_infinite_scroll(onreachend: Callback<()>, enabled: bool) -> NodeRef {
+    let sentinel = use_ref(NodeRef::default);
+    let observer = use_ref(|| None::<ReachEndObserver>);
+
+    let node_ref = sentinel.borrow().clone();
+    let sentinel = node_ref.clone();
+    use_effect(move || {
+        let mut observer = observer.borrow_mut();
+        match &*observer {
+            Some(observer) => {
+                observer.set_onreachend(onreachend);
+                observer.set_enabled(enabled);
+            }
+            None => {
+                if let Some(el) = sentinel.cast::<Element>() {
+                    let new = ReachEndObserver::new(el, onreachend);
+                    new.set_enabled(enabled);
+                    *observer = Some(new);
+                }
+            }
+        }
+        || ()
+    });
+
+    node_ref
+}
+
+/// Props for [`InfiniteScroll`]
+#[derive(Debug, Clone, PartialEq, Properties)]
+pub struct InfiniteScrollProps {
+    /// Called when the end of the children becomes visible
+    pub onreachend: Callback<()>,
+    /// Whether `onreachend` is called. Set it to `false` while items are loading or when all
+    /// items are loaded.
+    #[prop_or(true)]
+    pub enabled: bool,
+    /// Items of the list
+    #[prop_or_default]
+    pub children: Children,
+}
+
+/// Renders its children followed by a sentinel and calls `onreachend` when the sentinel becomes
+/// visible.
+///
+/// See the [module docs](self) for when the callback is called.
+///
+/// # Example
+/// ```rust
+/// # use yew::prelude::*;
+/// use yew::infinite_scroll::InfiniteScroll;
+///
+/// # struct Msg;
+/// # struct Feed { items: Vec<String>, loading: bool }
+/// # impl Component for Feed {
+/// #     type Message = Msg;
+/// #     type Properties = ();
+/// #     fn create(_ctx: &Context<Self>) -> Self { unimplemented!() }
+/// fn view(&self, ctx: &Context<Self>) -> Html {
+///     html! {
+///         <div style="height: 400px; overflow: auto">
+///             <InfiniteScroll
+///                 onreachend={ctx.link().callback(|_| Msg)}
+///                 enabled={!self.loading}
+///             >
+///                 { for self.items.iter().map(|item| html! { <p>{ item }</p> }) }
+///             </InfiniteScroll>
+///         </div>
+///     }
+/// }
+/// # }
+/// ```
+pub struct InfiniteScroll {
+    sentinel: NodeRef,
+    observer: Option<ReachEndObserver>,
+}
+
+impl Component for InfiniteScroll {
+    type Message = ();
+    type Properties = InfiniteScrollProps;
+
+    fn create(_ctx: &Context<Self>) -> Self {
+        Self {
+            sentinel: NodeRef::default(),
+            observer: None,
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        html! {
+            <>
+                { for ctx.props().children.iter() }
+                <div ref={self.sentinel.clone()} aria-hidden="true" />
+            </>
+        }
+    }
+
+    fn rendered(&mut self, ctx: &Context<Self>, _first_render: bool) {
+        let props = ctx.props();
+        match &self.observer {
+            Some(observer) => {
+                observer.set_onreachend(props.onreachend.clone());
+                observer.set_enabled(props.enabled);
+            }
+            None => {
+                if let Some(el) = self.sentinel.cast::<Element>() {
+                    let observer = ReachEndObserver::new(el, props.onreachend.clone());
+                    observer.set_enabled(props.enabled);
+                    self.observer = Some(observer);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "wasm_test")]
+    use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+
+    #[cfg(feature = "wasm_test")]
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    #[test]
+    fn reports_once_until_rearmed() {
+        let mut shared = Shared {
+            onreachend: Callback::from(|_| ()),
+            enabled: true,
+            fired: false,
+        };
+
+        assert!(!shared.report(false));
+        assert!(shared.report(true));
+        assert!(!shared.report(true));
+
+        // Re-armed while disabled, e.g. while loading
+        shared.fired = false;
+        shared.enabled = false;
+        assert!(!shared.report(true));
+        assert!(shared.fired);
+    }
+}
@@ -0,0 +1,450 @@
+//! Polling of gamepads and the keyboard for games.
+//!
+//! Only available with the `input` feature. While there are subscriptions, Yew polls the
+//! [Gamepad API](https://developer.mozilla.org/en-US/docs/Web/API/Gamepad_API) at every animation
+//! frame and passes the changes since the previous frame, together with the keys pressed and
+//! released in between, to the subscribers. Components subscribe with
+//! [`Scope::subscribe_input`] and receive the changes as messages, in one batch per frame.
+//!
+//! ```rust
+//! # use yew::prelude::*;
+//! use yew::input::{InputChange, InputSubscription};
+//!
+//! enum Msg {
+//!     Jump,
+//!     Move(f64),
+//! }
+//!
+//! struct Player {
+//!     x: f64,
+//!     _input: InputSubscription,
+//! }
+//!
+//! impl Component for Player {
+//!     type Message = Msg;
+//!     type Properties = ();
+//!
+//!     fn create(ctx: &Context<Self>) -> Self {
+//!         let input = ctx.link().subscribe_input(|change| match change {
+//!             InputChange::Button { button: 0, pressed: true, .. } => Some(Msg::Jump),
+//!             InputChange::Key { code, pressed: true } if code == "Space" => Some(Msg::Jump),
+//!             InputChange::Axis { axis: 0, value, .. } => Some(Msg::Move(value)),
+//!             _ => None,
+//!         });
+//!         Self { x: 0.0, _input: input }
+//!     }
+//!
+//!     fn update(&mut self, _ctx: &Context<Self>, msg: Msg) -> bool {
+//!         match msg {
+//!             Msg::Jump => false,
+//!             Msg::Move(speed) => {
+//!                 self.x += speed;
+//!                 true
+//!             }
+//!         }
+//!     }
+//!
+//!     fn view(&self, _ctx: &Context<Self>) -> Html {
+//!         html! { <div class="player" style={format!("left: {}px", self.x)} /> }
+//!     }
+//! }
+//! ```
+
+use crate::html::{Component, Scope};
+use crate::utils::window;
+use crate::Callback;
+use gloo::events::EventListener;
+use slab::Slab;
+use std::cell::RefCell;
+use std::collections::HashSet;
+use wasm_bindgen::closure::Closure;
+use wasm_bindgen::JsCast;
+use web_sys::{Gamepad, GamepadButton, KeyboardEvent};
+
+/// Changes of axes smaller than this are not reported, as the sticks of gamepads are noisy
+const AXIS_THRESHOLD: f64 = 0.01;
+
+/// A button of a gamepad
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ButtonState {
+    /// Whether the button is pressed
+    pub pressed: bool,
+    /// How far the button is pressed, from `0.0` to `1.0` for analog buttons like triggers
+    pub value: f64,
+}
+
+/// The state of a gamepad at a frame
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GamepadState {
+    /// Index of the gamepad, which stays the same while it is connected
+    pub index: u32,
+    /// Identifies the model of the gamepad
+    pub id: String,
+    /// The buttons, in the order of the
+    /// [standard mapping](https://w3c.github.io/gamepad/#remapping) if the browser knows the model
+    pub buttons: Vec<ButtonState>,
+    /// The axes, from `-1.0` to `1.0`
+    pub axes: Vec<f64>,
+}
+
+impl GamepadState {
+    fn from_gamepad(gamepad: &Gamepad) -> Self {
+        let buttons = gamepad
+            .buttons()
+            .iter()
+            .map(|button| {
+                let button: GamepadButton = button.unchecked_into();
+                ButtonState {
+                    pressed: button.pressed(),
+                    value: button.value(),
+                }
+            })
+            .collect();
+        let axes = gamepad
+            .axes()
+            .iter()
+            .map(|axis| axis.as_f64().unwrap_or_default())
+            .collect();
+        Self {
+            index: gamepad.index(),
+            id: gamepad.id(),
+            buttons,
+            axes,
+        }
+    }
+
+    /// Appends the changes from `old` to `self` to `changes`
+    fn diff(&self, old: &Self, changes: &mut Vec<InputChange>) {
+        let gamepad = self.index;
+        for (button, (new, old)) in self.buttons.iter().zip(&old.buttons).enumerate() {
+            if new != old {
+                changes.push(InputChange::Button {
+                    gamepad,
+                    button,
+                    pressed: new.pressed,
+                    value: new.value,
+                });
+            }
+        }
+        for (axis, (new, old)) in self.axes.iter().zip(&old.axes).enumerate() {
+            if (new - old).abs() >= AXIS_THRESHOLD || (*new == 0.0 && *old != 0.0) {
+                changes.push(InputChange::Axis {
+                    gamepad,
+                    axis,
+                    value: *new,
+                });
+            }
+        }
+    }
+}
+
+/// A change of the input since the previous frame
+#[derive(Debug, Clone, PartialEq)]
+pub enum InputChange {
+    /// A gamepad was connected
+    GamepadConnected(GamepadState),
+    /// The gamepad with the index was disconnected
+    GamepadDisconnected(u32),
+    /// A button of a gamepad was pressed, released, or pressed further
+    Button {
+        /// Index of the gamepad
+        gamepad: u32,
+        /// Index of the button
+        button: usize,
+        /// Whether the button is pressed
+        pressed: bool,
+        /// How far the button is pressed
+        value: f64,
+    },
+    /// An axis of a gamepad moved
+    Axis {
+        /// Index of the gamepad
+        gamepad: u32,
+        /// Index of the axis
+        axis: usize,
+        /// Position of the axis
+        value: f64,
+    },
+    /// A key was pressed or released. Repeated `keydown` events are not reported.
+    Key {
+        /// [Code](https://developer.mozilla.org/en-US/docs/Web/API/KeyboardEvent/code) of the
+        /// physical key, e.g. `"KeyW"`, which doesn't depend on the keyboard layout
+        code: String,
+        /// Whether the key is pressed
+        pressed: bool,
+    },
+}
+
+/// State of the polling loop, which exists while there are subscribers
+struct InputLoop {
+    subscribers: Slab<Callback<Vec<InputChange>>>,
+    gamepads: Vec<GamepadState>,
+    keys: HashSet<String>,
+    /// Key changes since the last frame
+    pending: Vec<InputChange>,
+    frame: Option<i32>,
+    _listeners: [EventListener; 3],
+}
+
+thread_local! {
+    static LOOP: RefCell<Option<InputLoop>> = RefCell::new(None);
+    static ON_FRAME: Closure<dyn FnMut(f64)> =
+        Closure::wrap(Box::new(|_| poll()) as Box<dyn FnMut(f64)>);
+}
+
+impl InputLoop {
+    fn new() -> Self {
+        let on_key = |pressed: bool| {
+            move |event: &web_sys::Event| {
+                let event = match event.dyn_ref::<KeyboardEvent>() {
+                    Some(event) if !event.repeat() => event,
+                    _ => return,
+                };
+                let code = event.code();
+                with_loop(|input| {
+                    let changed = if pressed {
+                        input.keys.insert(code.clone())
+                    } else {
+                        input.keys.remove(&code)
+                    };
+                    if changed {
+                        input.pending.push(InputChange::Key { code, pressed });
+                    }
+                });
+            }
+        };
+        // Keys released while the window isn't focused don't fire `keyup`
+        let on_blur = |_: &web_sys::Event| {
+            with_loop(|input| {
+                let released = input.keys.drain().map(|code| InputChange::Key {
+                    code,
+                    pressed: false,
+                });
+                input.pending.extend(released);
+            });
+        };
+
+        let window = window();
+        Self {
+            subscribers: Slab::new(),
+            gamepads: Vec::new(),
+            keys: HashSet::new(),
+            pending: Vec::new(),
+            frame: None,
+            _listeners: [
+                EventListener::new(&window, "keydown", on_key(true)),
+                EventListener::new(&window, "keyup", on_key(false)),
+                EventListener::new(&window, "blur", on_blur),
+            ],
+        }
+    }
+
+    fn request_frame(&mut self) {
+        if self.frame.is_none() {
+            self.frame = ON_FRAME.with(|on_frame| {
+                window()
+                    .request_animation_frame(on_frame.as_ref().unchecked_ref())
+                    .ok()
+            });
+        }
+    }
+
+    /// Polls the gamepads and returns the changes since the last frame
+    fn changes(&mut self) -> Vec<InputChange> {
+        let mut changes = std::mem::take(&mut self.pending);
+        let connected: Vec<GamepadState> = window()
+            .navigator()
+            .get_gamepads()
+            .map(|gamepads| {
+                gamepads
+                    .iter()
+                    .filter_map(|gamepad| gamepad.dyn_into::<Gamepad>().ok())
+                    .filter(Gamepad::connected)
+                    .map(|gamepad| GamepadState::from_gamepad(&gamepad))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        for old in &self.gamepads {
+            if !connected.iter().any(|new| new.index == old.index) {
+                changes.push(InputChange::GamepadDisconnected(old.index));
+            }
+        }
+        for new in &connected {
+            match self.gamepads.iter().find(|old| old.index == new.index) {
+                Some(old) => new.diff(old, &mut changes),
+                None => changes.push(InputChange::GamepadConnected(new.clone())),
+            }
+        }
+        self.gamepads = connected;
+        changes
+    }
+}
+
+impl Drop for InputLoop {
+    fn drop(&mut self) {
+        if let Some(frame) = self.frame {
+            let _ = window().cancel_animation_frame(frame);
+        }
+    }
+}
+
+fn with_loop(f: impl FnOnce(&mut InputLoop)) {
+    LOOP.with(|input| {
+        if let Some(input) = input.borrow_mut().as_mut() {
+            f(input);
+        }
+    })
+}
+
+/// Runs at every animation frame while there are subscribers
+fn poll() {
+    let mut batch = None;
+    with_loop(|input| {
+        input.frame = None;
+        let changes = input.changes();
+        if !changes.is_empty() {
+            let subscribers: Vec<_> = input.subscribers.iter().map(|(_, s)| s.clone()).collect();
+            batch = Some((changes, subscribers));
+        }
+        input.request_frame();
+    });
+
+    // Subscribers may unsubscribe when they are notified
+    if let Some((changes, subscribers)) = batch {
+        for subscriber in subscribers {
+            subscriber.emit(changes.clone());
+        }
+    }
+}
+
+/// Keeps a subscription to the input alive. The polling stops when the last subscription is
+/// dropped.
+#[derive(Debug)]
+pub struct InputSubscription {
+    key: usize,
+}
+
+impl Drop for InputSubscription {
+    fn drop(&mut self) {
+        let stopped = LOOP.with(|input| {
+            let mut input = input.borrow_mut();
+            let subscribers = match input.as_mut() {
+                Some(running) => {
+                    running.subscribers.remove(self.key);
+                    running.subscribers.len()
+                }
+                None => return None,
+            };
+            if subscribers == 0 {
+                input.take()
+            } else {
+                None
+            }
+        });
+        // Dropped outside of the borrow, as dropping the listeners may call into JS
+        drop(stopped);
+    }
+}
+
+/// Calls `callback` with the changes of the input at every animation frame with changes.
+pub fn subscribe(callback: Callback<Vec<InputChange>>) -> InputSubscription {
+    LOOP.with(|input| {
+        let mut input = input.borrow_mut();
+        let running = input.get_or_insert_with(InputLoop::new);
+        let key = running.subscribers.insert(callback);
+        running.request_frame();
+        InputSubscription { key }
+    })
+}
+
+/// Returns the gamepads connected at the last polled frame. Empty while there are no
+/// subscriptions.
+pub fn gamepads() -> Vec<GamepadState> {
+    LOOP.with(|input| {
+        input
+            .borrow()
+            .as_ref()
+            .map(|input| input.gamepads.clone())
+            .unwrap_or_default()
+    })
+}
+
+/// Returns whether the key with `code`, e.g. `"KeyW"`, is pressed. Always `false` while there
+/// are no subscriptions.
+pub fn is_key_pressed(code: &str) -> bool {
+    LOOP.with(|input| {
+        input
+            .borrow()
+            .as_ref()
+            .map_or(false, |input| input.keys.contains(code))
+    })
+}
+
+impl<COMP: Component> Scope<COMP> {
+    /// Subscribes to the changes of gamepads and the keyboard, see the
+    /// [`input`](crate::input) module. `f` maps the changes of a frame to the messages sent to
+    /// the component in one batch.
+    ///
+    /// Only available with the `input` feature. The component stays subscribed while the
+    /// returned [`InputSubscription`] is kept.
+    pub fn subscribe_input<F>(&self, f: F) -> InputSubscription
+    where
+        F: Fn(InputChange) -> Option<COMP::Message> + 'static,
+    {
+        let scope = self.clone();
+        subscribe(Callback::from(move |changes: Vec<InputChange>| {
+            scope.send_message_batch(changes.into_iter().filter_map(&f).collect());
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "wasm_test")]
+    use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+
+    #[cfg(feature = "wasm_test")]
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    fn pad(buttons: &[bool], axes: &[f64]) -> GamepadState {
+        GamepadState {
+            index: 1,
+            id: "pad".to_owned(),
+            buttons: buttons
+                .iter()
+                .map(|&pressed| ButtonState {
+                    pressed,
+                    value: if pressed { 1.0 } else { 0.0 },
+                })
+                .collect(),
+            axes: axes.to_vec(),
+        }
+    }
+
+    #[test]
+    fn diff_reports_changed_buttons_and_axes() {
+        let old = pad(&[false, false], &[0.0, 0.5]);
+        let new = pad(&[false, true], &[0.005, 0.0]);
+        let mut changes = Vec::new();
+        new.diff(&old, &mut changes);
+        assert_eq!(
+            changes,
+            vec![
+                InputChange::Button {
+                    gamepad: 1,
+                    button: 1,
+                    pressed: true,
+                    value: 1.0,
+                },
+                InputChange::Axis {
+                    gamepad: 1,
+                    axis: 1,
+                    value: 0.0,
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,255 @@
+//! Locale-aware formatting of numbers, sizes and times in text.
+//!
+//! The adapters [`Localized`], [`Bytes`] and [`RelativeTime`] format their value with the `Intl`
+//! APIs of the browser. They convert into [`Html`] that renders the value in the [`Locale`]
+//! provided by the closest `ContextProvider<Locale>` and renders again when it changes. Without
+//! provider, the language of the browser is used.
+//!
+//! The adapters intentionally don't implement [`Display`](std::fmt::Display), which would turn
+//! them into plain text that ignores the provided locale. [`LocaleFormat::format`] formats them
+//! to a string.
+//!
+//! # Example
+//! ```rust
+//! # use yew::prelude::*;
+//! use yew::intl::{Bytes, Locale, Localized};
+//!
+//! #[function_component(Usage)]
+//! fn usage() -> Html {
+//!     html! {
+//!         <ContextProvider<Locale> context={Locale::new("de-DE")}>
+//!             <p>{ Localized(1234.5) }{ " items, " }{ Bytes(3_500_000) }</p>
+//!         </ContextProvider<Locale>>
+//!     }
+//! }
+//! ```
+
+use crate::context::ContextHandle;
+use crate::html::{Component, Context, Html, NodeRef};
+use crate::utils::window;
+use crate::virtual_dom::VComp;
+use crate::{html, Properties};
+use js_sys::{Array, Intl, Object, Reflect};
+use std::rc::Rc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use wasm_bindgen::JsValue;
+
+/// A BCP 47 language tag like `en-US`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Locale(Rc<str>);
+
+impl Locale {
+    /// Creates a locale from a language tag
+    pub fn new(tag: impl Into<Rc<str>>) -> Self {
+        Self(tag.into())
+    }
+
+    /// Returns the preferred language of the browser
+    pub fn browser() -> Self {
+        Self::new(
+            window()
+                .navigator()
+                .language()
+                .unwrap_or_else(|| "en".to_string()),
+        )
+    }
+
+    /// Returns the language tag
+    pub fn tag(&self) -> &str {
+        &self.0
+    }
+
+    fn locales(&self) -> Array {
+        Array::of1(&JsValue::from_str(&self.0))
+    }
+}
+
+/// A value that is formatted according to a [`Locale`]
+pub trait LocaleFormat: Clone + PartialEq + 'static {
+    /// Formats the value in `locale`
+    fn format(&self, locale: &Locale) -> String;
+}
+
+fn options(entries: &[(&str, JsValue)]) -> Object {
+    let options = Object::new();
+    for (key, value) in entries {
+        Reflect::set(&options, &JsValue::from_str(key), value).expect("set option");
+    }
+    options
+}
+
+fn format_number(locale: &Locale, value: f64, options: &Object) -> String {
+    let format = Intl::NumberFormat::new(&locale.locales(), options).format();
+    format
+        .call1(&JsValue::NULL, &value.into())
+        .ok()
+        .and_then(|formatted| formatted.as_string())
+        .unwrap_or_else(|| value.to_string())
+}
+
+/// A number with the digit grouping and decimal separator of the locale, e.g. `1,234.5`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Localized(pub f64);
+
+impl LocaleFormat for Localized {
+    fn format(&self, locale: &Locale) -> String {
+        format_number(locale, self.0, &Object::new())
+    }
+}
+
+/// A size in bytes in the largest unit it fills, e.g. `3.5 MB`. Units are multiples of 1000.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Bytes(pub u64);
+
+impl LocaleFormat for Bytes {
+    fn format(&self, locale: &Locale) -> String {
+        const UNITS: [&str; 6] = [
+            "byte", "kilobyte", "megabyte", "gigabyte", "terabyte", "petabyte",
+        ];
+        let mut value = self.0 as f64;
+        let mut unit = 0;
+        while value >= 1000.0 && unit < UNITS.len() - 1 {
+            value /= 1000.0;
+            unit += 1;
+        }
+        let options = options(&[
+            ("style", "unit".into()),
+            ("unit", UNITS[unit].into()),
+            ("maximumFractionDigits", 1.into()),
+        ]);
+        format_number(locale, value, &options)
+    }
+}
+
+/// A time relative to now, e.g. `5 minutes ago` or `tomorrow`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RelativeTime(pub SystemTime);
+
+impl RelativeTime {
+    /// Returns the seconds from now to the time, negative if it is in the past. The clock of the
+    /// browser is used, as `SystemTime::now` is not available.
+    fn seconds_from_now(&self) -> f64 {
+        let millis = |time: SystemTime| match time.duration_since(UNIX_EPOCH) {
+            Ok(duration) => duration.as_secs_f64() * 1000.0,
+            Err(err) => -err.duration().as_secs_f64() * 1000.0,
+        };
+        (millis(self.0) - js_sys::Date::now()) / 1000.0
+    }
+}
+
+impl LocaleFormat for RelativeTime {
+    fn format(&self, locale: &Locale) -> String {
+        const UNITS: [(&str, f64); 7] = [
+            ("second", 60.0),
+            ("minute", 60.0),
+            ("hour", 24.0),
+            ("day", 7.0),
+            ("week", 30.0 / 7.0),
+            ("month", 12.0),
+            ("year", f64::INFINITY),
+        ];
+        let mut value = self.seconds_from_now();
+        let mut unit = UNITS[0].0;
+        for (name, next) in UNITS.iter() {
+            unit = name;
+            if value.abs() < *next {
+                break;
+            }
+            value /= next;
+        }
+        let options = options(&[("numeric", "auto".into())]);
+        Intl::RelativeTimeFormat::new(&locale.locales(), &options)
+            .format(value.round(), unit)
+            .into()
+    }
+}
+
+/// Props for [`Formatted`]
+#[derive(Debug, Clone, PartialEq, Properties)]
+pub struct FormattedProps<T: LocaleFormat> {
+    /// The formatted value
+    pub value: T,
+}
+
+/// Renders a [`LocaleFormat`] value as text in the provided [`Locale`]. The adapters of this
+/// module convert into it.
+#[derive(Debug)]
+pub struct Formatted<T: LocaleFormat> {
+    locale: Locale,
+    _handle: Option<ContextHandle<Locale>>,
+    _value: std::marker::PhantomData<T>,
+}
+
+impl<T: LocaleFormat> Component for Formatted<T> {
+    type Message = Locale;
+    type Properties = FormattedProps<T>;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let (locale, handle) = match ctx.link().context(ctx.link().callback(|locale| locale)) {
+            Some((locale, handle)) => (locale, Some(handle)),
+            None => (Locale::browser(), None),
+        };
+        Self {
+            locale,
+            _handle: handle,
+            _value: std::marker::PhantomData,
+        }
+    }
+
+    fn update(&mut self, _ctx: &Context<Self>, locale: Self::Message) -> bool {
+        let changed = self.locale != locale;
+        self.locale = locale;
+        changed
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        html! { { ctx.props().value.format(&self.locale) } }
+    }
+}
+
+macro_rules! impl_into_html {
+    ($($ty:ty)*) => {
+        $(
+            impl From<$ty> for Html {
+                fn from(value: $ty) -> Self {
+                    let props = Rc::new(FormattedProps { value });
+                    VComp::new::<Formatted<$ty>>(props, NodeRef::default(), None).into()
+                }
+            }
+        )*
+    };
+}
+
+impl_into_html!(Localized Bytes RelativeTime);
+
+/// Returns a time `duration` before now, for [`RelativeTime`]
+pub fn ago(duration: Duration) -> SystemTime {
+    let now = UNIX_EPOCH + Duration::from_secs_f64(js_sys::Date::now() / 1000.0);
+    now - duration
+}
+
+#[cfg(all(test, feature = "wasm_test"))]
+mod tests {
+    use super::*;
+    use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    #[test]
+    fn formats_in_locales() {
+        let en = Locale::new("en-US");
+        let de = Locale::new("de-DE");
+        assert_eq!(Localized(1234.5).format(&en), "1,234.5");
+        assert_eq!(Localized(1234.5).format(&de), "1.234,5");
+        assert_eq!(Bytes(3_500_000).format(&en), "3.5 MB");
+        assert_eq!(Bytes(12).format(&en), "12 byte");
+        assert_eq!(
+            RelativeTime(ago(Duration::from_secs(5 * 60))).format(&en),
+            "5 minutes ago"
+        );
+        assert_eq!(
+            RelativeTime(ago(Duration::from_secs(26 * 3600))).format(&en),
+            "yesterday"
+        );
+    }
+}
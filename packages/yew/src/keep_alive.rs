@@ -0,0 +1,214 @@
+//! This module contains the [`KeepAlive`] component, which keeps removed children mounted.
+
+use crate::utils::document;
+use crate::virtual_dom::{Key, VList, VNode};
+use crate::{create_portal, Children, Component, Context, Html, Properties};
+use web_sys::Element;
+
+/// Properties of [`KeepAlive`]
+#[derive(Debug, Clone, PartialEq, Properties)]
+pub struct KeepAliveProps {
+    /// Maximum number of removed children kept. The ones removed first are destroyed first.
+    #[prop_or(8)]
+    pub max: usize,
+    /// The shown children, told apart by their keys
+    #[prop_or_default]
+    pub children: Children,
+}
+
+/// Keeps its children mounted when they are removed, e.g. the pages of tabs or routes, and
+/// shows them again with their state when they are added back.
+///
+/// Removed children are moved out of the document with their components, which keep receiving
+/// messages. Only children with a key are kept; the others are destroyed when removed, as
+/// anywhere else.
+///
+/// The shown children are rendered in an element with `display: contents`. Children shown again
+/// are appended to it, so when several children are shown at once their order may differ from
+/// the one they are passed in.
+///
+/// # Example
+/// ```rust
+/// # use yew::prelude::*;
+/// use yew::keep_alive::KeepAlive;
+///
+/// # #[derive(Clone, PartialEq, Properties)]
+/// # struct EditorProps { file: String }
+/// # #[function_component(Editor)]
+/// # fn editor(_props: &EditorProps) -> Html { html! {} }
+/// # fn view(open: &str) -> Html {
+/// // The editor of each file keeps its undo history while another file is open
+/// html! {
+///     <KeepAlive max=4>
+///         <Editor key={open} file={open.to_owned()} />
+///     </KeepAlive>
+/// }
+/// # }
+/// ```
+pub struct KeepAlive {
+    /// Element the shown children are rendered in
+    container: Element,
+    /// Element out of the document the removed children are kept in
+    storage: Element,
+    shown: Vec<(Key, Html)>,
+    /// Removed children, in the order they were removed
+    kept: Vec<(Key, Html)>,
+}
+
+impl KeepAlive {
+    fn update_children(&mut self, ctx: &Context<Self>) {
+        let props = ctx.props();
+        let shown: Vec<_> = props
+            .children
+            .iter()
+            .enumerate()
+            .map(|(index, child)| {
+                // Unkeyed children get a key no keyed child can have, which is never kept
+                let key = child
+                    .key()
+                    .unwrap_or_else(|| Key::from(format!("\0{}", index)));
+                (key, child)
+            })
+            .collect();
+
+        let removed = self
+            .shown
+            .drain(..)
+            .filter(|(key, child)| child.has_key() && !shown.iter().any(|(k, _)| k == key));
+        self.kept.extend(removed);
+        self.kept
+            .retain(|(key, _)| !shown.iter().any(|(k, _)| k == key));
+        let evicted = self.kept.len().saturating_sub(props.max);
+        self.kept.drain(..evicted);
+        self.shown = shown;
+    }
+}
+
+impl Component for KeepAlive {
+    type Message = ();
+    type Properties = KeepAliveProps;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let document = document();
+        let container = document.create_element("div").unwrap();
+        container
+            .set_attribute("style", "display: contents")
+            .unwrap();
+        let mut keep_alive = Self {
+            container,
+            storage: document.create_element("div").unwrap(),
+            shown: Vec::new(),
+            kept: Vec::new(),
+        };
+        keep_alive.update_children(ctx);
+        keep_alive
+    }
+
+    fn changed(&mut self, ctx: &Context<Self>) -> bool {
+        self.update_children(ctx);
+        true
+    }
+
+    fn view(&self, _ctx: &Context<Self>) -> Html {
+        // Every child is rendered through a portal keyed like the child, so moving it between
+        // the container and the storage moves its nodes instead of mounting it again
+        let portal = |(key, child): &(Key, Html), host: &Element| {
+            let portal = create_portal(child.clone(), host.clone());
+            VNode::VList(VList::with_children(vec![portal], Some(key.clone())))
+        };
+        let shown = self
+            .shown
+            .iter()
+            .map(|child| portal(child, &self.container));
+        let kept = self.kept.iter().map(|child| portal(child, &self.storage));
+
+        let mut list = VList::new();
+        list.add_child(VNode::VRef(self.container.clone().into()));
+        list.add_children(shown.chain(kept));
+        list.into()
+    }
+}
+
+#[cfg(all(test, feature = "wasm_test"))]
+mod tests {
+    extern crate self as yew;
+
+    use super::*;
+    use crate::{html, AppBuilder};
+    use std::cell::Cell;
+    use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    thread_local! {
+        static CREATED: Cell<usize> = Cell::new(0);
+    }
+
+    #[derive(Clone, PartialEq, Properties)]
+    struct PageProps {
+        name: &'static str,
+    }
+
+    struct Page;
+
+    impl Component for Page {
+        type Message = ();
+        type Properties = PageProps;
+
+        fn create(_ctx: &Context<Self>) -> Self {
+            CREATED.with(|created| created.set(created.get() + 1));
+            Self
+        }
+
+        fn view(&self, ctx: &Context<Self>) -> Html {
+            html! { <p>{ ctx.props().name }</p> }
+        }
+    }
+
+    struct Tabs {
+        open: &'static str,
+    }
+
+    impl Component for Tabs {
+        type Message = &'static str;
+        type Properties = ();
+
+        fn create(_ctx: &Context<Self>) -> Self {
+            Self { open: "a" }
+        }
+
+        fn update(&mut self, _ctx: &Context<Self>, open: &'static str) -> bool {
+            self.open = open;
+            true
+        }
+
+        fn view(&self, _ctx: &Context<Self>) -> Html {
+            html! {
+                <KeepAlive max=1>
+                    <Page key={self.open} name={self.open} />
+                </KeepAlive>
+            }
+        }
+    }
+
+    #[test]
+    fn removed_children_stay_mounted() {
+        let host = document().create_element("div").unwrap();
+        let app = AppBuilder::<Tabs>::new().mount(host.clone(), ());
+        let page = host.query_selector("p").unwrap().unwrap();
+        assert_eq!(page.text_content().unwrap(), "a");
+
+        app.send_message("b");
+        assert_eq!(host.text_content().unwrap(), "b");
+        app.send_message("a");
+        assert_eq!(host.text_content().unwrap(), "a");
+        assert_eq!(host.query_selector("p").unwrap(), Some(page));
+        assert_eq!(CREATED.with(Cell::get), 2);
+
+        // Only one removed child is kept
+        app.send_message("c");
+        app.send_message("b");
+        app.send_message("a");
+        assert_eq!(CREATED.with(Cell::get), 5);
+    }
+}
@@ -107,6 +107,37 @@ pub use yew_macro::classes;
 ///
 /// More information about using the `html!` macro can be found in the [Yew Docs]
 ///
+/// # Text and whitespace
+///
+/// Text is written as string literals, either in blocks or directly among the children. Literals
+/// are rendered as written by default. The `whitespace` prop of a fragment sets how whitespace
+/// in the literals written directly among its descendants is rendered instead: `"collapse"`
+/// collapses runs of whitespace into a single space, as browsers render them, and `"trim"` also
+/// removes whitespace at the start and end of literals. Literals in blocks, like `{" "}`, and the
+/// content of `<pre>`, `<textarea>`, `<script>` and `<style>` are always rendered as written.
+///
+/// ```
+/// # use yew::prelude::*;
+/// # fn test() {
+/// html! {
+///     <whitespace="trim">
+///         <p>
+///             "Read the"{" "}<a href="/docs">"documentation"</a>"."
+///         </p>
+///     </>
+/// };
+/// # }
+/// ```
+///
+/// # Listeners
+///
+/// Listeners like `onclick` take callbacks, usually created with
+/// [`Scope::callback`](crate::html::Scope::callback). The message returned by the closure of the
+/// callback is checked against the `Message` of the component, so a mismatch is reported at the
+/// closure. A unit variant of the `Message`, like `onclick={Msg::Increment}`, is short for
+/// `onclick={ctx.link().callback(|_| Msg::Increment)}`, so it needs the `ctx` of the `view` it is
+/// rendered in. Other paths looking like variants, e.g. constants, can be passed in parentheses.
+///
 /// [`Html`]: ./html/type.Html.html
 /// [`html_nested!`]: ./macro.html_nested.html
 /// [Yew Docs]: https://yew.rs/concepts/html/
@@ -248,37 +279,101 @@ pub use yew_macro::html_nested;
 /// [Yew Docs]: https://yew.rs/concepts/components/properties
 pub use yew_macro::props;
 
+/// This macro creates an [`AttrGroup`], a reusable group of attributes which is spread onto
+/// elements in [`html!`] with `..group`.
+///
+/// Attributes are written as in [`html!`] and can be separated by commas. `class` adds classes
+/// and listeners like `onclick` add listeners to the group. Attributes set on an element take
+/// precedence over those of the groups spread onto it.
+///
+/// ```
+/// # use yew::prelude::*;
+/// use yew::attrs;
+///
+/// # fn view(disabled: bool) -> Html {
+/// let button = attrs! { class="btn", role="button", tabindex=0, {disabled} };
+/// html! { <span ..button aria-label="Save" /> }
+/// # }
+/// ```
+///
+/// [`AttrGroup`]: ./virtual_dom/struct.AttrGroup.html
+/// [`html!`]: ./macro.html.html
+pub use yew_macro::attrs;
+
 /// This module contains macros which implements html! macro and JSX-like templates
 pub mod macros {
+    pub use crate::attrs;
     pub use crate::classes;
     pub use crate::html;
     pub use crate::html_nested;
     pub use crate::props;
 }
 
+pub mod a11y;
 mod app_handle;
 pub mod callback;
+pub mod code_block;
+pub mod command_palette;
 pub mod context;
+pub mod context_menu;
+pub mod defer;
+pub mod dialog;
+pub mod editable;
+mod error;
+pub mod event_sink;
+pub mod form;
+pub mod fullscreen;
 pub mod functional;
+pub mod gesture;
+pub mod history_entry;
 pub mod html;
+pub mod iframe_portal;
+pub mod image;
+pub mod indexed_db;
+pub mod infinite_scroll;
+#[cfg(feature = "input")]
+pub mod input;
+pub mod intl;
+pub mod keep_alive;
+pub mod live;
+pub mod live_view;
+#[cfg(feature = "markdown")]
+pub mod markdown;
+pub mod platform;
+pub mod popout;
+pub mod popover;
+pub mod preload;
+pub mod query;
 pub mod scheduler;
+pub mod selection;
+#[cfg(feature = "stats")]
+pub mod stats;
+pub mod text_selection;
+pub mod toast;
+pub mod undo;
 pub mod utils;
 pub mod virtual_dom;
+pub mod virtual_grid;
+pub mod window_bridge;
 
 pub use web_sys;
 
 /// The module that contains all events available in the framework.
 pub mod events {
-    pub use crate::html::TargetCast;
+    pub use crate::html::{EventDataset, TargetCast};
 
     #[doc(no_inline)]
     pub use web_sys::{
-        AnimationEvent, DragEvent, ErrorEvent, Event, FocusEvent, InputEvent, KeyboardEvent,
-        MouseEvent, PointerEvent, ProgressEvent, TouchEvent, TransitionEvent, UiEvent, WheelEvent,
+        AnimationEvent, CompositionEvent, DragEvent, ErrorEvent, Event, FocusEvent, InputEvent,
+        KeyboardEvent, MouseEvent, PointerEvent, ProgressEvent, TouchEvent, TransitionEvent,
+        UiEvent, WheelEvent,
     };
 }
 
-pub use crate::app_handle::AppHandle;
+pub use crate::app_handle::{AppBuilder, AppConfig, AppHandle};
+pub use crate::error::Error;
+pub use crate::event_sink::set_event_sink;
+pub use crate::virtual_dom::vportal::create_portal;
 use web_sys::Element;
 
 thread_local! {
@@ -293,7 +388,7 @@ pub fn set_custom_panic_hook(hook: Box<dyn Fn(&PanicInfo<'_>) + Sync + Send + 's
     PANIC_HOOK_IS_SET.with(|hook_is_set| hook_is_set.set(true));
 }
 
-fn set_default_panic_hook() {
+pub(crate) fn set_default_panic_hook() {
     if !PANIC_HOOK_IS_SET.with(|hook_is_set| hook_is_set.replace(true)) {
         std::panic::set_hook(Box::new(console_error_panic_hook::hook));
     }
@@ -301,6 +396,10 @@ fn set_default_panic_hook() {
 
 /// The main entry point of a Yew application.
 /// If you would like to pass props, use the `start_app_with_props_in_element` method.
+///
+/// The element may be rendered by another Yew app, as long as that app renders it without
+/// children. The app mounted in it should then be destroyed together with the component rendering
+/// the element.
 pub fn start_app_in_element<COMP>(element: Element) -> AppHandle<COMP>
 where
     COMP: Component,
@@ -341,8 +440,7 @@ pub fn start_app_with_props_in_element<COMP>(
 where
     COMP: Component,
 {
-    set_default_panic_hook();
-    AppHandle::<COMP>::mount_with_props(element, Rc::new(props))
+    AppBuilder::new().mount(element, props)
 }
 
 /// The main entry point of a Yew application.
@@ -351,13 +449,7 @@ pub fn start_app_with_props<COMP>(props: COMP::Properties) -> AppHandle<COMP>
 where
     COMP: Component,
 {
-    start_app_with_props_in_element(
-        crate::utils::document()
-            .body()
-            .expect("no body node found")
-            .into(),
-        props,
-    )
+    AppBuilder::new().mount_to_body(props)
 }
 
 /// The main entry point of a Yew application.
@@ -369,8 +461,7 @@ pub fn start_app_with_props_as_body<COMP>(props: COMP::Properties) -> AppHandle<
 where
     COMP: Component,
 {
-    set_default_panic_hook();
-    AppHandle::<COMP>::mount_as_body_with_props(Rc::new(props))
+    AppBuilder::new().mount_as_body(props)
 }
 
 /// The Yew Prelude
@@ -384,7 +475,7 @@ where
 pub mod prelude {
     pub use crate::app_handle::AppHandle;
     pub use crate::callback::Callback;
-    pub use crate::context::ContextProvider;
+    pub use crate::context::{ContextProvider, Service};
     pub use crate::events::*;
     pub use crate::html::{
         Children, ChildrenWithProps, Classes, Component, Context, Html, NodeRef, Properties,
@@ -395,4 +486,3 @@ pub mod prelude {
 }
 
 pub use self::prelude::*;
-use std::rc::Rc;
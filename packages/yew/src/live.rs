@@ -0,0 +1,285 @@
+//! This module defines [`Live`] values, which update the DOM without re-rendering components.
+
+use crate::html::NodeRef;
+use crate::utils::document;
+use crate::{Callback, Component, Context, Html, Properties};
+use slab::Slab;
+use std::cell::RefCell;
+use std::fmt;
+use std::rc::{Rc, Weak};
+use web_sys::{Element, Text};
+
+struct LiveInner<T> {
+    value: RefCell<T>,
+    subscribers: RefCell<Slab<Callback<T>>>,
+}
+
+/// A shared value that notifies subscribers when it changes.
+///
+/// `Live` is meant for values that change with a high frequency, like cursor positions or audio
+/// levels. A parent passes a `Live` to a child as a prop once and then calls [`set`](Self::set)
+/// on it: the child is not re-rendered, instead only the places bound to the value are updated,
+/// e.g. text rendered with [`LiveText`] or an attribute bound with
+/// [`bind_attribute`](Self::bind_attribute).
+///
+/// Clones share the value. Two `Live`s are equal if they share the value, so passing the same
+/// `Live` again does not count as a change of props.
+///
+/// # Example
+/// ```rust
+/// # use yew::prelude::*;
+/// use yew::live::{Live, LiveText};
+///
+/// #[derive(Clone, PartialEq, Properties)]
+/// struct MeterProps {
+///     level: Live<u32>,
+/// }
+///
+/// #[function_component(Meter)]
+/// fn meter(props: &MeterProps) -> Html {
+///     html! {
+///         <p>{ "Level: " }<LiveText<u32> value={props.level.clone()} /></p>
+///     }
+/// }
+///
+/// // In the parent, e.g. in an audio processing callback:
+/// # fn on_audio(level: &Live<u32>, rms: u32) {
+/// level.set(rms);
+/// # }
+/// ```
+pub struct Live<T: 'static>(Rc<LiveInner<T>>);
+
+impl<T: Clone + PartialEq + 'static> Live<T> {
+    /// Creates a new value
+    pub fn new(value: T) -> Self {
+        Self(Rc::new(LiveInner {
+            value: RefCell::new(value),
+            subscribers: Default::default(),
+        }))
+    }
+
+    /// Returns the current value
+    pub fn get(&self) -> T {
+        self.0.value.borrow().clone()
+    }
+
+    /// Sets the value and notifies the subscribers, if it changed
+    pub fn set(&self, value: T) {
+        if *self.0.value.borrow() == value {
+            return;
+        }
+        *self.0.value.borrow_mut() = value.clone();
+
+        // Subscribers may subscribe or unsubscribe when notified
+        let subscribers: Vec<Callback<T>> = self
+            .0
+            .subscribers
+            .borrow()
+            .iter()
+            .map(|(_, cb)| cb.clone())
+            .collect();
+        for subscriber in subscribers {
+            subscriber.emit(value.clone());
+        }
+    }
+
+    /// Calls `callback` with the new value whenever it changes, until the returned handle is
+    /// dropped
+    pub fn subscribe(&self, callback: Callback<T>) -> LiveHandle<T> {
+        let key = self.0.subscribers.borrow_mut().insert(callback);
+        LiveHandle {
+            live: Rc::downgrade(&self.0),
+            key,
+        }
+    }
+
+    /// Keeps the attribute `name` of the element referenced by `node_ref` set to the value, until
+    /// the returned handle is dropped.
+    ///
+    /// The attribute is only updated on changes, so the element should be rendered with the
+    /// current value, e.g. `style={live.get().to_string()}`.
+    pub fn bind_attribute(&self, node_ref: NodeRef, name: &'static str) -> LiveHandle<T>
+    where
+        T: fmt::Display,
+    {
+        self.subscribe(Callback::from(move |value: T| {
+            if let Some(el) = node_ref.cast::<Element>() {
+                let _ = el.set_attribute(name, &value.to_string());
+            }
+        }))
+    }
+}
+
+impl<T: 'static> Clone for Live<T> {
+    fn clone(&self) -> Self {
+        Self(Rc::clone(&self.0))
+    }
+}
+
+impl<T: 'static> PartialEq for Live<T> {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl<T: Default + Clone + PartialEq + 'static> Default for Live<T> {
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+impl<T: fmt::Debug + 'static> fmt::Debug for Live<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Live")
+            .field(&*self.0.value.borrow())
+            .finish()
+    }
+}
+
+/// Subscription to a [`Live`] value. The subscriber is removed when the handle is dropped.
+pub struct LiveHandle<T: 'static> {
+    live: Weak<LiveInner<T>>,
+    key: usize,
+}
+
+impl<T: 'static> Drop for LiveHandle<T> {
+    fn drop(&mut self) {
+        if let Some(live) = self.live.upgrade() {
+            live.subscribers.borrow_mut().remove(self.key);
+        }
+    }
+}
+
+impl<T: 'static> fmt::Debug for LiveHandle<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("LiveHandle<_>")
+    }
+}
+
+/// Props for [`LiveText`]
+#[derive(Clone, PartialEq, Properties)]
+pub struct LiveTextProps<T: Clone + PartialEq + 'static> {
+    /// Value to render
+    pub value: Live<T>,
+}
+
+/// Renders a [`Live`] value as text and updates the text node directly when the value changes.
+pub struct LiveText<T: Clone + PartialEq + fmt::Display + 'static> {
+    node: Text,
+    _handle: LiveHandle<T>,
+}
+
+impl<T: Clone + PartialEq + fmt::Display + 'static> LiveText<T> {
+    fn bind(node: &Text, value: &Live<T>) -> LiveHandle<T> {
+        node.set_node_value(Some(&value.get().to_string()));
+        let node = node.clone();
+        value.subscribe(Callback::from(move |value: T| {
+            node.set_node_value(Some(&value.to_string()));
+        }))
+    }
+}
+
+impl<T: Clone + PartialEq + fmt::Display + 'static> Component for LiveText<T> {
+    type Message = ();
+    type Properties = LiveTextProps<T>;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let node = document().create_text_node("");
+        let handle = Self::bind(&node, &ctx.props().value);
+        Self {
+            node,
+            _handle: handle,
+        }
+    }
+
+    fn changed(&mut self, ctx: &Context<Self>) -> bool {
+        self._handle = Self::bind(&self.node, &ctx.props().value);
+        false
+    }
+
+    fn view(&self, _ctx: &Context<Self>) -> Html {
+        Html::VRef(self.node.clone().into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate self as yew;
+
+    use super::*;
+    use crate::html;
+    use crate::html::Scope;
+
+    #[cfg(feature = "wasm_test")]
+    use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+
+    #[cfg(feature = "wasm_test")]
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    #[test]
+    fn notifies_subscribers_of_changes() {
+        let live = Live::new(1);
+        let seen = Rc::new(RefCell::new(Vec::new()));
+        let handle = {
+            let seen = Rc::clone(&seen);
+            live.subscribe(Callback::from(move |v| seen.borrow_mut().push(v)))
+        };
+
+        live.set(2);
+        live.set(2);
+        live.clone().set(3);
+        assert_eq!(live.get(), 3);
+        drop(handle);
+        live.set(4);
+        assert_eq!(*seen.borrow(), vec![2, 3]);
+    }
+
+    struct Parent {
+        views: Rc<RefCell<u32>>,
+    }
+
+    #[derive(Clone, PartialEq, Properties)]
+    struct ParentProps {
+        level: Live<u32>,
+        views: Rc<RefCell<u32>>,
+    }
+
+    impl Component for Parent {
+        type Message = ();
+        type Properties = ParentProps;
+
+        fn create(ctx: &Context<Self>) -> Self {
+            Self {
+                views: Rc::clone(&ctx.props().views),
+            }
+        }
+
+        fn view(&self, ctx: &Context<Self>) -> Html {
+            *self.views.borrow_mut() += 1;
+            html! { <p><LiveText<u32> value={ctx.props().level.clone()} /></p> }
+        }
+    }
+
+    #[test]
+    fn live_text_updates_without_render() {
+        let level = Live::new(1);
+        let views = Rc::new(RefCell::new(0));
+        let scope = Scope::<Parent>::new(None);
+        let el = document().create_element("div").unwrap();
+        let props = ParentProps {
+            level: level.clone(),
+            views: Rc::clone(&views),
+        };
+        scope.mount_in_place(
+            el.clone(),
+            NodeRef::default(),
+            NodeRef::default(),
+            Rc::new(props),
+        );
+        assert_eq!(el.inner_html(), "<p>1</p>");
+
+        level.set(42);
+        assert_eq!(el.inner_html(), "<p>42</p>");
+        assert_eq!(*views.borrow(), 1);
+    }
+}
@@ -0,0 +1,382 @@
+//! Server-driven components, which run on one side of a connection and are displayed on the
+//! other.
+//!
+//! A [`LiveSession`] mounts a component on the side that owns the state and streams the DOM
+//! operations of every commit as [`ServerMessage`]s. The browser only runs [`connect`], which
+//! applies the operations to an element and sends the events of the user back as
+//! [`ClientMessage`]s. Messages are sent as JSON over a WebSocket.
+//!
+//! The session side renders with the regular virtual DOM into a detached element, so it needs a
+//! DOM to run on, e.g. a headless browser or another tab. It does not run on native targets: a
+//! server process has to host the sessions in such an environment and relay their messages.
+//! Events are replayed with their type and the value of the target only, so listeners of live
+//! components should not rely on other fields, like the mouse position.
+
+use crate::html::{NodeRef, Scope};
+use crate::utils::document;
+use crate::virtual_dom::ops::{self, CommitSubscription, NodeId, Op, OpApplier};
+use crate::{Callback, Component};
+use gloo::events::EventListener;
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+use std::collections::HashSet;
+use std::fmt;
+use std::rc::Rc;
+use wasm_bindgen::closure::Closure;
+use wasm_bindgen::{JsCast, JsValue};
+use web_sys::{
+    Element, Event, EventInit, HtmlInputElement, HtmlSelectElement, HtmlTextAreaElement,
+    MessageEvent, Node, WebSocket,
+};
+
+/// Events forwarded by [`connect`]
+pub const FORWARDED_EVENTS: &[&str] = &["click", "input", "change", "submit", "keydown"];
+
+/// Message from a [`LiveSession`] to the client
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ServerMessage {
+    /// Sent first, with the id of the element the component is mounted in
+    Init {
+        /// Id of the host element, which the client binds to its own element
+        root: NodeId,
+    },
+    /// Operations of a commit
+    Ops(Vec<Op>),
+}
+
+/// Message from the client to a [`LiveSession`]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ClientMessage {
+    /// An event dispatched to an element of the component
+    Event {
+        /// Id of the target element
+        target: NodeId,
+        /// Type of the event, e.g. `click`
+        kind: String,
+        /// `value` of the target, for form elements
+        value: Option<String>,
+        /// `checked` state of the target, for `input` elements
+        checked: Option<bool>,
+    },
+}
+
+/// A component rendered for a remote client
+pub struct LiveSession<COMP: Component> {
+    scope: Scope<COMP>,
+    host: Element,
+    _subscription: CommitSubscription,
+}
+
+impl<COMP: Component> LiveSession<COMP> {
+    /// Mounts the component and calls `send` with the messages for the client
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no DOM, like on native targets.
+    pub fn start(props: COMP::Properties, send: impl Fn(ServerMessage) + 'static) -> Self {
+        let host = document().create_element("div").unwrap();
+        let root = ops::node_id(&host);
+        send(ServerMessage::Init { root });
+
+        // Node ids are global, so the operations of other sessions on the same thread are
+        // filtered out
+        let owned = RefCell::new(std::iter::once(root).collect::<HashSet<_>>());
+        let subscription = ops::subscribe_commits(Callback::from(move |ops: Vec<Op>| {
+            let ops = filter_owned(&mut owned.borrow_mut(), ops);
+            if !ops.is_empty() {
+                send(ServerMessage::Ops(ops));
+            }
+        }));
+
+        let scope = Scope::new(None);
+        scope.mount_in_place(
+            host.clone(),
+            NodeRef::default(),
+            NodeRef::default(),
+            Rc::new(props),
+        );
+        Self {
+            scope,
+            host,
+            _subscription: subscription,
+        }
+    }
+
+    /// Handles a message of the client
+    pub fn handle(&self, msg: ClientMessage) {
+        match msg {
+            ClientMessage::Event {
+                target,
+                kind,
+                value,
+                checked,
+            } => {
+                let target = match self.find(target) {
+                    Some(target) => target,
+                    None => return,
+                };
+                if let Some(value) = value {
+                    set_value(&target, &value);
+                }
+                if let (Some(checked), Some(input)) =
+                    (checked, target.dyn_ref::<HtmlInputElement>())
+                {
+                    input.set_checked(checked);
+                }
+                let mut init = EventInit::new();
+                init.bubbles(true).cancelable(true);
+                let event = Event::new_with_event_init_dict(&kind, &init).unwrap();
+                let _ = target.dispatch_event(&event);
+            }
+        }
+    }
+
+    /// Returns the scope of the component
+    pub fn scope(&self) -> &Scope<COMP> {
+        &self.scope
+    }
+
+    /// Finds the element of the component with the id `id`
+    fn find(&self, id: NodeId) -> Option<Element> {
+        let elements = self.host.query_selector_all("*").ok()?;
+        (0..elements.length())
+            .filter_map(|i| elements.item(i))
+            .find(|node| ops::node_id(node) == id)
+            .and_then(|node| node.dyn_into().ok())
+    }
+
+    /// Destroys the component
+    pub fn destroy(mut self) {
+        use crate::html::Scoped;
+        self.scope.destroy();
+    }
+}
+
+impl<COMP: Component> fmt::Debug for LiveSession<COMP> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("LiveSession<_>")
+    }
+}
+
+/// Returns the operations on nodes in the tree of a session, whose ids are in `owned`, and adds
+/// the nodes inserted into the tree to `owned`
+fn filter_owned(owned: &mut HashSet<NodeId>, ops: Vec<Op>) -> Vec<Op> {
+    // Subtrees are built before they are inserted, so insertions decide which new nodes belong
+    // to the session
+    loop {
+        let before = owned.len();
+        for op in &ops {
+            if let Op::Insert { parent, node, .. } = op {
+                if owned.contains(parent) {
+                    owned.insert(*node);
+                }
+            }
+        }
+        if owned.len() == before {
+            break;
+        }
+    }
+
+    ops.into_iter()
+        .filter(|op| {
+            let node = match op {
+                Op::CreateElement { id, .. }
+                | Op::CreateText { id, .. }
+                | Op::CreateComment { id, .. } => id,
+                Op::Insert { parent, .. } => parent,
+                Op::Remove { node }
+                | Op::SetAttribute { node, .. }
+                | Op::RemoveAttribute { node, .. }
+                | Op::SetText { node, .. }
+                | Op::SetValue { node, .. }
+                | Op::SetChecked { node, .. } => node,
+            };
+            owned.contains(node)
+        })
+        .collect()
+}
+
+fn set_value(target: &Element, value: &str) {
+    if let Some(input) = target.dyn_ref::<HtmlInputElement>() {
+        input.set_value(value);
+    } else if let Some(textarea) = target.dyn_ref::<HtmlTextAreaElement>() {
+        textarea.set_value(value);
+    } else if let Some(select) = target.dyn_ref::<HtmlSelectElement>() {
+        select.set_value(value);
+    }
+}
+
+fn get_value(target: &Element) -> Option<String> {
+    if let Some(input) = target.dyn_ref::<HtmlInputElement>() {
+        Some(input.value())
+    } else if let Some(textarea) = target.dyn_ref::<HtmlTextAreaElement>() {
+        Some(textarea.value())
+    } else {
+        target
+            .dyn_ref::<HtmlSelectElement>()
+            .map(|select| select.value())
+    }
+}
+
+/// Connection of [`connect`]. The socket is closed when it is dropped.
+pub struct LiveViewHandle {
+    socket: WebSocket,
+    _on_message: Closure<dyn FnMut(MessageEvent)>,
+    _listeners: Vec<EventListener>,
+}
+
+impl Drop for LiveViewHandle {
+    fn drop(&mut self) {
+        self.socket.set_onmessage(None);
+        let _ = self.socket.close();
+    }
+}
+
+impl fmt::Debug for LiveViewHandle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("LiveViewHandle")
+    }
+}
+
+/// Connects to the [`LiveSession`] served at `url` and displays it in `element`.
+///
+/// The events in [`FORWARDED_EVENTS`] are sent to the session. The default action of `submit`
+/// events is prevented, as the page would be reloaded otherwise.
+pub fn connect(url: &str, element: Element) -> Result<LiveViewHandle, JsValue> {
+    let socket = WebSocket::new(url)?;
+    let applier = Rc::new(RefCell::new(OpApplier::new()));
+
+    let on_message = {
+        let applier = Rc::clone(&applier);
+        let element = element.clone();
+        Closure::wrap(Box::new(move |event: MessageEvent| {
+            let msg = event
+                .data()
+                .as_string()
+                .and_then(|data| serde_json::from_str::<ServerMessage>(&data).ok());
+            match msg {
+                Some(ServerMessage::Init { root }) => {
+                    applier.borrow_mut().bind(root, element.clone().into())
+                }
+                Some(ServerMessage::Ops(ops)) => {
+                    if let Err(err) = applier.borrow_mut().apply_all(ops) {
+                        log::error!("failed to apply operations of live view: {}", err);
+                    }
+                }
+                None => log::error!("invalid message from live view session"),
+            }
+        }) as Box<dyn FnMut(MessageEvent)>)
+    };
+    socket.set_onmessage(Some(on_message.as_ref().unchecked_ref()));
+
+    let listeners = FORWARDED_EVENTS
+        .iter()
+        .map(|kind| {
+            let applier = Rc::clone(&applier);
+            let socket = socket.clone();
+            EventListener::new(&element, *kind, move |event| {
+                if *kind == "submit" {
+                    event.prevent_default();
+                }
+                // Find the closest element the session knows
+                let applier = applier.borrow();
+                let mut current = event.target().and_then(|t| t.dyn_into::<Node>().ok());
+                while let Some(node) = current {
+                    if let Some(target) = applier.id_of(&node) {
+                        let element = node.dyn_ref::<Element>();
+                        let msg = ClientMessage::Event {
+                            target,
+                            kind: kind.to_string(),
+                            value: element.and_then(get_value),
+                            checked: element
+                                .and_then(|el| el.dyn_ref::<HtmlInputElement>())
+                                .map(|input| input.checked()),
+                        };
+                        let _ = socket.send_with_str(&serde_json::to_string(&msg).unwrap());
+                        return;
+                    }
+                    current = node.parent_node();
+                }
+            })
+        })
+        .collect();
+
+    Ok(LiveViewHandle {
+        socket,
+        _on_message: on_message,
+        _listeners: listeners,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate self as yew;
+
+    use super::*;
+    use crate::{html, Context, Html};
+
+    #[cfg(feature = "wasm_test")]
+    use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+
+    #[cfg(feature = "wasm_test")]
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    struct Counter {
+        count: u32,
+    }
+
+    impl Component for Counter {
+        type Message = ();
+        type Properties = ();
+
+        fn create(_ctx: &Context<Self>) -> Self {
+            Self { count: 0 }
+        }
+
+        fn update(&mut self, _ctx: &Context<Self>, _msg: ()) -> bool {
+            self.count += 1;
+            true
+        }
+
+        fn view(&self, ctx: &Context<Self>) -> Html {
+            html! {
+                <button onclick={ctx.link().callback(|_| ())}>{ self.count }</button>
+            }
+        }
+    }
+
+    #[test]
+    fn session_streams_operations_and_handles_events() {
+        let sent = Rc::new(RefCell::new(Vec::new()));
+        let session = {
+            let sent = Rc::clone(&sent);
+            LiveSession::<Counter>::start((), move |msg| sent.borrow_mut().push(msg))
+        };
+
+        // Replay on a client element, as `connect` does
+        let client = document().create_element("div").unwrap();
+        let mut applier = OpApplier::new();
+        let replay = |applier: &mut OpApplier, sent: &RefCell<Vec<ServerMessage>>| {
+            for msg in sent.borrow_mut().drain(..) {
+                match msg {
+                    ServerMessage::Init { root } => applier.bind(root, client.clone().into()),
+                    ServerMessage::Ops(ops) => applier.apply_all(ops).unwrap(),
+                }
+            }
+        };
+        replay(&mut applier, &sent);
+        assert_eq!(client.inner_html(), "<button>0</button>");
+
+        let button = client.first_child().unwrap();
+        session.handle(ClientMessage::Event {
+            target: applier.id_of(&button).unwrap(),
+            kind: "click".into(),
+            value: None,
+            checked: None,
+        });
+        replay(&mut applier, &sent);
+        assert_eq!(client.inner_html(), "<button>1</button>");
+
+        session.destroy();
+    }
+}
@@ -0,0 +1,498 @@
+//! Rendering of markdown into virtual DOM nodes.
+//!
+//! Markdown is parsed with `pulldown-cmark` and turned into a tree of [`VTag`]s rather than set
+//! as `innerHTML`, so it is diffed like any other content and can't inject scripts: raw HTML in
+//! the markdown is rendered as text and `javascript:` links are dropped.
+//!
+//! Elements can be rendered differently by passing a [`Renderer`], e.g. to highlight code
+//! blocks with a component. Elements it doesn't render fall back to [`render_default`].
+//!
+//! This module is only available with the `markdown` feature.
+
+use crate::html::{Component, Context, Html};
+use crate::virtual_dom::{AttrValue, VTag, VText};
+use crate::{html, Properties};
+use pulldown_cmark::{Alignment, CodeBlockKind, Event, Options, Parser, Tag};
+use std::fmt;
+use std::rc::Rc;
+
+/// An element of a markdown document.
+///
+/// Elements with content are passed their rendered children. Code blocks and images get their
+/// content as text instead.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Element {
+    /// A paragraph
+    Paragraph,
+    /// A heading of `level` 1 to 6
+    Heading(u32),
+    /// A block quote
+    BlockQuote,
+    /// A fenced or indented code block
+    CodeBlock {
+        /// Language of a fenced block, e.g. `rust`
+        language: Option<String>,
+        /// The code
+        code: String,
+    },
+    /// A list, numbered from `start` if it is ordered
+    List {
+        /// Number of the first item of an ordered list
+        start: Option<u64>,
+    },
+    /// An item of a list
+    Item,
+    /// The checkbox of an item of a task list
+    TaskMarker(bool),
+    /// A table
+    Table,
+    /// The header row of a table
+    TableHead,
+    /// A row of a table
+    TableRow,
+    /// A cell of a table
+    TableCell {
+        /// The cell is in the header row
+        header: bool,
+        /// Alignment of the column, `left`, `center` or `right`
+        align: Option<&'static str>,
+    },
+    /// Emphasized text
+    Emphasis,
+    /// Strong text
+    Strong,
+    /// Struck through text
+    Strikethrough,
+    /// Inline code
+    Code(String),
+    /// A link
+    Link {
+        /// Target of the link, empty if it was dropped as unsafe
+        href: String,
+        /// Title of the link
+        title: String,
+    },
+    /// An image
+    Image {
+        /// Source of the image
+        src: String,
+        /// Title of the image
+        title: String,
+        /// Text alternative of the image
+        alt: String,
+    },
+    /// A horizontal rule
+    Rule,
+    /// A hard line break
+    HardBreak,
+    /// A reference to a footnote
+    FootnoteReference(String),
+    /// The definition of a footnote
+    FootnoteDefinition(String),
+}
+
+/// Renders some elements differently. Returning `None` renders the element with
+/// [`render_default`]. Renderers are equal if they are the same instance.
+#[derive(Clone)]
+pub struct Renderer(Rc<dyn Fn(&Element, Html) -> Option<Html>>);
+
+impl Renderer {
+    /// Wraps `render`, which is called with every element and its rendered children
+    pub fn new(render: impl Fn(&Element, Html) -> Option<Html> + 'static) -> Self {
+        Self(Rc::new(render))
+    }
+}
+
+impl PartialEq for Renderer {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl fmt::Debug for Renderer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Renderer<_>")
+    }
+}
+
+fn tag(name: &'static str, children: Html) -> VTag {
+    let mut tag = VTag::new(name);
+    match children {
+        Html::VList(mut list) => tag.add_children(list.drain(..)),
+        child => tag.add_child(child),
+    }
+    tag
+}
+
+/// Renders `element` with the HTML element it stands for
+pub fn render_default(element: &Element, children: Html) -> Html {
+    let tag = match element {
+        Element::Paragraph => tag("p", children),
+        Element::Heading(level) => {
+            let name = ["h1", "h2", "h3", "h4", "h5", "h6"][(*level as usize).clamp(1, 6) - 1];
+            tag(name, children)
+        }
+        Element::BlockQuote => tag("blockquote", children),
+        Element::CodeBlock { language, code } => {
+            let mut inner = tag("code", VText::new(code.clone()).into());
+            if let Some(language) = language {
+                inner.add_attribute("class", format!("language-{}", language));
+            }
+            tag("pre", inner.into())
+        }
+        Element::List { start: None } => tag("ul", children),
+        Element::List { start: Some(start) } => {
+            let mut list = tag("ol", children);
+            if *start != 1 {
+                list.add_attribute("start", start.to_string());
+            }
+            list
+        }
+        Element::Item => tag("li", children),
+        Element::TaskMarker(checked) => {
+            let mut input = VTag::new("input");
+            input.add_attribute("type", "checkbox");
+            input.add_attribute("disabled", "");
+            input.set_checked(*checked);
+            input
+        }
+        Element::Table => tag("table", children),
+        Element::TableHead => tag("thead", tag("tr", children).into()),
+        Element::TableRow => tag("tr", children),
+        Element::TableCell { header, align } => {
+            let mut cell = tag(if *header { "th" } else { "td" }, children);
+            if let Some(align) = align {
+                cell.add_attribute("style", format!("text-align: {}", align));
+            }
+            cell
+        }
+        Element::Emphasis => tag("em", children),
+        Element::Strong => tag("strong", children),
+        Element::Strikethrough => tag("del", children),
+        Element::Code(code) => tag("code", VText::new(code.clone()).into()),
+        Element::Link { href, title } => {
+            let mut link = tag("a", children);
+            if !href.is_empty() {
+                link.add_attribute("href", href.clone());
+            }
+            if !title.is_empty() {
+                link.add_attribute("title", title.clone());
+            }
+            link
+        }
+        Element::Image { src, title, alt } => {
+            let mut image = VTag::new("img");
+            image.add_attribute("src", src.clone());
+            image.add_attribute("alt", alt.clone());
+            if !title.is_empty() {
+                image.add_attribute("title", title.clone());
+            }
+            image
+        }
+        Element::Rule => VTag::new("hr"),
+        Element::HardBreak => VTag::new("br"),
+        Element::FootnoteReference(name) => {
+            let mut link = tag("a", VText::new(name.clone()).into());
+            link.add_attribute("href", format!("#footnote-{}", name));
+            tag("sup", link.into())
+        }
+        Element::FootnoteDefinition(name) => {
+            let mut definition = tag("div", children);
+            definition.add_attribute("id", format!("footnote-{}", name));
+            definition.add_attribute("class", "footnote");
+            definition
+        }
+    };
+    tag.into()
+}
+
+/// Drops links to `javascript:` and other schemes that run code
+fn safe_url(url: &str) -> String {
+    let scheme = url
+        .split(|c| c == ':' || c == '/' || c == '?' || c == '#')
+        .next()
+        .filter(|_| url.contains(':'))
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase();
+    match scheme.as_str() {
+        "javascript" | "vbscript" | "data" => String::new(),
+        _ => url.to_owned(),
+    }
+}
+
+/// Content collected for an element that wasn't closed yet
+struct Open {
+    element: Element,
+    children: Vec<Html>,
+    /// Text of code blocks and alternative text of images
+    text: String,
+}
+
+/// Renders `markdown` with the elements of [`render_default`]
+pub fn render(markdown: &str) -> Html {
+    render_with(markdown, None)
+}
+
+/// Renders `markdown`, rendering elements with `renderer` if it is given. Tables,
+/// strikethrough, task lists and footnotes are supported.
+pub fn render_with(markdown: &str, renderer: Option<&Renderer>) -> Html {
+    let options = Options::ENABLE_TABLES
+        | Options::ENABLE_STRIKETHROUGH
+        | Options::ENABLE_TASKLISTS
+        | Options::ENABLE_FOOTNOTES;
+    let render = |element: &Element, children: Html| {
+        renderer
+            .and_then(|renderer| (renderer.0)(element, children.clone()))
+            .unwrap_or_else(|| render_default(element, children))
+    };
+
+    let mut stack = vec![Open {
+        element: Element::Paragraph,
+        children: Vec::new(),
+        text: String::new(),
+    }];
+    let mut alignments = Vec::new();
+    let mut column = 0;
+    let mut in_head = false;
+
+    for event in Parser::new_ext(markdown, options) {
+        let top = stack.last_mut().expect("root");
+        match event {
+            Event::Start(tag) => {
+                let element = match tag {
+                    Tag::Paragraph => Element::Paragraph,
+                    Tag::Heading(level) => Element::Heading(level),
+                    Tag::BlockQuote => Element::BlockQuote,
+                    Tag::CodeBlock(kind) => Element::CodeBlock {
+                        language: match kind {
+                            CodeBlockKind::Fenced(info) => {
+                                info.split_whitespace().next().map(str::to_owned)
+                            }
+                            CodeBlockKind::Indented => None,
+                        },
+                        code: String::new(),
+                    },
+                    Tag::List(start) => Element::List { start },
+                    Tag::Item => Element::Item,
+                    Tag::Table(columns) => {
+                        alignments = columns;
+                        Element::Table
+                    }
+                    Tag::TableHead => {
+                        in_head = true;
+                        column = 0;
+                        Element::TableHead
+                    }
+                    Tag::TableRow => {
+                        column = 0;
+                        Element::TableRow
+                    }
+                    Tag::TableCell => {
+                        let align = match alignments.get(column) {
+                            Some(Alignment::Left) => Some("left"),
+                            Some(Alignment::Center) => Some("center"),
+                            Some(Alignment::Right) => Some("right"),
+                            _ => None,
+                        };
+                        column += 1;
+                        Element::TableCell {
+                            header: in_head,
+                            align,
+                        }
+                    }
+                    Tag::Emphasis => Element::Emphasis,
+                    Tag::Strong => Element::Strong,
+                    Tag::Strikethrough => Element::Strikethrough,
+                    Tag::Link(_, href, title) => Element::Link {
+                        href: safe_url(&href),
+                        title: title.to_string(),
+                    },
+                    Tag::Image(_, src, title) => Element::Image {
+                        src: safe_url(&src),
+                        title: title.to_string(),
+                        alt: String::new(),
+                    },
+                    Tag::FootnoteDefinition(name) => Element::FootnoteDefinition(name.to_string()),
+                };
+                stack.push(Open {
+                    element,
+                    children: Vec::new(),
+                    text: String::new(),
+                });
+            }
+            Event::End(tag) => {
+                if let Tag::TableHead = tag {
+                    in_head = false;
+                }
+                let Open {
+                    mut element,
+                    children,
+                    text,
+                } = stack.pop().expect("start of the element");
+                let parent = stack.last_mut().expect("root");
+                parent.text.push_str(&text);
+                match &mut element {
+                    Element::CodeBlock { code, .. } => *code = text,
+                    Element::Image { alt, .. } => *alt = text,
+                    _ => {}
+                }
+                let node = render(&element, children.into_iter().collect());
+                stack.last_mut().expect("root").children.push(node);
+            }
+            // Raw HTML is shown as text
+            Event::Text(text) | Event::Html(text) => {
+                top.text.push_str(&text);
+                top.children.push(VText::new(text.to_string()).into());
+            }
+            Event::Code(code) => {
+                top.text.push_str(&code);
+                top.children
+                    .push(render(&Element::Code(code.to_string()), Html::default()));
+            }
+            Event::SoftBreak => {
+                top.text.push('\n');
+                top.children.push(VText::new("\n").into());
+            }
+            Event::HardBreak => top
+                .children
+                .push(render(&Element::HardBreak, Html::default())),
+            Event::Rule => top.children.push(render(&Element::Rule, Html::default())),
+            Event::TaskListMarker(checked) => top
+                .children
+                .push(render(&Element::TaskMarker(checked), Html::default())),
+            Event::FootnoteReference(name) => top.children.push(render(
+                &Element::FootnoteReference(name.to_string()),
+                Html::default(),
+            )),
+        }
+    }
+
+    let root = stack.pop().expect("root");
+    root.children.into_iter().collect()
+}
+
+/// Props for [`Markdown`]
+#[derive(Debug, Clone, PartialEq, Properties)]
+pub struct MarkdownProps {
+    /// The markdown text
+    pub source: AttrValue,
+    /// Renders some elements differently
+    #[prop_or_default]
+    pub renderer: Option<Renderer>,
+}
+
+/// Renders markdown. It is only parsed again when its props change.
+///
+/// # Example
+/// ```rust
+/// # use yew::prelude::*;
+/// use yew::markdown::{Element, Markdown, Renderer};
+///
+/// #[function_component(Readme)]
+/// fn readme() -> Html {
+///     // Code blocks are rendered by another component, e.g. one highlighting them
+///     let renderer = use_state(|| {
+///         Renderer::new(|element, _children| match element {
+///             Element::CodeBlock { code, .. } => {
+///                 Some(html! { <textarea readonly=true value={code.clone()} /> })
+///             }
+///             _ => None,
+///         })
+///     });
+///     let source = "# Title\n\nSome *text*.\n\n```rust\nfn main() {}\n```";
+///     html! { <Markdown {source} renderer={(*renderer).clone()} /> }
+/// }
+/// ```
+#[derive(Debug)]
+pub struct Markdown {
+    rendered: Html,
+}
+
+impl Component for Markdown {
+    type Message = ();
+    type Properties = MarkdownProps;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let props = ctx.props();
+        Self {
+            rendered: render_with(&props.source, props.renderer.as_ref()),
+        }
+    }
+
+    fn changed(&mut self, ctx: &Context<Self>) -> bool {
+        let props = ctx.props();
+        self.rendered = render_with(&props.source, props.renderer.as_ref());
+        true
+    }
+
+    fn view(&self, _ctx: &Context<Self>) -> Html {
+        html! { { self.rendered.clone() } }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "wasm_test")]
+    use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+
+    #[cfg(feature = "wasm_test")]
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    #[cfg(feature = "wasm_test")]
+    fn render_to_html(source: &str, renderer: Option<Renderer>) -> String {
+        let element = crate::utils::document().create_element("div").unwrap();
+        let props = MarkdownProps {
+            source: source.to_owned().into(),
+            renderer,
+        };
+        crate::start_app_with_props_in_element::<Markdown>(element.clone(), props);
+        element.inner_html()
+    }
+
+    #[cfg(feature = "wasm_test")]
+    #[test]
+    fn renders_elements() {
+        assert_eq!(
+            render_to_html(
+                "# Hi\n\nSome *text* <b>x</b> [a](javascript:alert(1))",
+                None
+            ),
+            "<h1>Hi</h1><p>Some <em>text</em> &lt;b&gt;x&lt;/b&gt; <a>a</a></p>"
+        );
+        assert_eq!(
+            render_to_html("- [x] done\n\n| a | b |\n|--:|---|\n| 1 | 2 |", None),
+            "<ul><li><input type=\"checkbox\" disabled=\"\">done</li></ul>\
+             <table><thead><tr><th style=\"text-align: right\">a</th><th>b</th></tr></thead>\
+             <tr><td style=\"text-align: right\">1</td><td>2</td></tr></table>"
+        );
+    }
+
+    #[cfg(feature = "wasm_test")]
+    #[test]
+    fn renders_with_renderer() {
+        let renderer = Renderer::new(|element, _| match element {
+            Element::CodeBlock { language, code } => {
+                Some(html! { <span class={language.clone()}>{ code.clone() }</span> })
+            }
+            _ => None,
+        });
+        assert_eq!(
+            render_to_html(
+                "```rust\nfn main() {}\n```\n![an *image*](a.png)",
+                Some(renderer)
+            ),
+            "<span class=\"rust\">fn main() {}\n</span><p><img src=\"a.png\" alt=\"an image\"></p>"
+        );
+    }
+
+    #[test]
+    fn drops_unsafe_urls() {
+        assert_eq!(safe_url("JavaScript:alert(1)"), "");
+        assert_eq!(safe_url(" data:text/html,x"), "");
+        assert_eq!(safe_url("https://yew.rs"), "https://yew.rs");
+        assert_eq!(safe_url("/docs?a=b:c"), "/docs?a=b:c");
+    }
+}
@@ -0,0 +1,291 @@
+//! A shared `AudioContext` managed by the component tree.
+//!
+//! Browsers create audio contexts suspended until the user interacts with the page.
+//! [`AudioProvider`] creates one context for its descendants, resumes it at the first click, tap
+//! or key press, and suspends it while the page is hidden. Function components get the context
+//! with [`use_audio`], struct components with
+//! [`Scope::context::<Audio>`](crate::html::Scope::context), and are rendered again when its
+//! state changes, e.g. to show a "tap to enable sound" hint while it is suspended.
+
+use crate::context::ContextProvider;
+use crate::functional::use_context;
+use crate::utils::document;
+use crate::{html, Children, Component, Context, Html, Properties};
+use gloo::events::{EventListener, EventListenerOptions};
+use web_sys::{AudioContext, AudioContextState};
+
+/// State of an [`Audio`] context
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AudioState {
+    /// The context doesn't play, because the user didn't interact with the page yet or the page
+    /// is hidden
+    Suspended,
+    /// The context plays
+    Running,
+    /// The context was closed and can't be used anymore
+    Closed,
+}
+
+impl From<AudioContextState> for AudioState {
+    fn from(state: AudioContextState) -> Self {
+        match state {
+            AudioContextState::Running => Self::Running,
+            AudioContextState::Closed => Self::Closed,
+            _ => Self::Suspended,
+        }
+    }
+}
+
+/// The `AudioContext` of an [`AudioProvider`] and its state
+#[derive(Debug, Clone, PartialEq)]
+pub struct Audio {
+    context: AudioContext,
+    state: AudioState,
+}
+
+impl Audio {
+    /// Returns the context, e.g. to create audio nodes connected to its destination
+    pub fn context(&self) -> &AudioContext {
+        &self.context
+    }
+
+    /// Returns the state of the context when it was provided
+    pub fn state(&self) -> AudioState {
+        self.state
+    }
+
+    /// Returns whether the context plays
+    pub fn is_running(&self) -> bool {
+        self.state == AudioState::Running
+    }
+}
+
+/// Returns the [`Audio`] of the closest [`AudioProvider`], or `None` if the component is not a
+/// descendant of one or the browser doesn't support the Web Audio API.
+///
+/// # Example
+/// ```rust
+/// # use yew::prelude::*;
+/// use yew::platform::audio::use_audio;
+/// use yew::web_sys::AudioContext;
+///
+/// # fn play_beep(_context: &AudioContext) {}
+/// #[function_component(Beep)]
+/// fn beep() -> Html {
+///     let audio = match use_audio() {
+///         Some(audio) => audio,
+///         None => return html! {},
+///     };
+///     let hint = (!audio.is_running()).then(|| html! { <p>{ "Tap to enable sound" }</p> });
+///     let onclick = Callback::from(move |_| play_beep(audio.context()));
+///     html! { <>{ for hint }<button {onclick}>{ "Beep" }</button></> }
+/// }
+/// ```
+pub fn use_audio() -> Option<Audio> {
+    use_context::<Audio>()
+}
+
+/// Properties of [`AudioProvider`]
+#[derive(Debug, Clone, PartialEq, Properties)]
+pub struct AudioProviderProps {
+    /// Suspends the context while the page is hidden, e.g. in a background tab, and resumes it
+    /// when the page is shown again
+    #[prop_or(true)]
+    pub suspend_when_hidden: bool,
+    /// The components using the context
+    #[prop_or_default]
+    pub children: Children,
+}
+
+#[doc(hidden)]
+#[derive(Debug)]
+pub enum AudioMsg {
+    Gesture,
+    VisibilityChanged,
+    StateChanged,
+}
+
+/// Provides an `AudioContext` to its descendants, see the [module docs](self).
+///
+/// The context is closed when the provider is destroyed.
+pub struct AudioProvider {
+    audio: Option<Audio>,
+    /// The context was suspended because the page was hidden
+    suspended_when_hidden: bool,
+    _listeners: Vec<EventListener>,
+}
+
+impl AudioProvider {
+    fn resume(&self) {
+        if let Some(audio) = &self.audio {
+            let _ = audio.context.resume();
+        }
+    }
+}
+
+impl Component for AudioProvider {
+    type Message = AudioMsg;
+    type Properties = AudioProviderProps;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let context = match AudioContext::new() {
+            Ok(context) => context,
+            Err(_) => {
+                return Self {
+                    audio: None,
+                    suspended_when_hidden: false,
+                    _listeners: Vec::new(),
+                }
+            }
+        };
+
+        let document = document();
+        let link = ctx.link();
+        // Autoplay policies only count the activation of the page in listeners of these events
+        let gestures = ["click", "touchend", "keydown"].iter().map(|event| {
+            let link = link.clone();
+            EventListener::new_with_options(
+                &document,
+                *event,
+                EventListenerOptions::run_in_capture_phase(),
+                move |_| link.send_message(AudioMsg::Gesture),
+            )
+        });
+        let visibility = {
+            let link = link.clone();
+            EventListener::new(&document, "visibilitychange", move |_| {
+                link.send_message(AudioMsg::VisibilityChanged)
+            })
+        };
+        let state = {
+            let link = link.clone();
+            EventListener::new(&context, "statechange", move |_| {
+                link.send_message(AudioMsg::StateChanged)
+            })
+        };
+
+        Self {
+            audio: Some(Audio {
+                state: context.state().into(),
+                context,
+            }),
+            suspended_when_hidden: false,
+            _listeners: gestures.chain(vec![visibility, state]).collect(),
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        let audio = match &mut self.audio {
+            Some(audio) => audio,
+            None => return false,
+        };
+        match msg {
+            AudioMsg::Gesture => {
+                if audio.state == AudioState::Suspended && !document().hidden() {
+                    self.suspended_when_hidden = false;
+                    self.resume();
+                }
+                false
+            }
+            AudioMsg::VisibilityChanged => {
+                if document().hidden() {
+                    if ctx.props().suspend_when_hidden && audio.state == AudioState::Running {
+                        self.suspended_when_hidden = true;
+                        let _ = audio.context.suspend();
+                    }
+                } else if self.suspended_when_hidden {
+                    self.suspended_when_hidden = false;
+                    self.resume();
+                }
+                false
+            }
+            AudioMsg::StateChanged => {
+                let state = audio.context.state().into();
+                if state == audio.state {
+                    return false;
+                }
+                audio.state = state;
+                true
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let children = html! { <>{ for ctx.props().children.iter() }</> };
+        match &self.audio {
+            Some(audio) => html! {
+                <ContextProvider<Audio> context={audio.clone()}>
+                    { children }
+                </ContextProvider<Audio>>
+            },
+            None => children,
+        }
+    }
+
+    fn destroy(&mut self, _ctx: &Context<Self>) {
+        if let Some(audio) = &self.audio {
+            let _ = audio.context.close();
+        }
+    }
+}
+
+#[cfg(all(test, feature = "wasm_test"))]
+mod tests {
+    extern crate self as yew;
+
+    use super::*;
+    use crate::{AppBuilder, Callback};
+    use std::cell::RefCell;
+    use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    thread_local! {
+        static PROVIDED: RefCell<Option<Audio>> = RefCell::new(None);
+    }
+
+    struct Consumer;
+
+    impl Component for Consumer {
+        type Message = ();
+        type Properties = ();
+
+        fn create(_ctx: &Context<Self>) -> Self {
+            Self
+        }
+
+        fn view(&self, ctx: &Context<Self>) -> Html {
+            let audio = ctx.link().context::<Audio>(Callback::noop());
+            PROVIDED.with(|provided| *provided.borrow_mut() = audio.map(|(audio, _)| audio));
+            html! {}
+        }
+    }
+
+    struct App;
+
+    impl Component for App {
+        type Message = ();
+        type Properties = ();
+
+        fn create(_ctx: &Context<Self>) -> Self {
+            Self
+        }
+
+        fn view(&self, _ctx: &Context<Self>) -> Html {
+            html! { <AudioProvider><Consumer /></AudioProvider> }
+        }
+    }
+
+    #[test]
+    fn descendants_get_the_context() {
+        let host = document().create_element("div").unwrap();
+        let app = AppBuilder::<App>::new().mount(host, ());
+
+        let audio = PROVIDED
+            .with(|provided| provided.borrow_mut().take())
+            .unwrap();
+        assert_ne!(audio.state(), AudioState::Closed);
+        assert!(audio.context().current_time() >= 0.0);
+        app.destroy();
+    }
+}
@@ -0,0 +1,7 @@
+//! This module contains bindings to browser platform APIs that are not part of the DOM.
+
+pub mod audio;
+pub mod push;
+pub mod service_worker;
+#[cfg(feature = "webrtc")]
+pub mod webrtc;
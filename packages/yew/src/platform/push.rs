@@ -0,0 +1,263 @@
+//! Notification permission and push subscriptions.
+//!
+//! The functions return futures, so their results can be turned into messages with
+//! [`send_future`](crate::html::Scope::send_future). Subscribing needs a registered
+//! [service worker](super::service_worker), which receives the pushed messages.
+//!
+//! # Example
+//! ```rust
+//! # use yew::prelude::*;
+//! use yew::platform::push::{self, PushError, PushSubscriptionInfo};
+//!
+//! const VAPID_PUBLIC_KEY: &str = "BEl62iUYgUivxIkv69yViEuiBIa-Ib9-SkvMeAtA3LFgDzkrxZJjSgSnfckjBJuBkr3qBUYIHBQFLXYp5Nksh8U";
+//!
+//! enum Msg {
+//!     Subscribe,
+//!     Subscribed(Result<PushSubscriptionInfo, PushError>),
+//! }
+//!
+//! # struct Settings;
+//! # impl Component for Settings {
+//! #     type Message = Msg;
+//! #     type Properties = ();
+//! #     fn create(_ctx: &Context<Self>) -> Self { Self }
+//! #     fn view(&self, _ctx: &Context<Self>) -> Html { unimplemented!() }
+//! fn update(&mut self, ctx: &Context<Self>, msg: Msg) -> bool {
+//!     match msg {
+//!         Msg::Subscribe => {
+//!             let subscribe = push::subscribe(VAPID_PUBLIC_KEY);
+//!             ctx.link().send_future(async { Msg::Subscribed(subscribe.await) });
+//!             false
+//!         }
+//!         Msg::Subscribed(Ok(subscription)) => {
+//!             // Send `subscription.to_json()` to the backend
+//!             false
+//!         }
+//!         Msg::Subscribed(Err(err)) => {
+//!             log::warn!("failed to subscribe: {}", err);
+//!             false
+//!         }
+//!     }
+//! }
+//! # }
+//! ```
+
+use super::service_worker::{container, error_message};
+use js_sys::{Uint8Array, JSON};
+use serde::{Deserialize, Serialize};
+use std::future::Future;
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{
+    Notification, NotificationPermission, PushManager, PushSubscription,
+    PushSubscriptionOptionsInit, ServiceWorkerRegistration,
+};
+
+/// Error of a push operation
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum PushError {
+    /// The browser doesn't support service workers, notifications or push
+    #[error("push notifications are not supported")]
+    Unsupported,
+    /// The user denied showing notifications
+    #[error("notification permission denied")]
+    PermissionDenied,
+    /// The VAPID key is not valid base64url
+    #[error("invalid VAPID key")]
+    InvalidKey,
+    /// The browser failed the request
+    #[error("push error: {0}")]
+    Js(String),
+}
+
+impl From<JsValue> for PushError {
+    fn from(err: JsValue) -> Self {
+        Self::Js(error_message(&err))
+    }
+}
+
+/// Subscription to push messages, as the backend needs it to send messages.
+///
+/// It serializes to the JSON of `PushSubscription.toJSON()`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PushSubscriptionInfo {
+    /// URL messages are sent to
+    pub endpoint: String,
+    /// Time the subscription expires at, in milliseconds since the epoch
+    #[serde(default)]
+    pub expiration_time: Option<f64>,
+    /// Keys to encrypt messages with
+    pub keys: PushSubscriptionKeys,
+}
+
+/// Keys of a [`PushSubscriptionInfo`], encoded in base64url
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PushSubscriptionKeys {
+    /// Public key of the client
+    pub p256dh: String,
+    /// Authentication secret
+    pub auth: String,
+}
+
+impl PushSubscriptionInfo {
+    /// Returns the JSON to register the subscription with the backend
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap()
+    }
+
+    fn from_subscription(subscription: &PushSubscription) -> Result<Self, PushError> {
+        let json = String::from(JSON::stringify(subscription)?);
+        serde_json::from_str(&json).map_err(|err| PushError::Js(err.to_string()))
+    }
+}
+
+/// Returns whether notifications may be shown
+pub fn permission() -> NotificationPermission {
+    if is_supported() {
+        Notification::permission()
+    } else {
+        NotificationPermission::Denied
+    }
+}
+
+fn is_supported() -> bool {
+    let global = js_sys::global();
+    container().is_some()
+        && js_sys::Reflect::has(&global, &JsValue::from_str("Notification")).unwrap_or(false)
+        && js_sys::Reflect::has(&global, &JsValue::from_str("PushManager")).unwrap_or(false)
+}
+
+/// Asks the user to allow notifications, unless they decided already, and resolves to their
+/// decision. Browsers only ask in response to a user action, e.g. a click.
+pub fn request_permission() -> impl Future<Output = Result<NotificationPermission, PushError>> {
+    let request = if is_supported() {
+        Notification::request_permission().map_err(PushError::from)
+    } else {
+        Err(PushError::Unsupported)
+    };
+    async move {
+        let permission = JsFuture::from(request?).await?;
+        Ok(NotificationPermission::from_js_value(&permission)
+            .unwrap_or(NotificationPermission::Default))
+    }
+}
+
+async fn push_manager() -> Result<PushManager, PushError> {
+    if !is_supported() {
+        return Err(PushError::Unsupported);
+    }
+    let container = container().ok_or(PushError::Unsupported)?;
+    let registration: ServiceWorkerRegistration =
+        JsFuture::from(container.ready()?).await?.unchecked_into();
+    Ok(registration.push_manager()?)
+}
+
+/// Resolves to the current subscription, if any
+pub fn subscription() -> impl Future<Output = Result<Option<PushSubscriptionInfo>, PushError>> {
+    async {
+        let subscription = JsFuture::from(push_manager().await?.get_subscription()?).await?;
+        match subscription.dyn_ref::<PushSubscription>() {
+            Some(subscription) => PushSubscriptionInfo::from_subscription(subscription).map(Some),
+            None => Ok(None),
+        }
+    }
+}
+
+/// Requests the notification permission if needed and subscribes to push messages sent by
+/// the server with the VAPID `application_server_key`, encoded in base64url.
+///
+/// An existing subscription with the same key is returned as is.
+pub fn subscribe(
+    application_server_key: &str,
+) -> impl Future<Output = Result<PushSubscriptionInfo, PushError>> {
+    let key = decode_base64_url(application_server_key);
+    let permission = request_permission();
+    async move {
+        let key = key.ok_or(PushError::InvalidKey)?;
+        if permission.await? != NotificationPermission::Granted {
+            return Err(PushError::PermissionDenied);
+        }
+
+        let mut options = PushSubscriptionOptionsInit::new();
+        // Browsers only allow subscriptions whose messages show a notification
+        options.user_visible_only(true);
+        options.application_server_key(Some(&Uint8Array::from(key.as_slice())));
+        let subscription = push_manager().await?.subscribe_with_options(&options)?;
+        let subscription: PushSubscription = JsFuture::from(subscription).await?.unchecked_into();
+        PushSubscriptionInfo::from_subscription(&subscription)
+    }
+}
+
+/// Cancels the current subscription and resolves to whether there was one
+pub fn unsubscribe() -> impl Future<Output = Result<bool, PushError>> {
+    async {
+        let subscription = JsFuture::from(push_manager().await?.get_subscription()?).await?;
+        match subscription.dyn_into::<PushSubscription>() {
+            Ok(subscription) => {
+                let unsubscribed = JsFuture::from(subscription.unsubscribe()?).await?;
+                Ok(unsubscribed.as_bool().unwrap_or(false))
+            }
+            Err(_) => Ok(false),
+        }
+    }
+}
+
+/// Decodes base64url, as VAPID keys are usually encoded, or standard base64
+fn decode_base64_url(input: &str) -> Option<Vec<u8>> {
+    let mut bytes = Vec::with_capacity(input.len() * 3 / 4);
+    let mut buffer = 0u32;
+    let mut bits = 0;
+    for c in input.trim_end_matches('=').bytes() {
+        let value = match c {
+            b'A'..=b'Z' => c - b'A',
+            b'a'..=b'z' => c - b'a' + 26,
+            b'0'..=b'9' => c - b'0' + 52,
+            b'-' | b'+' => 62,
+            b'_' | b'/' => 63,
+            _ => return None,
+        };
+        buffer = (buffer << 6 | u32::from(value)) & 0x3fff;
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((buffer >> bits) as u8);
+        }
+    }
+    Some(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "wasm_test")]
+    use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+
+    #[cfg(feature = "wasm_test")]
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    #[test]
+    fn decodes_vapid_keys() {
+        assert_eq!(decode_base64_url("AQID"), Some(vec![1, 2, 3]));
+        assert_eq!(decode_base64_url("_-8"), Some(vec![0xff, 0xef]));
+        assert_eq!(decode_base64_url("/+8="), Some(vec![0xff, 0xef]));
+        assert_eq!(decode_base64_url("a b"), None);
+        assert_eq!(
+            decode_base64_url("BEl62iUYgUivxIkv69yViEuiBIa-Ib9-SkvMeAtA3LFgDzkrxZJjSgSnfckjBJuBkr3qBUYIHBQFLXYp5Nksh8U")
+                .unwrap()
+                .len(),
+            65
+        );
+    }
+
+    #[test]
+    fn parses_subscription_json() {
+        let json = r#"{"endpoint":"https://push.example/1","expirationTime":null,"keys":{"p256dh":"a","auth":"b"}}"#;
+        let info: PushSubscriptionInfo = serde_json::from_str(json).unwrap();
+        assert_eq!(info.endpoint, "https://push.example/1");
+        assert_eq!(info.expiration_time, None);
+        assert_eq!(info.keys.auth, "b");
+        assert_eq!(info.to_json(), json);
+    }
+}
@@ -0,0 +1,301 @@
+//! Registration of a service worker and its lifecycle.
+//!
+//! [`register`] reports the lifecycle of the worker as [`ServiceWorkerEvent`]s, which are usually
+//! turned into messages of a component with [`Scope::callback`](crate::html::Scope::callback).
+//! When a new version of the worker waits to take over, a "new version available" banner can call
+//! [`skip_waiting_and_reload`].
+//!
+//! # Example
+//! ```rust
+//! # use yew::prelude::*;
+//! use yew::platform::service_worker::{
+//!     register, skip_waiting_and_reload, ServiceWorkerEvent, ServiceWorkerHandle,
+//! };
+//!
+//! enum Msg {
+//!     ServiceWorker(ServiceWorkerEvent),
+//!     Reload,
+//! }
+//!
+//! struct App {
+//!     _service_worker: ServiceWorkerHandle,
+//!     update_available: bool,
+//! }
+//!
+//! impl Component for App {
+//!     type Message = Msg;
+//!     type Properties = ();
+//!
+//!     fn create(ctx: &Context<Self>) -> Self {
+//!         Self {
+//!             _service_worker: register("/sw.js", ctx.link().callback(Msg::ServiceWorker)),
+//!             update_available: false,
+//!         }
+//!     }
+//!
+//!     fn update(&mut self, _ctx: &Context<Self>, msg: Msg) -> bool {
+//!         match msg {
+//!             Msg::ServiceWorker(ServiceWorkerEvent::UpdateAvailable) => {
+//!                 self.update_available = true;
+//!                 true
+//!             }
+//!             Msg::ServiceWorker(_) => false,
+//!             Msg::Reload => {
+//!                 skip_waiting_and_reload();
+//!                 false
+//!             }
+//!         }
+//!     }
+//!
+//!     fn view(&self, ctx: &Context<Self>) -> Html {
+//!         if self.update_available {
+//!             html! {
+//!                 <button onclick={ctx.link().callback(|_| Msg::Reload)}>
+//!                     { "A new version is available. Reload" }
+//!                 </button>
+//!             }
+//!         } else {
+//!             html! {}
+//!         }
+//!     }
+//! }
+//! ```
+
+use crate::callback::Callback;
+use crate::utils::window;
+use gloo::events::EventListener;
+use std::cell::RefCell;
+use std::fmt;
+use std::rc::{Rc, Weak};
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::{spawn_local, JsFuture};
+use web_sys::{
+    ServiceWorker, ServiceWorkerContainer, ServiceWorkerRegistration, ServiceWorkerState,
+};
+
+/// Message posted to a waiting worker by [`skip_waiting_and_reload`]. The worker is expected to
+/// call `self.skipWaiting()` when it receives it.
+pub const SKIP_WAITING_MESSAGE: &str = "SKIP_WAITING";
+
+/// A change of the lifecycle of a service worker
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ServiceWorkerEvent {
+    /// The worker was registered
+    Registered,
+    /// A new version of the worker is being installed
+    Installing,
+    /// The first version of the worker was installed and controls the next page loads, e.g. the
+    /// app works offline from now on
+    Installed,
+    /// A new version of the worker was installed and is waiting for the pages controlled by the
+    /// current version to close
+    UpdateAvailable,
+    /// The worker could not be registered
+    Failed(String),
+}
+
+pub(super) fn container() -> Option<ServiceWorkerContainer> {
+    let navigator = window().navigator();
+    // `navigator.serviceWorker` is missing in insecure contexts
+    if js_sys::Reflect::has(&navigator, &JsValue::from_str("serviceWorker")).unwrap_or(false) {
+        Some(navigator.service_worker())
+    } else {
+        None
+    }
+}
+
+fn has_controller() -> bool {
+    container()
+        .and_then(|container| container.controller())
+        .is_some()
+}
+
+pub(super) fn error_message(err: &JsValue) -> String {
+    err.dyn_ref::<js_sys::Error>()
+        .map(|err| String::from(err.message()))
+        .unwrap_or_else(|| format!("{:?}", err))
+}
+
+#[derive(Default)]
+struct Inner {
+    registration: Option<ServiceWorkerRegistration>,
+    listeners: Vec<EventListener>,
+}
+
+/// Handle of a service worker registered with [`register`].
+///
+/// Dropping the handle stops reporting events, the worker stays registered.
+pub struct ServiceWorkerHandle {
+    inner: Rc<RefCell<Inner>>,
+}
+
+impl ServiceWorkerHandle {
+    /// Returns the registration, once the worker was registered
+    pub fn registration(&self) -> Option<ServiceWorkerRegistration> {
+        self.inner.borrow().registration.clone()
+    }
+
+    /// Checks whether the worker script changed. A new version is reported with
+    /// [`ServiceWorkerEvent::Installing`].
+    pub fn update(&self) {
+        if let Some(registration) = &self.inner.borrow().registration {
+            if let Ok(promise) = registration.update() {
+                spawn_local(async move {
+                    let _ = JsFuture::from(promise).await;
+                });
+            }
+        }
+    }
+}
+
+impl fmt::Debug for ServiceWorkerHandle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("ServiceWorkerHandle")
+    }
+}
+
+/// Reports [`ServiceWorkerEvent::Installed`] or [`ServiceWorkerEvent::UpdateAvailable`] when
+/// `worker` is installed
+fn watch(
+    inner: &Weak<RefCell<Inner>>,
+    worker: ServiceWorker,
+    callback: Callback<ServiceWorkerEvent>,
+) {
+    let listener = {
+        let target = worker.clone();
+        EventListener::new(&target, "statechange", move |_| {
+            if worker.state() == ServiceWorkerState::Installed {
+                // Without a controller this is the first version
+                callback.emit(if has_controller() {
+                    ServiceWorkerEvent::UpdateAvailable
+                } else {
+                    ServiceWorkerEvent::Installed
+                });
+            }
+        })
+    };
+    if let Some(inner) = inner.upgrade() {
+        inner.borrow_mut().listeners.push(listener);
+    }
+}
+
+/// Registers the service worker at `path` and reports its lifecycle to `callback` until the
+/// returned handle is dropped.
+///
+/// If a new version is already waiting, e.g. because it was installed during an earlier visit,
+/// [`ServiceWorkerEvent::UpdateAvailable`] is reported right after registering.
+pub fn register(path: &str, callback: Callback<ServiceWorkerEvent>) -> ServiceWorkerHandle {
+    let inner: Rc<RefCell<Inner>> = Default::default();
+    let container = match container() {
+        Some(container) => container,
+        None => {
+            callback.emit(ServiceWorkerEvent::Failed(
+                "service workers are not supported".to_owned(),
+            ));
+            return ServiceWorkerHandle { inner };
+        }
+    };
+
+    let promise = container.register(path);
+    let weak = Rc::downgrade(&inner);
+    spawn_local(async move {
+        let registration: ServiceWorkerRegistration = match JsFuture::from(promise).await {
+            Ok(registration) => registration.unchecked_into(),
+            Err(err) => {
+                callback.emit(ServiceWorkerEvent::Failed(error_message(&err)));
+                return;
+            }
+        };
+        let inner = match weak.upgrade() {
+            Some(inner) => inner,
+            None => return,
+        };
+
+        let onupdatefound = {
+            let weak = Weak::clone(&weak);
+            let target = registration.clone();
+            let callback = callback.clone();
+            EventListener::new(&registration, "updatefound", move |_| {
+                if let Some(worker) = target.installing() {
+                    callback.emit(ServiceWorkerEvent::Installing);
+                    watch(&weak, worker, callback.clone());
+                }
+            })
+        };
+        {
+            let mut inner = inner.borrow_mut();
+            inner.listeners.push(onupdatefound);
+            inner.registration = Some(registration.clone());
+        }
+
+        callback.emit(ServiceWorkerEvent::Registered);
+        if registration.waiting().is_some() && has_controller() {
+            callback.emit(ServiceWorkerEvent::UpdateAvailable);
+        } else if let Some(worker) = registration.installing() {
+            callback.emit(ServiceWorkerEvent::Installing);
+            watch(&weak, worker, callback);
+        }
+    });
+
+    ServiceWorkerHandle { inner }
+}
+
+/// Activates the waiting version of the service worker and reloads the page once it took over.
+///
+/// The waiting worker receives [`SKIP_WAITING_MESSAGE`] and has to call `self.skipWaiting()`, as
+/// in:
+/// ```js
+/// self.addEventListener("message", (event) => {
+///     if (event.data === "SKIP_WAITING") self.skipWaiting();
+/// });
+/// ```
+/// Nothing happens if no version is waiting.
+pub fn skip_waiting_and_reload() {
+    let container = match container() {
+        Some(container) => container,
+        None => return,
+    };
+    spawn_local(async move {
+        let registration = match JsFuture::from(container.get_registration()).await {
+            Ok(registration) => registration.dyn_into::<ServiceWorkerRegistration>().ok(),
+            Err(_) => None,
+        };
+        let waiting = match registration.and_then(|registration| registration.waiting()) {
+            Some(waiting) => waiting,
+            None => return,
+        };
+
+        EventListener::once(&container, "controllerchange", |_| {
+            let _ = window().location().reload();
+        })
+        .forget();
+        if let Err(err) = waiting.post_message(&JsValue::from_str(SKIP_WAITING_MESSAGE)) {
+            log::error!("failed to activate the waiting service worker: {:?}", err);
+        }
+    });
+}
+
+#[cfg(all(test, feature = "wasm_test"))]
+mod tests {
+    use super::*;
+    use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    #[test]
+    async fn reports_failed_registration() {
+        let mut resolve = None;
+        let failed = js_sys::Promise::new(&mut |res, _| resolve = Some(res));
+        let resolve = resolve.unwrap();
+        let _handle = register(
+            "/missing-service-worker.js",
+            Callback::from(move |event: ServiceWorkerEvent| {
+                if let ServiceWorkerEvent::Failed(message) = event {
+                    let _ = resolve.call1(&JsValue::UNDEFINED, &JsValue::from_str(&message));
+                }
+            }),
+        );
+        let message = JsFuture::from(failed).await.unwrap();
+        assert!(message.as_string().is_some());
+    }
+}
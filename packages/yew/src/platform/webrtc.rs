@@ -0,0 +1,473 @@
+//! Peer-to-peer data channels over WebRTC.
+//!
+//! Only available with the `webrtc` feature. A [`PeerConnection`] wraps an `RTCPeerConnection`
+//! with a single data channel carrying typed messages, converted with `serde`. Peers find each
+//! other through a signaling service supplied by the app, e.g. a WebSocket server: the connection
+//! passes the [`Signal`]s to send to the other peer to a callback, and the app passes the signals
+//! received from the other peer to [`PeerConnection::signal`].
+//!
+//! Components connect with [`Scope::connect_peer`] and receive [`PeerEvent`]s as messages. The
+//! connection is closed when it is dropped, e.g. with the component keeping it.
+
+use crate::html::{Component, Scope};
+use crate::Callback;
+use gloo::events::EventListener;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+use std::fmt;
+use std::marker::PhantomData;
+use std::rc::Rc;
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::{spawn_local, JsFuture};
+use web_sys::{
+    MessageEvent, RtcConfiguration, RtcDataChannel, RtcDataChannelEvent, RtcIceCandidateInit,
+    RtcIceConnectionState, RtcIceServer, RtcPeerConnection, RtcPeerConnectionIceEvent, RtcSdpType,
+    RtcSessionDescriptionInit,
+};
+
+/// Label of the data channel
+const CHANNEL: &str = "yew";
+
+/// Error of a [`PeerConnection`]
+#[derive(Debug, thiserror::Error)]
+pub enum PeerError {
+    /// The browser failed an operation
+    #[error("WebRTC error: {0}")]
+    Js(String),
+    /// The data channel is not open yet or anymore
+    #[error("the data channel is not open")]
+    NotOpen,
+    /// A message could not be converted
+    #[error("failed to convert message: {0}")]
+    Serde(#[from] serde_json::Error),
+}
+
+impl From<JsValue> for PeerError {
+    fn from(err: JsValue) -> Self {
+        let message = err
+            .dyn_ref::<js_sys::Error>()
+            .map(|err| String::from(err.message()))
+            .or_else(|| err.as_string())
+            .unwrap_or_else(|| format!("{:?}", err));
+        Self::Js(message)
+    }
+}
+
+/// A signaling message, passed between the peers by the app
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Signal {
+    /// The session description offered by the peer starting the connection
+    Offer(String),
+    /// The session description answering an offer
+    Answer(String),
+    /// A network candidate for the connection
+    Candidate {
+        /// The candidate in SDP format
+        candidate: String,
+        /// Media stream identification of the candidate
+        sdp_mid: Option<String>,
+        /// Index of the media description of the candidate
+        sdp_m_line_index: Option<u16>,
+    },
+}
+
+/// Which side of the connection a peer is
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PeerRole {
+    /// Creates the data channel and sends the offer
+    Offerer,
+    /// Waits for the offer and answers it
+    Answerer,
+}
+
+/// State of a [`PeerConnection`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PeerState {
+    /// The peers are exchanging signals
+    Connecting,
+    /// The peers are connected
+    Connected,
+    /// The connection was lost, it may recover
+    Disconnected,
+    /// The connection failed
+    Failed,
+    /// The connection was closed
+    Closed,
+}
+
+impl From<RtcIceConnectionState> for PeerState {
+    fn from(state: RtcIceConnectionState) -> Self {
+        match state {
+            RtcIceConnectionState::Connected | RtcIceConnectionState::Completed => Self::Connected,
+            RtcIceConnectionState::Disconnected => Self::Disconnected,
+            RtcIceConnectionState::Failed => Self::Failed,
+            RtcIceConnectionState::Closed => Self::Closed,
+            _ => Self::Connecting,
+        }
+    }
+}
+
+/// An event of a [`PeerConnection`]
+#[derive(Debug)]
+pub enum PeerEvent<T> {
+    /// The state of the connection changed
+    StateChanged(PeerState),
+    /// The data channel opened, messages can be sent
+    Open,
+    /// A message was received
+    Message(T),
+    /// The data channel was closed
+    Closed,
+    /// An operation failed, e.g. applying a signal
+    Error(PeerError),
+}
+
+/// Configuration of a [`PeerConnection`]
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PeerConfig {
+    ice_servers: Vec<String>,
+}
+
+impl PeerConfig {
+    /// Adds a STUN or TURN server used to find a route between the peers, e.g.
+    /// `"stun:stun.example.com:3478"`
+    pub fn ice_server(mut self, url: impl Into<String>) -> Self {
+        self.ice_servers.push(url.into());
+        self
+    }
+
+    fn to_js(&self) -> RtcConfiguration {
+        let servers: js_sys::Array = self
+            .ice_servers
+            .iter()
+            .map(|url| {
+                let mut server = RtcIceServer::new();
+                server.urls(&JsValue::from_str(url));
+                JsValue::from(server)
+            })
+            .collect();
+        let mut config = RtcConfiguration::new();
+        config.ice_servers(&servers);
+        config
+    }
+}
+
+struct Inner<T> {
+    connection: RtcPeerConnection,
+    channel: RefCell<Option<RtcDataChannel>>,
+    listeners: RefCell<Vec<EventListener>>,
+    on_signal: Callback<Signal>,
+    on_event: Callback<PeerEvent<T>>,
+}
+
+impl<T: DeserializeOwned + 'static> Inner<T> {
+    fn error(&self, err: impl Into<PeerError>) {
+        self.on_event.emit(PeerEvent::Error(err.into()));
+    }
+
+    fn listen(
+        self: &Rc<Self>,
+        target: &web_sys::EventTarget,
+        event: &'static str,
+        f: impl Fn(&Rc<Self>, &web_sys::Event) + 'static,
+    ) {
+        let inner = Rc::downgrade(self);
+        let listener = EventListener::new(target, event, move |event| {
+            if let Some(inner) = inner.upgrade() {
+                f(&inner, event);
+            }
+        });
+        self.listeners.borrow_mut().push(listener);
+    }
+
+    fn attach_channel(self: &Rc<Self>, channel: RtcDataChannel) {
+        self.listen(&channel, "open", |inner, _| {
+            inner.on_event.emit(PeerEvent::Open)
+        });
+        self.listen(&channel, "close", |inner, _| {
+            inner.on_event.emit(PeerEvent::Closed)
+        });
+        self.listen(&channel, "message", |inner, event| {
+            let data = event
+                .dyn_ref::<MessageEvent>()
+                .and_then(|event| event.data().as_string())
+                .unwrap_or_default();
+            match serde_json::from_str(&data) {
+                Ok(message) => inner.on_event.emit(PeerEvent::Message(message)),
+                Err(err) => inner.error(err),
+            }
+        });
+        *self.channel.borrow_mut() = Some(channel);
+    }
+
+    /// Applies a signal received from the other peer
+    async fn apply(self: Rc<Self>, signal: Signal) -> Result<(), PeerError> {
+        let connection = &self.connection;
+        match signal {
+            Signal::Offer(sdp) => {
+                let mut offer = RtcSessionDescriptionInit::new(RtcSdpType::Offer);
+                offer.sdp(&sdp);
+                JsFuture::from(connection.set_remote_description(&offer)).await?;
+                let answer = JsFuture::from(connection.create_answer()).await?;
+                let sdp = sdp_of(&answer)?;
+                let mut answer = RtcSessionDescriptionInit::new(RtcSdpType::Answer);
+                answer.sdp(&sdp);
+                JsFuture::from(connection.set_local_description(&answer)).await?;
+                self.on_signal.emit(Signal::Answer(sdp));
+            }
+            Signal::Answer(sdp) => {
+                let mut answer = RtcSessionDescriptionInit::new(RtcSdpType::Answer);
+                answer.sdp(&sdp);
+                JsFuture::from(connection.set_remote_description(&answer)).await?;
+            }
+            Signal::Candidate {
+                candidate,
+                sdp_mid,
+                sdp_m_line_index,
+            } => {
+                let mut init = RtcIceCandidateInit::new(&candidate);
+                init.sdp_mid(sdp_mid.as_deref());
+                init.sdp_m_line_index(sdp_m_line_index);
+                let added =
+                    connection.add_ice_candidate_with_opt_rtc_ice_candidate_init(Some(&init));
+                JsFuture::from(added).await?;
+            }
+        }
+        Ok(())
+    }
+
+    async fn offer(self: Rc<Self>) -> Result<(), PeerError> {
+        let offer = JsFuture::from(self.connection.create_offer()).await?;
+        let sdp = sdp_of(&offer)?;
+        let mut init = RtcSessionDescriptionInit::new(RtcSdpType::Offer);
+        init.sdp(&sdp);
+        JsFuture::from(self.connection.set_local_description(&init)).await?;
+        self.on_signal.emit(Signal::Offer(sdp));
+        Ok(())
+    }
+}
+
+fn sdp_of(description: &JsValue) -> Result<String, PeerError> {
+    js_sys::Reflect::get(description, &JsValue::from_str("sdp"))?
+        .as_string()
+        .ok_or_else(|| PeerError::Js("session description without SDP".to_owned()))
+}
+
+/// A WebRTC connection to another peer with a data channel of messages of type `T`, see the
+/// [module docs](self).
+pub struct PeerConnection<T> {
+    inner: Rc<Inner<T>>,
+    _message: PhantomData<fn(T)>,
+}
+
+impl<T: Serialize + DeserializeOwned + 'static> PeerConnection<T> {
+    /// Starts connecting to the other peer. `on_signal` receives the signals to pass to the other
+    /// peer, `on_event` the events of the connection.
+    pub fn connect(
+        config: &PeerConfig,
+        role: PeerRole,
+        on_signal: Callback<Signal>,
+        on_event: Callback<PeerEvent<T>>,
+    ) -> Result<Self, PeerError> {
+        let connection = RtcPeerConnection::new_with_configuration(&config.to_js())?;
+        let inner = Rc::new(Inner {
+            connection: connection.clone(),
+            channel: RefCell::new(None),
+            listeners: RefCell::new(Vec::new()),
+            on_signal,
+            on_event,
+        });
+
+        inner.listen(&connection, "icecandidate", |inner, event| {
+            let candidate = event
+                .dyn_ref::<RtcPeerConnectionIceEvent>()
+                .and_then(RtcPeerConnectionIceEvent::candidate);
+            // The last event has no candidate
+            if let Some(candidate) = candidate {
+                inner.on_signal.emit(Signal::Candidate {
+                    candidate: candidate.candidate(),
+                    sdp_mid: candidate.sdp_mid(),
+                    sdp_m_line_index: candidate.sdp_m_line_index(),
+                });
+            }
+        });
+        inner.listen(&connection, "iceconnectionstatechange", |inner, _| {
+            let state = inner.connection.ice_connection_state().into();
+            inner.on_event.emit(PeerEvent::StateChanged(state));
+        });
+
+        match role {
+            PeerRole::Offerer => {
+                inner.attach_channel(connection.create_data_channel(CHANNEL));
+                let offering = Rc::clone(&inner);
+                spawn_local(async move {
+                    let errors = Rc::clone(&offering);
+                    if let Err(err) = offering.offer().await {
+                        errors.error(err);
+                    }
+                });
+            }
+            PeerRole::Answerer => {
+                inner.listen(&connection, "datachannel", |inner, event| {
+                    if let Some(event) = event.dyn_ref::<RtcDataChannelEvent>() {
+                        inner.attach_channel(event.channel());
+                    }
+                });
+            }
+        }
+
+        Ok(Self {
+            inner,
+            _message: PhantomData,
+        })
+    }
+
+    /// Applies a signal received from the other peer. Failures are reported as
+    /// [`PeerEvent::Error`].
+    pub fn signal(&self, signal: Signal) {
+        let inner = Rc::clone(&self.inner);
+        spawn_local(async move {
+            let errors = Rc::clone(&inner);
+            if let Err(err) = inner.apply(signal).await {
+                errors.error(err);
+            }
+        });
+    }
+
+    /// Sends `message` to the other peer
+    pub fn send(&self, message: &T) -> Result<(), PeerError> {
+        let channel = self.inner.channel.borrow();
+        let channel = match &*channel {
+            Some(channel) if channel.ready_state() == web_sys::RtcDataChannelState::Open => channel,
+            _ => return Err(PeerError::NotOpen),
+        };
+        channel.send_with_str(&serde_json::to_string(message)?)?;
+        Ok(())
+    }
+
+    /// Returns the state of the connection
+    pub fn state(&self) -> PeerState {
+        self.inner.connection.ice_connection_state().into()
+    }
+}
+
+impl<T> Drop for PeerConnection<T> {
+    fn drop(&mut self) {
+        // Events fired by closing are not reported
+        self.inner.listeners.borrow_mut().clear();
+        if let Some(channel) = self.inner.channel.borrow_mut().take() {
+            channel.close();
+        }
+        self.inner.connection.close();
+    }
+}
+
+impl<T> fmt::Debug for PeerConnection<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PeerConnection")
+            .field("connection", &self.inner.connection)
+            .finish()
+    }
+}
+
+impl<COMP: Component> Scope<COMP> {
+    /// Connects to another peer over WebRTC, see the [`webrtc`](crate::platform::webrtc) module.
+    /// `f` maps the events of the connection to messages of the component.
+    ///
+    /// Only available with the `webrtc` feature. The connection is closed when the returned
+    /// [`PeerConnection`] is dropped, so components keep it in their state.
+    pub fn connect_peer<T, F, M>(
+        &self,
+        config: &PeerConfig,
+        role: PeerRole,
+        on_signal: Callback<Signal>,
+        f: F,
+    ) -> Result<PeerConnection<T>, PeerError>
+    where
+        T: Serialize + DeserializeOwned + 'static,
+        M: Into<COMP::Message>,
+        F: Fn(PeerEvent<T>) -> M + 'static,
+    {
+        PeerConnection::connect(config, role, on_signal, self.callback(f))
+    }
+}
+
+#[cfg(all(test, feature = "wasm_test"))]
+mod tests {
+    use super::*;
+    use crate::utils::window;
+    use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    async fn sleep(ms: i32) {
+        let promise = js_sys::Promise::new(&mut |resolve, _| {
+            window()
+                .set_timeout_with_callback_and_timeout_and_arguments_0(&resolve, ms)
+                .unwrap();
+        });
+        JsFuture::from(promise).await.unwrap();
+    }
+
+    type Slot = Rc<RefCell<Option<PeerConnection<String>>>>;
+
+    /// Passes the signals to the connection in `slot`
+    fn signaling(slot: &Slot) -> Callback<Signal> {
+        let slot = Rc::clone(slot);
+        Callback::from(move |signal| {
+            if let Some(peer) = &*slot.borrow() {
+                peer.signal(signal);
+            }
+        })
+    }
+
+    #[test]
+    async fn peers_exchange_messages() {
+        let offerer: Slot = Default::default();
+        let answerer: Slot = Default::default();
+        let received = Rc::new(RefCell::new(Vec::new()));
+
+        let on_received = {
+            let received = Rc::clone(&received);
+            Callback::from(move |event: PeerEvent<String>| {
+                if let PeerEvent::Message(message) = event {
+                    received.borrow_mut().push(message);
+                }
+            })
+        };
+        *answerer.borrow_mut() = Some(
+            PeerConnection::connect(
+                &PeerConfig::default(),
+                PeerRole::Answerer,
+                signaling(&offerer),
+                on_received,
+            )
+            .unwrap(),
+        );
+        *offerer.borrow_mut() = Some(
+            PeerConnection::connect(
+                &PeerConfig::default(),
+                PeerRole::Offerer,
+                signaling(&answerer),
+                Callback::noop(),
+            )
+            .unwrap(),
+        );
+
+        for _ in 0..100 {
+            let sent = offerer.borrow().as_ref().unwrap().send(&"hello".to_owned());
+            if sent.is_ok() {
+                break;
+            }
+            sleep(50).await;
+        }
+        for _ in 0..100 {
+            if !received.borrow().is_empty() {
+                break;
+            }
+            sleep(50).await;
+        }
+        assert_eq!(*received.borrow(), vec!["hello".to_owned()]);
+    }
+}
@@ -0,0 +1,351 @@
+//! Rendering part of the tree into a separate browser window.
+//!
+//! [`PopoutWindow`] opens a window with `window.open` and renders its children into the body of
+//! the window's document through a [portal](crate::create_portal), like pop-out panels of
+//! dashboards. The children stay part of the tree of the component rendering the window, so they
+//! share contexts, callbacks and messages with the opener. The window is closed when the component
+//! is destroyed or the opener is unloaded.
+//!
+//! Windows showing a page of their own, which runs a separate app, exchange messages with the
+//! opener through a [`WindowChannel`].
+
+use crate::html::{Component, Context, Html};
+use crate::iframe_portal::IFramePortal;
+use crate::utils::{window, Environment};
+use crate::virtual_dom::AttrValue;
+use crate::{create_portal, html, Callback, Children, Properties};
+use gloo::events::EventListener;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::marker::PhantomData;
+use wasm_bindgen::{JsCast, JsValue};
+use web_sys::{BroadcastChannel, Document, MessageEvent, Window};
+
+/// Props for [`PopoutWindow`]
+#[derive(Debug, Clone, PartialEq, Properties)]
+pub struct PopoutWindowProps {
+    /// Name of the window. A window of the same name opened before is reused.
+    #[prop_or(AttrValue::Borrowed("_blank"))]
+    pub name: AttrValue,
+    /// Features of the window, e.g. `"width=400,height=600"`
+    #[prop_or_default]
+    pub features: Option<AttrValue>,
+    /// Title of the window
+    #[prop_or_default]
+    pub title: Option<String>,
+    /// CSS rules added to the head of the window in `<style>` elements
+    #[prop_or_default]
+    pub styles: Vec<AttrValue>,
+    /// Copies the `<style>` and `<link rel="stylesheet">` elements of the page into the window
+    /// when it is opened
+    #[prop_or_default]
+    pub copy_styles: bool,
+    /// Called with the environment of the window once the children are rendered into it
+    #[prop_or_default]
+    pub onopen: Callback<Environment>,
+    /// Called when the user closes the window. The children are no longer rendered, so the
+    /// parent usually stops rendering the component, or renders the children in the page again.
+    #[prop_or_default]
+    pub onclose: Callback<()>,
+    /// Called when the browser doesn't open the window, e.g. because of a popup blocker
+    #[prop_or_default]
+    pub onblocked: Callback<()>,
+    /// Content rendered into the body of the window
+    #[prop_or_default]
+    pub children: Children,
+}
+
+#[doc(hidden)]
+pub enum PopoutWindowMsg {
+    Opened,
+    Closed,
+}
+
+/// Opens a window and renders its children into it.
+///
+/// Browsers only open windows in response to user interaction, so the component is usually
+/// rendered after a click on a "pop out" button.
+///
+/// # Example
+/// ```rust
+/// # use yew::prelude::*;
+/// use yew::popout::PopoutWindow;
+///
+/// #[derive(Clone, PartialEq, Properties)]
+/// struct ChartProps {
+///     popped_out: bool,
+///     onpopout: Callback<bool>,
+/// }
+///
+/// #[function_component(Chart)]
+/// fn chart(props: &ChartProps) -> Html {
+///     let chart = html! { <canvas width="400" height="300" /> };
+///     if props.popped_out {
+///         let onclose = props.onpopout.reform(|_| false);
+///         html! {
+///             <PopoutWindow title="Chart" features="width=420,height=320" {onclose}>
+///                 { chart }
+///             </PopoutWindow>
+///         }
+///     } else {
+///         let onclick = props.onpopout.reform(|_| true);
+///         html! { <>{ chart }<button {onclick}>{ "Pop out" }</button></> }
+///     }
+/// }
+/// ```
+#[derive(Debug)]
+pub struct PopoutWindow {
+    window: Option<Window>,
+    document: Option<Document>,
+    /// Listeners of the window being closed and of the opener being unloaded
+    listeners: Vec<EventListener>,
+    /// `onopen` is called after the next render
+    opened: bool,
+}
+
+impl PopoutWindow {
+    fn open(&mut self, ctx: &Context<Self>) {
+        let props = ctx.props();
+        let features = props.features.as_deref().unwrap_or("");
+        let opened = window()
+            .open_with_url_and_target_and_features("", &props.name, features)
+            .ok()
+            .flatten();
+        let (opened, document) = match opened.and_then(|w| Some((w.document()?, w))) {
+            Some((document, opened)) => (opened, document),
+            None => {
+                props.onblocked.emit(());
+                return;
+            }
+        };
+
+        if let Some(title) = &props.title {
+            document.set_title(title);
+        }
+        if let (true, Some(head)) = (props.copy_styles, document.head()) {
+            IFramePortal::copy_styles(&document, &head);
+        }
+
+        let link = ctx.link().clone();
+        let closed = EventListener::new(&opened, "pagehide", move |_| {
+            link.send_message(PopoutWindowMsg::Closed)
+        });
+        let unload = {
+            let opened = opened.clone();
+            EventListener::new(&window(), "pagehide", move |_| {
+                let _ = opened.close();
+            })
+        };
+        self.listeners = vec![closed, unload];
+        self.window = Some(opened);
+        self.document = Some(document);
+        ctx.link().send_message(PopoutWindowMsg::Opened);
+    }
+
+    fn close(&mut self) {
+        self.listeners.clear();
+        self.document = None;
+        if let Some(opened) = self.window.take() {
+            let _ = opened.close();
+        }
+    }
+}
+
+impl Component for PopoutWindow {
+    type Message = PopoutWindowMsg;
+    type Properties = PopoutWindowProps;
+
+    fn create(_ctx: &Context<Self>) -> Self {
+        Self {
+            window: None,
+            document: None,
+            listeners: Vec::new(),
+            opened: false,
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            PopoutWindowMsg::Opened => {
+                self.opened = true;
+                true
+            }
+            PopoutWindowMsg::Closed => {
+                self.close();
+                ctx.props().onclose.emit(());
+                true
+            }
+        }
+    }
+
+    fn changed(&mut self, ctx: &Context<Self>) -> bool {
+        if let (Some(document), Some(title)) = (&self.document, &ctx.props().title) {
+            document.set_title(title);
+        }
+        true
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let props = ctx.props();
+        let content = self
+            .document
+            .as_ref()
+            .and_then(|document| Some((document.head()?, document.body()?)))
+            .map(|(head, body)| {
+                let styles = html! {
+                    <>
+                        { for props.styles.iter().map(|css| html! {
+                            <style>{ css.to_string() }</style>
+                        }) }
+                    </>
+                };
+                let children = html! { <>{ for props.children.iter() }</> };
+                html! {
+                    <>
+                        { create_portal(styles, head.into()) }
+                        { create_portal(children, body.into()) }
+                    </>
+                }
+            });
+        html! { <>{ for content }</> }
+    }
+
+    fn rendered(&mut self, ctx: &Context<Self>, first_render: bool) {
+        if first_render {
+            self.open(ctx);
+        }
+        if let (true, Some(document)) = (self.opened, &self.document) {
+            self.opened = false;
+            ctx.props()
+                .onopen
+                .emit(Environment::of_document(document.clone()));
+        }
+    }
+
+    fn destroy(&mut self, _ctx: &Context<Self>) {
+        self.close();
+    }
+}
+
+/// Error of a [`WindowChannel`]
+#[derive(Debug, thiserror::Error)]
+pub enum ChannelError {
+    /// The browser doesn't support `BroadcastChannel` or failed to post the message
+    #[error("BroadcastChannel error: {0:?}")]
+    Js(JsValue),
+    /// A message could not be converted
+    #[error("failed to convert message: {0}")]
+    Serde(#[from] serde_json::Error),
+}
+
+impl From<JsValue> for ChannelError {
+    fn from(err: JsValue) -> Self {
+        Self::Js(err)
+    }
+}
+
+/// Typed messages exchanged between the windows of an origin over a
+/// [`BroadcastChannel`](https://developer.mozilla.org/en-US/docs/Web/API/BroadcastChannel).
+///
+/// Messages are sent to the channels of the same name in all other windows, tabs and iframes of
+/// the origin, and are converted with `serde`. The channel is closed when it is dropped.
+///
+/// # Example
+/// ```rust,no_run
+/// # use yew::prelude::*;
+/// use serde::{Deserialize, Serialize};
+/// use yew::popout::WindowChannel;
+///
+/// #[derive(Serialize, Deserialize)]
+/// enum Selection {
+///     Row(u32),
+///     None,
+/// }
+///
+/// let onmessage = Callback::from(|selection: Selection| {
+///     // Highlight the row selected in another window
+/// });
+/// let channel = WindowChannel::open("selection", onmessage).unwrap();
+/// channel.send(&Selection::Row(3)).unwrap();
+/// ```
+#[derive(Debug)]
+pub struct WindowChannel<T> {
+    channel: BroadcastChannel,
+    _listener: EventListener,
+    _message: PhantomData<fn(T)>,
+}
+
+impl<T: Serialize + DeserializeOwned + 'static> WindowChannel<T> {
+    /// Opens the channel `name`, passing the messages it receives to `onmessage`. Messages which
+    /// can't be converted to `T` are ignored.
+    pub fn open(name: &str, onmessage: Callback<T>) -> Result<Self, ChannelError> {
+        let channel = BroadcastChannel::new(name)?;
+        let listener = EventListener::new(&channel, "message", move |event| {
+            let message = event
+                .dyn_ref::<MessageEvent>()
+                .and_then(|event| event.data().as_string())
+                .and_then(|data| serde_json::from_str(&data).ok());
+            if let Some(message) = message {
+                onmessage.emit(message);
+            }
+        });
+        Ok(Self {
+            channel,
+            _listener: listener,
+            _message: PhantomData,
+        })
+    }
+
+    /// Sends `message` to the channels of the same name in the other windows
+    pub fn send(&self, message: &T) -> Result<(), ChannelError> {
+        let data = serde_json::to_string(message)?;
+        self.channel.post_message(&JsValue::from_str(&data))?;
+        Ok(())
+    }
+
+    /// Returns the name of the channel
+    pub fn name(&self) -> String {
+        self.channel.name()
+    }
+}
+
+impl<T> Drop for WindowChannel<T> {
+    fn drop(&mut self) {
+        self.channel.close();
+    }
+}
+
+#[cfg(all(test, feature = "wasm_test"))]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+    use wasm_bindgen_futures::JsFuture;
+    use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    #[test]
+    async fn channels_of_the_same_name_exchange_messages() {
+        let received = Rc::new(RefCell::new(Vec::new()));
+        let _receiver = {
+            let received = Rc::clone(&received);
+            WindowChannel::<(u32, String)>::open(
+                "yew-test-channel",
+                Callback::from(move |message| received.borrow_mut().push(message)),
+            )
+            .unwrap()
+        };
+        let sender = WindowChannel::open("yew-test-channel", Callback::noop()).unwrap();
+        sender.send(&(1, "one".to_owned())).unwrap();
+
+        // Messages are delivered in a task of their own
+        let delivered = js_sys::Promise::new(&mut |resolve, _| {
+            window()
+                .set_timeout_with_callback_and_timeout_and_arguments_0(&resolve, 50)
+                .unwrap();
+        });
+        JsFuture::from(delivered).await.unwrap();
+        assert_eq!(*received.borrow(), vec![(1, "one".to_owned())]);
+    }
+}
@@ -0,0 +1,506 @@
+//! Floating elements positioned next to an anchor, like popovers and tooltips.
+//!
+//! [`compute_position`] places a floating element on a side of an anchor. If it doesn't fit in
+//! the viewport there, it flips to the opposite side and then shifts along the side to stay in
+//! view. [`Popover`] renders its children into the body through a
+//! [portal](crate::create_portal) at the computed position, which it updates whenever the page
+//! scrolls or the window is resized.
+
+use crate::html::{Component, Context, Html, NodeRef};
+use crate::utils::{document, window};
+use crate::{classes, create_portal, html, Children, Properties};
+use gloo::events::{EventListener, EventListenerOptions, EventListenerPhase};
+use web_sys::Element;
+
+/// Side of the anchor a floating element is placed on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Side {
+    /// Above the anchor
+    Top,
+    /// Below the anchor
+    Bottom,
+    /// Left of the anchor
+    Left,
+    /// Right of the anchor
+    Right,
+}
+
+impl Side {
+    fn opposite(self) -> Self {
+        match self {
+            Self::Top => Self::Bottom,
+            Self::Bottom => Self::Top,
+            Self::Left => Self::Right,
+            Self::Right => Self::Left,
+        }
+    }
+
+    fn is_vertical(self) -> bool {
+        matches!(self, Self::Top | Self::Bottom)
+    }
+
+    /// Returns the name of the side, e.g. for CSS
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Top => "top",
+            Self::Bottom => "bottom",
+            Self::Left => "left",
+            Self::Right => "right",
+        }
+    }
+}
+
+/// Alignment of a floating element along the side of the anchor
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Align {
+    /// Aligned with the start of the anchor, i.e. its left or top edge
+    Start,
+    /// Centered on the anchor
+    Center,
+    /// Aligned with the end of the anchor, i.e. its right or bottom edge
+    End,
+}
+
+/// Where a floating element is placed relative to its anchor
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Placement {
+    /// Side of the anchor
+    pub side: Side,
+    /// Alignment along the side
+    pub align: Align,
+}
+
+impl Placement {
+    /// Creates a placement
+    pub const fn new(side: Side, align: Align) -> Self {
+        Self { side, align }
+    }
+}
+
+impl Default for Placement {
+    fn default() -> Self {
+        Self::new(Side::Bottom, Align::Start)
+    }
+}
+
+/// A rectangle in viewport coordinates
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Rect {
+    /// Left edge
+    pub x: f64,
+    /// Top edge
+    pub y: f64,
+    /// Width
+    pub width: f64,
+    /// Height
+    pub height: f64,
+}
+
+impl Rect {
+    /// Returns the bounding rectangle of `element`
+    pub fn of(element: &Element) -> Self {
+        let rect = element.get_bounding_client_rect();
+        Self {
+            x: rect.x(),
+            y: rect.y(),
+            width: rect.width(),
+            height: rect.height(),
+        }
+    }
+
+    /// Returns the rectangle of the viewport
+    pub fn viewport() -> Self {
+        let window = window();
+        let size = |value: Result<wasm_bindgen::JsValue, _>| {
+            value.ok().and_then(|value| value.as_f64()).unwrap_or(0.0)
+        };
+        Self {
+            x: 0.0,
+            y: 0.0,
+            width: size(window.inner_width()),
+            height: size(window.inner_height()),
+        }
+    }
+}
+
+/// Position computed by [`compute_position`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Position {
+    /// Left edge of the floating element
+    pub x: f64,
+    /// Top edge of the floating element
+    pub y: f64,
+    /// The placement that was used, which has the opposite side if the element was flipped
+    pub placement: Placement,
+}
+
+/// Options of [`compute_position`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PositionOptions {
+    /// Preferred placement
+    pub placement: Placement,
+    /// Distance between the anchor and the floating element
+    pub offset: f64,
+    /// Minimum distance between the floating element and the edges of the viewport
+    pub padding: f64,
+    /// Place the element on the opposite side if it doesn't fit on the preferred one
+    pub flip: bool,
+    /// Move the element along the side to keep it in the viewport
+    pub shift: bool,
+}
+
+impl Default for PositionOptions {
+    fn default() -> Self {
+        Self {
+            placement: Placement::default(),
+            offset: 8.0,
+            padding: 8.0,
+            flip: true,
+            shift: true,
+        }
+    }
+}
+
+/// Returns the position of a floating element of `size` (width, height) next to `anchor`
+pub fn compute_position(
+    anchor: Rect,
+    size: (f64, f64),
+    viewport: Rect,
+    options: PositionOptions,
+) -> Position {
+    let PositionOptions {
+        placement,
+        offset,
+        padding,
+        flip,
+        shift,
+    } = options;
+    let (width, height) = size;
+
+    let main_axis = |side: Side| match side {
+        Side::Top => anchor.y - offset - height,
+        Side::Bottom => anchor.y + anchor.height + offset,
+        Side::Left => anchor.x - offset - width,
+        Side::Right => anchor.x + anchor.width + offset,
+    };
+    // Space left between the element and the edge of the viewport, negative if it overflows
+    let space = |side: Side| {
+        let position = main_axis(side);
+        match side {
+            Side::Top => position - viewport.y - padding,
+            Side::Bottom => viewport.y + viewport.height - padding - (position + height),
+            Side::Left => position - viewport.x - padding,
+            Side::Right => viewport.x + viewport.width - padding - (position + width),
+        }
+    };
+
+    let mut side = placement.side;
+    if flip && space(side) < 0.0 && space(side.opposite()) > space(side) {
+        side = side.opposite();
+    }
+
+    let (start, length, size, min, max) = if side.is_vertical() {
+        (
+            anchor.x,
+            anchor.width,
+            width,
+            viewport.x,
+            viewport.x + viewport.width,
+        )
+    } else {
+        (
+            anchor.y,
+            anchor.height,
+            height,
+            viewport.y,
+            viewport.y + viewport.height,
+        )
+    };
+    let mut cross = match placement.align {
+        Align::Start => start,
+        Align::Center => start + (length - size) / 2.0,
+        Align::End => start + length - size,
+    };
+    if shift {
+        // Elements larger than the viewport stay at its start
+        cross = cross.min(max - padding - size).max(min + padding);
+    }
+
+    let main = main_axis(side);
+    let (x, y) = if side.is_vertical() {
+        (cross, main)
+    } else {
+        (main, cross)
+    };
+    Position {
+        x,
+        y,
+        placement: Placement::new(side, placement.align),
+    }
+}
+
+/// Props for [`Popover`]
+#[derive(Debug, Clone, PartialEq, Properties)]
+pub struct PopoverProps {
+    /// The element the popover is placed next to
+    pub anchor: NodeRef,
+    /// The popover is shown
+    #[prop_or(true)]
+    pub open: bool,
+    /// How the popover is positioned
+    #[prop_or_default]
+    pub options: PositionOptions,
+    /// Classes of the popover
+    #[prop_or_default]
+    pub class: Option<String>,
+    /// ARIA role of the popover, e.g. `tooltip`
+    #[prop_or_default]
+    pub role: Option<String>,
+    /// Content of the popover
+    #[prop_or_default]
+    pub children: Children,
+}
+
+#[doc(hidden)]
+pub enum PopoverMsg {
+    Update,
+}
+
+/// A floating element rendered into the body next to its anchor.
+///
+/// The popover has `position: fixed` and the side it was placed on in `data-side`, e.g. to
+/// point an arrow at the anchor. It is hidden until its position is known.
+///
+/// # Example
+/// ```rust
+/// # use yew::prelude::*;
+/// use yew::popover::{Align, Placement, Popover, PositionOptions, Side};
+///
+/// #[function_component(Help)]
+/// fn help() -> Html {
+///     let anchor = use_state(NodeRef::default);
+///     let open = use_state(|| false);
+///     let (show, hide) = {
+///         let (a, b) = (open.clone(), open.clone());
+///         (Callback::from(move |_| a.set(true)), Callback::from(move |_| b.set(false)))
+///     };
+///     let options = PositionOptions {
+///         placement: Placement::new(Side::Top, Align::Center),
+///         ..Default::default()
+///     };
+///     html! {
+///         <>
+///             <button ref={(*anchor).clone()} onmouseenter={show} onmouseleave={hide}>
+///                 { "?" }
+///             </button>
+///             <Popover anchor={(*anchor).clone()} open={*open} {options} role="tooltip">
+///                 { "Shows the help" }
+///             </Popover>
+///         </>
+///     }
+/// }
+/// ```
+#[derive(Debug)]
+pub struct Popover {
+    host: Element,
+    floating: NodeRef,
+    listeners: Vec<EventListener>,
+}
+
+impl Popover {
+    fn update_position(&self, ctx: &Context<Self>) {
+        let props = ctx.props();
+        let (anchor, floating) = match (
+            props.anchor.cast::<Element>(),
+            self.floating.cast::<Element>(),
+        ) {
+            (Some(anchor), Some(floating)) => (anchor, floating),
+            _ => return,
+        };
+        let size = Rect::of(&floating);
+        let position = compute_position(
+            Rect::of(&anchor),
+            (size.width, size.height),
+            Rect::viewport(),
+            props.options,
+        );
+        // The style is not rendered, so the differ leaves it alone
+        let style = format!(
+            "position: fixed; left: {}px; top: {}px",
+            position.x.round(),
+            position.y.round()
+        );
+        let _ = floating.set_attribute("style", &style);
+        let _ = floating.set_attribute("data-side", position.placement.side.as_str());
+    }
+
+    fn listen(&mut self, ctx: &Context<Self>) {
+        if !ctx.props().open {
+            self.listeners.clear();
+            return;
+        }
+        if !self.listeners.is_empty() {
+            return;
+        }
+        // Scrolling of any ancestor of the anchor is caught in the capture phase
+        let options = EventListenerOptions {
+            phase: EventListenerPhase::Capture,
+            passive: true,
+        };
+        let window = window();
+        self.listeners = ["scroll", "resize"]
+            .iter()
+            .map(|event| {
+                let link = ctx.link().clone();
+                EventListener::new_with_options(&window, *event, options, move |_| {
+                    link.send_message(PopoverMsg::Update)
+                })
+            })
+            .collect();
+    }
+}
+
+impl Component for Popover {
+    type Message = PopoverMsg;
+    type Properties = PopoverProps;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let mut popover = Self {
+            host: document().body().expect("no body").into(),
+            floating: NodeRef::default(),
+            listeners: Vec::new(),
+        };
+        popover.listen(ctx);
+        popover
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        let PopoverMsg::Update = msg;
+        self.update_position(ctx);
+        false
+    }
+
+    fn changed(&mut self, ctx: &Context<Self>) -> bool {
+        self.listen(ctx);
+        true
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let props = ctx.props();
+        if !props.open {
+            return Html::default();
+        }
+        let popover = html! {
+            <div
+                ref={self.floating.clone()}
+                class={classes!("yew-popover", props.class.clone())}
+                role={props.role.clone()}
+            >
+                { for props.children.iter() }
+            </div>
+        };
+        create_portal(popover, self.host.clone())
+    }
+
+    fn rendered(&mut self, ctx: &Context<Self>, _first_render: bool) {
+        if let Some(floating) = self.floating.cast::<Element>() {
+            // Hidden until the first position is known
+            if !floating.has_attribute("style") {
+                let _ = floating.set_attribute("style", "position: fixed; visibility: hidden");
+            }
+        }
+        self.update_position(ctx);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "wasm_test")]
+    use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+
+    #[cfg(feature = "wasm_test")]
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    const VIEWPORT: Rect = Rect {
+        x: 0.0,
+        y: 0.0,
+        width: 800.0,
+        height: 600.0,
+    };
+
+    fn anchor(x: f64, y: f64) -> Rect {
+        Rect {
+            x,
+            y,
+            width: 100.0,
+            height: 20.0,
+        }
+    }
+
+    fn options(side: Side, align: Align) -> PositionOptions {
+        PositionOptions {
+            placement: Placement::new(side, align),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn places_on_preferred_side() {
+        let position = compute_position(
+            anchor(100.0, 100.0),
+            (50.0, 30.0),
+            VIEWPORT,
+            options(Side::Bottom, Align::Center),
+        );
+        assert_eq!(
+            position,
+            Position {
+                x: 125.0,
+                y: 128.0,
+                placement: Placement::new(Side::Bottom, Align::Center),
+            }
+        );
+    }
+
+    #[test]
+    fn flips_when_out_of_view() {
+        let position = compute_position(
+            anchor(100.0, 560.0),
+            (50.0, 60.0),
+            VIEWPORT,
+            options(Side::Bottom, Align::Start),
+        );
+        assert_eq!(position.placement.side, Side::Top);
+        assert_eq!(position.y, 492.0);
+
+        // Neither side fits better, so it stays
+        let position = compute_position(
+            anchor(100.0, 290.0),
+            (50.0, 400.0),
+            VIEWPORT,
+            options(Side::Bottom, Align::Start),
+        );
+        assert_eq!(position.placement.side, Side::Bottom);
+    }
+
+    #[test]
+    fn shifts_into_view() {
+        let position = compute_position(
+            anchor(760.0, 100.0),
+            (200.0, 30.0),
+            VIEWPORT,
+            options(Side::Bottom, Align::Start),
+        );
+        assert_eq!(position.x, 592.0);
+
+        let position = compute_position(
+            anchor(200.0, 0.0),
+            (100.0, 100.0),
+            VIEWPORT,
+            options(Side::Right, Align::End),
+        );
+        assert_eq!((position.x, position.y), (308.0, 8.0));
+    }
+}
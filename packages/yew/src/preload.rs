@@ -0,0 +1,427 @@
+//! Data loaded before the components that need it are mounted.
+//!
+//! A [`Preload`] type describes how the data for an input is loaded, where the input is e.g. a
+//! route or an id from the props of a page. [`Preloaded`] renders its fallback until the data for
+//! its input is ready, then renders its children and provides the data to them as a
+//! [`Prepared`] context. The children get it with [`use_prepared`] or [`Scope::prepared`], so
+//! they are created with their data instead of loading it themselves.
+//!
+//! [`preload`] starts loading ahead of time, e.g. when a navigation starts or a link is hovered.
+//! Loads are shared: a `Preloaded` mounted while its data is being preloaded waits for the same
+//! load, and data that is already loaded is handed to the next `Preloaded` with the same input
+//! and then dropped from the cache.
+//!
+//! Components that load their own data with no need to share it can use an
+//! [`AsyncComponent`](crate::html::AsyncComponent) instead.
+
+use crate::context::ContextProvider;
+use crate::functional::use_context;
+use crate::html::Scope;
+use crate::{html, Callback, Children, Component, Context, Html, Properties};
+use std::any::{Any, TypeId};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt;
+use std::future::Future;
+use std::ops::Deref;
+use std::pin::Pin;
+use std::rc::Rc;
+use wasm_bindgen_futures::spawn_local;
+
+/// Loads the data for an input before it is rendered.
+///
+/// # Example
+/// ```rust
+/// use std::future::Future;
+/// use std::pin::Pin;
+/// use yew::preload::Preload;
+///
+/// # async fn fetch_user(id: u32) -> String { id.to_string() }
+/// pub struct UserData;
+///
+/// impl Preload for UserData {
+///     type Input = u32;
+///     type Data = String;
+///
+///     fn preload(id: &u32) -> Pin<Box<dyn Future<Output = String>>> {
+///         Box::pin(fetch_user(*id))
+///     }
+/// }
+/// ```
+pub trait Preload: 'static {
+    /// What the data is loaded for. Loads for equal inputs are shared.
+    type Input: Clone + PartialEq + 'static;
+
+    /// The loaded data
+    type Data: 'static;
+
+    /// Returns the future loading the data for `input`
+    fn preload(input: &Self::Input) -> Pin<Box<dyn Future<Output = Self::Data>>>;
+}
+
+enum State<P: Preload> {
+    Loading(Vec<Callback<Rc<P::Data>>>),
+    Ready(Rc<P::Data>),
+}
+
+struct Entry<P: Preload> {
+    input: P::Input,
+    state: State<P>,
+}
+
+thread_local! {
+    /// `Vec<Entry<P>>` by the type id of `P`
+    static CACHE: RefCell<HashMap<TypeId, Box<dyn Any>>> = RefCell::new(HashMap::new());
+}
+
+fn with_entries<P: Preload, R>(f: impl FnOnce(&mut Vec<Entry<P>>) -> R) -> R {
+    CACHE.with(|cache| {
+        let mut cache = cache.borrow_mut();
+        let entries = cache
+            .entry(TypeId::of::<P>())
+            .or_insert_with(|| Box::new(Vec::<Entry<P>>::new()));
+        f(entries.downcast_mut().expect("preload cache of wrong type"))
+    })
+}
+
+/// Returns the data for `input` if it is loaded, removing it from the cache. Otherwise `callback`
+/// is called with it once it is, starting the load if needed.
+fn load<P: Preload>(
+    input: &P::Input,
+    callback: Option<Callback<Rc<P::Data>>>,
+) -> Option<Rc<P::Data>> {
+    let start = with_entries::<P, _>(|entries| {
+        let index = match entries.iter().position(|entry| entry.input == *input) {
+            Some(index) => index,
+            None => {
+                entries.push(Entry {
+                    input: input.clone(),
+                    state: State::Loading(callback.into_iter().collect()),
+                });
+                return Ok(true);
+            }
+        };
+        match (&mut entries[index].state, callback) {
+            (State::Loading(callbacks), callback) => callbacks.extend(callback),
+            (State::Ready(data), Some(_)) => {
+                let data = Rc::clone(data);
+                entries.remove(index);
+                return Err(data);
+            }
+            (State::Ready(_), None) => (),
+        }
+        Ok(false)
+    });
+
+    match start {
+        Ok(true) => {
+            let input = input.clone();
+            let future = P::preload(&input);
+            spawn_local(async move {
+                let data = Rc::new(future.await);
+                let callbacks = with_entries::<P, _>(|entries| {
+                    let index = entries.iter().position(|entry| entry.input == input)?;
+                    match &entries[index].state {
+                        // Kept for the next `Preloaded`
+                        State::Loading(callbacks) if callbacks.is_empty() => {
+                            entries[index].state = State::Ready(Rc::clone(&data));
+                            None
+                        }
+                        State::Loading(_) => match entries.remove(index).state {
+                            State::Loading(callbacks) => Some(callbacks),
+                            State::Ready(_) => None,
+                        },
+                        State::Ready(_) => None,
+                    }
+                });
+                for callback in callbacks.into_iter().flatten() {
+                    callback.emit(Rc::clone(&data));
+                }
+            });
+            None
+        }
+        Ok(false) => None,
+        Err(data) => Some(data),
+    }
+}
+
+/// Starts loading the data for `input`, unless it is already loading or loaded
+pub fn preload<P: Preload>(input: P::Input) {
+    load::<P>(&input, None);
+}
+
+/// Drops the data loaded with [`preload`] for `P` that no [`Preloaded`] has taken yet
+pub fn clear<P: Preload>() {
+    with_entries::<P, _>(|entries| {
+        entries.retain(|entry| matches!(entry.state, State::Loading(_)));
+    });
+}
+
+/// Data loaded by `P`, provided by [`Preloaded`]
+pub struct Prepared<P: Preload>(Rc<P::Data>);
+
+impl<P: Preload> Prepared<P> {
+    /// Returns the shared data
+    pub fn rc(&self) -> &Rc<P::Data> {
+        &self.0
+    }
+}
+
+impl<P: Preload> Deref for Prepared<P> {
+    type Target = P::Data;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<P: Preload> Clone for Prepared<P> {
+    fn clone(&self) -> Self {
+        Self(Rc::clone(&self.0))
+    }
+}
+
+impl<P: Preload> PartialEq for Prepared<P> {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl<P: Preload> fmt::Debug for Prepared<P>
+where
+    P::Data: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Prepared").field(&self.0).finish()
+    }
+}
+
+/// Returns the data of the closest [`Preloaded`] of `P`.
+///
+/// # Panics
+/// Panics if the component is not a descendant of a `Preloaded<P>`.
+pub fn use_prepared<P: Preload>() -> Prepared<P> {
+    use_context::<Prepared<P>>().expect("use_prepared must be used inside a Preloaded")
+}
+
+impl<COMP: Component> Scope<COMP> {
+    /// Returns the data of the closest [`Preloaded`] of `P`, or `None` if the component is not
+    /// a descendant of one
+    pub fn prepared<P: Preload>(&self) -> Option<Prepared<P>> {
+        let (prepared, _handle) = self.context::<Prepared<P>>(Callback::noop())?;
+        Some(prepared)
+    }
+}
+
+/// Props for [`Preloaded`]
+#[derive(Properties)]
+pub struct PreloadedProps<P: Preload> {
+    /// What the data is loaded for
+    pub input: P::Input,
+    /// Rendered while the data is loading
+    #[prop_or_default]
+    pub fallback: Html,
+    /// Rendered with the data
+    #[prop_or_default]
+    pub children: Children,
+}
+
+impl<P: Preload> PartialEq for PreloadedProps<P> {
+    fn eq(&self, other: &Self) -> bool {
+        self.input == other.input
+            && self.fallback == other.fallback
+            && self.children == other.children
+    }
+}
+
+#[doc(hidden)]
+pub enum PreloadedMsg<P: Preload> {
+    Ready(u32, Rc<P::Data>),
+}
+
+/// Renders its children once the data of `P` for its input is loaded, see the
+/// [module docs](self).
+///
+/// # Example
+/// ```rust
+/// # use yew::prelude::*;
+/// # use std::{future::Future, pin::Pin};
+/// use yew::preload::{use_prepared, Preload, Preloaded};
+///
+/// # async fn fetch_user(id: u32) -> String { id.to_string() }
+/// pub struct UserData;
+///
+/// impl Preload for UserData {
+///     type Input = u32;
+///     type Data = String;
+///
+///     fn preload(id: &u32) -> Pin<Box<dyn Future<Output = String>>> {
+///         Box::pin(fetch_user(*id))
+///     }
+/// }
+///
+/// #[function_component(UserName)]
+/// fn user_name() -> Html {
+///     let name = use_prepared::<UserData>();
+///     html! { <h1>{ &*name }</h1> }
+/// }
+///
+/// # fn view() -> Html {
+/// html! {
+///     <Preloaded<UserData> input=42 fallback={html! { <p>{ "Loading…" }</p> }}>
+///         <UserName />
+///     </Preloaded<UserData>>
+/// }
+/// # }
+/// ```
+pub struct Preloaded<P: Preload> {
+    input: P::Input,
+    /// Incremented whenever the input changes, to discard stale results
+    generation: u32,
+    prepared: Option<Prepared<P>>,
+}
+
+impl<P: Preload> Preloaded<P> {
+    fn load(&mut self, ctx: &Context<Self>) {
+        let generation = self.generation;
+        let callback = ctx
+            .link()
+            .callback(move |data| PreloadedMsg::Ready(generation, data));
+        self.prepared = load::<P>(&self.input, Some(callback)).map(Prepared);
+    }
+}
+
+impl<P: Preload> Component for Preloaded<P> {
+    type Message = PreloadedMsg<P>;
+    type Properties = PreloadedProps<P>;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let mut preloaded = Self {
+            input: ctx.props().input.clone(),
+            generation: 0,
+            prepared: None,
+        };
+        preloaded.load(ctx);
+        preloaded
+    }
+
+    fn update(&mut self, _ctx: &Context<Self>, msg: Self::Message) -> bool {
+        let PreloadedMsg::Ready(generation, data) = msg;
+        if generation != self.generation {
+            return false;
+        }
+        self.prepared = Some(Prepared(data));
+        true
+    }
+
+    fn changed(&mut self, ctx: &Context<Self>) -> bool {
+        if ctx.props().input != self.input {
+            self.input = ctx.props().input.clone();
+            self.generation += 1;
+            self.load(ctx);
+        }
+        true
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let props = ctx.props();
+        match &self.prepared {
+            Some(prepared) => html! {
+                <ContextProvider<Prepared<P>> context={prepared.clone()}>
+                    { for props.children.iter() }
+                </ContextProvider<Prepared<P>>>
+            },
+            None => props.fallback.clone(),
+        }
+    }
+}
+
+#[cfg(all(test, feature = "wasm_test"))]
+mod tests {
+    extern crate self as yew;
+
+    use super::*;
+    use crate::html::NodeRef;
+    use crate::utils::document;
+    use std::cell::Cell;
+    use wasm_bindgen::JsValue;
+    use wasm_bindgen_futures::JsFuture;
+    use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    thread_local! {
+        static LOADS: Cell<u32> = Cell::new(0);
+    }
+
+    struct Doubled;
+
+    impl Preload for Doubled {
+        type Input = u32;
+        type Data = u32;
+
+        fn preload(input: &u32) -> Pin<Box<dyn Future<Output = u32>>> {
+            LOADS.with(|loads| loads.set(loads.get() + 1));
+            let input = *input;
+            Box::pin(async move { input * 2 })
+        }
+    }
+
+    struct Show;
+
+    impl Component for Show {
+        type Message = ();
+        type Properties = ();
+
+        fn create(_ctx: &Context<Self>) -> Self {
+            Self
+        }
+
+        fn view(&self, ctx: &Context<Self>) -> Html {
+            let prepared = ctx.link().prepared::<Doubled>().unwrap();
+            html! { { *prepared } }
+        }
+    }
+
+    /// Lets the spawned futures run
+    async fn tick() {
+        JsFuture::from(js_sys::Promise::resolve(&JsValue::NULL))
+            .await
+            .unwrap();
+    }
+
+    fn mount(input: u32) -> web_sys::Element {
+        let el = document().create_element("div").unwrap();
+        let props = PreloadedProps::<Doubled> {
+            input,
+            fallback: Html::from("loading"),
+            children: Children::new(vec![html! { <Show /> }]),
+        };
+        Scope::<Preloaded<Doubled>>::new(None).mount_in_place(
+            el.clone(),
+            NodeRef::default(),
+            NodeRef::default(),
+            Rc::new(props),
+        );
+        el
+    }
+
+    #[test]
+    async fn preloaded_data_is_taken_once() {
+        preload::<Doubled>(21);
+        tick().await;
+        assert_eq!(LOADS.with(Cell::get), 1);
+
+        // Mounted with the data that is already loaded
+        let el = mount(21);
+        assert_eq!(el.text_content().as_deref(), Some("42"));
+        assert_eq!(LOADS.with(Cell::get), 1);
+
+        // The data was taken, so it is loaded again
+        let el = mount(21);
+        assert_eq!(el.text_content().as_deref(), Some("loading"));
+        tick().await;
+        assert_eq!(el.text_content().as_deref(), Some("42"));
+        assert_eq!(LOADS.with(Cell::get), 2);
+    }
+}
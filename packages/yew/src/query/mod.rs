@@ -0,0 +1,567 @@
+//! Cached queries of remote data.
+//!
+//! A query is identified by a string key and loaded by an async fetcher. The results are cached
+//! on the current thread and shared by all subscribers of the key:
+//!
+//! * Subscribing while the same key is being fetched doesn't start another request.
+//! * Cached data is returned right away. If it is older than
+//!   [`stale_time_ms`](QueryOptions::stale_time_ms), it is refetched in the background and the
+//!   subscribers get the new data when it arrives ("stale-while-revalidate").
+//! * Stale queries are refetched when the window gets the focus or the browser goes online again.
+//! * [`invalidate`] marks queries as stale, e.g. after a mutation, and refetches them if they are
+//!   subscribed to. [`set_data`] replaces the cached data without a request.
+//!
+//! Struct components [`subscribe`] with a callback of their scope and keep the [`QueryHandle`].
+//! Function components use [`use_query`].
+//!
+//! Changes that have to reach a server even if the browser is offline go through the
+//! [`mutation`] queue.
+
+pub mod mutation;
+
+use crate::callback::Callback;
+use crate::functional::use_hook;
+use crate::utils::window;
+use gloo::events::EventListener;
+use slab::Slab;
+use std::any::Any;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt;
+use std::future::Future;
+use std::marker::PhantomData;
+use std::rc::Rc;
+use wasm_bindgen_futures::spawn_local;
+
+/// State of a query
+pub struct QueryState<T, E> {
+    /// Data of the last successful fetch
+    pub data: Option<Rc<T>>,
+    /// Error of the last fetch, if it failed
+    pub error: Option<Rc<E>>,
+    /// A fetch is in progress
+    pub fetching: bool,
+}
+
+impl<T, E> QueryState<T, E> {
+    /// Returns whether the query is loaded for the first time
+    pub fn is_loading(&self) -> bool {
+        self.data.is_none() && self.error.is_none()
+    }
+}
+
+impl<T, E> Default for QueryState<T, E> {
+    fn default() -> Self {
+        Self {
+            data: None,
+            error: None,
+            fetching: false,
+        }
+    }
+}
+
+impl<T, E> Clone for QueryState<T, E> {
+    fn clone(&self) -> Self {
+        Self {
+            data: self.data.clone(),
+            error: self.error.clone(),
+            fetching: self.fetching,
+        }
+    }
+}
+
+/// States are equal if they hold the same results
+impl<T, E> PartialEq for QueryState<T, E> {
+    fn eq(&self, other: &Self) -> bool {
+        fn same<T>(a: &Option<Rc<T>>, b: &Option<Rc<T>>) -> bool {
+            match (a, b) {
+                (Some(a), Some(b)) => Rc::ptr_eq(a, b),
+                (None, None) => true,
+                _ => false,
+            }
+        }
+        same(&self.data, &other.data)
+            && same(&self.error, &other.error)
+            && self.fetching == other.fetching
+    }
+}
+
+impl<T: fmt::Debug, E: fmt::Debug> fmt::Debug for QueryState<T, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("QueryState")
+            .field("data", &self.data)
+            .field("error", &self.error)
+            .field("fetching", &self.fetching)
+            .finish()
+    }
+}
+
+/// Options of a subscription to a query
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct QueryOptions {
+    /// Milliseconds after which fetched data is stale and refetched
+    pub stale_time_ms: f64,
+    /// Refetch stale data when the window gets the focus
+    pub refetch_on_focus: bool,
+    /// Refetch stale data when the browser goes online
+    pub refetch_on_online: bool,
+}
+
+impl Default for QueryOptions {
+    fn default() -> Self {
+        Self {
+            stale_time_ms: 0.,
+            refetch_on_focus: true,
+            refetch_on_online: true,
+        }
+    }
+}
+
+type Subscriber = Rc<dyn Fn(Rc<dyn Any>)>;
+
+struct Entry {
+    /// The `QueryState<T, E>` of the query
+    state: Rc<dyn Any>,
+    fetched_at: Option<f64>,
+    invalidated: bool,
+    /// Starts a fetch with the fetcher of the latest subscriber
+    fetch: Rc<dyn Fn()>,
+    subscribers: Slab<(Subscriber, QueryOptions)>,
+}
+
+impl Entry {
+    fn is_stale(&self, stale_time_ms: f64) -> bool {
+        match self.fetched_at {
+            Some(fetched_at) => {
+                self.invalidated || js_sys::Date::now() - fetched_at >= stale_time_ms
+            }
+            None => true,
+        }
+    }
+
+    /// Returns whether a subscriber matching `filter` considers the entry stale
+    fn needs_refetch(&self, filter: impl Fn(&QueryOptions) -> bool) -> bool {
+        self.subscribers
+            .iter()
+            .any(|(_, (_, options))| filter(options) && self.is_stale(options.stale_time_ms))
+    }
+}
+
+#[derive(Default)]
+struct Registry {
+    entries: HashMap<String, Entry>,
+    listeners: Vec<EventListener>,
+}
+
+thread_local! {
+    static REGISTRY: RefCell<Registry> = Default::default();
+}
+
+/// Starts the fetches of the entries matching `filter`. The registry must not be borrowed.
+fn refetch_where(filter: impl Fn(&Entry) -> bool) {
+    let fetches: Vec<Rc<dyn Fn()>> = REGISTRY.with(|registry| {
+        registry
+            .borrow()
+            .entries
+            .values()
+            .filter(|entry| filter(entry))
+            .map(|entry| Rc::clone(&entry.fetch))
+            .collect()
+    });
+    for fetch in fetches {
+        fetch();
+    }
+}
+
+fn install_listeners(registry: &mut Registry) {
+    if !registry.listeners.is_empty() {
+        return;
+    }
+    let window = window();
+    registry.listeners = vec![
+        EventListener::new(&window, "focus", |_| {
+            refetch_where(|entry| entry.needs_refetch(|options| options.refetch_on_focus))
+        }),
+        EventListener::new(&window, "online", |_| {
+            refetch_where(|entry| entry.needs_refetch(|options| options.refetch_on_online))
+        }),
+    ];
+}
+
+/// Replaces the state of the query `key` and notifies its subscribers
+fn set_state<T: 'static, E: 'static>(
+    key: &str,
+    f: impl FnOnce(&mut Entry, QueryState<T, E>) -> QueryState<T, E>,
+) {
+    let notify = REGISTRY.with(|registry| {
+        let mut registry = registry.borrow_mut();
+        let entry = registry.entries.get_mut(key)?;
+        let old = entry.state.downcast_ref::<QueryState<T, E>>()?.clone();
+        let state: Rc<dyn Any> = Rc::new(f(entry, old));
+        entry.state = Rc::clone(&state);
+        let subscribers: Vec<Subscriber> = entry
+            .subscribers
+            .iter()
+            .map(|(_, (subscriber, _))| Rc::clone(subscriber))
+            .collect();
+        Some((state, subscribers))
+    });
+    if let Some((state, subscribers)) = notify {
+        for subscriber in subscribers {
+            subscriber(Rc::clone(&state));
+        }
+    }
+}
+
+fn fetch<T, E, F, Fut>(key: &str, fetcher: &F)
+where
+    T: 'static,
+    E: 'static,
+    F: Fn() -> Fut,
+    Fut: Future<Output = Result<T, E>> + 'static,
+{
+    let fetching = REGISTRY.with(|registry| {
+        registry
+            .borrow()
+            .entries
+            .get(key)
+            .and_then(|entry| entry.state.downcast_ref::<QueryState<T, E>>())
+            .map_or(true, |state| state.fetching)
+    });
+    // Concurrent requests are deduplicated
+    if fetching {
+        return;
+    }
+    set_state::<T, E>(key, |_, state| QueryState {
+        fetching: true,
+        ..state
+    });
+
+    let key = key.to_owned();
+    let future = fetcher();
+    spawn_local(async move {
+        let result = future.await;
+        set_state::<T, E>(&key, move |entry, state| match result {
+            Ok(data) => {
+                entry.fetched_at = Some(js_sys::Date::now());
+                entry.invalidated = false;
+                QueryState {
+                    data: Some(Rc::new(data)),
+                    error: None,
+                    fetching: false,
+                }
+            }
+            Err(error) => QueryState {
+                error: Some(Rc::new(error)),
+                fetching: false,
+                ..state
+            },
+        });
+    });
+}
+
+/// Subscribes to the query `key`, which is loaded with `fetcher`.
+///
+/// `callback` is called with the state of the query whenever it changes. The query is fetched
+/// unless fresh data is cached or it is being fetched already. The subscription ends when the
+/// returned handle is dropped.
+///
+/// All subscribers of a key must use the same data and error types. Refetches use the fetcher of
+/// the latest subscriber.
+pub fn subscribe<T, E, F, Fut>(
+    key: impl Into<String>,
+    fetcher: F,
+    options: QueryOptions,
+    callback: Callback<QueryState<T, E>>,
+) -> QueryHandle<T, E>
+where
+    T: 'static,
+    E: 'static,
+    F: Fn() -> Fut + 'static,
+    Fut: Future<Output = Result<T, E>> + 'static,
+{
+    let key = key.into();
+    let fetch_fn: Rc<dyn Fn()> = {
+        let key = key.clone();
+        Rc::new(move || fetch(&key, &fetcher))
+    };
+    let subscriber: Subscriber =
+        Rc::new(
+            move |state: Rc<dyn Any>| match state.downcast_ref::<QueryState<T, E>>() {
+                Some(state) => callback.emit(state.clone()),
+                None => log::error!("query subscribed to with different types"),
+            },
+        );
+
+    let (id, stale) = REGISTRY.with(|registry| {
+        let mut registry = registry.borrow_mut();
+        install_listeners(&mut registry);
+        let entry = registry
+            .entries
+            .entry(key.clone())
+            .or_insert_with(|| Entry {
+                state: Rc::new(QueryState::<T, E>::default()),
+                fetched_at: None,
+                invalidated: false,
+                fetch: Rc::clone(&fetch_fn),
+                subscribers: Slab::new(),
+            });
+        entry.fetch = Rc::clone(&fetch_fn);
+        let id = entry.subscribers.insert((subscriber, options));
+        (id, entry.is_stale(options.stale_time_ms))
+    });
+    if stale {
+        fetch_fn();
+    }
+
+    QueryHandle {
+        key,
+        id,
+        _marker: PhantomData,
+    }
+}
+
+/// Handle of a subscription made with [`subscribe`]. The subscription ends when it is dropped.
+pub struct QueryHandle<T, E> {
+    key: String,
+    id: usize,
+    _marker: PhantomData<(T, E)>,
+}
+
+impl<T: 'static, E: 'static> QueryHandle<T, E> {
+    /// Returns the key of the query
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    /// Returns the current state of the query
+    pub fn state(&self) -> QueryState<T, E> {
+        REGISTRY.with(|registry| {
+            registry
+                .borrow()
+                .entries
+                .get(&self.key)
+                .and_then(|entry| entry.state.downcast_ref::<QueryState<T, E>>())
+                .cloned()
+                .unwrap_or_default()
+        })
+    }
+
+    /// Fetches the query again, unless it is being fetched already
+    pub fn refetch(&self) {
+        let key = self.key.as_str();
+        let fetch = REGISTRY.with(|registry| {
+            registry
+                .borrow()
+                .entries
+                .get(key)
+                .map(|entry| Rc::clone(&entry.fetch))
+        });
+        if let Some(fetch) = fetch {
+            fetch();
+        }
+    }
+}
+
+impl<T, E> Drop for QueryHandle<T, E> {
+    fn drop(&mut self) {
+        REGISTRY.with(|registry| {
+            if let Some(entry) = registry.borrow_mut().entries.get_mut(&self.key) {
+                entry.subscribers.try_remove(self.id);
+            }
+        });
+    }
+}
+
+impl<T, E> fmt::Debug for QueryHandle<T, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("QueryHandle")
+            .field("key", &self.key)
+            .finish()
+    }
+}
+
+/// Marks the query `key` as stale and refetches it if it is subscribed to
+pub fn invalidate(key: &str) {
+    invalidate_where(|k| k == key)
+}
+
+/// Marks the queries whose keys match `predicate` as stale and refetches the ones that are
+/// subscribed to, e.g. all queries starting with `todos/`
+pub fn invalidate_where(predicate: impl Fn(&str) -> bool) {
+    REGISTRY.with(|registry| {
+        for (key, entry) in registry.borrow_mut().entries.iter_mut() {
+            if predicate(key) {
+                entry.invalidated = true;
+            }
+        }
+    });
+    refetch_where(|entry| entry.invalidated && !entry.subscribers.is_empty());
+}
+
+/// Replaces the cached data of the query `key`, e.g. with the result of a mutation, and notifies
+/// its subscribers. The data counts as freshly fetched.
+pub fn set_data<T: 'static, E: 'static>(key: &str, data: T) {
+    set_state::<T, E>(key, move |entry, state| {
+        entry.fetched_at = Some(js_sys::Date::now());
+        entry.invalidated = false;
+        QueryState {
+            data: Some(Rc::new(data)),
+            error: None,
+            ..state
+        }
+    });
+}
+
+/// Returns the cached data of the query `key`
+pub fn get_data<T: 'static, E: 'static>(key: &str) -> Option<Rc<T>> {
+    REGISTRY.with(|registry| {
+        registry
+            .borrow()
+            .entries
+            .get(key)
+            .and_then(|entry| entry.state.downcast_ref::<QueryState<T, E>>())
+            .and_then(|state| state.data.clone())
+    })
+}
+
+struct UseQuery<T, E> {
+    handle: Option<QueryHandle<T, E>>,
+    state: QueryState<T, E>,
+}
+
+/// This hook subscribes to the query `key` and re-renders the component when its state changes.
+///
+/// The subscription moves to another query when `key` changes. See [`subscribe`] for how queries
+/// are fetched.
+///
+/// # Example
+/// ```rust
+/// # use yew::prelude::*;
+/// use yew::query::{use_query, QueryOptions};
+///
+/// # async fn fetch_user(id: u32) -> Result<String, String> { Ok(id.to_string()) }
+/// #[derive(Properties, PartialEq)]
+/// struct UserProps {
+///     id: u32,
+/// }
+///
+/// #[function_component(User)]
+/// fn user(props: &UserProps) -> Html {
+///     let id = props.id;
+///     let user = use_query(format!("users/{}", id), move || fetch_user(id), QueryOptions::default());
+///
+///     match (&user.data, &user.error) {
+///         (Some(name), _) => html! { <p>{ name.to_string() }</p> },
+///         (None, Some(err)) => html! { <p>{ format!("Error: {}", err) }</p> },
+///         (None, None) => html! { <p>{ "Loading…" }</p> },
+///     }
+/// }
+/// ```
+pub fn use_query<T, E, F, Fut>(
+    key: impl Into<String>,
+    fetcher: F,
+    options: QueryOptions,
+) -> QueryState<T, E>
+where
+    T: 'static,
+    E: 'static,
+    F: Fn() -> Fut + 'static,
+    Fut: Future<Output = Result<T, E>> + 'static,
+{
+    let key = key.into();
+    use_hook(
+        || UseQuery {
+            handle: None,
+            state: QueryState::default(),
+        },
+        move |hook: &mut UseQuery<T, E>, updater| {
+            if hook.handle.as_ref().map(QueryHandle::key) != Some(key.as_str()) {
+                // Drop the old subscription first, so its callback doesn't fire anymore
+                hook.handle = None;
+                let callback = Callback::from(move |state: QueryState<T, E>| {
+                    updater.callback(move |hook: &mut UseQuery<T, E>| {
+                        if hook.state == state {
+                            false
+                        } else {
+                            hook.state = state;
+                            true
+                        }
+                    })
+                });
+                let handle = subscribe(key, fetcher, options, callback);
+                hook.state = handle.state();
+                hook.handle = Some(handle);
+            }
+            hook.state.clone()
+        },
+        |hook| {
+            hook.handle = None;
+        },
+    )
+}
+
+#[cfg(all(test, feature = "wasm_test"))]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+    use wasm_bindgen::JsValue;
+    use wasm_bindgen_futures::JsFuture;
+    use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    async fn tick() {
+        JsFuture::from(js_sys::Promise::resolve(&JsValue::NULL))
+            .await
+            .unwrap();
+    }
+
+    #[test]
+    async fn caches_deduplicates_and_invalidates() {
+        let requests = Rc::new(Cell::new(0));
+        let fetcher = {
+            let requests = Rc::clone(&requests);
+            move || {
+                requests.set(requests.get() + 1);
+                let n = requests.get();
+                async move { Ok::<u32, ()>(n) }
+            }
+        };
+        let options = QueryOptions {
+            stale_time_ms: 60_000.,
+            ..Default::default()
+        };
+
+        let states = Rc::new(RefCell::new(Vec::new()));
+        let callback = {
+            let states = Rc::clone(&states);
+            Callback::from(move |state: QueryState<u32, ()>| states.borrow_mut().push(state))
+        };
+        let a = subscribe("test/dedup", fetcher.clone(), options, callback.clone());
+        let b = subscribe("test/dedup", fetcher.clone(), options, Callback::noop());
+        assert!(a.state().fetching);
+        assert_eq!(requests.get(), 1);
+
+        tick().await;
+        assert_eq!(a.state().data.as_deref(), Some(&1));
+        assert_eq!(b.state().data.as_deref(), Some(&1));
+        assert!(!states.borrow().last().unwrap().fetching);
+
+        // Fresh data is served from the cache
+        let c = subscribe("test/dedup", fetcher, options, Callback::noop());
+        assert_eq!(requests.get(), 1);
+        assert_eq!(c.state().data.as_deref(), Some(&1));
+
+        // Stale data is kept while revalidating
+        invalidate("test/dedup");
+        assert_eq!(requests.get(), 2);
+        assert!(a.state().fetching);
+        assert_eq!(a.state().data.as_deref(), Some(&1));
+        tick().await;
+        assert_eq!(a.state().data.as_deref(), Some(&2));
+
+        set_data::<u32, ()>("test/dedup", 10);
+        assert_eq!(get_data::<u32, ()>("test/dedup").as_deref(), Some(&10));
+    }
+}
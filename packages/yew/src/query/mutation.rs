@@ -0,0 +1,299 @@
+//! A queue of mutations that survives going offline and reloading the page.
+//!
+//! Mutations are enqueued as a kind and a serializable payload and stored in `localStorage`
+//! before they are sent, so a mutation made offline is sent when the app runs again. The queue
+//! is processed in order by the executor [registered](register) for each kind. If an executor
+//! fails with [`MutationError::Offline`], processing pauses until the browser goes online.
+//!
+//! Subscribers of [`subscribe_status`] get a [`SyncStatus`] whenever the queue changes, e.g. to
+//! show that changes are not saved yet.
+
+use super::invalidate;
+use crate::callback::Callback;
+use crate::utils::window;
+use gloo::events::EventListener;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use slab::Slab;
+use std::cell::RefCell;
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::rc::Rc;
+use thiserror::Error;
+use wasm_bindgen_futures::spawn_local;
+
+/// Key of the queue in `localStorage`
+const STORAGE_KEY: &str = "yew-mutations";
+
+/// Error of a mutation executor
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum MutationError {
+    /// The mutation could not be sent and is retried when the browser goes online
+    #[error("offline")]
+    Offline,
+    /// The mutation was rejected and is dropped from the queue
+    #[error("mutation rejected: {0}")]
+    Rejected(String),
+}
+
+/// State of the mutation queue
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SyncStatus {
+    /// Number of mutations that were not sent yet
+    pub pending: usize,
+    /// A mutation is being sent
+    pub syncing: bool,
+    /// Processing is paused until the browser goes online
+    pub offline: bool,
+    /// Error of the last rejected mutation
+    pub last_error: Option<MutationError>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Pending {
+    kind: String,
+    payload: serde_json::Value,
+    /// Query keys invalidated after the mutation was sent
+    invalidates: Vec<String>,
+}
+
+type Executor =
+    Rc<dyn Fn(serde_json::Value) -> Pin<Box<dyn Future<Output = Result<(), MutationError>>>>>;
+
+#[derive(Default)]
+struct Queue {
+    loaded: bool,
+    pending: VecDeque<Pending>,
+    executors: HashMap<String, Executor>,
+    status: SyncStatus,
+    subscribers: Slab<Callback<SyncStatus>>,
+    online_listener: Option<EventListener>,
+}
+
+thread_local! {
+    static QUEUE: RefCell<Queue> = Default::default();
+}
+
+impl Queue {
+    /// Loads the stored mutations once
+    fn load(&mut self) {
+        if self.loaded {
+            return;
+        }
+        self.loaded = true;
+        self.pending = storage()
+            .and_then(|storage| storage.get_item(STORAGE_KEY).ok().flatten())
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default();
+        self.status.pending = self.pending.len();
+        self.online_listener = Some(EventListener::new(&window(), "online", |_| {
+            update(|queue| queue.status.offline = false);
+            process();
+        }));
+    }
+
+    fn store(&self) {
+        if let Some(storage) = storage() {
+            let json = serde_json::to_string(&self.pending).unwrap();
+            if storage.set_item(STORAGE_KEY, &json).is_err() {
+                log::error!("failed to store the mutation queue");
+            }
+        }
+    }
+}
+
+fn storage() -> Option<web_sys::Storage> {
+    window().local_storage().ok().flatten()
+}
+
+/// Updates the queue and notifies the status subscribers. The queue must not be borrowed.
+fn update<R>(f: impl FnOnce(&mut Queue) -> R) -> R {
+    let (result, notify) = QUEUE.with(|queue| {
+        let mut queue = queue.borrow_mut();
+        queue.load();
+        let before = queue.status.clone();
+        let result = f(&mut queue);
+        queue.status.pending = queue.pending.len();
+        let notify = if queue.status != before {
+            let subscribers: Vec<_> = queue.subscribers.iter().map(|(_, cb)| cb.clone()).collect();
+            Some((queue.status.clone(), subscribers))
+        } else {
+            None
+        };
+        (result, notify)
+    });
+    if let Some((status, subscribers)) = notify {
+        for subscriber in subscribers {
+            subscriber.emit(status.clone());
+        }
+    }
+    result
+}
+
+/// Sends the next mutation, unless one is being sent or the queue is paused
+fn process() {
+    let next = update(|queue| {
+        if queue.status.syncing || queue.status.offline {
+            return None;
+        }
+        let pending = queue.pending.front()?;
+        // Mutations of kinds without executor wait until one is registered
+        let executor = Rc::clone(queue.executors.get(&pending.kind)?);
+        queue.status.syncing = true;
+        Some((executor, pending.payload.clone()))
+    });
+    // The executor may enqueue mutations itself
+    let future = match next {
+        Some((executor, payload)) => executor(payload),
+        None => return,
+    };
+
+    spawn_local(async move {
+        let result = future.await;
+        let invalidates = update(|queue| {
+            queue.status.syncing = false;
+            match result {
+                Err(MutationError::Offline) => {
+                    queue.status.offline = true;
+                    return Vec::new();
+                }
+                Err(err) => queue.status.last_error = Some(err),
+                Ok(()) => {}
+            }
+            let sent = queue.pending.pop_front();
+            queue.store();
+            sent.map(|sent| sent.invalidates).unwrap_or_default()
+        });
+        for key in invalidates {
+            invalidate(&key);
+        }
+        process();
+    });
+}
+
+/// Registers the executor of the mutations of `kind`, which sends their payload, e.g. to a
+/// server. Mutations of this kind stored by a previous run are sent afterwards.
+pub fn register<M, F, Fut>(kind: &str, executor: F)
+where
+    M: DeserializeOwned + 'static,
+    F: Fn(M) -> Fut + 'static,
+    Fut: Future<Output = Result<(), MutationError>> + 'static,
+{
+    let executor: Executor = Rc::new(move |payload| match serde_json::from_value(payload) {
+        Ok(mutation) => Box::pin(executor(mutation)),
+        Err(err) => {
+            let err = MutationError::Rejected(format!("invalid payload: {}", err));
+            Box::pin(async move { Err(err) })
+        }
+    });
+    update(|queue| queue.executors.insert(kind.to_owned(), executor));
+    process();
+}
+
+/// Enqueues a mutation of `kind` and stores the queue. The queries with the keys in
+/// `invalidates` are invalidated after it was sent.
+pub fn enqueue<M: Serialize>(kind: &str, payload: &M, invalidates: &[&str]) {
+    let pending = Pending {
+        kind: kind.to_owned(),
+        payload: serde_json::to_value(payload).expect("failed to serialize mutation"),
+        invalidates: invalidates.iter().map(|key| key.to_string()).collect(),
+    };
+    update(|queue| {
+        queue.pending.push_back(pending);
+        queue.store();
+    });
+    process();
+}
+
+/// Returns the state of the queue
+pub fn status() -> SyncStatus {
+    update(|queue| queue.status.clone())
+}
+
+/// Calls `callback` whenever the [`SyncStatus`] changes, until the handle is dropped
+pub fn subscribe_status(callback: Callback<SyncStatus>) -> StatusHandle {
+    let id = update(|queue| queue.subscribers.insert(callback));
+    StatusHandle { id }
+}
+
+/// Subscription of [`subscribe_status`]
+pub struct StatusHandle {
+    id: usize,
+}
+
+impl Drop for StatusHandle {
+    fn drop(&mut self) {
+        QUEUE.with(|queue| {
+            queue.borrow_mut().subscribers.try_remove(self.id);
+        });
+    }
+}
+
+impl fmt::Debug for StatusHandle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("StatusHandle")
+    }
+}
+
+#[cfg(all(test, feature = "wasm_test"))]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+    use wasm_bindgen::JsValue;
+    use wasm_bindgen_futures::JsFuture;
+    use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    async fn tick() {
+        JsFuture::from(js_sys::Promise::resolve(&JsValue::NULL))
+            .await
+            .unwrap();
+    }
+
+    #[test]
+    async fn retries_when_online_again() {
+        let online = Rc::new(Cell::new(false));
+        let sent = Rc::new(RefCell::new(Vec::new()));
+        {
+            let online = Rc::clone(&online);
+            let sent = Rc::clone(&sent);
+            register("test/rename", move |name: String| {
+                let result = if online.get() {
+                    sent.borrow_mut().push(name);
+                    Ok(())
+                } else {
+                    Err(MutationError::Offline)
+                };
+                async move { result }
+            });
+        }
+
+        enqueue("test/rename", &"a", &[]);
+        enqueue("test/rename", &"b", &[]);
+        tick().await;
+        assert_eq!(
+            status(),
+            SyncStatus {
+                pending: 2,
+                syncing: false,
+                offline: true,
+                last_error: None,
+            }
+        );
+        let stored = storage().unwrap().get_item(STORAGE_KEY).unwrap().unwrap();
+        assert!(stored.contains("\"a\"") && stored.contains("\"b\""));
+
+        online.set(true);
+        window()
+            .dispatch_event(&web_sys::Event::new("online").unwrap())
+            .unwrap();
+        for _ in 0..4 {
+            tick().await;
+        }
+        assert_eq!(*sent.borrow(), vec!["a", "b"]);
+        assert_eq!(status().pending, 0);
+    }
+}
@@ -1,8 +1,20 @@
 //! This module contains a scheduler.
+//!
+//! Component lifecycle events run right away, until the DOM is up to date. Background work, like
+//! dropping the nodes of removed subtrees, runs in slices when the browser is idle.
+//!
+//! A [`YieldPolicy`] set with [`set_yield_policy`] decides when a slice ends, so apps with heavy
+//! rendering or background work can tune how responsive the page stays. It ends slices of idle
+//! Runnables, of component lifecycle events and of the chunks of
+//! [`send_message_batch_chunked`](crate::html::Scope::send_message_batch_chunked). Without one,
+//! lifecycle events are never sliced.
 
 use std::cell::RefCell;
 use std::collections::VecDeque;
+use std::fmt;
 use std::rc::Rc;
+use wasm_bindgen::{closure::Closure, JsCast, JsValue};
+use web_sys::IdleDeadline;
 
 /// Alias for Rc<RefCell<T>>
 pub type Shared<T> = Rc<RefCell<T>>;
@@ -13,6 +25,8 @@ thread_local! {
     /// Exclusivity of mutable access is controlled by only accessing it through a set of public
     /// functions.
     static SCHEDULER: RefCell<Scheduler> = Default::default();
+
+    static YIELD_POLICY: RefCell<Option<Rc<dyn YieldPolicy>>> = RefCell::new(None);
 }
 
 /// A routine which could be run.
@@ -36,6 +50,13 @@ struct Scheduler {
 
     // Stack
     rendered: Vec<Box<dyn Runnable>>,
+
+    // Teardown run when the browser is idle
+    idle: VecDeque<Box<dyn Runnable>>,
+    idle_requested: bool,
+
+    // Whether the Runnables left by a yielding slice are scheduled to run
+    resume_requested: bool,
 }
 
 /// Execute closure with a mutable reference to the scheduler
@@ -65,6 +86,259 @@ pub(crate) fn push_component_destroy(runnable: Box<dyn Runnable>) {
     with(|s| s.destroy.push_back(runnable));
 }
 
+/// Push a Runnable to be executed when the browser is idle, e.g. to drop the nodes of a large
+/// subtree after it was removed from the DOM. Idle Runnables run in slices ended by the
+/// [`YieldPolicy`].
+pub fn push_idle(runnable: Box<dyn Runnable>) {
+    let request = SCHEDULER.with(|s| {
+        let mut s = s.borrow_mut();
+        s.idle.push_back(runnable);
+        !std::mem::replace(&mut s.idle_requested, true)
+    });
+    if request {
+        request_idle();
+    }
+}
+
+/// Executes the Runnables waiting for the browser to be idle right away
+pub fn flush_idle() {
+    while let Some(runnable) = SCHEDULER.with(|s| s.borrow_mut().idle.pop_front()) {
+        runnable.run();
+        start();
+    }
+}
+
+/// Time in milliseconds idle Runnables run for when the browser doesn't report idle periods
+const IDLE_BUDGET: f64 = 5.0;
+
+/// Time in milliseconds a slice of rendering runs for before [`IdlePeriod`] yields
+const RENDER_BUDGET: f64 = 10.0;
+
+/// Runs [`run_idle`] in the next idle period, or soon when `requestIdleCallback` is not supported
+fn request_idle() {
+    let window = web_sys::window().expect("no window available");
+    let callback = Closure::once_into_js(|deadline: JsValue| run_idle(deadline.dyn_into().ok()));
+    let supported =
+        js_sys::Reflect::has(&window, &JsValue::from_str("requestIdleCallback")).unwrap_or(false);
+    let _ = if supported {
+        window.request_idle_callback(callback.unchecked_ref())
+    } else {
+        window.set_timeout_with_callback(callback.unchecked_ref())
+    };
+}
+
+/// Progress of a slice of work, which a [`YieldPolicy`] decides on
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SliceInfo {
+    /// Time the slice has run for, in milliseconds
+    pub elapsed_ms: f64,
+    /// Time left in the idle period, in milliseconds. Browsers which don't report idle periods
+    /// get a short budget, and so do slices of rendering.
+    pub remaining_ms: f64,
+    /// Number of Runnables, or chunks of messages, left
+    pub pending: usize,
+}
+
+/// Decides when a slice of work ends and the scheduler yields to the browser, see
+/// [`set_yield_policy`].
+///
+/// Closures taking a [`SliceInfo`] are policies, and policies are combined with [`AnyOf`].
+pub trait YieldPolicy {
+    /// Returns whether to end the slice after a Runnable ran, or a chunk of messages was
+    /// processed. The remaining ones run in the next idle period, or in the next task when
+    /// rendering.
+    fn should_yield(&self, slice: &SliceInfo) -> bool;
+}
+
+impl<F: Fn(&SliceInfo) -> bool> YieldPolicy for F {
+    fn should_yield(&self, slice: &SliceInfo) -> bool {
+        self(slice)
+    }
+}
+
+/// Yields when the idle period, or the budget of a slice of rendering, is over. This is the
+/// default policy for idle Runnables.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct IdlePeriod;
+
+impl YieldPolicy for IdlePeriod {
+    fn should_yield(&self, slice: &SliceInfo) -> bool {
+        slice.remaining_ms <= 0.0
+    }
+}
+
+/// Yields when the slice ran for the given number of milliseconds, even if the idle period is
+/// longer
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TimeBudget(pub f64);
+
+impl YieldPolicy for TimeBudget {
+    fn should_yield(&self, slice: &SliceInfo) -> bool {
+        slice.elapsed_ms >= self.0
+    }
+}
+
+/// Yields when the user interacts with the page, as reported by
+/// `navigator.scheduling.isInputPending()`. Never yields in browsers which don't support it.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct InputPending;
+
+impl YieldPolicy for InputPending {
+    fn should_yield(&self, _slice: &SliceInfo) -> bool {
+        let is_input_pending = || {
+            let navigator = web_sys::window()?.navigator();
+            let scheduling = js_sys::Reflect::get(&navigator, &JsValue::from_str("scheduling"))
+                .ok()
+                .filter(JsValue::is_object)?;
+            let function: js_sys::Function =
+                js_sys::Reflect::get(&scheduling, &JsValue::from_str("isInputPending"))
+                    .ok()?
+                    .dyn_into()
+                    .ok()?;
+            function.call0(&scheduling).ok()?.as_bool()
+        };
+        is_input_pending().unwrap_or(false)
+    }
+}
+
+/// Yields while a navigation started with the Navigation API is in progress, so it isn't held
+/// up. Never yields in browsers which don't support it.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct NavigationPending;
+
+impl YieldPolicy for NavigationPending {
+    fn should_yield(&self, _slice: &SliceInfo) -> bool {
+        let transition = || {
+            let window = web_sys::window()?;
+            let navigation = js_sys::Reflect::get(&window, &JsValue::from_str("navigation"))
+                .ok()
+                .filter(JsValue::is_object)?;
+            js_sys::Reflect::get(&navigation, &JsValue::from_str("transition")).ok()
+        };
+        transition().map_or(false, |transition| transition.is_object())
+    }
+}
+
+/// Yields when any of the policies does
+///
+/// ```rust
+/// use yew::scheduler::{set_yield_policy, AnyOf, IdlePeriod, InputPending, TimeBudget};
+///
+/// set_yield_policy(AnyOf(vec![
+///     Box::new(IdlePeriod),
+///     Box::new(TimeBudget(4.0)),
+///     Box::new(InputPending),
+/// ]));
+/// ```
+pub struct AnyOf(pub Vec<Box<dyn YieldPolicy>>);
+
+impl YieldPolicy for AnyOf {
+    fn should_yield(&self, slice: &SliceInfo) -> bool {
+        self.0.iter().any(|policy| policy.should_yield(slice))
+    }
+}
+
+impl fmt::Debug for AnyOf {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "AnyOf({} policies)", self.0.len())
+    }
+}
+
+/// Sets the policy deciding when slices of work end.
+///
+/// By default, slices of idle Runnables end with the [`IdlePeriod`], chunked batches of messages
+/// yield after every chunk and other lifecycle events run until the DOM is up to date. With a
+/// policy set, lifecycle events yield when it says so, and the DOM is updated in the next tasks.
+pub fn set_yield_policy(policy: impl YieldPolicy + 'static) {
+    YIELD_POLICY.with(|p| *p.borrow_mut() = Some(Rc::new(policy)));
+}
+
+/// Restores the default [`YieldPolicy`]
+pub fn reset_yield_policy() {
+    YIELD_POLICY.with(|p| *p.borrow_mut() = None);
+}
+
+/// Returns whether a policy is set with [`set_yield_policy`]
+fn has_yield_policy() -> bool {
+    YIELD_POLICY.with(|p| p.borrow().is_some())
+}
+
+/// Asks the policy set with [`set_yield_policy`], if any
+fn policy_yields(slice: &SliceInfo) -> Option<bool> {
+    // The policy is cloned, so it may set another one
+    let policy = YIELD_POLICY.with(|p| p.borrow().clone())?;
+    Some(policy.should_yield(slice))
+}
+
+fn should_yield(slice: &SliceInfo) -> bool {
+    policy_yields(slice).unwrap_or_else(|| IdlePeriod.should_yield(slice))
+}
+
+/// Returns whether a slice of rendering, which started at `start_time` as returned by
+/// `Date.now()`, ends with `pending` units of work left. Returns `None` without a policy.
+pub(crate) fn render_yields(start_time: f64, pending: usize) -> Option<bool> {
+    let elapsed_ms = js_sys::Date::now() - start_time;
+    policy_yields(&SliceInfo {
+        elapsed_ms,
+        remaining_ms: RENDER_BUDGET - elapsed_ms,
+        pending,
+    })
+}
+
+/// Runs [`start`] in the next task, to continue a slice of rendering which yielded
+fn request_resume() {
+    let request =
+        SCHEDULER.with(|s| !std::mem::replace(&mut s.borrow_mut().resume_requested, true));
+    if request {
+        let callback = Closure::once_into_js(|| {
+            with(|s| s.resume_requested = false);
+            start();
+        });
+        let _ = web_sys::window()
+            .expect("no window available")
+            .set_timeout_with_callback(callback.unchecked_ref());
+    }
+}
+
+/// Executes idle Runnables until the yield policy ends the slice
+fn run_idle(deadline: Option<IdleDeadline>) {
+    let now = js_sys::Date::now;
+    let start_time = now();
+    let end = start_time + IDLE_BUDGET;
+    let remaining = || match &deadline {
+        Some(deadline) => deadline.time_remaining(),
+        None => end - now(),
+    };
+
+    loop {
+        let runnable = SCHEDULER.with(|s| s.borrow_mut().idle.pop_front());
+        match runnable {
+            Some(runnable) => {
+                runnable.run();
+                start();
+            }
+            None => break,
+        }
+        let slice = SliceInfo {
+            elapsed_ms: now() - start_time,
+            remaining_ms: remaining(),
+            pending: SCHEDULER.with(|s| s.borrow().idle.len()),
+        };
+        if slice.pending == 0 || should_yield(&slice) {
+            break;
+        }
+    }
+
+    let request = SCHEDULER.with(|s| {
+        let mut s = s.borrow_mut();
+        s.idle_requested = !s.idle.is_empty();
+        s.idle_requested
+    });
+    if request {
+        request_idle();
+    }
+}
+
 /// Push a component render Runnable to be executed
 #[inline]
 pub(crate) fn push_component_render(runnable: Box<dyn Runnable>) {
@@ -99,14 +373,46 @@ pub(crate) fn start() {
 
     LOCK.with(|l| {
         if let Ok(_lock) = l.try_borrow_mut() {
-            while let Some(runnable) = SCHEDULER.with(|s| s.borrow_mut().next_runnable()) {
-                runnable.run();
+            // Rendering is only sliced with a policy
+            let start_time = has_yield_policy().then(js_sys::Date::now);
+            loop {
+                while let Some(runnable) = SCHEDULER.with(|s| s.borrow_mut().next_runnable()) {
+                    runnable.run();
+                    if let Some(start_time) = start_time {
+                        // The commit ends once the remaining Runnables ran
+                        let pending = SCHEDULER.with(|s| s.borrow().pending());
+                        if pending > 0 && render_yields(start_time, pending) == Some(true) {
+                            request_resume();
+                            return;
+                        }
+                    }
+                }
+                crate::virtual_dom::scroll_anchor::end_commit();
+                crate::virtual_dom::command::end_commit();
+                crate::virtual_dom::ops::end_commit();
+                #[cfg(feature = "stats")]
+                crate::stats::end_commit();
+
+                // Messages sent by element commands are committed right after
+                if SCHEDULER.with(|s| s.borrow().pending()) == 0 {
+                    break;
+                }
             }
         }
     });
 }
 
 impl Scheduler {
+    /// Number of Runnables queued, apart from the teardown run when the browser is idle
+    fn pending(&self) -> usize {
+        self.destroy.len()
+            + self.create.len()
+            + self.update.len()
+            + self.render.len()
+            + self.rendered.len()
+            + self.main.len()
+    }
+
     /// Pop next Runnable to be executed according to Runnable type execution priority
     fn next_runnable(&mut self) -> Option<Box<dyn Runnable>> {
         self.destroy
@@ -121,8 +427,16 @@ impl Scheduler {
 
 #[cfg(test)]
 mod tests {
+    extern crate self as yew;
+
     use super::*;
 
+    #[cfg(feature = "wasm_test")]
+    use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+
+    #[cfg(feature = "wasm_test")]
+    wasm_bindgen_test_configure!(run_in_browser);
+
     #[test]
     fn push_executes_runnables_immediately() {
         use std::cell::Cell;
@@ -141,4 +455,64 @@ mod tests {
         push(Box::new(Test));
         FLAG.with(|v| assert!(v.get()));
     }
+
+    #[test]
+    fn yield_policies() {
+        let slice = SliceInfo {
+            elapsed_ms: 3.0,
+            remaining_ms: 10.0,
+            pending: 2,
+        };
+        assert!(!IdlePeriod.should_yield(&slice));
+        assert!(TimeBudget(2.0).should_yield(&slice));
+        assert!(!TimeBudget(4.0).should_yield(&slice));
+
+        let few_left = |slice: &SliceInfo| slice.pending < 3;
+        assert!(AnyOf(vec![Box::new(IdlePeriod), Box::new(few_left)]).should_yield(&slice));
+        assert!(!AnyOf(vec![Box::new(IdlePeriod), Box::new(TimeBudget(4.0))]).should_yield(&slice));
+    }
+
+    #[cfg(feature = "wasm_test")]
+    #[test]
+    async fn policies_slice_rendering() {
+        use crate::html::{NodeRef, Scope};
+        use crate::utils::document;
+        use crate::{html, Component, Context, Html};
+
+        struct Comp;
+
+        impl Component for Comp {
+            type Message = ();
+            type Properties = ();
+
+            fn create(_ctx: &Context<Self>) -> Self {
+                Comp
+            }
+
+            fn view(&self, _ctx: &Context<Self>) -> Html {
+                html! { <p>{ "text" }</p> }
+            }
+        }
+
+        let scope = Scope::<Comp>::new(None);
+        let el = document().create_element("div").unwrap();
+        set_yield_policy(|_: &SliceInfo| true);
+        scope.mount_in_place(
+            el.clone(),
+            NodeRef::default(),
+            NodeRef::default(),
+            Rc::new(()),
+        );
+        let first_slice = el.inner_html();
+        reset_yield_policy();
+
+        let promise = js_sys::Promise::new(&mut |resolve, _| {
+            crate::utils::window()
+                .set_timeout_with_callback_and_timeout_and_arguments_0(&resolve, 10)
+                .unwrap();
+        });
+        wasm_bindgen_futures::JsFuture::from(promise).await.unwrap();
+        assert_eq!(first_slice, "");
+        assert_eq!(el.inner_html(), "<p>text</p>");
+    }
 }
@@ -0,0 +1,450 @@
+//! This module contains [`SelectionModel`], the selection logic of lists and tables.
+//!
+//! The model implements the conventions of desktop platforms: a click selects an item, a click
+//! with <kbd>Ctrl</kbd> (<kbd>⌘</kbd> on macOS) toggles it and a click with <kbd>Shift</kbd>
+//! selects the range from the last clicked item. Arrow keys move the selection the same way.
+//! Struct components keep a `SelectionModel` in their state, function components use
+//! [`use_selection`], whose handle can also be shared with the rows as a context.
+
+use crate::functional::use_hook;
+use indexmap::IndexSet;
+use std::fmt;
+use std::hash::Hash;
+use std::ops::Deref;
+use std::rc::Rc;
+use web_sys::{KeyboardEvent, MouseEvent};
+
+/// How many items can be selected
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SelectionMode {
+    /// At most one item
+    Single,
+    /// Any number of items
+    Multiple,
+}
+
+/// Modifier keys held during a click or key press
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Modifiers {
+    /// <kbd>Shift</kbd> is held
+    pub shift: bool,
+    /// <kbd>Ctrl</kbd> or <kbd>⌘</kbd> is held
+    pub ctrl: bool,
+}
+
+impl From<&MouseEvent> for Modifiers {
+    fn from(event: &MouseEvent) -> Self {
+        Self {
+            shift: event.shift_key(),
+            ctrl: event.ctrl_key() || event.meta_key(),
+        }
+    }
+}
+
+impl From<&KeyboardEvent> for Modifiers {
+    fn from(event: &KeyboardEvent) -> Self {
+        Self {
+            shift: event.shift_key(),
+            ctrl: event.ctrl_key() || event.meta_key(),
+        }
+    }
+}
+
+/// The selected items of a list or table, identified by keys of type `K`.
+///
+/// Methods taking an `order` need the keys of all items in the order they are displayed, to
+/// select ranges. They return whether the selection changed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SelectionModel<K: Eq + Hash> {
+    mode: SelectionMode,
+    selected: IndexSet<K>,
+    /// Item ranges start from
+    anchor: Option<K>,
+}
+
+impl<K: Clone + Eq + Hash> SelectionModel<K> {
+    /// Creates an empty selection
+    pub fn new(mode: SelectionMode) -> Self {
+        Self {
+            mode,
+            selected: IndexSet::new(),
+            anchor: None,
+        }
+    }
+
+    /// Returns the mode of the selection
+    pub fn mode(&self) -> SelectionMode {
+        self.mode
+    }
+
+    /// Returns whether `key` is selected
+    pub fn is_selected(&self, key: &K) -> bool {
+        self.selected.contains(key)
+    }
+
+    /// Returns the selected keys in the order they were selected
+    pub fn selected(&self) -> impl Iterator<Item = &K> {
+        self.selected.iter()
+    }
+
+    /// Returns the number of selected items
+    pub fn len(&self) -> usize {
+        self.selected.len()
+    }
+
+    /// Returns whether no item is selected
+    pub fn is_empty(&self) -> bool {
+        self.selected.is_empty()
+    }
+
+    /// Returns the item ranges start from, which is the item clicked last without
+    /// <kbd>Shift</kbd>
+    pub fn anchor(&self) -> Option<&K> {
+        self.anchor.as_ref()
+    }
+
+    /// Handles a click on the item with `key`
+    pub fn click(&mut self, key: &K, modifiers: Modifiers, order: &[K]) -> bool {
+        self.changes(|model| match modifiers {
+            Modifiers { shift: true, ctrl } if model.mode == SelectionMode::Multiple => {
+                model.select_range(key, ctrl, order)
+            }
+            Modifiers { ctrl: true, .. } => {
+                model.toggle_inner(key);
+                model.anchor = Some(key.clone());
+            }
+            _ => model.select_only(key),
+        })
+    }
+
+    /// Handles moving to the item with `key` with the keyboard, e.g. with the arrow keys.
+    ///
+    /// With <kbd>Ctrl</kbd> only the focus moves and the selection is kept, so items can be
+    /// toggled with [`toggle`](Self::toggle) afterwards.
+    pub fn move_to(&mut self, key: &K, modifiers: Modifiers, order: &[K]) -> bool {
+        self.changes(|model| match modifiers {
+            Modifiers { ctrl: true, .. } => {}
+            Modifiers { shift: true, .. } if model.mode == SelectionMode::Multiple => {
+                model.select_range(key, false, order)
+            }
+            _ => model.select_only(key),
+        })
+    }
+
+    /// Toggles the item with `key`, e.g. when <kbd>Space</kbd> is pressed
+    pub fn toggle(&mut self, key: &K) -> bool {
+        self.changes(|model| {
+            model.toggle_inner(key);
+            model.anchor = Some(key.clone());
+        })
+    }
+
+    /// Selects all items, if multiple items can be selected
+    pub fn select_all(&mut self, order: &[K]) -> bool {
+        self.changes(|model| {
+            if model.mode == SelectionMode::Multiple {
+                model.selected.extend(order.iter().cloned());
+            }
+        })
+    }
+
+    /// Selects exactly the items with `keys`
+    pub fn set(&mut self, keys: impl IntoIterator<Item = K>) -> bool {
+        self.changes(|model| {
+            model.selected.clear();
+            for key in keys {
+                if model.mode == SelectionMode::Single {
+                    model.selected.clear();
+                }
+                model.selected.insert(key);
+            }
+            model.anchor = model.selected.last().cloned();
+        })
+    }
+
+    /// Deselects all items
+    pub fn clear(&mut self) -> bool {
+        self.changes(|model| {
+            model.selected.clear();
+            model.anchor = None;
+        })
+    }
+
+    /// Keeps only the selected items that are in `order`, e.g. after items were removed
+    pub fn retain(&mut self, order: &[K]) -> bool {
+        self.changes(|model| {
+            model.selected.retain(|key| order.contains(key));
+            if !matches!(&model.anchor, Some(anchor) if order.contains(anchor)) {
+                model.anchor = None;
+            }
+        })
+    }
+
+    fn changes(&mut self, f: impl FnOnce(&mut Self)) -> bool {
+        let before = self.selected.clone();
+        f(self);
+        before != self.selected
+    }
+
+    fn select_only(&mut self, key: &K) {
+        self.selected.clear();
+        self.selected.insert(key.clone());
+        self.anchor = Some(key.clone());
+    }
+
+    fn toggle_inner(&mut self, key: &K) {
+        if !self.selected.shift_remove(key) {
+            if self.mode == SelectionMode::Single {
+                self.selected.clear();
+            }
+            self.selected.insert(key.clone());
+        }
+    }
+
+    /// Selects the items from the anchor to `key`. The anchor is kept, so the range can be
+    /// changed by selecting another end.
+    fn select_range(&mut self, key: &K, extend: bool, order: &[K]) {
+        let end = order.iter().position(|k| k == key);
+        let start = self
+            .anchor
+            .as_ref()
+            .and_then(|anchor| order.iter().position(|k| k == anchor));
+        match (start, end) {
+            (Some(start), Some(end)) => {
+                if !extend {
+                    self.selected.clear();
+                }
+                let range = if start <= end {
+                    start..=end
+                } else {
+                    end..=start
+                };
+                self.selected.extend(order[range].iter().cloned());
+            }
+            _ => self.select_only(key),
+        }
+    }
+}
+
+struct UseSelection<K: Eq + Hash> {
+    model: Rc<SelectionModel<K>>,
+}
+
+/// This hook keeps a [`SelectionModel`] and re-renders the component when the selection changes.
+///
+/// # Example
+/// ```rust
+/// # use yew::prelude::*;
+/// use yew::selection::{use_selection, Modifiers, SelectionMode};
+///
+/// #[derive(Properties, PartialEq)]
+/// struct TableProps {
+///     ids: Vec<u32>,
+/// }
+///
+/// #[function_component(Table)]
+/// fn table(props: &TableProps) -> Html {
+///     let selection = use_selection::<u32>(SelectionMode::Multiple);
+///     let rows = props.ids.iter().map(|&id| {
+///         let onclick = {
+///             let selection = selection.clone();
+///             let ids = props.ids.clone();
+///             Callback::from(move |e: MouseEvent| {
+///                 selection.click(id, Modifiers::from(&e), ids.clone())
+///             })
+///         };
+///         html! {
+///             <tr {onclick} aria-selected={selection.is_selected(&id).to_string()}>
+///                 <td>{ id }</td>
+///             </tr>
+///         }
+///     });
+///     html! { <table>{ for rows }</table> }
+/// }
+/// ```
+pub fn use_selection<K: Clone + Eq + Hash + 'static>(mode: SelectionMode) -> UseSelectionHandle<K> {
+    use_hook(
+        move || UseSelection {
+            model: Rc::new(SelectionModel::new(mode)),
+        },
+        move |hook, updater| {
+            let dispatch: Rc<dyn Fn(Box<dyn FnOnce(&mut SelectionModel<K>) -> bool>)> =
+                Rc::new(move |f| {
+                    updater.callback(move |st: &mut UseSelection<K>| f(Rc::make_mut(&mut st.model)))
+                });
+            UseSelectionHandle {
+                model: Rc::clone(&hook.model),
+                dispatch,
+            }
+        },
+        |_| {},
+    )
+}
+
+/// Handle of the [`use_selection`] hook.
+///
+/// It dereferences to the [`SelectionModel`] of the current render. The methods taking `self`
+/// update the selection and re-render the component if it changed. Handles are equal if they
+/// refer to the same state of the selection.
+pub struct UseSelectionHandle<K: Eq + Hash> {
+    model: Rc<SelectionModel<K>>,
+    #[allow(clippy::type_complexity)]
+    dispatch: Rc<dyn Fn(Box<dyn FnOnce(&mut SelectionModel<K>) -> bool>)>,
+}
+
+impl<K: Clone + Eq + Hash + 'static> UseSelectionHandle<K> {
+    /// See [`SelectionModel::click`]
+    pub fn click(&self, key: K, modifiers: Modifiers, order: impl AsRef<[K]> + 'static) {
+        (self.dispatch)(Box::new(move |model| {
+            model.click(&key, modifiers, order.as_ref())
+        }))
+    }
+
+    /// See [`SelectionModel::move_to`]
+    pub fn move_to(&self, key: K, modifiers: Modifiers, order: impl AsRef<[K]> + 'static) {
+        (self.dispatch)(Box::new(move |model| {
+            model.move_to(&key, modifiers, order.as_ref())
+        }))
+    }
+
+    /// See [`SelectionModel::toggle`]
+    pub fn toggle(&self, key: K) {
+        (self.dispatch)(Box::new(move |model| model.toggle(&key)))
+    }
+
+    /// See [`SelectionModel::select_all`]
+    pub fn select_all(&self, order: impl AsRef<[K]> + 'static) {
+        (self.dispatch)(Box::new(move |model| model.select_all(order.as_ref())))
+    }
+
+    /// See [`SelectionModel::set`]
+    pub fn set(&self, keys: impl IntoIterator<Item = K> + 'static) {
+        (self.dispatch)(Box::new(move |model| model.set(keys)))
+    }
+
+    /// See [`SelectionModel::clear`]
+    pub fn clear(&self) {
+        (self.dispatch)(Box::new(|model| model.clear()))
+    }
+}
+
+impl<K: Eq + Hash> Deref for UseSelectionHandle<K> {
+    type Target = SelectionModel<K>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.model
+    }
+}
+
+impl<K: Eq + Hash> Clone for UseSelectionHandle<K> {
+    fn clone(&self) -> Self {
+        Self {
+            model: Rc::clone(&self.model),
+            dispatch: Rc::clone(&self.dispatch),
+        }
+    }
+}
+
+impl<K: Eq + Hash> PartialEq for UseSelectionHandle<K> {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.model, &other.model)
+    }
+}
+
+impl<K: Eq + Hash + fmt::Debug> fmt::Debug for UseSelectionHandle<K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("UseSelectionHandle")
+            .field("model", &self.model)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "wasm_test")]
+    use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+
+    #[cfg(feature = "wasm_test")]
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    const ORDER: &[u32] = &[1, 2, 3, 4, 5];
+    const PLAIN: Modifiers = Modifiers {
+        shift: false,
+        ctrl: false,
+    };
+    const SHIFT: Modifiers = Modifiers {
+        shift: true,
+        ctrl: false,
+    };
+    const CTRL: Modifiers = Modifiers {
+        shift: false,
+        ctrl: true,
+    };
+
+    fn selected(model: &SelectionModel<u32>) -> Vec<u32> {
+        let mut keys: Vec<u32> = model.selected().copied().collect();
+        keys.sort_unstable();
+        keys
+    }
+
+    #[test]
+    fn clicks_select_toggle_and_select_ranges() {
+        let mut model = SelectionModel::new(SelectionMode::Multiple);
+        assert!(model.click(&2, PLAIN, ORDER));
+        assert!(model.click(&4, SHIFT, ORDER));
+        assert_eq!(selected(&model), vec![2, 3, 4]);
+
+        // The anchor stays, so the range shrinks
+        assert!(model.click(&3, SHIFT, ORDER));
+        assert_eq!(selected(&model), vec![2, 3]);
+        assert!(!model.click(&3, SHIFT, ORDER));
+
+        assert!(model.click(&5, CTRL, ORDER));
+        assert!(model.click(&2, CTRL, ORDER));
+        assert_eq!(selected(&model), vec![3, 5]);
+        assert_eq!(model.anchor(), Some(&2));
+
+        assert!(model.click(
+            &4,
+            Modifiers {
+                shift: true,
+                ctrl: true
+            },
+            ORDER
+        ));
+        assert_eq!(selected(&model), vec![2, 3, 4, 5]);
+
+        assert!(model.click(&1, PLAIN, ORDER));
+        assert_eq!(selected(&model), vec![1]);
+    }
+
+    #[test]
+    fn single_mode_keeps_one_item() {
+        let mut model = SelectionModel::new(SelectionMode::Single);
+        model.click(&2, PLAIN, ORDER);
+        model.click(&4, SHIFT, ORDER);
+        assert_eq!(selected(&model), vec![4]);
+        model.click(&5, CTRL, ORDER);
+        assert_eq!(selected(&model), vec![5]);
+        assert!(!model.select_all(ORDER));
+        assert!(model.click(&5, CTRL, ORDER));
+        assert!(model.is_empty());
+    }
+
+    #[test]
+    fn keyboard_moves_and_extends_selection() {
+        let mut model = SelectionModel::new(SelectionMode::Multiple);
+        model.move_to(&1, PLAIN, ORDER);
+        model.move_to(&2, SHIFT, ORDER);
+        model.move_to(&3, SHIFT, ORDER);
+        assert_eq!(selected(&model), vec![1, 2, 3]);
+        assert!(!model.move_to(&5, CTRL, ORDER));
+        assert!(model.toggle(&5));
+        assert_eq!(selected(&model), vec![1, 2, 3, 5]);
+
+        assert!(model.retain(&[2, 5]));
+        assert_eq!(selected(&model), vec![2, 5]);
+    }
+}
@@ -0,0 +1,239 @@
+//! Counters of the DOM operations performed by the virtual DOM.
+//!
+//! Only available with the `stats` feature. The counters are updated at every call into the DOM
+//! API, so they show the real cost of reconciliation and can be compared across changes in CI:
+//!
+//! ```rust
+//! # fn render_big_table() {}
+//! yew::stats::reset();
+//! render_big_table();
+//! let ops = yew::stats::total();
+//! assert!(ops.create_element <= 1_000, "too many elements created: {:?}", ops);
+//! ```
+//!
+//! A commit is a run of the scheduler that performed at least one DOM operation, e.g. the
+//! handling of a message and all the updates and renders it caused.
+
+use std::cell::RefCell;
+use std::ops::AddAssign;
+
+/// Numbers of DOM operations by kind
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DomOps {
+    /// `Document.createElement()` and `Document.createElementNS()`
+    pub create_element: u64,
+    /// `Document.createTextNode()`
+    pub create_text: u64,
+    /// `Node.insertBefore()` and `Node.appendChild()`, for new and moved nodes
+    pub insert_before: u64,
+    /// `Node.removeChild()`
+    pub remove_child: u64,
+    /// `Element.setAttribute()`
+    pub set_attribute: u64,
+    /// `Element.removeAttribute()`
+    pub remove_attribute: u64,
+    /// `CSSStyleDeclaration.setProperty()` and `removeProperty()`, for partial updates of the
+    /// `style` attribute
+    pub set_style: u64,
+    /// Changes of the text of text nodes
+    pub set_text: u64,
+    /// Changes of the `value` of `input` and `textarea` elements
+    pub set_value: u64,
+    /// Changes of the `checked` state of `input` elements
+    pub set_checked: u64,
+}
+
+impl DomOps {
+    /// Returns the number of operations of all kinds
+    pub fn total(&self) -> u64 {
+        self.create_element
+            + self.create_text
+            + self.insert_before
+            + self.remove_child
+            + self.set_attribute
+            + self.remove_attribute
+            + self.set_style
+            + self.set_text
+            + self.set_value
+            + self.set_checked
+    }
+}
+
+impl AddAssign for DomOps {
+    fn add_assign(&mut self, rhs: Self) {
+        self.create_element += rhs.create_element;
+        self.create_text += rhs.create_text;
+        self.insert_before += rhs.insert_before;
+        self.remove_child += rhs.remove_child;
+        self.set_attribute += rhs.set_attribute;
+        self.remove_attribute += rhs.remove_attribute;
+        self.set_style += rhs.set_style;
+        self.set_text += rhs.set_text;
+        self.set_value += rhs.set_value;
+        self.set_checked += rhs.set_checked;
+    }
+}
+
+#[derive(Default)]
+struct Stats {
+    /// Operations of the commit in progress
+    current: DomOps,
+    last_commit: DomOps,
+    total: DomOps,
+    commits: u64,
+}
+
+thread_local! {
+    static STATS: RefCell<Stats> = Default::default();
+}
+
+/// Counts an operation of the commit in progress
+pub(crate) fn count(f: impl FnOnce(&mut DomOps)) {
+    STATS.with(|stats| f(&mut stats.borrow_mut().current));
+}
+
+/// Ends the commit in progress, if it performed any operations
+pub(crate) fn end_commit() {
+    STATS.with(|stats| {
+        let mut stats = stats.borrow_mut();
+        let current = std::mem::take(&mut stats.current);
+        if current.total() == 0 {
+            return;
+        }
+        stats.last_commit = current;
+        stats.total += current;
+        stats.commits += 1;
+    });
+}
+
+/// Returns the operations performed by the last commit
+pub fn last_commit() -> DomOps {
+    STATS.with(|stats| stats.borrow().last_commit)
+}
+
+/// Returns the operations performed by all commits since the last [`reset`]
+pub fn total() -> DomOps {
+    STATS.with(|stats| stats.borrow().total)
+}
+
+/// Returns the number of commits since the last [`reset`]
+pub fn commits() -> u64 {
+    STATS.with(|stats| stats.borrow().commits)
+}
+
+/// Resets all counters to zero
+pub fn reset() {
+    STATS.with(|stats| *stats.borrow_mut() = Default::default());
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate self as yew;
+
+    use super::*;
+    use crate::html::{NodeRef, Scope};
+    use crate::utils::document;
+    use crate::{html, Component, Context, Html};
+    use std::rc::Rc;
+
+    #[cfg(feature = "wasm_test")]
+    use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+
+    #[cfg(feature = "wasm_test")]
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    struct Toggle {
+        on: bool,
+    }
+
+    impl Component for Toggle {
+        type Message = ();
+        type Properties = ();
+
+        fn create(_ctx: &Context<Self>) -> Self {
+            Toggle { on: false }
+        }
+
+        fn update(&mut self, _ctx: &Context<Self>, _msg: Self::Message) -> bool {
+            self.on = !self.on;
+            true
+        }
+
+        fn view(&self, _ctx: &Context<Self>) -> Html {
+            html! { <p class={if self.on { "on" } else { "off" }}>{ "toggle" }</p> }
+        }
+    }
+
+    struct Styled {
+        width: u32,
+    }
+
+    impl Component for Styled {
+        type Message = u32;
+        type Properties = ();
+
+        fn create(_ctx: &Context<Self>) -> Self {
+            Styled { width: 1 }
+        }
+
+        fn update(&mut self, _ctx: &Context<Self>, width: Self::Message) -> bool {
+            self.width = width;
+            true
+        }
+
+        fn view(&self, _ctx: &Context<Self>) -> Html {
+            let style = format!("color: red; width: {}px", self.width);
+            html! { <p {style}></p> }
+        }
+    }
+
+    #[test]
+    fn counts_partial_style_updates() {
+        reset();
+        let scope = Scope::<Styled>::new(None);
+        let el = document().create_element("div").unwrap();
+        scope.mount_in_place(el, NodeRef::default(), NodeRef::default(), Rc::new(()));
+
+        scope.send_message(2);
+        // `width` is removed and set again
+        assert_eq!(
+            last_commit(),
+            DomOps {
+                set_style: 2,
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn counts_operations_per_commit() {
+        reset();
+        let scope = Scope::<Toggle>::new(None);
+        let el = document().create_element("div").unwrap();
+        scope.mount_in_place(el, NodeRef::default(), NodeRef::default(), Rc::new(()));
+
+        assert_eq!(commits(), 1);
+        let mount = last_commit();
+        // The placeholder, the paragraph and its text
+        assert_eq!(mount.create_text, 2);
+        assert_eq!(mount.create_element, 1);
+        assert_eq!(mount.insert_before, 3);
+        assert_eq!(mount.remove_child, 1);
+        assert_eq!(mount.set_attribute, 1);
+
+        scope.send_message(());
+        assert_eq!(commits(), 2);
+        assert_eq!(
+            last_commit(),
+            DomOps {
+                set_attribute: 1,
+                ..Default::default()
+            }
+        );
+        assert_eq!(total().total(), mount.total() + 1);
+
+        reset();
+        assert_eq!(commits(), 0);
+        assert_eq!(total(), DomOps::default());
+    }
+}
@@ -0,0 +1,202 @@
+//! Reading and setting the selection in a subtree by offsets in its text.
+//!
+//! Offsets count the UTF-16 code units of the text nodes of the subtree in document order, like
+//! `textContent` and the selection of inputs, so they don't depend on how the text is split into
+//! elements. The subtree is usually the element of a [`NodeRef`](crate::html::NodeRef):
+//!
+//! ```rust
+//! # use yew::prelude::*;
+//! use yew::text_selection::{set_selection, TextRange};
+//!
+//! fn select_first_word(node_ref: &NodeRef) {
+//!     if let Some(root) = node_ref.get() {
+//!         let text = root.text_content().unwrap_or_default();
+//!         let end = text.find(' ').unwrap_or(text.len());
+//!         set_selection(&root, TextRange::new(0, end as u32));
+//!     }
+//! }
+//! ```
+
+use crate::utils::{document, window};
+use web_sys::{Node, Range};
+
+/// What to show of a tree walker showing only text nodes
+const SHOW_TEXT: u32 = 4;
+
+/// A range of the text of a subtree, in UTF-16 code units
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct TextRange {
+    /// Offset of the first selected unit
+    pub start: u32,
+    /// Offset after the last selected unit
+    pub end: u32,
+}
+
+impl TextRange {
+    /// Creates a range, swapping `start` and `end` if they are reversed
+    pub fn new(start: u32, end: u32) -> Self {
+        Self {
+            start: start.min(end),
+            end: start.max(end),
+        }
+    }
+
+    /// Creates an empty range at `offset`, i.e. a caret
+    pub fn caret(offset: u32) -> Self {
+        Self::new(offset, offset)
+    }
+
+    /// Returns the number of selected code units
+    pub fn len(&self) -> u32 {
+        self.end - self.start
+    }
+
+    /// Returns whether the range is empty, i.e. a caret
+    pub fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
+}
+
+fn utf16_len(text: &str) -> u32 {
+    text.encode_utf16().count() as u32
+}
+
+/// Returns the text offset in `root` of the DOM position `offset` in `node`, which is a
+/// character offset in text nodes and a child index in elements. Returns `None` if `node` is
+/// not in `root`.
+pub fn text_offset(root: &Node, node: &Node, offset: u32) -> Option<u32> {
+    if !root.contains(Some(node)) {
+        return None;
+    }
+    let before = document().create_range().ok()?;
+    before.select_node_contents(root).ok()?;
+    before.set_end(node, offset).ok()?;
+    Some(utf16_len(&String::from(before.to_string())))
+}
+
+/// Returns the DOM position of the text `offset` in `root`: a text node and the offset in it.
+/// Offsets past the end of the text are clamped to it.
+pub fn dom_position(root: &Node, offset: u32) -> Option<(Node, u32)> {
+    let walker = document()
+        .create_tree_walker_with_what_to_show(root, SHOW_TEXT)
+        .ok()?;
+    let mut remaining = offset;
+    let mut last = None;
+    while let Ok(Some(node)) = walker.next_node() {
+        let len = utf16_len(&node.text_content().unwrap_or_default());
+        if remaining <= len {
+            return Some((node, remaining));
+        }
+        remaining -= len;
+        last = Some((node, len));
+    }
+    // Subtrees without text only have the position at their start
+    Some(last.unwrap_or_else(|| (root.clone(), 0)))
+}
+
+/// Returns a DOM [`Range`] spanning `range` of the text of `root`, e.g. to measure or wrap it
+pub fn dom_range(root: &Node, range: TextRange) -> Option<Range> {
+    let (start, start_offset) = dom_position(root, range.start)?;
+    let (end, end_offset) = dom_position(root, range.end)?;
+    let dom_range = document().create_range().ok()?;
+    dom_range.set_start(&start, start_offset).ok()?;
+    dom_range.set_end(&end, end_offset).ok()?;
+    Some(dom_range)
+}
+
+/// Returns the selection of the document if it is in `root`. Backward selections are returned
+/// in document order.
+pub fn selection_in(root: &Node) -> Option<TextRange> {
+    let selection = window().get_selection().ok()??;
+    if selection.range_count() == 0 {
+        return None;
+    }
+    let range = selection.get_range_at(0).ok()?;
+    let start = text_offset(
+        root,
+        &range.start_container().ok()?,
+        range.start_offset().ok()?,
+    )?;
+    let end = text_offset(root, &range.end_container().ok()?, range.end_offset().ok()?)?;
+    Some(TextRange::new(start, end))
+}
+
+/// Selects `range` of the text of `root`. Returns whether the selection was set.
+pub fn set_selection(root: &Node, range: TextRange) -> bool {
+    let set = || {
+        let dom_range = dom_range(root, range)?;
+        let selection = window().get_selection().ok()??;
+        selection.remove_all_ranges().ok()?;
+        selection.add_range(&dom_range).ok()
+    };
+    set().is_some()
+}
+
+/// Returns the ranges of all occurrences of `needle` in the text of `root`, e.g. to highlight
+/// them for find-in-page
+pub fn find_text(root: &Node, needle: &str) -> Vec<TextRange> {
+    let text = root.text_content().unwrap_or_default();
+    if needle.is_empty() {
+        return Vec::new();
+    }
+    let len = utf16_len(needle);
+    let mut ranges = Vec::new();
+    let mut offset = 0;
+    let mut counted = 0;
+    for (index, _) in text.match_indices(needle) {
+        // Matches don't overlap and come in order, so the prefix is counted once
+        offset += utf16_len(&text[counted..index]);
+        counted = index;
+        ranges.push(TextRange::new(offset, offset + len));
+    }
+    ranges
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "wasm_test")]
+    use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+
+    #[cfg(feature = "wasm_test")]
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    #[test]
+    fn normalizes_ranges() {
+        assert_eq!(TextRange::new(5, 2), TextRange { start: 2, end: 5 });
+        assert_eq!(TextRange::new(5, 2).len(), 3);
+        assert!(TextRange::caret(4).is_empty());
+    }
+
+    #[cfg(feature = "wasm_test")]
+    #[test]
+    fn maps_offsets_across_elements() {
+        let root = document().create_element("p").unwrap();
+        root.set_inner_html("Hé <b>bold</b> text, <i>bold</i>!");
+        document().body().unwrap().append_child(&root).unwrap();
+
+        let bold = root
+            .query_selector("b")
+            .unwrap()
+            .unwrap()
+            .first_child()
+            .unwrap();
+        assert_eq!(text_offset(&root, &bold, 2), Some(5));
+        let (node, offset) = dom_position(&root, 5).unwrap();
+        assert_eq!((node, offset), (bold, 2));
+
+        assert!(set_selection(&root, TextRange::new(3, 7)));
+        assert_eq!(selection_in(&root), Some(TextRange::new(3, 7)));
+        assert_eq!(
+            dom_range(&root, TextRange::new(3, 7)).unwrap().to_string(),
+            "bold"
+        );
+
+        assert_eq!(
+            find_text(&root, "bold"),
+            vec![TextRange::new(3, 7), TextRange::new(14, 18)]
+        );
+        root.remove();
+    }
+}
@@ -0,0 +1,474 @@
+//! Notifications that are dismissed automatically.
+//!
+//! [`ToastProvider`] renders a stack of toasts into the body of the document through a
+//! [portal](crate::create_portal) and provides a [`Toaster`] to its descendants, which function
+//! components get with [`use_toast`] and struct components use through [`Scope::toast`].
+//!
+//! The toasts are not styled. Their classes follow their lifecycle for CSS transitions:
+//! `yew-toast-entering` right after a toast was added, `yew-toast-shown` a moment later and
+//! `yew-toast-leaving` while it is removed. They also have a class of their [`ToastLevel`], e.g.
+//! `yew-toast-error`.
+//!
+//! The stack is an ARIA live region, so screen readers announce new toasts. Errors are
+//! announced right away.
+
+use crate::context::ContextProvider;
+use crate::functional::use_context;
+use crate::html::Scope;
+use crate::utils::document;
+use crate::{
+    classes, create_portal, html, Callback, Children, Component, Context, Html, Properties,
+};
+use gloo::timers::callback::Timeout;
+use std::cell::Cell;
+use std::collections::HashMap;
+use std::rc::Rc;
+use web_sys::Element;
+
+/// How a toast is announced and styled
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ToastLevel {
+    /// Information
+    Info,
+    /// The confirmation of an action
+    Success,
+    /// A problem that didn't prevent an action
+    Warning,
+    /// A failed action
+    Error,
+}
+
+impl ToastLevel {
+    fn class(self) -> &'static str {
+        match self {
+            Self::Info => "yew-toast-info",
+            Self::Success => "yew-toast-success",
+            Self::Warning => "yew-toast-warning",
+            Self::Error => "yew-toast-error",
+        }
+    }
+}
+
+impl Default for ToastLevel {
+    fn default() -> Self {
+        Self::Info
+    }
+}
+
+/// A notification
+#[derive(Debug, Clone, PartialEq)]
+pub struct Toast {
+    /// Text of the toast
+    pub message: String,
+    /// Level of the toast
+    pub level: ToastLevel,
+    /// Time after which the toast is dismissed. `None` uses the duration of the provider, see
+    /// [`sticky`](Self::sticky) for toasts dismissed by the user only.
+    pub duration_ms: Option<u32>,
+    sticky: bool,
+}
+
+impl Toast {
+    /// Creates a toast of `level`
+    pub fn new(level: ToastLevel, message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            level,
+            duration_ms: None,
+            sticky: false,
+        }
+    }
+
+    /// Creates an information toast
+    pub fn info(message: impl Into<String>) -> Self {
+        Self::new(ToastLevel::Info, message)
+    }
+
+    /// Creates a success toast
+    pub fn success(message: impl Into<String>) -> Self {
+        Self::new(ToastLevel::Success, message)
+    }
+
+    /// Creates a warning toast
+    pub fn warning(message: impl Into<String>) -> Self {
+        Self::new(ToastLevel::Warning, message)
+    }
+
+    /// Creates an error toast
+    pub fn error(message: impl Into<String>) -> Self {
+        Self::new(ToastLevel::Error, message)
+    }
+
+    /// Sets the time after which the toast is dismissed
+    pub fn duration_ms(mut self, duration_ms: u32) -> Self {
+        self.duration_ms = Some(duration_ms);
+        self
+    }
+
+    /// Keeps the toast until the user dismisses it
+    pub fn sticky(mut self) -> Self {
+        self.sticky = true;
+        self
+    }
+}
+
+impl From<&str> for Toast {
+    fn from(message: &str) -> Self {
+        Self::info(message)
+    }
+}
+
+impl From<String> for Toast {
+    fn from(message: String) -> Self {
+        Self::info(message)
+    }
+}
+
+/// Identifies a toast shown with [`Toaster::show`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ToastId(usize);
+
+/// Shows and dismisses the toasts of a [`ToastProvider`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Toaster {
+    link: Callback<ToastMsg>,
+    next_id: Rc<Cell<usize>>,
+}
+
+impl Toaster {
+    /// Shows `toast`
+    pub fn show(&self, toast: impl Into<Toast>) -> ToastId {
+        let id = ToastId(self.next_id.get());
+        self.next_id.set(id.0 + 1);
+        self.link.emit(ToastMsg::Show(id, toast.into()));
+        id
+    }
+
+    /// Dismisses the toast with `id`, if it is still shown
+    pub fn dismiss(&self, id: ToastId) {
+        self.link.emit(ToastMsg::Dismiss(id));
+    }
+}
+
+/// Returns the [`Toaster`] of the closest [`ToastProvider`].
+///
+/// # Panics
+/// Panics if the component is not a descendant of a [`ToastProvider`].
+///
+/// # Example
+/// ```rust
+/// # use yew::prelude::*;
+/// use yew::toast::{use_toast, Toast};
+///
+/// #[function_component(SaveButton)]
+/// fn save_button() -> Html {
+///     let toaster = use_toast();
+///     let onclick = Callback::from(move |_| {
+///         toaster.show(Toast::success("Saved"));
+///     });
+///     html! { <button {onclick}>{ "Save" }</button> }
+/// }
+/// ```
+pub fn use_toast() -> Toaster {
+    use_context::<Toaster>().expect("use_toast must be used inside a ToastProvider")
+}
+
+impl<COMP: Component> Scope<COMP> {
+    /// Shows `toast` with the closest [`ToastProvider`]. Returns `None` if the component is not a
+    /// descendant of one.
+    pub fn toast(&self, toast: impl Into<Toast>) -> Option<ToastId> {
+        let (toaster, _handle) = self.context::<Toaster>(Callback::noop())?;
+        Some(toaster.show(toast))
+    }
+}
+
+/// Properties of [`ToastProvider`]
+#[derive(Debug, Clone, PartialEq, Properties)]
+pub struct ToastProviderProps {
+    /// Time after which toasts are dismissed, unless they set one of their own
+    #[prop_or(4000)]
+    pub duration_ms: u32,
+    /// Duration of the leave transition, after which a dismissed toast is removed
+    #[prop_or(200)]
+    pub leave_ms: u32,
+    /// Maximum number of toasts shown at once. The oldest toasts are dismissed first.
+    #[prop_or(5)]
+    pub max: usize,
+    /// Classes of the stack
+    #[prop_or_default]
+    pub class: crate::Classes,
+    /// The components that may show toasts
+    #[prop_or_default]
+    pub children: Children,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Phase {
+    Entering,
+    Shown,
+    Leaving,
+}
+
+impl Phase {
+    fn class(self) -> &'static str {
+        match self {
+            Self::Entering => "yew-toast-entering",
+            Self::Shown => "yew-toast-shown",
+            Self::Leaving => "yew-toast-leaving",
+        }
+    }
+}
+
+struct Entry {
+    id: ToastId,
+    toast: Toast,
+    phase: Phase,
+}
+
+#[doc(hidden)]
+#[derive(Debug)]
+pub enum ToastMsg {
+    Show(ToastId, Toast),
+    Entered(ToastId),
+    Dismiss(ToastId),
+    Remove(ToastId),
+}
+
+/// Shows toasts on top of the page, see the [module docs](self).
+///
+/// # Example
+/// ```rust
+/// # use yew::prelude::*;
+/// use yew::toast::{Toast, ToastProvider};
+///
+/// struct Editor;
+///
+/// impl Component for Editor {
+///     type Message = ();
+///     type Properties = ();
+///
+///     fn create(_ctx: &Context<Self>) -> Self {
+///         Self
+///     }
+///
+///     fn update(&mut self, ctx: &Context<Self>, _msg: ()) -> bool {
+///         ctx.link().toast(Toast::error("Failed to save").sticky());
+///         false
+///     }
+///
+///     fn view(&self, ctx: &Context<Self>) -> Html {
+///         html! { <button onclick={ctx.link().callback(|_| ())}>{ "Save" }</button> }
+///     }
+/// }
+///
+/// # fn view() -> Html {
+/// html! {
+///     <ToastProvider duration_ms=3000>
+///         <Editor />
+///     </ToastProvider>
+/// }
+/// # }
+/// ```
+pub struct ToastProvider {
+    toaster: Toaster,
+    host: Element,
+    toasts: Vec<Entry>,
+    timers: HashMap<ToastId, Timeout>,
+}
+
+impl ToastProvider {
+    /// Calls `update` with `msg` after `ms`
+    fn schedule(&mut self, ctx: &Context<Self>, id: ToastId, ms: u32, msg: ToastMsg) {
+        let link = ctx.link().clone();
+        let timeout = Timeout::new(ms, move || link.send_message(msg));
+        self.timers.insert(id, timeout);
+    }
+
+    fn dismiss(&mut self, ctx: &Context<Self>, id: ToastId) -> bool {
+        match self.toasts.iter_mut().find(|entry| entry.id == id) {
+            Some(entry) if entry.phase != Phase::Leaving => {
+                entry.phase = Phase::Leaving;
+                self.schedule(ctx, id, ctx.props().leave_ms, ToastMsg::Remove(id));
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+impl Component for ToastProvider {
+    type Message = ToastMsg;
+    type Properties = ToastProviderProps;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        Self {
+            toaster: Toaster {
+                link: ctx.link().callback(|msg| msg),
+                next_id: Rc::default(),
+            },
+            host: document().body().expect("no body node found").into(),
+            toasts: Vec::new(),
+            timers: HashMap::new(),
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            ToastMsg::Show(id, toast) => {
+                self.toasts.push(Entry {
+                    id,
+                    toast,
+                    phase: Phase::Entering,
+                });
+                // The entering class has to be rendered before it is replaced, so it transitions
+                self.schedule(ctx, id, 20, ToastMsg::Entered(id));
+
+                let max = ctx.props().max;
+                let shown: Vec<_> = self
+                    .toasts
+                    .iter()
+                    .filter(|entry| entry.phase != Phase::Leaving)
+                    .map(|entry| entry.id)
+                    .collect();
+                for id in shown.iter().take(shown.len().saturating_sub(max)) {
+                    self.dismiss(ctx, *id);
+                }
+                true
+            }
+            ToastMsg::Entered(id) => {
+                let entry = match self.toasts.iter_mut().find(|entry| entry.id == id) {
+                    Some(entry) if entry.phase == Phase::Entering => entry,
+                    _ => return false,
+                };
+                entry.phase = Phase::Shown;
+                if !entry.toast.sticky {
+                    let duration_ms = entry.toast.duration_ms.unwrap_or(ctx.props().duration_ms);
+                    self.schedule(ctx, id, duration_ms, ToastMsg::Dismiss(id));
+                } else {
+                    self.timers.remove(&id);
+                }
+                true
+            }
+            ToastMsg::Dismiss(id) => self.dismiss(ctx, id),
+            ToastMsg::Remove(id) => {
+                self.timers.remove(&id);
+                self.toasts.retain(|entry| entry.id != id);
+                true
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let props = ctx.props();
+        let toasts = self.toasts.iter().map(|entry| {
+            let id = entry.id;
+            let ondismiss = ctx.link().callback(move |_| ToastMsg::Dismiss(id));
+            let role = if entry.toast.level == ToastLevel::Error {
+                Some("alert")
+            } else {
+                None
+            };
+            html! {
+                <div
+                    key={id.0.to_string()}
+                    class={classes!("yew-toast", entry.toast.level.class(), entry.phase.class())}
+                    {role}
+                >
+                    <span class="yew-toast-message">{ &entry.toast.message }</span>
+                    <button class="yew-toast-dismiss" aria-label="Dismiss" onclick={ondismiss}>
+                        { "×" }
+                    </button>
+                </div>
+            }
+        });
+        let stack = html! {
+            <div
+                class={classes!("yew-toasts", props.class.clone())}
+                role="status"
+                aria-live="polite"
+            >
+                { for toasts }
+            </div>
+        };
+
+        html! {
+            <ContextProvider<Toaster> context={self.toaster.clone()}>
+                { for props.children.iter() }
+                { create_portal(stack, self.host.clone()) }
+            </ContextProvider<Toaster>>
+        }
+    }
+}
+
+#[cfg(all(test, feature = "wasm_test"))]
+mod tests {
+    extern crate self as yew;
+
+    use super::*;
+    use wasm_bindgen_futures::JsFuture;
+    use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    async fn sleep(ms: i32) {
+        let promise = js_sys::Promise::new(&mut |resolve, _| {
+            crate::utils::window()
+                .set_timeout_with_callback_and_timeout_and_arguments_0(&resolve, ms)
+                .unwrap();
+        });
+        JsFuture::from(promise).await.unwrap();
+    }
+
+    struct Probe;
+
+    impl Component for Probe {
+        type Message = ();
+        type Properties = ();
+
+        fn create(ctx: &Context<Self>) -> Self {
+            ctx.link().toast(Toast::error("failed").duration_ms(50));
+            Self
+        }
+
+        fn view(&self, _ctx: &Context<Self>) -> Html {
+            html! {}
+        }
+    }
+
+    struct App;
+
+    impl Component for App {
+        type Message = ();
+        type Properties = ();
+
+        fn create(_ctx: &Context<Self>) -> Self {
+            Self
+        }
+
+        fn view(&self, _ctx: &Context<Self>) -> Html {
+            html! {
+                <ToastProvider leave_ms=10 class="test-toasts">
+                    <Probe />
+                </ToastProvider>
+            }
+        }
+    }
+
+    #[test]
+    async fn toasts_are_shown_and_dismissed() {
+        let root = document().create_element("div").unwrap();
+        document().body().unwrap().append_child(&root).unwrap();
+        crate::start_app_in_element::<App>(root);
+
+        let stack = document().query_selector(".test-toasts").unwrap().unwrap();
+        let toast = stack.first_element_child().unwrap();
+        assert_eq!(toast.get_attribute("role").as_deref(), Some("alert"));
+        assert!(toast.class_list().contains("yew-toast-entering"));
+
+        sleep(40).await;
+        assert!(toast.class_list().contains("yew-toast-shown"));
+
+        sleep(100).await;
+        assert_eq!(stack.child_element_count(), 0);
+    }
+}
@@ -0,0 +1,350 @@
+//! This module contains [`UndoableState`], a value with an undo and redo history.
+//!
+//! Struct components keep an `UndoableState` in their state and forward [`UndoMsg`]s to
+//! [`UndoableState::apply`]. Function components use [`use_undoable_state`]. Both can map the
+//! usual keyboard shortcuts to messages with [`UndoMsg::from_shortcut`].
+
+use crate::functional::use_hook;
+use std::collections::VecDeque;
+use std::fmt;
+use std::ops::Deref;
+use std::rc::Rc;
+use web_sys::KeyboardEvent;
+
+/// Number of checkpoints kept by [`UndoableState::new`]
+pub const DEFAULT_DEPTH: usize = 100;
+
+/// A change of the history of an [`UndoableState`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UndoMsg {
+    /// Goes back to the previous checkpoint
+    Undo,
+    /// Goes forward to the checkpoint left with [`Undo`](Self::Undo)
+    Redo,
+    /// Records the current value as a checkpoint
+    Checkpoint,
+}
+
+impl UndoMsg {
+    /// Returns the message of a keyboard shortcut: <kbd>Ctrl</kbd>+<kbd>Z</kbd> undoes,
+    /// <kbd>Ctrl</kbd>+<kbd>Shift</kbd>+<kbd>Z</kbd> and <kbd>Ctrl</kbd>+<kbd>Y</kbd> redo.
+    /// <kbd>⌘</kbd> works in place of <kbd>Ctrl</kbd>.
+    ///
+    /// Text inputs undo their own edits with the same shortcuts, so the default action of the
+    /// event should be prevented when a message is returned.
+    pub fn from_shortcut(event: &KeyboardEvent) -> Option<Self> {
+        if !(event.ctrl_key() || event.meta_key()) || event.alt_key() {
+            return None;
+        }
+        match (event.key().to_lowercase().as_str(), event.shift_key()) {
+            ("z", false) => Some(Self::Undo),
+            ("z", true) | ("y", false) => Some(Self::Redo),
+            _ => None,
+        }
+    }
+}
+
+/// A value that can go back to previous checkpoints.
+///
+/// [`set`](Self::set) records the current value as a checkpoint before replacing it. Changes
+/// made with [`get_mut`](Self::get_mut) are not recorded, so a burst of changes, e.g. typing,
+/// can be undone at once by calling [`checkpoint`](Self::checkpoint) before it. Only the most
+/// recent `depth` checkpoints are kept.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UndoableState<T> {
+    present: T,
+    past: VecDeque<T>,
+    future: Vec<T>,
+    depth: usize,
+}
+
+impl<T: Clone> UndoableState<T> {
+    /// Creates a new state keeping [`DEFAULT_DEPTH`] checkpoints
+    pub fn new(value: T) -> Self {
+        Self::with_depth(value, DEFAULT_DEPTH)
+    }
+
+    /// Creates a new state keeping `depth` checkpoints
+    pub fn with_depth(value: T, depth: usize) -> Self {
+        Self {
+            present: value,
+            past: VecDeque::new(),
+            future: Vec::new(),
+            depth,
+        }
+    }
+
+    /// Returns the current value
+    pub fn get(&self) -> &T {
+        &self.present
+    }
+
+    /// Returns the current value for changes that are not recorded as a checkpoint
+    pub fn get_mut(&mut self) -> &mut T {
+        &mut self.present
+    }
+
+    /// Records the current value as a checkpoint and replaces it with `value`
+    pub fn set(&mut self, value: T) {
+        self.checkpoint();
+        self.present = value;
+    }
+
+    /// Records the current value as a checkpoint, which discards the checkpoints that could be
+    /// redone
+    pub fn checkpoint(&mut self) {
+        if self.depth == 0 {
+            return;
+        }
+        if self.past.len() == self.depth {
+            self.past.pop_front();
+        }
+        self.past.push_back(self.present.clone());
+        self.future.clear();
+    }
+
+    /// Returns whether there is a checkpoint to go back to
+    pub fn can_undo(&self) -> bool {
+        !self.past.is_empty()
+    }
+
+    /// Returns whether there is a checkpoint to go forward to
+    pub fn can_redo(&self) -> bool {
+        !self.future.is_empty()
+    }
+
+    /// Goes back to the previous checkpoint. Returns whether there was one.
+    pub fn undo(&mut self) -> bool {
+        match self.past.pop_back() {
+            Some(value) => {
+                self.future
+                    .push(std::mem::replace(&mut self.present, value));
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Goes forward to the checkpoint left with [`undo`](Self::undo). Returns whether there was
+    /// one.
+    pub fn redo(&mut self) -> bool {
+        match self.future.pop() {
+            Some(value) => {
+                self.past
+                    .push_back(std::mem::replace(&mut self.present, value));
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Applies `msg` and returns whether the current value changed, which is what `update` of a
+    /// struct component returns
+    pub fn apply(&mut self, msg: UndoMsg) -> bool {
+        match msg {
+            UndoMsg::Undo => self.undo(),
+            UndoMsg::Redo => self.redo(),
+            UndoMsg::Checkpoint => {
+                self.checkpoint();
+                false
+            }
+        }
+    }
+}
+
+impl<T> Deref for UndoableState<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.present
+    }
+}
+
+struct UseUndoableState<T> {
+    state: Rc<UndoableState<T>>,
+}
+
+/// This hook keeps an [`UndoableState`] keeping `depth` checkpoints.
+///
+/// # Example
+/// ```rust
+/// # use yew::prelude::*;
+/// use yew::undo::{use_undoable_state, UndoMsg};
+///
+/// #[function_component(Counter)]
+/// fn counter() -> Html {
+///     let count = use_undoable_state(|| 0, 50);
+///     let onclick = {
+///         let count = count.clone();
+///         Callback::from(move |_| count.set(**count + 1))
+///     };
+///     let onkeydown = {
+///         let count = count.clone();
+///         Callback::from(move |e: KeyboardEvent| {
+///             if let Some(msg) = UndoMsg::from_shortcut(&e) {
+///                 e.prevent_default();
+///                 count.apply(msg);
+///             }
+///         })
+///     };
+///
+///     html! {
+///         <div tabindex="0" {onkeydown}>
+///             <button {onclick}>{ "+1" }</button>
+///             <p>{ **count }</p>
+///         </div>
+///     }
+/// }
+/// ```
+pub fn use_undoable_state<T, F>(initial: F, depth: usize) -> UseUndoableStateHandle<T>
+where
+    T: Clone + 'static,
+    F: FnOnce() -> T + 'static,
+{
+    use_hook(
+        move || UseUndoableState {
+            state: Rc::new(UndoableState::with_depth(initial(), depth)),
+        },
+        move |hook, updater| {
+            let dispatch: Rc<dyn Fn(Box<dyn FnOnce(&mut UndoableState<T>) -> bool>)> =
+                Rc::new(move |f| {
+                    updater.callback(move |st: &mut UseUndoableState<T>| {
+                        f(Rc::make_mut(&mut st.state))
+                    })
+                });
+            UseUndoableStateHandle {
+                state: Rc::clone(&hook.state),
+                dispatch,
+            }
+        },
+        |_| {},
+    )
+}
+
+/// Handle of the [`use_undoable_state`] hook.
+///
+/// It dereferences to the [`UndoableState`] of the current render. Its methods update the state
+/// and re-render the component if the current value changed.
+pub struct UseUndoableStateHandle<T> {
+    state: Rc<UndoableState<T>>,
+    #[allow(clippy::type_complexity)]
+    dispatch: Rc<dyn Fn(Box<dyn FnOnce(&mut UndoableState<T>) -> bool>)>,
+}
+
+impl<T: Clone + 'static> UseUndoableStateHandle<T> {
+    /// See [`UndoableState::set`]
+    pub fn set(&self, value: T) {
+        (self.dispatch)(Box::new(move |state| {
+            state.set(value);
+            true
+        }))
+    }
+
+    /// Changes the current value without recording a checkpoint, see
+    /// [`UndoableState::get_mut`]
+    pub fn update(&self, f: impl FnOnce(&mut T) + 'static) {
+        (self.dispatch)(Box::new(move |state| {
+            f(state.get_mut());
+            true
+        }))
+    }
+
+    /// See [`UndoableState::apply`]
+    pub fn apply(&self, msg: UndoMsg) {
+        (self.dispatch)(Box::new(move |state| state.apply(msg)))
+    }
+
+    /// See [`UndoableState::undo`]
+    pub fn undo(&self) {
+        self.apply(UndoMsg::Undo)
+    }
+
+    /// See [`UndoableState::redo`]
+    pub fn redo(&self) {
+        self.apply(UndoMsg::Redo)
+    }
+
+    /// See [`UndoableState::checkpoint`]
+    pub fn checkpoint(&self) {
+        self.apply(UndoMsg::Checkpoint)
+    }
+}
+
+impl<T> Deref for UseUndoableStateHandle<T> {
+    type Target = UndoableState<T>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.state
+    }
+}
+
+impl<T> Clone for UseUndoableStateHandle<T> {
+    fn clone(&self) -> Self {
+        Self {
+            state: Rc::clone(&self.state),
+            dispatch: Rc::clone(&self.dispatch),
+        }
+    }
+}
+
+impl<T> PartialEq for UseUndoableStateHandle<T> {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.state, &other.state)
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for UseUndoableStateHandle<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("UseUndoableStateHandle")
+            .field("state", &self.state)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "wasm_test")]
+    use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+
+    #[cfg(feature = "wasm_test")]
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    #[test]
+    fn undo_and_redo_between_checkpoints() {
+        let mut state = UndoableState::new(String::new());
+        state.set("a".to_string());
+        state.checkpoint();
+        state.get_mut().push('b');
+        state.get_mut().push('c');
+        assert_eq!(state.get(), "abc");
+
+        assert!(state.apply(UndoMsg::Undo));
+        assert_eq!(state.get(), "a");
+        assert!(state.apply(UndoMsg::Undo));
+        assert_eq!(state.get(), "");
+        assert!(!state.can_undo());
+
+        assert!(state.apply(UndoMsg::Redo));
+        assert_eq!(state.get(), "a");
+
+        // A new checkpoint discards what could be redone
+        state.set("x".to_string());
+        assert!(!state.can_redo());
+        assert!(state.undo());
+        assert_eq!(state.get(), "a");
+    }
+
+    #[test]
+    fn keeps_only_depth_checkpoints() {
+        let mut state = UndoableState::with_depth(0, 2);
+        for i in 1..=5 {
+            state.set(i);
+        }
+        assert!(state.undo());
+        assert!(state.undo());
+        assert!(!state.undo());
+        assert_eq!(*state, 3);
+    }
+}
@@ -17,6 +17,54 @@ pub fn document() -> Document {
     window().document().unwrap()
 }
 
+/// The window and document an app renders into.
+///
+/// Apps render into the current document by default. An app mounted with
+/// [`AppBuilder::environment`](crate::AppBuilder::environment) creates its nodes in the document
+/// of its environment instead, e.g. the document of an iframe or one created for tests, and its
+/// components reach it with [`Scope::environment`](crate::html::Scope::environment).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Environment {
+    window: Option<Window>,
+    document: Option<Document>,
+}
+
+impl Environment {
+    /// Returns the environment of the current window
+    pub fn current() -> Self {
+        Self::default()
+    }
+
+    /// Creates an environment of `document`. Its window is the one the document is shown in,
+    /// or the current window for documents which aren't shown, like those created with
+    /// [`DomImplementation::create_html_document`].
+    ///
+    /// [`DomImplementation::create_html_document`]: web_sys::DomImplementation::create_html_document
+    pub fn of_document(document: Document) -> Self {
+        Self {
+            window: document.default_view(),
+            document: Some(document),
+        }
+    }
+
+    /// Returns the window
+    pub fn window(&self) -> Window {
+        self.window.clone().unwrap_or_else(window)
+    }
+
+    /// Returns the document
+    pub fn document(&self) -> Document {
+        self.document.clone().unwrap_or_else(document)
+    }
+
+    /// Returns the document, or `None` if the environment is the current window and there is none
+    pub(crate) fn try_document(&self) -> Option<Document> {
+        self.document
+            .clone()
+            .or_else(|| web_sys::window()?.document())
+    }
+}
+
 /// Returns the `host` for the current document. Useful for connecting to the server which serves
 /// the app.
 pub fn host() -> Result<String, Error> {
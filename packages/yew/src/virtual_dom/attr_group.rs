@@ -0,0 +1,144 @@
+//! This module contains groups of attributes which are spread onto elements.
+
+use super::{AttrValue, Listener, VTag};
+use crate::html::{Classes, IntoPropValue};
+use std::rc::Rc;
+
+/// A reusable group of attributes, classes and listeners, created with [`attrs!`](crate::attrs)
+/// and spread onto elements in `html!` with `..group`.
+///
+/// The attributes of a group are defaults, so attributes set on the element take precedence.
+/// Classes are added to the classes of the element, and listeners are called in addition to
+/// those of the element.
+///
+/// # Example
+/// ```rust
+/// # use yew::prelude::*;
+/// use yew::attrs;
+///
+/// # fn view() -> Html {
+/// let button = attrs! { class="btn", role="button", tabindex=0 };
+/// html! {
+///     <>
+///         <span ..button>{ "Save" }</span>
+///         <span ..button class="btn-danger" tabindex="-1">{ "Delete" }</span>
+///     </>
+/// }
+/// # }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct AttrGroup {
+    attributes: Vec<(&'static str, AttrValue)>,
+    classes: Classes,
+    listeners: Vec<Rc<dyn Listener>>,
+}
+
+impl AttrGroup {
+    /// Creates an empty group
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the value of the attribute `key`
+    pub fn attribute(&self, key: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(k, _)| *k == key)
+            .map(|(_, value)| value.as_ref())
+    }
+
+    /// Sets the attribute `key`, or removes it if `value` is `None`
+    pub fn set_attribute(
+        &mut self,
+        key: &'static str,
+        value: impl IntoPropValue<Option<AttrValue>>,
+    ) {
+        self.attributes.retain(|(k, _)| *k != key);
+        if let Some(value) = value.into_prop_value() {
+            self.attributes.push((key, value));
+        }
+    }
+
+    /// Returns the classes
+    pub fn classes(&self) -> &Classes {
+        &self.classes
+    }
+
+    /// Adds classes
+    pub fn add_class(&mut self, classes: impl Into<Classes>) {
+        self.classes.push(classes);
+    }
+
+    /// Adds a listener
+    pub fn add_listener(&mut self, listener: Rc<dyn Listener>) {
+        self.listeners.push(listener);
+    }
+
+    #[doc(hidden)]
+    pub fn __macro_push_listener(&mut self, listener: Option<Rc<dyn Listener>>) {
+        self.listeners.extend(listener);
+    }
+
+    /// Adds the attributes, classes and listeners of the group to `tag`, keeping the attributes
+    /// it already has
+    pub(crate) fn spread(&self, tag: &mut VTag) {
+        if !self.attributes.is_empty() || !self.classes.is_empty() {
+            let attributes = tag.attributes.get_mut_index_map();
+            for (key, value) in &self.attributes {
+                attributes.entry(*key).or_insert_with(|| value.clone());
+            }
+            if !self.classes.is_empty() {
+                let mut classes = self.classes.clone();
+                if let Some(own) = attributes.get("class") {
+                    classes.push(own.to_string());
+                }
+                attributes.insert("class", classes.into_prop_value());
+            }
+        }
+        for listener in &self.listeners {
+            tag.add_listener(Rc::clone(listener));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate self as yew;
+
+    use super::*;
+    use crate::{attrs, html, Html};
+
+    #[cfg(feature = "wasm_test")]
+    use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+
+    #[cfg(feature = "wasm_test")]
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    fn attributes(node: Html) -> Vec<(&'static str, String)> {
+        match node {
+            Html::VTag(tag) => tag
+                .attributes
+                .iter()
+                .map(|(key, value)| (key, value.to_owned()))
+                .collect(),
+            _ => panic!("expected a tag"),
+        }
+    }
+
+    #[test]
+    fn element_attributes_take_precedence() {
+        let disabled = true;
+        let button = attrs! { class="btn", role="button", tabindex=0, {disabled} };
+        assert_eq!(button.attribute("tabindex"), Some("0"));
+        assert_eq!(button.attribute("disabled"), Some("disabled"));
+
+        let spread = attributes(html! { <span ..{&button} /> });
+        assert!(spread.contains(&("role", "button".to_owned())));
+        assert!(spread.contains(&("class", "btn".to_owned())));
+
+        let overridden = attributes(html! { <span ..button class="danger" tabindex="-1" /> });
+        assert!(overridden.contains(&("tabindex", "-1".to_owned())));
+        assert!(overridden.contains(&("class", "btn danger".to_owned())));
+        assert!(overridden.contains(&("role", "button".to_owned())));
+    }
+}
@@ -0,0 +1,138 @@
+//! Imperative commands run on elements after a commit.
+
+use crate::Callback;
+use std::cell::RefCell;
+use wasm_bindgen::JsCast;
+use web_sys::{Element, HtmlElement, HtmlInputElement, HtmlTextAreaElement};
+
+/// An imperative operation on an element, set with the `commands` attribute in `html!`, e.g.
+/// `<input commands={vec![ElementCommand::Focus]} />`.
+///
+/// Commands run once after the commit in which the element is created, and again after commits
+/// in which they differ from those of the previous render, so components don't need a [`NodeRef`]
+/// and flags to, for example, focus an input once it is shown.
+///
+/// [`NodeRef`]: crate::html::NodeRef
+#[derive(Clone, Debug, PartialEq)]
+pub enum ElementCommand {
+    /// Focuses the element
+    Focus,
+    /// Removes the focus from the element
+    Blur,
+    /// Selects the text of an `<input>` or `<textarea>`
+    Select,
+    /// Scrolls the element to the vertical offset in pixels
+    ScrollTo(i32),
+    /// Scrolls the page so the element is visible
+    ScrollIntoView,
+    /// Calls the callback with the element
+    Run(Callback<Element>),
+}
+
+impl ElementCommand {
+    fn run(&self, element: &Element) {
+        match self {
+            Self::Focus => {
+                if let Some(element) = element.dyn_ref::<HtmlElement>() {
+                    let _ = element.focus();
+                }
+            }
+            Self::Blur => {
+                if let Some(element) = element.dyn_ref::<HtmlElement>() {
+                    let _ = element.blur();
+                }
+            }
+            Self::Select => {
+                if let Some(input) = element.dyn_ref::<HtmlInputElement>() {
+                    input.select();
+                } else if let Some(textarea) = element.dyn_ref::<HtmlTextAreaElement>() {
+                    textarea.select();
+                }
+            }
+            Self::ScrollTo(top) => element.set_scroll_top(*top),
+            Self::ScrollIntoView => element.scroll_into_view(),
+            Self::Run(callback) => callback.emit(element.clone()),
+        }
+    }
+}
+
+thread_local! {
+    static QUEUED: RefCell<Vec<(Element, Vec<ElementCommand>)>> = Default::default();
+}
+
+/// Queues the commands of `element` to run after the commit
+pub(crate) fn queue(element: &Element, commands: Vec<ElementCommand>) {
+    QUEUED.with(|queued| queued.borrow_mut().push((element.clone(), commands)));
+}
+
+/// Runs the commands queued in the commit, in the order the elements were patched. Commands of
+/// elements which were removed again are dropped.
+pub(crate) fn end_commit() {
+    let queued = QUEUED.with(|queued| std::mem::take(&mut *queued.borrow_mut()));
+    for (element, commands) in queued {
+        if !element.is_connected() {
+            continue;
+        }
+        for command in &commands {
+            command.run(&element);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate self as yew;
+
+    use super::*;
+    use crate::html::{NodeRef, Scope};
+    use crate::utils::document;
+    use crate::{html, Component, Context, Html};
+    use std::rc::Rc;
+
+    #[cfg(feature = "wasm_test")]
+    use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+
+    #[cfg(feature = "wasm_test")]
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    struct Counter {
+        count: u32,
+    }
+
+    impl Component for Counter {
+        type Message = ();
+        type Properties = ();
+
+        fn create(_ctx: &Context<Self>) -> Self {
+            Counter { count: 0 }
+        }
+
+        fn update(&mut self, _ctx: &Context<Self>, _msg: Self::Message) -> bool {
+            self.count += 1;
+            true
+        }
+
+        fn view(&self, ctx: &Context<Self>) -> Html {
+            let commands = match self.count {
+                0 => vec![ElementCommand::Run(ctx.link().callback(|_| ()))],
+                _ => Vec::new(),
+            };
+            html! { <p commands={commands}>{ self.count }</p> }
+        }
+    }
+
+    #[test]
+    fn messages_of_commands_are_rendered() {
+        let scope = Scope::<Counter>::new(None);
+        let el = document().create_element("div").unwrap();
+        document().body().unwrap().append_child(&el).unwrap();
+        scope.mount_in_place(
+            el.clone(),
+            NodeRef::default(),
+            NodeRef::default(),
+            Rc::new(()),
+        );
+
+        assert_eq!(el.inner_html(), "<p>1</p>");
+    }
+}
@@ -0,0 +1,117 @@
+//! Support for inert subtrees, set with the `inert` attribute in `html!`.
+//!
+//! Browsers supporting the attribute keep users from focusing and interacting with an inert
+//! subtree. In other browsers, a polyfill stops focus and interaction events targeting an inert
+//! subtree in the capture phase. In both cases, Yew listeners ignore events targeting an inert
+//! subtree, including events dispatched by scripts.
+
+use gloo::events::{EventListener, EventListenerOptions};
+use std::cell::{Cell, RefCell};
+use wasm_bindgen::JsCast;
+use web_sys::{Document, Element, Event, HtmlElement, Node};
+
+/// Events stopped by the polyfill
+const INTERACTION_EVENTS: &[&str] = &[
+    "click",
+    "dblclick",
+    "contextmenu",
+    "mousedown",
+    "mouseup",
+    "pointerdown",
+    "pointerup",
+    "touchstart",
+    "touchend",
+    "keydown",
+    "keyup",
+    "keypress",
+    "focusin",
+];
+
+thread_local! {
+    /// Whether an inert element was rendered, so listeners only look for inert ancestors when
+    /// there can be some
+    static RENDERED: Cell<bool> = Cell::new(false);
+    /// Documents the polyfill is installed in
+    static POLYFILLED: RefCell<Vec<(Document, Vec<EventListener>)>> = Default::default();
+    static SUPPORTED: Cell<Option<bool>> = Cell::new(None);
+}
+
+/// Marks `element` as inert or not
+pub(crate) fn set(element: &Element, inert: bool) {
+    if !inert {
+        let _ = element.remove_attribute("inert");
+        return;
+    }
+
+    let _ = element.set_attribute("inert", "");
+    RENDERED.with(|rendered| rendered.set(true));
+    if !is_supported(element) {
+        if let Some(document) = element.owner_document() {
+            install_polyfill(document);
+        }
+    }
+}
+
+/// Returns whether the target of `event` is in an inert subtree
+pub(crate) fn is_inert_target(event: &Event) -> bool {
+    if !RENDERED.with(Cell::get) {
+        return false;
+    }
+    event
+        .target()
+        .and_then(|target| target.dyn_into::<Node>().ok())
+        .and_then(|node| match node.dyn_into::<Element>() {
+            Ok(element) => Some(element),
+            Err(node) => node.parent_element(),
+        })
+        .map_or(false, |element| is_inert(&element))
+}
+
+fn is_inert(element: &Element) -> bool {
+    matches!(element.closest("[inert]"), Ok(Some(_)))
+}
+
+fn is_supported(element: &Element) -> bool {
+    SUPPORTED.with(|supported| match supported.get() {
+        Some(supported) => supported,
+        None => {
+            let value = js_sys::Reflect::has(element, &"inert".into()).unwrap_or(false);
+            supported.set(Some(value));
+            value
+        }
+    })
+}
+
+fn install_polyfill(document: Document) {
+    POLYFILLED.with(|polyfilled| {
+        let mut polyfilled = polyfilled.borrow_mut();
+        if polyfilled
+            .iter()
+            .any(|(installed, _)| installed == &document)
+        {
+            return;
+        }
+
+        let options = EventListenerOptions::run_in_capture_phase();
+        let listeners = INTERACTION_EVENTS
+            .iter()
+            .map(|name| {
+                EventListener::new_with_options(&document, *name, options, |event| {
+                    let target = event
+                        .target()
+                        .and_then(|target| target.dyn_into::<Element>().ok());
+                    if let Some(target) = target.filter(is_inert) {
+                        event.prevent_default();
+                        event.stop_immediate_propagation();
+                        if event.type_() == "focusin" {
+                            if let Some(target) = target.dyn_ref::<HtmlElement>() {
+                                let _ = target.blur();
+                            }
+                        }
+                    }
+                })
+            })
+            .collect();
+        polyfilled.push((document, listeners));
+    });
+}
@@ -1,21 +1,114 @@
 //! This module contains the implementation yew's virtual nodes' keys.
 
 use crate::html::ImplicitClone;
+use std::cell::UnsafeCell;
+use std::cmp::Ordering;
+use std::convert::TryFrom;
 use std::fmt::{self, Display, Formatter};
+use std::hash::{Hash, Hasher};
 use std::ops::Deref;
 use std::rc::Rc;
 
 /// Represents the (optional) key of Yew's virtual nodes.
 ///
+/// Integer keys are stored without converting them to strings. Keys of different signedness
+/// compare equal, if they hold the same number, but an integer key never equals a string key.
+/// They still deref to their decimal representation, which is only formatted when it is first
+/// needed.
+///
 /// Keys are cheap to clone.
 #[derive(Clone, Debug, Ord, PartialOrd, Eq, PartialEq, Hash)]
 pub struct Key {
-    key: Rc<str>,
+    key: KeyInner,
+}
+
+/// Storage of a [Key]. Non-negative integers are always stored as [KeyInner::Unsigned], so the
+/// derived comparisons do not depend on the integer type a key was created from.
+#[derive(Clone, Debug, Ord, PartialOrd, Eq, PartialEq, Hash)]
+enum KeyInner {
+    Unsigned(u64, Digits),
+    Signed(i64, Digits),
+    Str(Rc<str>),
+}
+
+/// Decimal representation of an integer key, formatted when the key is first dereferenced. It is
+/// derived from the number, so it takes no part in comparisons. The thin pointer keeps integer
+/// keys as small as string keys.
+#[derive(Default)]
+struct Digits(UnsafeCell<Option<Rc<String>>>);
+
+impl Digits {
+    fn get(&self, n: impl Display) -> &str {
+        if self.cached().is_none() {
+            let digits = Rc::new(n.to_string());
+            // SAFETY: the cell is only written while it is empty, so no reference into it exists,
+            // and it can't be shared with other threads
+            unsafe { *self.0.get() = Some(digits) };
+        }
+        self.cached()
+            .as_deref()
+            .expect("digits were just formatted")
+    }
+
+    fn cached(&self) -> &Option<Rc<String>> {
+        // SAFETY: see `get`
+        unsafe { &*self.0.get() }
+    }
+}
+
+impl Clone for Digits {
+    fn clone(&self) -> Self {
+        Self(UnsafeCell::new(self.cached().clone()))
+    }
+}
+
+impl fmt::Debug for Digits {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.cached().fmt(f)
+    }
+}
+
+impl PartialEq for Digits {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl Eq for Digits {}
+
+impl PartialOrd for Digits {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Digits {
+    fn cmp(&self, _other: &Self) -> Ordering {
+        Ordering::Equal
+    }
+}
+
+impl Hash for Digits {
+    fn hash<H: Hasher>(&self, _state: &mut H) {}
+}
+
+impl Key {
+    /// Returns the key as a string slice, if it was created from a string
+    pub fn as_str(&self) -> Option<&str> {
+        match &self.key {
+            KeyInner::Str(s) => Some(s),
+            _ => None,
+        }
+    }
 }
 
 impl Display for Key {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        self.key.fmt(f)
+        match &self.key {
+            KeyInner::Unsigned(n, _) => n.fmt(f),
+            KeyInner::Signed(n, _) => n.fmt(f),
+            KeyInner::Str(s) => s.fmt(f),
+        }
     }
 }
 
@@ -23,13 +116,19 @@ impl Deref for Key {
     type Target = str;
 
     fn deref(&self) -> &str {
-        self.key.as_ref()
+        match &self.key {
+            KeyInner::Unsigned(n, digits) => digits.get(n),
+            KeyInner::Signed(n, digits) => digits.get(n),
+            KeyInner::Str(s) => s,
+        }
     }
 }
 
 impl From<Rc<str>> for Key {
     fn from(key: Rc<str>) -> Self {
-        Self { key }
+        Self {
+            key: KeyInner::Str(key),
+        }
     }
 }
 
@@ -40,6 +139,25 @@ impl From<&'_ str> for Key {
     }
 }
 
+impl From<u64> for Key {
+    fn from(key: u64) -> Self {
+        Self {
+            key: KeyInner::Unsigned(key, Digits::default()),
+        }
+    }
+}
+
+impl From<i64> for Key {
+    fn from(key: i64) -> Self {
+        Self {
+            key: match u64::try_from(key) {
+                Ok(n) => KeyInner::Unsigned(n, Digits::default()),
+                Err(_) => KeyInner::Signed(key, Digits::default()),
+            },
+        }
+    }
+}
+
 impl ImplicitClone for Key {}
 
 macro_rules! key_impl_from_to_string {
@@ -54,21 +172,46 @@ macro_rules! key_impl_from_to_string {
 
 key_impl_from_to_string!(String);
 key_impl_from_to_string!(char);
-key_impl_from_to_string!(u8);
-key_impl_from_to_string!(u16);
-key_impl_from_to_string!(u32);
-key_impl_from_to_string!(u64);
-key_impl_from_to_string!(u128);
-key_impl_from_to_string!(usize);
-key_impl_from_to_string!(i8);
-key_impl_from_to_string!(i16);
-key_impl_from_to_string!(i32);
-key_impl_from_to_string!(i64);
-key_impl_from_to_string!(i128);
-key_impl_from_to_string!(isize);
+
+macro_rules! key_impl_from_int {
+    ($wide:ty => $($type:ty)*) => {
+        $(
+            impl From<$type> for Key {
+                fn from(key: $type) -> Self {
+                    Self::from(key as $wide)
+                }
+            }
+        )*
+    };
+}
+
+key_impl_from_int!(u64 => u8 u16 u32 usize);
+key_impl_from_int!(i64 => i8 i16 i32 isize);
+
+macro_rules! key_impl_from_wide_int {
+    ($($type:ty)*) => {
+        $(
+            impl From<$type> for Key {
+                /// Falls back to a string key, if `key` does not fit into 64 bits
+                fn from(key: $type) -> Self {
+                    if let Ok(n) = u64::try_from(key) {
+                        Self::from(n)
+                    } else if let Ok(n) = i64::try_from(key) {
+                        Self::from(n)
+                    } else {
+                        Self::from(key.to_string().as_str())
+                    }
+                }
+            }
+        )*
+    };
+}
+
+key_impl_from_wide_int!(u128 i128);
 
 #[cfg(test)]
 mod test {
+    use super::Key;
     use crate::html;
     use std::rc::Rc;
 
@@ -100,4 +243,28 @@ mod test {
             </>
         };
     }
+
+    #[test]
+    fn integer_keys() {
+        assert_eq!(Key::from(5_u8), Key::from(5_i64));
+        assert_eq!(Key::from(-5_i32), Key::from(-5_i128));
+        assert_eq!(Key::from(u128::MAX), Key::from(u128::MAX.to_string()));
+        assert_ne!(Key::from(5_u32), Key::from("5"));
+        assert_eq!(Key::from(-5_i8).to_string(), "-5");
+        assert_eq!(Key::from("a").as_str(), Some("a"));
+        assert_eq!(Key::from(1_usize).as_str(), None);
+        assert_eq!(&*Key::from(i64::MIN), i64::MIN.to_string());
+        assert_eq!(&*Key::from(u64::MAX), u64::MAX.to_string());
+        assert_eq!(&*Key::from("a"), "a");
+    }
+
+    #[test]
+    fn integer_keys_are_formatted_lazily() {
+        let key = Key::from(42_u32);
+        let clone = key.clone();
+        assert_eq!(&*key, "42");
+        assert_eq!(&*key, "42");
+        assert_eq!(&*clone, "42");
+        assert!(std::mem::size_of::<Key>() <= 3 * std::mem::size_of::<u64>());
+    }
 }
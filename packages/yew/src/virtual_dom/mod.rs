@@ -1,7 +1,17 @@
 //! This module contains Yew's implementation of a reactive virtual DOM.
 
+#[doc(hidden)]
+pub mod attr_group;
+#[doc(hidden)]
+pub mod command;
+pub(crate) mod inert;
 #[doc(hidden)]
 pub mod key;
+pub(crate) mod mutation;
+pub mod ops;
+pub mod patch;
+pub(crate) mod scroll_anchor;
+pub(crate) mod style;
 #[doc(hidden)]
 pub mod vcomp;
 #[doc(hidden)]
@@ -9,6 +19,8 @@ pub mod vlist;
 #[doc(hidden)]
 pub mod vnode;
 #[doc(hidden)]
+pub mod vportal;
+#[doc(hidden)]
 pub mod vtag;
 #[doc(hidden)]
 pub mod vtext;
@@ -16,9 +28,14 @@ pub mod vtext;
 use crate::html::{AnyScope, NodeRef};
 use gloo::events::EventListener;
 use indexmap::IndexMap;
-use std::{borrow::Cow, collections::HashMap, fmt, hint::unreachable_unchecked, iter};
+use mutation::{Insertion, Mutation};
+use std::{borrow::Cow, collections::HashMap, fmt, hint::unreachable_unchecked, iter, rc::Rc};
 use web_sys::{Element, Node};
 
+#[doc(inline)]
+pub use self::attr_group::AttrGroup;
+#[doc(inline)]
+pub use self::command::ElementCommand;
 #[doc(inline)]
 pub use self::key::Key;
 #[doc(inline)]
@@ -28,6 +45,8 @@ pub use self::vlist::VList;
 #[doc(inline)]
 pub use self::vnode::VNode;
 #[doc(inline)]
+pub use self::vportal::VPortal;
+#[doc(inline)]
 pub use self::vtag::VTag;
 #[doc(inline)]
 pub use self::vtext::VText;
@@ -39,6 +58,28 @@ pub trait Listener {
     fn kind(&self) -> &'static str;
     /// Attaches a listener to the element.
     fn attach(&self, element: &Element) -> EventListener;
+
+    /// Returns the name of the DOM event, e.g. `click` for the kind `onclick`
+    fn event_name(&self) -> &'static str {
+        let kind = self.kind();
+        kind.strip_prefix("on").unwrap_or(kind)
+    }
+
+    /// Returns the function handling the events dispatched to the element, if the listener can be
+    /// called by Yew instead of being attached with [`attach`](Self::attach), e.g. when listeners
+    /// are delegated to the root of the app.
+    ///
+    /// Listeners returning `None` are always attached to their element.
+    #[allow(unused_variables)]
+    fn handler(&self, element: &Element) -> Option<Rc<dyn Fn(&web_sys::Event)>> {
+        None
+    }
+
+    /// Returns whether the listener is only called for the first event. Once it has fired, it is
+    /// not attached again when its element is patched.
+    fn is_once(&self) -> bool {
+        false
+    }
 }
 
 impl fmt::Debug for dyn Listener {
@@ -141,6 +182,27 @@ impl Attributes {
         }
     }
 
+    /// Replaces the value of the attribute `key` with the result of `f`, if the attribute is set.
+    pub(crate) fn map_value(&mut self, key: &str, f: impl FnOnce(&str) -> AttrValue) {
+        let value = match self {
+            Self::Static(arr) => {
+                if !arr.iter().any(|kv| kv[0] == key) {
+                    return;
+                }
+                self.get_mut_index_map().get_mut(key)
+            }
+            Self::Dynamic { keys, values } => match keys.iter().position(|k| *k == key) {
+                Some(i) => values[i].as_mut(),
+                None => None,
+            },
+            Self::IndexMap(m) => m.get_mut(key),
+        };
+        if let Some(value) = value {
+            let new = f(value);
+            *value = new;
+        }
+    }
+
     #[cold]
     fn apply_diff_index_maps<'a, A, B>(
         el: &Element,
@@ -160,7 +222,7 @@ impl Attributes {
                         break;
                     }
                     if new_value != old_value.as_ref() {
-                        Self::set_attribute(el, new_key, new_value);
+                        Self::update_attribute(el, new_key, old_value.as_ref(), new_value);
                     }
                 }
                 // new attributes
@@ -169,7 +231,7 @@ impl Attributes {
                         match old.get(key) {
                             Some(old_value) => {
                                 if value != old_value.as_ref() {
-                                    Self::set_attribute(el, key, value);
+                                    Self::update_attribute(el, key, old_value.as_ref(), value);
                                 }
                             }
                             None => {
@@ -216,11 +278,10 @@ impl Attributes {
 
         // Update existing or set new
         for (k, new) in new.iter() {
-            if match old.get(k) {
-                Some(old) => old != new,
-                None => true,
-            } {
-                el.set_attribute(k, new).unwrap();
+            match old.get(k) {
+                Some(old) if old != new => Self::update_attribute(el, k, old, new),
+                Some(_) => (),
+                None => Self::set_attribute(el, k, new),
             }
         }
 
@@ -233,10 +294,33 @@ impl Attributes {
     }
 
     fn set_attribute(el: &Element, key: &str, value: &str) {
+        mutation::record(Mutation::SetAttribute {
+            el,
+            name: key,
+            value,
+        });
         el.set_attribute(key, value).expect("invalid attribute key")
     }
 
+    /// Changes the attribute `key` from `old` to `new`. Only the changed declarations of the
+    /// `style` attribute are applied, when possible.
+    fn update_attribute(el: &Element, key: &str, old: &str, new: &str) {
+        let changes = match key {
+            "style" => style::apply_diff(el, old, new),
+            _ => None,
+        };
+        match changes {
+            Some(changes) => mutation::record(Mutation::UpdateStyle {
+                el,
+                value: new,
+                changes,
+            }),
+            None => Self::set_attribute(el, key, new),
+        }
+    }
+
     fn remove_attribute(el: &Element, key: &str) {
+        mutation::record(Mutation::RemoveAttribute { el, name: key });
         el.remove_attribute(key)
             .expect("could not remove attribute")
     }
@@ -305,7 +389,7 @@ impl Apply for Attributes {
                     match unsafe { (new_v.get_unchecked(i), old_v.get_unchecked(i)) } {
                         (Some(new), Some(old)) => {
                             if new != old {
-                                set!(new);
+                                Self::update_attribute(el, key!(), old, new);
                             }
                         }
                         (Some(new), None) => set!(new),
@@ -385,7 +469,18 @@ pub(crate) trait VDiff {
     ) -> NodeRef;
 }
 
-pub(crate) fn insert_node(node: &Node, parent: &Element, next_sibling: Option<&Node>) {
+pub(crate) fn insert_node(
+    node: &Node,
+    parent: &Element,
+    next_sibling: Option<&Node>,
+    insertion: Insertion,
+) {
+    mutation::record(Mutation::Insert {
+        parent,
+        node,
+        next_sibling,
+        insertion,
+    });
     match next_sibling {
         Some(next_sibling) => parent
             .insert_before(node, Some(next_sibling))
@@ -0,0 +1,242 @@
+//! The single point through which the virtual DOM reports its DOM operations.
+//!
+//! Every mutation is reported once with [`record`], right next to the call into the DOM API. The
+//! [`Op`]s of [`ops`](super::ops), the [`Patch`]es of [`patch`](super::patch) and the counters of
+//! [`stats`](crate::stats) are all derived from it, so they can't disagree.
+
+use super::ops::{self, Op};
+use super::patch::{self, Patch};
+use web_sys::{Element, Node};
+
+/// How a node is inserted. Only [`Patch`]es tell these apart.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Insertion {
+    /// A node created by the virtual DOM right before, which is part of its creation
+    Created,
+    /// A node rendered by a `VRef`
+    Reference,
+    /// A rendered node moved to another position
+    Moved,
+    /// A comment marking the bounds of a component
+    Marker,
+}
+
+/// A DOM operation performed by the virtual DOM
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum Mutation<'a> {
+    /// An element was created
+    CreateElement {
+        el: &'a Element,
+        tag: &'a str,
+        namespace: Option<&'a str>,
+    },
+    /// A text node was created
+    CreateText { node: &'a Node, text: &'a str },
+    /// A comment marking the bounds of a component was created
+    CreateMarker { node: &'a Node, text: &'a str },
+    /// A node is inserted into `parent` before `next_sibling`, or at the end
+    Insert {
+        parent: &'a Element,
+        node: &'a Node,
+        next_sibling: Option<&'a Node>,
+        insertion: Insertion,
+    },
+    /// A node is removed from its parent
+    Remove { node: &'a Node },
+    /// A comment marking the bounds of a component is removed from its parent
+    RemoveMarker { node: &'a Node },
+    /// An attribute is set
+    SetAttribute {
+        el: &'a Element,
+        name: &'a str,
+        value: &'a str,
+    },
+    /// The `style` attribute was changed to `value` by `changes` calls of `setProperty()` and
+    /// `removeProperty()`
+    UpdateStyle {
+        el: &'a Element,
+        value: &'a str,
+        changes: u64,
+    },
+    /// An attribute is removed
+    RemoveAttribute { el: &'a Element, name: &'a str },
+    /// The text of a text node is changed
+    SetText { node: &'a Node, text: &'a str },
+    /// The `value` of an `input` or `textarea` element is set
+    SetValue { node: &'a Node, value: &'a str },
+    /// The `checked` state of an `input` element is set
+    SetChecked { node: &'a Node, checked: bool },
+}
+
+impl Mutation<'_> {
+    fn to_op(self) -> Op {
+        match self {
+            Self::CreateElement { el, tag, namespace } => Op::CreateElement {
+                id: ops::node_id(el),
+                tag: tag.into(),
+                namespace: namespace.map(Into::into),
+            },
+            Self::CreateText { node, text } => Op::CreateText {
+                id: ops::node_id(node),
+                text: text.into(),
+            },
+            Self::CreateMarker { node, text } => Op::CreateComment {
+                id: ops::node_id(node),
+                text: text.into(),
+            },
+            Self::Insert {
+                parent,
+                node,
+                next_sibling,
+                ..
+            } => Op::Insert {
+                parent: ops::node_id(parent),
+                node: ops::node_id(node),
+                next_sibling: next_sibling.map(ops::node_id),
+            },
+            Self::Remove { node } | Self::RemoveMarker { node } => Op::Remove {
+                node: ops::node_id(node),
+            },
+            Self::SetAttribute { el, name, value } => Op::SetAttribute {
+                node: ops::node_id(el),
+                name: name.into(),
+                value: value.into(),
+            },
+            Self::UpdateStyle { el, value, .. } => Op::SetAttribute {
+                node: ops::node_id(el),
+                name: "style".into(),
+                value: value.into(),
+            },
+            Self::RemoveAttribute { el, name } => Op::RemoveAttribute {
+                node: ops::node_id(el),
+                name: name.into(),
+            },
+            Self::SetText { node, text } => Op::SetText {
+                node: ops::node_id(node),
+                text: text.into(),
+            },
+            Self::SetValue { node, value } => Op::SetValue {
+                node: ops::node_id(node),
+                value: value.into(),
+            },
+            Self::SetChecked { node, checked } => Op::SetChecked {
+                node: ops::node_id(node),
+                checked,
+            },
+        }
+    }
+
+    /// Returns the [`Patch`] of the operation. Insertions of new nodes are part of their
+    /// creation, and component markers are no [`Patch`]es at all.
+    fn to_patch(self) -> Option<Patch> {
+        Some(match self {
+            Self::CreateElement { tag, .. } => Patch::CreateElement { tag: tag.into() },
+            Self::CreateText { text, .. } => Patch::CreateText { text: text.into() },
+            Self::Insert { insertion, .. } => match insertion {
+                Insertion::Reference => Patch::Insert,
+                Insertion::Moved => Patch::Move,
+                Insertion::Created | Insertion::Marker => return None,
+            },
+            Self::Remove { .. } => Patch::Remove,
+            Self::SetAttribute { name, value, .. } => Patch::SetAttribute {
+                name: name.into(),
+                value: value.into(),
+            },
+            Self::UpdateStyle { value, .. } => Patch::SetAttribute {
+                name: "style".into(),
+                value: value.into(),
+            },
+            Self::RemoveAttribute { name, .. } => Patch::RemoveAttribute { name: name.into() },
+            Self::SetText { text, .. } => Patch::SetText { text: text.into() },
+            Self::SetValue { value, .. } => Patch::SetValue {
+                value: value.into(),
+            },
+            Self::SetChecked { checked, .. } => Patch::SetChecked { checked },
+            Self::CreateMarker { .. } | Self::RemoveMarker { .. } => return None,
+        })
+    }
+
+    #[cfg(feature = "stats")]
+    fn count(self, ops: &mut crate::stats::DomOps) {
+        match self {
+            Self::CreateElement { .. } => ops.create_element += 1,
+            Self::CreateText { .. } => ops.create_text += 1,
+            // Not a counted kind of operation
+            Self::CreateMarker { .. } => {}
+            Self::Insert { .. } => ops.insert_before += 1,
+            Self::Remove { .. } | Self::RemoveMarker { .. } => ops.remove_child += 1,
+            Self::SetAttribute { .. } => ops.set_attribute += 1,
+            Self::UpdateStyle { changes, .. } => ops.set_style += changes,
+            Self::RemoveAttribute { .. } => ops.remove_attribute += 1,
+            Self::SetText { .. } => ops.set_text += 1,
+            Self::SetValue { .. } => ops.set_value += 1,
+            Self::SetChecked { .. } => ops.set_checked += 1,
+        }
+    }
+}
+
+/// Reports a DOM operation of the virtual DOM. Nothing is allocated unless operations or
+/// patches are being recorded.
+pub(crate) fn record(mutation: Mutation<'_>) {
+    #[cfg(feature = "stats")]
+    crate::stats::count(|ops| mutation.count(ops));
+    patch::record(|| mutation.to_patch());
+    ops::record(|| mutation.to_op());
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate self as yew;
+
+    use super::*;
+    use crate::html::{NodeRef, Scope};
+    use crate::utils::document;
+    use crate::{html, Component, Context, Html};
+    use std::rc::Rc;
+
+    #[cfg(feature = "wasm_test")]
+    use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+
+    #[cfg(feature = "wasm_test")]
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    struct Comp;
+
+    impl Component for Comp {
+        type Message = ();
+        type Properties = ();
+
+        fn create(_ctx: &Context<Self>) -> Self {
+            Comp
+        }
+
+        fn view(&self, _ctx: &Context<Self>) -> Html {
+            html! { <p class="a">{ "text" }</p> }
+        }
+    }
+
+    #[test]
+    fn patches_and_ops_are_derived_together() {
+        let scope = Scope::<Comp>::new(None);
+        let el = document().create_element("div").unwrap();
+        let mut patches = Vec::new();
+        let ops = ops::record_ops(|| {
+            patches = patch::record_patches(|| {
+                scope.mount_in_place(el, NodeRef::default(), NodeRef::default(), Rc::new(()))
+            })
+        });
+
+        let ops: Vec<_> = ops
+            .into_iter()
+            .filter(|op| !matches!(op, Op::Insert { .. }))
+            .map(|op| match op {
+                Op::CreateElement { tag, .. } => Patch::CreateElement { tag },
+                Op::CreateText { text, .. } => Patch::CreateText { text },
+                Op::Remove { .. } => Patch::Remove,
+                Op::SetAttribute { name, value, .. } => Patch::SetAttribute { name, value },
+                op => panic!("unexpected operation {:?}", op),
+            })
+            .collect();
+        assert_eq!(ops, patches);
+    }
+}
@@ -0,0 +1,448 @@
+//! A serializable format of the DOM operations performed by the virtual DOM.
+//!
+//! Unlike [`Patch`](super::patch::Patch), which describes operations for assertions in tests,
+//! an [`Op`] names the nodes it applies to, so a recorded sequence can be sent over a channel,
+//! e.g. to another window or to a thin client, and replayed on another document by an
+//! [`OpApplier`].
+//!
+//! ```rust
+//! # use yew::html::Scope;
+//! # use yew::Component;
+//! use yew::virtual_dom::ops::{self, OpApplier};
+//!
+//! # fn example<C: Component>(
+//! #     scope: Scope<C>,
+//! #     msg: C::Message,
+//! #     host: web_sys::Element,
+//! #     mirror: web_sys::Element,
+//! # ) {
+//! let ops = ops::record_ops(|| scope.send_message(msg));
+//! let json = serde_json::to_string(&ops).unwrap();
+//!
+//! // On the receiving side, with `mirror` standing for `host`
+//! let mut applier = OpApplier::new();
+//! applier.bind(ops::node_id(&host), mirror.into());
+//! applier.apply_all(serde_json::from_str(&json).unwrap()).unwrap();
+//! # }
+//! ```
+//!
+//! Nodes rendered by `VRef`s were not created by the virtual DOM, so operations on them can only
+//! be replayed if they are bound with [`OpApplier::bind`] first.
+
+use crate::utils::Environment;
+use crate::Callback;
+use serde::{Deserialize, Serialize};
+use slab::Slab;
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use wasm_bindgen::{JsCast, JsValue};
+use web_sys::{Element, HtmlInputElement, HtmlTextAreaElement, Node};
+
+/// Identifier of a DOM node in recorded operations
+pub type NodeId = u32;
+
+/// A DOM operation on nodes identified by [`NodeId`]s
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Op {
+    /// Creates an element, which is not inserted yet
+    CreateElement {
+        /// Id of the new element
+        id: NodeId,
+        /// Tag name of the element
+        tag: String,
+        /// Namespace of the element, if it is not an HTML element
+        namespace: Option<String>,
+    },
+    /// Creates a text node, which is not inserted yet
+    CreateText {
+        /// Id of the new node
+        id: NodeId,
+        /// Text of the node
+        text: String,
+    },
+    /// Creates a comment, which is not inserted yet. Only recorded for the markers of the
+    /// `component_markers` feature.
+    CreateComment {
+        /// Id of the new node
+        id: NodeId,
+        /// Text of the comment
+        text: String,
+    },
+    /// Inserts `node` into `parent` before `next_sibling`, or at the end. Inserting a node that
+    /// is already in the document moves it.
+    Insert {
+        /// Id of the parent element
+        parent: NodeId,
+        /// Id of the inserted node
+        node: NodeId,
+        /// Id of the node to insert before
+        next_sibling: Option<NodeId>,
+    },
+    /// Removes a node from its parent
+    Remove {
+        /// Id of the removed node
+        node: NodeId,
+    },
+    /// Sets an attribute
+    SetAttribute {
+        /// Id of the element
+        node: NodeId,
+        /// Name of the attribute
+        name: String,
+        /// New value of the attribute
+        value: String,
+    },
+    /// Removes an attribute
+    RemoveAttribute {
+        /// Id of the element
+        node: NodeId,
+        /// Name of the attribute
+        name: String,
+    },
+    /// Changes the text of a text node
+    SetText {
+        /// Id of the text node
+        node: NodeId,
+        /// New text of the node
+        text: String,
+    },
+    /// Sets the `value` of an `input` or `textarea` element
+    SetValue {
+        /// Id of the element
+        node: NodeId,
+        /// New value
+        value: String,
+    },
+    /// Sets the `checked` state of an `input` element
+    SetChecked {
+        /// Id of the element
+        node: NodeId,
+        /// New state
+        checked: bool,
+    },
+}
+
+/// Name of the property holding the [`NodeId`] of a node
+const NODE_ID_KEY: &str = "__yew_node_id";
+
+thread_local! {
+    static RECORDED: RefCell<Option<Vec<Op>>> = RefCell::new(None);
+    static NEXT_NODE_ID: Cell<NodeId> = Cell::new(0);
+    /// Operations of the current commit, if anyone subscribed to commits
+    static COMMIT: RefCell<Option<Vec<Op>>> = RefCell::new(None);
+    static SUBSCRIBERS: RefCell<Slab<Callback<Vec<Op>>>> = Default::default();
+}
+
+/// Returns the id of `node` in recorded operations, assigning one if it has none yet
+pub fn node_id(node: &Node) -> NodeId {
+    let key = JsValue::from_str(NODE_ID_KEY);
+    if let Some(id) = js_sys::Reflect::get(node, &key)
+        .ok()
+        .and_then(|id| id.as_f64())
+    {
+        return id as NodeId;
+    }
+
+    let id = NEXT_NODE_ID.with(|id| id.replace(id.get() + 1));
+    let _ = js_sys::Reflect::set(node, &key, &JsValue::from(id));
+    id
+}
+
+/// Records the operation returned by `op`, if operations are being recorded
+pub(crate) fn record(op: impl FnOnce() -> Op) {
+    RECORDED.with(|recorded| {
+        COMMIT.with(|commit| {
+            let mut recorded = recorded.borrow_mut();
+            let mut commit = commit.borrow_mut();
+            if recorded.is_none() && commit.is_none() {
+                return;
+            }
+            let op = op();
+            if let Some(commit) = commit.as_mut() {
+                commit.push(op.clone());
+            }
+            if let Some(recorded) = recorded.as_mut() {
+                recorded.push(op);
+            }
+        })
+    });
+}
+
+/// Notifies the subscribers of the operations of the commit that just ended
+pub(crate) fn end_commit() {
+    let ops = COMMIT.with(|commit| match commit.borrow_mut().as_mut() {
+        Some(ops) if !ops.is_empty() => Some(std::mem::take(ops)),
+        _ => None,
+    });
+    if let Some(ops) = ops {
+        let subscribers: Vec<_> =
+            SUBSCRIBERS.with(|s| s.borrow().iter().map(|(_, cb)| cb.clone()).collect());
+        for subscriber in subscribers {
+            subscriber.emit(ops.clone());
+        }
+    }
+}
+
+/// Calls `callback` with the operations of every commit, i.e. every run of the scheduler that
+/// changed the DOM, until the returned handle is dropped.
+///
+/// Unlike [`record_ops`], this also covers commits caused by futures and timers.
+pub fn subscribe_commits(callback: Callback<Vec<Op>>) -> CommitSubscription {
+    let key = SUBSCRIBERS.with(|s| s.borrow_mut().insert(callback));
+    COMMIT.with(|commit| {
+        commit.borrow_mut().get_or_insert_with(Vec::new);
+    });
+    CommitSubscription { key }
+}
+
+/// Subscription created by [`subscribe_commits`]
+#[derive(Debug)]
+pub struct CommitSubscription {
+    key: usize,
+}
+
+impl Drop for CommitSubscription {
+    fn drop(&mut self) {
+        let empty = SUBSCRIBERS.with(|s| {
+            let mut s = s.borrow_mut();
+            s.remove(self.key);
+            s.is_empty()
+        });
+        if empty {
+            COMMIT.with(|commit| *commit.borrow_mut() = None);
+        }
+    }
+}
+
+/// Runs `f` and returns the DOM operations it caused, in order.
+///
+/// Nested recordings are merged: the operations are returned by the innermost call and also
+/// recorded by the outer ones.
+pub fn record_ops(f: impl FnOnce()) -> Vec<Op> {
+    let outer = RECORDED.with(|recorded| recorded.borrow_mut().replace(Vec::new()));
+    f();
+    let ops = RECORDED
+        .with(|recorded| recorded.borrow_mut().take())
+        .unwrap_or_default();
+    if let Some(mut outer) = outer {
+        outer.extend(ops.iter().cloned());
+        RECORDED.with(|recorded| *recorded.borrow_mut() = Some(outer));
+    }
+    ops
+}
+
+/// Error applying an [`Op`]
+#[derive(Debug, thiserror::Error)]
+pub enum ApplyError {
+    /// The operation refers to a node that was neither created by an earlier operation nor bound
+    #[error("unknown node {0}")]
+    UnknownNode(NodeId),
+    /// The node has the wrong type for the operation, e.g. `SetChecked` on a text node
+    #[error("node {0} does not support the operation")]
+    WrongNodeType(NodeId),
+    /// The DOM rejected the operation
+    #[error("DOM error: {0:?}")]
+    Dom(JsValue),
+}
+
+impl From<JsValue> for ApplyError {
+    fn from(err: JsValue) -> Self {
+        Self::Dom(err)
+    }
+}
+
+/// Replays recorded [`Op`]s on the current document, or the document of an [`Environment`]
+#[derive(Debug, Default)]
+pub struct OpApplier {
+    nodes: HashMap<NodeId, Node>,
+    environment: Environment,
+}
+
+impl OpApplier {
+    /// Creates an applier that knows no nodes
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates an applier that creates nodes in the document of `environment`
+    pub fn with_environment(environment: Environment) -> Self {
+        Self {
+            nodes: HashMap::new(),
+            environment,
+        }
+    }
+
+    /// Makes `node` stand for the node with the id `id` on the recording side, typically the
+    /// element the app is mounted in
+    pub fn bind(&mut self, id: NodeId, node: Node) {
+        self.nodes.insert(id, node);
+    }
+
+    /// Returns the node standing for `id`
+    pub fn node(&self, id: NodeId) -> Option<&Node> {
+        self.nodes.get(&id)
+    }
+
+    /// Returns the id `node` stands for
+    pub fn id_of(&self, node: &Node) -> Option<NodeId> {
+        self.nodes
+            .iter()
+            .find(|(_, n)| *n == node)
+            .map(|(id, _)| *id)
+    }
+
+    fn get(&self, id: NodeId) -> Result<&Node, ApplyError> {
+        self.nodes.get(&id).ok_or(ApplyError::UnknownNode(id))
+    }
+
+    fn get_as<T: JsCast>(&self, id: NodeId) -> Result<&T, ApplyError> {
+        self.get(id)?
+            .dyn_ref::<T>()
+            .ok_or(ApplyError::WrongNodeType(id))
+    }
+
+    /// Applies a single operation
+    pub fn apply(&mut self, op: Op) -> Result<(), ApplyError> {
+        match op {
+            Op::CreateElement { id, tag, namespace } => {
+                let document = self.environment.document();
+                let el = match namespace {
+                    Some(ns) => document.create_element_ns(Some(&ns), &tag)?,
+                    None => document.create_element(&tag)?,
+                };
+                self.nodes.insert(id, el.into());
+            }
+            Op::CreateText { id, text } => {
+                let text = self.environment.document().create_text_node(&text);
+                self.nodes.insert(id, text.into());
+            }
+            Op::CreateComment { id, text } => {
+                let comment = self.environment.document().create_comment(&text);
+                self.nodes.insert(id, comment.into());
+            }
+            Op::Insert {
+                parent,
+                node,
+                next_sibling,
+            } => {
+                let next_sibling = next_sibling.map(|id| self.get(id)).transpose()?;
+                self.get(parent)?
+                    .insert_before(self.get(node)?, next_sibling)?;
+            }
+            Op::Remove { node } => {
+                let node = self
+                    .nodes
+                    .remove(&node)
+                    .ok_or(ApplyError::UnknownNode(node))?;
+                if let Some(parent) = node.parent_node() {
+                    parent.remove_child(&node)?;
+                }
+            }
+            Op::SetAttribute { node, name, value } => {
+                self.get_as::<Element>(node)?.set_attribute(&name, &value)?;
+            }
+            Op::RemoveAttribute { node, name } => {
+                self.get_as::<Element>(node)?.remove_attribute(&name)?;
+            }
+            Op::SetText { node, text } => self.get(node)?.set_node_value(Some(&text)),
+            Op::SetValue { node, value } => {
+                if let Some(input) = self.get(node)?.dyn_ref::<HtmlInputElement>() {
+                    input.set_value(&value);
+                } else {
+                    self.get_as::<HtmlTextAreaElement>(node)?.set_value(&value);
+                }
+            }
+            Op::SetChecked { node, checked } => {
+                self.get_as::<HtmlInputElement>(node)?.set_checked(checked)
+            }
+        }
+        Ok(())
+    }
+
+    /// Applies operations in order, stopping at the first error
+    pub fn apply_all(&mut self, ops: impl IntoIterator<Item = Op>) -> Result<(), ApplyError> {
+        ops.into_iter().try_for_each(|op| self.apply(op))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate self as yew;
+
+    use super::*;
+    use crate::html::{NodeRef, Scope};
+    use crate::utils::document;
+    use crate::{html, Component, Context, Html};
+    use std::rc::Rc;
+
+    #[cfg(feature = "wasm_test")]
+    use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+
+    #[cfg(feature = "wasm_test")]
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    struct List {
+        items: Vec<u32>,
+    }
+
+    impl Component for List {
+        type Message = Vec<u32>;
+        type Properties = ();
+
+        fn create(_ctx: &Context<Self>) -> Self {
+            Self { items: vec![1, 2] }
+        }
+
+        fn update(&mut self, _ctx: &Context<Self>, items: Self::Message) -> bool {
+            self.items = items;
+            true
+        }
+
+        fn view(&self, _ctx: &Context<Self>) -> Html {
+            html! {
+                <ul class={format!("len-{}", self.items.len())}>
+                    { for self.items.iter().map(|i| html! { <li key={*i}>{ i }</li> }) }
+                </ul>
+            }
+        }
+    }
+
+    /// Sends the operations through JSON, as they would be sent to another document
+    fn replay(applier: &mut OpApplier, ops: Vec<Op>) {
+        let json = serde_json::to_string(&ops).unwrap();
+        applier
+            .apply_all(serde_json::from_str::<Vec<Op>>(&json).unwrap())
+            .unwrap();
+    }
+
+    #[test]
+    fn replayed_ops_mirror_the_dom() {
+        let host = document().create_element("div").unwrap();
+        let mirror = document().create_element("div").unwrap();
+        let mut applier = OpApplier::new();
+        applier.bind(node_id(&host), mirror.clone().into());
+
+        let scope = Scope::<List>::new(None);
+        let ops = record_ops(|| {
+            scope.mount_in_place(
+                host.clone(),
+                NodeRef::default(),
+                NodeRef::default(),
+                Rc::new(()),
+            )
+        });
+        replay(&mut applier, ops);
+        assert_eq!(mirror.inner_html(), host.inner_html());
+
+        let ops = record_ops(|| scope.send_message(vec![3, 2, 1]));
+        assert!(ops
+            .iter()
+            .all(|op| !matches!(op, Op::CreateElement { tag, .. } if tag == "ul")));
+        replay(&mut applier, ops);
+        assert_eq!(mirror.inner_html(), host.inner_html());
+        assert_eq!(
+            mirror.inner_html(),
+            r#"<ul class="len-3"><li>3</li><li>2</li><li>1</li></ul>"#
+        );
+    }
+}
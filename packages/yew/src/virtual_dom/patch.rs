@@ -0,0 +1,313 @@
+//! Recording of the DOM operations performed by the virtual DOM.
+//!
+//! This is meant for tests of performance-sensitive components: wrap a state change in
+//! [`record_patches`], or use a [`PatchTester`], and assert on the operations it caused, e.g.
+//! that toggling a flag sets exactly one attribute and creates no nodes.
+
+use crate::html::{NodeRef, Scope};
+use crate::utils::document;
+use crate::Component;
+use std::cell::RefCell;
+use std::rc::Rc;
+use web_sys::Element;
+
+/// A single operation on the DOM
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Patch {
+    /// An element was created and inserted
+    CreateElement {
+        /// Tag name of the element
+        tag: String,
+    },
+    /// A text node was created and inserted. Components insert an empty text node as their
+    /// placeholder when mounted.
+    CreateText {
+        /// Text of the node
+        text: String,
+    },
+    /// A node referenced by a `VRef` was inserted
+    Insert,
+    /// A rendered node was moved to another position
+    Move,
+    /// A node was removed from its parent
+    Remove,
+    /// An attribute was set
+    SetAttribute {
+        /// Name of the attribute
+        name: String,
+        /// New value of the attribute
+        value: String,
+    },
+    /// An attribute was removed
+    RemoveAttribute {
+        /// Name of the attribute
+        name: String,
+    },
+    /// The text of a text node was changed
+    SetText {
+        /// New text of the node
+        text: String,
+    },
+    /// The `value` of an `input` or `textarea` element was set
+    SetValue {
+        /// New value
+        value: String,
+    },
+    /// The `checked` state of an `input` element was set. This happens on every patch of an
+    /// `input` element.
+    SetChecked {
+        /// New state
+        checked: bool,
+    },
+}
+
+impl Patch {
+    /// Returns `true` if the operation created, inserted, moved or removed a node, as opposed to
+    /// updating an existing node.
+    pub fn is_structural(&self) -> bool {
+        matches!(
+            self,
+            Self::CreateElement { .. }
+                | Self::CreateText { .. }
+                | Self::Insert
+                | Self::Move
+                | Self::Remove
+        )
+    }
+}
+
+thread_local! {
+    static RECORDED: RefCell<Option<Vec<Patch>>> = RefCell::new(None);
+}
+
+/// Records the patch returned by `patch`, if a recording is in progress. `patch` is only called
+/// in that case, so nothing is allocated otherwise.
+pub(crate) fn record(patch: impl FnOnce() -> Option<Patch>) {
+    RECORDED.with(|recorded| {
+        if let Some(patches) = recorded.borrow_mut().as_mut() {
+            patches.extend(patch());
+        }
+    });
+}
+
+/// Runs `f` and returns the DOM operations the virtual DOM performed meanwhile, in order.
+///
+/// Updates of components are included if they are processed before `f` returns, which is the
+/// case for messages sent and props passed outside of another update.
+///
+/// # Example
+/// ```rust
+/// # use yew::prelude::*;
+/// use yew::virtual_dom::patch::{record_patches, Patch};
+///
+/// # enum Msg { Select(usize) }
+/// # struct Table;
+/// # impl Component for Table {
+/// #     type Message = Msg;
+/// #     type Properties = ();
+/// #     fn create(_ctx: &Context<Self>) -> Self { Table }
+/// #     fn view(&self, _ctx: &Context<Self>) -> Html { html! {} }
+/// # }
+/// fn assert_select_is_cheap(link: &yew::html::Scope<Table>) {
+///     let patches = record_patches(|| link.send_message(Msg::Select(3)));
+///     // Only the `class` of the old and the new selected row change
+///     assert_eq!(patches.len(), 2);
+///     assert!(patches.iter().all(|p| matches!(p, Patch::SetAttribute { .. })));
+/// }
+/// ```
+pub fn record_patches(f: impl FnOnce()) -> Vec<Patch> {
+    // Support nested recordings by restoring the outer one afterwards
+    let outer = RECORDED.with(|recorded| recorded.replace(Some(Vec::new())));
+    f();
+    let patches = RECORDED.with(|recorded| recorded.replace(None));
+    let patches = patches.unwrap_or_default();
+    if let Some(mut outer) = outer {
+        outer.extend(patches.iter().cloned());
+        RECORDED.with(|recorded| *recorded.borrow_mut() = Some(outer));
+    }
+    patches
+}
+
+/// Mounts a component into a detached element and records the DOM operations caused by
+/// subsequent changes of its props or messages sent to it.
+///
+/// # Example
+/// ```rust
+/// # use yew::prelude::*;
+/// use yew::virtual_dom::patch::{Patch, PatchTester};
+///
+/// # #[derive(Clone, PartialEq, Properties)]
+/// # struct Props { active: bool }
+/// # struct Badge;
+/// # impl Component for Badge {
+/// #     type Message = ();
+/// #     type Properties = Props;
+/// #     fn create(_ctx: &Context<Self>) -> Self { Badge }
+/// #     fn view(&self, ctx: &Context<Self>) -> Html {
+/// #         html! { <span class={classes!(ctx.props().active.then(|| "active"))}>{ "badge" }</span> }
+/// #     }
+/// # }
+/// # fn test() {
+/// let mut tester = PatchTester::<Badge>::mount(Props { active: false });
+/// let patches = tester.set_props(Props { active: true });
+/// assert_eq!(patches.len(), 1);
+/// assert!(!patches.iter().any(Patch::is_structural));
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct PatchTester<COMP: Component> {
+    scope: Scope<COMP>,
+    parent: Element,
+    node_ref: NodeRef,
+    mount_patches: Vec<Patch>,
+}
+
+impl<COMP: Component> PatchTester<COMP> {
+    /// Mounts the component with `props`
+    pub fn mount(props: COMP::Properties) -> Self {
+        let scope = Scope::new(None);
+        let parent = document().create_element("div").unwrap();
+        let node_ref = NodeRef::default();
+        let mount_patches = record_patches(|| {
+            scope.mount_in_place(
+                parent.clone(),
+                NodeRef::default(),
+                node_ref.clone(),
+                Rc::new(props),
+            )
+        });
+        Self {
+            scope,
+            parent,
+            node_ref,
+            mount_patches,
+        }
+    }
+
+    /// Returns the operations performed while mounting the component
+    pub fn mount_patches(&self) -> &[Patch] {
+        &self.mount_patches
+    }
+
+    /// Passes new props to the component and returns the operations performed to patch it
+    pub fn set_props(&mut self, props: COMP::Properties) -> Vec<Patch> {
+        record_patches(|| {
+            self.scope
+                .reuse(Rc::new(props), self.node_ref.clone(), NodeRef::default())
+        })
+    }
+
+    /// Sends a message to the component and returns the operations performed to patch it
+    pub fn send_message(&mut self, msg: impl Into<COMP::Message>) -> Vec<Patch> {
+        record_patches(|| self.scope.send_message(msg))
+    }
+
+    /// Returns the scope of the component, e.g. to emit its callbacks within
+    /// [`record_patches`]
+    pub fn scope(&self) -> &Scope<COMP> {
+        &self.scope
+    }
+
+    /// Returns the element the component is mounted in
+    pub fn parent(&self) -> &Element {
+        &self.parent
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate self as yew;
+
+    use super::*;
+    use crate::{html, Context, Html, Properties};
+
+    #[cfg(feature = "wasm_test")]
+    use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+
+    #[cfg(feature = "wasm_test")]
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    #[derive(Clone, PartialEq, Properties)]
+    struct ListProps {
+        items: Vec<u32>,
+        title: String,
+    }
+
+    struct List;
+
+    impl Component for List {
+        type Message = ();
+        type Properties = ListProps;
+
+        fn create(_ctx: &Context<Self>) -> Self {
+            List
+        }
+
+        fn view(&self, ctx: &Context<Self>) -> Html {
+            let props = ctx.props();
+            html! {
+                <ul title={props.title.clone()}>
+                    { for props.items.iter().map(|i| html! { <li key={*i}>{ i }</li> }) }
+                </ul>
+            }
+        }
+    }
+
+    fn props(items: &[u32], title: &str) -> ListProps {
+        ListProps {
+            items: items.to_vec(),
+            title: title.into(),
+        }
+    }
+
+    #[test]
+    fn records_patches_of_prop_changes() {
+        let mut tester = PatchTester::<List>::mount(props(&[1, 2], "a"));
+        assert_eq!(
+            tester.mount_patches(),
+            &[
+                Patch::CreateText { text: "".into() },
+                Patch::CreateElement { tag: "ul".into() },
+                // The placeholder is replaced by the root element
+                Patch::Remove,
+                Patch::SetAttribute {
+                    name: "title".into(),
+                    value: "a".into()
+                },
+                Patch::CreateElement { tag: "li".into() },
+                Patch::CreateText { text: "1".into() },
+                Patch::CreateElement { tag: "li".into() },
+                Patch::CreateText { text: "2".into() },
+            ][..]
+        );
+
+        assert_eq!(
+            tester.set_props(props(&[1, 2], "b")),
+            vec![Patch::SetAttribute {
+                name: "title".into(),
+                value: "b".into()
+            }]
+        );
+        assert_eq!(tester.set_props(props(&[1, 2], "b")), vec![]);
+
+        let patches = tester.set_props(props(&[2, 1], "b"));
+        assert!(!patches.is_empty());
+        assert!(patches.iter().all(|p| *p == Patch::Move));
+        assert_eq!(tester.parent().text_content().as_deref(), Some("21"));
+    }
+
+    #[test]
+    fn nested_recordings_are_merged() {
+        let (inner, outer) = {
+            let mut inner = Vec::new();
+            let outer = record_patches(|| {
+                record(|| Some(Patch::Move));
+                inner = record_patches(|| record(|| Some(Patch::Remove)));
+            });
+            (inner, outer)
+        };
+        assert_eq!(inner, vec![Patch::Remove]);
+        assert_eq!(outer, vec![Patch::Move, Patch::Remove]);
+        assert_eq!(record_patches(|| ()), vec![]);
+    }
+}
@@ -0,0 +1,53 @@
+//! Keeping the content of scroll containers in place when items are added above it.
+
+use std::cell::RefCell;
+use web_sys::Element;
+
+/// Position of an anchor, measured before its container was patched
+struct Captured {
+    container: Element,
+    anchor: Element,
+    top: f64,
+}
+
+thread_local! {
+    static CAPTURED: RefCell<Vec<Captured>> = Default::default();
+}
+
+/// Measures the position of `anchor` before `container` is patched
+pub(crate) fn capture(container: &Element, anchor: Element) {
+    if !container.contains(Some(&anchor)) {
+        return;
+    }
+    let top = anchor.get_bounding_client_rect().top();
+    CAPTURED.with(|captured| {
+        captured.borrow_mut().push(Captured {
+            container: container.clone(),
+            anchor,
+            top,
+        })
+    });
+}
+
+/// Scrolls the containers patched in the commit so their anchors are where they were before it.
+///
+/// Runs after the whole commit, as the items added around an anchor may be rendered by child
+/// components.
+pub(crate) fn end_commit() {
+    let captured = CAPTURED.with(|captured| std::mem::take(&mut *captured.borrow_mut()));
+    for Captured {
+        container,
+        anchor,
+        top,
+    } in captured
+    {
+        // The anchor was removed, so there is nothing to keep in place
+        if !container.contains(Some(&anchor)) {
+            continue;
+        }
+        let delta = anchor.get_bounding_client_rect().top() - top;
+        if delta != 0.0 {
+            container.set_scroll_top(container.scroll_top() + delta.round() as i32);
+        }
+    }
+}
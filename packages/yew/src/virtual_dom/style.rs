@@ -0,0 +1,286 @@
+//! Partial updates of the `style` attribute.
+//!
+//! When the `style` attribute of an element changes, only the declarations that changed are
+//! applied with `CSSStyleDeclaration.setProperty()` and `removeProperty()`, so the browser doesn't
+//! parse the whole attribute again. Styles with shorthand properties, whose declarations depend
+//! on their order, are set as a whole.
+
+use std::cell::Cell;
+use wasm_bindgen::{JsCast, JsValue};
+use web_sys::{CssStyleDeclaration, Element};
+
+/// Shorthand properties, which set the longhand properties declared before them
+const SHORTHANDS: &[&str] = &[
+    "all",
+    "animation",
+    "background",
+    "border",
+    "border-block",
+    "border-block-end",
+    "border-block-start",
+    "border-bottom",
+    "border-color",
+    "border-image",
+    "border-inline",
+    "border-inline-end",
+    "border-inline-start",
+    "border-left",
+    "border-radius",
+    "border-right",
+    "border-style",
+    "border-top",
+    "border-width",
+    "column-rule",
+    "columns",
+    "flex",
+    "flex-flow",
+    "font",
+    "font-synthesis",
+    "font-variant",
+    "gap",
+    "grid",
+    "grid-area",
+    "grid-column",
+    "grid-gap",
+    "grid-row",
+    "grid-template",
+    "inset",
+    "inset-block",
+    "inset-inline",
+    "list-style",
+    "margin",
+    "margin-block",
+    "margin-inline",
+    "mask",
+    "mask-border",
+    "offset",
+    "outline",
+    "overflow",
+    "overscroll-behavior",
+    "padding",
+    "padding-block",
+    "padding-inline",
+    "place-content",
+    "place-items",
+    "place-self",
+    "scroll-margin",
+    "scroll-margin-block",
+    "scroll-margin-inline",
+    "scroll-padding",
+    "scroll-padding-block",
+    "scroll-padding-inline",
+    "text-decoration",
+    "text-emphasis",
+    "transition",
+];
+
+/// A declaration of an inline style
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Declaration<'a> {
+    property: &'a str,
+    value: &'a str,
+    important: bool,
+}
+
+impl<'a> Declaration<'a> {
+    fn parse(text: &'a str) -> Option<Self> {
+        let colon = text.find(':')?;
+        let property = text[..colon].trim();
+        if property.is_empty() {
+            return None;
+        }
+        let value = text[colon + 1..].trim();
+        let (value, important) = match value.rfind('!') {
+            Some(bang) if value[bang + 1..].trim().eq_ignore_ascii_case("important") => {
+                (value[..bang].trim_end(), true)
+            }
+            _ => (value, false),
+        };
+        Some(Self {
+            property,
+            value,
+            important,
+        })
+    }
+
+    fn is_shorthand(&self) -> bool {
+        SHORTHANDS
+            .iter()
+            .any(|shorthand| shorthand.eq_ignore_ascii_case(self.property))
+    }
+
+    fn is_same_property(&self, other: &Self) -> bool {
+        // Only custom properties are case-sensitive
+        if self.property.starts_with("--") {
+            self.property == other.property
+        } else {
+            self.property.eq_ignore_ascii_case(other.property)
+        }
+    }
+}
+
+/// Splits `style` into its declarations. Returns `None` for styles which can't be split reliably,
+/// e.g. with comments, escapes or unbalanced quotes.
+fn parse(style: &str) -> Option<Vec<Declaration<'_>>> {
+    if style.contains("/*") || style.contains('\\') {
+        return None;
+    }
+
+    let mut texts = Vec::new();
+    let mut depth = 0_usize;
+    let mut quote = None;
+    let mut start = 0;
+    for (i, c) in style.char_indices() {
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some(_) => {}
+            None => match c {
+                '"' | '\'' => quote = Some(c),
+                '(' => depth += 1,
+                ')' => depth = depth.checked_sub(1)?,
+                ';' if depth == 0 => {
+                    texts.push(&style[start..i]);
+                    start = i + 1;
+                }
+                _ => {}
+            },
+        }
+    }
+    if quote.is_some() || depth != 0 {
+        return None;
+    }
+    texts.push(&style[start..]);
+
+    texts
+        .into_iter()
+        .map(str::trim)
+        .filter(|text| !text.is_empty())
+        .map(Declaration::parse)
+        .collect()
+}
+
+/// Returns whether the declarations can be applied one by one: there are no shorthands and every
+/// property is declared once
+fn is_independent(declarations: &[Declaration<'_>]) -> bool {
+    declarations.iter().enumerate().all(|(i, declaration)| {
+        !declaration.is_shorthand()
+            && !declarations[..i]
+                .iter()
+                .any(|other| other.is_same_property(declaration))
+    })
+}
+
+/// Applies the change of the `style` attribute of `el` from `old` to `new` declaration by
+/// declaration. Returns the number of declarations set or removed, or `None` without touching
+/// `el` if the attribute has to be set as a whole.
+pub(crate) fn apply_diff(el: &Element, old: &str, new: &str) -> Option<u64> {
+    let (old, new) = match (parse(old), parse(new)) {
+        (Some(old), Some(new)) if is_independent(&old) && is_independent(&new) => (old, new),
+        _ => return None,
+    };
+    let style = js_sys::Reflect::get(el, &JsValue::from_str("style"))
+        .ok()
+        .and_then(|style| style.dyn_into::<CssStyleDeclaration>().ok())?;
+    let changes = Cell::new(0);
+    let remove = |property: &str| {
+        changes.set(changes.get() + 1);
+        let _ = style.remove_property(property);
+    };
+
+    for declaration in &old {
+        if !new.iter().any(|other| other.is_same_property(declaration)) {
+            remove(declaration.property);
+        }
+    }
+    for declaration in &new {
+        match old.iter().find(|other| other.is_same_property(declaration)) {
+            Some(old) if old == declaration => continue,
+            // Invalid values are ignored by `setProperty()`, but unset the property when the
+            // attribute is set
+            Some(old) => remove(old.property),
+            None => {}
+        }
+        changes.set(changes.get() + 1);
+        let priority = if declaration.important {
+            "important"
+        } else {
+            ""
+        };
+        let _ = style.set_property_with_priority(declaration.property, declaration.value, priority);
+    }
+    Some(changes.get())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::document;
+
+    #[cfg(feature = "wasm_test")]
+    use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+
+    #[cfg(feature = "wasm_test")]
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    fn declaration(
+        property: &'static str,
+        value: &'static str,
+        important: bool,
+    ) -> Declaration<'static> {
+        Declaration {
+            property,
+            value,
+            important,
+        }
+    }
+
+    #[test]
+    fn parses_declarations() {
+        assert_eq!(
+            parse(r#" color: red ;; background-image: url("a;b.png"); width: 1px !important"#)
+                .unwrap(),
+            vec![
+                declaration("color", "red", false),
+                declaration("background-image", r#"url("a;b.png")"#, false),
+                declaration("width", "1px", true),
+            ]
+        );
+        assert_eq!(parse("").unwrap(), vec![]);
+        assert!(parse("color red").is_none());
+        assert!(parse("content: \"a").is_none());
+        assert!(parse("color: red /* note */").is_none());
+    }
+
+    #[test]
+    fn shorthands_and_duplicates_are_not_independent() {
+        assert!(is_independent(
+            &parse("color: red; --Color: 1; --color: 2").unwrap()
+        ));
+        assert!(!is_independent(
+            &parse("margin-top: 1px; margin: 0").unwrap()
+        ));
+        assert!(!is_independent(&parse("color: red; COLOR: blue").unwrap()));
+    }
+
+    #[test]
+    fn applies_changed_declarations() {
+        let el = document().create_element("div").unwrap();
+        el.set_attribute("style", "color: red; width: 1px; height: 2px")
+            .unwrap();
+        assert_eq!(
+            apply_diff(
+                &el,
+                "color: red; width: 1px; height: 2px",
+                "color: red; width: 3px; top: 0px",
+            ),
+            // `height` removed, `width` removed and set, `top` set
+            Some(4)
+        );
+        assert_eq!(
+            el.get_attribute("style").unwrap(),
+            "color: red; width: 3px; top: 0px;"
+        );
+
+        assert_eq!(apply_diff(&el, "color: red", "margin: 0"), None);
+    }
+}
@@ -1,23 +1,78 @@
 //! This module contains the implementation of a virtual component (`VComp`).
 
-use super::{Key, VDiff, VNode};
+use super::mutation::{self, Insertion, Mutation};
+use super::{insert_node, Key, VDiff, VNode};
+use crate::event_sink::{self, FrameworkEvent};
 use crate::html::{AnyScope, Component, NodeRef, Scope, Scoped};
-use std::any::TypeId;
+use std::any::{type_name, TypeId};
 use std::borrow::Borrow;
 use std::fmt;
 use std::ops::Deref;
 use std::rc::Rc;
-use web_sys::Element;
+use web_sys::{Document, Element, Node};
 
 /// A virtual component.
 pub struct VComp {
     type_id: TypeId,
+    /// Type name of the component, for its markers
+    name: &'static str,
     scope: Option<Box<dyn Scoped>>,
     props: Option<Box<dyn Mountable>>,
+    /// Set when mounted with the `component_markers` feature, unless the app has stable output
+    markers: Option<Markers>,
     pub(crate) node_ref: NodeRef,
     pub(crate) key: Option<Key>,
 }
 
+/// Comments delimiting the DOM nodes of a component, inserted with the `component_markers`
+/// feature, e.g. `<!--yew:app::Counter-->` and `<!--/yew:app::Counter-->`.
+///
+/// They let hydration, devtools and other tools map DOM nodes back to the components that
+/// rendered them. The root component of an app has no markers, as it fills the element the app
+/// is mounted in, and neither do the components of apps with
+/// [stable output](crate::AppConfig::stable_output).
+struct Markers {
+    start: Node,
+    end: Node,
+}
+
+impl Markers {
+    /// Inserts the markers of the component named `name` before `next_sibling`
+    fn insert(
+        name: &str,
+        document: &Document,
+        parent: &Element,
+        next_sibling: Option<&Node>,
+    ) -> Self {
+        let create = |text: String| -> Node {
+            let comment: Node = document.create_comment(&text).into();
+            mutation::record(Mutation::CreateMarker {
+                node: &comment,
+                text: &text,
+            });
+            comment
+        };
+        let markers = Self {
+            start: create(format!("yew:{}", name)),
+            end: create(format!("/yew:{}", name)),
+        };
+        insert_node(&markers.start, parent, next_sibling, Insertion::Marker);
+        insert_node(&markers.end, parent, next_sibling, Insertion::Marker);
+        markers
+    }
+
+    fn remove(&self, parent: &Element) {
+        for marker in &[&self.start, &self.end] {
+            mutation::record(Mutation::RemoveMarker { node: marker });
+            if parent.remove_child(marker).is_err() {
+                event_sink::report(FrameworkEvent::NodeNotFound {
+                    node: "component marker",
+                });
+            }
+        }
+    }
+}
+
 impl Clone for VComp {
     fn clone(&self) -> Self {
         if self.scope.is_some() {
@@ -26,8 +81,10 @@ impl Clone for VComp {
 
         Self {
             type_id: self.type_id,
+            name: self.name,
             scope: None,
             props: self.props.as_ref().map(|m| m.copy()),
+            markers: None,
             node_ref: self.node_ref.clone(),
             key: self.key.clone(),
         }
@@ -93,16 +150,107 @@ impl VComp {
     {
         VComp {
             type_id: TypeId::of::<COMP>(),
+            name: type_name::<COMP>(),
             node_ref,
             props: Some(Box::new(PropsWrapper::<COMP>::new(props))),
             scope: None,
+            markers: None,
             key,
         }
     }
 
+    /// Returns the first DOM node of the component, which is its start marker if it has markers
+    pub(crate) fn first_node(&self) -> Option<Node> {
+        match &self.markers {
+            Some(markers) => Some(markers.start.clone()),
+            None => self.node_ref.get(),
+        }
+    }
+
+    pub(crate) fn move_before(&self, parent: &Element, next_sibling: &Option<Node>) {
+        if let Some(markers) = &self.markers {
+            insert_node(
+                &markers.start,
+                parent,
+                next_sibling.as_ref(),
+                Insertion::Marker,
+            );
+        }
+        self.root_vnode()
+            .expect("VComp has no root vnode")
+            .move_before(parent, next_sibling);
+        if let Some(markers) = &self.markers {
+            insert_node(
+                &markers.end,
+                parent,
+                next_sibling.as_ref(),
+                Insertion::Marker,
+            );
+        }
+    }
+
+    /// Moves the component to `next_parent` before `next_sibling`, see [`VNode::shift`]
+    pub(crate) fn shift(&self, next_parent: &Element, next_sibling: NodeRef) {
+        let next_sibling = match &self.markers {
+            Some(markers) => {
+                insert_node(
+                    &markers.end,
+                    next_parent,
+                    next_sibling.get().as_ref(),
+                    Insertion::Marker,
+                );
+                insert_node(
+                    &markers.start,
+                    next_parent,
+                    Some(&markers.end),
+                    Insertion::Marker,
+                );
+                NodeRef::new(markers.end.clone())
+            }
+            None => next_sibling,
+        };
+        self.scope
+            .as_ref()
+            .expect("VComp is not mounted")
+            .shift_node(next_parent.clone(), next_sibling);
+    }
+
+    /// Appends the HTML markup of the component to `buf`, including its markers
+    pub(crate) fn write_html(&self, buf: &mut String) {
+        let marker = |buf: &mut String, node: &Node| {
+            buf.push_str("<!--");
+            buf.push_str(&node.text_content().unwrap_or_default());
+            buf.push_str("-->");
+        };
+        if let Some(markers) = &self.markers {
+            marker(buf, &markers.start);
+        }
+        if let Some(root) = self.root_vnode() {
+            root.write_html(buf);
+        }
+        if let Some(markers) = &self.markers {
+            marker(buf, &markers.end);
+        }
+    }
+
+    /// Returns the node the component is rendered before: its end marker if it has markers
+    fn inner_next_sibling(&self, next_sibling: NodeRef) -> NodeRef {
+        match &self.markers {
+            Some(markers) => NodeRef::new(markers.end.clone()),
+            None => next_sibling,
+        }
+    }
+
     pub(crate) fn root_vnode(&self) -> Option<impl Deref<Target = VNode> + '_> {
         self.scope.as_ref().and_then(|scope| scope.root_vnode())
     }
+
+    /// Destroys the component, see [`VNode::release`]
+    pub(crate) fn release(&mut self) {
+        if let Some(mut scope) = self.scope.take() {
+            scope.destroy();
+        }
+    }
 }
 
 trait Mountable {
@@ -155,8 +303,11 @@ impl<COMP: Component> Mountable for PropsWrapper<COMP> {
 }
 
 impl VDiff for VComp {
-    fn detach(&mut self, _parent: &Element) {
+    fn detach(&mut self, parent: &Element) {
         self.scope.take().expect("VComp is not mounted").destroy();
+        if let Some(markers) = self.markers.take() {
+            markers.remove(parent);
+        }
     }
 
     fn apply(
@@ -174,15 +325,31 @@ impl VDiff for VComp {
                 if self.type_id == vcomp.type_id && self.key == vcomp.key {
                     self.node_ref.reuse(vcomp.node_ref.clone());
                     let scope = vcomp.scope.take().expect("VComp is not mounted");
+                    self.markers = vcomp.markers.take();
+                    let next_sibling = self.inner_next_sibling(next_sibling);
                     mountable.reuse(self.node_ref.clone(), scope.borrow(), next_sibling);
                     self.scope = Some(scope);
-                    return vcomp.node_ref.clone();
+                    return match &self.markers {
+                        Some(markers) => NodeRef::new(markers.start.clone()),
+                        None => vcomp.node_ref.clone(),
+                    };
                 }
             }
 
             ancestor.detach(parent);
         }
 
+        if cfg!(feature = "component_markers") && !parent_scope.app_config().stable_output() {
+            let document = parent_scope.environment().document();
+            let next_sibling = next_sibling.get();
+            self.markers = Some(Markers::insert(
+                self.name,
+                &document,
+                parent,
+                next_sibling.as_ref(),
+            ));
+        }
+        let next_sibling = self.inner_next_sibling(next_sibling);
         self.scope = Some(mountable.mount(
             self.node_ref.clone(),
             parent_scope,
@@ -190,7 +357,10 @@ impl VDiff for VComp {
             next_sibling,
         ));
 
-        self.node_ref.clone()
+        match &self.markers {
+            Some(markers) => NodeRef::new(markers.start.clone()),
+            None => self.node_ref.clone(),
+        }
     }
 }
 
@@ -325,7 +495,7 @@ mod tests {
         let test_node: Node = document().create_text_node("test").into();
         let test_node_ref = NodeRef::new(test_node);
         let check_node_ref = |vnode: VNode| {
-            assert_eq!(vnode.first_node(), test_node_ref.get().unwrap());
+            assert_eq!(vnode.first_node(), test_node_ref.get());
         };
 
         let props = Props {
@@ -485,6 +655,22 @@ mod tests {
         elem.detach(&parent);
         assert!(node_ref.get().is_none());
     }
+
+    #[cfg(feature = "component_markers")]
+    #[test]
+    fn markers_delimit_components() {
+        let (scope, parent) = setup_parent();
+        let mut node = html! { <><Comp /><p /></> };
+        node.apply(&scope, &parent, NodeRef::default(), None);
+        assert_eq!(
+            parent.inner_html(),
+            "<!--yew:yew::virtual_dom::vcomp::tests::Comp--><div></div>\
+             <!--/yew:yew::virtual_dom::vcomp::tests::Comp--><p></p>"
+        );
+
+        node.detach(&parent);
+        assert_eq!(parent.inner_html(), "");
+    }
 }
 
 #[cfg(test)]
@@ -1,8 +1,10 @@
 //! This module contains fragments implementation.
-use super::{Key, VDiff, VNode, VText};
-use crate::html::{AnyScope, NodeRef};
+use super::{Key, VDiff, VNode};
+use crate::html::{AnyScope, NodeRef, StyleScope};
+use std::cell::RefCell;
 use std::collections::HashMap;
-use std::ops::{Deref, DerefMut};
+use std::iter::FromIterator;
+use std::ops::{AddAssign, Deref, DerefMut};
 use web_sys::Element;
 
 /// This struct represents a fragment of the Virtual DOM tree.
@@ -45,6 +47,78 @@ impl DerefMut for VList {
     }
 }
 
+impl<A: Into<VNode>> AddAssign<A> for VList {
+    /// Appends a child. Empty unkeyed lists, e.g. from [`VNode::when`], are skipped. Unlike
+    /// with `html!`, the position of the following children then depends on the condition, which
+    /// makes unkeyed diffs replace more nodes when it changes.
+    fn add_assign(&mut self, child: A) {
+        match child.into() {
+            VNode::VList(list) if list.is_empty() && list.key.is_none() => {}
+            child => self.add_child(child),
+        }
+    }
+}
+
+impl<A: Into<VNode>> FromIterator<A> for VList {
+    /// Collects the children in a single pass, reusing the buffer of a previously rendered list
+    /// when the iterator is big enough to benefit from it
+    fn from_iter<T: IntoIterator<Item = A>>(iter: T) -> Self {
+        let it = iter.into_iter();
+        let (lower, upper) = it.size_hint();
+        let mut children = spare_children(upper.unwrap_or(lower));
+        let mut fully_keyed = true;
+        children.extend(it.map(|ch| {
+            let ch = ch.into();
+            fully_keyed &= ch.has_key();
+            ch
+        }));
+        VList {
+            children,
+            fully_keyed,
+            key: None,
+        }
+    }
+}
+
+/// Child buffers smaller than this are not worth pooling
+const MIN_SPARE_CAPACITY: usize = 32;
+
+/// Maximum number of child buffers kept for reuse
+const MAX_SPARE_BUFFERS: usize = 8;
+
+thread_local! {
+    /// Emptied child buffers of previously rendered lists
+    static SPARE_CHILDREN: RefCell<Vec<Vec<VNode>>> = RefCell::new(Vec::new());
+}
+
+/// Returns an empty child buffer with at least `capacity`, reusing a spare one if possible
+fn spare_children(capacity: usize) -> Vec<VNode> {
+    if capacity < MIN_SPARE_CAPACITY {
+        return Vec::with_capacity(capacity);
+    }
+    SPARE_CHILDREN
+        .with(|spare| {
+            let mut spare = spare.borrow_mut();
+            let i = spare.iter().position(|buf| buf.capacity() >= capacity)?;
+            Some(spare.swap_remove(i))
+        })
+        .unwrap_or_else(|| Vec::with_capacity(capacity))
+}
+
+/// Keeps the allocation of an emptied child buffer for later reuse by [spare_children]
+fn recycle_children(mut buf: Vec<VNode>) {
+    if buf.capacity() < MIN_SPARE_CAPACITY {
+        return;
+    }
+    buf.clear();
+    SPARE_CHILDREN.with(|spare| {
+        let mut spare = spare.borrow_mut();
+        if spare.len() < MAX_SPARE_BUFFERS {
+            spare.push(buf);
+        }
+    });
+}
+
 /// Log an operation during tests for debugging purposes
 /// Set RUSTFLAGS="--cfg verbose_tests" environment variable to activate.
 macro_rules! test_log {
@@ -107,17 +181,31 @@ impl VList {
         self.fully_keyed = self.children.iter().all(|ch| ch.has_key());
     }
 
+    /// Releases the children, see [`VNode::release`]
+    pub(crate) fn release(&mut self) {
+        for child in &mut self.children {
+            child.release();
+        }
+    }
+
+    /// Prefixes the classes of the elements in the list with `scope`
+    pub(crate) fn apply_style_scope(&mut self, scope: &StyleScope) {
+        for child in self.children.iter_mut() {
+            child.apply_style_scope(scope);
+        }
+    }
+
     /// Diff and patch unkeyed child lists
     fn apply_unkeyed(
         parent_scope: &AnyScope,
         parent: &Element,
         mut next_sibling: NodeRef,
         lefts: &mut [VNode],
-        rights: Vec<VNode>,
+        mut rights: Vec<VNode>,
     ) -> NodeRef {
         let mut diff = lefts.len() as isize - rights.len() as isize;
         let mut lefts_it = lefts.iter_mut().rev();
-        let mut rights_it = rights.into_iter().rev();
+        let mut rights_it = rights.drain(..).rev();
 
         macro_rules! apply {
             ($l:expr, $r:expr) => {
@@ -151,6 +239,7 @@ impl VList {
             apply!(l, r.into());
         }
 
+        recycle_children(rights);
         next_sibling
     }
 
@@ -302,6 +391,9 @@ impl VList {
             apply!(l, take!(r));
         }
 
+        // All nodes have been taken out of the buffer, so only its allocation is left
+        unsafe { rights.set_len(0) };
+        recycle_children(unsafe { transmute(rights) });
         next_sibling
     }
 }
@@ -330,10 +422,13 @@ impl VDiff for VList {
         // i.e. the current DOM list element that we want to replace with self.
 
         if self.children.is_empty() {
-            // Without a placeholder the next element becomes first
-            // and corrupts the order of rendering
-            // We use empty text element to stake out a place
-            self.add_child(VText::new("").into());
+            // Empty lists render nothing. They are positioned by the nodes following them, so
+            // previous siblings insert their nodes before `next_sibling`, and components rendering
+            // an empty list link their node refs to it.
+            if let Some(mut ancestor) = ancestor {
+                ancestor.detach(parent);
+            }
+            return next_sibling;
         }
 
         let lefts = &mut self.children;
@@ -365,6 +460,127 @@ impl VDiff for VList {
     }
 }
 
+#[cfg(test)]
+mod tests {
+    extern crate self as yew;
+
+    use super::*;
+    use crate::utils::document;
+    use crate::{html, Component, Context, Html, Properties};
+
+    #[cfg(feature = "wasm_test")]
+    use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+
+    #[cfg(feature = "wasm_test")]
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    #[test]
+    fn add_assign_skips_empty_nodes() {
+        let mut list = VList::new();
+        list += html! { <p /> };
+        list += Html::when(false, || html! { <b /> });
+        list += Html::when(true, || html! { <i /> });
+        list += "text";
+        assert_eq!(list.len(), 3);
+
+        let joined = Html::join(vec!["a", "b", "c"], html! { <br /> });
+        match joined {
+            VNode::VList(list) => assert_eq!(list.len(), 5),
+            _ => panic!("expected a list"),
+        }
+        assert_eq!(Html::join(Vec::<Html>::new(), html! { <br /> }), html! {});
+    }
+
+    #[test]
+    fn whitespace_modes_apply_to_text_literals() {
+        let trimmed = html! {
+            <whitespace="trim">
+                "  Hello,\n   world  "{ " " }<b>" ! "</b>
+                "   "
+                <pre>" kept "</pre>
+            </>
+        };
+        let expected = html! {
+            <>{ "Hello, world" }{ " " }<b>{ "!" }</b><pre>{ " kept " }</pre></>
+        };
+        assert_eq!(trimmed, expected);
+
+        let collapsed = html! { <whitespace="collapse">"  a \n b "</> };
+        assert_eq!(collapsed, html! { <>{ " a b " }</> });
+    }
+
+    #[test]
+    fn collect_tracks_keys() {
+        let keyed: VList = (0..3).map(|i| html! { <p key={i} /> }).collect();
+        assert!(keyed.fully_keyed);
+        assert_eq!(keyed.len(), 3);
+
+        let mixed: VList = (0..3)
+            .map(|i| {
+                if i == 1 {
+                    html! { <p /> }
+                } else {
+                    html! { <p key={i} /> }
+                }
+            })
+            .collect();
+        assert!(!mixed.fully_keyed);
+    }
+
+    #[test]
+    fn collect_reuses_rendered_buffer() {
+        let scope = AnyScope::test();
+        let parent = document().create_element("div").unwrap();
+        let render = || (0..64).map(|i| html! { <p key={i} /> }).collect::<VList>();
+
+        let mut first = render();
+        first.apply(&scope, &parent, NodeRef::default(), None);
+        let mut second = render();
+        second.apply(&scope, &parent, NodeRef::default(), Some(first.into()));
+
+        // The buffer of `first` was emptied while patching and is handed out again
+        SPARE_CHILDREN.with(|spare| assert!(!spare.borrow().is_empty()));
+        let third = render();
+        assert!(third.capacity() >= 64);
+        assert_eq!(parent.child_element_count(), 64);
+    }
+
+    #[derive(Clone, PartialEq, Properties)]
+    struct MaybeProps {
+        show: bool,
+    }
+
+    struct Maybe;
+
+    impl Component for Maybe {
+        type Message = ();
+        type Properties = MaybeProps;
+
+        fn create(_ctx: &Context<Self>) -> Self {
+            Self
+        }
+
+        fn view(&self, ctx: &Context<Self>) -> Html {
+            Html::when(ctx.props().show, || html! { <i /> })
+        }
+    }
+
+    #[test]
+    fn empty_lists_render_no_nodes() {
+        let scope = AnyScope::test();
+        let parent = document().create_element("div").unwrap();
+
+        let mut node = html! { <><p />{ Html::default() }<Maybe show=false /><b /></> };
+        node.apply(&scope, &parent, NodeRef::default(), None);
+        assert_eq!(parent.inner_html(), "<p></p><b></b>");
+
+        // Both keep their position between the siblings
+        let mut next = html! { <><p />{ html! { <u /> } }<Maybe show=true /><b /></> };
+        next.apply(&scope, &parent, NodeRef::default(), Some(node));
+        assert_eq!(parent.inner_html(), "<p></p><u></u><i></i><b></b>");
+    }
+}
+
 #[cfg(test)]
 mod layout_tests {
     extern crate self as yew;
@@ -1,12 +1,14 @@
 //! This module contains the implementation of abstract virtual node.
 
-use super::{Key, VChild, VComp, VDiff, VList, VTag, VText};
-use crate::html::{AnyScope, Component, NodeRef};
-use log::warn;
+use super::mutation::{self, Insertion, Mutation};
+use super::{Key, VChild, VComp, VDiff, VList, VPortal, VTag, VText};
+use crate::event_sink::{self, FrameworkEvent};
+use crate::html::{AnyScope, Component, NodeRef, StyleScope};
+use crate::scheduler::{self, Runnable};
 use std::cmp::PartialEq;
 use std::fmt;
 use std::iter::FromIterator;
-
+use wasm_bindgen::JsCast;
 use web_sys::{Element, Node};
 
 /// Bind virtual element to a DOM reference.
@@ -22,14 +24,56 @@ pub enum VNode {
     VList(VList),
     /// A holder for any `Node` (necessary for replacing node).
     VRef(Node),
+    /// A portal rendering its content into another element.
+    VPortal(VPortal),
 }
 
 impl VNode {
+    /// Returns the node built by `f` if `cond` is true, or an empty node, which a parent [VList]
+    /// skips when it is appended with `+=`.
+    ///
+    /// ```rust
+    /// # use yew::prelude::*;
+    /// fn badge(unread: u32) -> Html {
+    ///     Html::when(unread > 0, || html! { <span class="badge">{ unread }</span> })
+    /// }
+    /// ```
+    pub fn when(cond: bool, f: impl FnOnce() -> VNode) -> VNode {
+        if cond {
+            f()
+        } else {
+            VNode::default()
+        }
+    }
+
+    /// Returns a list of the nodes of `iter` with a copy of `separator` between each of them.
+    ///
+    /// ```rust
+    /// # use yew::prelude::*;
+    /// fn tags(tags: &[&str]) -> Html {
+    ///     Html::join(tags.iter().map(|tag| html! { <a>{ tag }</a> }), html! { ", " })
+    /// }
+    /// ```
+    pub fn join<I>(iter: I, separator: VNode) -> VNode
+    where
+        I: IntoIterator,
+        I::Item: Into<VNode>,
+    {
+        let mut list = VList::new();
+        for (i, node) in iter.into_iter().enumerate() {
+            if i > 0 {
+                list += separator.clone();
+            }
+            list += node;
+        }
+        list.into()
+    }
+
     pub fn key(&self) -> Option<Key> {
         match self {
             VNode::VComp(vcomp) => vcomp.key.clone(),
             VNode::VList(vlist) => vlist.key.clone(),
-            VNode::VRef(_) => None,
+            VNode::VRef(_) | VNode::VPortal(_) => None,
             VNode::VTag(vtag) => vtag.key.clone(),
             VNode::VText(_) => None,
         }
@@ -40,26 +84,36 @@ impl VNode {
         match self {
             VNode::VComp(vcomp) => vcomp.key.is_some(),
             VNode::VList(vlist) => vlist.key.is_some(),
-            VNode::VRef(_) | VNode::VText(_) => false,
+            VNode::VRef(_) | VNode::VText(_) | VNode::VPortal(_) => false,
             VNode::VTag(vtag) => vtag.key.is_some(),
         }
     }
 
     /// Returns the first DOM node that is used to designate the position of the virtual DOM node.
-    pub(crate) fn first_node(&self) -> Node {
+    ///
+    /// Empty lists render no nodes, so this is `None` for them, and for components rendering
+    /// nothing it is the node following the component.
+    pub(crate) fn first_node(&self) -> Option<Node> {
         match self {
-            VNode::VTag(vtag) => vtag
-                .reference()
-                .expect("VTag is not mounted")
-                .clone()
-                .into(),
+            VNode::VTag(vtag) => Some(
+                vtag.reference()
+                    .expect("VTag is not mounted")
+                    .clone()
+                    .into(),
+            ),
             VNode::VText(vtext) => {
                 let text_node = vtext.reference.as_ref().expect("VText is not mounted");
-                text_node.clone().into()
+                Some(text_node.clone().into())
             }
-            VNode::VComp(vcomp) => vcomp.node_ref.get().expect("VComp is not mounted"),
-            VNode::VList(vlist) => vlist.get(0).expect("VList is not mounted").first_node(),
-            VNode::VRef(node) => node.clone(),
+            VNode::VComp(vcomp) => vcomp.first_node(),
+            VNode::VList(vlist) => vlist.iter().find_map(VNode::first_node),
+            VNode::VRef(node) => Some(node.clone()),
+            VNode::VPortal(vportal) => Some(
+                vportal
+                    .placeholder()
+                    .expect("VPortal is not mounted")
+                    .clone(),
+            ),
         }
     }
 
@@ -70,15 +124,139 @@ impl VNode {
                     node.move_before(parent, next_sibling);
                 }
             }
-            VNode::VComp(vcomp) => {
-                vcomp
-                    .root_vnode()
-                    .expect("VComp has no root vnode")
-                    .move_before(parent, next_sibling);
+            VNode::VComp(vcomp) => vcomp.move_before(parent, next_sibling),
+            _ => {
+                let node = self.first_node().expect("node is not mounted");
+                super::insert_node(&node, parent, next_sibling.as_ref(), Insertion::Moved)
+            }
+        };
+    }
+
+    /// Moves the DOM nodes to `next_parent` before `next_sibling`. Unlike
+    /// [`move_before`](Self::move_before), the components in the node render into the new parent
+    /// from then on.
+    pub(crate) fn shift(&self, next_parent: &Element, next_sibling: NodeRef) {
+        match self {
+            VNode::VList(vlist) => {
+                for node in vlist.iter() {
+                    node.shift(next_parent, next_sibling.clone());
+                }
+            }
+            VNode::VComp(vcomp) => vcomp.shift(next_parent, next_sibling),
+            _ => {
+                let node = self.first_node().expect("node is not mounted");
+                super::insert_node(
+                    &node,
+                    next_parent,
+                    next_sibling.get().as_ref(),
+                    Insertion::Moved,
+                )
             }
-            _ => super::insert_node(&self.first_node(), parent, next_sibling.as_ref()),
         };
     }
+
+    /// Releases what must not outlive the removal of the node from the DOM, before the node is
+    /// dropped with a [`Teardown`]: listeners and node refs are dropped, components destroyed and
+    /// the content of portals removed.
+    pub(crate) fn release(&mut self) {
+        match self {
+            VNode::VTag(vtag) => vtag.release(),
+            VNode::VComp(vcomp) => vcomp.release(),
+            VNode::VList(vlist) => vlist.release(),
+            VNode::VPortal(vportal) => vportal.release(),
+            VNode::VText(_) | VNode::VRef(_) => {}
+        }
+    }
+
+    /// Prefixes the classes of the elements in this [VNode] with `scope`. Does not descend into
+    /// components.
+    pub(crate) fn apply_style_scope(&mut self, scope: &StyleScope) {
+        match self {
+            VNode::VTag(vtag) => vtag.apply_style_scope(scope),
+            VNode::VList(vlist) => vlist.apply_style_scope(scope),
+            // The content of a portal is rendered by the same component
+            VNode::VPortal(vportal) => vportal.node.apply_style_scope(scope),
+            VNode::VText(_) | VNode::VComp(_) | VNode::VRef(_) => (),
+        }
+    }
+
+    /// Appends the HTML markup of the DOM nodes rendered for this [VNode] to `buf`.
+    ///
+    /// Nodes that are not mounted are skipped.
+    pub(crate) fn write_html(&self, buf: &mut String) {
+        match self {
+            VNode::VTag(vtag) => {
+                if let Some(el) = vtag.reference() {
+                    buf.push_str(&el.outer_html());
+                }
+            }
+            VNode::VText(vtext) => push_escaped(buf, &vtext.text),
+            VNode::VComp(vcomp) => vcomp.write_html(buf),
+            VNode::VList(vlist) => {
+                for child in vlist.iter() {
+                    child.write_html(buf);
+                }
+            }
+            VNode::VRef(node) => match node.node_type() {
+                Node::ELEMENT_NODE => buf.push_str(&node.unchecked_ref::<Element>().outer_html()),
+                Node::TEXT_NODE => push_escaped(buf, &node.node_value().unwrap_or_default()),
+                Node::COMMENT_NODE => {
+                    buf.push_str("<!--");
+                    buf.push_str(&node.node_value().unwrap_or_default());
+                    buf.push_str("-->");
+                }
+                // Other nodes have no markup of their own
+                _ => {}
+            },
+            // The content is not part of this subtree in the document
+            VNode::VPortal(_) => {}
+        }
+    }
+}
+
+/// Appends `text` to `buf`, escaping the characters that are significant in HTML text content
+fn push_escaped(buf: &mut String, text: &str) {
+    for c in text.chars() {
+        match c {
+            '&' => buf.push_str("&amp;"),
+            '<' => buf.push_str("&lt;"),
+            '>' => buf.push_str("&gt;"),
+            c => buf.push(c),
+        }
+    }
+}
+
+/// Number of nodes a [`Teardown`] drops before yielding to the browser
+const TEARDOWN_CHUNK: usize = 256;
+
+/// Drops released nodes in chunks when the browser is idle, so removing a large subtree doesn't
+/// block the page
+pub(crate) struct Teardown {
+    pub(crate) nodes: Vec<VNode>,
+}
+
+impl Runnable for Teardown {
+    fn run(mut self: Box<Self>) {
+        for _ in 0..TEARDOWN_CHUNK {
+            let mut node = match self.nodes.pop() {
+                Some(node) => node,
+                None => return,
+            };
+            // Children are dropped in the following iterations instead of recursively
+            match &mut node {
+                VNode::VTag(vtag) => {
+                    if let Some(children) = vtag.children_mut() {
+                        self.nodes.append(children);
+                    }
+                }
+                VNode::VList(vlist) => self.nodes.append(vlist),
+                _ => {}
+            }
+        }
+        if !self.nodes.is_empty() {
+            scheduler::push_idle(self);
+        }
+    }
 }
 
 impl VDiff for VNode {
@@ -89,9 +267,11 @@ impl VDiff for VNode {
             VNode::VText(ref mut vtext) => vtext.detach(parent),
             VNode::VComp(ref mut vcomp) => vcomp.detach(parent),
             VNode::VList(ref mut vlist) => vlist.detach(parent),
+            VNode::VPortal(ref mut vportal) => vportal.detach(parent),
             VNode::VRef(ref node) => {
+                mutation::record(Mutation::Remove { node });
                 if parent.remove_child(node).is_err() {
-                    warn!("Node not found to remove VRef");
+                    event_sink::report(FrameworkEvent::NodeNotFound { node: "VRef" });
                 }
             }
         }
@@ -115,6 +295,9 @@ impl VDiff for VNode {
             VNode::VList(ref mut vlist) => {
                 vlist.apply(parent_scope, parent, next_sibling, ancestor)
             }
+            VNode::VPortal(ref mut vportal) => {
+                vportal.apply(parent_scope, parent, next_sibling, ancestor)
+            }
             VNode::VRef(ref mut node) => {
                 if let Some(mut ancestor) = ancestor {
                     if let VNode::VRef(n) = &ancestor {
@@ -124,7 +307,12 @@ impl VDiff for VNode {
                     }
                     ancestor.detach(parent);
                 }
-                super::insert_node(node, parent, next_sibling.get().as_ref());
+                super::insert_node(
+                    node,
+                    parent,
+                    next_sibling.get().as_ref(),
+                    Insertion::Reference,
+                );
                 NodeRef::new(node.clone())
             }
         }
@@ -158,6 +346,13 @@ impl From<VTag> for VNode {
     }
 }
 
+impl From<VPortal> for VNode {
+    #[inline]
+    fn from(vportal: VPortal) -> Self {
+        VNode::VPortal(vportal)
+    }
+}
+
 impl From<VComp> for VNode {
     #[inline]
     fn from(vcomp: VComp) -> Self {
@@ -182,10 +377,7 @@ impl<T: ToString> From<T> for VNode {
 
 impl<A: Into<VNode>> FromIterator<A> for VNode {
     fn from_iter<T: IntoIterator<Item = A>>(iter: T) -> Self {
-        VNode::VList(VList::with_children(
-            iter.into_iter().map(|n| n.into()).collect(),
-            None,
-        ))
+        VNode::VList(iter.into_iter().collect())
     }
 }
 
@@ -197,6 +389,7 @@ impl fmt::Debug for VNode {
             VNode::VComp(ref vcomp) => vcomp.fmt(f),
             VNode::VList(ref vlist) => vlist.fmt(f),
             VNode::VRef(ref vref) => write!(f, "VRef ( \"{}\" )", crate::utils::print_node(vref)),
+            VNode::VPortal(ref vportal) => vportal.fmt(f),
         }
     }
 }
@@ -208,6 +401,7 @@ impl PartialEq for VNode {
             (VNode::VText(a), VNode::VText(b)) => a == b,
             (VNode::VList(a), VNode::VList(b)) => a == b,
             (VNode::VRef(a), VNode::VRef(b)) => a == b,
+            (VNode::VPortal(a), VNode::VPortal(b)) => a == b,
             // TODO: Need to improve PartialEq for VComp before enabling.
             (VNode::VComp(_), VNode::VComp(_)) => false,
             _ => false,
@@ -247,3 +441,31 @@ mod layout_tests {
         diff_layouts(vec![layout1, layout2]);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::document;
+
+    #[cfg(feature = "wasm_test")]
+    use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+
+    #[cfg(feature = "wasm_test")]
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    #[test]
+    fn write_html_of_references() {
+        let write = |node: Node| {
+            let mut buf = String::new();
+            VNode::VRef(node).write_html(&mut buf);
+            buf
+        };
+        let document = document();
+        assert_eq!(write(document.create_comment("note").into()), "<!--note-->");
+        assert_eq!(write(document.create_text_node("1 < 2").into()), "1 &lt; 2");
+        assert_eq!(
+            write(document.create_element("i").unwrap().into()),
+            "<i></i>"
+        );
+    }
+}
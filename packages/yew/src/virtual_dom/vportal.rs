@@ -0,0 +1,228 @@
+//! This module contains the implementation of a portal `VPortal`.
+
+use super::mutation::{self, Insertion, Mutation};
+use super::{VDiff, VNode};
+use crate::event_sink::{self, FrameworkEvent};
+use crate::html::{AnyScope, NodeRef};
+use std::fmt;
+use web_sys::{Element, Node};
+
+/// A virtual node whose content is rendered into another element of the document, e.g. the body
+/// for dialogs and notifications that must not be clipped by their ancestors.
+///
+/// The content is part of the tree of the component rendering the portal, so it receives its
+/// messages and contexts. At its position in the tree the portal renders an empty text node.
+#[derive(Clone)]
+pub struct VPortal {
+    /// The element the content is appended to
+    pub host: Element,
+    /// The content of the portal
+    pub node: Box<VNode>,
+    /// The empty text node marking the position of the portal
+    placeholder: Option<Node>,
+}
+
+impl VPortal {
+    /// Creates a portal rendering `node` into `host`
+    pub fn new(node: VNode, host: Element) -> Self {
+        Self {
+            host,
+            node: Box::new(node),
+            placeholder: None,
+        }
+    }
+
+    /// Returns the empty text node marking the position of the portal, if it is mounted
+    pub(crate) fn placeholder(&self) -> Option<&Node> {
+        self.placeholder.as_ref()
+    }
+
+    /// Removes the content from the host, which is not part of the released subtree, see
+    /// [`VNode::release`]
+    pub(crate) fn release(&mut self) {
+        self.node.detach(&self.host);
+        self.placeholder = None;
+    }
+}
+
+impl fmt::Debug for VPortal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("VPortal")
+            .field("host", &crate::utils::print_node(&self.host))
+            .field("node", &self.node)
+            .finish()
+    }
+}
+
+impl VDiff for VPortal {
+    fn detach(&mut self, parent: &Element) {
+        self.node.detach(&self.host);
+        let placeholder = self
+            .placeholder
+            .take()
+            .expect("tried to remove not rendered VPortal from DOM");
+        mutation::record(Mutation::Remove { node: &placeholder });
+        if parent.remove_child(&placeholder).is_err() {
+            event_sink::report(FrameworkEvent::NodeNotFound { node: "VPortal" });
+        }
+    }
+
+    fn apply(
+        &mut self,
+        parent_scope: &AnyScope,
+        parent: &Element,
+        next_sibling: NodeRef,
+        ancestor: Option<VNode>,
+    ) -> NodeRef {
+        let mut ancestor_node = None;
+        if let Some(ancestor) = ancestor {
+            match ancestor {
+                VNode::VPortal(mut portal) => {
+                    self.placeholder = portal.placeholder.take();
+                    // Content rendered into another host is moved, so its components keep their
+                    // state
+                    if portal.host != self.host {
+                        portal.node.shift(&self.host, NodeRef::default());
+                    }
+                    ancestor_node = Some(*portal.node);
+                }
+                mut ancestor => ancestor.detach(parent),
+            }
+        }
+
+        let placeholder = match &self.placeholder {
+            Some(placeholder) => placeholder.clone(),
+            None => {
+                let placeholder: Node = parent_scope
+                    .environment()
+                    .document()
+                    .create_text_node("")
+                    .into();
+                mutation::record(Mutation::CreateText {
+                    node: &placeholder,
+                    text: "",
+                });
+                super::insert_node(
+                    &placeholder,
+                    parent,
+                    next_sibling.get().as_ref(),
+                    Insertion::Created,
+                );
+                self.placeholder = Some(placeholder.clone());
+                placeholder
+            }
+        };
+
+        self.node
+            .apply(parent_scope, &self.host, NodeRef::default(), ancestor_node);
+        NodeRef::new(placeholder)
+    }
+}
+
+impl PartialEq for VPortal {
+    fn eq(&self, other: &Self) -> bool {
+        self.host == other.host && self.node == other.node
+    }
+}
+
+/// Renders `node` into `host` instead of the position it is rendered at, see [`VPortal`].
+///
+/// # Example
+/// ```rust
+/// # use yew::prelude::*;
+/// use yew::create_portal;
+///
+/// # fn view() -> Html {
+/// let body = yew::utils::document().body().unwrap();
+/// let tooltip = html! { <div class="tooltip">{ "Shown above everything" }</div> };
+/// html! {
+///     <div class="card">
+///         { create_portal(tooltip, body.into()) }
+///     </div>
+/// }
+/// # }
+/// ```
+pub fn create_portal(node: VNode, host: Element) -> VNode {
+    VNode::VPortal(VPortal::new(node, host))
+}
+
+#[cfg(test)]
+mod layout_tests {
+    extern crate self as yew;
+
+    use super::*;
+    use crate::html;
+    use crate::utils::document;
+    use crate::virtual_dom::layout_tests::{diff_layouts, TestLayout};
+
+    #[cfg(feature = "wasm_test")]
+    use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+
+    #[cfg(feature = "wasm_test")]
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    fn portal(host: &Element, content: VNode) -> VNode {
+        html! {
+            <>
+                { "a" }
+                { create_portal(content, host.clone()) }
+                { "b" }
+            </>
+        }
+    }
+
+    #[test]
+    fn diff() {
+        let host = document().create_element("div").unwrap();
+        let layout1 = TestLayout {
+            name: "1",
+            node: portal(&host, html! { <p>{ "in portal" }</p> }),
+            expected: "ab",
+        };
+        let layout2 = TestLayout {
+            name: "2",
+            node: portal(&host, html! { <i /> }),
+            expected: "ab",
+        };
+        diff_layouts(vec![layout1, layout2]);
+        assert_eq!(host.inner_html(), "");
+    }
+
+    #[test]
+    fn content_is_rendered_into_host() {
+        let host = document().create_element("div").unwrap();
+        let parent = document().create_element("div").unwrap();
+        let scope = AnyScope::test();
+
+        let mut node = portal(&host, html! { <i /> });
+        node.apply(&scope, &parent, NodeRef::default(), None);
+        assert_eq!(parent.inner_html(), "ab");
+        assert_eq!(host.inner_html(), "<i></i>");
+
+        let mut next = portal(&host, html! { <b /> });
+        next.apply(&scope, &parent, NodeRef::default(), Some(node));
+        assert_eq!(host.inner_html(), "<b></b>");
+
+        next.detach(&parent);
+        assert_eq!(parent.inner_html(), "");
+        assert_eq!(host.inner_html(), "");
+    }
+
+    #[test]
+    fn content_is_moved_to_another_host() {
+        let host = document().create_element("div").unwrap();
+        let other = document().create_element("div").unwrap();
+        let parent = document().create_element("div").unwrap();
+        let scope = AnyScope::test();
+
+        let mut node = portal(&host, html! { <i /> });
+        node.apply(&scope, &parent, NodeRef::default(), None);
+        let element = host.first_child().unwrap();
+
+        let mut next = portal(&other, html! { <i /> });
+        next.apply(&scope, &parent, NodeRef::default(), Some(node));
+        assert_eq!(host.inner_html(), "");
+        assert_eq!(other.inner_html(), "<i></i>");
+        assert_eq!(other.first_child(), Some(element));
+    }
+}
@@ -1,10 +1,16 @@
 //! This module contains the implementation of a virtual element node [VTag].
 
-use super::{Apply, AttrValue, Attributes, Key, Listener, VDiff, VList, VNode};
-use crate::html::{AnyScope, IntoPropValue, NodeRef};
-use crate::utils::document;
-use gloo::events::EventListener;
-use log::warn;
+use super::mutation::{self, Insertion, Mutation};
+use super::{
+    vnode::Teardown, Apply, AttrGroup, AttrValue, Attributes, ElementCommand, Key, Listener, VDiff,
+    VList, VNode,
+};
+use crate::callback::Callback;
+use crate::event_sink::{self, FrameworkEvent};
+use crate::html::{
+    AnyScope, IntoEventCallback, IntoPropValue, ListenerHandle, NodeRef, StyleScope,
+};
+use crate::scheduler;
 use std::borrow::Cow;
 use std::cmp::PartialEq;
 use std::hint::unreachable_unchecked;
@@ -37,7 +43,7 @@ impl<T: AccessValue> Apply for Value<T> {
 
     fn apply(&mut self, el: &Self::Element) {
         if let Some(v) = &self.0 {
-            el.set_value(v);
+            Self::set(el, v);
         }
     }
 
@@ -46,18 +52,28 @@ impl<T: AccessValue> Apply for Value<T> {
             (Some(new), Some(_)) => {
                 // Refresh value from the DOM. It might have changed.
                 if new != &el.value() {
-                    el.set_value(new);
+                    Self::set(el, new);
                 }
             }
-            (Some(new), None) => el.set_value(new),
-            (None, Some(_)) => el.set_value(""),
+            (Some(new), None) => Self::set(el, new),
+            (None, Some(_)) => Self::set(el, ""),
             (None, None) => (),
         }
     }
 }
 
+impl<T: AccessValue> Value<T> {
+    fn set(el: &T, value: &str) {
+        mutation::record(Mutation::SetValue {
+            node: el.as_ref(),
+            value,
+        });
+        el.set_value(value);
+    }
+}
+
 /// Able to have its value read or set
-trait AccessValue {
+trait AccessValue: AsRef<web_sys::Node> {
     fn value(&self) -> String;
     fn set_value(&self, v: &str);
 }
@@ -103,7 +119,7 @@ impl Apply for InputFields {
     fn apply(&mut self, el: &Self::Element) {
         // IMPORTANT! This parameter has to be set every time
         // to prevent strange behaviour in the browser when the DOM changes
-        el.set_checked(self.checked);
+        self.set_checked(el);
 
         self.value.apply(el);
     }
@@ -111,12 +127,22 @@ impl Apply for InputFields {
     fn apply_diff(&mut self, el: &Self::Element, ancestor: Self) {
         // IMPORTANT! This parameter has to be set every time
         // to prevent strange behaviour in the browser when the DOM changes
-        el.set_checked(self.checked);
+        self.set_checked(el);
 
         self.value.apply_diff(el, ancestor.value);
     }
 }
 
+impl InputFields {
+    fn set_checked(&self, el: &InputElement) {
+        mutation::record(Mutation::SetChecked {
+            node: el,
+            checked: self.checked,
+        });
+        el.set_checked(self.checked);
+    }
+}
+
 /// [VTag] fields that are specific to different [VTag] kinds.
 /// Decreases the memory footprint of [VTag] by avoiding impossible field and value combinations.
 #[derive(Debug, Clone)]
@@ -154,27 +180,43 @@ enum Listeners {
 
     /// Already registered listeners.
     /// Keeps handlers for attached listeners to have an opportunity to drop them later
-    Registered(Vec<EventListener>),
+    Registered(Vec<ListenerHandle>),
 }
 
-impl Apply for Listeners {
-    type Element = Element;
-
-    fn apply(&mut self, el: &Self::Element) {
+impl Listeners {
+    /// Attaches pending listeners to `el` as configured for the app of `parent_scope`
+    fn apply(&mut self, parent_scope: &AnyScope, el: &Element) {
         if let Self::Pending(v) = self {
+            let root = &parent_scope.root().listeners;
             *self = Self::Registered(
                 std::mem::take(v)
                     .into_iter()
-                    .map(|l| l.attach(el))
+                    .map(|l| root.attach(&*l, el))
                     .collect(),
             );
         }
     }
 
-    fn apply_diff(&mut self, el: &Self::Element, _ancestor: Self) {
-        // All we need to do with `_ancestor` is drop it
+    fn apply_diff(&mut self, parent_scope: &AnyScope, el: &Element, ancestor: Self) {
+        // The other listeners of `ancestor` are dropped and attached again
+        let mut fired: Vec<ListenerHandle> = match ancestor {
+            Self::Registered(handles) => handles
+                .into_iter()
+                .filter(|handle| handle.fired_once().is_some())
+                .collect(),
+            Self::Pending(_) => Vec::new(),
+        };
+        // `once` listeners which have fired are not armed again while they are rendered
+        if let Self::Pending(v) = self {
+            let once = |kind: &str| v.iter().any(|l| l.is_once() && l.kind() == kind);
+            fired.retain(|handle| handle.fired_once().map_or(false, once));
+            v.retain(|l| !l.is_once() || !fired.iter().any(|h| h.fired_once() == Some(l.kind())));
+        }
 
-        self.apply(el);
+        self.apply(parent_scope, el);
+        if let Self::Registered(handles) = self {
+            handles.append(&mut fired);
+        }
     }
 }
 
@@ -206,6 +248,22 @@ impl From<Vec<Rc<dyn Listener>>> for Listeners {
     }
 }
 
+/// Callbacks notified around the patching of an [Element]
+#[derive(Debug, Clone, Default)]
+struct PatchHooks {
+    /// Called with an existing element right before the differ mutates it
+    before: Option<Callback<Element>>,
+
+    /// Called with the element right after the differ has created or patched it
+    after: Option<Callback<Element>>,
+
+    /// Descendant kept in place when the children are patched
+    scroll_anchor: Option<NodeRef>,
+
+    /// Commands run after the commit when they change
+    commands: Vec<ElementCommand>,
+}
+
 /// A type for a virtual
 /// [Element](https://developer.mozilla.org/en-US/docs/Web/API/Element)
 /// representation.
@@ -220,6 +278,12 @@ pub struct VTag {
     /// A reference to the DOM [`Element`].
     reference: Option<Element>,
 
+    /// Callbacks notified around patching. Boxed, as they are rarely set.
+    patch_hooks: Option<Box<PatchHooks>>,
+
+    /// Whether the element and its descendants are inert
+    inert: bool,
+
     /// A node reference used for DOM access in Component lifecycle methods
     pub node_ref: NodeRef,
 
@@ -235,6 +299,8 @@ impl Clone for VTag {
             inner: self.inner.clone(),
             reference: None,
             listeners: self.listeners.clone(),
+            patch_hooks: self.patch_hooks.clone(),
+            inert: self.inert,
             attributes: self.attributes.clone(),
             node_ref: self.node_ref.clone(),
             key: self.key.clone(),
@@ -367,6 +433,8 @@ impl VTag {
             reference: None,
             attributes,
             listeners: listeners.into(),
+            patch_hooks: None,
+            inert: false,
             node_ref,
             key,
         }
@@ -421,6 +489,16 @@ impl VTag {
         }
     }
 
+    /// Drops the listeners and clears the node ref of the element and its children, see
+    /// [`VNode::release`]
+    pub(crate) fn release(&mut self) {
+        self.listeners = Listeners::Registered(Vec::new());
+        self.node_ref.set(None);
+        if let VTagInner::Other { children, .. } = &mut self.inner {
+            children.release();
+        }
+    }
+
     /// Returns the `value` of an
     /// [InputElement](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/input) or
     /// [TextArea](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/textarea)
@@ -490,6 +568,14 @@ impl VTag {
         self.attributes = attrs.into();
     }
 
+    /// Adds the attributes, classes and listeners of `group`. Attributes the tag already has are
+    /// kept.
+    ///
+    /// Used for `..group` in the `html!` macro.
+    pub fn spread(&mut self, group: &AttrGroup) {
+        group.spread(self);
+    }
+
     #[doc(hidden)]
     pub fn __macro_push_attr(&mut self, key: &'static str, value: impl IntoPropValue<AttrValue>) {
         self.attributes
@@ -515,21 +601,118 @@ impl VTag {
         }
     }
 
-    fn create_element(&self, parent: &Element) -> Element {
+    /// Sets a callback invoked with the underlying [Element] right before the differ patches its
+    /// attributes, listeners and children.
+    ///
+    /// It is only invoked when an element from a previous render is reused, which makes it the
+    /// place to measure the "first" position of a FLIP animation. Set with the `on_before_patch`
+    /// attribute in the `html!` macro.
+    pub fn set_before_patch(&mut self, callback: impl IntoEventCallback<Element>) {
+        self.patch_hooks.get_or_insert_with(Default::default).before =
+            callback.into_event_callback();
+    }
+
+    /// Sets a callback invoked with the underlying [Element] right after the differ has created
+    /// or patched it and its children.
+    ///
+    /// Set with the `on_after_patch` attribute in the `html!` macro.
+    pub fn set_after_patch(&mut self, callback: impl IntoEventCallback<Element>) {
+        self.patch_hooks.get_or_insert_with(Default::default).after =
+            callback.into_event_callback();
+    }
+
+    /// Sets the descendant that stays in place when this scroll container is patched.
+    ///
+    /// The position of the element referenced by `anchor` is measured before the children are
+    /// patched. After the commit, `scrollTop` is adjusted by how far the anchor moved, so items
+    /// prepended to a chat history or a list growing upwards don't push the visible content
+    /// down. Set with the `scroll_anchor` attribute in the `html!` macro.
+    pub fn set_scroll_anchor(&mut self, anchor: NodeRef) {
+        self.patch_hooks
+            .get_or_insert_with(Default::default)
+            .scroll_anchor = Some(anchor);
+    }
+
+    /// Sets commands run on the underlying [Element] after the commit, like focusing it.
+    ///
+    /// The commands run once after the element is created, and again whenever they differ from
+    /// the commands of the previous render. Set with the `commands` attribute in the `html!`
+    /// macro.
+    pub fn set_commands(&mut self, commands: impl IntoIterator<Item = ElementCommand>) {
+        self.patch_hooks
+            .get_or_insert_with(Default::default)
+            .commands = commands.into_iter().collect();
+    }
+
+    /// Makes the element and its descendants inert, like the background behind a modal dialog.
+    ///
+    /// Users can't focus or interact with an inert subtree, and Yew listeners ignore the events
+    /// targeting it, including events dispatched by scripts. Browsers without support for the
+    /// `inert` attribute get a polyfill stopping focus and interaction events. Set with the `inert`
+    /// attribute in the `html!` macro.
+    pub fn set_inert(&mut self, inert: bool) {
+        self.inert = inert;
+    }
+
+    /// Returns whether the element and its descendants are inert
+    pub fn is_inert(&self) -> bool {
+        self.inert
+    }
+
+    /// Prefixes the classes of this element and its children with `scope`
+    pub(crate) fn apply_style_scope(&mut self, scope: &StyleScope) {
+        self.attributes
+            .map_value("class", |classes| scope.prefix(classes).into());
+        if let VTagInner::Other { children, .. } = &mut self.inner {
+            children.apply_style_scope(scope);
+        }
+    }
+
+    fn create_element(&self, parent_scope: &AnyScope, parent: &Element) -> Element {
         let tag = self.tag();
-        if tag == "svg"
+        let namespace = if tag == "svg"
             || parent
                 .namespace_uri()
                 .map_or(false, |ns| ns == SVG_NAMESPACE)
         {
-            let namespace = Some(SVG_NAMESPACE);
-            document()
-                .create_element_ns(namespace, tag)
-                .expect("can't create namespaced element for vtag")
+            Some(SVG_NAMESPACE)
         } else {
-            document()
+            None
+        };
+        let document = parent_scope.environment().document();
+        let el = match namespace {
+            Some(namespace) => document
+                .create_element_ns(Some(namespace), tag)
+                .expect("can't create namespaced element for vtag"),
+            None => document
                 .create_element(tag)
-                .expect("can't create element for vtag")
+                .expect("can't create element for vtag"),
+        };
+        mutation::record(Mutation::CreateElement {
+            el: &el,
+            tag,
+            namespace,
+        });
+        el
+    }
+
+    /// Sets the attributes of a new element in the order of their names, for apps with
+    /// [stable output](crate::AppConfig::stable_output)
+    fn apply_sorted_attributes(&self, el: &Element) {
+        let mut attributes: Vec<_> = self
+            .attributes
+            .iter()
+            .map(|(key, value)| (key, Some(value)))
+            .collect();
+        if self.inert {
+            attributes.push(("inert", None));
+        }
+        attributes.sort_unstable_by_key(|(key, _)| *key);
+        for (key, value) in attributes {
+            match value {
+                Some(value) => Attributes::set_attribute(el, key, value),
+                None => super::inert::set(el, true),
+            }
         }
     }
 }
@@ -542,12 +725,20 @@ impl VDiff for VTag {
             .take()
             .expect("tried to remove not rendered VTag from DOM");
 
-        // recursively remove its children
+        // Listeners are dropped right away, so no events reach the removed subtree. The children
+        // stay in the removed element and are dropped when the browser is idle.
+        self.listeners = Listeners::Registered(Vec::new());
         if let VTagInner::Other { children, .. } = &mut self.inner {
-            children.detach(&node);
+            if !children.is_empty() {
+                children.release();
+                scheduler::push_idle(Box::new(Teardown {
+                    nodes: vec![std::mem::take(children).into()],
+                }));
+            }
         }
+        mutation::record(Mutation::Remove { node: &node });
         if parent.remove_child(&node).is_err() {
-            warn!("Node not found to remove VTag");
+            event_sink::report(FrameworkEvent::NodeNotFound { node: "VTag" });
         }
         self.node_ref.set(None);
     }
@@ -564,7 +755,7 @@ impl VDiff for VTag {
         // This kind of branching patching routine reduces branch predictor misses and the need to
         // unpack the enums (including `Option`s) all the time, resulting in a more streamlined
         // patching flow
-        let (ancestor_tag, el) = match ancestor {
+        let (mut ancestor_tag, el) = match ancestor {
             Some(mut ancestor) => {
                 // If the ancestor is a tag of the same type, don't recreate, keep the
                 // old tag and update its attributes and children.
@@ -592,23 +783,37 @@ impl VDiff for VTag {
                         _ => unsafe { unreachable_unchecked() },
                     }
                 } else {
-                    let el = self.create_element(parent);
-                    super::insert_node(&el, parent, Some(&ancestor.first_node()));
+                    let el = self.create_element(parent_scope, parent);
+                    let position = ancestor.first_node().or_else(|| next_sibling.get());
+                    super::insert_node(&el, parent, position.as_ref(), Insertion::Created);
                     ancestor.detach(parent);
                     (None, el)
                 }
             }
             None => (None, {
-                let el = self.create_element(parent);
-                super::insert_node(&el, parent, next_sibling.get().as_ref());
+                let el = self.create_element(parent_scope, parent);
+                super::insert_node(&el, parent, next_sibling.get().as_ref(), Insertion::Created);
                 el
             }),
         };
 
+        let ancestor_commands = ancestor_tag
+            .as_mut()
+            .and_then(|a| a.patch_hooks.as_mut())
+            .map(|hooks| std::mem::take(&mut hooks.commands))
+            .unwrap_or_default();
+
         match ancestor_tag {
             None => {
-                self.attributes.apply(&el);
-                self.listeners.apply(&el);
+                if parent_scope.app_config().stable_output() {
+                    self.apply_sorted_attributes(&el);
+                } else {
+                    self.attributes.apply(&el);
+                    if self.inert {
+                        super::inert::set(&el, true);
+                    }
+                }
+                self.listeners.apply(parent_scope, &el);
 
                 match &mut self.inner {
                     VTagInner::Input(f) => {
@@ -625,8 +830,25 @@ impl VDiff for VTag {
                 }
             }
             Some(ancestor) => {
+                if let Some(hooks) = &self.patch_hooks {
+                    if let Some(cb) = &hooks.before {
+                        cb.emit(el.clone());
+                    }
+                    if let Some(anchor) = hooks
+                        .scroll_anchor
+                        .as_ref()
+                        .and_then(NodeRef::cast::<Element>)
+                    {
+                        super::scroll_anchor::capture(&el, anchor);
+                    }
+                }
+
                 self.attributes.apply_diff(&el, ancestor.attributes);
-                self.listeners.apply_diff(&el, ancestor.listeners);
+                self.listeners
+                    .apply_diff(parent_scope, &el, ancestor.listeners);
+                if self.inert != ancestor.inert {
+                    super::inert::set(&el, self.inert);
+                }
 
                 match (&mut self.inner, ancestor.inner) {
                     (VTagInner::Input(new), VTagInner::Input(old)) => {
@@ -654,6 +876,14 @@ impl VDiff for VTag {
         };
 
         self.node_ref.set(Some(el.deref().clone()));
+        if let Some(hooks) = &self.patch_hooks {
+            if let Some(cb) = &hooks.after {
+                cb.emit(el.clone());
+            }
+            if !hooks.commands.is_empty() && hooks.commands != ancestor_commands {
+                super::command::queue(&el, hooks.commands.clone());
+            }
+        }
         self.reference = el.into();
         self.node_ref.clone()
     }
@@ -673,6 +903,7 @@ impl PartialEq for VTag {
             _ => false,
         }) && self.listeners.eq(&other.listeners)
             && self.attributes == other.attributes
+            && self.inert == other.inert
             // Diff children last, as recursion is the most expensive
             && match (&self.inner, &other.inner) {
                 (Other { children: ch_l, .. }, Other { children: ch_r, .. }) => ch_l == ch_r,
@@ -684,6 +915,7 @@ impl PartialEq for VTag {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::utils::document;
     use crate::{html, Html};
 
     #[cfg(feature = "wasm_test")]
@@ -978,6 +1210,34 @@ mod tests {
         }
     }
 
+    #[test]
+    fn it_applies_style_scope() {
+        let scope = StyleScope::new("scope");
+        let dynamic = String::from("dynamic");
+        let mut node = html! {
+            <div class="static">
+                <span class={dynamic}></span>
+                <p></p>
+            </div>
+        };
+        node.apply_style_scope(&scope);
+
+        let vtag = assert_vtag(&node);
+        assert_eq!(
+            vtag.attributes.iter().collect::<Vec<_>>(),
+            vec![("class", "scope-static")]
+        );
+        let children = vtag.children();
+        assert_eq!(
+            assert_vtag(&children[0])
+                .attributes
+                .iter()
+                .collect::<Vec<_>>(),
+            vec![("class", "scope-dynamic")]
+        );
+        assert_eq!(assert_vtag(&children[1]).attributes.iter().count(), 0);
+    }
+
     #[test]
     fn it_does_not_set_missing_class_name() {
         let scope = test_scope();
@@ -1172,6 +1432,198 @@ mod tests {
         elem.detach(&parent);
         assert!(node_ref.get().is_none());
     }
+
+    #[test]
+    fn detached_children_are_released_immediately() {
+        use std::cell::Cell;
+
+        let scope = test_scope();
+        let parent = document().create_element("div").unwrap();
+        document().body().unwrap().append_child(&parent).unwrap();
+
+        let clicks = Rc::new(Cell::new(0));
+        let onclick = {
+            let clicks = Rc::clone(&clicks);
+            move |_: web_sys::MouseEvent| clicks.set(clicks.get() + 1)
+        };
+        let button_ref = NodeRef::default();
+        let mut elem = html! {
+            <div>
+                <button ref={button_ref.clone()} {onclick}>{ "click" }</button>
+            </div>
+        };
+        elem.apply(&scope, &parent, NodeRef::default(), None);
+        let button = button_ref.get().unwrap();
+
+        elem.detach(&parent);
+        assert!(parent.first_child().is_none());
+        assert!(button_ref.get().is_none());
+        button
+            .dispatch_event(&web_sys::Event::new("click").unwrap())
+            .unwrap();
+        assert_eq!(clicks.get(), 0);
+
+        // The children are dropped when the browser is idle
+        crate::scheduler::flush_idle();
+        assert_eq!(Rc::strong_count(&clicks), 1);
+    }
+
+    #[test]
+    fn once_listener_fires_once() {
+        use std::cell::Cell;
+
+        let scope = test_scope();
+        let parent = document().create_element("div").unwrap();
+        document().body().unwrap().append_child(&parent).unwrap();
+
+        let clicks = Rc::new(Cell::new(0));
+        let onclick = {
+            let clicks = Rc::clone(&clicks);
+            move |_: web_sys::MouseEvent| clicks.set(clicks.get() + 1)
+        };
+        let mut elem = html! { <div onclick:once={onclick.clone()}></div> };
+        elem.apply(&scope, &parent, NodeRef::default(), None);
+
+        let el = assert_vtag(&elem).reference().unwrap().clone();
+        let click = || {
+            let event = web_sys::Event::new("click").unwrap();
+            el.dispatch_event(&event).unwrap();
+        };
+        click();
+        click();
+        assert_eq!(clicks.get(), 1);
+
+        // Re-rendering doesn't arm the listener again
+        let mut new_elem = html! { <div onclick:once={onclick.clone()}></div> };
+        new_elem.apply(&scope, &parent, NodeRef::default(), Some(elem));
+        click();
+        let mut elem = html! { <div onclick:once={onclick}></div> };
+        elem.apply(&scope, &parent, NodeRef::default(), Some(new_elem));
+        click();
+        assert_eq!(clicks.get(), 1);
+    }
+
+    #[test]
+    fn patch_hooks_see_element_before_and_after_patch() {
+        use std::cell::RefCell;
+
+        let scope = test_scope();
+        let parent = document().create_element("div").unwrap();
+        document().body().unwrap().append_child(&parent).unwrap();
+
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let gen_html = |class: &'static str| {
+            let before = {
+                let log = Rc::clone(&log);
+                move |el: Element| log.borrow_mut().push(format!("before {}", el.class_name()))
+            };
+            let after = {
+                let log = Rc::clone(&log);
+                move |el: Element| log.borrow_mut().push(format!("after {}", el.class_name()))
+            };
+            html! { <div {class} on_before_patch={before} on_after_patch={after}></div> }
+        };
+
+        let mut first = gen_html("a");
+        first.apply(&scope, &parent, NodeRef::default(), None);
+        assert_eq!(*log.borrow(), vec!["after a"]);
+
+        let mut second = gen_html("b");
+        second.apply(&scope, &parent, NodeRef::default(), Some(first));
+        assert_eq!(*log.borrow(), vec!["after a", "before a", "after b"]);
+    }
+
+    #[test]
+    fn scroll_anchor_stays_in_place_when_items_are_prepended() {
+        let scope = test_scope();
+        let parent = document().create_element("div").unwrap();
+        document().body().unwrap().append_child(&parent).unwrap();
+
+        let anchor = NodeRef::default();
+        let gen_html = |items: std::ops::Range<u32>| {
+            let item_ref = |i| match i {
+                10 => anchor.clone(),
+                _ => NodeRef::default(),
+            };
+            html! {
+                <div style="height: 100px; overflow: auto;" scroll_anchor={anchor.clone()}>
+                    { for items.map(|i| html! {
+                        <div key={i} style="height: 50px;" ref={item_ref(i)}>{ i }</div>
+                    }) }
+                </div>
+            }
+        };
+
+        let mut first = gen_html(10..20);
+        first.apply(&scope, &parent, NodeRef::default(), None);
+        let container = assert_vtag(&first).reference().unwrap().clone();
+        container.set_scroll_top(25);
+
+        let mut second = gen_html(6..20);
+        second.apply(&scope, &parent, NodeRef::default(), Some(first));
+        crate::virtual_dom::scroll_anchor::end_commit();
+        assert_eq!(container.scroll_top(), 25 + 4 * 50);
+    }
+
+    #[test]
+    fn commands_run_once_after_commit() {
+        let scope = test_scope();
+        let parent = document().create_element("div").unwrap();
+        document().body().unwrap().append_child(&parent).unwrap();
+
+        let gen_html = || html! { <input commands={vec![ElementCommand::Focus]} /> };
+
+        let mut first = gen_html();
+        first.apply(&scope, &parent, NodeRef::default(), None);
+        let input = assert_vtag(&first).reference().unwrap().clone();
+        assert_ne!(document().active_element(), Some(input.clone()));
+        crate::virtual_dom::command::end_commit();
+        assert_eq!(document().active_element(), Some(input.clone()));
+
+        input.unchecked_ref::<InputElement>().blur().unwrap();
+        let mut second = gen_html();
+        second.apply(&scope, &parent, NodeRef::default(), Some(first));
+        crate::virtual_dom::command::end_commit();
+        assert_ne!(document().active_element(), Some(input));
+    }
+
+    #[test]
+    fn listeners_ignore_events_in_inert_subtrees() {
+        use std::cell::Cell;
+
+        let scope = test_scope();
+        let parent = document().create_element("div").unwrap();
+        document().body().unwrap().append_child(&parent).unwrap();
+
+        let clicks = Rc::new(Cell::new(0));
+        let button_ref = NodeRef::default();
+        let gen_html = |inert: bool| {
+            let onclick = {
+                let clicks = Rc::clone(&clicks);
+                move |_: web_sys::MouseEvent| clicks.set(clicks.get() + 1)
+            };
+            html! { <div {inert}><button ref={button_ref.clone()} {onclick} /></div> }
+        };
+        let click = || {
+            let button = button_ref.get().unwrap();
+            button
+                .dispatch_event(&web_sys::Event::new("click").unwrap())
+                .unwrap();
+        };
+
+        let mut first = gen_html(true);
+        first.apply(&scope, &parent, NodeRef::default(), None);
+        let div = assert_vtag(&first).reference().unwrap().clone();
+        assert!(div.has_attribute("inert"));
+        click();
+        assert_eq!(clicks.get(), 0);
+
+        let mut second = gen_html(false);
+        second.apply(&scope, &parent, NodeRef::default(), Some(first));
+        assert!(!div.has_attribute("inert"));
+        click();
+        assert_eq!(clicks.get(), 1);
+    }
 }
 
 #[cfg(test)]
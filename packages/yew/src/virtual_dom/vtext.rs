@@ -1,9 +1,9 @@
 //! This module contains the implementation of a virtual text node `VText`.
 
+use super::mutation::{self, Insertion, Mutation};
 use super::{AttrValue, VDiff, VNode};
+use crate::event_sink::{self, FrameworkEvent};
 use crate::html::{AnyScope, NodeRef};
-use crate::utils::document;
-use log::warn;
 use std::cmp::PartialEq;
 use web_sys::{Element, Text as TextNode};
 
@@ -49,15 +49,16 @@ impl VDiff for VText {
             .reference
             .take()
             .expect("tried to remove not rendered VText from DOM");
+        mutation::record(Mutation::Remove { node: &node });
         if parent.remove_child(&node).is_err() {
-            warn!("Node not found to remove VText");
+            event_sink::report(FrameworkEvent::NodeNotFound { node: "VText" });
         }
     }
 
     /// Renders virtual node over existing `TextNode`, but only if value of text has changed.
     fn apply(
         &mut self,
-        _parent_scope: &AnyScope,
+        parent_scope: &AnyScope,
         parent: &Element,
         next_sibling: NodeRef,
         ancestor: Option<VNode>,
@@ -70,6 +71,10 @@ impl VDiff for VText {
                     .clone()
                     .expect("Rendered VText nodes should have a ref");
                 if self.text != vtext.text {
+                    mutation::record(Mutation::SetText {
+                        node: &text_node,
+                        text: &self.text,
+                    });
                     text_node.set_node_value(Some(&self.text));
                 }
 
@@ -79,8 +84,20 @@ impl VDiff for VText {
             ancestor.detach(parent);
         }
 
-        let text_node = document().create_text_node(&self.text);
-        super::insert_node(&text_node, parent, next_sibling.get().as_ref());
+        let text_node = parent_scope
+            .environment()
+            .document()
+            .create_text_node(&self.text);
+        mutation::record(Mutation::CreateText {
+            node: &text_node,
+            text: &self.text,
+        });
+        super::insert_node(
+            &text_node,
+            parent,
+            next_sibling.get().as_ref(),
+            Insertion::Created,
+        );
         self.reference = Some(text_node.clone());
         NodeRef::new(text_node.into())
     }
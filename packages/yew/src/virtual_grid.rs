@@ -0,0 +1,361 @@
+//! This module contains [`VirtualGrid`], which renders only the visible cells of large grids.
+
+use crate::html::{Classes, NodeRef};
+use crate::{html, Component, Context, Html, Properties};
+use std::ops::Range;
+use std::rc::Rc;
+use wasm_bindgen::closure::Closure;
+use wasm_bindgen::prelude::wasm_bindgen;
+use wasm_bindgen::JsCast;
+use web_sys::Element;
+
+#[wasm_bindgen]
+extern "C" {
+    // `ResizeObserver` is an unstable API in `web-sys`
+    type ResizeObserver;
+
+    #[wasm_bindgen(constructor)]
+    fn new(callback: &js_sys::Function) -> ResizeObserver;
+
+    #[wasm_bindgen(method)]
+    fn observe(this: &ResizeObserver, target: &Element);
+
+    #[wasm_bindgen(method)]
+    fn disconnect(this: &ResizeObserver);
+}
+
+/// Renders the cell at a row and column of a [`VirtualGrid`]
+pub struct RenderCell(Rc<dyn Fn(usize, usize) -> Html>);
+
+impl RenderCell {
+    /// Creates a new [`RenderCell`]
+    pub fn new(value: impl Fn(usize, usize) -> Html + 'static) -> Self {
+        Self(Rc::new(value))
+    }
+}
+
+/// Renders the header of a row or column of a [`VirtualGrid`]
+pub struct RenderHeader(Rc<dyn Fn(usize) -> Html>);
+
+impl RenderHeader {
+    /// Creates a new [`RenderHeader`]
+    pub fn new(value: impl Fn(usize) -> Html + 'static) -> Self {
+        Self(Rc::new(value))
+    }
+}
+
+macro_rules! impl_render_fn {
+    ($($ty:ident),*) => {$(
+        impl Clone for $ty {
+            fn clone(&self) -> Self {
+                Self(Rc::clone(&self.0))
+            }
+        }
+
+        impl PartialEq for $ty {
+            fn eq(&self, other: &Self) -> bool {
+                // https://github.com/rust-lang/rust-clippy/issues/6524
+                #[allow(clippy::vtable_address_comparisons)]
+                Rc::ptr_eq(&self.0, &other.0)
+            }
+        }
+
+        impl std::fmt::Debug for $ty {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.write_str(stringify!($ty))
+            }
+        }
+    )*};
+}
+
+impl_render_fn!(RenderCell, RenderHeader);
+
+/// Props for [`VirtualGrid`]
+#[derive(Debug, Clone, PartialEq, Properties)]
+pub struct VirtualGridProps {
+    /// Number of rows
+    pub rows: usize,
+    /// Number of columns
+    pub columns: usize,
+    /// Height of every row in pixels
+    pub row_height: f64,
+    /// Width of every column in pixels
+    pub column_width: f64,
+    /// Renders the cells
+    pub render_cell: RenderCell,
+
+    /// Renders the headers of the columns, which stick to the top of the grid
+    #[prop_or_default]
+    pub column_header: Option<RenderHeader>,
+    /// Height of the column headers in pixels
+    #[prop_or(0.)]
+    pub column_header_height: f64,
+    /// Renders the headers of the rows, which stick to the left of the grid
+    #[prop_or_default]
+    pub row_header: Option<RenderHeader>,
+    /// Width of the row headers in pixels
+    #[prop_or(0.)]
+    pub row_header_width: f64,
+
+    /// Number of rows and columns rendered beyond each edge of the viewport, so fast scrolling
+    /// doesn't show blank cells
+    #[prop_or(2)]
+    pub overscan: usize,
+    /// Classes of the scroll container, which has to be given a size, e.g. with `height: 100%`
+    #[prop_or_default]
+    pub class: Classes,
+}
+
+/// Messages of [`VirtualGrid`]
+#[doc(hidden)]
+pub enum VirtualGridMsg {
+    Scroll,
+    Resize,
+}
+
+/// A grid rendering only the cells in and around its viewport.
+///
+/// Rows and columns have fixed sizes, so the visible cells are computed from the scroll offsets
+/// and the size of the scroll container, which is measured whenever it is resized. Cells are
+/// keyed by their position, so cells that stay visible while scrolling are moved instead of
+/// re-created.
+///
+/// # Example
+/// ```rust
+/// # use yew::prelude::*;
+/// use yew::virtual_grid::{RenderCell, RenderHeader, VirtualGrid};
+///
+/// # #[function_component(Sheet)]
+/// # fn sheet() -> Html {
+/// html! {
+///     <VirtualGrid
+///         class="sheet"
+///         rows={100_000}
+///         columns={500}
+///         row_height={24.}
+///         column_width={96.}
+///         render_cell={RenderCell::new(|row, column| html! { { format!("{}:{}", row, column) } })}
+///         column_header={RenderHeader::new(|column| html! { { column } })}
+///         column_header_height={24.}
+///     />
+/// }
+/// # }
+/// ```
+pub struct VirtualGrid {
+    node_ref: NodeRef,
+    scroll_top: f64,
+    scroll_left: f64,
+    width: f64,
+    height: f64,
+    resize_observer: Option<(ResizeObserver, Closure<dyn FnMut()>)>,
+}
+
+impl VirtualGrid {
+    fn measure(&mut self) {
+        if let Some(el) = self.node_ref.cast::<Element>() {
+            self.scroll_top = el.scroll_top() as f64;
+            self.scroll_left = el.scroll_left() as f64;
+            self.width = el.client_width() as f64;
+            self.height = el.client_height() as f64;
+        }
+    }
+
+    fn rows(&self, props: &VirtualGridProps) -> Range<usize> {
+        visible_range(
+            self.scroll_top,
+            self.height - props.column_header_height,
+            props.row_height,
+            props.rows,
+            props.overscan,
+        )
+    }
+
+    fn columns(&self, props: &VirtualGridProps) -> Range<usize> {
+        visible_range(
+            self.scroll_left,
+            self.width - props.row_header_width,
+            props.column_width,
+            props.columns,
+            props.overscan,
+        )
+    }
+}
+
+/// Returns the range of the items of `size` pixels, out of `count`, visible in a viewport of
+/// `viewport` pixels scrolled by `offset` pixels, extended by `overscan` items at both ends
+fn visible_range(
+    offset: f64,
+    viewport: f64,
+    size: f64,
+    count: usize,
+    overscan: usize,
+) -> Range<usize> {
+    if size <= 0. || count == 0 {
+        return 0..0;
+    }
+    let first = (offset.max(0.) / size).floor() as usize;
+    let last = ((offset.max(0.) + viewport.max(0.)) / size).ceil() as usize;
+    first.saturating_sub(overscan).min(count)..last.saturating_add(overscan).min(count)
+}
+
+impl Component for VirtualGrid {
+    type Message = VirtualGridMsg;
+    type Properties = VirtualGridProps;
+
+    fn create(_ctx: &Context<Self>) -> Self {
+        Self {
+            node_ref: NodeRef::default(),
+            scroll_top: 0.,
+            scroll_left: 0.,
+            width: 0.,
+            height: 0.,
+            resize_observer: None,
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, _msg: Self::Message) -> bool {
+        let props = ctx.props();
+        let before = (self.rows(props), self.columns(props));
+        self.measure();
+        before != (self.rows(props), self.columns(props))
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let props = ctx.props();
+        let total_width = props.row_header_width + props.columns as f64 * props.column_width;
+        let body_height = props.rows as f64 * props.row_height;
+        let rows = self.rows(props);
+        let columns = self.columns(props);
+
+        let cell_style = |top: f64, left: f64, width: f64, height: f64| {
+            format!(
+                "position: absolute; top: {}px; left: {}px; width: {}px; height: {}px;",
+                top, left, width, height
+            )
+        };
+
+        let column_headers = props.column_header.as_ref().map(|render| {
+            html! {
+                <div
+                    class="yew-grid-column-headers"
+                    style={format!(
+                        "position: sticky; top: 0; z-index: 2; width: {}px; height: {}px;",
+                        total_width, props.column_header_height
+                    )}
+                >
+                    <div style={format!(
+                        "position: sticky; left: 0; z-index: 1; width: {}px; height: 100%;",
+                        props.row_header_width
+                    )} />
+                    { for columns.clone().map(|column| html! {
+                        <div key={column} role="columnheader" style={cell_style(
+                            0.,
+                            props.row_header_width + column as f64 * props.column_width,
+                            props.column_width,
+                            props.column_header_height,
+                        )}>
+                            { (render.0)(column) }
+                        </div>
+                    }) }
+                </div>
+            }
+        });
+
+        let row_headers = props.row_header.as_ref().map(|render| {
+            html! {
+                <div
+                    class="yew-grid-row-headers"
+                    style={format!(
+                        "position: sticky; left: 0; z-index: 1; width: {}px; height: {}px;",
+                        props.row_header_width, body_height
+                    )}
+                >
+                    { for rows.clone().map(|row| html! {
+                        <div key={row} role="rowheader" style={cell_style(
+                            row as f64 * props.row_height,
+                            0.,
+                            props.row_header_width,
+                            props.row_height,
+                        )}>
+                            { (render.0)(row) }
+                        </div>
+                    }) }
+                </div>
+            }
+        });
+
+        let cells = rows.flat_map(|row| columns.clone().map(move |column| (row, column)));
+
+        html! {
+            <div
+                ref={self.node_ref.clone()}
+                class={props.class.clone()}
+                role="grid"
+                aria-rowcount={props.rows.to_string()}
+                aria-colcount={props.columns.to_string()}
+                style="overflow: auto;"
+                onscroll={ctx.link().callback(|_| VirtualGridMsg::Scroll)}
+            >
+                { for column_headers }
+                <div style={format!(
+                    "position: relative; width: {}px; height: {}px;",
+                    total_width, body_height
+                )}>
+                    { for row_headers }
+                    { for cells.map(|(row, column)| html! {
+                        <div key={format!("{}:{}", row, column)} role="gridcell" style={cell_style(
+                            row as f64 * props.row_height,
+                            props.row_header_width + column as f64 * props.column_width,
+                            props.column_width,
+                            props.row_height,
+                        )}>
+                            { (props.render_cell.0)(row, column) }
+                        </div>
+                    }) }
+                </div>
+            </div>
+        }
+    }
+
+    fn rendered(&mut self, ctx: &Context<Self>, first_render: bool) {
+        if !first_render {
+            return;
+        }
+        if let Some(el) = self.node_ref.cast::<Element>() {
+            let link = ctx.link().clone();
+            let closure = Closure::wrap(
+                Box::new(move || link.send_message(VirtualGridMsg::Resize)) as Box<dyn FnMut()>,
+            );
+            let observer = ResizeObserver::new(closure.as_ref().unchecked_ref());
+            // Reports the initial size, which renders the first visible cells
+            observer.observe(&el);
+            self.resize_observer = Some((observer, closure));
+        }
+    }
+
+    fn destroy(&mut self, _ctx: &Context<Self>) {
+        if let Some((observer, _)) = self.resize_observer.take() {
+            observer.disconnect();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "wasm_test")]
+    use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+
+    #[cfg(feature = "wasm_test")]
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    #[test]
+    fn visible_range_covers_viewport_and_overscan() {
+        assert_eq!(visible_range(0., 100., 20., 1000, 0), 0..5);
+        assert_eq!(visible_range(30., 100., 20., 1000, 0), 1..7);
+        assert_eq!(visible_range(30., 100., 20., 1000, 2), 0..9);
+        assert_eq!(visible_range(19_950., 100., 20., 1000, 2), 995..1000);
+        assert_eq!(visible_range(0., 100., 20., 0, 2), 0..0);
+    }
+}
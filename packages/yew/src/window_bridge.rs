@@ -0,0 +1,287 @@
+//! Typed messages between windows of different origins with `postMessage`.
+//!
+//! A [`WindowBridge`] connects the current window to another one, e.g. the page embedding the app
+//! in an iframe, an iframe of the app or a popup. Messages are converted with `serde` and are
+//! only accepted from the other window while it shows a page of the expected origin, so
+//! subscribers don't have to validate them. Components subscribe with [`Scope::subscribe_bridge`].
+
+use crate::html::{Component, Scope};
+use crate::utils::window;
+use crate::Callback;
+use gloo::events::EventListener;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use slab::Slab;
+use std::cell::RefCell;
+use std::fmt;
+use std::marker::PhantomData;
+use std::rc::{Rc, Weak};
+use wasm_bindgen::{JsCast, JsValue};
+use web_sys::{HtmlIFrameElement, MessageEvent, Window};
+
+/// Name of the bridges created with [`WindowBridge::new`]
+const DEFAULT_NAME: &str = "yew";
+
+/// Error of a [`WindowBridge`]
+#[derive(Debug, thiserror::Error)]
+pub enum BridgeError {
+    /// The browser failed to post the message, e.g. because it can't be cloned
+    #[error("postMessage error: {0:?}")]
+    Js(JsValue),
+    /// A message could not be converted
+    #[error("failed to convert message: {0}")]
+    Serde(#[from] serde_json::Error),
+}
+
+impl From<JsValue> for BridgeError {
+    fn from(err: JsValue) -> Self {
+        Self::Js(err)
+    }
+}
+
+/// Message as posted, which tells apart the messages of bridges from other uses of `postMessage`
+/// on the page
+#[derive(Serialize, Deserialize)]
+struct Envelope<N, T> {
+    bridge: N,
+    data: T,
+}
+
+type Subscribers<T> = Rc<RefCell<Slab<Callback<T>>>>;
+
+fn is_same(a: &impl AsRef<JsValue>, b: &impl AsRef<JsValue>) -> bool {
+    a.as_ref() == b.as_ref()
+}
+
+/// Typed `postMessage` communication with another window, see the [module docs](self).
+///
+/// # Example
+/// ```rust,no_run
+/// use serde::{Deserialize, Serialize};
+/// use yew::window_bridge::WindowBridge;
+/// # use yew::Callback;
+///
+/// #[derive(Clone, Serialize, Deserialize)]
+/// enum HostMessage {
+///     Resize { height: u32 },
+///     Theme(String),
+/// }
+///
+/// // In a widget embedded by https://host.example
+/// let bridge = WindowBridge::<HostMessage>::parent("https://host.example").unwrap();
+/// bridge.send(&HostMessage::Resize { height: 480 }).unwrap();
+/// let _subscription = bridge.subscribe(Callback::from(|message: HostMessage| match message {
+///     HostMessage::Theme(_theme) => { /* apply the theme of the host */ }
+///     HostMessage::Resize { .. } => {}
+/// }));
+/// ```
+pub struct WindowBridge<T> {
+    target: Window,
+    /// Origin of the target, or `*`
+    target_origin: String,
+    name: String,
+    subscribers: Subscribers<T>,
+    _listener: EventListener,
+    _message: PhantomData<fn(T)>,
+}
+
+impl<T: Serialize + DeserializeOwned + Clone + 'static> WindowBridge<T> {
+    /// Creates a bridge to `target` at `origin`, e.g. `"https://host.example"`. Messages are only
+    /// sent to `target` while it shows a page of `origin`, and only accepted from it.
+    ///
+    /// With the origin `"*"`, messages are sent to and accepted from any page shown in `target`,
+    /// which is only safe for messages without secrets that can't be used to attack the app.
+    pub fn new(target: Window, origin: impl Into<String>) -> Self {
+        Self::with_name(target, origin, DEFAULT_NAME)
+    }
+
+    /// Creates a bridge like [`new`](Self::new), which only exchanges messages with bridges of
+    /// the same `name` in the other window, for apps with several independent bridges
+    pub fn with_name(target: Window, origin: impl Into<String>, name: impl Into<String>) -> Self {
+        let target_origin = origin.into();
+        let name = name.into();
+        let subscribers: Subscribers<T> = Default::default();
+
+        let listener = {
+            let source = target.clone();
+            let origin = target_origin.clone();
+            let name = name.clone();
+            let subscribers = Rc::clone(&subscribers);
+            EventListener::new(&window(), "message", move |event| {
+                let event = match event.dyn_ref::<MessageEvent>() {
+                    Some(event) => event,
+                    None => return,
+                };
+                let from_target = event.source().map_or(false, |s| is_same(&s, &source));
+                if !from_target || (origin != "*" && event.origin() != origin) {
+                    return;
+                }
+                let envelope = event
+                    .data()
+                    .as_string()
+                    .and_then(|data| serde_json::from_str::<Envelope<String, T>>(&data).ok())
+                    .filter(|envelope| envelope.bridge == name);
+                if let Some(envelope) = envelope {
+                    // Subscribers may subscribe or unsubscribe when they are notified
+                    let subscribers: Vec<_> = subscribers
+                        .borrow()
+                        .iter()
+                        .map(|(_, s)| s.clone())
+                        .collect();
+                    for subscriber in subscribers {
+                        subscriber.emit(envelope.data.clone());
+                    }
+                }
+            })
+        };
+
+        Self {
+            target,
+            target_origin,
+            name,
+            subscribers,
+            _listener: listener,
+            _message: PhantomData,
+        }
+    }
+
+    /// Creates a bridge to the page embedding the app in an iframe at `origin`. Returns `None`
+    /// if the app is not embedded.
+    pub fn parent(origin: impl Into<String>) -> Option<Self> {
+        let current = window();
+        let parent = current.parent().ok().flatten()?;
+        if is_same(&parent, &current) {
+            return None;
+        }
+        Some(Self::new(parent, origin))
+    }
+
+    /// Creates a bridge to the window which opened the current one at `origin`. Returns `None`
+    /// if there is none.
+    pub fn opener(origin: impl Into<String>) -> Option<Self> {
+        let opener = window().opener().ok()?;
+        let opener = opener.dyn_into::<Window>().ok()?;
+        Some(Self::new(opener, origin))
+    }
+
+    /// Creates a bridge to the page shown in `iframe` at `origin`. Returns `None` if the iframe is
+    /// not part of a document.
+    pub fn iframe(iframe: &HtmlIFrameElement, origin: impl Into<String>) -> Option<Self> {
+        Some(Self::new(iframe.content_window()?, origin))
+    }
+
+    /// Sends `message` to the other window
+    pub fn send(&self, message: &T) -> Result<(), BridgeError> {
+        let envelope = Envelope {
+            bridge: &self.name,
+            data: message,
+        };
+        let data = serde_json::to_string(&envelope)?;
+        self.target
+            .post_message(&JsValue::from_str(&data), &self.target_origin)?;
+        Ok(())
+    }
+
+    /// Calls `callback` with the messages received from the other window, while the returned
+    /// subscription is kept
+    pub fn subscribe(&self, callback: Callback<T>) -> BridgeSubscription<T> {
+        let key = self.subscribers.borrow_mut().insert(callback);
+        BridgeSubscription {
+            subscribers: Rc::downgrade(&self.subscribers),
+            key,
+        }
+    }
+
+    /// Returns the origin messages are sent to and accepted from
+    pub fn origin(&self) -> &str {
+        &self.target_origin
+    }
+}
+
+impl<T> fmt::Debug for WindowBridge<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WindowBridge")
+            .field("target_origin", &self.target_origin)
+            .field("name", &self.name)
+            .finish()
+    }
+}
+
+/// Keeps a subscription to the messages of a [`WindowBridge`] alive. Dropping it unsubscribes.
+pub struct BridgeSubscription<T> {
+    subscribers: Weak<RefCell<Slab<Callback<T>>>>,
+    key: usize,
+}
+
+impl<T> Drop for BridgeSubscription<T> {
+    fn drop(&mut self) {
+        if let Some(subscribers) = self.subscribers.upgrade() {
+            subscribers.borrow_mut().remove(self.key);
+        }
+    }
+}
+
+impl<T> fmt::Debug for BridgeSubscription<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BridgeSubscription")
+            .field("key", &self.key)
+            .finish()
+    }
+}
+
+impl<COMP: Component> Scope<COMP> {
+    /// Sends the messages `bridge` receives from the other window to the component, mapped with
+    /// `f`, while the returned subscription is kept.
+    pub fn subscribe_bridge<T, F, M>(&self, bridge: &WindowBridge<T>, f: F) -> BridgeSubscription<T>
+    where
+        T: Serialize + DeserializeOwned + Clone + 'static,
+        M: Into<COMP::Message>,
+        F: Fn(T) -> M + 'static,
+    {
+        bridge.subscribe(self.callback(f))
+    }
+}
+
+#[cfg(all(test, feature = "wasm_test"))]
+mod tests {
+    use super::*;
+    use wasm_bindgen_futures::JsFuture;
+    use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    async fn sleep(ms: i32) {
+        let promise = js_sys::Promise::new(&mut |resolve, _| {
+            window()
+                .set_timeout_with_callback_and_timeout_and_arguments_0(&resolve, ms)
+                .unwrap();
+        });
+        JsFuture::from(promise).await.unwrap();
+    }
+
+    fn received<T: 'static>(log: &Rc<RefCell<Vec<T>>>) -> Callback<T> {
+        let log = Rc::clone(log);
+        Callback::from(move |message| log.borrow_mut().push(message))
+    }
+
+    #[test]
+    async fn messages_are_filtered_by_origin_and_name() {
+        let origin = window().location().origin().unwrap();
+        let bridge = WindowBridge::<u32>::new(window(), origin.clone());
+        let other = WindowBridge::<u32>::with_name(window(), origin, "other");
+        let foreign = WindowBridge::<u32>::new(window(), "https://foreign.example");
+
+        let (log, other_log, foreign_log) = Default::default();
+        let _subscriptions = (
+            bridge.subscribe(received(&log)),
+            other.subscribe(received(&other_log)),
+            foreign.subscribe(received(&foreign_log)),
+        );
+        bridge.send(&1).unwrap();
+        sleep(50).await;
+
+        assert_eq!(*log.borrow(), vec![1]);
+        assert!(other_log.borrow().is_empty());
+        assert!(foreign_log.borrow().is_empty());
+    }
+}
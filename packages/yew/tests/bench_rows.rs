@@ -0,0 +1,288 @@
+//! Benchmarks of the operations of the
+//! [js-framework-benchmark](https://github.com/krausest/js-framework-benchmark) on a keyed table
+//! of rows, run against the current tree.
+//!
+//! Run them with `cargo make bench` in `packages/yew`. With the `stats` feature also enabled, the
+//! DOM operations of every benchmark are logged as well.
+#![cfg(feature = "wasm_bench")]
+
+use wasm_bindgen_test::*;
+use yew::html::Scope;
+use yew::{html, AppHandle, Component, Context, Html};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+/// Number of times every benchmark is run. The median is reported.
+const SAMPLES: usize = 10;
+
+const ADJECTIVES: &[&str] = &[
+    "pretty",
+    "large",
+    "big",
+    "small",
+    "tall",
+    "short",
+    "long",
+    "handsome",
+    "plain",
+    "quaint",
+    "clean",
+    "elegant",
+    "easy",
+    "angry",
+    "crazy",
+    "helpful",
+    "mushy",
+    "odd",
+    "unsightly",
+    "adorable",
+    "important",
+    "inexpensive",
+    "cheap",
+    "expensive",
+    "fancy",
+];
+const COLOURS: &[&str] = &[
+    "red", "yellow", "blue", "green", "pink", "brown", "purple", "brown", "white", "black",
+    "orange",
+];
+const NOUNS: &[&str] = &[
+    "table", "chair", "house", "bbq", "desk", "car", "pony", "cookie", "sandwich", "burger",
+    "pizza", "mouse", "keyboard",
+];
+
+struct Row {
+    id: usize,
+    label: String,
+}
+
+enum Msg {
+    Create(usize),
+    Append(usize),
+    UpdateEvery10th,
+    Select(usize),
+    Swap,
+    Remove(usize),
+    Clear,
+}
+
+/// The table of the benchmark, following the keyed reference implementations
+struct Bench {
+    rows: Vec<Row>,
+    next_id: usize,
+    selected: Option<usize>,
+    /// State of a linear congruential generator, so every run builds the same labels
+    seed: u32,
+}
+
+impl Bench {
+    fn random(&mut self, max: usize) -> usize {
+        self.seed = self.seed.wrapping_mul(1_103_515_245).wrapping_add(12345);
+        (self.seed >> 16) as usize % max
+    }
+
+    fn build_rows(&mut self, count: usize) -> Vec<Row> {
+        (0..count)
+            .map(|_| {
+                let label = format!(
+                    "{} {} {}",
+                    ADJECTIVES[self.random(ADJECTIVES.len())],
+                    COLOURS[self.random(COLOURS.len())],
+                    NOUNS[self.random(NOUNS.len())],
+                );
+                self.next_id += 1;
+                Row {
+                    id: self.next_id,
+                    label,
+                }
+            })
+            .collect()
+    }
+}
+
+impl Component for Bench {
+    type Message = Msg;
+    type Properties = ();
+
+    fn create(_ctx: &Context<Self>) -> Self {
+        Self {
+            rows: Vec::new(),
+            next_id: 0,
+            selected: None,
+            seed: 42,
+        }
+    }
+
+    fn update(&mut self, _ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::Create(count) => {
+                self.rows = self.build_rows(count);
+                self.selected = None;
+            }
+            Msg::Append(count) => {
+                let rows = self.build_rows(count);
+                self.rows.extend(rows);
+            }
+            Msg::UpdateEvery10th => {
+                for row in self.rows.iter_mut().step_by(10) {
+                    row.label += " !!!";
+                }
+            }
+            Msg::Select(id) => self.selected = Some(id),
+            Msg::Swap => {
+                if self.rows.len() > 998 {
+                    self.rows.swap(1, 998);
+                }
+            }
+            Msg::Remove(id) => self.rows.retain(|row| row.id != id),
+            Msg::Clear => self.rows.clear(),
+        }
+        true
+    }
+
+    fn view(&self, _ctx: &Context<Self>) -> Html {
+        html! {
+            <table class="table table-hover table-striped test-data">
+                <tbody>
+                    { for self.rows.iter().map(|row| html! {
+                        <tr key={row.id} class={if self.selected == Some(row.id) { "danger" } else { "" }}>
+                            <td class="col-md-1">{ row.id }</td>
+                            <td class="col-md-4"><a>{ &row.label }</a></td>
+                            <td class="col-md-1">
+                                <a><span class="glyphicon glyphicon-remove" aria-hidden="true" /></a>
+                            </td>
+                            <td class="col-md-6" />
+                        </tr>
+                    }) }
+                </tbody>
+            </table>
+        }
+    }
+}
+
+fn now() -> f64 {
+    web_sys::window()
+        .and_then(|w| w.performance())
+        .expect("no performance API")
+        .now()
+}
+
+/// Runs `setup` and then measures `run` on a freshly mounted table for every sample and logs the
+/// median and the minimum
+fn bench(name: &str, setup: impl Fn(&Scope<Bench>), run: impl Fn(&Scope<Bench>)) {
+    let mut times = Vec::with_capacity(SAMPLES);
+    for _ in 0..SAMPLES {
+        let element = yew::utils::document().create_element("div").unwrap();
+        yew::utils::document()
+            .body()
+            .unwrap()
+            .append_child(&element)
+            .unwrap();
+        let app: AppHandle<Bench> = yew::start_app_in_element(element.clone());
+        setup(&app);
+
+        #[cfg(feature = "stats")]
+        yew::stats::reset();
+        let start = now();
+        run(&app);
+        times.push(now() - start);
+
+        app.destroy();
+        element.remove();
+    }
+
+    times.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let report = format!(
+        "{:<24}: {:>8.2} ms median, {:>8.2} ms min",
+        name,
+        times[times.len() / 2],
+        times[0]
+    );
+    #[cfg(feature = "stats")]
+    let report = {
+        let ops = yew::stats::total();
+        format!("{}, {} DOM operations: {:?}", report, ops.total(), ops)
+    };
+    console_log!("{}", report);
+}
+
+#[wasm_bindgen_test]
+fn bench_create_1k_rows() {
+    bench(
+        "create 1k rows",
+        |_| (),
+        |app| app.send_message(Msg::Create(1_000)),
+    );
+}
+
+#[wasm_bindgen_test]
+fn bench_create_10k_rows() {
+    bench(
+        "create 10k rows",
+        |_| (),
+        |app| app.send_message(Msg::Create(10_000)),
+    );
+}
+
+#[wasm_bindgen_test]
+fn bench_replace_1k_rows() {
+    bench(
+        "replace 1k rows",
+        |app| app.send_message(Msg::Create(1_000)),
+        |app| app.send_message(Msg::Create(1_000)),
+    );
+}
+
+#[wasm_bindgen_test]
+fn bench_partial_update() {
+    bench(
+        "update every 10th row",
+        |app| app.send_message(Msg::Create(10_000)),
+        |app| app.send_message(Msg::UpdateEvery10th),
+    );
+}
+
+#[wasm_bindgen_test]
+fn bench_select_row() {
+    bench(
+        "select row",
+        |app| app.send_message(Msg::Create(1_000)),
+        |app| app.send_message(Msg::Select(500)),
+    );
+}
+
+#[wasm_bindgen_test]
+fn bench_swap_rows() {
+    bench(
+        "swap rows",
+        |app| app.send_message(Msg::Create(1_000)),
+        |app| app.send_message(Msg::Swap),
+    );
+}
+
+#[wasm_bindgen_test]
+fn bench_remove_row() {
+    bench(
+        "remove row",
+        |app| app.send_message(Msg::Create(1_000)),
+        |app| app.send_message(Msg::Remove(500)),
+    );
+}
+
+#[wasm_bindgen_test]
+fn bench_append_1k_rows() {
+    bench(
+        "append 1k rows",
+        |app| app.send_message(Msg::Create(10_000)),
+        |app| app.send_message(Msg::Append(1_000)),
+    );
+}
+
+#[wasm_bindgen_test]
+fn bench_clear_rows() {
+    bench(
+        "clear 10k rows",
+        |app| app.send_message(Msg::Create(10_000)),
+        |app| app.send_message(Msg::Clear),
+    );
+}
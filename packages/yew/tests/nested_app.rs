@@ -0,0 +1,112 @@
+mod common;
+
+use common::obtain_result_by_id;
+use wasm_bindgen_test::*;
+use yew::html::{ListenerConfig, ListenerMode};
+use yew::{html, AppBuilder, AppHandle, Component, Context, Html, NodeRef, Properties};
+
+wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+#[derive(Clone, PartialEq, Properties)]
+struct ChildProps {
+    label: String,
+}
+
+struct Child;
+
+impl Component for Child {
+    type Message = ();
+    type Properties = ChildProps;
+
+    fn create(_ctx: &Context<Self>) -> Self {
+        Self
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        html! { <span>{ &ctx.props().label }</span> }
+    }
+}
+
+enum HostMsg {
+    Increment,
+}
+
+/// Renders an empty element and mounts `Child` in it as a separate app
+struct Host {
+    count: u32,
+    slot: NodeRef,
+    child: Option<AppHandle<Child>>,
+}
+
+impl Component for Host {
+    type Message = HostMsg;
+    type Properties = ();
+
+    fn create(_ctx: &Context<Self>) -> Self {
+        Self {
+            count: 0,
+            slot: NodeRef::default(),
+            child: None,
+        }
+    }
+
+    fn update(&mut self, _ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            HostMsg::Increment => self.count += 1,
+        }
+        true
+    }
+
+    fn view(&self, _ctx: &Context<Self>) -> Html {
+        html! {
+            <div id="nested-host" data-count={self.count.to_string()}>
+                <p>{ self.count }</p>
+                <div ref={self.slot.clone()} />
+            </div>
+        }
+    }
+
+    fn rendered(&mut self, _ctx: &Context<Self>, first_render: bool) {
+        if first_render {
+            let props = ChildProps {
+                label: "embedded".into(),
+            };
+            self.child = Some(
+                AppBuilder::new()
+                    .listeners(ListenerConfig::default().mode(ListenerMode::Delegated))
+                    .mount(self.slot.cast().unwrap(), props),
+            );
+        }
+    }
+
+    fn destroy(&mut self, _ctx: &Context<Self>) {
+        if let Some(child) = self.child.take() {
+            child.destroy();
+        }
+    }
+}
+
+#[wasm_bindgen_test]
+fn nested_app_survives_host_renders_and_is_torn_down_with_it() {
+    let element = yew::utils::document().create_element("div").unwrap();
+    yew::utils::document()
+        .body()
+        .unwrap()
+        .append_child(&element)
+        .unwrap();
+    let host: AppHandle<Host> = yew::start_app_in_element(element.clone());
+    assert_eq!(
+        obtain_result_by_id("nested-host"),
+        "<p>0</p><div><span>embedded</span></div>"
+    );
+
+    host.send_message(HostMsg::Increment);
+    assert_eq!(
+        obtain_result_by_id("nested-host"),
+        "<p>1</p><div><span>embedded</span></div>"
+    );
+
+    host.destroy();
+    assert_eq!(element.inner_html(), "");
+    element.remove();
+}
@@ -3,10 +3,11 @@ mod common;
 use common::obtain_result_by_id;
 use std::rc::Rc;
 use wasm_bindgen_test::*;
+use yew::context::{IntoService, ServiceProvider};
 use yew::functional::{
-    use_context, use_effect, use_ref, use_state, FunctionComponent, FunctionProvider,
+    use_context, use_effect, use_ref, use_service, use_state, FunctionComponent, FunctionProvider,
 };
-use yew::{html, Children, ContextProvider, Html, Properties};
+use yew::{html, Children, Component, Context, ContextProvider, Html, Properties};
 
 wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
 
@@ -294,3 +295,97 @@ fn use_context_update_works() {
         "current: hello world!, total: 4"
     );
 }
+
+#[wasm_bindgen_test]
+fn use_service_finds_provided_implementation() {
+    trait Greeter {
+        fn greet(&self) -> String;
+    }
+
+    #[derive(yew::context::Service)]
+    #[service(dyn Greeter)]
+    struct FakeGreeter;
+
+    impl Greeter for FakeGreeter {
+        fn greet(&self) -> String {
+            "hello from the fake".into()
+        }
+    }
+
+    struct InnerFunction;
+    impl FunctionProvider for InnerFunction {
+        type TProps = ();
+
+        fn run(_props: &Self::TProps) -> Html {
+            let greeter = use_service::<dyn Greeter>().expect("no greeter provided");
+            return html! {
+                <div id="result">{ greeter.greet() }</div>
+            };
+        }
+    }
+    type Inner = FunctionComponent<InnerFunction>;
+
+    struct OuterFunction;
+    impl FunctionProvider for OuterFunction {
+        type TProps = ();
+
+        fn run(_props: &Self::TProps) -> Html {
+            type GreeterProvider = ServiceProvider<dyn Greeter>;
+            return html! {
+                <GreeterProvider context={FakeGreeter.into_service()}>
+                    <div><Inner /></div>
+                </GreeterProvider>
+            };
+        }
+    }
+    type Outer = FunctionComponent<OuterFunction>;
+
+    yew::start_app_in_element::<Outer>(yew::utils::document().get_element_by_id("output").unwrap());
+    let result: String = obtain_result_by_id("result");
+    assert_eq!("hello from the fake", result);
+}
+
+#[wasm_bindgen_test]
+fn context_value_reads_without_subscribing() {
+    #[derive(Clone, Debug, PartialEq)]
+    struct Theme(&'static str);
+
+    struct Inner {
+        theme: Option<Theme>,
+    }
+
+    impl Component for Inner {
+        type Message = ();
+        type Properties = ();
+
+        fn create(ctx: &Context<Self>) -> Self {
+            Self {
+                theme: ctx.link().context_value::<Theme>(),
+            }
+        }
+
+        fn view(&self, _ctx: &Context<Self>) -> Html {
+            let theme = self.theme.as_ref().map_or("none", |theme| theme.0);
+            html! { <div id="result">{ theme }</div> }
+        }
+    }
+
+    struct OuterFunction;
+    impl FunctionProvider for OuterFunction {
+        type TProps = ();
+
+        fn run(_props: &Self::TProps) -> Html {
+            type ThemeProvider = ContextProvider<Theme>;
+            return html! {
+                <ThemeProvider context={Theme("dark")}>
+                    <Inner />
+                </ThemeProvider>
+            };
+        }
+    }
+    type Outer = FunctionComponent<OuterFunction>;
+
+    yew::start_app_in_element::<Outer>(yew::utils::document().get_element_by_id("output").unwrap());
+    let result: String = obtain_result_by_id("result");
+    assert_eq!("dark", result);
+}
@@ -5,7 +5,8 @@ use std::ops::{Deref, DerefMut};
 use std::rc::Rc;
 use wasm_bindgen_test::*;
 use yew::functional::{
-    use_effect_with_deps, use_ref, use_state, FunctionComponent, FunctionProvider,
+    use_effect, use_effect_with_deps, use_layout_effect, use_ref, use_state, FunctionComponent,
+    FunctionProvider,
 };
 use yew::{html, Html, Properties};
 
@@ -201,3 +202,49 @@ fn use_effect_refires_on_dependency_change() {
 
     assert_eq!(result.as_str(), "11");
 }
+
+#[wasm_bindgen_test]
+fn use_layout_effect_runs_before_effects() {
+    struct UseLayoutEffectFunction {}
+    impl FunctionProvider for UseLayoutEffectFunction {
+        type TProps = ();
+
+        fn run(_: &Self::TProps) -> Html {
+            let order = use_state(String::new);
+            let log = use_ref(Vec::new);
+
+            {
+                let log = Rc::clone(&log);
+                let order = order.clone();
+                use_effect(move || {
+                    log.borrow_mut().push("effect");
+                    if order.is_empty() {
+                        order.set(log.borrow().join(","));
+                    }
+                    || ()
+                });
+            }
+            {
+                let log = Rc::clone(&log);
+                use_layout_effect(move || {
+                    log.borrow_mut().push("layout");
+                    || ()
+                });
+            }
+
+            return html! {
+                <div>
+                    { "The test result is" }
+                    <div id="result">{ (*order).clone() }</div>
+                    { "\n" }
+                </div>
+            };
+        }
+    }
+    type UseLayoutEffectComponent = FunctionComponent<UseLayoutEffectFunction>;
+    yew::start_app_in_element::<UseLayoutEffectComponent>(
+        yew::utils::document().get_element_by_id("output").unwrap(),
+    );
+    let result = obtain_result();
+    assert_eq!(result.as_str(), "layout,effect");
+}
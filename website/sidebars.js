@@ -82,6 +82,7 @@ module.exports = {
             items: [
                 "advanced-topics/how-it-works",
                 "advanced-topics/optimizations",
+                "advanced-topics/embedding-apps",
             ]
         },
         {